@@ -1,16 +1,25 @@
 #[cfg(test)]
 mod tests;
 
-use std::{env, fmt, net::IpAddr, path::PathBuf};
+use std::{
+    env, fmt,
+    net::IpAddr,
+    path::PathBuf,
+    sync::{Arc, PoisonError, RwLock},
+};
 
 use config::{Config, File, FileFormat};
+use ipnet::IpNet;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 
 /// Default path to the bindizr configuration file.
 pub(crate) const BINDIZR_CONF_PATH: &str = "/etc/bindizr/bindizr.conf.toml";
 
-static BINDIZR_CONFIG: OnceCell<BindizrConfig> = OnceCell::new();
+static BINDIZR_CONFIG: OnceCell<RwLock<Arc<BindizrConfig>>> = OnceCell::new();
+
+/// The file [`initialize`] loaded, which [`reload`] re-reads.
+static BINDIZR_CONFIG_PATH: OnceCell<String> = OnceCell::new();
 
 /// Top-level bindizr configuration.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -18,7 +27,11 @@ pub struct BindizrConfig {
     pub api: ApiConfig,
     pub database: DatabaseConfig,
     pub dns: DnsConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
 }
 
 /// HTTP API server settings.
@@ -45,7 +58,7 @@ fn default_metrics_enabled() -> bool {
 }
 
 /// Database backend selection and per-backend connection settings.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct DatabaseConfig {
     #[serde(rename = "type")]
     pub database_type: DatabaseType,
@@ -55,10 +68,18 @@ pub struct DatabaseConfig {
     pub sqlite: SqliteConfig,
     #[serde(default)]
     pub postgresql: PostgresqlConfig,
+    /// Apply pending schema migrations at startup. When off, startup refuses
+    /// an outdated schema until `bindizr db migrate` has run.
+    #[serde(default = "default_auto_migrate")]
+    pub auto_migrate: bool,
+}
+
+fn default_auto_migrate() -> bool {
+    true
 }
 
 /// Supported database backends.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DatabaseType {
     Mysql,
@@ -91,19 +112,19 @@ impl std::str::FromStr for DatabaseType {
 }
 
 /// MySQL connection settings.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct MysqlConfig {
     pub server_url: String,
 }
 
 /// SQLite connection settings.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct SqliteConfig {
     pub file_path: String,
 }
 
 /// PostgreSQL connection settings.
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct PostgresqlConfig {
     pub server_url: String,
 }
@@ -131,10 +152,130 @@ pub struct DnsConfig {
     pub notify_retries: u32,
     #[serde(default = "default_notify_timeout_secs")]
     pub notify_timeout_secs: u64,
+    /// How often the background monitor probes every secondary for every
+    /// zone's SOA serial; 0 turns the monitor off.
+    #[serde(default = "default_sync_monitor_interval_secs")]
+    pub sync_monitor_interval_secs: u64,
+    /// Re-NOTIFY a secondary once it has lagged this long, and again each
+    /// time as long passes while it still lags; 0 never re-NOTIFYs.
+    #[serde(default = "default_sync_renotify_after_secs")]
+    pub sync_renotify_after_secs: u64,
     /// Accept unsigned nsupdate requests. Not recommended in production;
     /// signed requests are always verified.
     #[serde(default)]
     pub nsupdate_allow_unsigned: bool,
+    /// Answer ordinary queries (A, MX, TXT, ...) from zone data as an
+    /// authoritative server. Off, only SOA, XFR, and UPDATE are served.
+    #[serde(default)]
+    pub answer_queries: bool,
+    /// The most a UDP answer may carry for a query with EDNS, whatever
+    /// larger size the requester advertises; also the size advertised back.
+    #[serde(default = "default_edns_udp_payload_size")]
+    pub edns_udp_payload_size: u16,
+    /// Serve zone transfers and SOA queries over TLS (XoT, RFC 9103) on
+    /// `tls_listen_port`, next to the plain TCP/UDP listener.
+    #[serde(default)]
+    pub tls_enabled: bool,
+    #[serde(default = "default_tls_listen_port")]
+    pub tls_listen_port: u16,
+    /// PEM certificate chain and private key the TLS listener presents.
+    #[serde(default)]
+    pub tls_cert_path: String,
+    #[serde(default)]
+    pub tls_key_path: String,
+    /// PEM CA bundle for mutual TLS: when set, clients must present a
+    /// certificate it issued, and the certificate's names can admit them to
+    /// transfers.
+    #[serde(default)]
+    pub tls_client_ca_path: String,
+}
+
+/// One `secondary_addrs` entry: `host[:port]` or a CIDR prefix, optionally
+/// followed by `key <name>` naming the TSIG key its transfers and NOTIFYs are
+/// signed with. A leading `!` makes it a deny entry, which refuses matching
+/// clients whatever else they match and takes no key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecondaryEntry {
+    /// Without the `!` of a deny entry.
+    pub address: String,
+    /// Normalized like a stored key name: lowercase, no trailing dot.
+    pub key: Option<String>,
+    pub deny: bool,
+}
+
+impl SecondaryEntry {
+    pub fn parse(entry: &str) -> Result<Self, String> {
+        let mut tokens = entry.split_whitespace();
+        let address = tokens
+            .next()
+            .ok_or_else(|| "empty secondary entry".to_string())?;
+        let (address, deny) = match address.strip_prefix('!') {
+            Some(address) => (address.to_string(), true),
+            None => (address.to_string(), false),
+        };
+        if address.is_empty() {
+            return Err(format!(
+                "invalid secondary entry '{}': missing address",
+                entry.trim()
+            ));
+        }
+        if address.contains('/') {
+            address.parse::<IpNet>().map_err(|_| {
+                format!(
+                    "invalid secondary entry '{}': '{}' is not a CIDR prefix",
+                    entry.trim(),
+                    address
+                )
+            })?;
+        }
+
+        let key = match (tokens.next(), tokens.next(), tokens.next()) {
+            (None, _, _) => None,
+            (Some(keyword), Some(name), None) if keyword.eq_ignore_ascii_case("key") => {
+                Some(name.trim_end_matches('.').to_ascii_lowercase())
+            }
+            _ => {
+                return Err(format!(
+                    "invalid secondary entry '{}': expected \"host[:port]\" or \"host[:port] key <name>\"",
+                    entry.trim()
+                ));
+            }
+        };
+        if deny && key.is_some() {
+            return Err(format!(
+                "invalid secondary entry '{}': a deny entry cannot carry a key",
+                entry.trim()
+            ));
+        }
+
+        Ok(Self { address, key, deny })
+    }
+
+    /// Whether the entry names one server NOTIFY can be sent to and its
+    /// serial probed, rather than a deny entry or a CIDR prefix.
+    pub fn is_server(&self) -> bool {
+        !self.deny && !self.address.contains('/')
+    }
+}
+
+impl DnsConfig {
+    /// The parsed `secondary_addrs` entries; malformed ones were rejected
+    /// when the config loaded, so any that remain here are skipped.
+    pub fn secondary_entries(&self) -> Vec<SecondaryEntry> {
+        self.secondary_addrs
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .filter_map(|entry| SecondaryEntry::parse(entry).ok())
+            .collect()
+    }
+
+    /// The entries that name a server, the NOTIFY and probe targets.
+    pub fn secondary_servers(&self) -> Vec<SecondaryEntry> {
+        self.secondary_entries()
+            .into_iter()
+            .filter(SecondaryEntry::is_server)
+            .collect()
+    }
 }
 
 fn default_notify_after_update() -> bool {
@@ -153,6 +294,16 @@ fn default_zone_cache() -> bool {
     true
 }
 
+/// The DNS Flag Day 2020 value: fits an IPv6 minimum MTU without
+/// fragmenting.
+fn default_edns_udp_payload_size() -> u16 {
+    1232
+}
+
+fn default_tls_listen_port() -> u16 {
+    853
+}
+
 /// When zone reload/NOTIFY runs relative to the write request.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -193,6 +344,117 @@ fn default_notify_timeout_secs() -> u64 {
     5
 }
 
+fn default_sync_monitor_interval_secs() -> u64 {
+    60
+}
+
+fn default_sync_renotify_after_secs() -> u64 {
+    300
+}
+
+/// Zone history retention: how many serials of snapshots and change
+/// journal each zone keeps. A serial is pruned only once it is outside every
+/// configured limit; with neither set, history is kept forever.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct HistoryConfig {
+    /// Keep each zone's newest N serials; 0 sets no count limit.
+    #[serde(default)]
+    pub keep_serials: u32,
+    /// Keep serials recorded within the last N days; 0 sets no age limit.
+    #[serde(default)]
+    pub keep_days: u32,
+    /// How often the background compaction runs.
+    #[serde(default = "default_prune_interval_secs")]
+    pub prune_interval_secs: u64,
+    /// Days audit log entries are kept; 0 keeps them forever. Independent of
+    /// the limits above, which never remove audit entries.
+    #[serde(default)]
+    pub audit_keep_days: u32,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            keep_serials: 0,
+            keep_days: 0,
+            prune_interval_secs: default_prune_interval_secs(),
+            audit_keep_days: 0,
+        }
+    }
+}
+
+impl HistoryConfig {
+    /// Whether any retention limit is set, i.e. whether compaction runs.
+    pub fn retention_enabled(&self) -> bool {
+        self.keep_serials > 0 || self.keep_days > 0
+    }
+}
+
+fn default_prune_interval_secs() -> u64 {
+    3600
+}
+
+/// Outbound webhook delivery: how often a failed delivery is retried and
+/// how long finished deliveries stay listed.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct WebhookConfig {
+    /// Attempts before a delivery is marked failed.
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: u32,
+    /// Wait before the first retry; each later retry doubles it.
+    #[serde(default = "default_webhook_initial_backoff_secs")]
+    pub initial_backoff_secs: u64,
+    /// Upper bound on the wait between retries.
+    #[serde(default = "default_webhook_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+    /// Per-request timeout for a delivery attempt.
+    #[serde(default = "default_webhook_timeout_secs")]
+    pub timeout_secs: u64,
+    /// How often the queue is polled for due retries.
+    #[serde(default = "default_webhook_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// Days delivered and failed deliveries are kept; 0 keeps them forever.
+    #[serde(default = "default_webhook_retention_days")]
+    pub retention_days: u32,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_webhook_max_attempts(),
+            initial_backoff_secs: default_webhook_initial_backoff_secs(),
+            max_backoff_secs: default_webhook_max_backoff_secs(),
+            timeout_secs: default_webhook_timeout_secs(),
+            poll_interval_secs: default_webhook_poll_interval_secs(),
+            retention_days: default_webhook_retention_days(),
+        }
+    }
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    10
+}
+
+fn default_webhook_initial_backoff_secs() -> u64 {
+    10
+}
+
+fn default_webhook_max_backoff_secs() -> u64 {
+    3600
+}
+
+fn default_webhook_timeout_secs() -> u64 {
+    10
+}
+
+fn default_webhook_poll_interval_secs() -> u64 {
+    5
+}
+
+fn default_webhook_retention_days() -> u32 {
+    7
+}
+
 /// Logging settings.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoggingConfig {
@@ -246,11 +508,88 @@ pub fn initialize(conf_file_path: Option<&str>) -> Result<(), String> {
     println!("Initializing configuration from file: {}", conf_file_path);
 
     let bindizr_config = load_config_file(&conf_file_path)?;
-    BINDIZR_CONFIG.get_or_init(|| bindizr_config);
+    BINDIZR_CONFIG.get_or_init(|| RwLock::new(Arc::new(bindizr_config)));
+    BINDIZR_CONFIG_PATH.get_or_init(|| conf_file_path);
 
     Ok(())
 }
 
+/// Result of a successful [`reload`].
+#[derive(Debug)]
+pub struct ReloadOutcome {
+    pub config: Arc<BindizrConfig>,
+    /// Keys that changed in the file but are only read at startup; they keep
+    /// their running values until the next restart.
+    pub restart_required: Vec<&'static str>,
+}
+
+/// Re-read and validate the file [`initialize`] loaded and swap it in as the
+/// global config. An invalid file leaves the running config untouched.
+/// Readers holding the previous config (an in-flight transfer, say) keep it
+/// until they finish.
+pub fn reload() -> Result<ReloadOutcome, String> {
+    let conf_file_path = BINDIZR_CONFIG_PATH
+        .get()
+        .ok_or_else(|| "Configuration not initialized".to_string())?;
+
+    let mut bindizr_config = load_config_file(conf_file_path)?;
+    let restart_required = keep_startup_settings(&get_bindizr_config(), &mut bindizr_config);
+
+    let bindizr_config = Arc::new(bindizr_config);
+    let lock = BINDIZR_CONFIG
+        .get()
+        .ok_or_else(|| "Configuration not initialized".to_string())?;
+    *lock.write().unwrap_or_else(PoisonError::into_inner) = bindizr_config.clone();
+
+    Ok(ReloadOutcome {
+        config: bindizr_config,
+        restart_required,
+    })
+}
+
+/// Carry the settings that listeners, the database pool, and the API router
+/// consumed at startup over from `running` into `reloaded`, so the reloaded
+/// config still describes the process. Returns the keys that differed.
+fn keep_startup_settings(
+    running: &BindizrConfig,
+    reloaded: &mut BindizrConfig,
+) -> Vec<&'static str> {
+    let mut restart_required = Vec::new();
+
+    macro_rules! keep {
+        ($($section:ident . $field:ident),* $(,)?) => {
+            $(
+                if reloaded.$section.$field != running.$section.$field {
+                    restart_required.push(concat!(stringify!($section), ".", stringify!($field)));
+                    reloaded.$section.$field = running.$section.$field.clone();
+                }
+            )*
+        };
+    }
+
+    keep!(
+        api.listen_addr,
+        api.listen_port,
+        api.require_authentication,
+        api.metrics_enabled,
+        api.external_dns_enabled,
+        api.openapi_enabled,
+        dns.listen_addr,
+        dns.listen_port,
+        dns.tls_enabled,
+        dns.tls_listen_port,
+        dns.tls_cert_path,
+        dns.tls_key_path,
+        dns.tls_client_ca_path,
+    );
+    if reloaded.database != running.database {
+        restart_required.push("database");
+        reloaded.database = running.database.clone();
+    }
+
+    restart_required
+}
+
 /// Resolve the config file path: explicit argument, then `BINDIZR_CONFIG_PATH`,
 /// then the default path.
 pub fn resolve_config_path(conf_file_path: Option<&str>) -> String {
@@ -305,6 +644,8 @@ fn parse_bindizr_config_with_env(
     apply_env_overrides_from(&mut bindizr_config, get_env)?;
     validate_database_config(&bindizr_config.database)?;
     validate_dns_config(&bindizr_config.dns)?;
+    validate_history_config(&bindizr_config.history)?;
+    validate_webhook_config(&bindizr_config.webhook)?;
 
     Ok(bindizr_config)
 }
@@ -336,6 +677,9 @@ fn apply_env_overrides_from(
     if let Some(value) = get_env("BINDIZR_DATABASE_TYPE") {
         config.database.database_type = parse_env_value("BINDIZR_DATABASE_TYPE", &value)?;
     }
+    if let Some(value) = get_env("BINDIZR_DATABASE_AUTO_MIGRATE") {
+        config.database.auto_migrate = parse_env_value("BINDIZR_DATABASE_AUTO_MIGRATE", &value)?;
+    }
     if let Some(value) = get_env("BINDIZR_MYSQL_SERVER_URL") {
         config.database.mysql.server_url = value;
     }
@@ -365,6 +709,28 @@ fn apply_env_overrides_from(
         config.dns.nsupdate_allow_unsigned =
             parse_env_value("BINDIZR_NSUPDATE_ALLOW_UNSIGNED", &value)?;
     }
+    if let Some(value) = get_env("BINDIZR_ANSWER_QUERIES") {
+        config.dns.answer_queries = parse_env_value("BINDIZR_ANSWER_QUERIES", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_EDNS_UDP_PAYLOAD_SIZE") {
+        config.dns.edns_udp_payload_size =
+            parse_env_value("BINDIZR_EDNS_UDP_PAYLOAD_SIZE", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_TLS_ENABLED") {
+        config.dns.tls_enabled = parse_env_value("BINDIZR_TLS_ENABLED", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_TLS_PORT") {
+        config.dns.tls_listen_port = parse_env_value("BINDIZR_TLS_PORT", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_TLS_CERT_PATH") {
+        config.dns.tls_cert_path = value;
+    }
+    if let Some(value) = get_env("BINDIZR_TLS_KEY_PATH") {
+        config.dns.tls_key_path = value;
+    }
+    if let Some(value) = get_env("BINDIZR_TLS_CLIENT_CA_PATH") {
+        config.dns.tls_client_ca_path = value;
+    }
     if let Some(value) = get_env("BINDIZR_NOTIFY_AFTER_UPDATE") {
         config.dns.notify_after_update = parse_env_value("BINDIZR_NOTIFY_AFTER_UPDATE", &value)?;
     }
@@ -386,6 +752,49 @@ fn apply_env_overrides_from(
     if let Some(value) = get_env("BINDIZR_NOTIFY_TIMEOUT_SECS") {
         config.dns.notify_timeout_secs = parse_env_value("BINDIZR_NOTIFY_TIMEOUT_SECS", &value)?;
     }
+    if let Some(value) = get_env("BINDIZR_SYNC_MONITOR_INTERVAL_SECS") {
+        config.dns.sync_monitor_interval_secs =
+            parse_env_value("BINDIZR_SYNC_MONITOR_INTERVAL_SECS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_SYNC_RENOTIFY_AFTER_SECS") {
+        config.dns.sync_renotify_after_secs =
+            parse_env_value("BINDIZR_SYNC_RENOTIFY_AFTER_SECS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_HISTORY_KEEP_SERIALS") {
+        config.history.keep_serials = parse_env_value("BINDIZR_HISTORY_KEEP_SERIALS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_HISTORY_KEEP_DAYS") {
+        config.history.keep_days = parse_env_value("BINDIZR_HISTORY_KEEP_DAYS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_HISTORY_PRUNE_INTERVAL_SECS") {
+        config.history.prune_interval_secs =
+            parse_env_value("BINDIZR_HISTORY_PRUNE_INTERVAL_SECS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_HISTORY_AUDIT_KEEP_DAYS") {
+        config.history.audit_keep_days =
+            parse_env_value("BINDIZR_HISTORY_AUDIT_KEEP_DAYS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_WEBHOOK_MAX_ATTEMPTS") {
+        config.webhook.max_attempts = parse_env_value("BINDIZR_WEBHOOK_MAX_ATTEMPTS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_WEBHOOK_INITIAL_BACKOFF_SECS") {
+        config.webhook.initial_backoff_secs =
+            parse_env_value("BINDIZR_WEBHOOK_INITIAL_BACKOFF_SECS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_WEBHOOK_MAX_BACKOFF_SECS") {
+        config.webhook.max_backoff_secs =
+            parse_env_value("BINDIZR_WEBHOOK_MAX_BACKOFF_SECS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_WEBHOOK_TIMEOUT_SECS") {
+        config.webhook.timeout_secs = parse_env_value("BINDIZR_WEBHOOK_TIMEOUT_SECS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_WEBHOOK_POLL_INTERVAL_SECS") {
+        config.webhook.poll_interval_secs =
+            parse_env_value("BINDIZR_WEBHOOK_POLL_INTERVAL_SECS", &value)?;
+    }
+    if let Some(value) = get_env("BINDIZR_WEBHOOK_RETENTION_DAYS") {
+        config.webhook.retention_days = parse_env_value("BINDIZR_WEBHOOK_RETENTION_DAYS", &value)?;
+    }
     if let Some(value) = get_env("BINDIZR_LOG_LEVEL") {
         config.logging.log_level = parse_env_value("BINDIZR_LOG_LEVEL", &value)?;
     }
@@ -420,7 +829,9 @@ fn validate_database_config(config: &DatabaseConfig) -> Result<(), String> {
 }
 
 /// Reject separators-only `secondary_addrs` (e.g. ","), which would otherwise
-/// read as "no secondaries configured".
+/// read as "no secondaries configured", entries with a malformed `key`
+/// clause, an EDNS payload size outside 512..=4096, and a TLS listener
+/// without its certificate.
 fn validate_dns_config(config: &DnsConfig) -> Result<(), String> {
     let raw = &config.secondary_addrs;
     if !raw.trim().is_empty() && raw.split(',').all(|entry| entry.trim().is_empty()) {
@@ -429,10 +840,74 @@ fn validate_dns_config(config: &DnsConfig) -> Result<(), String> {
                 .to_string(),
         );
     }
+    for entry in raw.split(',').filter(|entry| !entry.trim().is_empty()) {
+        SecondaryEntry::parse(entry).map_err(|e| format!("dns.secondary_addrs: {}", e))?;
+    }
+    if !(MIN_EDNS_UDP_PAYLOAD_SIZE..=MAX_EDNS_UDP_PAYLOAD_SIZE)
+        .contains(&config.edns_udp_payload_size)
+    {
+        return Err(format!(
+            "dns.edns_udp_payload_size must be between {} and {}",
+            MIN_EDNS_UDP_PAYLOAD_SIZE, MAX_EDNS_UDP_PAYLOAD_SIZE
+        ));
+    }
+    if config.tls_enabled
+        && (config.tls_cert_path.trim().is_empty() || config.tls_key_path.trim().is_empty())
+    {
+        return Err(
+            "dns.tls_cert_path and dns.tls_key_path must be set when dns.tls_enabled is true"
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// A UDP reply may always be 512 bytes (RFC 1035, Section 4.2.1); more than
+/// 4096 invites fragmentation (RFC 6891, Section 6.2.5).
+const MIN_EDNS_UDP_PAYLOAD_SIZE: u16 = 512;
+const MAX_EDNS_UDP_PAYLOAD_SIZE: u16 = 4096;
+
+fn validate_history_config(config: &HistoryConfig) -> Result<(), String> {
+    if config.prune_interval_secs == 0 {
+        return Err("history.prune_interval_secs must be greater than 0".to_string());
+    }
+    Ok(())
+}
+
+/// One week: past that a receiver is gone, not briefly down.
+const MAX_WEBHOOK_BACKOFF_SECS: u64 = 7 * 24 * 3600;
+
+fn validate_webhook_config(config: &WebhookConfig) -> Result<(), String> {
+    if config.max_attempts == 0 {
+        return Err("webhook.max_attempts must be greater than 0".to_string());
+    }
+    if config.timeout_secs == 0 {
+        return Err("webhook.timeout_secs must be greater than 0".to_string());
+    }
+    if config.poll_interval_secs == 0 {
+        return Err("webhook.poll_interval_secs must be greater than 0".to_string());
+    }
+    if config.max_backoff_secs > MAX_WEBHOOK_BACKOFF_SECS {
+        return Err(format!(
+            "webhook.max_backoff_secs must be at most {}",
+            MAX_WEBHOOK_BACKOFF_SECS
+        ));
+    }
+    if config.initial_backoff_secs > config.max_backoff_secs {
+        return Err(
+            "webhook.initial_backoff_secs must not exceed webhook.max_backoff_secs".to_string(),
+        );
+    }
     Ok(())
 }
 
-/// Return the global configuration; panics if [`initialize`] has not run.
-pub fn get_bindizr_config() -> &'static BindizrConfig {
-    BINDIZR_CONFIG.get().expect("Configuration not initialized")
+/// Return the current global configuration; panics if [`initialize`] has
+/// not run. A [`reload`] swaps it, so read it per use rather than keeping it.
+pub fn get_bindizr_config() -> Arc<BindizrConfig> {
+    BINDIZR_CONFIG
+        .get()
+        .expect("Configuration not initialized")
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
 }
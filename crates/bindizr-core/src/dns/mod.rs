@@ -1,9 +1,14 @@
 pub mod name;
 pub mod record;
+pub mod serial;
 
 /// Well-known name of the BIND catalog zone (RFC 9432).
 pub const CATALOG_ZONE_NAME: &str = "catalog.bind";
 
+/// Largest whole zone bindizr takes in at once, as an uploaded zone file or
+/// bulk request body, or as the text of a zone pulled over AXFR.
+pub const MAX_ZONE_CONTENT_BYTES: usize = 32 * 1024 * 1024;
+
 /// Whether `zone_name` is the virtual RFC 9432 catalog zone. Case-insensitive
 /// per RFC 4343; callers pass client-cased query names as-is.
 pub fn is_catalog_zone(zone_name: &str) -> bool {
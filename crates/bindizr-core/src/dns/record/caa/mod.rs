@@ -0,0 +1,155 @@
+//! CAA record values (RFC 8659): an issuer-critical flags octet, a property
+//! tag, and the property value.
+
+use super::{
+    TxtRecordValue,
+    value::{parse_character_string, split_leading_fields, validate_domain_record_value},
+};
+
+/// Longest tag RFC 8659, Section 4.1 allows.
+const MAX_CAA_TAG_LEN: usize = 15;
+
+/// A CAA value. Stored as `<flags> <tag> "<value>"` with a lowercase tag and
+/// the value quoted per RFC 1035, Section 5.1, so equal rdata has one spelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaaRecordValue {
+    flags: u8,
+    tag: String,
+    value: Vec<u8>,
+}
+
+impl CaaRecordValue {
+    /// Parse `<flags> <tag> <value>`; the value may be a quoted string or a
+    /// single bare token.
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        let Some((fields, property)) = split_leading_fields(value, 2) else {
+            return Err(format!(
+                "CAA record value must be '<flags> <tag> <value>': {value}"
+            ));
+        };
+        if property.is_empty() {
+            return Err(format!(
+                "CAA record value must be '<flags> <tag> <value>': {value}"
+            ));
+        }
+
+        let flags = fields[0]
+            .parse::<u8>()
+            .map_err(|_| format!("CAA flags must be between 0 and 255: {}", fields[0]))?;
+
+        Ok(Self {
+            flags,
+            tag: fields[1].to_ascii_lowercase(),
+            value: parse_character_string("CAA value", property)?,
+        })
+    }
+
+    /// Decode wire rdata: flags, a length-prefixed tag, then the value to the
+    /// end of the rdata.
+    pub fn from_rdata(rdata: &[u8]) -> Result<Self, String> {
+        let [flags, tag_len, rest @ ..] = rdata else {
+            return Err("CAA rdata is truncated".to_string());
+        };
+        let tag_len = usize::from(*tag_len);
+        if rest.len() < tag_len {
+            return Err("CAA rdata is truncated".to_string());
+        }
+        let tag = std::str::from_utf8(&rest[..tag_len])
+            .map_err(|_| "CAA tag must be ASCII".to_string())?;
+
+        let parsed = Self {
+            flags: *flags,
+            tag: tag.to_ascii_lowercase(),
+            value: rest[tag_len..].to_vec(),
+        };
+        parsed.validate()?;
+        Ok(parsed)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.tag.is_empty() || self.tag.len() > MAX_CAA_TAG_LEN {
+            return Err(format!(
+                "CAA tag must be 1 to {MAX_CAA_TAG_LEN} characters: {}",
+                self.tag
+            ));
+        }
+        if !self.tag.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(format!(
+                "CAA tag must contain only letters and digits: {}",
+                self.tag
+            ));
+        }
+        // 2 bytes for flags and the tag length octet, on top of the 16-bit rdata limit.
+        if 2 + self.tag.len() + self.value.len() > usize::from(u16::MAX) {
+            return Err("CAA value is too long".to_string());
+        }
+
+        match self.tag.as_str() {
+            "issue" | "issuewild" | "issuemail" => self.validate_issuer_value(),
+            "iodef" => self.validate_iodef_value(),
+            // Unknown properties are carried opaquely (RFC 8659, Section 4.1).
+            _ => Ok(()),
+        }
+    }
+
+    /// `issue`-style values are `[issuer-domain-name] [; key=value]*`; an
+    /// empty issuer forbids issuance (RFC 8659, Section 4.2).
+    fn validate_issuer_value(&self) -> Result<(), String> {
+        let value = std::str::from_utf8(&self.value)
+            .map_err(|_| format!("CAA {} value must be ASCII", self.tag))?;
+        let issuer = value.split(';').next().unwrap_or_default().trim();
+        if issuer.is_empty() {
+            return Ok(());
+        }
+        validate_domain_record_value(&format!("CAA {} issuer", self.tag), issuer)
+    }
+
+    /// `iodef` values are a `mailto:`, `http:` or `https:` URL (RFC 8659,
+    /// Section 4.4).
+    fn validate_iodef_value(&self) -> Result<(), String> {
+        let value = std::str::from_utf8(&self.value)
+            .map_err(|_| "CAA iodef value must be ASCII".to_string())?;
+        let lower = value.to_ascii_lowercase();
+        if ["mailto:", "http://", "https://"]
+            .iter()
+            .any(|scheme| lower.starts_with(scheme) && lower.len() > scheme.len())
+        {
+            return Ok(());
+        }
+        Err(format!(
+            "CAA iodef value must be a mailto:, http:// or https:// URL: {value}"
+        ))
+    }
+
+    /// Whether the issuer-critical flag (bit 0, value 128) is set.
+    pub fn is_critical(&self) -> bool {
+        self.flags & 0x80 != 0
+    }
+
+    /// The property tag, lowercased.
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn canonical(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.flags,
+            self.tag,
+            TxtRecordValue::to_quoted_charstr(&self.value)
+        )
+    }
+
+    /// The wire rdata.
+    pub fn to_rdata(&self) -> Vec<u8> {
+        let mut rdata = Vec::with_capacity(2 + self.tag.len() + self.value.len());
+        rdata.push(self.flags);
+        rdata.push(self.tag.len() as u8);
+        rdata.extend_from_slice(self.tag.as_bytes());
+        rdata.extend_from_slice(&self.value);
+        rdata
+    }
+}
+
+#[cfg(test)]
+mod tests;
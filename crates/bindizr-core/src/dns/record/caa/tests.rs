@@ -0,0 +1,75 @@
+use super::CaaRecordValue;
+
+#[test]
+fn parse_canonicalizes_tag_case_and_quoting() {
+    for value in [
+        "0 issue \"letsencrypt.org\"",
+        "0 ISSUE letsencrypt.org",
+        "0   Issue   \"letsencrypt.org\"  ",
+    ] {
+        let parsed = CaaRecordValue::parse(value).unwrap();
+        parsed.validate().unwrap();
+        assert_eq!(parsed.canonical(), "0 issue \"letsencrypt.org\"", "{value}");
+    }
+}
+
+#[test]
+fn parse_keeps_spaces_and_escapes_inside_quotes() {
+    let parsed = CaaRecordValue::parse(r#"128 tbs "Unknown \"property\" here""#).unwrap();
+    parsed.validate().unwrap();
+
+    assert!(parsed.is_critical());
+    assert_eq!(parsed.canonical(), r#"128 tbs "Unknown \"property\" here""#);
+}
+
+#[test]
+fn issue_accepts_empty_issuer_and_parameters() {
+    for value in [
+        "0 issue \";\"",
+        "0 issuewild \"\"",
+        "0 issue \"ca.example.net; account=230123\"",
+    ] {
+        CaaRecordValue::parse(value).unwrap().validate().unwrap();
+    }
+}
+
+#[test]
+fn validate_rejects_malformed_values() {
+    for value in [
+        "0 issue",
+        "256 issue \"ca.example.net\"",
+        "0 iss-ue \"ca.example.net\"",
+        "0 averyveryverylongtag \"x\"",
+        "0 issue \"bad issuer!.example\"",
+        "0 iodef \"ftp://example.com\"",
+        "0 issue \"unterminated",
+        "0 issue two tokens",
+    ] {
+        assert!(
+            CaaRecordValue::parse(value)
+                .and_then(|v| v.validate())
+                .is_err(),
+            "{value} was accepted"
+        );
+    }
+}
+
+#[test]
+fn iodef_accepts_mail_and_web_urls() {
+    for value in [
+        "0 iodef \"mailto:security@example.com\"",
+        "0 iodef \"https://iodef.example.com/\"",
+    ] {
+        CaaRecordValue::parse(value).unwrap().validate().unwrap();
+    }
+}
+
+#[test]
+fn rdata_round_trips() {
+    let parsed = CaaRecordValue::parse("0 issue \"letsencrypt.org\"").unwrap();
+    let rdata = parsed.to_rdata();
+
+    assert_eq!(&rdata[..7], b"\x00\x05issue");
+    assert_eq!(CaaRecordValue::from_rdata(&rdata).unwrap(), parsed);
+    assert!(CaaRecordValue::from_rdata(&[0, 5, b'i']).is_err());
+}
@@ -4,17 +4,23 @@
 
 mod a;
 mod aaaa;
+mod caa;
 mod cname;
 mod mx;
 mod ns;
 mod ptr;
 mod soa;
 mod srv;
+mod sshfp;
+mod svcb;
+mod tlsa;
 mod txt;
+mod unknown;
 mod value;
 
 pub(crate) use a::ARecordValue;
 pub(crate) use aaaa::AaaaRecordValue;
+pub use caa::CaaRecordValue;
 pub(crate) use cname::CnameRecordValue;
 pub use mx::MxRecordValue;
 pub(crate) use ns::NsRecordValue;
@@ -22,4 +28,9 @@ pub(crate) use ptr::PtrRecordValue;
 pub use soa::SoaMailbox;
 pub(crate) use soa::SoaRecordValue;
 pub(crate) use srv::SrvRecordValue;
+pub use sshfp::SshfpRecordValue;
+pub use svcb::SvcbRecordValue;
+pub use tlsa::TlsaRecordValue;
 pub use txt::{TxtContent, TxtRecordValue};
+pub use unknown::UnknownRecordValue;
+pub(crate) use value::decode_wire_name;
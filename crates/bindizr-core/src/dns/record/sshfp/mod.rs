@@ -0,0 +1,96 @@
+//! SSHFP record values (RFC 4255): a host key algorithm, a fingerprint type,
+//! and the fingerprint.
+
+use super::value::{parse_hex_record_field, parse_u8_record_field, split_leading_fields};
+
+/// An SSHFP value. Stored as `<algorithm> <fp-type> <hex>` with a lowercase
+/// hex fingerprint, so equal rdata has one spelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshfpRecordValue {
+    algorithm: u8,
+    fingerprint_type: u8,
+    fingerprint: Vec<u8>,
+}
+
+impl SshfpRecordValue {
+    /// Parse `<algorithm> <fp-type> <hex>`; the hex may be split by whitespace.
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        let Some((fields, fingerprint)) =
+            split_leading_fields(value, 2).filter(|(_, f)| !f.is_empty())
+        else {
+            return Err(format!(
+                "SSHFP record value must be '<algorithm> <fp-type> <fingerprint>': {value}"
+            ));
+        };
+
+        Ok(Self {
+            algorithm: parse_u8_record_field("SSHFP algorithm", fields[0])?,
+            fingerprint_type: parse_u8_record_field("SSHFP fingerprint type", fields[1])?,
+            fingerprint: parse_hex_record_field("SSHFP fingerprint", fingerprint)?,
+        })
+    }
+
+    /// Decode wire rdata: algorithm and fingerprint type octets, then the
+    /// fingerprint to the end of the rdata.
+    pub fn from_rdata(rdata: &[u8]) -> Result<Self, String> {
+        let [algorithm, fingerprint_type, fingerprint @ ..] = rdata else {
+            return Err("SSHFP rdata is truncated".to_string());
+        };
+
+        let parsed = Self {
+            algorithm: *algorithm,
+            fingerprint_type: *fingerprint_type,
+            fingerprint: fingerprint.to_vec(),
+        };
+        parsed.validate()?;
+        Ok(parsed)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        // RSA, DSA, ECDSA, Ed25519, Ed448 (RFC 4255, 6594, 7479, 8709).
+        if !matches!(self.algorithm, 1..=4 | 6) {
+            return Err(format!(
+                "SSHFP algorithm must be 1 (RSA), 2 (DSA), 3 (ECDSA), 4 (Ed25519), or 6 (Ed448): {}",
+                self.algorithm
+            ));
+        }
+
+        let expected_len = match self.fingerprint_type {
+            1 => 20, // SHA-1
+            2 => 32, // SHA-256
+            other => {
+                return Err(format!(
+                    "SSHFP fingerprint type must be 1 (SHA-1) or 2 (SHA-256): {other}"
+                ));
+            }
+        };
+        if self.fingerprint.len() != expected_len {
+            return Err(format!(
+                "SSHFP fingerprint type {} takes a {expected_len}-byte fingerprint, got {} bytes",
+                self.fingerprint_type,
+                self.fingerprint.len()
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn canonical(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.algorithm,
+            self.fingerprint_type,
+            hex::encode(&self.fingerprint)
+        )
+    }
+
+    /// The wire rdata.
+    pub fn to_rdata(&self) -> Vec<u8> {
+        let mut rdata = vec![self.algorithm, self.fingerprint_type];
+        rdata.extend_from_slice(&self.fingerprint);
+        rdata
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,45 @@
+use super::SshfpRecordValue;
+
+const SHA1: &str = "123456789abcdef67890123456789abcdef67890";
+
+#[test]
+fn parse_canonicalizes_hex_case() {
+    let parsed = SshfpRecordValue::parse(&format!("4  1  {}", SHA1.to_uppercase())).unwrap();
+    parsed.validate().unwrap();
+
+    assert_eq!(parsed.canonical(), format!("4 1 {SHA1}"));
+}
+
+#[test]
+fn rdata_round_trips() {
+    let parsed = SshfpRecordValue::parse(&format!("1 1 {SHA1}")).unwrap();
+    parsed.validate().unwrap();
+
+    assert_eq!(
+        SshfpRecordValue::from_rdata(&parsed.to_rdata()).unwrap(),
+        parsed
+    );
+}
+
+#[test]
+fn validate_rejects_unknown_algorithms_and_fingerprint_lengths() {
+    for value in [
+        format!("0 1 {SHA1}"),
+        format!("5 1 {SHA1}"),
+        format!("4 3 {SHA1}"),
+        format!("4 2 {SHA1}"),
+    ] {
+        let parsed = SshfpRecordValue::parse(&value).unwrap();
+        assert!(parsed.validate().is_err(), "{value} was accepted");
+    }
+}
+
+#[test]
+fn parse_rejects_malformed_values() {
+    for value in ["4 1", "a 1 abcd", "4 1 not-hex"] {
+        assert!(
+            SshfpRecordValue::parse(value).is_err(),
+            "{value} was accepted"
+        );
+    }
+}
@@ -0,0 +1,508 @@
+//! SVCB and HTTPS record values (RFC 9460): a target name plus SvcParams,
+//! with the SvcPriority held in the priority column like MX and SRV.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use base64::Engine;
+
+use super::{
+    TxtRecordValue,
+    value::{
+        decode_wire_name, encode_wire_name, parse_character_string,
+        parse_optional_u16_record_field, parse_u16_record_field, validate_domain_record_value,
+    },
+};
+use crate::dns::name::to_fqdn_lowercase;
+
+const KEY_MANDATORY: u16 = 0;
+const KEY_ALPN: u16 = 1;
+const KEY_NO_DEFAULT_ALPN: u16 = 2;
+const KEY_PORT: u16 = 3;
+const KEY_IPV4HINT: u16 = 4;
+const KEY_ECH: u16 = 5;
+const KEY_IPV6HINT: u16 = 6;
+/// Reserved "invalid key" (RFC 9460, Section 14.3.2).
+const KEY_INVALID: u16 = 65535;
+
+/// The decoded value of one SvcParam.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SvcParamValue {
+    Mandatory(Vec<u16>),
+    Alpn(Vec<Vec<u8>>),
+    NoDefaultAlpn,
+    Port(u16),
+    Ipv4Hint(Vec<Ipv4Addr>),
+    Ech(Vec<u8>),
+    Ipv6Hint(Vec<Ipv6Addr>),
+    /// A key bindizr does not interpret, carried as opaque octets.
+    Opaque(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SvcParam {
+    key: u16,
+    value: SvcParamValue,
+}
+
+/// An SVCB or HTTPS value. Stored as `<target> [<key>=<value> ...]` with a
+/// lowercase FQDN target and the params in ascending key order, so equal
+/// rdata has one spelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvcbRecordValue {
+    priority: u16,
+    target: String,
+    params: Vec<SvcParam>,
+}
+
+impl SvcbRecordValue {
+    /// The value is `<target> [<params>]`; the SvcPriority comes from the
+    /// priority field (default 10), never inline. Params may come in any
+    /// order and are sorted by key.
+    pub(crate) fn parse(value: &str, fallback_priority: Option<i32>) -> Result<Self, String> {
+        let tokens = split_param_tokens(value)?;
+        let Some((target, params)) = tokens.split_first() else {
+            return Err(format!(
+                "SVCB record value must be '<target> [<key>=<value> ...]', with the priority in the priority field: {value}"
+            ));
+        };
+
+        let mut parsed = params
+            .iter()
+            .map(|token| parse_param(token))
+            .collect::<Result<Vec<_>, _>>()?;
+        parsed.sort_by_key(|param| param.key);
+
+        Ok(Self {
+            priority: parse_optional_u16_record_field("SVCB priority", fallback_priority)?,
+            target: to_fqdn_lowercase(target),
+            params: parsed,
+        })
+    }
+
+    /// Decode wire rdata: SvcPriority, an uncompressed TargetName, then
+    /// SvcParams in strictly ascending key order (RFC 9460, Section 2.2).
+    pub fn from_rdata(rdata: &[u8]) -> Result<Self, String> {
+        let [hi, lo, rest @ ..] = rdata else {
+            return Err("SVCB rdata is truncated".to_string());
+        };
+        let priority = u16::from_be_bytes([*hi, *lo]);
+        let (target, used) = decode_wire_name("SVCB target", rest)?;
+
+        let mut params = Vec::new();
+        let mut pos = used;
+        while pos < rest.len() {
+            let header = rest
+                .get(pos..pos + 4)
+                .ok_or_else(|| "SVCB param is truncated".to_string())?;
+            let key = u16::from_be_bytes([header[0], header[1]]);
+            let len = usize::from(u16::from_be_bytes([header[2], header[3]]));
+            pos += 4;
+            let data = rest
+                .get(pos..pos + len)
+                .ok_or_else(|| "SVCB param is truncated".to_string())?;
+            pos += len;
+
+            if params.last().is_some_and(|last: &SvcParam| last.key >= key) {
+                return Err("SVCB params must be in strictly ascending key order".to_string());
+            }
+            params.push(SvcParam {
+                key,
+                value: decode_param_value(key, data)?,
+            });
+        }
+
+        let parsed = Self {
+            priority,
+            target,
+            params,
+        };
+        parsed.validate()?;
+        Ok(parsed)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        if self.target != "." {
+            validate_domain_record_value("SVCB target", &self.target)?;
+        }
+
+        // AliasMode carries no params (RFC 9460, Section 2.4.2); bindizr
+        // refuses them rather than publishing params resolvers must ignore.
+        if self.priority == 0 && !self.params.is_empty() {
+            return Err("SVCB AliasMode (priority 0) records must not have params".to_string());
+        }
+
+        for pair in self.params.windows(2) {
+            if pair[0].key == pair[1].key {
+                return Err(format!(
+                    "SVCB param '{}' is given more than once",
+                    key_name(pair[0].key)
+                ));
+            }
+        }
+
+        for param in &self.params {
+            match &param.value {
+                SvcParamValue::Mandatory(keys) => self.validate_mandatory(keys)?,
+                SvcParamValue::Alpn(ids) if ids.is_empty() => {
+                    return Err("SVCB alpn must list at least one protocol".to_string());
+                }
+                SvcParamValue::NoDefaultAlpn if !self.has_key(KEY_ALPN) => {
+                    return Err("SVCB no-default-alpn requires alpn".to_string());
+                }
+                SvcParamValue::Ipv4Hint(addrs) if addrs.is_empty() => {
+                    return Err("SVCB ipv4hint must list at least one address".to_string());
+                }
+                SvcParamValue::Ipv6Hint(addrs) if addrs.is_empty() => {
+                    return Err("SVCB ipv6hint must list at least one address".to_string());
+                }
+                SvcParamValue::Ech(config) if config.is_empty() => {
+                    return Err("SVCB ech must not be empty".to_string());
+                }
+                _ => {}
+            }
+        }
+
+        if self.to_rdata().len() > usize::from(u16::MAX) {
+            return Err("SVCB value is too long".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Every key `mandatory` lists must be present, and `mandatory` may not
+    /// list itself (RFC 9460, Section 8).
+    fn validate_mandatory(&self, keys: &[u16]) -> Result<(), String> {
+        if keys.is_empty() {
+            return Err("SVCB mandatory must list at least one key".to_string());
+        }
+        for (index, key) in keys.iter().enumerate() {
+            if *key == KEY_MANDATORY {
+                return Err("SVCB mandatory must not list itself".to_string());
+            }
+            if keys[..index].contains(key) {
+                return Err(format!(
+                    "SVCB mandatory lists '{}' more than once",
+                    key_name(*key)
+                ));
+            }
+            if !self.has_key(*key) {
+                return Err(format!(
+                    "SVCB mandatory lists '{}', which is not present",
+                    key_name(*key)
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn has_key(&self, key: u16) -> bool {
+        self.params.iter().any(|param| param.key == key)
+    }
+
+    /// The SvcPriority; 0 is AliasMode.
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    pub(crate) fn canonical(&self) -> String {
+        format!("{} {}", self.priority, self.encoded())
+    }
+
+    /// The value column's form: the target and params, without the priority.
+    pub fn encoded(&self) -> String {
+        let mut out = self.target.clone();
+        for param in &self.params {
+            out.push(' ');
+            out.push_str(&render_param(param));
+        }
+        out
+    }
+
+    /// The wire rdata.
+    pub fn to_rdata(&self) -> Vec<u8> {
+        let mut rdata = self.priority.to_be_bytes().to_vec();
+        rdata.extend_from_slice(&encode_wire_name(&self.target));
+        for param in &self.params {
+            let value = encode_param_value(&param.value);
+            rdata.extend_from_slice(&param.key.to_be_bytes());
+            rdata.extend_from_slice(&(value.len() as u16).to_be_bytes());
+            rdata.extend_from_slice(&value);
+        }
+        rdata
+    }
+}
+
+fn key_name(key: u16) -> String {
+    match key {
+        KEY_MANDATORY => "mandatory".to_string(),
+        KEY_ALPN => "alpn".to_string(),
+        KEY_NO_DEFAULT_ALPN => "no-default-alpn".to_string(),
+        KEY_PORT => "port".to_string(),
+        KEY_IPV4HINT => "ipv4hint".to_string(),
+        KEY_ECH => "ech".to_string(),
+        KEY_IPV6HINT => "ipv6hint".to_string(),
+        other => format!("key{other}"),
+    }
+}
+
+fn parse_key(name: &str) -> Result<u16, String> {
+    let key = match name.to_ascii_lowercase().as_str() {
+        "mandatory" => KEY_MANDATORY,
+        "alpn" => KEY_ALPN,
+        "no-default-alpn" => KEY_NO_DEFAULT_ALPN,
+        "port" => KEY_PORT,
+        "ipv4hint" => KEY_IPV4HINT,
+        "ech" => KEY_ECH,
+        "ipv6hint" => KEY_IPV6HINT,
+        other => other
+            .strip_prefix("key")
+            .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|digits| digits.parse::<u16>().ok())
+            .ok_or_else(|| format!("unknown SVCB param key: {name}"))?,
+    };
+    if key == KEY_INVALID {
+        return Err("SVCB param key65535 is reserved".to_string());
+    }
+    Ok(key)
+}
+
+/// Split a value into whitespace-separated tokens, keeping quoted param
+/// values (which may contain spaces) inside their token.
+fn split_param_tokens(value: &str) -> Result<Vec<&str>, String> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (index, ch) in value.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match ch {
+            '\\' => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if let Some(begin) = start.take() {
+                    tokens.push(&value[begin..index]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(index);
+    }
+
+    if in_quotes {
+        return Err("SVCB value contains an unterminated quote".to_string());
+    }
+    if let Some(begin) = start {
+        tokens.push(&value[begin..]);
+    }
+    Ok(tokens)
+}
+
+fn parse_param(token: &str) -> Result<SvcParam, String> {
+    let (name, raw) = match token.split_once('=') {
+        Some((name, raw)) => (name, Some(raw)),
+        None => (token, None),
+    };
+    let key = parse_key(name)?;
+    let field = format!("SVCB {} value", key_name(key));
+    let bytes = raw
+        .map(|raw| parse_character_string(&field, raw))
+        .transpose()?;
+    let text = || -> Result<String, String> {
+        let bytes = bytes
+            .clone()
+            .ok_or_else(|| format!("SVCB param '{}' requires a value", key_name(key)))?;
+        String::from_utf8(bytes).map_err(|_| format!("{field} must be ASCII"))
+    };
+
+    let value = match key {
+        KEY_MANDATORY => SvcParamValue::Mandatory(
+            text()?
+                .split(',')
+                .map(parse_key)
+                .collect::<Result<_, _>>()?,
+        ),
+        KEY_ALPN => {
+            let ids = text()?
+                .split(',')
+                .map(|id| {
+                    if id.is_empty() || id.len() > 255 {
+                        Err("SVCB alpn ids must be 1 to 255 bytes".to_string())
+                    } else {
+                        Ok(id.as_bytes().to_vec())
+                    }
+                })
+                .collect::<Result<_, _>>()?;
+            SvcParamValue::Alpn(ids)
+        }
+        KEY_NO_DEFAULT_ALPN => {
+            if bytes.as_ref().is_some_and(|value| !value.is_empty()) {
+                return Err("SVCB no-default-alpn takes no value".to_string());
+            }
+            SvcParamValue::NoDefaultAlpn
+        }
+        KEY_PORT => SvcParamValue::Port(parse_u16_record_field("SVCB port", &text()?)?),
+        KEY_IPV4HINT => SvcParamValue::Ipv4Hint(
+            text()?
+                .split(',')
+                .map(|addr| {
+                    addr.parse::<Ipv4Addr>()
+                        .map_err(|_| format!("SVCB ipv4hint must list IPv4 addresses: {addr}"))
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        KEY_ECH => SvcParamValue::Ech(
+            base64::engine::general_purpose::STANDARD
+                .decode(text()?)
+                .map_err(|_| "SVCB ech must be base64".to_string())?,
+        ),
+        KEY_IPV6HINT => SvcParamValue::Ipv6Hint(
+            text()?
+                .split(',')
+                .map(|addr| {
+                    addr.parse::<Ipv6Addr>()
+                        .map_err(|_| format!("SVCB ipv6hint must list IPv6 addresses: {addr}"))
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        _ => SvcParamValue::Opaque(bytes.unwrap_or_default()),
+    };
+
+    Ok(SvcParam { key, value })
+}
+
+fn decode_param_value(key: u16, data: &[u8]) -> Result<SvcParamValue, String> {
+    let invalid = || format!("invalid SVCB {} param", key_name(key));
+    Ok(match key {
+        KEY_MANDATORY => {
+            if data.len() % 2 != 0 {
+                return Err(invalid());
+            }
+            SvcParamValue::Mandatory(
+                data.chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect(),
+            )
+        }
+        KEY_ALPN => {
+            let mut ids = Vec::new();
+            let mut pos = 0usize;
+            while pos < data.len() {
+                let len = usize::from(data[pos]);
+                let id = data.get(pos + 1..pos + 1 + len).ok_or_else(invalid)?;
+                // Presentation splits the list on commas, so an id holding one
+                // could not be shown or re-entered.
+                if id.is_empty() || id.contains(&b',') {
+                    return Err(invalid());
+                }
+                ids.push(id.to_vec());
+                pos += 1 + len;
+            }
+            SvcParamValue::Alpn(ids)
+        }
+        KEY_NO_DEFAULT_ALPN => {
+            if !data.is_empty() {
+                return Err(invalid());
+            }
+            SvcParamValue::NoDefaultAlpn
+        }
+        KEY_PORT => match data {
+            [hi, lo] => SvcParamValue::Port(u16::from_be_bytes([*hi, *lo])),
+            _ => return Err(invalid()),
+        },
+        KEY_IPV4HINT => {
+            if data.len() % 4 != 0 {
+                return Err(invalid());
+            }
+            SvcParamValue::Ipv4Hint(
+                data.chunks_exact(4)
+                    .map(|octets| Ipv4Addr::new(octets[0], octets[1], octets[2], octets[3]))
+                    .collect(),
+            )
+        }
+        KEY_ECH => SvcParamValue::Ech(data.to_vec()),
+        KEY_IPV6HINT => {
+            if data.len() % 16 != 0 {
+                return Err(invalid());
+            }
+            SvcParamValue::Ipv6Hint(
+                data.chunks_exact(16)
+                    .map(|octets| {
+                        let mut addr = [0u8; 16];
+                        addr.copy_from_slice(octets);
+                        Ipv6Addr::from(addr)
+                    })
+                    .collect(),
+            )
+        }
+        KEY_INVALID => return Err("SVCB param key65535 is reserved".to_string()),
+        _ => SvcParamValue::Opaque(data.to_vec()),
+    })
+}
+
+fn encode_param_value(value: &SvcParamValue) -> Vec<u8> {
+    match value {
+        SvcParamValue::Mandatory(keys) => {
+            let mut sorted = keys.clone();
+            sorted.sort_unstable();
+            sorted.iter().flat_map(|key| key.to_be_bytes()).collect()
+        }
+        SvcParamValue::Alpn(ids) => {
+            let mut out = Vec::new();
+            for id in ids {
+                out.push(id.len() as u8);
+                out.extend_from_slice(id);
+            }
+            out
+        }
+        SvcParamValue::NoDefaultAlpn => Vec::new(),
+        SvcParamValue::Port(port) => port.to_be_bytes().to_vec(),
+        SvcParamValue::Ipv4Hint(addrs) => addrs.iter().flat_map(|addr| addr.octets()).collect(),
+        SvcParamValue::Ech(config) | SvcParamValue::Opaque(config) => config.clone(),
+        SvcParamValue::Ipv6Hint(addrs) => addrs.iter().flat_map(|addr| addr.octets()).collect(),
+    }
+}
+
+fn render_param(param: &SvcParam) -> String {
+    let name = key_name(param.key);
+    let join = |items: Vec<String>| items.join(",");
+    match &param.value {
+        SvcParamValue::Mandatory(keys) => {
+            let mut sorted = keys.clone();
+            sorted.sort_unstable();
+            format!(
+                "{name}={}",
+                join(sorted.into_iter().map(key_name).collect())
+            )
+        }
+        SvcParamValue::Alpn(ids) => format!(
+            "{name}={}",
+            TxtRecordValue::to_quoted_charstr(&ids.join(&b','))
+        ),
+        SvcParamValue::NoDefaultAlpn => name,
+        SvcParamValue::Port(port) => format!("{name}={port}"),
+        SvcParamValue::Ipv4Hint(addrs) => format!(
+            "{name}={}",
+            join(addrs.iter().map(ToString::to_string).collect())
+        ),
+        SvcParamValue::Ech(config) => format!(
+            "{name}={}",
+            base64::engine::general_purpose::STANDARD.encode(config)
+        ),
+        SvcParamValue::Ipv6Hint(addrs) => format!(
+            "{name}={}",
+            join(addrs.iter().map(ToString::to_string).collect())
+        ),
+        SvcParamValue::Opaque(value) if value.is_empty() => name,
+        SvcParamValue::Opaque(value) => {
+            format!("{name}={}", TxtRecordValue::to_quoted_charstr(value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,97 @@
+use super::SvcbRecordValue;
+
+#[test]
+fn parse_sorts_params_and_canonicalizes_spelling() {
+    let parsed = SvcbRecordValue::parse(
+        "Svc.Example.com ipv6hint=2001:DB8::1 port=8443 ALPN=h2,h3 mandatory=port,alpn",
+        Some(1),
+    )
+    .unwrap();
+    parsed.validate().unwrap();
+
+    assert_eq!(
+        parsed.encoded(),
+        "svc.example.com. mandatory=alpn,port alpn=\"h2,h3\" port=8443 ipv6hint=2001:db8::1"
+    );
+    assert_eq!(parsed.priority(), 1);
+}
+
+#[test]
+fn priority_defaults_to_ten_and_target_may_be_root() {
+    let parsed = SvcbRecordValue::parse(". alpn=h2", None).unwrap();
+    parsed.validate().unwrap();
+
+    assert_eq!(parsed.canonical(), "10 . alpn=\"h2\"");
+}
+
+#[test]
+fn rdata_round_trips() {
+    for value in [
+        "svc.example.com. alpn=\"h2,h3\" no-default-alpn port=443 ipv4hint=192.0.2.1,192.0.2.2",
+        "svc.example.com. ech=AEX+DQBBpQAgACD4 key667=\"hello world\"",
+        "svc.example.com. key65000",
+    ] {
+        let parsed = SvcbRecordValue::parse(value, Some(1)).unwrap();
+        parsed.validate().unwrap();
+
+        let decoded = SvcbRecordValue::from_rdata(&parsed.to_rdata()).unwrap();
+        assert_eq!(decoded, parsed, "{value}");
+        assert_eq!(decoded.encoded(), value);
+    }
+}
+
+#[test]
+fn alias_mode_round_trips_without_params() {
+    let parsed = SvcbRecordValue::parse("pool.example.net.", Some(0)).unwrap();
+    parsed.validate().unwrap();
+
+    let rdata = parsed.to_rdata();
+    assert_eq!(&rdata[..2], &[0, 0]);
+    assert_eq!(
+        SvcbRecordValue::from_rdata(&rdata).unwrap().canonical(),
+        "0 pool.example.net."
+    );
+}
+
+#[test]
+fn validate_rejects_inconsistent_params() {
+    for (value, priority) in [
+        ("svc.example.com. alpn=h2", Some(0)),
+        ("svc.example.com. port=443 port=8443", Some(1)),
+        ("svc.example.com. mandatory=port", Some(1)),
+        ("svc.example.com. mandatory=mandatory,port port=1", Some(1)),
+        ("svc.example.com. no-default-alpn", Some(1)),
+    ] {
+        let parsed = SvcbRecordValue::parse(value, priority).unwrap();
+        assert!(parsed.validate().is_err(), "{value} was accepted");
+    }
+}
+
+#[test]
+fn parse_rejects_malformed_params() {
+    for value in [
+        "",
+        "svc.example.com. port=https",
+        "svc.example.com. ipv4hint=2001:db8::1",
+        "svc.example.com. ech=not*base64",
+        "svc.example.com. key65535=x",
+        "svc.example.com. bogus=1",
+        "svc.example.com. alpn=\"h2",
+        "svc.example.com. no-default-alpn=x",
+    ] {
+        assert!(
+            SvcbRecordValue::parse(value, Some(1)).is_err(),
+            "{value} was accepted"
+        );
+    }
+}
+
+#[test]
+fn from_rdata_rejects_unordered_or_truncated_params() {
+    // priority 1, root target, port then alpn: keys out of order.
+    let unordered = [0, 1, 0, 0, 3, 0, 2, 1, 187, 0, 1, 0, 3, 2, b'h', b'2'];
+    assert!(SvcbRecordValue::from_rdata(&unordered).is_err());
+
+    let truncated = [0, 1, 0, 0, 3, 0, 2, 1];
+    assert!(SvcbRecordValue::from_rdata(&truncated).is_err());
+}
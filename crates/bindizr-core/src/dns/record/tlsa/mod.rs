@@ -0,0 +1,120 @@
+//! TLSA record values (RFC 6698): certificate usage, selector, matching type,
+//! and the certificate association data.
+
+use super::value::{parse_hex_record_field, parse_u8_record_field, split_leading_fields};
+
+/// Private-use value shared by all three TLSA parameter registries.
+const PRIVATE_USE: u8 = 255;
+
+/// A TLSA value. Stored as `<usage> <selector> <matching-type> <hex>` with
+/// the association data as one lowercase hex string, so equal rdata has one
+/// spelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsaRecordValue {
+    usage: u8,
+    selector: u8,
+    matching_type: u8,
+    data: Vec<u8>,
+}
+
+impl TlsaRecordValue {
+    /// Parse `<usage> <selector> <matching-type> <hex>`; the hex may be split
+    /// by whitespace.
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        let Some((fields, data)) = split_leading_fields(value, 3).filter(|(_, d)| !d.is_empty())
+        else {
+            return Err(format!(
+                "TLSA record value must be '<usage> <selector> <matching-type> <data>': {value}"
+            ));
+        };
+
+        Ok(Self {
+            usage: parse_u8_record_field("TLSA certificate usage", fields[0])?,
+            selector: parse_u8_record_field("TLSA selector", fields[1])?,
+            matching_type: parse_u8_record_field("TLSA matching type", fields[2])?,
+            data: parse_hex_record_field("TLSA certificate association data", data)?,
+        })
+    }
+
+    /// Decode wire rdata: three parameter octets, then the association data to
+    /// the end of the rdata.
+    pub fn from_rdata(rdata: &[u8]) -> Result<Self, String> {
+        let [usage, selector, matching_type, data @ ..] = rdata else {
+            return Err("TLSA rdata is truncated".to_string());
+        };
+
+        let parsed = Self {
+            usage: *usage,
+            selector: *selector,
+            matching_type: *matching_type,
+            data: data.to_vec(),
+        };
+        parsed.validate()?;
+        Ok(parsed)
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        // PKIX-TA, PKIX-EE, DANE-TA, DANE-EE (RFC 7218).
+        if self.usage > 3 && self.usage != PRIVATE_USE {
+            return Err(format!(
+                "TLSA certificate usage must be 0-3 or 255: {}",
+                self.usage
+            ));
+        }
+        // Cert, SPKI.
+        if self.selector > 1 && self.selector != PRIVATE_USE {
+            return Err(format!(
+                "TLSA selector must be 0, 1, or 255: {}",
+                self.selector
+            ));
+        }
+
+        let expected_len = match self.matching_type {
+            0 | PRIVATE_USE => None,
+            1 => Some(32), // SHA2-256
+            2 => Some(64), // SHA2-512
+            other => {
+                return Err(format!(
+                    "TLSA matching type must be 0, 1, 2, or 255: {other}"
+                ));
+            }
+        };
+        if self.data.is_empty() {
+            return Err("TLSA certificate association data must not be empty".to_string());
+        }
+        if let Some(expected_len) = expected_len
+            && self.data.len() != expected_len
+        {
+            return Err(format!(
+                "TLSA matching type {} takes a {expected_len}-byte digest, got {} bytes",
+                self.matching_type,
+                self.data.len()
+            ));
+        }
+        if self.data.len() + 3 > usize::from(u16::MAX) {
+            return Err("TLSA certificate association data is too long".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn canonical(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.usage,
+            self.selector,
+            self.matching_type,
+            hex::encode(&self.data)
+        )
+    }
+
+    /// The wire rdata.
+    pub fn to_rdata(&self) -> Vec<u8> {
+        let mut rdata = vec![self.usage, self.selector, self.matching_type];
+        rdata.extend_from_slice(&self.data);
+        rdata
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,56 @@
+use super::TlsaRecordValue;
+
+const SHA256: &str = "8cb0fc6c527506a053f4f14c8464bebbd6dede2738d11468dd953d7d6a3021f1";
+
+#[test]
+fn parse_joins_split_hex_and_lowercases_it() {
+    let parsed = TlsaRecordValue::parse(&format!(
+        "3 1 1 {} {}",
+        &SHA256[..32].to_uppercase(),
+        &SHA256[32..]
+    ))
+    .unwrap();
+    parsed.validate().unwrap();
+
+    assert_eq!(parsed.canonical(), format!("3 1 1 {SHA256}"));
+}
+
+#[test]
+fn rdata_round_trips() {
+    let parsed = TlsaRecordValue::parse("2 0 0 308201a2300d06092a864886f70d01").unwrap();
+    parsed.validate().unwrap();
+
+    let rdata = parsed.to_rdata();
+    assert_eq!(&rdata[..3], &[2, 0, 0]);
+    assert_eq!(TlsaRecordValue::from_rdata(&rdata).unwrap(), parsed);
+}
+
+#[test]
+fn validate_rejects_unknown_parameters_and_digest_lengths() {
+    for value in [
+        format!("4 1 1 {SHA256}"),
+        format!("3 2 1 {SHA256}"),
+        format!("3 1 3 {SHA256}"),
+        format!("3 1 2 {SHA256}"),
+        "3 1 1 abcd".to_string(),
+    ] {
+        let parsed = TlsaRecordValue::parse(&value).unwrap();
+        assert!(parsed.validate().is_err(), "{value} was accepted");
+    }
+}
+
+#[test]
+fn parse_rejects_malformed_values() {
+    for value in [
+        "3 1 1",
+        "3 1 x abcd",
+        "256 1 1 abcd",
+        "3 1 1 xyz",
+        "3 1 1 abc",
+    ] {
+        assert!(
+            TlsaRecordValue::parse(value).is_err(),
+            "{value} was accepted"
+        );
+    }
+}
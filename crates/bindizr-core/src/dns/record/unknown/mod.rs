@@ -0,0 +1,64 @@
+//! Opaque values for record types bindizr does not model, in the RFC 3597
+//! generic form `\# <length> <hex>`.
+
+use super::value::parse_hex_record_field;
+
+/// An opaque rdata value. Stored as `\# <length> <hex>` with lowercase hex in
+/// one token (`\# 0` for empty rdata), so equal rdata has one spelling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownRecordValue(Vec<u8>);
+
+impl UnknownRecordValue {
+    /// Parse `\# <length> [<hex> ...]`; the hex may be split by whitespace and
+    /// must decode to exactly `<length>` octets (RFC 3597, Section 5).
+    pub(crate) fn parse(value: &str) -> Result<Self, String> {
+        let mut fields = value.split_whitespace();
+        if fields.next() != Some(r"\#") {
+            return Err(format!(
+                r"record value must be in the generic form '\# <length> <hex>': {value}"
+            ));
+        }
+        let length = fields
+            .next()
+            .and_then(|length| length.parse::<u16>().ok())
+            .ok_or_else(|| format!(r"generic record length must be 0 to 65535: {value}"))?;
+
+        let hex = fields.collect::<Vec<_>>().join("");
+        let rdata = if hex.is_empty() {
+            Vec::new()
+        } else {
+            parse_hex_record_field("generic record data", &hex)?
+        };
+        if rdata.len() != usize::from(length) {
+            return Err(format!(
+                "generic record length is {length} but the data is {} bytes",
+                rdata.len()
+            ));
+        }
+
+        Ok(Self(rdata))
+    }
+
+    /// Wrap wire rdata.
+    pub fn from_rdata(rdata: &[u8]) -> Result<Self, String> {
+        if rdata.len() > usize::from(u16::MAX) {
+            return Err("generic record data is too long".to_string());
+        }
+        Ok(Self(rdata.to_vec()))
+    }
+
+    pub fn canonical(&self) -> String {
+        if self.0.is_empty() {
+            return r"\# 0".to_string();
+        }
+        format!(r"\# {} {}", self.0.len(), hex::encode(&self.0))
+    }
+
+    /// The wire rdata.
+    pub fn to_rdata(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,36 @@
+use super::UnknownRecordValue;
+
+#[test]
+fn parse_joins_split_hex_and_lowercases_it() {
+    let parsed = UnknownRecordValue::parse(r"\# 6  0A0000 01 FfEe").unwrap();
+
+    assert_eq!(parsed.canonical(), r"\# 6 0a000001ffee");
+    assert_eq!(parsed.to_rdata(), vec![0x0a, 0, 0, 1, 0xff, 0xee]);
+}
+
+#[test]
+fn empty_rdata_round_trips() {
+    let parsed = UnknownRecordValue::parse(r"\# 0").unwrap();
+
+    assert_eq!(parsed.canonical(), r"\# 0");
+    assert_eq!(UnknownRecordValue::from_rdata(&[]).unwrap(), parsed);
+}
+
+#[test]
+fn parse_rejects_values_outside_the_generic_syntax() {
+    for value in [
+        "10.0.0.1",
+        r"\#",
+        r"\# x 00",
+        r"\# 2 00",
+        r"\# 1 0001",
+        r"\# 1 zz",
+        r"\# 1 0",
+        r"\# 65536 00",
+    ] {
+        assert!(
+            UnknownRecordValue::parse(value).is_err(),
+            "{value} was accepted"
+        );
+    }
+}
@@ -15,6 +15,12 @@ pub(crate) fn parse_u16_record_field(field: &str, value: &str) -> Result<u16, St
         .map_err(|_| format!("{field} must be an unsigned 16-bit integer: {value}"))
 }
 
+pub(crate) fn parse_u8_record_field(field: &str, value: &str) -> Result<u8, String> {
+    value
+        .parse::<u8>()
+        .map_err(|_| format!("{field} must be between 0 and 255: {value}"))
+}
+
 pub(crate) fn parse_u32_record_field(field: &str, value: &str) -> Result<u32, String> {
     value
         .parse::<u32>()
@@ -51,6 +57,140 @@ pub(crate) fn validate_domain_record_value(field: &str, value: &str) -> Result<(
     Ok(())
 }
 
+/// Split off `count` leading whitespace-separated fields, returning them with
+/// the untouched remainder (which may itself contain spaces, e.g. a quoted
+/// string). `None` if the value has fewer fields.
+pub(crate) fn split_leading_fields(value: &str, count: usize) -> Option<(Vec<&str>, &str)> {
+    let mut rest = value.trim_start();
+    let mut fields = Vec::with_capacity(count);
+    for _ in 0..count {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some((fields, rest.trim_end()))
+}
+
+/// Decode one presentation-form character-string: either a quoted string
+/// (which may contain spaces) or a bare token, with `\X` and `\DDD` escapes
+/// resolved per RFC 1035, Section 5.1.
+pub(crate) fn parse_character_string(field: &str, input: &str) -> Result<Vec<u8>, String> {
+    let (body, quoted) = match input.strip_prefix('"') {
+        Some(inner) => (inner, true),
+        None => (input, false),
+    };
+
+    let mut out = Vec::with_capacity(body.len());
+    let mut bytes = body.bytes();
+    let mut closed = false;
+    while let Some(byte) = bytes.next() {
+        match byte {
+            b'"' if quoted => {
+                closed = true;
+                break;
+            }
+            b'\\' => match bytes.next() {
+                Some(d @ b'0'..=b'9') => {
+                    let d2 = bytes.next().filter(u8::is_ascii_digit);
+                    let d3 = bytes.next().filter(u8::is_ascii_digit);
+                    let (Some(d2), Some(d3)) = (d2, d3) else {
+                        return Err(format!("{field} contains an invalid \\DDD escape"));
+                    };
+                    let code =
+                        (d - b'0') as u16 * 100 + (d2 - b'0') as u16 * 10 + (d3 - b'0') as u16;
+                    if code > 255 {
+                        return Err(format!("{field} contains an invalid \\DDD escape"));
+                    }
+                    out.push(code as u8);
+                }
+                Some(escaped) => out.push(escaped),
+                None => return Err(format!("{field} contains a dangling escape")),
+            },
+            b if !quoted && (b.is_ascii_whitespace() || b == b'"') => {
+                return Err(format!(
+                    "{field} must be quoted when it contains spaces or quotes"
+                ));
+            }
+            other => out.push(other),
+        }
+    }
+
+    if quoted && !closed {
+        return Err(format!("{field} contains an unterminated quote"));
+    }
+    if quoted && bytes.next().is_some() {
+        return Err(format!("{field} has trailing data after the closing quote"));
+    }
+
+    Ok(out)
+}
+
+/// Decode a hex field; whitespace is ignored, since zone files may split a
+/// long digest across several tokens.
+pub(crate) fn parse_hex_record_field(field: &str, value: &str) -> Result<Vec<u8>, String> {
+    let digits = value.split_whitespace().collect::<String>();
+    if digits.is_empty() {
+        return Err(format!("{field} must not be empty"));
+    }
+    hex::decode(&digits).map_err(|_| format!("{field} must be hexadecimal: {digits}"))
+}
+
+/// Encode a validated presentation-form name (no escapes) as uncompressed
+/// wire labels; `.` is the root name.
+pub(crate) fn encode_wire_name(name: &str) -> Vec<u8> {
+    let mut wire = Vec::with_capacity(name.len() + 2);
+    for label in name
+        .trim_end_matches('.')
+        .split('.')
+        .filter(|l| !l.is_empty())
+    {
+        wire.push(label.len() as u8);
+        wire.extend_from_slice(label.as_bytes());
+    }
+    wire.push(0);
+    wire
+}
+
+/// Decode an uncompressed wire name at the start of `data` into a lowercase
+/// FQDN, returning it with the number of bytes consumed. Compression pointers
+/// are refused: the rdata fields that use this forbid them.
+pub(crate) fn decode_wire_name(field: &str, data: &[u8]) -> Result<(String, usize), String> {
+    let mut labels = Vec::new();
+    let mut pos = 0usize;
+    loop {
+        let len = *data
+            .get(pos)
+            .ok_or_else(|| format!("{field} is truncated"))? as usize;
+        pos += 1;
+        if len == 0 {
+            break;
+        }
+        if len & 0xC0 != 0 {
+            return Err(format!("{field} must not be compressed"));
+        }
+        let label = data
+            .get(pos..pos + len)
+            .ok_or_else(|| format!("{field} is truncated"))?;
+        let label = std::str::from_utf8(label).map_err(|_| format!("{field} must be ASCII"))?;
+        // Joined with dots below, a dot inside a label would read as a boundary.
+        if label.contains('.') {
+            return Err(format!("{field} must not have a '.' inside a label"));
+        }
+        labels.push(label.to_ascii_lowercase());
+        pos += len;
+    }
+
+    if labels.is_empty() {
+        return Ok((".".to_string(), pos));
+    }
+    let name = format!("{}.", labels.join("."));
+    validate_domain_record_value(field, &name)?;
+    Ok((name, pos))
+}
+
 #[cfg(test)]
 mod tests {
     use super::validate_domain_record_value;
@@ -0,0 +1,103 @@
+//! SOA serial numbers and their RFC 1982 arithmetic.
+//!
+//! On the wire a serial is an unsigned 32-bit number that wraps around; two
+//! serials are ordered by which one is less than 2^31 steps ahead of the
+//! other, not by magnitude. The database keeps serials in signed 32-bit
+//! columns holding the same bits, so a serial past `i32::MAX` is stored
+//! negative: convert with [`to_wire`] and [`from_wire`] at the boundary, and
+//! compare with [`compare`] rather than `<`.
+
+use std::cmp::Ordering;
+
+/// Half the serial space: the largest step RFC 1982 addition allows is one
+/// less than this, and two serials exactly this far apart are incomparable.
+const HALF: u32 = 1 << 31;
+
+/// The wire serial a stored value holds.
+pub fn to_wire(stored: i32) -> u32 {
+    stored as u32
+}
+
+/// The stored form of a wire serial.
+pub fn from_wire(serial: u32) -> i32 {
+    serial as i32
+}
+
+/// RFC 1982 §3.2 comparison. `None` when the two serials are exactly 2^31
+/// apart, where the ordering is undefined.
+pub fn compare(a: u32, b: u32) -> Option<Ordering> {
+    match b.wrapping_sub(a) {
+        0 => Some(Ordering::Equal),
+        HALF => None,
+        ahead if ahead < HALF => Some(Ordering::Less),
+        _ => Some(Ordering::Greater),
+    }
+}
+
+/// Whether `a` is a later serial than `b`.
+pub fn is_newer(a: u32, b: u32) -> bool {
+    compare(a, b) == Some(Ordering::Greater)
+}
+
+/// The serial to move to from `current`: `candidate` when it is newer,
+/// otherwise `current + 1`, wrapping past `u32::MAX` to 0.
+pub fn advance(current: u32, candidate: u32) -> u32 {
+    if is_newer(candidate, current) {
+        candidate
+    } else {
+        current.wrapping_add(1)
+    }
+}
+
+/// How many steps `to` is ahead of `from`, counting around the wrap.
+pub fn distance(from: u32, to: u32) -> u32 {
+    to.wrapping_sub(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::*;
+
+    #[test]
+    fn stored_serials_round_trip_through_the_wire_form() {
+        assert_eq!(to_wire(42), 42);
+        assert_eq!(to_wire(-1), u32::MAX);
+        assert_eq!(to_wire(i32::MIN), 1 << 31);
+        for serial in [0, 1, i32::MAX as u32, 1 << 31, u32::MAX] {
+            assert_eq!(to_wire(from_wire(serial)), serial);
+        }
+    }
+
+    #[test]
+    fn compare_orders_serials_across_the_wrap() {
+        assert_eq!(compare(1, 2), Some(Ordering::Less));
+        assert_eq!(compare(2, 1), Some(Ordering::Greater));
+        assert_eq!(compare(7, 7), Some(Ordering::Equal));
+        // u32::MAX + 1 wraps to 0, which is the later serial.
+        assert!(is_newer(0, u32::MAX));
+        assert!(is_newer(5, u32::MAX - 5));
+        assert!(!is_newer(u32::MAX - 5, 5));
+        // Anything less than 2^31 ahead is newer; 2^31 apart is undefined.
+        assert!(is_newer(HALF - 1, 0));
+        assert_eq!(compare(0, HALF), None);
+        assert_eq!(compare(HALF, 0), None);
+    }
+
+    #[test]
+    fn advance_only_moves_forward() {
+        assert_eq!(advance(10, 20), 20);
+        assert_eq!(advance(10, 5), 11);
+        assert_eq!(advance(10, 10), 11);
+        assert_eq!(advance(u32::MAX, 3), 3);
+        assert_eq!(advance(u32::MAX, u32::MAX - 1), 0);
+    }
+
+    #[test]
+    fn distance_counts_around_the_wrap() {
+        assert_eq!(distance(3, 10), 7);
+        assert_eq!(distance(u32::MAX, 1), 2);
+        assert_eq!(distance(10, 10), 0);
+    }
+}
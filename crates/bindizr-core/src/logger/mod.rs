@@ -1,6 +1,6 @@
 use std::io::{self, Write};
 
-use log::{Level, Metadata, Record};
+use log::{Level, LevelFilter, Metadata, Record};
 
 use crate::config;
 
@@ -41,19 +41,18 @@ macro_rules! log_debug_enabled {
     };
 }
 
-/// Simple `log` implementation that writes to stderr.
-struct Logger {
-    log_level: Level,
-}
+/// Simple `log` implementation that writes to stderr. The level lives in
+/// `log::max_level`, so [`set_level`] can change it at runtime.
+struct Logger;
 
 impl log::Log for Logger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= self.log_level
+        metadata.level() <= log::max_level()
     }
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
-            let log_message = if self.log_level == Level::Debug {
+            let log_message = if log::max_level() == LevelFilter::Debug {
                 format!(
                     "{} - {}: {}\n",
                     record.level(),
@@ -82,21 +81,27 @@ pub fn initialize() {
 /// Install the global logger at an explicit level, for binaries that do not
 /// load the bindizr configuration file (e.g. the ExternalDNS adapter).
 pub fn initialize_with_level(level: config::LogLevel) {
-    let log_level = match level {
-        config::LogLevel::Error => Level::Error,
-        config::LogLevel::Warn => Level::Warn,
-        config::LogLevel::Debug => Level::Debug,
-        config::LogLevel::Trace => Level::Trace,
-        config::LogLevel::Info => Level::Info,
-    };
-
-    let logger = Logger { log_level };
-
-    if let Err(e) = log::set_boxed_logger(Box::new(logger)) {
+    if let Err(e) = log::set_boxed_logger(Box::new(Logger)) {
         eprintln!("Failed to set logger: {}", e);
         return;
     }
+    let log_level = to_log_level(level);
     log::set_max_level(log_level.to_level_filter());
 
     println!("Console logging level: {}", log_level);
 }
+
+/// Change the level of the installed logger, e.g. after a config reload.
+pub fn set_level(level: config::LogLevel) {
+    log::set_max_level(to_log_level(level).to_level_filter());
+}
+
+fn to_log_level(level: config::LogLevel) -> Level {
+    match level {
+        config::LogLevel::Error => Level::Error,
+        config::LogLevel::Warn => Level::Warn,
+        config::LogLevel::Debug => Level::Debug,
+        config::LogLevel::Trace => Level::Trace,
+        config::LogLevel::Info => Level::Info,
+    }
+}
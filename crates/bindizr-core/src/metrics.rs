@@ -24,6 +24,9 @@ pub struct Metrics {
     pub notify_sent_total: IntCounterVec,
     pub nsupdate_requests_total: IntCounterVec,
     pub zone_serial_bumps_total: IntCounter,
+    pub webhook_deliveries_total: IntCounterVec,
+    pub secondary_serial_lag: IntGaugeVec,
+    pub secondary_probe_failures_total: IntCounterVec,
 }
 
 static METRICS: OnceLock<Metrics> = OnceLock::new();
@@ -138,6 +141,36 @@ impl Metrics {
         .expect("valid metric definition");
         register(&registry, &nsupdate_requests_total);
 
+        let webhook_deliveries_total = IntCounterVec::new(
+            Opts::new(
+                "bindizr_webhook_deliveries_total",
+                "Webhook delivery attempts, by outcome.",
+            ),
+            &["result"],
+        )
+        .expect("valid metric definition");
+        register(&registry, &webhook_deliveries_total);
+
+        let secondary_serial_lag = IntGaugeVec::new(
+            Opts::new(
+                "bindizr_secondary_serial_lag",
+                "Serials each secondary is behind each zone at the sync monitor's last successful probe; negative when ahead.",
+            ),
+            &["zone", "secondary"],
+        )
+        .expect("valid metric definition");
+        register(&registry, &secondary_serial_lag);
+
+        let secondary_probe_failures_total = IntCounterVec::new(
+            Opts::new(
+                "bindizr_secondary_probe_failures_total",
+                "Sync monitor SOA probes that got no serial from a secondary.",
+            ),
+            &["zone", "secondary"],
+        )
+        .expect("valid metric definition");
+        register(&registry, &secondary_probe_failures_total);
+
         let zone_serial_bumps_total = IntCounter::new(
             "bindizr_zone_serial_bumps_total",
             "Zone serial writes across every update path.",
@@ -156,6 +189,9 @@ impl Metrics {
             notify_sent_total,
             nsupdate_requests_total,
             zone_serial_bumps_total,
+            webhook_deliveries_total,
+            secondary_serial_lag,
+            secondary_probe_failures_total,
         }
     }
 
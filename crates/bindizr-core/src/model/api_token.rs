@@ -1,6 +1,89 @@
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 
+/// What an API token may do. `Scoped` tokens act only within their
+/// `zone_token_policies` grants; every other role spans all zones, each
+/// including the rights of the roles before it. Fixed at creation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum TokenRole {
+    /// Record-plane rights in the zones its policies grant, nothing else.
+    #[default]
+    Scoped,
+    /// Read every zone, record and snapshot; write nothing.
+    Viewer,
+    /// Also create, change and delete records in every zone.
+    RecordEditor,
+    /// Also create, change and delete zones, and manage their transfer ACLs,
+    /// imports, rollbacks and history.
+    ZoneAdmin,
+    /// Everything, including API tokens, TSIG keys and their policies.
+    Admin,
+}
+
+impl TokenRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenRole::Scoped => "scoped",
+            TokenRole::Viewer => "viewer",
+            TokenRole::RecordEditor => "record-editor",
+            TokenRole::ZoneAdmin => "zone-admin",
+            TokenRole::Admin => "admin",
+        }
+    }
+
+    /// Whether this role carries every right of `required`. `Scoped` rights
+    /// come from policies, so no other role includes them and they include
+    /// nothing but themselves.
+    pub fn includes(&self, required: TokenRole) -> bool {
+        match (self, required) {
+            (TokenRole::Scoped, required) => required == TokenRole::Scoped,
+            (_, TokenRole::Scoped) => false,
+            (held, required) => held.rank() >= required.rank(),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            TokenRole::Scoped => 0,
+            TokenRole::Viewer => 1,
+            TokenRole::RecordEditor => 2,
+            TokenRole::ZoneAdmin => 3,
+            TokenRole::Admin => 4,
+        }
+    }
+}
+
+impl std::fmt::Display for TokenRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TokenRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "scoped" => Ok(TokenRole::Scoped),
+            "viewer" => Ok(TokenRole::Viewer),
+            "record-editor" => Ok(TokenRole::RecordEditor),
+            "zone-admin" => Ok(TokenRole::ZoneAdmin),
+            "admin" => Ok(TokenRole::Admin),
+            _ => Err(format!(
+                "unsupported token role '{}' (expected scoped, viewer, record-editor, zone-admin or admin)",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for TokenRole {
+    type Error = String;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 /// An API authentication token and its metadata.
 #[derive(Debug, PartialEq, Eq, Clone, FromRow)]
 pub struct ApiToken {
@@ -9,10 +92,50 @@ pub struct ApiToken {
     pub name: String,
     pub token: String,
     pub description: Option<String>,
-    /// Global tokens may manage every zone and the zone plane; scoped tokens
-    /// are limited to their `zone_token_policies` grants. Fixed at creation.
-    pub is_global: bool,
+    #[sqlx(try_from = "String")]
+    pub role: TokenRole,
     pub created_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>, // None means the token never expires
     pub last_used_at: Option<DateTime<Utc>>, // None until the token is first used
+    /// Hash of the secret a rotation replaced, still accepted until
+    /// `previous_token_expires_at`.
+    pub previous_token: Option<String>,
+    pub previous_token_expires_at: Option<DateTime<Utc>>,
+}
+
+impl ApiToken {
+    /// Global (admin) tokens may manage every zone and the zone plane
+    /// without policies.
+    pub fn is_global(&self) -> bool {
+        self.role == TokenRole::Admin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TokenRole;
+
+    #[test]
+    fn roles_include_the_roles_below_them() {
+        assert!(TokenRole::Admin.includes(TokenRole::ZoneAdmin));
+        assert!(TokenRole::ZoneAdmin.includes(TokenRole::RecordEditor));
+        assert!(TokenRole::RecordEditor.includes(TokenRole::Viewer));
+        assert!(TokenRole::Viewer.includes(TokenRole::Viewer));
+        assert!(!TokenRole::Viewer.includes(TokenRole::RecordEditor));
+        assert!(!TokenRole::ZoneAdmin.includes(TokenRole::Admin));
+    }
+
+    #[test]
+    fn scoped_is_outside_the_hierarchy() {
+        assert!(!TokenRole::Scoped.includes(TokenRole::Viewer));
+        assert!(!TokenRole::Admin.includes(TokenRole::Scoped));
+        assert!(TokenRole::Scoped.includes(TokenRole::Scoped));
+    }
+
+    #[test]
+    fn roles_parse_case_insensitively() {
+        assert_eq!("Zone-Admin".parse(), Ok(TokenRole::ZoneAdmin));
+        assert_eq!("record_editor".parse(), Ok(TokenRole::RecordEditor));
+        assert!("root".parse::<TokenRole>().is_err());
+    }
 }
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A named catalog zone (RFC 9432) served alongside the default
+/// `catalog.bind`. Zones are assigned to it through their `catalog`
+/// property, so one secondary pool can consume a subset of the zones.
+#[derive(Debug, PartialEq, Eq, Clone, FromRow)]
+pub struct Catalog {
+    pub id: i32,
+    /// Catalog zone name, stored lowercase without the trailing dot.
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One catalog property of a member zone. `name` is `catalog` (the named
+/// catalog listing the zone, absent for `catalog.bind`), an RFC 9432 member
+/// property (`group` or `coo`), or a custom `ext.<property>`.
+#[derive(Debug, PartialEq, Eq, Clone, FromRow)]
+pub struct ZoneCatalogProperty {
+    pub id: i32,
+    pub zone_id: i32,
+    pub name: String,
+    pub value: String,
+}
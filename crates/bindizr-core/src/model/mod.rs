@@ -1,8 +1,14 @@
 pub mod api_token;
+pub mod catalog;
 pub mod record;
 pub mod tsig_key;
+pub mod webhook;
 pub mod zone;
+pub mod zone_audit;
 pub mod zone_change;
+pub mod zone_notify_target;
 pub mod zone_snapshot;
+pub mod zone_template;
 pub mod zone_token_policy;
+pub mod zone_transfer_acl;
 pub mod zone_tsig_policy;
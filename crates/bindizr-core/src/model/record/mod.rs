@@ -1,4 +1,7 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    net::{Ipv4Addr, Ipv6Addr},
+};
 
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
@@ -6,8 +9,10 @@ use sqlx::FromRow;
 use crate::dns::{
     name::{OwnerName, ZoneName, to_fqdn_lowercase},
     record::{
-        ARecordValue, AaaaRecordValue, CnameRecordValue, MxRecordValue, NsRecordValue,
-        PtrRecordValue, SoaRecordValue, SrvRecordValue, TxtContent, TxtRecordValue,
+        ARecordValue, AaaaRecordValue, CaaRecordValue, CnameRecordValue, MxRecordValue,
+        NsRecordValue, PtrRecordValue, SoaRecordValue, SrvRecordValue, SshfpRecordValue,
+        SvcbRecordValue, TlsaRecordValue, TxtContent, TxtRecordValue, UnknownRecordValue,
+        decode_wire_name,
     },
 };
 
@@ -21,7 +26,7 @@ pub struct Record {
     pub record_type: RecordType,
     pub value: String,
     pub ttl: i32,              // TTL in seconds
-    pub priority: Option<i32>, // Priority (MX and SRV records)
+    pub priority: Option<i32>, // Priority (MX, SRV, SVCB and HTTPS records)
     pub created_at: DateTime<Utc>,
     pub zone_id: i32,
 }
@@ -74,7 +79,8 @@ impl RecordWithZone {
     }
 }
 
-/// Supported DNS resource record types.
+/// Supported DNS resource record types. Types bindizr does not model are
+/// carried as [`RecordType::Unknown`] with RFC 3597 opaque rdata.
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum RecordType {
@@ -87,6 +93,13 @@ pub enum RecordType {
     SOA,
     SRV,
     PTR,
+    CAA,
+    SVCB,
+    HTTPS,
+    TLSA,
+    SSHFP,
+    /// `TYPEnnn`: any other type code, stored as `\# <length> <hex>`.
+    Unknown(u16),
 }
 impl std::fmt::Display for RecordType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -114,15 +127,26 @@ impl std::str::FromStr for RecordType {
             "SOA" => Ok(RecordType::SOA),
             "SRV" => Ok(RecordType::SRV),
             "PTR" => Ok(RecordType::PTR),
-            _ => Err(format!("Invalid record type: {}", s)),
+            "CAA" => Ok(RecordType::CAA),
+            "SVCB" => Ok(RecordType::SVCB),
+            "HTTPS" => Ok(RecordType::HTTPS),
+            "TLSA" => Ok(RecordType::TLSA),
+            "SSHFP" => Ok(RecordType::SSHFP),
+            upper => upper
+                .strip_prefix("TYPE")
+                .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|digits| digits.parse::<u16>().ok())
+                .ok_or_else(|| format!("Invalid record type: {}", s))
+                .and_then(RecordType::from_type_code),
         }
     }
 }
 
 impl RecordType {
-    /// Return the record type's presentation-format mnemonic (e.g. `"A"`).
-    pub fn as_str(&self) -> &'static str {
-        match self {
+    /// Return the record type's presentation-format mnemonic (e.g. `"A"`), or
+    /// `TYPEnnn` for an unmodeled type.
+    pub fn as_str(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
             RecordType::A => "A",
             RecordType::AAAA => "AAAA",
             RecordType::CNAME => "CNAME",
@@ -132,14 +156,67 @@ impl RecordType {
             RecordType::SOA => "SOA",
             RecordType::SRV => "SRV",
             RecordType::PTR => "PTR",
+            RecordType::CAA => "CAA",
+            RecordType::SVCB => "SVCB",
+            RecordType::HTTPS => "HTTPS",
+            RecordType::TLSA => "TLSA",
+            RecordType::SSHFP => "SSHFP",
+            RecordType::Unknown(code) => return Cow::Owned(format!("TYPE{code}")),
+        })
+    }
+
+    /// The type for an RR type code: a modeled variant where there is one
+    /// (so `TYPE1` is `A`, per RFC 3597, Section 5), otherwise
+    /// [`RecordType::Unknown`]. Codes that cannot name stored data — reserved
+    /// 0 and 65535, OPT, and the meta and query types 128-255 — are refused.
+    pub fn from_type_code(code: u16) -> Result<Self, String> {
+        Ok(match code {
+            1 => RecordType::A,
+            2 => RecordType::NS,
+            5 => RecordType::CNAME,
+            6 => RecordType::SOA,
+            12 => RecordType::PTR,
+            15 => RecordType::MX,
+            16 => RecordType::TXT,
+            28 => RecordType::AAAA,
+            33 => RecordType::SRV,
+            44 => RecordType::SSHFP,
+            52 => RecordType::TLSA,
+            64 => RecordType::SVCB,
+            65 => RecordType::HTTPS,
+            257 => RecordType::CAA,
+            0 | 41 | 128..=255 | 65535 => {
+                return Err(format!("TYPE{code} cannot be stored as a record"));
+            }
+            other => RecordType::Unknown(other),
+        })
+    }
+
+    /// The RR type code.
+    pub fn type_code(&self) -> u16 {
+        match self {
+            RecordType::A => 1,
+            RecordType::NS => 2,
+            RecordType::CNAME => 5,
+            RecordType::SOA => 6,
+            RecordType::PTR => 12,
+            RecordType::MX => 15,
+            RecordType::TXT => 16,
+            RecordType::AAAA => 28,
+            RecordType::SRV => 33,
+            RecordType::SSHFP => 44,
+            RecordType::TLSA => 52,
+            RecordType::SVCB => 64,
+            RecordType::HTTPS => 65,
+            RecordType::CAA => 257,
+            RecordType::Unknown(code) => *code,
         }
     }
 
     /// Validate a stored value (and its priority column) for this record type.
     /// Errors are plain messages; callers map them to their own error kind.
     pub fn validate_value(&self, value: &str, priority: Option<i32>) -> Result<(), String> {
-        // Only MX and SRV encode a priority
-        if priority.is_some() && !matches!(self, RecordType::MX | RecordType::SRV) {
+        if priority.is_some() && !self.takes_priority() {
             return Err(format!("{} records do not take a priority", self));
         }
 
@@ -154,6 +231,13 @@ impl RecordType {
             RecordType::SOA => SoaRecordValue::parse(value)?.validate(),
             RecordType::SRV => SrvRecordValue::parse(value, priority)?.validate(),
             RecordType::PTR => PtrRecordValue::parse(value).map(|_| ()),
+            RecordType::CAA => CaaRecordValue::parse(value)?.validate(),
+            RecordType::SVCB | RecordType::HTTPS => {
+                SvcbRecordValue::parse(value, priority)?.validate()
+            }
+            RecordType::TLSA => TlsaRecordValue::parse(value)?.validate(),
+            RecordType::SSHFP => SshfpRecordValue::parse(value)?.validate(),
+            RecordType::Unknown(_) => UnknownRecordValue::parse(value).map(|_| ()),
         }
     }
 
@@ -201,6 +285,23 @@ impl RecordType {
             RecordType::PTR => PtrRecordValue::parse(value)
                 .map(|parsed| Cow::Owned(parsed.canonical()))
                 .unwrap_or_else(|_| Cow::Owned(to_fqdn_lowercase(value))),
+            RecordType::CAA => CaaRecordValue::parse(value)
+                .map(|parsed| Cow::Owned(parsed.canonical()))
+                .unwrap_or(Cow::Borrowed(value)),
+            RecordType::SVCB | RecordType::HTTPS => {
+                SvcbRecordValue::parse(value, fallback_priority)
+                    .map(|parsed| Cow::Owned(parsed.canonical()))
+                    .unwrap_or(Cow::Borrowed(value))
+            }
+            RecordType::TLSA => TlsaRecordValue::parse(value)
+                .map(|parsed| Cow::Owned(parsed.canonical()))
+                .unwrap_or(Cow::Borrowed(value)),
+            RecordType::SSHFP => SshfpRecordValue::parse(value)
+                .map(|parsed| Cow::Owned(parsed.canonical()))
+                .unwrap_or(Cow::Borrowed(value)),
+            RecordType::Unknown(_) => UnknownRecordValue::parse(value)
+                .map(|parsed| Cow::Owned(parsed.canonical()))
+                .unwrap_or(Cow::Borrowed(value)),
         }
     }
 
@@ -208,6 +309,9 @@ impl RecordType {
     /// record rows encode, so every entry path stores equal bytes. TXT takes
     /// presentation form; other TXT grammars go through [`TxtRecordValue`] directly.
     pub fn encoded_value(&self, value: &str, priority: Option<i32>) -> Result<String, String> {
+        if let Some((decoded, priority)) = self.decode_generic(value, priority)? {
+            return self.encoded_value(&decoded, priority);
+        }
         // TXT keeps raw bytes; every other type tolerates surrounding whitespace.
         let trimmed = value.trim();
         match self {
@@ -232,6 +336,55 @@ impl RecordType {
                 parsed.validate()?;
                 Ok(parsed.canonical())
             }
+            RecordType::CAA => {
+                let parsed = CaaRecordValue::parse(trimmed)?;
+                parsed.validate()?;
+                Ok(parsed.canonical())
+            }
+            RecordType::SVCB | RecordType::HTTPS => {
+                let parsed = SvcbRecordValue::parse(trimmed, priority)?;
+                parsed.validate()?;
+                Ok(parsed.encoded())
+            }
+            RecordType::TLSA => {
+                let parsed = TlsaRecordValue::parse(trimmed)?;
+                parsed.validate()?;
+                Ok(parsed.canonical())
+            }
+            RecordType::SSHFP => {
+                let parsed = SshfpRecordValue::parse(trimmed)?;
+                parsed.validate()?;
+                Ok(parsed.canonical())
+            }
+            RecordType::Unknown(_) => {
+                UnknownRecordValue::parse(trimmed).map(|parsed| parsed.canonical())
+            }
+        }
+    }
+
+    /// A modeled type's value given in the RFC 3597 generic form
+    /// (`\# <length> <hex>`), decoded into the value this type takes and its
+    /// priority, so it is checked and stored exactly as the presentation form
+    /// would be (RFC 3597, Section 5). `None` for any other value, and for
+    /// types bindizr does not model. A priority given alongside must match the
+    /// one carried in the rdata.
+    pub fn decode_generic(
+        &self,
+        value: &str,
+        priority: Option<i32>,
+    ) -> Result<Option<(String, Option<i32>)>, String> {
+        if matches!(self, RecordType::Unknown(_)) || !value.trim_start().starts_with(r"\#") {
+            return Ok(None);
+        }
+        let rdata = UnknownRecordValue::parse(value.trim())?.to_rdata();
+        let (decoded, wire_priority) = decode_modeled_rdata(self, &rdata)
+            .map_err(|e| format!("{self} generic record data is invalid: {e}"))?;
+
+        match (priority, wire_priority.map(i32::from)) {
+            (Some(given), Some(wire)) if given != wire => Err(format!(
+                "{self} priority {given} does not match the priority {wire} in the generic record data"
+            )),
+            (given, wire) => Ok(Some((decoded, wire.or(given)))),
         }
     }
 
@@ -249,6 +402,40 @@ impl RecordType {
         SrvRecordValue::wire_fields(value, priority)
     }
 
+    /// The wire rdata of a stored CAA value.
+    pub fn caa_wire_rdata(value: &str) -> Result<Vec<u8>, String> {
+        let parsed = CaaRecordValue::parse(value)?;
+        parsed.validate()?;
+        Ok(parsed.to_rdata())
+    }
+
+    /// The wire rdata of a stored SVCB or HTTPS value, with the SvcPriority
+    /// taken from the priority column.
+    pub fn svcb_wire_rdata(value: &str, priority: Option<i32>) -> Result<Vec<u8>, String> {
+        let parsed = SvcbRecordValue::parse(value, priority)?;
+        parsed.validate()?;
+        Ok(parsed.to_rdata())
+    }
+
+    /// The wire rdata of a stored TLSA value.
+    pub fn tlsa_wire_rdata(value: &str) -> Result<Vec<u8>, String> {
+        let parsed = TlsaRecordValue::parse(value)?;
+        parsed.validate()?;
+        Ok(parsed.to_rdata())
+    }
+
+    /// The wire rdata of a stored SSHFP value.
+    pub fn sshfp_wire_rdata(value: &str) -> Result<Vec<u8>, String> {
+        let parsed = SshfpRecordValue::parse(value)?;
+        parsed.validate()?;
+        Ok(parsed.to_rdata())
+    }
+
+    /// The wire rdata of a stored generic (`\# <length> <hex>`) value.
+    pub fn unknown_wire_rdata(value: &str) -> Result<Vec<u8>, String> {
+        UnknownRecordValue::parse(value).map(|parsed| parsed.to_rdata())
+    }
+
     /// Format a stored value of this record type for display.
     pub fn display_value(&self, value: &str) -> String {
         if *self == RecordType::TXT {
@@ -267,13 +454,21 @@ impl RecordType {
         }
     }
 
+    /// Whether this type keeps a priority in the priority column.
+    pub fn takes_priority(&self) -> bool {
+        matches!(
+            self,
+            RecordType::MX | RecordType::SRV | RecordType::SVCB | RecordType::HTTPS
+        )
+    }
+
     /// Whether this type's display form is a domain name.
     pub(crate) fn is_name_like(&self) -> bool {
         NAME_LIKE_RECORD_TYPES.contains(self)
     }
 
     /// Render a stored value plus its priority column as zone-file rdata:
-    /// MX/SRV carry the priority inline (default 10), TXT is quoted per
+    /// MX/SRV/SVCB/HTTPS carry the priority inline (default 10), TXT is quoted per
     /// character-string, and other types use their display form.
     pub fn presentation_rdata(&self, value: &str, priority: Option<i32>) -> String {
         match self {
@@ -282,7 +477,7 @@ impl RecordType {
                 // Not an encoded TXT value; quote it as a single character-string.
                 None => TxtRecordValue::to_quoted_charstr(value.as_bytes()),
             },
-            RecordType::MX | RecordType::SRV => {
+            _ if self.takes_priority() => {
                 format!("{} {}", priority.unwrap_or(10), self.display_value(value))
             }
             _ => self.display_value(value),
@@ -312,6 +507,93 @@ pub const EXTERNAL_DNS_RECORD_TYPES: &[RecordType] = &[
     RecordType::TXT,
 ];
 
+/// Decode wire rdata of a modeled type into the value column's form and, for
+/// the types that keep one, the priority.
+fn decode_modeled_rdata(
+    record_type: &RecordType,
+    rdata: &[u8],
+) -> Result<(String, Option<u16>), String> {
+    match record_type {
+        RecordType::A => {
+            let octets: [u8; 4] = rdata
+                .try_into()
+                .map_err(|_| format!("A rdata must be 4 bytes, not {}", rdata.len()))?;
+            Ok((Ipv4Addr::from(octets).to_string(), None))
+        }
+        RecordType::AAAA => {
+            let octets: [u8; 16] = rdata
+                .try_into()
+                .map_err(|_| format!("AAAA rdata must be 16 bytes, not {}", rdata.len()))?;
+            Ok((Ipv6Addr::from(octets).to_string(), None))
+        }
+        RecordType::CNAME | RecordType::NS | RecordType::PTR => Ok((
+            whole_wire_name(&format!("{record_type} target"), rdata)?,
+            None,
+        )),
+        RecordType::MX => {
+            let (preference, rest) = split_wire_u16("MX preference", rdata)?;
+            Ok((whole_wire_name("MX target", rest)?, Some(preference)))
+        }
+        RecordType::SRV => {
+            let (priority, rest) = split_wire_u16("SRV priority", rdata)?;
+            let (weight, rest) = split_wire_u16("SRV weight", rest)?;
+            let (port, rest) = split_wire_u16("SRV port", rest)?;
+            let target = whole_wire_name("SRV target", rest)?;
+            Ok((format!("{weight} {port} {target}"), Some(priority)))
+        }
+        RecordType::SOA => {
+            let (mname, used) = decode_wire_name("SOA mname", rdata)?;
+            let (rname, more) = decode_wire_name("SOA rname", &rdata[used..])?;
+            let counters = &rdata[used + more..];
+            if counters.len() != 20 {
+                return Err("SOA rdata must end in five 32-bit counters".to_string());
+            }
+            let counters = counters
+                .chunks_exact(4)
+                .map(|field| {
+                    u32::from_be_bytes([field[0], field[1], field[2], field[3]]).to_string()
+                })
+                .collect::<Vec<_>>();
+            Ok((format!("{mname} {rname} {}", counters.join(" ")), None))
+        }
+        RecordType::TXT => {
+            // One or more length-prefixed character-strings, filling the rdata.
+            let mut pos = 0usize;
+            while pos < rdata.len() {
+                pos += 1 + usize::from(rdata[pos]);
+            }
+            if rdata.is_empty() || pos != rdata.len() {
+                return Err("TXT rdata must be one or more character-strings".to_string());
+            }
+            Ok((TxtRecordValue::from_rdata(rdata).to_presentation(), None))
+        }
+        RecordType::CAA => Ok((CaaRecordValue::from_rdata(rdata)?.canonical(), None)),
+        RecordType::TLSA => Ok((TlsaRecordValue::from_rdata(rdata)?.canonical(), None)),
+        RecordType::SSHFP => Ok((SshfpRecordValue::from_rdata(rdata)?.canonical(), None)),
+        RecordType::SVCB | RecordType::HTTPS => {
+            let parsed = SvcbRecordValue::from_rdata(rdata)?;
+            Ok((parsed.encoded(), Some(parsed.priority())))
+        }
+        RecordType::Unknown(_) => Ok((UnknownRecordValue::from_rdata(rdata)?.canonical(), None)),
+    }
+}
+
+fn split_wire_u16<'a>(field: &str, data: &'a [u8]) -> Result<(u16, &'a [u8]), String> {
+    match data {
+        [hi, lo, rest @ ..] => Ok((u16::from_be_bytes([*hi, *lo]), rest)),
+        _ => Err(format!("{field} is truncated")),
+    }
+}
+
+/// A wire name that must be all of `data`.
+fn whole_wire_name(field: &str, data: &[u8]) -> Result<String, String> {
+    let (name, used) = decode_wire_name(field, data)?;
+    if used != data.len() {
+        return Err(format!("{field} is followed by trailing data"));
+    }
+    Ok(name)
+}
+
 // The priority lives in its own column, never in the value: MX stores `target`
 // and SRV `weight port target`.
 const MX_FIELD_COUNTS: &[usize] = &[1];
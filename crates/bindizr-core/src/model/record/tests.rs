@@ -192,3 +192,165 @@ fn display_value_leaves_wrong_field_count_unchanged() {
         );
     }
 }
+
+#[test]
+fn caa_values_encode_and_compare_canonically() {
+    assert_eq!(
+        RecordType::CAA
+            .encoded_value("  0 ISSUE letsencrypt.org ", None)
+            .as_deref(),
+        Ok("0 issue \"letsencrypt.org\"")
+    );
+    assert!(RecordType::CAA.values_equal(
+        "0 Issue \"letsencrypt.org\"",
+        None,
+        "0 issue letsencrypt.org",
+        None
+    ));
+    assert_eq!(
+        RecordType::CAA.presentation_rdata("0 iodef \"mailto:sec@example.com\"", None),
+        "0 iodef \"mailto:sec@example.com\""
+    );
+    assert!(
+        RecordType::CAA
+            .validate_value("0 issue \"letsencrypt.org\"", Some(10))
+            .is_err()
+    );
+}
+
+#[test]
+fn https_values_keep_priority_in_its_column() {
+    assert_eq!(
+        RecordType::HTTPS
+            .encoded_value(" svc.example.com port=443 alpn=h2 ", Some(1))
+            .as_deref(),
+        Ok("svc.example.com. alpn=\"h2\" port=443")
+    );
+    assert!(RecordType::SVCB.values_equal(
+        "svc.example.com. alpn=\"h2\"",
+        None,
+        "SVC.example.com ALPN=h2",
+        Some(10)
+    ));
+    assert_eq!(
+        RecordType::HTTPS.presentation_rdata("svc.example.com. alpn=\"h2\"", Some(1)),
+        "1 svc.example.com. alpn=\"h2\""
+    );
+    assert!(RecordType::HTTPS.takes_priority());
+    assert!(
+        RecordType::HTTPS
+            .validate_value("svc.example.com. alpn=h2", Some(0))
+            .is_err()
+    );
+}
+
+#[test]
+fn tlsa_and_sshfp_values_encode_as_lowercase_hex() {
+    let digest = "8CB0FC6C527506A053F4F14C8464BEBBD6DEDE2738D11468DD953D7D6A3021F1";
+    assert_eq!(
+        RecordType::TLSA
+            .encoded_value(&format!("3 1 1 {} {}", &digest[..32], &digest[32..]), None)
+            .unwrap(),
+        format!("3 1 1 {}", digest.to_lowercase())
+    );
+    assert!(RecordType::SSHFP.values_equal(
+        "4 2 ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789",
+        None,
+        "4 2 abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789",
+        None
+    ));
+    assert!(RecordType::SSHFP.encoded_value("4 2 abcd", None).is_err());
+    assert!(
+        RecordType::TLSA
+            .validate_value(&format!("3 1 1 {digest}"), Some(10))
+            .is_err()
+    );
+}
+
+#[test]
+fn generic_types_parse_and_encode_per_rfc_3597() {
+    assert_eq!("type65280".parse(), Ok(RecordType::Unknown(65280)));
+    assert_eq!("TYPE1".parse(), Ok(RecordType::A));
+    assert_eq!(RecordType::Unknown(65280).as_str(), "TYPE65280");
+    assert_eq!(RecordType::CAA.type_code(), 257);
+    for refused in ["TYPE0", "TYPE41", "TYPE252", "TYPE65535", "TYPE", "TYPE+1"] {
+        assert!(
+            refused.parse::<RecordType>().is_err(),
+            "{refused} was accepted"
+        );
+    }
+
+    assert_eq!(
+        RecordType::Unknown(65280)
+            .encoded_value(r" \# 3 AB CDEF ", None)
+            .as_deref(),
+        Ok(r"\# 3 abcdef")
+    );
+    assert_eq!(
+        RecordType::Unknown(65280).presentation_rdata(r"\# 3 abcdef", None),
+        r"\# 3 abcdef"
+    );
+    assert!(
+        RecordType::Unknown(65280)
+            .validate_value("10.0.0.1", None)
+            .is_err()
+    );
+}
+
+#[test]
+fn generic_rdata_for_modeled_types_is_stored_in_presentation_form() {
+    // RFC 3597, Section 5: `TYPE1 \# 4 c0000201` is the A record 192.0.2.1.
+    let a: RecordType = "TYPE1".parse().unwrap();
+    assert_eq!(
+        a.encoded_value(r"\# 4 c0000201", None).as_deref(),
+        Ok("192.0.2.1")
+    );
+    assert_eq!(
+        RecordType::CNAME
+            .encoded_value(r"\# 17 0377777707 4578616d706c6503636f6d00", None)
+            .as_deref(),
+        Ok("www.example.com.")
+    );
+    assert_eq!(
+        RecordType::TXT
+            .encoded_value(r"\# 6 05 68656c6c6f", None)
+            .ok(),
+        RecordType::TXT.encoded_value(r#""hello""#, None).ok()
+    );
+
+    // The preference comes out of the rdata and into the priority column.
+    let mx = r"\# 16 0014 046d61696c076578616d706c6500";
+    assert_eq!(
+        RecordType::MX.decode_generic(mx, None),
+        Ok(Some(("mail.example.".to_string(), Some(20))))
+    );
+    assert_eq!(
+        RecordType::MX.decode_generic(mx, Some(20)),
+        Ok(Some(("mail.example.".to_string(), Some(20))))
+    );
+    assert!(RecordType::MX.decode_generic(mx, Some(10)).is_err());
+    assert_eq!(
+        RecordType::MX.encoded_value(mx, None).as_deref(),
+        Ok("mail.example.")
+    );
+
+    // Only the generic form is decoded, and the rdata must fit the type.
+    assert_eq!(RecordType::A.decode_generic("192.0.2.1", None), Ok(None));
+    assert_eq!(
+        RecordType::Unknown(65280).decode_generic(r"\# 1 00", None),
+        Ok(None)
+    );
+    for (record_type, value) in [
+        (RecordType::A, r"\# 3 c00002"),
+        (RecordType::AAAA, r"\# 4 c0000201"),
+        (RecordType::CNAME, r"\# 4 016100ff"),
+        (RecordType::CNAME, r"\# 5 03612e6200"),
+        (RecordType::TXT, r"\# 2 0561"),
+        (RecordType::MX, r"\# 1 00"),
+    ] {
+        assert!(
+            record_type.encoded_value(value, None).is_err(),
+            "{record_type} {value} was accepted"
+        );
+    }
+}
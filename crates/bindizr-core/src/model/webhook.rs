@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// An outbound webhook subscription. A zone-scoped webhook hears about its
+/// zone only; one without a zone hears about every zone.
+#[derive(Debug, PartialEq, Eq, Clone, FromRow)]
+pub struct Webhook {
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+    /// HMAC-SHA256 key the payloads are signed with. Stored as given, since
+    /// signing needs it back.
+    pub secret: String,
+    pub zone_id: Option<i32>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One queued notification of a zone change to one webhook. Rows are
+/// written in the transaction that made the change and drained by the
+/// delivery worker, so a change is never announced without being committed.
+#[derive(Debug, PartialEq, Eq, Clone, FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i32,
+    /// `zone.created`, `zone.updated` or `zone.deleted`.
+    pub event: String,
+    pub zone_name: String,
+    /// The zone's serial before the change; `None` for a new zone.
+    pub from_serial: Option<i32>,
+    /// The zone's serial after the change; `None` for a deleted zone.
+    pub to_serial: Option<i32>,
+    /// The event's JSON body, built in the transaction that queued the
+    /// delivery. The delivery id and webhook name are added when it is sent.
+    pub payload: Option<String>,
+    /// `pending`, `delivered` or `failed`.
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
@@ -5,10 +5,64 @@ use crate::{
     dns::{
         name::{OwnerName, ZoneName, to_fqdn},
         record::SoaMailbox,
+        serial,
     },
     model::record::{Record, RecordType},
 };
 
+/// How a zone's SOA serial advances on each change. Whatever the scheme, a
+/// new serial is always later than the current one in RFC 1982 terms: when
+/// the scheme's value is not, the serial steps by one instead.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SerialPolicy {
+    /// Add one per change.
+    #[default]
+    Increment,
+    /// `YYYYMMDDnn`: today's date followed by a two-digit change counter.
+    Date,
+    /// Seconds since the Unix epoch at the time of the change.
+    UnixTime,
+}
+
+impl SerialPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SerialPolicy::Increment => "increment",
+            SerialPolicy::Date => "date",
+            SerialPolicy::UnixTime => "unixtime",
+        }
+    }
+}
+
+impl std::fmt::Display for SerialPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for SerialPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "increment" => Ok(SerialPolicy::Increment),
+            "date" | "yyyymmddnn" => Ok(SerialPolicy::Date),
+            "unixtime" => Ok(SerialPolicy::UnixTime),
+            _ => Err(format!(
+                "unsupported serial policy '{}' (expected increment, date (YYYYMMDDnn) or unixtime)",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for SerialPolicy {
+    type Error = String;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 /// Zone metadata used to generate the SOA and NS records.
 #[derive(Debug, PartialEq, Eq, Clone, FromRow)]
 pub struct Zone {
@@ -17,8 +71,10 @@ pub struct Zone {
     pub name: ZoneName,
     pub primary_ns: String,
     pub admin_email: String,
-    pub ttl: i32,         // Default TTL in seconds
-    pub serial: i32,      // SOA serial number
+    pub ttl: i32,    // Default TTL in seconds
+    pub serial: i32, // SOA serial number, stored form (see dns::serial)
+    #[sqlx(try_from = "String")]
+    pub serial_policy: SerialPolicy,
     pub refresh: i32,     // SOA refresh period in seconds
     pub retry: i32,       // SOA retry period in seconds
     pub expire: i32,      // SOA expire period in seconds
@@ -64,7 +120,7 @@ impl Zone {
             "{} {} {} {} {} {} {}",
             self.primary_ns,
             self.soa_mailbox()?,
-            self.serial,
+            serial::to_wire(self.serial),
             self.refresh,
             self.retry,
             self.expire,
@@ -1,6 +1,6 @@
 use chrono::Utc;
 
-use super::Zone;
+use super::{SerialPolicy, Zone};
 use crate::{
     dns::name::{OwnerName, ZoneName},
     model::record::RecordType,
@@ -14,6 +14,7 @@ fn test_zone() -> Zone {
         admin_email: "hostmaster@example.com".to_string(),
         ttl: 3600,
         serial: 1,
+        serial_policy: SerialPolicy::Increment,
         refresh: 7200,
         retry: 3600,
         expire: 604800,
@@ -62,3 +63,20 @@ fn primary_ns_record_builds_the_apex_row_for_the_zone() {
     assert_eq!(record.zone_id, zone.id);
     assert_eq!(record.priority, None);
 }
+
+#[test]
+fn serial_policy_parses_its_names_and_the_date_format_alias() {
+    assert_eq!("increment".parse(), Ok(SerialPolicy::Increment));
+    assert_eq!("DATE".parse(), Ok(SerialPolicy::Date));
+    assert_eq!("YYYYMMDDnn".parse(), Ok(SerialPolicy::Date));
+    assert_eq!("unixtime".parse(), Ok(SerialPolicy::UnixTime));
+    assert!("epoch".parse::<SerialPolicy>().is_err());
+}
+
+#[test]
+fn soa_rdata_writes_the_wire_serial() {
+    let mut zone = test_zone();
+    zone.serial = -1;
+
+    assert!(zone.soa_rdata().unwrap().contains(" 4294967295 "));
+}
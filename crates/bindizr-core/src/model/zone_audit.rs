@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+use super::zone_snapshot::ChangeAttribution;
+
+/// One audit log line: a serial of a zone, what happened to the zone, and who
+/// did it. Rows name their zone rather than reference it, so they outlive
+/// both the zone and its pruned history.
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub struct ZoneAuditEntry {
+    pub zone_name: String,
+    /// The serial the change produced; for a deletion, the zone's last one.
+    pub serial: i32,
+    /// `created`, `updated` or `deleted`; empty for entries copied from the
+    /// zone history when the audit log moved to its own table.
+    pub action: Option<String>,
+    #[sqlx(flatten)]
+    pub attribution: ChangeAttribution,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Whether a zone's NOTIFY target replaces the global secondary list for the
+/// zone or is notified on top of it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NotifyTargetKind {
+    /// Once a zone has one, its `notify` targets stand in for the global
+    /// `dns.secondary_addrs` servers.
+    Notify,
+    /// Notified in addition to whichever list applies, like BIND's
+    /// `also-notify`.
+    AlsoNotify,
+}
+
+impl NotifyTargetKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotifyTargetKind::Notify => "notify",
+            NotifyTargetKind::AlsoNotify => "also-notify",
+        }
+    }
+}
+
+impl std::fmt::Display for NotifyTargetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for NotifyTargetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "notify" => Ok(NotifyTargetKind::Notify),
+            "also-notify" | "also_notify" => Ok(NotifyTargetKind::AlsoNotify),
+            _ => Err(format!(
+                "unsupported NOTIFY target kind '{}' (expected notify or also-notify)",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for NotifyTargetKind {
+    type Error = String;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// One server a zone sends NOTIFY to and whose serial is probed for it.
+///
+/// `address` is `host[:port]` or an IP address, port 53 by default. With
+/// `tsig_key_name`, NOTIFYs to it are signed with that key.
+#[derive(Debug, PartialEq, Eq, Clone, FromRow)]
+pub struct ZoneNotifyTarget {
+    pub id: i32,
+    pub zone_id: i32,
+    #[sqlx(try_from = "String")]
+    pub kind: NotifyTargetKind,
+    pub address: String,
+    pub tsig_key_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -14,5 +14,21 @@ pub struct ZoneSnapshot {
     pub retry: i32,
     pub expire: i32,
     pub minimum_ttl: i32,
+    #[sqlx(flatten)]
+    pub attribution: ChangeAttribution,
     pub created_at: DateTime<Utc>,
 }
+
+/// Who made the change that produced a serial, and through which front end.
+/// Every field is empty for serials recorded before attribution existed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, FromRow)]
+pub struct ChangeAttribution {
+    /// `token:<name>`, `uid:<n>`, `tsig:<key>`, `anonymous`, `unsigned` or
+    /// `bindizr` for changes the daemon makes on its own.
+    pub actor: Option<String>,
+    pub tsig_key_name: Option<String>,
+    pub source_address: Option<String>,
+    /// `http`, `external-dns`, `socket`, `nsupdate` or `internal`.
+    pub transport: Option<String>,
+    pub request_id: Option<String>,
+}
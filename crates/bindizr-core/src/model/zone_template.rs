@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// A named set of records new zones can be created from. Record names and
+/// values may hold `{{variable}}` placeholders, filled in per zone.
+#[derive(Debug, PartialEq, Eq, Clone, FromRow)]
+pub struct ZoneTemplate {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    /// The template records as a JSON array, in the record request shape.
+    pub records: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The link between a zone and the template it was created from, so later
+/// template changes can be carried over to it.
+#[derive(Debug, PartialEq, Eq, Clone, FromRow)]
+pub struct ZoneTemplateLink {
+    pub id: i32,
+    pub zone_id: i32,
+    pub template_id: i32,
+    /// The variables the zone was created with, as a JSON object.
+    pub variables: String,
+    /// The template records (unrendered JSON) last applied to the zone: the
+    /// baseline a template change is diffed against.
+    pub applied_records: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -2,8 +2,9 @@ use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 
 /// Grants one API token record-plane rights over part of one zone, the HTTP
-/// twin of [`super::zone_tsig_policy::ZoneTsigPolicy`]. Global tokens
-/// (`ApiToken::is_global`) bypass policies entirely and hold no rows here.
+/// twin of [`super::zone_tsig_policy::ZoneTsigPolicy`]. Only
+/// `TokenRole::Scoped` tokens hold rows here; every other role spans all
+/// zones without policies.
 ///
 /// `record_name_pattern` and `record_types` take the same syntax as a TSIG
 /// policy's.
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+
+/// Whether a matching transfer ACL entry admits or refuses the client.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TransferAclAction {
+    Allow,
+    Deny,
+}
+
+impl TransferAclAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferAclAction::Allow => "allow",
+            TransferAclAction::Deny => "deny",
+        }
+    }
+}
+
+impl std::fmt::Display for TransferAclAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for TransferAclAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "allow" => Ok(TransferAclAction::Allow),
+            "deny" => Ok(TransferAclAction::Deny),
+            _ => Err(format!(
+                "unsupported transfer ACL action '{}' (expected allow or deny)",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for TransferAclAction {
+    type Error = String;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// One entry of a zone's own transfer ACL. A zone with entries is governed
+/// by them alone instead of the global `dns.secondary_addrs` list.
+///
+/// `address` is an IP address or a CIDR prefix. An allow entry with
+/// `tsig_key_name` admits a matching client only when the request is signed
+/// with that key; deny entries carry no key and win over any allow.
+#[derive(Debug, PartialEq, Eq, Clone, FromRow)]
+pub struct ZoneTransferAcl {
+    pub id: i32,
+    pub zone_id: i32,
+    #[sqlx(try_from = "String")]
+    pub action: TransferAclAction,
+    pub address: String,
+    pub tsig_key_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
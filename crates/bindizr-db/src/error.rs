@@ -22,6 +22,10 @@ pub enum DatabaseError {
 
     #[error("Pool error: {0}")]
     PoolError(String),
+
+    /// The database schema does not match this binary's migrations.
+    #[error("Schema version mismatch: {0}")]
+    SchemaMismatch(String),
 }
 
 impl DatabaseError {
@@ -9,13 +9,15 @@ use sqlx::{
 };
 
 pub mod error;
+mod migration;
 pub mod repository;
 mod schema;
 mod utils;
 
 pub use bindizr_core::model;
-pub(crate) use bindizr_core::{config, log_error, log_info};
+pub(crate) use bindizr_core::{config, log_info};
 use error::DatabaseError;
+pub use migration::{MigrationState, SCHEMA_VERSION, SchemaStatus};
 
 static DATABASE_POOL: OnceLock<DatabasePool> = OnceLock::new();
 static INITIALIZE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
@@ -28,14 +30,6 @@ pub(crate) enum DatabasePool {
     SQLite(Pool<Sqlite>),
 }
 
-/// Supported database backend types.
-#[derive(Debug, Clone)]
-pub(crate) enum DatabaseType {
-    MySQL,
-    PostgreSQL,
-    SQLite,
-}
-
 /// Initialize the global database pool from configuration. Idempotent.
 pub async fn initialize() -> Result<(), DatabaseError> {
     if is_initialized() {
@@ -48,26 +42,9 @@ pub async fn initialize() -> Result<(), DatabaseError> {
         return Ok(());
     }
 
-    let bindizr_config = config::get_bindizr_config();
-
-    let database_type = match bindizr_config.database.database_type {
-        config::DatabaseType::Mysql => DatabaseType::MySQL,
-        config::DatabaseType::Postgresql => DatabaseType::PostgreSQL,
-        config::DatabaseType::Sqlite => DatabaseType::SQLite,
-    };
-
-    let database_url = match database_type {
-        DatabaseType::MySQL => bindizr_config.database.mysql.server_url.clone(),
-        DatabaseType::PostgreSQL => bindizr_config.database.postgresql.server_url.clone(),
-        DatabaseType::SQLite => utils::to_sqlite_url(&bindizr_config.database.sqlite.file_path)
-            .map_err(DatabaseError::PoolError)?,
-    };
-
-    let pool = match database_type {
-        DatabaseType::MySQL => DatabasePool::new_mysql(&database_url).await?,
-        DatabaseType::PostgreSQL => DatabasePool::new_postgres(&database_url).await?,
-        DatabaseType::SQLite => DatabasePool::new_sqlite(&database_url).await?,
-    };
+    let database_config = &config::get_bindizr_config().database;
+    let pool = DatabasePool::connect(database_config).await?;
+    pool.prepare_schema(database_config.auto_migrate).await?;
 
     // Cannot fail: the pool is set only here, under INITIALIZE_LOCK.
     DATABASE_POOL
@@ -83,6 +60,27 @@ fn is_initialized() -> bool {
     DATABASE_POOL.get().is_some()
 }
 
+/// Apply the pending schema migrations to the database `database_config`
+/// names, without starting anything else. Returns the migrations applied.
+pub async fn migrate(
+    database_config: &config::DatabaseConfig,
+) -> Result<Vec<MigrationState>, DatabaseError> {
+    let pool = DatabasePool::connect(database_config).await?;
+    let result = pool.migrate().await;
+    pool.close().await;
+    result
+}
+
+/// Compare the schema of the database `database_config` names to this binary.
+pub async fn schema_status(
+    database_config: &config::DatabaseConfig,
+) -> Result<SchemaStatus, DatabaseError> {
+    let pool = DatabasePool::connect(database_config).await?;
+    let result = pool.schema_status().await;
+    pool.close().await;
+    result
+}
+
 /// Return the global database pool, panicking if not yet initialized.
 pub(crate) fn get_pool() -> &'static DatabasePool {
     DATABASE_POOL.get().expect("Database pool not initialized")
@@ -98,7 +96,35 @@ fn networked_pool_max_connections() -> u32 {
 }
 
 impl DatabasePool {
-    /// Connect to MySQL, create tables, and return the pool.
+    /// Connect to the backend `database_config` selects. The schema is left
+    /// as it is; see [`DatabasePool::prepare_schema`].
+    pub(crate) async fn connect(
+        database_config: &config::DatabaseConfig,
+    ) -> Result<Self, DatabaseError> {
+        match database_config.database_type {
+            config::DatabaseType::Mysql => {
+                DatabasePool::new_mysql(&database_config.mysql.server_url).await
+            }
+            config::DatabaseType::Postgresql => {
+                DatabasePool::new_postgres(&database_config.postgresql.server_url).await
+            }
+            config::DatabaseType::Sqlite => {
+                let url = utils::to_sqlite_url(&database_config.sqlite.file_path)
+                    .map_err(DatabaseError::PoolError)?;
+                DatabasePool::new_sqlite(&url).await
+            }
+        }
+    }
+
+    async fn close(&self) {
+        match self {
+            DatabasePool::MySQL(pool) => pool.close().await,
+            DatabasePool::PostgreSQL(pool) => pool.close().await,
+            DatabasePool::SQLite(pool) => pool.close().await,
+        }
+    }
+
+    /// Connect to MySQL and return the pool.
     pub(crate) async fn new_mysql(url: &str) -> Result<Self, DatabaseError> {
         let pool = MySqlPoolOptions::new()
             .max_connections(networked_pool_max_connections())
@@ -118,16 +144,10 @@ impl DatabasePool {
                 DatabaseError::PoolError(format!("Failed to create MySQL database pool: {}", e))
             })?;
 
-        let database_pool = DatabasePool::MySQL(pool);
-        database_pool
-            .create_tables()
-            .await
-            .map_err(DatabaseError::QueryFailed)?;
-
-        Ok(database_pool)
+        Ok(DatabasePool::MySQL(pool))
     }
 
-    /// Connect to PostgreSQL, create tables, and return the pool.
+    /// Connect to PostgreSQL and return the pool.
     pub(crate) async fn new_postgres(url: &str) -> Result<Self, DatabaseError> {
         let pool = PgPoolOptions::new()
             .max_connections(networked_pool_max_connections())
@@ -151,15 +171,9 @@ impl DatabasePool {
                 ))
             })?;
 
-        let database_pool = DatabasePool::PostgreSQL(pool);
-        database_pool
-            .create_tables()
-            .await
-            .map_err(DatabaseError::QueryFailed)?;
-
-        Ok(database_pool)
+        Ok(DatabasePool::PostgreSQL(pool))
     }
-    /// Connect to SQLite, create tables, and return the pool.
+    /// Connect to SQLite and return the pool.
     pub(crate) async fn new_sqlite(url: &str) -> Result<Self, DatabaseError> {
         let pool = SqlitePoolOptions::new()
             .after_connect(|conn, _| {
@@ -177,55 +191,7 @@ impl DatabasePool {
                 DatabaseError::PoolError(format!("Failed to create SQLite database pool: {}", e))
             })?;
 
-        let database_pool = DatabasePool::SQLite(pool);
-        database_pool
-            .create_tables()
-            .await
-            .map_err(DatabaseError::QueryFailed)?;
-
-        Ok(database_pool)
-    }
-
-    async fn create_tables(&self) -> Result<(), String> {
-        match self {
-            DatabasePool::MySQL(pool) => {
-                let mut conn = pool.acquire().await.map_err(|e| {
-                    log_error!("Failed to acquire MySQL connection: {}", e);
-                    e.to_string()
-                })?;
-                for query in schema::mysql_table_creation_queries() {
-                    sqlx::query(query).execute(&mut *conn).await.map_err(|e| {
-                        log_error!("Failed to execute query '{}': {}", query, e);
-                        e.to_string()
-                    })?;
-                }
-            }
-            DatabasePool::PostgreSQL(pool) => {
-                let mut conn = pool.acquire().await.map_err(|e| {
-                    log_error!("Failed to acquire PostgreSQL connection: {}", e);
-                    e.to_string()
-                })?;
-                for query in schema::postgres_table_creation_queries() {
-                    sqlx::query(query).execute(&mut *conn).await.map_err(|e| {
-                        log_error!("Failed to execute query '{}': {}", query, e);
-                        e.to_string()
-                    })?;
-                }
-            }
-            DatabasePool::SQLite(pool) => {
-                let mut conn = pool.acquire().await.map_err(|e| {
-                    log_error!("Failed to acquire SQLite connection: {}", e);
-                    e.to_string()
-                })?;
-                for query in schema::sqlite_table_creation_queries() {
-                    sqlx::query(query).execute(&mut *conn).await.map_err(|e| {
-                        log_error!("Failed to execute query '{}': {}", query, e);
-                        e.to_string()
-                    })?;
-                }
-            }
-        }
-        Ok(())
+        Ok(DatabasePool::SQLite(pool))
     }
 }
 
@@ -258,6 +224,36 @@ pub fn get_zone_token_policy_repository() -> Box<dyn repository::ZoneTokenPolicy
     repository::RepositoryFactory::create_zone_token_policy_repository(pool)
 }
 
+/// Return a zone transfer ACL repository backed by the global pool.
+pub fn get_zone_transfer_acl_repository() -> Box<dyn repository::ZoneTransferAclRepository> {
+    let pool = get_pool();
+    repository::RepositoryFactory::create_zone_transfer_acl_repository(pool)
+}
+
+/// Return a zone NOTIFY target repository backed by the global pool.
+pub fn get_zone_notify_target_repository() -> Box<dyn repository::ZoneNotifyTargetRepository> {
+    let pool = get_pool();
+    repository::RepositoryFactory::create_zone_notify_target_repository(pool)
+}
+
+/// Return a catalog repository backed by the global pool.
+pub fn get_catalog_repository() -> Box<dyn repository::CatalogRepository> {
+    let pool = get_pool();
+    repository::RepositoryFactory::create_catalog_repository(pool)
+}
+
+/// Return a zone template repository backed by the global pool.
+pub fn get_zone_template_repository() -> Box<dyn repository::ZoneTemplateRepository> {
+    let pool = get_pool();
+    repository::RepositoryFactory::create_zone_template_repository(pool)
+}
+
+/// Return a webhook repository backed by the global pool.
+pub fn get_webhook_repository() -> Box<dyn repository::WebhookRepository> {
+    let pool = get_pool();
+    repository::RepositoryFactory::create_webhook_repository(pool)
+}
+
 /// Return an API token repository backed by the global pool.
 pub fn get_api_token_repository() -> Box<dyn repository::ApiTokenRepository> {
     let pool = get_pool();
@@ -276,6 +272,12 @@ pub fn get_zone_snapshot_repository() -> Box<dyn repository::ZoneSnapshotReposit
     repository::RepositoryFactory::create_zone_snapshot_repository(pool)
 }
 
+/// Return an audit log repository backed by the global pool.
+pub fn get_audit_log_repository() -> Box<dyn repository::AuditLogRepository> {
+    let pool = get_pool();
+    repository::RepositoryFactory::create_audit_log_repository(pool)
+}
+
 /// Return a catalog zone state repository backed by the global pool.
 pub fn get_catalog_zone_state_repository() -> Box<dyn repository::CatalogZoneStateRepository> {
     let pool = get_pool();
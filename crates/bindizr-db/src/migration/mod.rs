@@ -0,0 +1,465 @@
+//! Versioned schema migrations. `schema_migrations` records every version a
+//! database has applied; startup applies the pending ones, and refuses to run
+//! against a database that a newer bindizr has already migrated further.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use sqlx::MySqlConnection;
+
+use crate::{
+    DatabasePool,
+    error::DatabaseError,
+    log_info,
+    schema::{self, MysqlObject, MysqlStatement},
+};
+
+/// One schema change, with its DDL for every backend.
+struct Migration {
+    version: i32,
+    description: &'static str,
+    mysql: fn() -> Vec<MysqlStatement>,
+    postgres: fn() -> Vec<&'static str>,
+    sqlite: fn() -> Vec<&'static str>,
+}
+
+/// Every migration in the order it applies. Append only: a released version
+/// is never edited or renumbered, since databases already record it.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "initial schema",
+        mysql: schema::mysql_table_creation_queries,
+        postgres: schema::postgres_table_creation_queries,
+        sqlite: schema::sqlite_table_creation_queries,
+    },
+    Migration {
+        version: 2,
+        description: "zone transfer ACLs",
+        mysql: schema::mysql_zone_transfer_acl_queries,
+        postgres: schema::postgres_zone_transfer_acl_queries,
+        sqlite: schema::sqlite_zone_transfer_acl_queries,
+    },
+    Migration {
+        version: 3,
+        description: "zone change attribution",
+        mysql: schema::mysql_change_attribution_queries,
+        postgres: schema::postgres_change_attribution_queries,
+        sqlite: schema::sqlite_change_attribution_queries,
+    },
+    Migration {
+        version: 4,
+        description: "zone serial policy",
+        mysql: schema::mysql_serial_policy_queries,
+        postgres: schema::postgres_serial_policy_queries,
+        sqlite: schema::sqlite_serial_policy_queries,
+    },
+    Migration {
+        version: 5,
+        description: "API token rotation",
+        mysql: schema::mysql_token_rotation_queries,
+        postgres: schema::postgres_token_rotation_queries,
+        sqlite: schema::sqlite_token_rotation_queries,
+    },
+    Migration {
+        version: 6,
+        description: "API token roles",
+        mysql: schema::mysql_token_role_queries,
+        postgres: schema::postgres_token_role_queries,
+        sqlite: schema::sqlite_token_role_queries,
+    },
+    Migration {
+        version: 7,
+        description: "catalog zones",
+        mysql: schema::mysql_catalog_queries,
+        postgres: schema::postgres_catalog_queries,
+        sqlite: schema::sqlite_catalog_queries,
+    },
+    Migration {
+        version: 8,
+        description: "webhooks",
+        mysql: schema::mysql_webhook_queries,
+        postgres: schema::postgres_webhook_queries,
+        sqlite: schema::sqlite_webhook_queries,
+    },
+    Migration {
+        version: 9,
+        description: "zone NOTIFY targets",
+        mysql: schema::mysql_zone_notify_target_queries,
+        postgres: schema::postgres_zone_notify_target_queries,
+        sqlite: schema::sqlite_zone_notify_target_queries,
+    },
+    Migration {
+        version: 10,
+        description: "zone templates",
+        mysql: schema::mysql_zone_template_queries,
+        postgres: schema::postgres_zone_template_queries,
+        sqlite: schema::sqlite_zone_template_queries,
+    },
+    Migration {
+        version: 11,
+        description: "zone audit log",
+        mysql: schema::mysql_audit_log_queries,
+        postgres: schema::postgres_audit_log_queries,
+        sqlite: schema::sqlite_audit_log_queries,
+    },
+];
+
+/// The schema version this binary runs against: its last migration's.
+pub const SCHEMA_VERSION: i32 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+/// Lock name (MySQL `GET_LOCK`) and key (PostgreSQL advisory lock) that keep
+/// two processes from applying the same migration at once.
+const MYSQL_LOCK_NAME: &str = "bindizr_schema_migrations";
+const POSTGRES_LOCK_KEY: i64 = 0x0062_696e_6469_7a72;
+const MYSQL_LOCK_TIMEOUT_SECS: i32 = 60;
+
+const MYSQL_MIGRATIONS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INT PRIMARY KEY,
+        description VARCHAR(255) NOT NULL,
+        applied_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+"#;
+
+const POSTGRES_MIGRATIONS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INTEGER PRIMARY KEY,
+        description VARCHAR(255) NOT NULL,
+        applied_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+"#;
+
+const SQLITE_MIGRATIONS_TABLE: &str = r#"
+    CREATE TABLE IF NOT EXISTS schema_migrations (
+        version INTEGER PRIMARY KEY,
+        description TEXT NOT NULL,
+        applied_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+    );
+"#;
+
+const SELECT_APPLIED: &str =
+    "SELECT version, description, applied_at FROM schema_migrations ORDER BY version";
+
+/// A `schema_migrations` row.
+type AppliedRow = (i32, String, DateTime<Utc>);
+
+/// A migration as this binary knows it or as a database recorded it.
+#[derive(Clone, Debug)]
+pub struct MigrationState {
+    pub version: i32,
+    pub description: String,
+    /// When the database applied it; `None` while it is pending.
+    pub applied_at: Option<DateTime<Utc>>,
+}
+
+/// How a database's schema compares to this binary's migrations.
+#[derive(Clone, Debug)]
+pub struct SchemaStatus {
+    /// Highest version the database has applied; 0 when it never migrated.
+    pub database_version: i32,
+    /// This binary's migrations, plus any applied version it does not know,
+    /// ordered by version.
+    pub migrations: Vec<MigrationState>,
+}
+
+impl SchemaStatus {
+    /// Migrations this binary has that the database has not applied.
+    pub fn pending(&self) -> impl Iterator<Item = &MigrationState> {
+        self.migrations
+            .iter()
+            .filter(|migration| migration.applied_at.is_none())
+    }
+
+    /// True when a newer bindizr migrated the database past this binary.
+    pub fn is_ahead_of_binary(&self) -> bool {
+        self.database_version > SCHEMA_VERSION
+    }
+}
+
+/// Merge the recorded rows with the known migrations.
+fn schema_status(applied: Vec<AppliedRow>) -> SchemaStatus {
+    let database_version = applied
+        .iter()
+        .map(|(version, _, _)| *version)
+        .max()
+        .unwrap_or(0);
+
+    let mut migrations: Vec<MigrationState> = MIGRATIONS
+        .iter()
+        .map(|migration| MigrationState {
+            version: migration.version,
+            description: migration.description.to_string(),
+            applied_at: applied
+                .iter()
+                .find(|(version, _, _)| *version == migration.version)
+                .map(|(_, _, applied_at)| *applied_at),
+        })
+        .collect();
+    migrations.extend(
+        applied
+            .into_iter()
+            .filter(|(version, _, _)| !MIGRATIONS.iter().any(|m| m.version == *version))
+            .map(|(version, description, applied_at)| MigrationState {
+                version,
+                description,
+                applied_at: Some(applied_at),
+            }),
+    );
+    migrations.sort_by_key(|migration| migration.version);
+
+    SchemaStatus {
+        database_version,
+        migrations,
+    }
+}
+
+/// The columns and indexes a MySQL database already has, as
+/// `(table, column)` and `(table, index)` pairs.
+#[derive(Default)]
+struct MysqlObjects {
+    columns: HashSet<(String, String)>,
+    indexes: HashSet<(String, String)>,
+}
+
+impl MysqlObjects {
+    async fn load(conn: &mut MySqlConnection) -> Result<Self, DatabaseError> {
+        let columns = sqlx::query_as::<_, (String, String)>(
+            "SELECT TABLE_NAME, COLUMN_NAME FROM information_schema.COLUMNS \
+             WHERE TABLE_SCHEMA = DATABASE()",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+        let indexes = sqlx::query_as::<_, (String, String)>(
+            "SELECT DISTINCT TABLE_NAME, INDEX_NAME FROM information_schema.STATISTICS \
+             WHERE TABLE_SCHEMA = DATABASE()",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(Self {
+            columns: columns.into_iter().collect(),
+            indexes: indexes.into_iter().collect(),
+        })
+    }
+
+    fn contains(&self, object: MysqlObject) -> bool {
+        match object {
+            MysqlObject::Column { table, column } => self
+                .columns
+                .contains(&(table.to_string(), column.to_string())),
+            MysqlObject::Index { table, index } => self
+                .indexes
+                .contains(&(table.to_string(), index.to_string())),
+        }
+    }
+}
+
+/// The statements of a MySQL migration still to run: all of them, less the
+/// columns and indexes an earlier, interrupted attempt already added.
+fn mysql_statements_to_run(
+    statements: Vec<MysqlStatement>,
+    existing: &MysqlObjects,
+) -> Vec<&'static str> {
+    statements
+        .into_iter()
+        .filter(|statement| {
+            !statement
+                .creates
+                .is_some_and(|object| existing.contains(object))
+        })
+        .map(|statement| statement.sql)
+        .collect()
+}
+
+fn schema_ahead_error(status: &SchemaStatus) -> DatabaseError {
+    DatabaseError::SchemaMismatch(format!(
+        "database schema is at version {}, newer than the {} this bindizr supports; upgrade bindizr",
+        status.database_version, SCHEMA_VERSION
+    ))
+}
+
+impl DatabasePool {
+    /// Bring the schema up to date at startup: apply pending migrations when
+    /// `auto_migrate` is on, otherwise require `bindizr db migrate` first.
+    pub(crate) async fn prepare_schema(&self, auto_migrate: bool) -> Result<(), DatabaseError> {
+        let status = self.schema_status().await?;
+        if status.is_ahead_of_binary() {
+            return Err(schema_ahead_error(&status));
+        }
+        if status.pending().next().is_none() {
+            return Ok(());
+        }
+        if !auto_migrate {
+            return Err(DatabaseError::SchemaMismatch(format!(
+                "database schema is at version {} but this bindizr requires {}; run `bindizr db migrate`",
+                status.database_version, SCHEMA_VERSION
+            )));
+        }
+
+        self.migrate().await.map(|_| ())
+    }
+
+    /// Read the applied migrations, creating `schema_migrations` if needed.
+    pub(crate) async fn schema_status(&self) -> Result<SchemaStatus, DatabaseError> {
+        let applied = match self {
+            DatabasePool::MySQL(pool) => {
+                let mut conn = pool.acquire().await?;
+                sqlx::query(MYSQL_MIGRATIONS_TABLE)
+                    .execute(&mut *conn)
+                    .await?;
+                sqlx::query_as::<_, AppliedRow>(SELECT_APPLIED)
+                    .fetch_all(&mut *conn)
+                    .await?
+            }
+            DatabasePool::PostgreSQL(pool) => {
+                let mut conn = pool.acquire().await?;
+                sqlx::query(POSTGRES_MIGRATIONS_TABLE)
+                    .execute(&mut *conn)
+                    .await?;
+                sqlx::query_as::<_, AppliedRow>(SELECT_APPLIED)
+                    .fetch_all(&mut *conn)
+                    .await?
+            }
+            DatabasePool::SQLite(pool) => {
+                let mut conn = pool.acquire().await?;
+                sqlx::query(SQLITE_MIGRATIONS_TABLE)
+                    .execute(&mut *conn)
+                    .await?;
+                sqlx::query_as::<_, AppliedRow>(SELECT_APPLIED)
+                    .fetch_all(&mut *conn)
+                    .await?
+            }
+        };
+
+        Ok(schema_status(applied))
+    }
+
+    /// Apply every pending migration in order and return the ones applied.
+    /// PostgreSQL and SQLite apply the batch in one transaction; MySQL
+    /// commits DDL implicitly, so there each statement commits on its own and
+    /// a migration interrupted partway finishes when it is re-run.
+    pub(crate) async fn migrate(&self) -> Result<Vec<MigrationState>, DatabaseError> {
+        let status = self.schema_status().await?;
+        if status.is_ahead_of_binary() {
+            return Err(schema_ahead_error(&status));
+        }
+
+        let mut applied = Vec::new();
+        match self {
+            DatabasePool::MySQL(pool) => {
+                let mut conn = pool.acquire().await?;
+                let locked: Option<i64> = sqlx::query_scalar("SELECT GET_LOCK(?, ?)")
+                    .bind(MYSQL_LOCK_NAME)
+                    .bind(MYSQL_LOCK_TIMEOUT_SECS)
+                    .fetch_one(&mut *conn)
+                    .await?;
+                if locked != Some(1) {
+                    return Err(DatabaseError::QueryFailed(
+                        "Timed out waiting for another process to finish migrating".to_string(),
+                    ));
+                }
+
+                let result = async {
+                    // Re-read under the lock: another process may have migrated.
+                    let done: Vec<i32> =
+                        sqlx::query_scalar("SELECT version FROM schema_migrations")
+                            .fetch_all(&mut *conn)
+                            .await?;
+                    for migration in MIGRATIONS.iter().filter(|m| !done.contains(&m.version)) {
+                        let existing = MysqlObjects::load(&mut conn).await?;
+                        for query in mysql_statements_to_run((migration.mysql)(), &existing) {
+                            sqlx::query(query).execute(&mut *conn).await?;
+                        }
+                        sqlx::query(
+                            "INSERT INTO schema_migrations (version, description) VALUES (?, ?)",
+                        )
+                        .bind(migration.version)
+                        .bind(migration.description)
+                        .execute(&mut *conn)
+                        .await?;
+                        applied.push(migration);
+                    }
+                    Ok::<_, DatabaseError>(())
+                }
+                .await;
+
+                let _ = sqlx::query("SELECT RELEASE_LOCK(?)")
+                    .bind(MYSQL_LOCK_NAME)
+                    .execute(&mut *conn)
+                    .await;
+                result?;
+            }
+            DatabasePool::PostgreSQL(pool) => {
+                let mut tx = pool.begin().await?;
+                sqlx::query("SELECT pg_advisory_xact_lock($1)")
+                    .bind(POSTGRES_LOCK_KEY)
+                    .execute(&mut *tx)
+                    .await?;
+
+                let done: Vec<i32> = sqlx::query_scalar("SELECT version FROM schema_migrations")
+                    .fetch_all(&mut *tx)
+                    .await?;
+                for migration in MIGRATIONS.iter().filter(|m| !done.contains(&m.version)) {
+                    for query in (migration.postgres)() {
+                        sqlx::query(query).execute(&mut *tx).await?;
+                    }
+                    sqlx::query(
+                        "INSERT INTO schema_migrations (version, description) VALUES ($1, $2)",
+                    )
+                    .bind(migration.version)
+                    .bind(migration.description)
+                    .execute(&mut *tx)
+                    .await?;
+                    applied.push(migration);
+                }
+
+                tx.commit().await?;
+            }
+            DatabasePool::SQLite(pool) => {
+                // IMMEDIATE takes the write lock before reading what is applied.
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
+
+                let done: Vec<i32> = sqlx::query_scalar("SELECT version FROM schema_migrations")
+                    .fetch_all(&mut *tx)
+                    .await?;
+                for migration in MIGRATIONS.iter().filter(|m| !done.contains(&m.version)) {
+                    for query in (migration.sqlite)() {
+                        sqlx::query(query).execute(&mut *tx).await?;
+                    }
+                    sqlx::query(
+                        "INSERT INTO schema_migrations (version, description) VALUES (?, ?)",
+                    )
+                    .bind(migration.version)
+                    .bind(migration.description)
+                    .execute(&mut *tx)
+                    .await?;
+                    applied.push(migration);
+                }
+
+                tx.commit().await?;
+            }
+        }
+
+        for migration in &applied {
+            log_info!(
+                "Applied schema migration {}: {}",
+                migration.version,
+                migration.description
+            );
+        }
+
+        let applied_at = Utc::now();
+        Ok(applied
+            .into_iter()
+            .map(|migration| MigrationState {
+                version: migration.version,
+                description: migration.description.to_string(),
+                applied_at: Some(applied_at),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use super::*;
+
+fn applied(version: i32, description: &str) -> AppliedRow {
+    (version, description.to_string(), Utc::now())
+}
+
+#[test]
+fn migrations_are_numbered_consecutively_from_one() {
+    for (index, migration) in MIGRATIONS.iter().enumerate() {
+        assert_eq!(migration.version, index as i32 + 1);
+        assert!(!(migration.mysql)().is_empty());
+        assert!(!(migration.postgres)().is_empty());
+        assert!(!(migration.sqlite)().is_empty());
+    }
+    assert_eq!(SCHEMA_VERSION, MIGRATIONS.len() as i32);
+}
+
+#[test]
+fn schema_status_lists_unapplied_migrations_as_pending() {
+    let status = schema_status(vec![applied(1, "initial schema")]);
+
+    assert_eq!(status.database_version, 1);
+    assert!(!status.is_ahead_of_binary());
+    let pending: Vec<i32> = status.pending().map(|m| m.version).collect();
+    assert_eq!(pending, (2..=SCHEMA_VERSION).collect::<Vec<_>>());
+
+    let fresh = schema_status(Vec::new());
+    assert_eq!(fresh.database_version, 0);
+    assert_eq!(fresh.pending().count(), MIGRATIONS.len());
+}
+
+#[test]
+fn schema_status_keeps_versions_only_a_newer_binary_knows() {
+    let mut rows: Vec<AppliedRow> = MIGRATIONS
+        .iter()
+        .map(|migration| applied(migration.version, migration.description))
+        .collect();
+    rows.push(applied(SCHEMA_VERSION + 1, "from the future"));
+
+    let status = schema_status(rows);
+
+    assert!(status.is_ahead_of_binary());
+    assert_eq!(status.pending().count(), 0);
+    let last = status.migrations.last().unwrap();
+    assert_eq!(last.version, SCHEMA_VERSION + 1);
+    assert_eq!(last.description, "from the future");
+}
+
+#[test]
+fn every_mysql_alter_and_create_index_names_what_it_adds() {
+    // Those are the statements MySQL cannot repeat, so re-running an
+    // interrupted migration relies on skipping them.
+    for migration in MIGRATIONS {
+        for statement in (migration.mysql)() {
+            let sql = statement.sql.trim_start();
+            if sql.starts_with("ALTER TABLE") || sql.starts_with("CREATE INDEX") {
+                assert!(
+                    statement.creates.is_some(),
+                    "migration {} runs an unguarded `{sql}`",
+                    migration.version
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn rerunning_a_partly_applied_mysql_migration_skips_what_it_already_added() {
+    let attribution = MIGRATIONS
+        .iter()
+        .find(|migration| migration.version == 3)
+        .unwrap();
+    let all = mysql_statements_to_run((attribution.mysql)(), &MysqlObjects::default());
+    assert_eq!(all.len(), 6);
+
+    // The columns committed, then the index failed.
+    let mut existing = MysqlObjects::default();
+    for column in [
+        "actor",
+        "tsig_key_name",
+        "source_address",
+        "transport",
+        "request_id",
+    ] {
+        existing
+            .columns
+            .insert(("zone_soa_history".to_string(), column.to_string()));
+    }
+    let rerun = mysql_statements_to_run((attribution.mysql)(), &existing);
+    assert_eq!(rerun.len(), 1);
+    assert!(rerun[0].contains("CREATE INDEX idx_zone_soa_history_created"));
+
+    // A column of the same name on another table does not count.
+    let mut elsewhere = MysqlObjects::default();
+    elsewhere
+        .columns
+        .insert(("zones".to_string(), "actor".to_string()));
+    assert_eq!(
+        mysql_statements_to_run((attribution.mysql)(), &elsewhere).len(),
+        6
+    );
+
+    // Statements that are safe to repeat always run again.
+    let roles = MIGRATIONS
+        .iter()
+        .find(|migration| migration.version == 6)
+        .unwrap();
+    let mut existing = MysqlObjects::default();
+    existing
+        .columns
+        .insert(("api_tokens".to_string(), "role".to_string()));
+    let rerun = mysql_statements_to_run((roles.mysql)(), &existing);
+    assert_eq!(
+        rerun,
+        vec!["UPDATE api_tokens SET role = 'admin' WHERE is_global = TRUE;"]
+    );
+}
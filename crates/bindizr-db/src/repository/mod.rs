@@ -8,16 +8,23 @@ pub(crate) mod sqlite;
 
 use async_trait::async_trait;
 use bindizr_core::dns::name::OwnerName;
+use chrono::{DateTime, Utc};
 use sqlx::{MySql, Postgres, Sqlite};
 
 use super::model::{
     api_token::ApiToken,
+    catalog::{Catalog, ZoneCatalogProperty},
     record::{Record, RecordWithZone},
     tsig_key::TsigKey,
+    webhook::{Webhook, WebhookDelivery},
     zone::Zone,
+    zone_audit::ZoneAuditEntry,
     zone_change::ZoneChange,
+    zone_notify_target::ZoneNotifyTarget,
     zone_snapshot::ZoneSnapshot,
+    zone_template::{ZoneTemplate, ZoneTemplateLink},
     zone_token_policy::ZoneTokenPolicy,
+    zone_transfer_acl::ZoneTransferAcl,
     zone_tsig_policy::ZoneTsigPolicy,
 };
 use crate::{DatabasePool, error::DatabaseError, get_pool};
@@ -80,6 +87,18 @@ pub struct RecordFilter {
     pub offset: Option<u64>,
 }
 
+/// Optional criteria for querying the audit log. `since` and `until` bound
+/// `created_at`, inclusive.
+#[derive(Clone, Debug, Default)]
+pub struct AuditFilter {
+    pub zone_name: Option<String>,
+    pub actor: Option<String>,
+    pub transport: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+}
+
 /// A database transaction spanning any of the supported backends.
 pub struct RepositoryTx<'a>(RepositoryTxKind<'a>);
 
@@ -281,6 +300,26 @@ pub trait ZoneTokenPolicyRepository: Send + Sync {
     async fn delete(&self, id: i32) -> Result<(), DatabaseError>;
 }
 
+/// Persistence operations for per-zone transfer ACL entries.
+#[async_trait]
+pub trait ZoneTransferAclRepository: Send + Sync {
+    async fn create(&self, entry: ZoneTransferAcl) -> Result<ZoneTransferAcl, DatabaseError>;
+    async fn get_by_id(&self, id: i32) -> Result<Option<ZoneTransferAcl>, DatabaseError>;
+    async fn list_by_zone_id(&self, zone_id: i32) -> Result<Vec<ZoneTransferAcl>, DatabaseError>;
+    /// Every zone's entries, for filtering the catalog zone per client.
+    async fn list_all(&self) -> Result<Vec<ZoneTransferAcl>, DatabaseError>;
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError>;
+}
+
+/// Persistence operations for per-zone NOTIFY targets.
+#[async_trait]
+pub trait ZoneNotifyTargetRepository: Send + Sync {
+    async fn create(&self, entry: ZoneNotifyTarget) -> Result<ZoneNotifyTarget, DatabaseError>;
+    async fn get_by_id(&self, id: i32) -> Result<Option<ZoneNotifyTarget>, DatabaseError>;
+    async fn list_by_zone_id(&self, zone_id: i32) -> Result<Vec<ZoneNotifyTarget>, DatabaseError>;
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError>;
+}
+
 /// Persistence operations for records.
 #[async_trait]
 pub trait RecordRepository: Send + Sync {
@@ -356,6 +395,8 @@ pub trait ZoneChangeRepository: Send + Sync {
         tx: &mut RepositoryTx<'_>,
         changes: &[ZoneChange],
     ) -> Result<(), DatabaseError>;
+    /// Changes with serial in `(from_serial, to_serial]` in RFC 1982 order,
+    /// so the range may span a serial wrap.
     async fn list_changes_between_serials(
         &self,
         zone_id: i32,
@@ -372,6 +413,24 @@ pub trait ZoneChangeRepository: Send + Sync {
         to_serial: i32,
         lock_level: LockLevel,
     ) -> Result<Vec<ZoneChange>, DatabaseError>;
+    /// Count a zone's changes at `serial` or older, counting back from
+    /// `current_serial`. Like the other pruning queries it runs under the
+    /// zone row lock the caller holds.
+    async fn count_through_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError>;
+    /// Delete a zone's changes at `serial` or older, returning how many.
+    async fn delete_through_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError>;
 }
 
 /// Persistence operations for zone snapshots.
@@ -387,7 +446,8 @@ pub trait ZoneSnapshotRepository: Send + Sync {
         zone_id: i32,
         serial: i32,
     ) -> Result<Option<ZoneSnapshot>, DatabaseError>;
-    /// Fetch every snapshot for a zone whose serial is in `[from_serial, to_serial]`.
+    /// Fetch every snapshot for a zone whose serial is in `[from_serial, to_serial]`
+    /// in RFC 1982 order.
     async fn list_by_zone_id_in_serial_range(
         &self,
         zone_id: i32,
@@ -411,6 +471,57 @@ pub trait ZoneSnapshotRepository: Send + Sync {
         serial: i32,
         lock_level: LockLevel,
     ) -> Result<Option<ZoneSnapshot>, DatabaseError>;
+    /// The serial `rank` places below a zone's newest snapshot (0 is the
+    /// newest), counting back from `current_serial`; `None` when the zone has
+    /// no more snapshots than that.
+    async fn find_serial_by_rank_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        rank: u64,
+    ) -> Result<Option<i32>, DatabaseError>;
+    /// The oldest serial, counting back from `current_serial`, among a zone's
+    /// snapshots recorded at or after `since`.
+    async fn find_oldest_serial_since_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        since: DateTime<Utc>,
+    ) -> Result<Option<i32>, DatabaseError>;
+    /// Count a zone's snapshots older than `serial`, counting back from
+    /// `current_serial`.
+    async fn count_before_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError>;
+    /// Delete a zone's snapshots older than `serial`, returning how many.
+    async fn delete_before_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError>;
+}
+
+/// Persistence operations for the audit log. Entries are only appended and
+/// aged out; nothing else deletes them.
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    async fn insert_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        entry: ZoneAuditEntry,
+    ) -> Result<(), DatabaseError>;
+    /// List entries across zones, newest first.
+    async fn list(&self, filter: AuditFilter) -> Result<Vec<ZoneAuditEntry>, DatabaseError>;
+    /// Delete entries recorded before `before`, returning how many.
+    async fn delete_before(&self, before: DateTime<Utc>) -> Result<u64, DatabaseError>;
 }
 
 /// Persistence operations for API tokens.
@@ -421,7 +532,110 @@ pub trait ApiTokenRepository: Send + Sync {
     async fn get_by_token(&self, token: &str) -> Result<Option<ApiToken>, DatabaseError>;
     async fn list_all(&self) -> Result<Vec<ApiToken>, DatabaseError>;
     async fn update(&self, token: ApiToken) -> Result<ApiToken, DatabaseError>;
+    /// Swap in a new secret hash, keeping `previous_token` valid until
+    /// `previous_token_expires_at`. Separate from `update`, whose
+    /// read-modify-write could otherwise restore a replaced secret.
+    async fn rotate(
+        &self,
+        id: i32,
+        token: &str,
+        previous_token: Option<&str>,
+        previous_token_expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DatabaseError>;
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError>;
+}
+
+/// Persistence operations for named catalogs and the catalog properties of
+/// their member zones.
+#[async_trait]
+pub trait CatalogRepository: Send + Sync {
+    async fn create(&self, catalog: Catalog) -> Result<Catalog, DatabaseError>;
+    async fn get_by_name(&self, name: &str) -> Result<Option<Catalog>, DatabaseError>;
+    async fn list_all(&self) -> Result<Vec<Catalog>, DatabaseError>;
     async fn delete(&self, id: i32) -> Result<(), DatabaseError>;
+    async fn list_properties_by_zone_id(
+        &self,
+        zone_id: i32,
+    ) -> Result<Vec<ZoneCatalogProperty>, DatabaseError>;
+    async fn list_all_properties(&self) -> Result<Vec<ZoneCatalogProperty>, DatabaseError>;
+    /// Replace every catalog property of a zone in one transaction; the
+    /// `(name, value)` pairs become its whole set.
+    async fn replace_properties(
+        &self,
+        zone_id: i32,
+        properties: &[(String, String)],
+    ) -> Result<(), DatabaseError>;
+}
+
+/// Persistence operations for zone templates and their links to the zones
+/// created from them.
+#[async_trait]
+pub trait ZoneTemplateRepository: Send + Sync {
+    async fn create(&self, template: ZoneTemplate) -> Result<ZoneTemplate, DatabaseError>;
+    async fn get_by_name(&self, name: &str) -> Result<Option<ZoneTemplate>, DatabaseError>;
+    async fn list_all(&self) -> Result<Vec<ZoneTemplate>, DatabaseError>;
+    /// Write back a template's description, records and `updated_at`.
+    async fn update(&self, template: &ZoneTemplate) -> Result<(), DatabaseError>;
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError>;
+    /// Link a zone to its template, in the transaction creating the zone.
+    async fn create_link_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        link: ZoneTemplateLink,
+    ) -> Result<ZoneTemplateLink, DatabaseError>;
+    async fn list_links_by_template_id(
+        &self,
+        template_id: i32,
+    ) -> Result<Vec<ZoneTemplateLink>, DatabaseError>;
+    /// Record which template records a linked zone now carries.
+    async fn update_link_applied_records(
+        &self,
+        id: i32,
+        applied_records: &str,
+    ) -> Result<(), DatabaseError>;
+}
+
+/// Persistence operations for webhooks and their delivery queue.
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn create(&self, webhook: Webhook) -> Result<Webhook, DatabaseError>;
+    async fn get_by_name(&self, name: &str) -> Result<Option<Webhook>, DatabaseError>;
+    async fn list_all(&self) -> Result<Vec<Webhook>, DatabaseError>;
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError>;
+    /// How many webhooks watch `zone_id`: the zone's own and the global ones.
+    async fn count_watching_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+    ) -> Result<u64, DatabaseError>;
+    /// Queue `delivery` once for every webhook watching `zone_id`: the
+    /// zone's own and the global ones. Its `id` and `webhook_id` are filled
+    /// in per row. Returns how many rows were queued.
+    async fn enqueue_deliveries_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        delivery: &WebhookDelivery,
+    ) -> Result<u64, DatabaseError>;
+    /// Pending deliveries whose next attempt is due at `now`, oldest first.
+    async fn list_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DatabaseError>;
+    /// A webhook's deliveries, newest first.
+    async fn list_deliveries_by_webhook_id(
+        &self,
+        webhook_id: i32,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DatabaseError>;
+    /// Write back a delivery's payload and attempt state.
+    async fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DatabaseError>;
+    /// Drop delivered and failed deliveries created before `before`.
+    async fn delete_finished_deliveries_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<u64, DatabaseError>;
 }
 
 /// Persistence operations for catalog zone state.
@@ -519,6 +733,87 @@ impl RepositoryFactory {
         }
     }
 
+    /// Create a zone transfer ACL repository for the given pool's backend.
+    pub(crate) fn create_zone_transfer_acl_repository(
+        pool: &DatabasePool,
+    ) -> Box<dyn ZoneTransferAclRepository> {
+        match pool {
+            DatabasePool::MySQL(mysql_pool) => Box::new(
+                mysql::MySqlZoneTransferAclRepository::new(mysql_pool.clone()),
+            ),
+            DatabasePool::PostgreSQL(postgres_pool) => Box::new(
+                postgres::PostgresZoneTransferAclRepository::new(postgres_pool.clone()),
+            ),
+            DatabasePool::SQLite(sqlite_pool) => Box::new(
+                sqlite::SqliteZoneTransferAclRepository::new(sqlite_pool.clone()),
+            ),
+        }
+    }
+
+    /// Create a zone NOTIFY target repository for the given pool's backend.
+    pub(crate) fn create_zone_notify_target_repository(
+        pool: &DatabasePool,
+    ) -> Box<dyn ZoneNotifyTargetRepository> {
+        match pool {
+            DatabasePool::MySQL(mysql_pool) => Box::new(
+                mysql::MySqlZoneNotifyTargetRepository::new(mysql_pool.clone()),
+            ),
+            DatabasePool::PostgreSQL(postgres_pool) => Box::new(
+                postgres::PostgresZoneNotifyTargetRepository::new(postgres_pool.clone()),
+            ),
+            DatabasePool::SQLite(sqlite_pool) => Box::new(
+                sqlite::SqliteZoneNotifyTargetRepository::new(sqlite_pool.clone()),
+            ),
+        }
+    }
+
+    /// Create a catalog repository for the given pool's backend.
+    pub(crate) fn create_catalog_repository(pool: &DatabasePool) -> Box<dyn CatalogRepository> {
+        match pool {
+            DatabasePool::MySQL(mysql_pool) => {
+                Box::new(mysql::MySqlCatalogRepository::new(mysql_pool.clone()))
+            }
+            DatabasePool::PostgreSQL(postgres_pool) => Box::new(
+                postgres::PostgresCatalogRepository::new(postgres_pool.clone()),
+            ),
+            DatabasePool::SQLite(sqlite_pool) => {
+                Box::new(sqlite::SqliteCatalogRepository::new(sqlite_pool.clone()))
+            }
+        }
+    }
+
+    /// Create a zone template repository for the given pool's backend.
+    pub(crate) fn create_zone_template_repository(
+        pool: &DatabasePool,
+    ) -> Box<dyn ZoneTemplateRepository> {
+        match pool {
+            DatabasePool::MySQL(mysql_pool) => {
+                Box::new(mysql::MySqlZoneTemplateRepository::new(mysql_pool.clone()))
+            }
+            DatabasePool::PostgreSQL(postgres_pool) => Box::new(
+                postgres::PostgresZoneTemplateRepository::new(postgres_pool.clone()),
+            ),
+            DatabasePool::SQLite(sqlite_pool) => Box::new(
+                sqlite::SqliteZoneTemplateRepository::new(sqlite_pool.clone()),
+            ),
+        }
+    }
+
+    /// Create a webhook repository for the given pool's backend.
+    pub(crate) fn create_webhook_repository(pool: &DatabasePool) -> Box<dyn WebhookRepository> {
+        match pool {
+            DatabasePool::MySQL(mysql_pool) => {
+                Box::new(mysql::MySqlWebhookRepository::new(mysql_pool.clone()))
+            }
+            DatabasePool::PostgreSQL(postgres_pool) => Box::new(
+                postgres::PostgresWebhookRepository::new(postgres_pool.clone()),
+            ),
+            DatabasePool::SQLite(sqlite_pool) => {
+                Box::new(sqlite::SqliteWebhookRepository::new(sqlite_pool.clone()))
+            }
+        }
+    }
+
     /// Create an API token repository for the given pool's backend.
     pub(crate) fn create_api_token_repository(pool: &DatabasePool) -> Box<dyn ApiTokenRepository> {
         match pool {
@@ -568,6 +863,21 @@ impl RepositoryFactory {
         }
     }
 
+    /// Create an audit log repository for the given pool's backend.
+    pub(crate) fn create_audit_log_repository(pool: &DatabasePool) -> Box<dyn AuditLogRepository> {
+        match pool {
+            DatabasePool::MySQL(mysql_pool) => {
+                Box::new(mysql::MySqlAuditLogRepository::new(mysql_pool.clone()))
+            }
+            DatabasePool::PostgreSQL(postgres_pool) => Box::new(
+                postgres::PostgresAuditLogRepository::new(postgres_pool.clone()),
+            ),
+            DatabasePool::SQLite(sqlite_pool) => {
+                Box::new(sqlite::SqliteAuditLogRepository::new(sqlite_pool.clone()))
+            }
+        }
+    }
+
     /// Create a catalog zone state repository for the given pool's backend.
     pub(crate) fn create_catalog_zone_state_repository(
         pool: &DatabasePool,
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use bindizr_core::dns::serial;
 
 use crate::{
     error::DatabaseError,
@@ -20,15 +21,15 @@ impl CatalogZoneStateRepository for MySqlCatalogZoneStateRepository {
     ) -> Result<i32, DatabaseError> {
         let mysql_tx = tx.as_mysql()?;
 
-        // Advance the catalog serial only when the signature changes, kept
-        // monotonic, so secondaries re-transfer the catalog zone only on real changes.
+        // Advance the catalog serial only when the signature changes, so
+        // secondaries re-transfer the catalog zone only on real changes. The
+        // next serial is worked out here rather than in SQL so it follows
+        // RFC 1982 order and wraps instead of overflowing the column.
         sqlx::query(
             r#"
             INSERT INTO catalog_zone_state (name, signature, serial)
             VALUES (?, ?, ?)
-            ON DUPLICATE KEY UPDATE
-                serial = IF(signature = VALUES(signature), serial, GREATEST(serial + 1, VALUES(serial))),
-                signature = VALUES(signature)
+            ON DUPLICATE KEY UPDATE name = name
             "#,
         )
         .bind(name)
@@ -38,16 +39,41 @@ impl CatalogZoneStateRepository for MySqlCatalogZoneStateRepository {
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
 
-        sqlx::query_scalar::<_, i32>(
+        let (current_signature, current_serial) = sqlx::query_as::<_, (String, i32)>(
             r#"
-            SELECT serial
+            SELECT signature, serial
             FROM catalog_zone_state
             WHERE name = ?
+            FOR UPDATE
             "#,
         )
         .bind(name)
         .fetch_one(&mut **mysql_tx)
         .await
-        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        if current_signature == signature {
+            return Ok(current_serial);
+        }
+
+        let next_serial = serial::from_wire(serial::advance(
+            serial::to_wire(current_serial),
+            serial::to_wire(base_serial),
+        ));
+        sqlx::query(
+            r#"
+            UPDATE catalog_zone_state
+            SET signature = ?, serial = ?
+            WHERE name = ?
+            "#,
+        )
+        .bind(signature)
+        .bind(next_serial)
+        .bind(name)
+        .execute(&mut **mysql_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        Ok(next_serial)
     }
 }
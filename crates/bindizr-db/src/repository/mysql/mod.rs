@@ -1,19 +1,31 @@
 mod api_token_repository_impl;
+mod audit_log_repository_impl;
+mod catalog_repository_impl;
 mod catalog_zone_state_repository_impl;
 mod record_repository_impl;
 mod tsig_key_repository_impl;
+mod webhook_repository_impl;
 mod zone_change_repository_impl;
+mod zone_notify_target_repository_impl;
 mod zone_repository_impl;
 mod zone_snapshot_repository_impl;
+mod zone_template_repository_impl;
 mod zone_token_policy_repository_impl;
+mod zone_transfer_acl_repository_impl;
 mod zone_tsig_policy_repository_impl;
 
 pub(crate) use api_token_repository_impl::MySqlApiTokenRepository;
+pub(crate) use audit_log_repository_impl::MySqlAuditLogRepository;
+pub(crate) use catalog_repository_impl::MySqlCatalogRepository;
 pub(crate) use catalog_zone_state_repository_impl::MySqlCatalogZoneStateRepository;
 pub(crate) use record_repository_impl::MySqlRecordRepository;
 pub(crate) use tsig_key_repository_impl::MySqlTsigKeyRepository;
+pub(crate) use webhook_repository_impl::MySqlWebhookRepository;
 pub(crate) use zone_change_repository_impl::MySqlZoneChangeRepository;
+pub(crate) use zone_notify_target_repository_impl::MySqlZoneNotifyTargetRepository;
 pub(crate) use zone_repository_impl::MySqlZoneRepository;
 pub(crate) use zone_snapshot_repository_impl::MySqlZoneSnapshotRepository;
+pub(crate) use zone_template_repository_impl::MySqlZoneTemplateRepository;
 pub(crate) use zone_token_policy_repository_impl::MySqlZoneTokenPolicyRepository;
+pub(crate) use zone_transfer_acl_repository_impl::MySqlZoneTransferAclRepository;
 pub(crate) use zone_tsig_policy_repository_impl::MySqlZoneTsigPolicyRepository;
@@ -4,7 +4,10 @@ use sqlx::{AssertSqlSafe, MySql, Pool};
 use crate::{
     error::DatabaseError,
     model::zone_change::ZoneChange,
-    repository::{LockLevel, RepositoryTx, ZoneChangeRepository, sql::lock_clause},
+    repository::{
+        LockLevel, RepositoryTx, ZoneChangeRepository,
+        sql::{lock_clause, serial_distance},
+    },
 };
 
 /// MySQL-backed implementation of `ZoneChangeRepository`.
@@ -70,13 +73,14 @@ impl ZoneChangeRepository for MySqlZoneChangeRepository {
             r#"
             SELECT zone_id, serial, operation, record_name, record_type, record_value, record_ttl, record_priority
             FROM zone_changes
-            WHERE zone_id = ? AND serial > ? AND serial <= ?
-            ORDER BY serial, id
+            WHERE zone_id = ? AND ((serial - ?) & 4294967295) BETWEEN 1 AND ?
+            ORDER BY (serial - ?) & 4294967295, id
             "#
         )
         .bind(zone_id)
-        .bind(from_serial)
-        .bind(to_serial)
+        .bind(i64::from(from_serial))
+        .bind(serial_distance(from_serial, to_serial))
+        .bind(i64::from(from_serial))
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
@@ -95,15 +99,58 @@ impl ZoneChangeRepository for MySqlZoneChangeRepository {
             AssertSqlSafe(format!("{}{}", r#"
             SELECT zone_id, serial, operation, record_name, record_type, record_value, record_ttl, record_priority
             FROM zone_changes
-            WHERE zone_id = ? AND serial > ? AND serial <= ?
-            ORDER BY serial, id
+            WHERE zone_id = ? AND ((serial - ?) & 4294967295) BETWEEN 1 AND ?
+            ORDER BY (serial - ?) & 4294967295, id
             "#, lock_clause(lock_level)))
         )
         .bind(zone_id)
-        .bind(from_serial)
-        .bind(to_serial)
+        .bind(i64::from(from_serial))
+        .bind(serial_distance(from_serial, to_serial))
+        .bind(i64::from(from_serial))
         .fetch_all(&mut **mysql_tx)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
     }
+
+    async fn count_through_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let mysql_tx = tx.as_mysql()?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM zone_changes WHERE zone_id = ? AND ((? - serial) & 4294967295) >= ?",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .fetch_one(&mut **mysql_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn delete_through_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let mysql_tx = tx.as_mysql()?;
+
+        sqlx::query(
+            "DELETE FROM zone_changes WHERE zone_id = ? AND ((? - serial) & 4294967295) >= ?",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .execute(&mut **mysql_tx)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
 }
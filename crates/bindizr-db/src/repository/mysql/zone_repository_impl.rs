@@ -32,8 +32,8 @@ impl ZoneRepository for MySqlZoneRepository {
 
         let result = sqlx::query(
             r#"
-            INSERT INTO zones (name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO zones (name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(zone.name.as_str())
@@ -41,6 +41,7 @@ impl ZoneRepository for MySqlZoneRepository {
         .bind(&zone.admin_email)
         .bind(zone.ttl)
         .bind(zone.serial)
+        .bind(zone.serial_policy.as_str())
         .bind(zone.refresh)
         .bind(zone.retry)
         .bind(zone.expire)
@@ -60,7 +61,7 @@ impl ZoneRepository for MySqlZoneRepository {
     ) -> Result<Option<Zone>, DatabaseError> {
         let mysql_tx = tx.as_mysql()?;
 
-        let zone = sqlx::query_as::<_, Zone>(AssertSqlSafe(format!("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE id = ?{}",lock_clause(lock_level))))
+        let zone = sqlx::query_as::<_, Zone>(AssertSqlSafe(format!("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE id = ?{}",lock_clause(lock_level))))
             .bind(id)
             .fetch_optional(&mut **mysql_tx)
             .await?;
@@ -71,7 +72,7 @@ impl ZoneRepository for MySqlZoneRepository {
     async fn get_by_name(&self, name: &str) -> Result<Option<Zone>, DatabaseError> {
         let mut conn = self.pool.acquire().await?;
 
-        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = ?")
+        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = ?")
             .bind(name)
             .fetch_optional(&mut *conn)
             .await
@@ -89,7 +90,7 @@ impl ZoneRepository for MySqlZoneRepository {
         let mysql_tx = tx.as_mysql()?;
 
         let zone = sqlx::query_as::<_, Zone>(AssertSqlSafe(
-            format!("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = ?{}",
+            format!("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = ?{}",
             lock_clause(lock_level),
         )))
         .bind(name)
@@ -102,7 +103,7 @@ impl ZoneRepository for MySqlZoneRepository {
     async fn list_all(&self) -> Result<Vec<Zone>, DatabaseError> {
         let mut conn = self.pool.acquire().await?;
 
-        let zones = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name")
+        let zones = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name")
             .fetch_all(&mut *conn)
             .await
             ?;
@@ -117,7 +118,7 @@ impl ZoneRepository for MySqlZoneRepository {
     ) -> Result<Vec<Zone>, DatabaseError> {
         let mysql_tx = tx.as_mysql()?;
 
-        let zones = sqlx::query_as::<_, Zone>(AssertSqlSafe(format!("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name{}",lock_clause(lock_level))))
+        let zones = sqlx::query_as::<_, Zone>(AssertSqlSafe(format!("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name{}",lock_clause(lock_level))))
             .fetch_all(&mut **mysql_tx)
             .await?;
 
@@ -129,7 +130,7 @@ impl ZoneRepository for MySqlZoneRepository {
         let search = like_pattern(filter.search.as_deref());
         let zones = sqlx::query_as::<_, Zone>(
             r#"
-            SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at
             FROM zones
             WHERE (? IS NULL OR LOWER(name) = LOWER(?))
               AND (? IS NULL OR id = ?)
@@ -263,7 +264,7 @@ impl ZoneRepository for MySqlZoneRepository {
         sqlx::query(
             r#"
             UPDATE zones 
-            SET name = ?, primary_ns = ?, admin_email = ?, ttl = ?, serial = ?, refresh = ?, retry = ?, expire = ?, minimum_ttl = ?
+            SET name = ?, primary_ns = ?, admin_email = ?, ttl = ?, serial = ?, serial_policy = ?, refresh = ?, retry = ?, expire = ?, minimum_ttl = ?
             WHERE id = ?
             "#,
         )
@@ -272,6 +273,7 @@ impl ZoneRepository for MySqlZoneRepository {
         .bind(&zone.admin_email)
         .bind(zone.ttl)
         .bind(zone.serial)
+        .bind(zone.serial_policy.as_str())
         .bind(zone.refresh)
         .bind(zone.retry)
         .bind(zone.expire)
@@ -1,10 +1,14 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{AssertSqlSafe, MySql, Pool};
 
 use crate::{
     error::DatabaseError,
     model::zone_snapshot::ZoneSnapshot,
-    repository::{LockLevel, RepositoryTx, ZoneSnapshotRepository, sql::lock_clause},
+    repository::{
+        LockLevel, RepositoryTx, ZoneSnapshotRepository,
+        sql::{lock_clause, serial_distance},
+    },
 };
 
 /// MySQL-backed implementation of `ZoneSnapshotRepository`.
@@ -29,8 +33,9 @@ impl ZoneSnapshotRepository for MySqlZoneSnapshotRepository {
 
         sqlx::query(
             r#"
-            INSERT INTO zone_soa_history (zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO zone_soa_history (zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                                          actor, tsig_key_name, source_address, transport, request_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON DUPLICATE KEY UPDATE
                 primary_ns = VALUES(primary_ns),
                 admin_email = VALUES(admin_email),
@@ -38,7 +43,12 @@ impl ZoneSnapshotRepository for MySqlZoneSnapshotRepository {
                 refresh = VALUES(refresh),
                 retry = VALUES(retry),
                 expire = VALUES(expire),
-                minimum_ttl = VALUES(minimum_ttl)
+                minimum_ttl = VALUES(minimum_ttl),
+                actor = VALUES(actor),
+                tsig_key_name = VALUES(tsig_key_name),
+                source_address = VALUES(source_address),
+                transport = VALUES(transport),
+                request_id = VALUES(request_id)
             "#,
         )
         .bind(snapshot.zone_id)
@@ -50,13 +60,19 @@ impl ZoneSnapshotRepository for MySqlZoneSnapshotRepository {
         .bind(snapshot.retry)
         .bind(snapshot.expire)
         .bind(snapshot.minimum_ttl)
+        .bind(&snapshot.attribution.actor)
+        .bind(&snapshot.attribution.tsig_key_name)
+        .bind(&snapshot.attribution.source_address)
+        .bind(&snapshot.attribution.transport)
+        .bind(&snapshot.attribution.request_id)
         .execute(&mut **mysql_tx)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
 
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = ? AND serial = ?
             "#,
@@ -75,7 +91,8 @@ impl ZoneSnapshotRepository for MySqlZoneSnapshotRepository {
     ) -> Result<Option<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = ? AND serial = ?
             "#,
@@ -95,14 +112,15 @@ impl ZoneSnapshotRepository for MySqlZoneSnapshotRepository {
     ) -> Result<Vec<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
-            WHERE zone_id = ? AND serial >= ? AND serial <= ?
+            WHERE zone_id = ? AND ((serial - ?) & 4294967295) <= ?
             "#,
         )
         .bind(zone_id)
-        .bind(from_serial)
-        .bind(to_serial)
+        .bind(i64::from(from_serial))
+        .bind(serial_distance(from_serial, to_serial))
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
@@ -115,10 +133,11 @@ impl ZoneSnapshotRepository for MySqlZoneSnapshotRepository {
     ) -> Result<Vec<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = ?
-            ORDER BY serial DESC
+            ORDER BY ((SELECT z.serial FROM zones z WHERE z.id = zone_soa_history.zone_id) - serial) & 4294967295
             LIMIT ? OFFSET ?
             "#,
         )
@@ -151,7 +170,8 @@ impl ZoneSnapshotRepository for MySqlZoneSnapshotRepository {
 
         sqlx::query_as::<_, ZoneSnapshot>(
             AssertSqlSafe(format!("{}{}", r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = ? AND serial = ?
             "#, lock_clause(lock_level))),
@@ -162,4 +182,98 @@ impl ZoneSnapshotRepository for MySqlZoneSnapshotRepository {
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
     }
+
+    async fn find_serial_by_rank_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        rank: u64,
+    ) -> Result<Option<i32>, DatabaseError> {
+        let mysql_tx = tx.as_mysql()?;
+
+        sqlx::query_scalar(
+            r#"
+            SELECT serial
+            FROM zone_soa_history
+            WHERE zone_id = ?
+            ORDER BY (? - serial) & 4294967295
+            LIMIT 1 OFFSET ?
+            "#,
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(i64::try_from(rank).unwrap_or(i64::MAX))
+        .fetch_optional(&mut **mysql_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn find_oldest_serial_since_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        since: DateTime<Utc>,
+    ) -> Result<Option<i32>, DatabaseError> {
+        let mysql_tx = tx.as_mysql()?;
+
+        sqlx::query_scalar(
+            r#"
+            SELECT serial
+            FROM zone_soa_history
+            WHERE zone_id = ? AND created_at >= ?
+            ORDER BY (? - serial) & 4294967295 DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(zone_id)
+        .bind(since)
+        .bind(i64::from(current_serial))
+        .fetch_optional(&mut **mysql_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn count_before_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let mysql_tx = tx.as_mysql()?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM zone_soa_history WHERE zone_id = ? AND ((? - serial) & 4294967295) > ?",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .fetch_one(&mut **mysql_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn delete_before_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let mysql_tx = tx.as_mysql()?;
+
+        sqlx::query(
+            "DELETE FROM zone_soa_history WHERE zone_id = ? AND ((? - serial) & 4294967295) > ?",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .execute(&mut **mysql_tx)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
 }
@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use sqlx::{MySql, Pool};
+
+use crate::{
+    error::DatabaseError, model::zone_transfer_acl::ZoneTransferAcl,
+    repository::ZoneTransferAclRepository,
+};
+
+/// MySQL-backed implementation of `ZoneTransferAclRepository`.
+pub(crate) struct MySqlZoneTransferAclRepository {
+    pool: Pool<MySql>,
+}
+
+impl MySqlZoneTransferAclRepository {
+    pub(crate) fn new(pool: Pool<MySql>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ZoneTransferAclRepository for MySqlZoneTransferAclRepository {
+    async fn create(&self, mut entry: ZoneTransferAcl) -> Result<ZoneTransferAcl, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO zone_transfer_acls (zone_id, action, address, tsig_key_name)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(entry.zone_id)
+        .bind(entry.action.as_str())
+        .bind(&entry.address)
+        .bind(&entry.tsig_key_name)
+        .execute(&mut *conn)
+        .await?;
+
+        entry.id = result.last_insert_id() as i32;
+        Ok(entry)
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<Option<ZoneTransferAcl>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let entry = sqlx::query_as::<_, ZoneTransferAcl>(
+            "SELECT id, zone_id, action, address, tsig_key_name, created_at FROM zone_transfer_acls WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(entry)
+    }
+
+    async fn list_by_zone_id(&self, zone_id: i32) -> Result<Vec<ZoneTransferAcl>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let entries = sqlx::query_as::<_, ZoneTransferAcl>(
+            "SELECT id, zone_id, action, address, tsig_key_name, created_at FROM zone_transfer_acls WHERE zone_id = ? ORDER BY id",
+        )
+        .bind(zone_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn list_all(&self) -> Result<Vec<ZoneTransferAcl>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let entries = sqlx::query_as::<_, ZoneTransferAcl>(
+            "SELECT id, zone_id, action, address, tsig_key_name, created_at FROM zone_transfer_acls ORDER BY zone_id, id",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("DELETE FROM zone_transfer_acls WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{Pool, Postgres, Row};
 
 use crate::{error::DatabaseError, model::api_token::ApiToken, repository::ApiTokenRepository};
@@ -21,15 +22,16 @@ impl ApiTokenRepository for PostgresApiTokenRepository {
 
         let result = sqlx::query(
             r#"
-            INSERT INTO api_tokens (name, token, description, is_global, expires_at)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO api_tokens (name, token, description, is_global, role, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
             RETURNING id
         "#,
         )
         .bind(&token.name)
         .bind(&token.token)
         .bind(&token.description)
-        .bind(token.is_global)
+        .bind(token.is_global())
+        .bind(token.role.as_str())
         .bind(token.expires_at)
         .fetch_one(&mut *conn)
         .await?;
@@ -43,7 +45,7 @@ impl ApiTokenRepository for PostgresApiTokenRepository {
         let mut conn = self.pool.acquire().await?;
 
         let row = sqlx::query_as::<_, ApiToken>(
-            "SELECT id, name, token, description, is_global, expires_at, created_at, last_used_at FROM api_tokens WHERE name = $1"
+            "SELECT id, name, token, description, role, expires_at, created_at, last_used_at, previous_token, previous_token_expires_at FROM api_tokens WHERE name = $1"
         )
         .bind(name)
         .fetch_optional(&mut *conn)
@@ -57,7 +59,7 @@ impl ApiTokenRepository for PostgresApiTokenRepository {
         let mut conn = self.pool.acquire().await?;
 
         let row = sqlx::query_as::<_, ApiToken>(
-            "SELECT id, name, token, description, is_global, expires_at, created_at, last_used_at FROM api_tokens WHERE token = $1"
+            "SELECT id, name, token, description, role, expires_at, created_at, last_used_at, previous_token, previous_token_expires_at FROM api_tokens WHERE token = $1 OR previous_token = $1"
         )
         .bind(token)
         .fetch_optional(&mut *conn)
@@ -71,7 +73,7 @@ impl ApiTokenRepository for PostgresApiTokenRepository {
         let mut conn = self.pool.acquire().await?;
 
         let rows = sqlx::query_as::<_, ApiToken>(
-            "SELECT id, name, token, description, is_global, expires_at, created_at, last_used_at FROM api_tokens ORDER BY created_at DESC"
+            "SELECT id, name, token, description, role, expires_at, created_at, last_used_at, previous_token, previous_token_expires_at FROM api_tokens ORDER BY created_at DESC"
         )
         .fetch_all(&mut *conn)
         .await
@@ -100,6 +102,32 @@ impl ApiTokenRepository for PostgresApiTokenRepository {
         Ok(token)
     }
 
+    async fn rotate(
+        &self,
+        id: i32,
+        token: &str,
+        previous_token: Option<&str>,
+        previous_token_expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE api_tokens
+            SET token = $1, previous_token = $2, previous_token_expires_at = $3
+            WHERE id = $4
+        "#,
+        )
+        .bind(token)
+        .bind(previous_token)
+        .bind(previous_token_expires_at)
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
     async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
         let mut conn = self.pool.acquire().await?;
 
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+
+use crate::{
+    error::DatabaseError,
+    model::zone_audit::ZoneAuditEntry,
+    repository::{AuditFilter, AuditLogRepository, RepositoryTx},
+};
+
+/// PostgreSQL-backed implementation of `AuditLogRepository`.
+pub(crate) struct PostgresAuditLogRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresAuditLogRepository {
+    pub(crate) fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for PostgresAuditLogRepository {
+    async fn insert_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        entry: ZoneAuditEntry,
+    ) -> Result<(), DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO zone_audit_log (zone_name, serial, action, actor, tsig_key_name,
+                                        source_address, transport, request_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(&entry.zone_name)
+        .bind(entry.serial)
+        .bind(&entry.action)
+        .bind(&entry.attribution.actor)
+        .bind(&entry.attribution.tsig_key_name)
+        .bind(&entry.attribution.source_address)
+        .bind(&entry.attribution.transport)
+        .bind(&entry.attribution.request_id)
+        .bind(entry.created_at)
+        .execute(&mut **postgres_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list(&self, filter: AuditFilter) -> Result<Vec<ZoneAuditEntry>, DatabaseError> {
+        sqlx::query_as::<_, ZoneAuditEntry>(
+            r#"
+            SELECT zone_name, serial, action, actor, tsig_key_name, source_address,
+                   transport, request_id, created_at
+            FROM zone_audit_log
+            WHERE ($1::TEXT IS NULL OR zone_name = $2)
+              AND ($3::TEXT IS NULL OR actor = $4)
+              AND ($5::TEXT IS NULL OR transport = $6)
+              AND ($7::TIMESTAMPTZ IS NULL OR created_at >= $8)
+              AND ($9::TIMESTAMPTZ IS NULL OR created_at <= $10)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $11
+            "#,
+        )
+        .bind(&filter.zone_name)
+        .bind(&filter.zone_name)
+        .bind(&filter.actor)
+        .bind(&filter.actor)
+        .bind(&filter.transport)
+        .bind(&filter.transport)
+        .bind(filter.since)
+        .bind(filter.since)
+        .bind(filter.until)
+        .bind(filter.until)
+        .bind(filter.limit.map(i64::from).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn delete_before(&self, before: DateTime<Utc>) -> Result<u64, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query("DELETE FROM zone_audit_log WHERE created_at < $1")
+            .bind(before)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
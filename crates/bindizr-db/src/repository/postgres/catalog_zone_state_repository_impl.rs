@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use bindizr_core::dns::serial;
 
 use crate::{
     error::DatabaseError,
@@ -20,27 +21,59 @@ impl CatalogZoneStateRepository for PostgresCatalogZoneStateRepository {
     ) -> Result<i32, DatabaseError> {
         let postgres_tx = tx.as_postgres()?;
 
-        // Advance the catalog serial only when the signature changes, kept
-        // monotonic, so secondaries re-transfer the catalog zone only on real changes.
-        sqlx::query_scalar::<_, i32>(
+        // Advance the catalog serial only when the signature changes, so
+        // secondaries re-transfer the catalog zone only on real changes. The
+        // next serial is worked out here rather than in SQL so it follows
+        // RFC 1982 order and wraps instead of overflowing the column.
+        sqlx::query(
             r#"
             INSERT INTO catalog_zone_state (name, signature, serial)
             VALUES ($1, $2, $3)
-            ON CONFLICT (name)
-            DO UPDATE SET
-                serial = CASE
-                    WHEN catalog_zone_state.signature = EXCLUDED.signature THEN catalog_zone_state.serial
-                    ELSE GREATEST(catalog_zone_state.serial + 1, EXCLUDED.serial)
-                END,
-                signature = EXCLUDED.signature
-            RETURNING serial
+            ON CONFLICT (name) DO NOTHING
             "#,
         )
         .bind(name)
         .bind(signature)
         .bind(base_serial)
+        .execute(&mut **postgres_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        let (current_signature, current_serial) = sqlx::query_as::<_, (String, i32)>(
+            r#"
+            SELECT signature, serial
+            FROM catalog_zone_state
+            WHERE name = $1
+            FOR UPDATE
+            "#,
+        )
+        .bind(name)
         .fetch_one(&mut **postgres_tx)
         .await
-        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        if current_signature == signature {
+            return Ok(current_serial);
+        }
+
+        let next_serial = serial::from_wire(serial::advance(
+            serial::to_wire(current_serial),
+            serial::to_wire(base_serial),
+        ));
+        sqlx::query(
+            r#"
+            UPDATE catalog_zone_state
+            SET signature = $1, serial = $2
+            WHERE name = $3
+            "#,
+        )
+        .bind(signature)
+        .bind(next_serial)
+        .bind(name)
+        .execute(&mut **postgres_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        Ok(next_serial)
     }
 }
@@ -1,19 +1,31 @@
 mod api_token_repository_impl;
+mod audit_log_repository_impl;
+mod catalog_repository_impl;
 mod catalog_zone_state_repository_impl;
 mod record_repository_impl;
 mod tsig_key_repository_impl;
+mod webhook_repository_impl;
 mod zone_change_repository_impl;
+mod zone_notify_target_repository_impl;
 mod zone_repository_impl;
 mod zone_snapshot_repository_impl;
+mod zone_template_repository_impl;
 mod zone_token_policy_repository_impl;
+mod zone_transfer_acl_repository_impl;
 mod zone_tsig_policy_repository_impl;
 
 pub(crate) use api_token_repository_impl::PostgresApiTokenRepository;
+pub(crate) use audit_log_repository_impl::PostgresAuditLogRepository;
+pub(crate) use catalog_repository_impl::PostgresCatalogRepository;
 pub(crate) use catalog_zone_state_repository_impl::PostgresCatalogZoneStateRepository;
 pub(crate) use record_repository_impl::PostgresRecordRepository;
 pub(crate) use tsig_key_repository_impl::PostgresTsigKeyRepository;
+pub(crate) use webhook_repository_impl::PostgresWebhookRepository;
 pub(crate) use zone_change_repository_impl::PostgresZoneChangeRepository;
+pub(crate) use zone_notify_target_repository_impl::PostgresZoneNotifyTargetRepository;
 pub(crate) use zone_repository_impl::PostgresZoneRepository;
 pub(crate) use zone_snapshot_repository_impl::PostgresZoneSnapshotRepository;
+pub(crate) use zone_template_repository_impl::PostgresZoneTemplateRepository;
 pub(crate) use zone_token_policy_repository_impl::PostgresZoneTokenPolicyRepository;
+pub(crate) use zone_transfer_acl_repository_impl::PostgresZoneTransferAclRepository;
 pub(crate) use zone_tsig_policy_repository_impl::PostgresZoneTsigPolicyRepository;
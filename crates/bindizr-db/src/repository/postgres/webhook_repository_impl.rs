@@ -0,0 +1,221 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres, Row};
+
+use crate::{
+    error::DatabaseError,
+    model::webhook::{Webhook, WebhookDelivery},
+    repository::{RepositoryTx, WebhookRepository},
+};
+
+/// PostgreSQL-backed implementation of `WebhookRepository`.
+pub(crate) struct PostgresWebhookRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresWebhookRepository {
+    pub(crate) fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for PostgresWebhookRepository {
+    async fn create(&self, mut webhook: Webhook) -> Result<Webhook, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO webhooks (name, url, secret, zone_id, description)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(&webhook.name)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.zone_id)
+        .bind(&webhook.description)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        webhook.id = result.get::<i32, _>(0);
+        Ok(webhook)
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<Webhook>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let webhook = sqlx::query_as::<_, Webhook>(
+            "SELECT id, name, url, secret, zone_id, description, created_at FROM webhooks WHERE name = $1",
+        )
+        .bind(name)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    async fn list_all(&self) -> Result<Vec<Webhook>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            "SELECT id, name, url, secret, zone_id, description, created_at FROM webhooks ORDER BY name",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("DELETE FROM webhooks WHERE id = $1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn count_watching_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+    ) -> Result<u64, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM webhooks WHERE zone_id IS NULL OR zone_id = $1",
+        )
+        .bind(zone_id)
+        .fetch_one(&mut **postgres_tx)
+        .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn enqueue_deliveries_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        delivery: &WebhookDelivery,
+    ) -> Result<u64, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries
+                (webhook_id, event, zone_name, from_serial, to_serial, payload, status, attempts,
+                 next_attempt_at)
+            SELECT id, $1, $2, $3, $4, $5, $6, $7, $8
+            FROM webhooks
+            WHERE zone_id IS NULL OR zone_id = $9
+            "#,
+        )
+        .bind(&delivery.event)
+        .bind(&delivery.zone_name)
+        .bind(delivery.from_serial)
+        .bind(delivery.to_serial)
+        .bind(&delivery.payload)
+        .bind(&delivery.status)
+        .bind(delivery.attempts)
+        .bind(delivery.next_attempt_at)
+        .bind(zone_id)
+        .execute(&mut **postgres_tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn list_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, webhook_id, event, zone_name, from_serial, to_serial, payload, status,
+                   attempts, next_attempt_at, last_error, created_at, delivered_at
+            FROM webhook_deliveries
+            WHERE status = 'pending' AND next_attempt_at <= $1
+            ORDER BY id
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(i64::from(limit))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    async fn list_deliveries_by_webhook_id(
+        &self,
+        webhook_id: i32,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, webhook_id, event, zone_name, from_serial, to_serial, payload, status,
+                   attempts, next_attempt_at, last_error, created_at, delivered_at
+            FROM webhook_deliveries
+            WHERE webhook_id = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(i64::from(limit))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    async fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET payload = $1, status = $2, attempts = $3, next_attempt_at = $4, last_error = $5,
+                delivered_at = $6
+            WHERE id = $7
+            "#,
+        )
+        .bind(&delivery.payload)
+        .bind(&delivery.status)
+        .bind(delivery.attempts)
+        .bind(delivery.next_attempt_at)
+        .bind(&delivery.last_error)
+        .bind(delivery.delivered_at)
+        .bind(delivery.id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_finished_deliveries_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<u64, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            "DELETE FROM webhook_deliveries WHERE status <> 'pending' AND created_at < $1",
+        )
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
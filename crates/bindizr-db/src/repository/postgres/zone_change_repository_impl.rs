@@ -4,7 +4,10 @@ use sqlx::{AssertSqlSafe, Pool, Postgres};
 use crate::{
     error::DatabaseError,
     model::zone_change::ZoneChange,
-    repository::{LockLevel, RepositoryTx, ZoneChangeRepository, sql::lock_clause},
+    repository::{
+        LockLevel, RepositoryTx, ZoneChangeRepository,
+        sql::{lock_clause, serial_distance},
+    },
 };
 
 /// PostgreSQL-backed implementation of `ZoneChangeRepository`.
@@ -81,13 +84,13 @@ impl ZoneChangeRepository for PostgresZoneChangeRepository {
             r#"
             SELECT zone_id, serial, operation, record_name, record_type, record_value, record_ttl, record_priority
             FROM zone_changes
-            WHERE zone_id = $1 AND serial > $2 AND serial <= $3
-            ORDER BY serial, id
+            WHERE zone_id = $1 AND ((serial - $2) & 4294967295) BETWEEN 1 AND $3
+            ORDER BY (serial - $2) & 4294967295, id
             "#
         )
         .bind(zone_id)
-        .bind(from_serial)
-        .bind(to_serial)
+        .bind(i64::from(from_serial))
+        .bind(serial_distance(from_serial, to_serial))
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
@@ -106,15 +109,57 @@ impl ZoneChangeRepository for PostgresZoneChangeRepository {
             AssertSqlSafe(format!("{}{}", r#"
             SELECT zone_id, serial, operation, record_name, record_type, record_value, record_ttl, record_priority
             FROM zone_changes
-            WHERE zone_id = $1 AND serial > $2 AND serial <= $3
-            ORDER BY serial, id
+            WHERE zone_id = $1 AND ((serial - $2) & 4294967295) BETWEEN 1 AND $3
+            ORDER BY (serial - $2) & 4294967295, id
             "#, lock_clause(lock_level)))
         )
         .bind(zone_id)
-        .bind(from_serial)
-        .bind(to_serial)
+        .bind(i64::from(from_serial))
+        .bind(serial_distance(from_serial, to_serial))
         .fetch_all(&mut **pg_tx)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
     }
+
+    async fn count_through_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM zone_changes WHERE zone_id = $1 AND (($2 - serial) & 4294967295) >= $3",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .fetch_one(&mut **postgres_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn delete_through_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        sqlx::query(
+            "DELETE FROM zone_changes WHERE zone_id = $1 AND (($2 - serial) & 4294967295) >= $3",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .execute(&mut **postgres_tx)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
 }
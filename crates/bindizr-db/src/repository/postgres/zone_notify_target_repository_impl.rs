@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres, Row};
+
+use crate::{
+    error::DatabaseError, model::zone_notify_target::ZoneNotifyTarget,
+    repository::ZoneNotifyTargetRepository,
+};
+
+/// PostgreSQL-backed implementation of `ZoneNotifyTargetRepository`.
+pub(crate) struct PostgresZoneNotifyTargetRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresZoneNotifyTargetRepository {
+    pub(crate) fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ZoneNotifyTargetRepository for PostgresZoneNotifyTargetRepository {
+    async fn create(&self, mut entry: ZoneNotifyTarget) -> Result<ZoneNotifyTarget, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO zone_notify_targets (zone_id, kind, address, tsig_key_name)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(entry.zone_id)
+        .bind(entry.kind.as_str())
+        .bind(&entry.address)
+        .bind(&entry.tsig_key_name)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        entry.id = result.get::<i32, _>(0);
+        Ok(entry)
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<Option<ZoneNotifyTarget>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let entry = sqlx::query_as::<_, ZoneNotifyTarget>(
+            "SELECT id, zone_id, kind, address, tsig_key_name, created_at FROM zone_notify_targets WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(entry)
+    }
+
+    async fn list_by_zone_id(&self, zone_id: i32) -> Result<Vec<ZoneNotifyTarget>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let entries = sqlx::query_as::<_, ZoneNotifyTarget>(
+            "SELECT id, zone_id, kind, address, tsig_key_name, created_at FROM zone_notify_targets WHERE zone_id = $1 ORDER BY id",
+        )
+        .bind(zone_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("DELETE FROM zone_notify_targets WHERE id = $1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -32,8 +32,8 @@ impl ZoneRepository for PostgresZoneRepository {
 
         let result = sqlx::query(
             r#"
-            INSERT INTO zones (name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO zones (name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             RETURNING id
             "#,
         )
@@ -42,6 +42,7 @@ impl ZoneRepository for PostgresZoneRepository {
         .bind(&zone.admin_email)
         .bind(zone.ttl)
         .bind(zone.serial)
+        .bind(zone.serial_policy.as_str())
         .bind(zone.refresh)
         .bind(zone.retry)
         .bind(zone.expire)
@@ -61,7 +62,7 @@ impl ZoneRepository for PostgresZoneRepository {
     ) -> Result<Option<Zone>, DatabaseError> {
         let postgres_tx = tx.as_postgres()?;
 
-        let zone = sqlx::query_as::<_, Zone>(AssertSqlSafe(format!("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE id = $1{}",lock_clause(lock_level))))
+        let zone = sqlx::query_as::<_, Zone>(AssertSqlSafe(format!("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE id = $1{}",lock_clause(lock_level))))
             .bind(id)
             .fetch_optional(&mut **postgres_tx)
             .await?;
@@ -72,7 +73,7 @@ impl ZoneRepository for PostgresZoneRepository {
     async fn get_by_name(&self, name: &str) -> Result<Option<Zone>, DatabaseError> {
         let mut conn = self.pool.acquire().await?;
 
-        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = $1")
+        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = $1")
             .bind(name)
             .fetch_optional(&mut *conn)
             .await?;
@@ -89,7 +90,7 @@ impl ZoneRepository for PostgresZoneRepository {
         let postgres_tx = tx.as_postgres()?;
 
         let zone = sqlx::query_as::<_, Zone>(AssertSqlSafe(
-            format!("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = $1{}",
+            format!("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = $1{}",
             lock_clause(lock_level),
         )))
         .bind(name)
@@ -102,7 +103,7 @@ impl ZoneRepository for PostgresZoneRepository {
     async fn list_all(&self) -> Result<Vec<Zone>, DatabaseError> {
         let mut conn = self.pool.acquire().await?;
 
-        let zones = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name")
+        let zones = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name")
             .fetch_all(&mut *conn)
             .await?;
 
@@ -116,7 +117,7 @@ impl ZoneRepository for PostgresZoneRepository {
     ) -> Result<Vec<Zone>, DatabaseError> {
         let postgres_tx = tx.as_postgres()?;
 
-        let zones = sqlx::query_as::<_, Zone>(AssertSqlSafe(format!("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name{}",lock_clause(lock_level))))
+        let zones = sqlx::query_as::<_, Zone>(AssertSqlSafe(format!("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name{}",lock_clause(lock_level))))
             .fetch_all(&mut **postgres_tx)
             .await?;
 
@@ -129,7 +130,7 @@ impl ZoneRepository for PostgresZoneRepository {
 
         let zones = sqlx::query_as::<_, Zone>(
             r#"
-            SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at
             FROM zones
             WHERE ($1::TEXT IS NULL OR LOWER(name) = LOWER($2))
               AND ($3::INT4 IS NULL OR id = $4)
@@ -263,8 +264,9 @@ impl ZoneRepository for PostgresZoneRepository {
             r#"
             UPDATE zones 
             SET name = $1, primary_ns = $2, admin_email = $3,
-                ttl = $4, serial = $5, refresh = $6, retry = $7, expire = $8, minimum_ttl = $9
-            WHERE id = $10
+                ttl = $4, serial = $5, serial_policy = $6, refresh = $7, retry = $8, expire = $9,
+                minimum_ttl = $10
+            WHERE id = $11
             "#,
         )
         .bind(zone.name.as_str())
@@ -272,6 +274,7 @@ impl ZoneRepository for PostgresZoneRepository {
         .bind(&zone.admin_email)
         .bind(zone.ttl)
         .bind(zone.serial)
+        .bind(zone.serial_policy.as_str())
         .bind(zone.refresh)
         .bind(zone.retry)
         .bind(zone.expire)
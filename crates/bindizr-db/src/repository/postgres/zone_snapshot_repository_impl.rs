@@ -1,10 +1,14 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{AssertSqlSafe, Pool, Postgres};
 
 use crate::{
     error::DatabaseError,
     model::zone_snapshot::ZoneSnapshot,
-    repository::{LockLevel, RepositoryTx, ZoneSnapshotRepository, sql::lock_clause},
+    repository::{
+        LockLevel, RepositoryTx, ZoneSnapshotRepository,
+        sql::{lock_clause, serial_distance},
+    },
 };
 
 /// PostgreSQL-backed implementation of `ZoneSnapshotRepository`.
@@ -29,8 +33,9 @@ impl ZoneSnapshotRepository for PostgresZoneSnapshotRepository {
 
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            INSERT INTO zone_soa_history (zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            INSERT INTO zone_soa_history (zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                                          actor, tsig_key_name, source_address, transport, request_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             ON CONFLICT (zone_id, serial)
             DO UPDATE SET
                 primary_ns = EXCLUDED.primary_ns,
@@ -39,8 +44,14 @@ impl ZoneSnapshotRepository for PostgresZoneSnapshotRepository {
                 refresh = EXCLUDED.refresh,
                 retry = EXCLUDED.retry,
                 expire = EXCLUDED.expire,
-                minimum_ttl = EXCLUDED.minimum_ttl
-            RETURNING id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+                minimum_ttl = EXCLUDED.minimum_ttl,
+                actor = EXCLUDED.actor,
+                tsig_key_name = EXCLUDED.tsig_key_name,
+                source_address = EXCLUDED.source_address,
+                transport = EXCLUDED.transport,
+                request_id = EXCLUDED.request_id
+            RETURNING id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             "#,
         )
         .bind(snapshot.zone_id)
@@ -52,6 +63,11 @@ impl ZoneSnapshotRepository for PostgresZoneSnapshotRepository {
         .bind(snapshot.retry)
         .bind(snapshot.expire)
         .bind(snapshot.minimum_ttl)
+        .bind(&snapshot.attribution.actor)
+        .bind(&snapshot.attribution.tsig_key_name)
+        .bind(&snapshot.attribution.source_address)
+        .bind(&snapshot.attribution.transport)
+        .bind(&snapshot.attribution.request_id)
         .fetch_one(&mut **postgres_tx)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
@@ -64,7 +80,8 @@ impl ZoneSnapshotRepository for PostgresZoneSnapshotRepository {
     ) -> Result<Option<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = $1 AND serial = $2
             "#,
@@ -84,14 +101,15 @@ impl ZoneSnapshotRepository for PostgresZoneSnapshotRepository {
     ) -> Result<Vec<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
-            WHERE zone_id = $1 AND serial >= $2 AND serial <= $3
+            WHERE zone_id = $1 AND ((serial - $2) & 4294967295) <= $3
             "#,
         )
         .bind(zone_id)
-        .bind(from_serial)
-        .bind(to_serial)
+        .bind(i64::from(from_serial))
+        .bind(serial_distance(from_serial, to_serial))
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
@@ -104,10 +122,11 @@ impl ZoneSnapshotRepository for PostgresZoneSnapshotRepository {
     ) -> Result<Vec<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = $1
-            ORDER BY serial DESC
+            ORDER BY ((SELECT z.serial FROM zones z WHERE z.id = zone_soa_history.zone_id)::BIGINT - serial) & 4294967295
             LIMIT $2 OFFSET $3
             "#,
         )
@@ -140,7 +159,8 @@ impl ZoneSnapshotRepository for PostgresZoneSnapshotRepository {
 
         sqlx::query_as::<_, ZoneSnapshot>(
             AssertSqlSafe(format!("{}{}", r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = $1 AND serial = $2
             "#, lock_clause(lock_level))),
@@ -151,4 +171,98 @@ impl ZoneSnapshotRepository for PostgresZoneSnapshotRepository {
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
     }
+
+    async fn find_serial_by_rank_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        rank: u64,
+    ) -> Result<Option<i32>, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        sqlx::query_scalar(
+            r#"
+            SELECT serial
+            FROM zone_soa_history
+            WHERE zone_id = $1
+            ORDER BY ($2 - serial) & 4294967295
+            LIMIT 1 OFFSET $3
+            "#,
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(i64::try_from(rank).unwrap_or(i64::MAX))
+        .fetch_optional(&mut **postgres_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn find_oldest_serial_since_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        since: DateTime<Utc>,
+    ) -> Result<Option<i32>, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        sqlx::query_scalar(
+            r#"
+            SELECT serial
+            FROM zone_soa_history
+            WHERE zone_id = $1 AND created_at >= $2
+            ORDER BY ($3 - serial) & 4294967295 DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(zone_id)
+        .bind(since)
+        .bind(i64::from(current_serial))
+        .fetch_optional(&mut **postgres_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn count_before_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM zone_soa_history WHERE zone_id = $1 AND (($2 - serial) & 4294967295) > $3",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .fetch_one(&mut **postgres_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn delete_before_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        sqlx::query(
+            "DELETE FROM zone_soa_history WHERE zone_id = $1 AND (($2 - serial) & 4294967295) > $3",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .execute(&mut **postgres_tx)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
 }
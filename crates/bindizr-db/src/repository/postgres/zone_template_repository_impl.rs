@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres, Row};
+
+use crate::{
+    error::DatabaseError,
+    model::zone_template::{ZoneTemplate, ZoneTemplateLink},
+    repository::{RepositoryTx, ZoneTemplateRepository},
+};
+
+/// PostgreSQL-backed implementation of `ZoneTemplateRepository`.
+pub(crate) struct PostgresZoneTemplateRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresZoneTemplateRepository {
+    pub(crate) fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ZoneTemplateRepository for PostgresZoneTemplateRepository {
+    async fn create(&self, mut template: ZoneTemplate) -> Result<ZoneTemplate, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO zone_templates (name, description, records, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+        )
+        .bind(&template.name)
+        .bind(&template.description)
+        .bind(&template.records)
+        .bind(template.created_at)
+        .bind(template.updated_at)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        template.id = result.get::<i32, _>(0);
+        Ok(template)
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<ZoneTemplate>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let template = sqlx::query_as::<_, ZoneTemplate>(
+            "SELECT id, name, description, records, created_at, updated_at FROM zone_templates WHERE name = $1",
+        )
+        .bind(name)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(template)
+    }
+
+    async fn list_all(&self) -> Result<Vec<ZoneTemplate>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let templates = sqlx::query_as::<_, ZoneTemplate>(
+            "SELECT id, name, description, records, created_at, updated_at FROM zone_templates ORDER BY name",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(templates)
+    }
+
+    async fn update(&self, template: &ZoneTemplate) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            "UPDATE zone_templates SET description = $1, records = $2, updated_at = $3 WHERE id = $4",
+        )
+        .bind(&template.description)
+        .bind(&template.records)
+        .bind(template.updated_at)
+        .bind(template.id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("DELETE FROM zone_templates WHERE id = $1")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn create_link_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        mut link: ZoneTemplateLink,
+    ) -> Result<ZoneTemplateLink, DatabaseError> {
+        let postgres_tx = tx.as_postgres()?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO zone_template_links (zone_id, template_id, variables, applied_records)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(link.zone_id)
+        .bind(link.template_id)
+        .bind(&link.variables)
+        .bind(&link.applied_records)
+        .fetch_one(&mut **postgres_tx)
+        .await?;
+
+        link.id = result.get::<i32, _>(0);
+        Ok(link)
+    }
+
+    async fn list_links_by_template_id(
+        &self,
+        template_id: i32,
+    ) -> Result<Vec<ZoneTemplateLink>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let links = sqlx::query_as::<_, ZoneTemplateLink>(
+            "SELECT id, zone_id, template_id, variables, applied_records, created_at FROM zone_template_links WHERE template_id = $1 ORDER BY id",
+        )
+        .bind(template_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(links)
+    }
+
+    async fn update_link_applied_records(
+        &self,
+        id: i32,
+        applied_records: &str,
+    ) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("UPDATE zone_template_links SET applied_records = $1 WHERE id = $2")
+            .bind(applied_records)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 //! SQL fragments and search-term normalization shared by the per-backend
 //! filter queries, rendered from the core types so no backend can drift.
 
-use bindizr_core::dns::name::OwnerName;
+use bindizr_core::dns::{name::OwnerName, serial};
 
 use super::{super::model::record::NAME_LIKE_RECORD_TYPES, LockLevel};
 
@@ -15,6 +15,13 @@ pub(crate) fn lock_clause(lock_level: LockLevel) -> &'static str {
     }
 }
 
+/// How many steps stored serial `to` lies ahead of `from` (RFC 1982). History
+/// queries order serials by this distance, written `(serial - ?) & 4294967295`
+/// in SQL, so a zone's history stays in order across a serial wrap.
+pub(crate) fn serial_distance(from: i32, to: i32) -> i64 {
+    i64::from(serial::distance(serial::to_wire(from), serial::to_wire(to)))
+}
+
 /// The owner name the apex is stored under, as an SQL literal.
 pub(crate) fn apex_owner_sql() -> String {
     format!("'{}'", OwnerName::apex().to_stored())
@@ -53,7 +60,7 @@ pub(crate) fn like_pattern(value: Option<&str>) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{apex_owner_sql, name_like_types_sql};
+    use super::{apex_owner_sql, name_like_types_sql, serial_distance};
 
     #[test]
     fn apex_owner_renders_as_a_quoted_sql_literal() {
@@ -68,4 +75,13 @@ mod tests {
         // are part of the query's syntax.
         assert_eq!(name_like_types_sql(), "'CNAME','NS','PTR','MX','SRV'");
     }
+
+    #[test]
+    fn serial_distance_counts_across_the_wrap() {
+        assert_eq!(serial_distance(3, 10), 7);
+        // Stored -1 is wire serial 4294967295; one step on is 0.
+        assert_eq!(serial_distance(-1, 0), 1);
+        assert_eq!(serial_distance(i32::MAX, i32::MIN), 1);
+        assert_eq!(serial_distance(10, 3), 4294967289);
+    }
 }
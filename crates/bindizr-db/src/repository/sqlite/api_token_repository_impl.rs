@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{Pool, Sqlite};
 
 use crate::{error::DatabaseError, model::api_token::ApiToken, repository::ApiTokenRepository};
@@ -21,14 +22,15 @@ impl ApiTokenRepository for SqliteApiTokenRepository {
 
         let result = sqlx::query(
             r#"
-            INSERT INTO api_tokens (name, token, description, is_global, expires_at)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO api_tokens (name, token, description, is_global, role, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&token.name)
         .bind(&token.token)
         .bind(&token.description)
-        .bind(token.is_global)
+        .bind(token.is_global())
+        .bind(token.role.as_str())
         .bind(token.expires_at)
         .execute(&mut *conn)
         .await?;
@@ -41,7 +43,7 @@ impl ApiTokenRepository for SqliteApiTokenRepository {
         let mut conn = self.pool.acquire().await?;
 
         let token = sqlx::query_as::<_, ApiToken>(
-            "SELECT id, name, token, description, is_global, expires_at, created_at, last_used_at FROM api_tokens WHERE name = ?"
+            "SELECT id, name, token, description, role, expires_at, created_at, last_used_at, previous_token, previous_token_expires_at FROM api_tokens WHERE name = ?"
         )
         .bind(name)
         .fetch_optional(&mut *conn)
@@ -55,9 +57,10 @@ impl ApiTokenRepository for SqliteApiTokenRepository {
         let mut conn = self.pool.acquire().await?;
 
         let api_token = sqlx::query_as::<_, ApiToken>(
-            "SELECT id, name, token, description, is_global, expires_at, created_at, last_used_at FROM api_tokens WHERE token = ?"
+            "SELECT id, name, token, description, role, expires_at, created_at, last_used_at, previous_token, previous_token_expires_at FROM api_tokens WHERE token = ? OR previous_token = ?"
         )
         .bind(token)
+        .bind(token)
         .fetch_optional(&mut *conn)
         .await
         ?;
@@ -69,7 +72,7 @@ impl ApiTokenRepository for SqliteApiTokenRepository {
         let mut conn = self.pool.acquire().await?;
 
         let tokens = sqlx::query_as::<_, ApiToken>(
-            "SELECT id, name, token, description, is_global, expires_at, created_at, last_used_at FROM api_tokens ORDER BY created_at DESC"
+            "SELECT id, name, token, description, role, expires_at, created_at, last_used_at, previous_token, previous_token_expires_at FROM api_tokens ORDER BY created_at DESC"
         )
         .fetch_all(&mut *conn)
         .await
@@ -98,6 +101,32 @@ impl ApiTokenRepository for SqliteApiTokenRepository {
         Ok(token)
     }
 
+    async fn rotate(
+        &self,
+        id: i32,
+        token: &str,
+        previous_token: Option<&str>,
+        previous_token_expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE api_tokens
+            SET token = ?, previous_token = ?, previous_token_expires_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(token)
+        .bind(previous_token)
+        .bind(previous_token_expires_at)
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
     async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
         let mut conn = self.pool.acquire().await?;
 
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    error::DatabaseError,
+    model::zone_audit::ZoneAuditEntry,
+    repository::{AuditFilter, AuditLogRepository, RepositoryTx},
+};
+
+/// SQLite-backed implementation of `AuditLogRepository`.
+pub(crate) struct SqliteAuditLogRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteAuditLogRepository {
+    pub(crate) fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for SqliteAuditLogRepository {
+    async fn insert_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        entry: ZoneAuditEntry,
+    ) -> Result<(), DatabaseError> {
+        let sqlite_tx = tx.as_sqlite()?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO zone_audit_log (zone_name, serial, action, actor, tsig_key_name,
+                                        source_address, transport, request_id, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&entry.zone_name)
+        .bind(entry.serial)
+        .bind(&entry.action)
+        .bind(&entry.attribution.actor)
+        .bind(&entry.attribution.tsig_key_name)
+        .bind(&entry.attribution.source_address)
+        .bind(&entry.attribution.transport)
+        .bind(&entry.attribution.request_id)
+        .bind(entry.created_at)
+        .execute(&mut **sqlite_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list(&self, filter: AuditFilter) -> Result<Vec<ZoneAuditEntry>, DatabaseError> {
+        sqlx::query_as::<_, ZoneAuditEntry>(
+            r#"
+            SELECT zone_name, serial, action, actor, tsig_key_name, source_address,
+                   transport, request_id, created_at
+            FROM zone_audit_log
+            WHERE (? IS NULL OR zone_name = ?)
+              AND (? IS NULL OR actor = ?)
+              AND (? IS NULL OR transport = ?)
+              AND (? IS NULL OR datetime(created_at) >= datetime(?))
+              AND (? IS NULL OR datetime(created_at) <= datetime(?))
+            ORDER BY created_at DESC, id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(&filter.zone_name)
+        .bind(&filter.zone_name)
+        .bind(&filter.actor)
+        .bind(&filter.actor)
+        .bind(&filter.transport)
+        .bind(&filter.transport)
+        .bind(filter.since)
+        .bind(filter.since)
+        .bind(filter.until)
+        .bind(filter.until)
+        .bind(filter.limit.map(i64::from).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn delete_before(&self, before: DateTime<Utc>) -> Result<u64, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result =
+            sqlx::query("DELETE FROM zone_audit_log WHERE datetime(created_at) < datetime(?)")
+                .bind(before)
+                .execute(&mut *conn)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+}
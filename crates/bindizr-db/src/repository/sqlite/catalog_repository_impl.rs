@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    error::DatabaseError,
+    model::catalog::{Catalog, ZoneCatalogProperty},
+    repository::CatalogRepository,
+};
+
+/// SQLite-backed implementation of `CatalogRepository`.
+pub(crate) struct SqliteCatalogRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteCatalogRepository {
+    pub(crate) fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CatalogRepository for SqliteCatalogRepository {
+    async fn create(&self, mut catalog: Catalog) -> Result<Catalog, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO catalogs (name, description)
+            VALUES (?, ?)
+            "#,
+        )
+        .bind(&catalog.name)
+        .bind(&catalog.description)
+        .execute(&mut *conn)
+        .await?;
+
+        catalog.id = result.last_insert_rowid() as i32;
+        Ok(catalog)
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<Catalog>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let catalog = sqlx::query_as::<_, Catalog>(
+            "SELECT id, name, description, created_at FROM catalogs WHERE name = ?",
+        )
+        .bind(name)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(catalog)
+    }
+
+    async fn list_all(&self) -> Result<Vec<Catalog>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let catalogs = sqlx::query_as::<_, Catalog>(
+            "SELECT id, name, description, created_at FROM catalogs ORDER BY name",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(catalogs)
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("DELETE FROM catalogs WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_properties_by_zone_id(
+        &self,
+        zone_id: i32,
+    ) -> Result<Vec<ZoneCatalogProperty>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let properties = sqlx::query_as::<_, ZoneCatalogProperty>(
+            "SELECT id, zone_id, name, value FROM zone_catalog_properties WHERE zone_id = ? ORDER BY name",
+        )
+        .bind(zone_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(properties)
+    }
+
+    async fn list_all_properties(&self) -> Result<Vec<ZoneCatalogProperty>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let properties = sqlx::query_as::<_, ZoneCatalogProperty>(
+            "SELECT id, zone_id, name, value FROM zone_catalog_properties ORDER BY zone_id, name",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(properties)
+    }
+
+    async fn replace_properties(
+        &self,
+        zone_id: i32,
+        properties: &[(String, String)],
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        sqlx::query("DELETE FROM zone_catalog_properties WHERE zone_id = ?")
+            .bind(zone_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (name, value) in properties {
+            sqlx::query(
+                r#"
+                INSERT INTO zone_catalog_properties (zone_id, name, value)
+                VALUES (?, ?, ?)
+                "#,
+            )
+            .bind(zone_id)
+            .bind(name)
+            .bind(value)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
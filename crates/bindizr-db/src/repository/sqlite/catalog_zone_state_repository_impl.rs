@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use bindizr_core::dns::serial;
 
 use crate::{
     error::DatabaseError,
@@ -20,19 +21,15 @@ impl CatalogZoneStateRepository for SqliteCatalogZoneStateRepository {
     ) -> Result<i32, DatabaseError> {
         let sqlite_tx = tx.as_sqlite()?;
 
-        // Advance the catalog serial only when the signature changes, kept
-        // monotonic, so secondaries re-transfer the catalog zone only on real changes.
+        // Advance the catalog serial only when the signature changes, so
+        // secondaries re-transfer the catalog zone only on real changes. The
+        // next serial is worked out here rather than in SQL so it follows
+        // RFC 1982 order and wraps instead of overflowing the column.
         sqlx::query(
             r#"
             INSERT INTO catalog_zone_state (name, signature, serial)
             VALUES (?, ?, ?)
-            ON CONFLICT(name)
-            DO UPDATE SET
-                serial = CASE
-                    WHEN signature = excluded.signature THEN serial
-                    ELSE max(serial + 1, excluded.serial)
-                END,
-                signature = excluded.signature
+            ON CONFLICT(name) DO NOTHING
             "#,
         )
         .bind(name)
@@ -42,9 +39,9 @@ impl CatalogZoneStateRepository for SqliteCatalogZoneStateRepository {
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
 
-        sqlx::query_scalar::<_, i32>(
+        let (current_signature, current_serial) = sqlx::query_as::<_, (String, i32)>(
             r#"
-            SELECT serial
+            SELECT signature, serial
             FROM catalog_zone_state
             WHERE name = ?
             "#,
@@ -52,6 +49,30 @@ impl CatalogZoneStateRepository for SqliteCatalogZoneStateRepository {
         .bind(name)
         .fetch_one(&mut **sqlite_tx)
         .await
-        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        if current_signature == signature {
+            return Ok(current_serial);
+        }
+
+        let next_serial = serial::from_wire(serial::advance(
+            serial::to_wire(current_serial),
+            serial::to_wire(base_serial),
+        ));
+        sqlx::query(
+            r#"
+            UPDATE catalog_zone_state
+            SET signature = ?, serial = ?
+            WHERE name = ?
+            "#,
+        )
+        .bind(signature)
+        .bind(next_serial)
+        .bind(name)
+        .execute(&mut **sqlite_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+
+        Ok(next_serial)
     }
 }
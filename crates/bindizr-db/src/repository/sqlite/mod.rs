@@ -1,19 +1,31 @@
 mod api_token_repository_impl;
+mod audit_log_repository_impl;
+mod catalog_repository_impl;
 mod catalog_zone_state_repository_impl;
 mod record_repository_impl;
 mod tsig_key_repository_impl;
+mod webhook_repository_impl;
 mod zone_change_repository_impl;
+mod zone_notify_target_repository_impl;
 mod zone_repository_impl;
 mod zone_snapshot_repository_impl;
+mod zone_template_repository_impl;
 mod zone_token_policy_repository_impl;
+mod zone_transfer_acl_repository_impl;
 mod zone_tsig_policy_repository_impl;
 
 pub(crate) use api_token_repository_impl::SqliteApiTokenRepository;
+pub(crate) use audit_log_repository_impl::SqliteAuditLogRepository;
+pub(crate) use catalog_repository_impl::SqliteCatalogRepository;
 pub(crate) use catalog_zone_state_repository_impl::SqliteCatalogZoneStateRepository;
 pub(crate) use record_repository_impl::SqliteRecordRepository;
 pub(crate) use tsig_key_repository_impl::SqliteTsigKeyRepository;
+pub(crate) use webhook_repository_impl::SqliteWebhookRepository;
 pub(crate) use zone_change_repository_impl::SqliteZoneChangeRepository;
+pub(crate) use zone_notify_target_repository_impl::SqliteZoneNotifyTargetRepository;
 pub(crate) use zone_repository_impl::SqliteZoneRepository;
 pub(crate) use zone_snapshot_repository_impl::SqliteZoneSnapshotRepository;
+pub(crate) use zone_template_repository_impl::SqliteZoneTemplateRepository;
 pub(crate) use zone_token_policy_repository_impl::SqliteZoneTokenPolicyRepository;
+pub(crate) use zone_transfer_acl_repository_impl::SqliteZoneTransferAclRepository;
 pub(crate) use zone_tsig_policy_repository_impl::SqliteZoneTsigPolicyRepository;
@@ -0,0 +1,220 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    error::DatabaseError,
+    model::webhook::{Webhook, WebhookDelivery},
+    repository::{RepositoryTx, WebhookRepository},
+};
+
+/// SQLite-backed implementation of `WebhookRepository`.
+pub(crate) struct SqliteWebhookRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteWebhookRepository {
+    pub(crate) fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for SqliteWebhookRepository {
+    async fn create(&self, mut webhook: Webhook) -> Result<Webhook, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO webhooks (name, url, secret, zone_id, description)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&webhook.name)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.zone_id)
+        .bind(&webhook.description)
+        .execute(&mut *conn)
+        .await?;
+
+        webhook.id = result.last_insert_rowid() as i32;
+        Ok(webhook)
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<Webhook>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let webhook = sqlx::query_as::<_, Webhook>(
+            "SELECT id, name, url, secret, zone_id, description, created_at FROM webhooks WHERE name = ?",
+        )
+        .bind(name)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    async fn list_all(&self) -> Result<Vec<Webhook>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            "SELECT id, name, url, secret, zone_id, description, created_at FROM webhooks ORDER BY name",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("DELETE FROM webhooks WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn count_watching_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+    ) -> Result<u64, DatabaseError> {
+        let sqlite_tx = tx.as_sqlite()?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM webhooks WHERE zone_id IS NULL OR zone_id = ?",
+        )
+        .bind(zone_id)
+        .fetch_one(&mut **sqlite_tx)
+        .await?;
+
+        Ok(count as u64)
+    }
+
+    async fn enqueue_deliveries_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        delivery: &WebhookDelivery,
+    ) -> Result<u64, DatabaseError> {
+        let sqlite_tx = tx.as_sqlite()?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries
+                (webhook_id, event, zone_name, from_serial, to_serial, payload, status, attempts,
+                 next_attempt_at)
+            SELECT id, ?, ?, ?, ?, ?, ?, ?, ?
+            FROM webhooks
+            WHERE zone_id IS NULL OR zone_id = ?
+            "#,
+        )
+        .bind(&delivery.event)
+        .bind(&delivery.zone_name)
+        .bind(delivery.from_serial)
+        .bind(delivery.to_serial)
+        .bind(&delivery.payload)
+        .bind(&delivery.status)
+        .bind(delivery.attempts)
+        .bind(delivery.next_attempt_at)
+        .bind(zone_id)
+        .execute(&mut **sqlite_tx)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn list_due_deliveries(
+        &self,
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, webhook_id, event, zone_name, from_serial, to_serial, payload, status,
+                   attempts, next_attempt_at, last_error, created_at, delivered_at
+            FROM webhook_deliveries
+            WHERE status = 'pending' AND datetime(next_attempt_at) <= datetime(?)
+            ORDER BY id
+            LIMIT ?
+            "#,
+        )
+        .bind(now)
+        .bind(i64::from(limit))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    async fn list_deliveries_by_webhook_id(
+        &self,
+        webhook_id: i32,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, webhook_id, event, zone_name, from_serial, to_serial, payload, status,
+                   attempts, next_attempt_at, last_error, created_at, delivered_at
+            FROM webhook_deliveries
+            WHERE webhook_id = ?
+            ORDER BY id DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(i64::from(limit))
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    async fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET payload = ?, status = ?, attempts = ?, next_attempt_at = ?, last_error = ?,
+                delivered_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&delivery.payload)
+        .bind(&delivery.status)
+        .bind(delivery.attempts)
+        .bind(delivery.next_attempt_at)
+        .bind(&delivery.last_error)
+        .bind(delivery.delivered_at)
+        .bind(delivery.id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_finished_deliveries_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> Result<u64, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            "DELETE FROM webhook_deliveries WHERE status <> 'pending' AND datetime(created_at) < datetime(?)",
+        )
+        .bind(before)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    error::DatabaseError, model::zone_notify_target::ZoneNotifyTarget,
+    repository::ZoneNotifyTargetRepository,
+};
+
+/// SQLite-backed implementation of `ZoneNotifyTargetRepository`.
+pub(crate) struct SqliteZoneNotifyTargetRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteZoneNotifyTargetRepository {
+    pub(crate) fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ZoneNotifyTargetRepository for SqliteZoneNotifyTargetRepository {
+    async fn create(&self, mut entry: ZoneNotifyTarget) -> Result<ZoneNotifyTarget, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO zone_notify_targets (zone_id, kind, address, tsig_key_name)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(entry.zone_id)
+        .bind(entry.kind.as_str())
+        .bind(&entry.address)
+        .bind(&entry.tsig_key_name)
+        .execute(&mut *conn)
+        .await?;
+
+        entry.id = result.last_insert_rowid() as i32;
+        Ok(entry)
+    }
+
+    async fn get_by_id(&self, id: i32) -> Result<Option<ZoneNotifyTarget>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let entry = sqlx::query_as::<_, ZoneNotifyTarget>(
+            "SELECT id, zone_id, kind, address, tsig_key_name, created_at FROM zone_notify_targets WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(entry)
+    }
+
+    async fn list_by_zone_id(&self, zone_id: i32) -> Result<Vec<ZoneNotifyTarget>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let entries = sqlx::query_as::<_, ZoneNotifyTarget>(
+            "SELECT id, zone_id, kind, address, tsig_key_name, created_at FROM zone_notify_targets WHERE zone_id = ? ORDER BY id",
+        )
+        .bind(zone_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("DELETE FROM zone_notify_targets WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
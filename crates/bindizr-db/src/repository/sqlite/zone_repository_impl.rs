@@ -29,8 +29,8 @@ impl ZoneRepository for SqliteZoneRepository {
 
         let result = sqlx::query(
             r#"
-            INSERT INTO zones (name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO zones (name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(zone.name.as_str())
@@ -38,6 +38,7 @@ impl ZoneRepository for SqliteZoneRepository {
         .bind(&zone.admin_email)
         .bind(zone.ttl)
         .bind(zone.serial)
+        .bind(zone.serial_policy.as_str())
         .bind(zone.refresh)
         .bind(zone.retry)
         .bind(zone.expire)
@@ -57,7 +58,7 @@ impl ZoneRepository for SqliteZoneRepository {
     ) -> Result<Option<Zone>, DatabaseError> {
         let sqlite_tx = tx.as_sqlite()?;
 
-        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE id = ?")
+        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE id = ?")
             .bind(id)
             .fetch_optional(&mut **sqlite_tx)
             .await?;
@@ -68,7 +69,7 @@ impl ZoneRepository for SqliteZoneRepository {
     async fn get_by_name(&self, name: &str) -> Result<Option<Zone>, DatabaseError> {
         let mut conn = self.pool.acquire().await?;
 
-        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = ?")
+        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = ?")
             .bind(name)
             .fetch_optional(&mut *conn)
             .await?;
@@ -84,7 +85,7 @@ impl ZoneRepository for SqliteZoneRepository {
     ) -> Result<Option<Zone>, DatabaseError> {
         let sqlite_tx = tx.as_sqlite()?;
 
-        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = ?")
+        let zone = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones WHERE name = ?")
             .bind(name)
             .fetch_optional(&mut **sqlite_tx)
             .await?;
@@ -95,7 +96,7 @@ impl ZoneRepository for SqliteZoneRepository {
     async fn list_all(&self) -> Result<Vec<Zone>, DatabaseError> {
         let mut conn = self.pool.acquire().await?;
 
-        let zones = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name")
+        let zones = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name")
             .fetch_all(&mut *conn)
             .await?;
 
@@ -109,7 +110,7 @@ impl ZoneRepository for SqliteZoneRepository {
     ) -> Result<Vec<Zone>, DatabaseError> {
         let sqlite_tx = tx.as_sqlite()?;
 
-        let zones = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name")
+        let zones = sqlx::query_as::<_, Zone>("SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at FROM zones ORDER BY name")
             .fetch_all(&mut **sqlite_tx)
             .await?;
 
@@ -122,7 +123,7 @@ impl ZoneRepository for SqliteZoneRepository {
 
         let zones = sqlx::query_as::<_, Zone>(
             r#"
-            SELECT id, name, primary_ns, admin_email, ttl, serial, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, name, primary_ns, admin_email, ttl, serial, serial_policy, refresh, retry, expire, minimum_ttl, created_at
             FROM zones
             WHERE (? IS NULL OR LOWER(name) = LOWER(?))
               AND (? IS NULL OR id = ?)
@@ -258,7 +259,7 @@ impl ZoneRepository for SqliteZoneRepository {
             r#"
             UPDATE zones 
             SET name = ?, primary_ns = ?, admin_email = ?,
-                ttl = ?, serial = ?, refresh = ?, retry = ?, expire = ?, minimum_ttl = ?
+                ttl = ?, serial = ?, serial_policy = ?, refresh = ?, retry = ?, expire = ?, minimum_ttl = ?
             WHERE id = ?
             "#,
         )
@@ -267,6 +268,7 @@ impl ZoneRepository for SqliteZoneRepository {
         .bind(&zone.admin_email)
         .bind(zone.ttl)
         .bind(zone.serial)
+        .bind(zone.serial_policy.as_str())
         .bind(zone.refresh)
         .bind(zone.retry)
         .bind(zone.expire)
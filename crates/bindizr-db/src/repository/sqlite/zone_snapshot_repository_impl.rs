@@ -1,10 +1,11 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sqlx::{Pool, Sqlite};
 
 use crate::{
     error::DatabaseError,
     model::zone_snapshot::ZoneSnapshot,
-    repository::{LockLevel, RepositoryTx, ZoneSnapshotRepository},
+    repository::{LockLevel, RepositoryTx, ZoneSnapshotRepository, sql::serial_distance},
 };
 
 /// SQLite-backed implementation of `ZoneSnapshotRepository`.
@@ -29,8 +30,9 @@ impl ZoneSnapshotRepository for SqliteZoneSnapshotRepository {
 
         sqlx::query(
             r#"
-            INSERT INTO zone_soa_history (zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO zone_soa_history (zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                                          actor, tsig_key_name, source_address, transport, request_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(zone_id, serial)
             DO UPDATE SET
                 primary_ns = excluded.primary_ns,
@@ -39,7 +41,12 @@ impl ZoneSnapshotRepository for SqliteZoneSnapshotRepository {
                 refresh = excluded.refresh,
                 retry = excluded.retry,
                 expire = excluded.expire,
-                minimum_ttl = excluded.minimum_ttl
+                minimum_ttl = excluded.minimum_ttl,
+                actor = excluded.actor,
+                tsig_key_name = excluded.tsig_key_name,
+                source_address = excluded.source_address,
+                transport = excluded.transport,
+                request_id = excluded.request_id
             "#,
         )
         .bind(snapshot.zone_id)
@@ -51,13 +58,19 @@ impl ZoneSnapshotRepository for SqliteZoneSnapshotRepository {
         .bind(snapshot.retry)
         .bind(snapshot.expire)
         .bind(snapshot.minimum_ttl)
+        .bind(&snapshot.attribution.actor)
+        .bind(&snapshot.attribution.tsig_key_name)
+        .bind(&snapshot.attribution.source_address)
+        .bind(&snapshot.attribution.transport)
+        .bind(&snapshot.attribution.request_id)
         .execute(&mut **sqlite_tx)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
 
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = ? AND serial = ?
             "#,
@@ -76,7 +89,8 @@ impl ZoneSnapshotRepository for SqliteZoneSnapshotRepository {
     ) -> Result<Option<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = ? AND serial = ?
             "#,
@@ -96,14 +110,15 @@ impl ZoneSnapshotRepository for SqliteZoneSnapshotRepository {
     ) -> Result<Vec<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
-            WHERE zone_id = ? AND serial >= ? AND serial <= ?
+            WHERE zone_id = ? AND ((serial - ?) & 4294967295) <= ?
             "#,
         )
         .bind(zone_id)
-        .bind(from_serial)
-        .bind(to_serial)
+        .bind(i64::from(from_serial))
+        .bind(serial_distance(from_serial, to_serial))
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
@@ -117,10 +132,11 @@ impl ZoneSnapshotRepository for SqliteZoneSnapshotRepository {
     ) -> Result<Vec<ZoneSnapshot>, DatabaseError> {
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = ?
-            ORDER BY serial DESC
+            ORDER BY ((SELECT z.serial FROM zones z WHERE z.id = zone_soa_history.zone_id) - serial) & 4294967295
             LIMIT ? OFFSET ?
             "#,
         )
@@ -153,7 +169,8 @@ impl ZoneSnapshotRepository for SqliteZoneSnapshotRepository {
 
         sqlx::query_as::<_, ZoneSnapshot>(
             r#"
-            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl, created_at
+            SELECT id, zone_id, serial, primary_ns, admin_email, ttl, refresh, retry, expire, minimum_ttl,
+                   actor, tsig_key_name, source_address, transport, request_id, created_at
             FROM zone_soa_history
             WHERE zone_id = ? AND serial = ?
             "#,
@@ -164,4 +181,98 @@ impl ZoneSnapshotRepository for SqliteZoneSnapshotRepository {
         .await
         .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
     }
+
+    async fn find_serial_by_rank_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        rank: u64,
+    ) -> Result<Option<i32>, DatabaseError> {
+        let sqlite_tx = tx.as_sqlite()?;
+
+        sqlx::query_scalar(
+            r#"
+            SELECT serial
+            FROM zone_soa_history
+            WHERE zone_id = ?
+            ORDER BY (? - serial) & 4294967295
+            LIMIT 1 OFFSET ?
+            "#,
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(i64::try_from(rank).unwrap_or(i64::MAX))
+        .fetch_optional(&mut **sqlite_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn find_oldest_serial_since_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        since: DateTime<Utc>,
+    ) -> Result<Option<i32>, DatabaseError> {
+        let sqlite_tx = tx.as_sqlite()?;
+
+        sqlx::query_scalar(
+            r#"
+            SELECT serial
+            FROM zone_soa_history
+            WHERE zone_id = ? AND datetime(created_at) >= datetime(?)
+            ORDER BY (? - serial) & 4294967295 DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(zone_id)
+        .bind(since)
+        .bind(i64::from(current_serial))
+        .fetch_optional(&mut **sqlite_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
+
+    async fn count_before_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let sqlite_tx = tx.as_sqlite()?;
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM zone_soa_history WHERE zone_id = ? AND ((? - serial) & 4294967295) > ?",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .fetch_one(&mut **sqlite_tx)
+        .await
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn delete_before_serial_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, DatabaseError> {
+        let sqlite_tx = tx.as_sqlite()?;
+
+        sqlx::query(
+            "DELETE FROM zone_soa_history WHERE zone_id = ? AND ((? - serial) & 4294967295) > ?",
+        )
+        .bind(zone_id)
+        .bind(i64::from(current_serial))
+        .bind(serial_distance(serial, current_serial))
+        .execute(&mut **sqlite_tx)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| DatabaseError::QueryFailed(e.to_string()))
+    }
 }
@@ -0,0 +1,151 @@
+use async_trait::async_trait;
+use sqlx::{Pool, Sqlite};
+
+use crate::{
+    error::DatabaseError,
+    model::zone_template::{ZoneTemplate, ZoneTemplateLink},
+    repository::{RepositoryTx, ZoneTemplateRepository},
+};
+
+/// SQLite-backed implementation of `ZoneTemplateRepository`.
+pub(crate) struct SqliteZoneTemplateRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteZoneTemplateRepository {
+    pub(crate) fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ZoneTemplateRepository for SqliteZoneTemplateRepository {
+    async fn create(&self, mut template: ZoneTemplate) -> Result<ZoneTemplate, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO zone_templates (name, description, records, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&template.name)
+        .bind(&template.description)
+        .bind(&template.records)
+        .bind(template.created_at)
+        .bind(template.updated_at)
+        .execute(&mut *conn)
+        .await?;
+
+        template.id = result.last_insert_rowid() as i32;
+        Ok(template)
+    }
+
+    async fn get_by_name(&self, name: &str) -> Result<Option<ZoneTemplate>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let template = sqlx::query_as::<_, ZoneTemplate>(
+            "SELECT id, name, description, records, created_at, updated_at FROM zone_templates WHERE name = ?",
+        )
+        .bind(name)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        Ok(template)
+    }
+
+    async fn list_all(&self) -> Result<Vec<ZoneTemplate>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let templates = sqlx::query_as::<_, ZoneTemplate>(
+            "SELECT id, name, description, records, created_at, updated_at FROM zone_templates ORDER BY name",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(templates)
+    }
+
+    async fn update(&self, template: &ZoneTemplate) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query(
+            "UPDATE zone_templates SET description = ?, records = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&template.description)
+        .bind(&template.records)
+        .bind(template.updated_at)
+        .bind(template.id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("DELETE FROM zone_templates WHERE id = ?")
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn create_link_tx(
+        &self,
+        tx: &mut RepositoryTx<'_>,
+        mut link: ZoneTemplateLink,
+    ) -> Result<ZoneTemplateLink, DatabaseError> {
+        let sqlite_tx = tx.as_sqlite()?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO zone_template_links (zone_id, template_id, variables, applied_records)
+            VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(link.zone_id)
+        .bind(link.template_id)
+        .bind(&link.variables)
+        .bind(&link.applied_records)
+        .execute(&mut **sqlite_tx)
+        .await?;
+
+        link.id = result.last_insert_rowid() as i32;
+        Ok(link)
+    }
+
+    async fn list_links_by_template_id(
+        &self,
+        template_id: i32,
+    ) -> Result<Vec<ZoneTemplateLink>, DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let links = sqlx::query_as::<_, ZoneTemplateLink>(
+            "SELECT id, zone_id, template_id, variables, applied_records, created_at FROM zone_template_links WHERE template_id = ? ORDER BY id",
+        )
+        .bind(template_id)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        Ok(links)
+    }
+
+    async fn update_link_applied_records(
+        &self,
+        id: i32,
+        applied_records: &str,
+    ) -> Result<(), DatabaseError> {
+        let mut conn = self.pool.acquire().await?;
+
+        sqlx::query("UPDATE zone_template_links SET applied_records = ? WHERE id = ?")
+            .bind(applied_records)
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
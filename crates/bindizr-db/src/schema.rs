@@ -1,11 +1,69 @@
-//! Table-creation DDL for each backend, run at startup to bring the schema up.
+//! DDL for each schema migration, one function per migration and backend.
+//! The migrations that run them, in order, are listed in [`crate::migration`].
 //!
 //! Name columns are `VARCHAR(512)`, not 255: rows hold the escaped presentation
 //! form, whose `\` escapes can nearly double the 253-byte wire limit.
 
-pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
+/// A MySQL migration statement. MySQL commits each DDL statement as it runs,
+/// so a migration that fails partway stays partly applied and unrecorded.
+/// Statements that add a column or index name it, and re-running the
+/// migration skips them once it exists; every other statement must be safe
+/// to run twice (`IF NOT EXISTS`, or an idempotent `UPDATE`).
+pub(super) struct MysqlStatement {
+    pub(super) sql: &'static str,
+    pub(super) creates: Option<MysqlObject>,
+}
+
+/// A schema object a MySQL statement adds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum MysqlObject {
+    Column {
+        table: &'static str,
+        column: &'static str,
+    },
+    Index {
+        table: &'static str,
+        index: &'static str,
+    },
+}
+
+impl MysqlStatement {
+    /// A statement that is safe to run again as it is.
+    pub(super) const fn always(sql: &'static str) -> Self {
+        Self { sql, creates: None }
+    }
+
+    /// An `ALTER TABLE ... ADD COLUMN` of one column.
+    pub(super) const fn add_column(
+        table: &'static str,
+        column: &'static str,
+        sql: &'static str,
+    ) -> Self {
+        Self {
+            sql,
+            creates: Some(MysqlObject::Column { table, column }),
+        }
+    }
+
+    /// A `CREATE INDEX`, which MySQL has no `IF NOT EXISTS` for.
+    pub(super) const fn create_index(
+        table: &'static str,
+        index: &'static str,
+        sql: &'static str,
+    ) -> Self {
+        Self {
+            sql,
+            creates: Some(MysqlObject::Index { table, index }),
+        }
+    }
+}
+
+/// Version 1: the schema as it stood before migrations were versioned. Every
+/// statement is `IF NOT EXISTS`, so it also adopts an existing database.
+pub(super) fn mysql_table_creation_queries() -> Vec<MysqlStatement> {
     vec![
-        r#"
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS zones (
             id INT PRIMARY KEY AUTO_INCREMENT,
             name VARCHAR(255) UNIQUE NOT NULL,
@@ -20,7 +78,9 @@ pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
             created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
         );
         "#,
-        r#"
+        ),
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS records (
             id INT PRIMARY KEY AUTO_INCREMENT,
             name VARCHAR(512) NOT NULL,
@@ -35,7 +95,9 @@ pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
             INDEX idx_records_zone_name (zone_id, name)
         );
         "#,
-        r#"
+        ),
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS zone_changes (
             id INT PRIMARY KEY AUTO_INCREMENT,
             zone_id INT NOT NULL,
@@ -51,7 +113,9 @@ pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
             INDEX idx_zone_serial (zone_id, serial)
         );
         "#,
-        r#"
+        ),
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS zone_soa_history (
             id INT PRIMARY KEY AUTO_INCREMENT,
             zone_id INT NOT NULL,
@@ -68,7 +132,9 @@ pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
             FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE
         );
         "#,
-        r#"
+        ),
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS api_tokens (
             id INT PRIMARY KEY AUTO_INCREMENT,
             name VARCHAR(255) UNIQUE NOT NULL,
@@ -80,14 +146,18 @@ pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
             last_used_at DATETIME
         );
         "#,
-        r#"
+        ),
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS catalog_zone_state (
             name VARCHAR(255) PRIMARY KEY,
             signature VARCHAR(64) NOT NULL,
             serial INT NOT NULL
         );
         "#,
-        r#"
+        ),
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS tsig_keys (
             id INT PRIMARY KEY AUTO_INCREMENT,
             name VARCHAR(255) UNIQUE NOT NULL,
@@ -97,7 +167,9 @@ pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
             created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
         );
         "#,
-        r#"
+        ),
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS zone_tsig_policies (
             id INT PRIMARY KEY AUTO_INCREMENT,
             zone_id INT NOT NULL,
@@ -111,7 +183,9 @@ pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
             INDEX idx_zone_tsig_policies_key (tsig_key_id)
         );
         "#,
-        r#"
+        ),
+        MysqlStatement::always(
+            r#"
         CREATE TABLE IF NOT EXISTS zone_token_policies (
             id INT PRIMARY KEY AUTO_INCREMENT,
             zone_id INT NOT NULL,
@@ -125,6 +199,7 @@ pub(super) fn mysql_table_creation_queries() -> Vec<&'static str> {
             INDEX idx_zone_token_policies_token (api_token_id)
         );
         "#,
+        ),
     ]
 }
 
@@ -401,3 +476,674 @@ pub(super) fn sqlite_table_creation_queries() -> Vec<&'static str> {
         "#,
     ]
 }
+
+/// Version 2: per-zone transfer ACLs.
+pub(super) fn mysql_zone_transfer_acl_queries() -> Vec<MysqlStatement> {
+    vec![MysqlStatement::always(
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_transfer_acls (
+            id INT PRIMARY KEY AUTO_INCREMENT,
+            zone_id INT NOT NULL,
+            action VARCHAR(10) NOT NULL,
+            address VARCHAR(64) NOT NULL,
+            tsig_key_name VARCHAR(255),
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE,
+            INDEX idx_zone_transfer_acls_zone (zone_id)
+        );
+        "#,
+    )]
+}
+
+pub(super) fn postgres_zone_transfer_acl_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_transfer_acls (
+            id SERIAL PRIMARY KEY,
+            zone_id INTEGER NOT NULL,
+            action VARCHAR(10) NOT NULL,
+            address VARCHAR(64) NOT NULL,
+            tsig_key_name VARCHAR(255),
+            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_transfer_acls_zone ON zone_transfer_acls(zone_id);
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_zone_transfer_acl_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_transfer_acls (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            zone_id INTEGER NOT NULL,
+            action TEXT NOT NULL,
+            address TEXT NOT NULL,
+            tsig_key_name TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_transfer_acls_zone ON zone_transfer_acls(zone_id);
+        "#,
+    ]
+}
+
+/// Version 3: attribution of each serial to the caller and front end that
+/// made it. Rows from before the migration keep NULLs.
+pub(super) fn mysql_change_attribution_queries() -> Vec<MysqlStatement> {
+    vec![
+        MysqlStatement::add_column(
+            "zone_soa_history",
+            "actor",
+            "ALTER TABLE zone_soa_history ADD COLUMN actor VARCHAR(255);",
+        ),
+        MysqlStatement::add_column(
+            "zone_soa_history",
+            "tsig_key_name",
+            "ALTER TABLE zone_soa_history ADD COLUMN tsig_key_name VARCHAR(255);",
+        ),
+        MysqlStatement::add_column(
+            "zone_soa_history",
+            "source_address",
+            "ALTER TABLE zone_soa_history ADD COLUMN source_address VARCHAR(64);",
+        ),
+        MysqlStatement::add_column(
+            "zone_soa_history",
+            "transport",
+            "ALTER TABLE zone_soa_history ADD COLUMN transport VARCHAR(20);",
+        ),
+        MysqlStatement::add_column(
+            "zone_soa_history",
+            "request_id",
+            "ALTER TABLE zone_soa_history ADD COLUMN request_id VARCHAR(64);",
+        ),
+        MysqlStatement::create_index(
+            "zone_soa_history",
+            "idx_zone_soa_history_created",
+            "CREATE INDEX idx_zone_soa_history_created ON zone_soa_history (created_at);",
+        ),
+    ]
+}
+
+pub(super) fn postgres_change_attribution_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        ALTER TABLE zone_soa_history
+            ADD COLUMN IF NOT EXISTS actor VARCHAR(255),
+            ADD COLUMN IF NOT EXISTS tsig_key_name VARCHAR(255),
+            ADD COLUMN IF NOT EXISTS source_address VARCHAR(64),
+            ADD COLUMN IF NOT EXISTS transport VARCHAR(20),
+            ADD COLUMN IF NOT EXISTS request_id VARCHAR(64);
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_soa_history_created ON zone_soa_history(created_at);
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_change_attribution_queries() -> Vec<&'static str> {
+    vec![
+        "ALTER TABLE zone_soa_history ADD COLUMN actor TEXT;",
+        "ALTER TABLE zone_soa_history ADD COLUMN tsig_key_name TEXT;",
+        "ALTER TABLE zone_soa_history ADD COLUMN source_address TEXT;",
+        "ALTER TABLE zone_soa_history ADD COLUMN transport TEXT;",
+        "ALTER TABLE zone_soa_history ADD COLUMN request_id TEXT;",
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_soa_history_created ON zone_soa_history(created_at);
+        "#,
+    ]
+}
+
+/// Version 4: the scheme each zone's SOA serial advances by. Existing zones
+/// keep incrementing.
+pub(super) fn mysql_serial_policy_queries() -> Vec<MysqlStatement> {
+    vec![MysqlStatement::add_column(
+        "zones",
+        "serial_policy",
+        "ALTER TABLE zones ADD COLUMN serial_policy VARCHAR(16) NOT NULL DEFAULT 'increment';",
+    )]
+}
+
+pub(super) fn postgres_serial_policy_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        ALTER TABLE zones ADD COLUMN IF NOT EXISTS serial_policy VARCHAR(16) NOT NULL DEFAULT 'increment';
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_serial_policy_queries() -> Vec<&'static str> {
+    vec!["ALTER TABLE zones ADD COLUMN serial_policy TEXT NOT NULL DEFAULT 'increment';"]
+}
+
+/// Version 5: previous-secret overlap for token rotation.
+pub(super) fn mysql_token_rotation_queries() -> Vec<MysqlStatement> {
+    vec![
+        MysqlStatement::add_column(
+            "api_tokens",
+            "previous_token",
+            "ALTER TABLE api_tokens ADD COLUMN previous_token VARCHAR(64);",
+        ),
+        MysqlStatement::add_column(
+            "api_tokens",
+            "previous_token_expires_at",
+            "ALTER TABLE api_tokens ADD COLUMN previous_token_expires_at DATETIME;",
+        ),
+        MysqlStatement::create_index(
+            "api_tokens",
+            "idx_api_tokens_previous_token",
+            "CREATE INDEX idx_api_tokens_previous_token ON api_tokens (previous_token);",
+        ),
+    ]
+}
+
+pub(super) fn postgres_token_rotation_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        ALTER TABLE api_tokens
+            ADD COLUMN IF NOT EXISTS previous_token VARCHAR(64),
+            ADD COLUMN IF NOT EXISTS previous_token_expires_at TIMESTAMPTZ;
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_api_tokens_previous_token ON api_tokens (previous_token);
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_token_rotation_queries() -> Vec<&'static str> {
+    vec![
+        "ALTER TABLE api_tokens ADD COLUMN previous_token TEXT;",
+        "ALTER TABLE api_tokens ADD COLUMN previous_token_expires_at DATETIME;",
+        "CREATE INDEX IF NOT EXISTS idx_api_tokens_previous_token ON api_tokens (previous_token);",
+    ]
+}
+
+/// Version 6: token roles; `is_global` stays as written for the tokens created
+/// before roles existed, and `role` is authoritative from here on.
+pub(super) fn mysql_token_role_queries() -> Vec<MysqlStatement> {
+    vec![
+        MysqlStatement::add_column(
+            "api_tokens",
+            "role",
+            "ALTER TABLE api_tokens ADD COLUMN role VARCHAR(16) NOT NULL DEFAULT 'scoped';",
+        ),
+        MysqlStatement::always("UPDATE api_tokens SET role = 'admin' WHERE is_global = TRUE;"),
+    ]
+}
+
+pub(super) fn postgres_token_role_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        ALTER TABLE api_tokens ADD COLUMN IF NOT EXISTS role VARCHAR(16) NOT NULL DEFAULT 'scoped';
+        "#,
+        "UPDATE api_tokens SET role = 'admin' WHERE is_global = TRUE;",
+    ]
+}
+
+pub(super) fn sqlite_token_role_queries() -> Vec<&'static str> {
+    vec![
+        "ALTER TABLE api_tokens ADD COLUMN role TEXT NOT NULL DEFAULT 'scoped';",
+        "UPDATE api_tokens SET role = 'admin' WHERE is_global = 1;",
+    ]
+}
+
+/// Version 7: named catalog zones and per-zone catalog properties.
+pub(super) fn mysql_catalog_queries() -> Vec<MysqlStatement> {
+    vec![
+        MysqlStatement::always(
+            r#"
+        CREATE TABLE IF NOT EXISTS catalogs (
+            id INT PRIMARY KEY AUTO_INCREMENT,
+            name VARCHAR(255) NOT NULL UNIQUE,
+            description TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        ),
+        MysqlStatement::always(
+            r#"
+        CREATE TABLE IF NOT EXISTS zone_catalog_properties (
+            id INT PRIMARY KEY AUTO_INCREMENT,
+            zone_id INT NOT NULL,
+            name VARCHAR(255) NOT NULL,
+            value VARCHAR(255) NOT NULL,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE,
+            UNIQUE KEY uq_zone_catalog_properties (zone_id, name)
+        );
+        "#,
+        ),
+    ]
+}
+
+pub(super) fn postgres_catalog_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS catalogs (
+            id SERIAL PRIMARY KEY,
+            name VARCHAR(255) NOT NULL UNIQUE,
+            description TEXT,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_catalog_properties (
+            id SERIAL PRIMARY KEY,
+            zone_id INTEGER NOT NULL,
+            name VARCHAR(255) NOT NULL,
+            value VARCHAR(255) NOT NULL,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE,
+            UNIQUE (zone_id, name)
+        );
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_catalog_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS catalogs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            description TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_catalog_properties (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            zone_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            value TEXT NOT NULL,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE,
+            UNIQUE (zone_id, name)
+        );
+        "#,
+    ]
+}
+
+/// Version 8: outbound webhooks and their persistent delivery queue.
+pub(super) fn mysql_webhook_queries() -> Vec<MysqlStatement> {
+    vec![
+        MysqlStatement::always(
+            r#"
+        CREATE TABLE IF NOT EXISTS webhooks (
+            id INT PRIMARY KEY AUTO_INCREMENT,
+            name VARCHAR(255) NOT NULL UNIQUE,
+            url TEXT NOT NULL,
+            secret VARCHAR(255) NOT NULL,
+            zone_id INT,
+            description TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE
+        );
+        "#,
+        ),
+        MysqlStatement::always(
+            r#"
+        CREATE TABLE IF NOT EXISTS webhook_deliveries (
+            id BIGINT PRIMARY KEY AUTO_INCREMENT,
+            webhook_id INT NOT NULL,
+            event VARCHAR(32) NOT NULL,
+            zone_name VARCHAR(255) NOT NULL,
+            from_serial INT,
+            to_serial INT,
+            payload LONGTEXT,
+            status VARCHAR(16) NOT NULL DEFAULT 'pending',
+            attempts INT NOT NULL DEFAULT 0,
+            next_attempt_at DATETIME NOT NULL,
+            last_error TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            delivered_at DATETIME,
+            FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE,
+            INDEX idx_webhook_deliveries_due (status, next_attempt_at),
+            INDEX idx_webhook_deliveries_webhook (webhook_id)
+        );
+        "#,
+        ),
+    ]
+}
+
+pub(super) fn postgres_webhook_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS webhooks (
+            id SERIAL PRIMARY KEY,
+            name VARCHAR(255) NOT NULL UNIQUE,
+            url TEXT NOT NULL,
+            secret VARCHAR(255) NOT NULL,
+            zone_id INTEGER,
+            description TEXT,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE
+        );
+        "#,
+        r#"
+        CREATE TABLE IF NOT EXISTS webhook_deliveries (
+            id BIGSERIAL PRIMARY KEY,
+            webhook_id INTEGER NOT NULL,
+            event VARCHAR(32) NOT NULL,
+            zone_name VARCHAR(255) NOT NULL,
+            from_serial INTEGER,
+            to_serial INTEGER,
+            payload TEXT,
+            status VARCHAR(16) NOT NULL DEFAULT 'pending',
+            attempts INTEGER NOT NULL DEFAULT 0,
+            next_attempt_at TIMESTAMPTZ NOT NULL,
+            last_error TEXT,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            delivered_at TIMESTAMPTZ,
+            FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at);
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id);
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_webhook_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS webhooks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            url TEXT NOT NULL,
+            secret TEXT NOT NULL,
+            zone_id INTEGER,
+            description TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE
+        );
+        "#,
+        r#"
+        CREATE TABLE IF NOT EXISTS webhook_deliveries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            webhook_id INTEGER NOT NULL,
+            event TEXT NOT NULL,
+            zone_name TEXT NOT NULL,
+            from_serial INTEGER,
+            to_serial INTEGER,
+            payload TEXT,
+            status TEXT NOT NULL DEFAULT 'pending',
+            attempts INTEGER NOT NULL DEFAULT 0,
+            next_attempt_at DATETIME NOT NULL,
+            last_error TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            delivered_at DATETIME,
+            FOREIGN KEY (webhook_id) REFERENCES webhooks(id) ON DELETE CASCADE
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at);
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id);
+        "#,
+    ]
+}
+
+/// Version 9: per-zone NOTIFY and also-notify targets.
+pub(super) fn mysql_zone_notify_target_queries() -> Vec<MysqlStatement> {
+    vec![MysqlStatement::always(
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_notify_targets (
+            id INT PRIMARY KEY AUTO_INCREMENT,
+            zone_id INT NOT NULL,
+            kind VARCHAR(16) NOT NULL,
+            address VARCHAR(255) NOT NULL,
+            tsig_key_name VARCHAR(255),
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE,
+            INDEX idx_zone_notify_targets_zone (zone_id)
+        );
+        "#,
+    )]
+}
+
+pub(super) fn postgres_zone_notify_target_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_notify_targets (
+            id SERIAL PRIMARY KEY,
+            zone_id INTEGER NOT NULL,
+            kind VARCHAR(16) NOT NULL,
+            address VARCHAR(255) NOT NULL,
+            tsig_key_name VARCHAR(255),
+            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_notify_targets_zone ON zone_notify_targets(zone_id);
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_zone_notify_target_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_notify_targets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            zone_id INTEGER NOT NULL,
+            kind TEXT NOT NULL,
+            address TEXT NOT NULL,
+            tsig_key_name TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_notify_targets_zone ON zone_notify_targets(zone_id);
+        "#,
+    ]
+}
+
+/// Version 10: zone templates and the zones created from them.
+pub(super) fn mysql_zone_template_queries() -> Vec<MysqlStatement> {
+    vec![
+        MysqlStatement::always(
+            r#"
+        CREATE TABLE IF NOT EXISTS zone_templates (
+            id INT PRIMARY KEY AUTO_INCREMENT,
+            name VARCHAR(255) NOT NULL UNIQUE,
+            description TEXT,
+            records LONGTEXT NOT NULL,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        ),
+        MysqlStatement::always(
+            r#"
+        CREATE TABLE IF NOT EXISTS zone_template_links (
+            id INT PRIMARY KEY AUTO_INCREMENT,
+            zone_id INT NOT NULL UNIQUE,
+            template_id INT NOT NULL,
+            variables TEXT NOT NULL,
+            applied_records LONGTEXT NOT NULL,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE,
+            FOREIGN KEY (template_id) REFERENCES zone_templates(id),
+            INDEX idx_zone_template_links_template (template_id)
+        );
+        "#,
+        ),
+    ]
+}
+
+pub(super) fn postgres_zone_template_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_templates (
+            id SERIAL PRIMARY KEY,
+            name VARCHAR(255) NOT NULL UNIQUE,
+            description TEXT,
+            records TEXT NOT NULL,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_template_links (
+            id SERIAL PRIMARY KEY,
+            zone_id INTEGER NOT NULL UNIQUE,
+            template_id INTEGER NOT NULL,
+            variables TEXT NOT NULL,
+            applied_records TEXT NOT NULL,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE,
+            FOREIGN KEY (template_id) REFERENCES zone_templates(id)
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_template_links_template ON zone_template_links(template_id);
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_zone_template_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_templates (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            description TEXT,
+            records TEXT NOT NULL,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_template_links (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            zone_id INTEGER NOT NULL UNIQUE,
+            template_id INTEGER NOT NULL,
+            variables TEXT NOT NULL,
+            applied_records TEXT NOT NULL,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            FOREIGN KEY (zone_id) REFERENCES zones(id) ON DELETE CASCADE,
+            FOREIGN KEY (template_id) REFERENCES zone_templates(id)
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_template_links_template ON zone_template_links(template_id);
+        "#,
+    ]
+}
+
+/// Version 11: the audit log in a table of its own. Entries name their zone
+/// instead of referencing it, so neither deleting the zone nor pruning its
+/// history removes them. The attributed history recorded so far is copied
+/// in; the copy runs only while the log is empty, so it is safe to repeat.
+pub(super) fn mysql_audit_log_queries() -> Vec<MysqlStatement> {
+    vec![
+        MysqlStatement::always(
+            r#"
+        CREATE TABLE IF NOT EXISTS zone_audit_log (
+            id BIGINT PRIMARY KEY AUTO_INCREMENT,
+            zone_name VARCHAR(255) NOT NULL,
+            serial INT NOT NULL,
+            action VARCHAR(16),
+            actor VARCHAR(255),
+            tsig_key_name VARCHAR(255),
+            source_address VARCHAR(64),
+            transport VARCHAR(20),
+            request_id VARCHAR(64),
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            INDEX idx_zone_audit_log_zone (zone_name),
+            INDEX idx_zone_audit_log_created (created_at)
+        );
+        "#,
+        ),
+        MysqlStatement::always(
+            r#"
+        INSERT INTO zone_audit_log (zone_name, serial, actor, tsig_key_name, source_address,
+                                    transport, request_id, created_at)
+        SELECT z.name, h.serial, h.actor, h.tsig_key_name, h.source_address,
+               h.transport, h.request_id, h.created_at
+        FROM zone_soa_history h
+        INNER JOIN zones z ON z.id = h.zone_id
+        WHERE NOT EXISTS (SELECT 1 FROM zone_audit_log)
+        ORDER BY h.created_at, h.id;
+        "#,
+        ),
+    ]
+}
+
+pub(super) fn postgres_audit_log_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_audit_log (
+            id BIGSERIAL PRIMARY KEY,
+            zone_name VARCHAR(255) NOT NULL,
+            serial INTEGER NOT NULL,
+            action VARCHAR(16),
+            actor VARCHAR(255),
+            tsig_key_name VARCHAR(255),
+            source_address VARCHAR(64),
+            transport VARCHAR(20),
+            request_id VARCHAR(64),
+            created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_audit_log_zone ON zone_audit_log(zone_name);
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_audit_log_created ON zone_audit_log(created_at);
+        "#,
+        r#"
+        INSERT INTO zone_audit_log (zone_name, serial, actor, tsig_key_name, source_address,
+                                    transport, request_id, created_at)
+        SELECT z.name, h.serial, h.actor, h.tsig_key_name, h.source_address,
+               h.transport, h.request_id, h.created_at
+        FROM zone_soa_history h
+        INNER JOIN zones z ON z.id = h.zone_id
+        WHERE NOT EXISTS (SELECT 1 FROM zone_audit_log)
+        ORDER BY h.created_at, h.id;
+        "#,
+    ]
+}
+
+pub(super) fn sqlite_audit_log_queries() -> Vec<&'static str> {
+    vec![
+        r#"
+        CREATE TABLE IF NOT EXISTS zone_audit_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            zone_name TEXT NOT NULL,
+            serial INTEGER NOT NULL,
+            action TEXT,
+            actor TEXT,
+            tsig_key_name TEXT,
+            source_address TEXT,
+            transport TEXT,
+            request_id TEXT,
+            created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_audit_log_zone ON zone_audit_log(zone_name);
+        "#,
+        r#"
+        CREATE INDEX IF NOT EXISTS idx_zone_audit_log_created ON zone_audit_log(created_at);
+        "#,
+        r#"
+        INSERT INTO zone_audit_log (zone_name, serial, actor, tsig_key_name, source_address,
+                                    transport, request_id, created_at)
+        SELECT z.name, h.serial, h.actor, h.tsig_key_name, h.source_address,
+               h.transport, h.request_id, h.created_at
+        FROM zone_soa_history h
+        INNER JOIN zones z ON z.id = h.zone_id
+        WHERE NOT EXISTS (SELECT 1 FROM zone_audit_log)
+        ORDER BY h.created_at, h.id;
+        "#,
+    ]
+}
@@ -0,0 +1,287 @@
+//! Client-side AXFR (RFC 5936): transfer a whole zone from another primary and
+//! render it as master-file text, so a zone can be pulled into bindizr through
+//! the same reconciliation as a zone-file import.
+
+use std::{fmt::Write, net::SocketAddr, str::FromStr, sync::Arc, time::Duration};
+
+use bindizr_core::dns::MAX_ZONE_CONTENT_BYTES;
+use domain::{
+    base::{
+        Message, MessageBuilder, Name, ParsedName, Record,
+        iana::Rcode,
+        zonefile_fmt::{DisplayKind, ZonefileFmt},
+    },
+    rdata::{AllRecordData, tsig::Time48},
+    tsig::{ClientSequence, Key},
+};
+use tokio::{net::TcpStream, time::timeout};
+
+use crate::{
+    error::XfrError,
+    log_info, log_warn,
+    model::tsig_key::TsigKey,
+    tsig,
+    wire::{read_tcp_message, write_tcp_message},
+};
+
+/// Bounds connecting, and the wait for each message of the response stream.
+const TRANSFER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Bounds a whole transfer, however steadily the primary keeps sending.
+const TRANSFER_DEADLINE: Duration = Duration::from_secs(300);
+
+/// A zone as the primary served it.
+pub struct TransferredZone {
+    /// The serial of the primary's SOA.
+    pub serial: u32,
+    /// Records transferred, the SOA not counted.
+    pub records: usize,
+    /// Every record, the SOA first, one master-file line each.
+    pub content: String,
+}
+
+/// Transfer `zone_name` from `server` (`host[:port]` or an IP address, port 53
+/// by default), signing the request and verifying every response message with
+/// `key` when one is given. A hostname is tried at each resolved address until
+/// one completes the transfer.
+pub async fn transfer_zone(
+    zone_name: &str,
+    server: &str,
+    key: Option<&TsigKey>,
+) -> Result<TransferredZone, XfrError> {
+    let qname = Name::<Vec<u8>>::from_str(zone_name)
+        .map_err(|e| XfrError::ProtocolError(format!("Invalid zone name: {}", e)))?;
+    let key = key
+        .map(tsig::domain_key)
+        .transpose()
+        .map_err(XfrError::ProtocolError)?;
+
+    let addrs = super::resolve_address(server.trim(), TRANSFER_TIMEOUT)
+        .await
+        .map_err(|e| XfrError::ProtocolError(format!("Failed to resolve {}: {}", server, e)))?;
+
+    let mut last_error = None;
+    for addr in addrs {
+        match transfer_from(&qname, addr, key.as_ref()).await {
+            Ok(zone) => {
+                log_info!(
+                    "Transferred zone {} (serial {}, {} record(s)) from {}",
+                    zone_name,
+                    zone.serial,
+                    zone.records,
+                    addr
+                );
+                return Ok(zone);
+            }
+            Err(e) => {
+                log_warn!("AXFR of zone {} from {} failed: {}", zone_name, addr, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.expect("resolve_address never yields an empty Ok"))
+}
+
+async fn transfer_from(
+    qname: &Name<Vec<u8>>,
+    server_addr: SocketAddr,
+    key: Option<&Arc<Key>>,
+) -> Result<TransferredZone, XfrError> {
+    timeout(TRANSFER_DEADLINE, receive_transfer(qname, server_addr, key))
+        .await
+        .map_err(|_| {
+            XfrError::ProtocolError(format!(
+                "AXFR from {} did not complete within {}s",
+                server_addr,
+                TRANSFER_DEADLINE.as_secs()
+            ))
+        })?
+}
+
+async fn receive_transfer(
+    qname: &Name<Vec<u8>>,
+    server_addr: SocketAddr,
+    key: Option<&Arc<Key>>,
+) -> Result<TransferredZone, XfrError> {
+    let mut stream = timeout(TRANSFER_TIMEOUT, TcpStream::connect(server_addr))
+        .await
+        .map_err(|_| XfrError::ProtocolError(format!("Connection to {} timed out", server_addr)))?
+        .map_err(XfrError::IoError)?;
+
+    let mut request = MessageBuilder::new_vec()
+        .request_axfr(qname)
+        .map_err(|e| XfrError::ProtocolError(format!("Failed to build AXFR request: {}", e)))?
+        .additional();
+    let query_id = request.header().id();
+    let mut sequence = key
+        .map(|key| ClientSequence::request(key.clone(), &mut request, Time48::now()))
+        .transpose()
+        .map_err(|e| XfrError::ProtocolError(format!("Failed to sign AXFR request: {}", e)))?;
+    write_tcp_message(&mut stream, &request.finish()).await?;
+
+    let mut collector = AxfrCollector::new(query_id);
+    loop {
+        let data = timeout(TRANSFER_TIMEOUT, read_tcp_message(&mut stream))
+            .await
+            .map_err(|_| {
+                XfrError::ProtocolError(format!("AXFR response timeout from {}", server_addr))
+            })??;
+        let mut message = Message::from_octets(data)
+            .map_err(|e| XfrError::ProtocolError(format!("AXFR response is malformed: {}", e)))?;
+
+        // An error response may go unsigned (a BADKEY, say), so its RCODE is
+        // the more useful report.
+        collector.check_header(&message)?;
+        if let Some(sequence) = sequence.as_mut() {
+            sequence.answer(&mut message, Time48::now()).map_err(|e| {
+                XfrError::ProtocolError(format!("AXFR response failed TSIG validation: {}", e))
+            })?;
+        }
+
+        if collector.add(&message)? {
+            break;
+        }
+    }
+
+    if let Some(sequence) = sequence {
+        sequence.done().map_err(|e| {
+            XfrError::ProtocolError(format!("AXFR response failed TSIG validation: {}", e))
+        })?;
+    }
+
+    Ok(collector.finish())
+}
+
+/// Accumulates the messages of one AXFR response: SOA, the zone's records,
+/// and the same SOA again to close it (RFC 5936, Section 2.2).
+struct AxfrCollector {
+    query_id: u16,
+    serial: Option<u32>,
+    records: usize,
+    content: String,
+    /// Cap on `content`, the same as on an uploaded zone file.
+    max_content_bytes: usize,
+    done: bool,
+}
+
+impl AxfrCollector {
+    fn new(query_id: u16) -> Self {
+        Self {
+            query_id,
+            serial: None,
+            records: 0,
+            content: String::new(),
+            max_content_bytes: MAX_ZONE_CONTENT_BYTES,
+            done: false,
+        }
+    }
+
+    fn check_header(&self, message: &Message<Vec<u8>>) -> Result<(), XfrError> {
+        let header = message.header();
+        if header.id() != self.query_id {
+            return Err(XfrError::ProtocolError(format!(
+                "AXFR response ID mismatch: expected {}, got {}",
+                self.query_id,
+                header.id()
+            )));
+        }
+        if !header.qr() {
+            return Err(XfrError::ProtocolError(
+                "AXFR response does not have QR bit set".to_string(),
+            ));
+        }
+        if header.rcode() != Rcode::NOERROR {
+            return Err(XfrError::ProtocolError(format!(
+                "AXFR refused by the primary: RCODE {}",
+                header.rcode()
+            )));
+        }
+        if header.tc() {
+            return Err(XfrError::ProtocolError(
+                "AXFR response is truncated".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Take in one response message; `true` once the closing SOA has arrived.
+    fn add(&mut self, message: &Message<Vec<u8>>) -> Result<bool, XfrError> {
+        if self.serial.is_none() && message.header_counts().ancount() == 0 {
+            return Err(XfrError::ProtocolError(
+                "AXFR response carries no records".to_string(),
+            ));
+        }
+
+        let answer = message
+            .answer()
+            .map_err(|e| XfrError::ProtocolError(format!("malformed answer section: {}", e)))?;
+        for record in answer.limit_to::<AllRecordData<_, ParsedName<_>>>() {
+            let record = record
+                .map_err(|e| XfrError::ProtocolError(format!("malformed AXFR record: {}", e)))?;
+            if self.done {
+                return Err(XfrError::ProtocolError(
+                    "AXFR response continues past its closing SOA".to_string(),
+                ));
+            }
+
+            match (record.data(), self.serial) {
+                (AllRecordData::Soa(soa), None) => {
+                    self.serial = Some(soa.serial().into_int());
+                    self.push_line(&record)?;
+                }
+                (AllRecordData::Soa(soa), Some(serial)) => {
+                    if soa.serial().into_int() != serial {
+                        return Err(XfrError::ProtocolError(format!(
+                            "AXFR closing SOA serial {} does not match opening serial {}",
+                            soa.serial().into_int(),
+                            serial
+                        )));
+                    }
+                    self.done = true;
+                }
+                (_, None) => {
+                    return Err(XfrError::ProtocolError(
+                        "AXFR response does not start with the zone's SOA".to_string(),
+                    ));
+                }
+                (_, Some(_)) => {
+                    self.records += 1;
+                    self.push_line(&record)?;
+                }
+            }
+        }
+
+        Ok(self.done)
+    }
+
+    fn push_line<Data>(&mut self, record: &Record<ParsedName<&[u8]>, Data>) -> Result<(), XfrError>
+    where
+        Data: domain::base::RecordData + ZonefileFmt,
+    {
+        // Writing into a String cannot fail.
+        let _ = writeln!(
+            self.content,
+            "{}",
+            record.display_zonefile(DisplayKind::Simple)
+        );
+        if self.content.len() > self.max_content_bytes {
+            return Err(XfrError::ProtocolError(format!(
+                "AXFR response exceeds the {} MiB zone size limit",
+                self.max_content_bytes / (1024 * 1024)
+            )));
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> TransferredZone {
+        TransferredZone {
+            serial: self.serial.unwrap_or_default(),
+            records: self.records,
+            content: self.content,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,223 @@
+use std::{net::Ipv4Addr, str::FromStr};
+
+use domain::{
+    base::{
+        Message, MessageBuilder, Name, Serial, Ttl,
+        iana::{Class, Rcode},
+        message_builder::AnswerBuilder,
+    },
+    rdata::{A, Mx, Soa, Txt},
+};
+
+use super::AxfrCollector;
+
+fn name(s: &str) -> Name<Vec<u8>> {
+    Name::from_str(s).unwrap()
+}
+
+fn soa(serial: u32) -> Soa<Name<Vec<u8>>> {
+    Soa::new(
+        name("ns1.example.com."),
+        name("hostmaster.example.com."),
+        Serial(serial),
+        Ttl::from_secs(7200),
+        Ttl::from_secs(3600),
+        Ttl::from_secs(604800),
+        Ttl::from_secs(300),
+    )
+}
+
+/// A response to query `id` whose answer section `fill` writes.
+fn response(
+    id: u16,
+    rcode: Rcode,
+    fill: impl FnOnce(&mut AnswerBuilder<Vec<u8>>),
+) -> Message<Vec<u8>> {
+    let mut builder = MessageBuilder::new_vec();
+    let header = builder.header_mut();
+    header.set_id(id);
+    header.set_qr(true);
+    header.set_rcode(rcode);
+    let mut answer = builder.answer();
+    fill(&mut answer);
+    Message::from_octets(answer.finish()).unwrap()
+}
+
+fn push_soa(answer: &mut AnswerBuilder<Vec<u8>>, serial: u32) {
+    answer
+        .push((name("example.com."), Class::IN, 3600, soa(serial)))
+        .unwrap();
+}
+
+#[test]
+fn collects_records_between_the_soas() {
+    let mut collector = AxfrCollector::new(7);
+    let message = response(7, Rcode::NOERROR, |answer| {
+        push_soa(answer, 2026);
+        answer
+            .push((
+                name("www.example.com."),
+                Class::IN,
+                300,
+                A::new(Ipv4Addr::new(192, 0, 2, 1)),
+            ))
+            .unwrap();
+        answer
+            .push((
+                name("example.com."),
+                Class::IN,
+                3600,
+                Mx::new(10, name("mail.example.com.")),
+            ))
+            .unwrap();
+        push_soa(answer, 2026);
+    });
+
+    collector.check_header(&message).unwrap();
+    assert!(collector.add(&message).unwrap());
+
+    let zone = collector.finish();
+    assert_eq!(zone.serial, 2026);
+    assert_eq!(zone.records, 2);
+    let lines: Vec<&str> = zone.content.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(
+        lines[0].starts_with("example.com. 3600 IN SOA"),
+        "{}",
+        lines[0]
+    );
+    assert_eq!(lines[1], "www.example.com. 300 IN A 192.0.2.1");
+    assert_eq!(lines[2], "example.com. 3600 IN MX 10 mail.example.com.");
+}
+
+#[test]
+fn spans_several_messages() {
+    let mut collector = AxfrCollector::new(7);
+    let first = response(7, Rcode::NOERROR, |answer| {
+        push_soa(answer, 5);
+        answer
+            .push((
+                name("example.com."),
+                Class::IN,
+                60,
+                Txt::<Vec<u8>>::build_from_slice(b"hello world").unwrap(),
+            ))
+            .unwrap();
+    });
+    let last = response(7, Rcode::NOERROR, |answer| push_soa(answer, 5));
+
+    assert!(!collector.add(&first).unwrap());
+    assert!(collector.add(&last).unwrap());
+
+    let zone = collector.finish();
+    assert_eq!(zone.records, 1);
+    assert!(
+        zone.content
+            .contains("example.com. 60 IN TXT \"hello world\""),
+        "{}",
+        zone.content
+    );
+}
+
+#[test]
+fn rejects_a_foreign_or_failed_response() {
+    let collector = AxfrCollector::new(7);
+
+    let foreign = response(8, Rcode::NOERROR, |answer| push_soa(answer, 1));
+    assert!(
+        collector
+            .check_header(&foreign)
+            .unwrap_err()
+            .to_string()
+            .contains("ID mismatch")
+    );
+
+    let refused = response(7, Rcode::REFUSED, |_| {});
+    assert!(
+        collector
+            .check_header(&refused)
+            .unwrap_err()
+            .to_string()
+            .contains("REFUSED")
+    );
+}
+
+#[test]
+fn rejects_a_stream_that_does_not_open_with_soa() {
+    let mut collector = AxfrCollector::new(7);
+
+    let empty = response(7, Rcode::NOERROR, |_| {});
+    assert!(collector.add(&empty).is_err());
+
+    let headless = response(7, Rcode::NOERROR, |answer| {
+        answer
+            .push((
+                name("www.example.com."),
+                Class::IN,
+                300,
+                A::new(Ipv4Addr::new(192, 0, 2, 1)),
+            ))
+            .unwrap();
+    });
+    assert!(
+        collector
+            .add(&headless)
+            .unwrap_err()
+            .to_string()
+            .contains("does not start with")
+    );
+}
+
+#[test]
+fn rejects_a_closing_soa_with_another_serial() {
+    let mut collector = AxfrCollector::new(7);
+    let message = response(7, Rcode::NOERROR, |answer| {
+        push_soa(answer, 1);
+        push_soa(answer, 2);
+    });
+
+    assert!(
+        collector
+            .add(&message)
+            .unwrap_err()
+            .to_string()
+            .contains("does not match")
+    );
+}
+
+#[test]
+fn rejects_records_after_the_closing_soa() {
+    let mut collector = AxfrCollector::new(7);
+    let message = response(7, Rcode::NOERROR, |answer| {
+        push_soa(answer, 1);
+        push_soa(answer, 1);
+        push_soa(answer, 1);
+    });
+
+    assert!(collector.add(&message).is_err());
+}
+
+#[test]
+fn stops_a_transfer_larger_than_the_zone_size_limit() {
+    let mut collector = AxfrCollector::new(7);
+    collector.max_content_bytes = 256;
+    let first = response(7, Rcode::NOERROR, |answer| push_soa(answer, 9));
+    assert!(!collector.add(&first).unwrap());
+
+    // A primary that never sends the closing SOA is cut off at the limit.
+    let more = response(7, Rcode::NOERROR, |answer| {
+        for octet in 1..=4 {
+            answer
+                .push((
+                    name("www.example.com."),
+                    Class::IN,
+                    300,
+                    A::new(Ipv4Addr::new(192, 0, 2, octet)),
+                ))
+                .unwrap();
+        }
+    });
+    assert!(!collector.add(&more).unwrap());
+    let err = collector.add(&more).unwrap_err().to_string();
+    assert!(err.contains("exceeds"), "{}", err);
+}
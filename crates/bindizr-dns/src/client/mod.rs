@@ -1,17 +1,19 @@
-//! Outbound DNS client paths: NOTIFY fan-out and secondary SOA probing, plus
-//! the UDP exchange, message-build, and secondary-resolution helpers they
-//! share.
+//! Outbound DNS client paths: NOTIFY fan-out, secondary SOA probing, and
+//! pulling zones from another primary by AXFR, plus the UDP exchange,
+//! message-build, and address-resolution helpers they share.
 
+pub mod axfr;
 pub mod notify;
 pub mod probe;
 
 use std::{net::SocketAddr, time::Duration};
 
-use domain::base::{MessageBuilder, Name, Rtype, iana::Opcode};
+use domain::base::{MessageBuilder, Name, Rtype, iana::Opcode, message_builder::AdditionalBuilder};
 use tokio::net::{UdpSocket, lookup_host};
 
 use crate::{
     address::{ParsedAddress, parse_address_target},
+    config::SecondaryEntry,
     error::XfrError,
     log_error,
 };
@@ -70,6 +72,17 @@ pub(crate) async fn udp_exchange(
 /// Build a single-SOA-question DNS message with a random id, returning
 /// `(query_id, wire bytes)`.
 pub(crate) fn build_question(opcode: Opcode, aa: bool, qname: &Name<Vec<u8>>) -> (u16, Vec<u8>) {
+    let (query_id, message) = question_message(opcode, aa, qname);
+    (query_id, message.finish())
+}
+
+/// [`build_question`] left open at the additional section, where a TSIG
+/// record can still be appended.
+pub(crate) fn question_message(
+    opcode: Opcode,
+    aa: bool,
+    qname: &Name<Vec<u8>>,
+) -> (u16, AdditionalBuilder<Vec<u8>>) {
     let query_id = rand::random::<u16>();
 
     let mut builder = MessageBuilder::new_vec();
@@ -84,58 +97,62 @@ pub(crate) fn build_question(opcode: Opcode, aa: bool, qname: &Name<Vec<u8>>) ->
         .push((qname, Rtype::SOA))
         .expect("composing into a Vec cannot run out of space");
 
-    (query_id, question.finish())
+    (query_id, question.additional())
 }
 
-/// Resolve the comma-separated `secondary_addrs` config value into per-entry
-/// results: the original entry text plus its resolved addresses (all of them;
-/// callers pick what they need) or the resolution failure. `resolve_timeout`
-/// bounds each hostname lookup so a stalled system resolver fails the entry
-/// instead of hanging the caller.
+/// Resolve the parsed `secondary_addrs` entries into per-entry results: the
+/// entry plus its resolved addresses (all of them; callers pick what they
+/// need) or the resolution failure. `resolve_timeout` bounds each hostname
+/// lookup so a stalled system resolver fails the entry instead of hanging the
+/// caller.
 pub(crate) async fn resolve_secondary_entries(
-    raw: &str,
+    secondaries: &[SecondaryEntry],
     resolve_timeout: Duration,
-) -> Vec<(String, Result<Vec<SocketAddr>, String>)> {
+) -> Vec<(SecondaryEntry, Result<Vec<SocketAddr>, String>)> {
     let mut entries = Vec::new();
 
-    for item in raw.split(',') {
-        let trimmed = item.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
+    for entry in secondaries {
+        let result = resolve_address(entry.address.as_str(), resolve_timeout).await;
+        entries.push((entry.clone(), result));
+    }
 
-        let result = match parse_address_target(trimmed, 53) {
-            ParsedAddress::SocketAddr(addr) => Ok(vec![addr]),
-            ParsedAddress::HostPort(host_port) => {
-                match tokio::time::timeout(resolve_timeout, lookup_host(&host_port)).await {
-                    Ok(Ok(resolved)) => {
-                        let addrs: Vec<SocketAddr> = resolved.collect();
-                        if addrs.is_empty() {
-                            Err("no addresses".to_string())
-                        } else {
-                            Ok(addrs)
-                        }
-                    }
-                    Ok(Err(e)) => {
-                        log_error!("Invalid server address '{}': {}", trimmed, e);
-                        Err(e.to_string())
-                    }
-                    Err(_) => {
-                        log_error!(
-                            "Resolving server address '{}' timed out after {} seconds",
-                            trimmed,
-                            resolve_timeout.as_secs()
-                        );
-                        Err(format!(
-                            "resolution timed out after {} seconds",
-                            resolve_timeout.as_secs()
-                        ))
+    entries
+}
+
+/// Resolve one `host[:port]` or IP address target (port 53 by default) to its
+/// socket addresses, with `resolve_timeout` bounding a hostname lookup.
+pub(crate) async fn resolve_address(
+    address: &str,
+    resolve_timeout: Duration,
+) -> Result<Vec<SocketAddr>, String> {
+    match parse_address_target(address, 53) {
+        ParsedAddress::SocketAddr(addr) => Ok(vec![addr]),
+        ParsedAddress::HostPort(host_port) => {
+            match tokio::time::timeout(resolve_timeout, lookup_host(&host_port)).await {
+                Ok(Ok(resolved)) => {
+                    let addrs: Vec<SocketAddr> = resolved.collect();
+                    if addrs.is_empty() {
+                        Err("no addresses".to_string())
+                    } else {
+                        Ok(addrs)
                     }
                 }
+                Ok(Err(e)) => {
+                    log_error!("Invalid server address '{}': {}", address, e);
+                    Err(e.to_string())
+                }
+                Err(_) => {
+                    log_error!(
+                        "Resolving server address '{}' timed out after {} seconds",
+                        address,
+                        resolve_timeout.as_secs()
+                    );
+                    Err(format!(
+                        "resolution timed out after {} seconds",
+                        resolve_timeout.as_secs()
+                    ))
+                }
             }
-        };
-        entries.push((trimmed.to_string(), result));
+        }
     }
-
-    entries
 }
@@ -1,12 +1,24 @@
-use std::{net::SocketAddr, str::FromStr, time::Duration};
-
-use domain::base::{
-    Message, Name,
-    iana::{Opcode, Rcode},
+use std::{net::SocketAddr, str::FromStr, sync::Arc, time::Duration};
+
+use domain::{
+    base::{
+        Message, Name,
+        iana::{Opcode, Rcode},
+    },
+    rdata::tsig::Time48,
+    tsig::{ClientTransaction, Key},
 };
 
 use crate::{
-    config, error::XfrError, log_error, log_info, metrics::metrics, service::zone::ZoneService,
+    config::{self, SecondaryEntry},
+    error::XfrError,
+    log_error, log_info,
+    metrics::metrics,
+    service::{
+        tsig_key::TsigKeyService,
+        zone::{ZoneService, notify_target::ZoneNotifyTargetService},
+    },
+    tsig,
 };
 
 /// Sends DNS NOTIFY to all configured secondary servers; a `None` zone_name
@@ -89,15 +101,46 @@ pub struct SecondaryNotify {
     pub result: Result<(), String>,
 }
 
-/// Send NOTIFY for a zone to every resolved secondary address (the transfer
-/// ACL admits each one, so every replica must hear the change). An empty
-/// `secondary_addrs` yields an empty list.
+/// Send NOTIFY for a zone to every resolved address of its NOTIFY targets:
+/// the zone's own, or the configured secondaries, plus its also-notify
+/// targets. Sending a NOTIFY admits no transfer; a target must also be
+/// allowed by the zone's transfer ACL to fetch the change. Each is signed
+/// with the entry's TSIG key when it names one. No targets yield an empty
+/// list.
 pub async fn notify_secondaries(zone_name: &str) -> Result<Vec<SecondaryNotify>, XfrError> {
-    let dns_config = &config::get_bindizr_config().dns;
-    let raw = dns_config.secondary_addrs.clone();
-    if raw.trim().is_empty() {
+    let secondaries = notify_targets(zone_name).await?;
+    notify_entries(zone_name, &secondaries).await
+}
+
+/// [`notify_secondaries`] for the one target whose address is `secondary`;
+/// a target no longer listed yields an empty list.
+pub async fn notify_secondary(
+    zone_name: &str,
+    secondary: &str,
+) -> Result<Vec<SecondaryNotify>, XfrError> {
+    let secondaries: Vec<_> = notify_targets(zone_name)
+        .await?
+        .into_iter()
+        .filter(|entry| entry.address == secondary)
+        .collect();
+    notify_entries(zone_name, &secondaries).await
+}
+
+/// The zone's NOTIFY targets, falling back to the configured secondaries.
+pub(crate) async fn notify_targets(zone_name: &str) -> Result<Vec<SecondaryEntry>, XfrError> {
+    ZoneNotifyTargetService::targets_for(zone_name)
+        .await
+        .map_err(|e| XfrError::DatabaseError(e.to_string()))
+}
+
+async fn notify_entries(
+    zone_name: &str,
+    secondaries: &[SecondaryEntry],
+) -> Result<Vec<SecondaryNotify>, XfrError> {
+    if secondaries.is_empty() {
         return Ok(Vec::new());
     }
+    let dns_config = &config::get_bindizr_config().dns;
     let timeout = Duration::from_secs(dns_config.notify_timeout_secs);
     let retries = dns_config.notify_retries;
 
@@ -105,7 +148,7 @@ pub async fn notify_secondaries(zone_name: &str) -> Result<Vec<SecondaryNotify>,
         .map_err(|e| XfrError::ProtocolError(format!("Invalid zone name: {}", e)))?;
 
     let mut reports = Vec::new();
-    for (entry, result) in super::resolve_secondary_entries(&raw, timeout).await {
+    for (entry, result) in super::resolve_secondary_entries(secondaries, timeout).await {
         let addrs = match result {
             Ok(addrs) => addrs,
             Err(e) => {
@@ -116,29 +159,50 @@ pub async fn notify_secondaries(zone_name: &str) -> Result<Vec<SecondaryNotify>,
                     .with_label_values(&["resolve_error"])
                     .inc();
                 reports.push(SecondaryNotify {
-                    address: entry,
+                    address: entry.address,
                     result: Err(format!("failed to resolve: {}", e)),
                 });
                 continue;
             }
         };
 
+        let key = match &entry.key {
+            Some(name) => notify_key(name).await.map(Some),
+            None => Ok(None),
+        };
+        let key = match key {
+            Ok(key) => key,
+            Err(e) => {
+                log_error!("Cannot sign NOTIFY to {}: {}", entry.address, e);
+                metrics()
+                    .notify_sent_total
+                    .with_label_values(&["error"])
+                    .inc();
+                reports.push(SecondaryNotify {
+                    address: entry.address,
+                    result: Err(e.to_string()),
+                });
+                continue;
+            }
+        };
+
         for addr in addrs {
-            let result = match send_notify_to_server(&qname, addr, timeout, retries).await {
-                Ok(()) => {
-                    log_info!("NOTIFY sent successfully to {}", addr);
-                    metrics().notify_sent_total.with_label_values(&["ok"]).inc();
-                    Ok(())
-                }
-                Err(e) => {
-                    log_error!("Failed to send NOTIFY to {}: {}", addr, e);
-                    metrics()
-                        .notify_sent_total
-                        .with_label_values(&["error"])
-                        .inc();
-                    Err(e.to_string())
-                }
-            };
+            let result =
+                match send_notify_to_server(&qname, addr, key.as_ref(), timeout, retries).await {
+                    Ok(()) => {
+                        log_info!("NOTIFY sent successfully to {}", addr);
+                        metrics().notify_sent_total.with_label_values(&["ok"]).inc();
+                        Ok(())
+                    }
+                    Err(e) => {
+                        log_error!("Failed to send NOTIFY to {}: {}", addr, e);
+                        metrics()
+                            .notify_sent_total
+                            .with_label_values(&["error"])
+                            .inc();
+                        Err(e.to_string())
+                    }
+                };
             reports.push(SecondaryNotify {
                 address: addr.to_string(),
                 result,
@@ -149,10 +213,20 @@ pub async fn notify_secondaries(zone_name: &str) -> Result<Vec<SecondaryNotify>,
     Ok(reports)
 }
 
+/// The stored TSIG key a secondary entry names, ready for signing.
+async fn notify_key(name: &str) -> Result<Arc<Key>, XfrError> {
+    let key = TsigKeyService::find_by_wire_name(name)
+        .await
+        .map_err(|e| XfrError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| XfrError::NotifyFailed(format!("TSIG key '{}' does not exist", name)))?;
+    tsig::domain_key(&key).map_err(XfrError::ProtocolError)
+}
+
 /// Sends a NOTIFY to one server, retrying up to the configured limit.
 async fn send_notify_to_server(
     zone_name: &Name<Vec<u8>>,
     server_addr: SocketAddr,
+    key: Option<&Arc<Key>>,
     timeout: Duration,
     retries: u32,
 ) -> Result<(), XfrError> {
@@ -160,7 +234,7 @@ async fn send_notify_to_server(
     let mut last_error = None;
 
     for attempt in 1..=attempts {
-        match send_notify_to_server_once(zone_name, server_addr, timeout).await {
+        match send_notify_to_server_once(zone_name, server_addr, key, timeout).await {
             Ok(()) => return Ok(()),
             Err(e) => {
                 if attempt < attempts {
@@ -185,9 +259,15 @@ async fn send_notify_to_server(
 async fn send_notify_to_server_once(
     zone_name: &Name<Vec<u8>>,
     server_addr: SocketAddr,
+    key: Option<&Arc<Key>>,
     timeout: Duration,
 ) -> Result<(), XfrError> {
-    let (query_id, notify_message) = super::build_question(Opcode::NOTIFY, true, zone_name);
+    let (query_id, mut message) = super::question_message(Opcode::NOTIFY, true, zone_name);
+    let transaction = key
+        .map(|key| ClientTransaction::request(key.clone(), &mut message, Time48::now()))
+        .transpose()
+        .map_err(|e| XfrError::ProtocolError(format!("Failed to sign NOTIFY: {}", e)))?;
+    let notify_message = message.finish();
 
     let (received, response) =
         super::udp_exchange(server_addr, timeout, &notify_message, "NOTIFY").await?;
@@ -200,6 +280,18 @@ async fn send_notify_to_server_once(
 
     validate_notify_response(query_id, &response[..received])?;
 
+    // A signed NOTIFY expects a response signed with the same key
+    // (RFC 8945, Section 5.3).
+    if let Some(transaction) = transaction {
+        let mut response = Message::from_octets(response[..received].to_vec())
+            .map_err(|e| XfrError::ProtocolError(format!("NOTIFY response is malformed: {}", e)))?;
+        transaction
+            .answer(&mut response, Time48::now())
+            .map_err(|e| {
+                XfrError::ProtocolError(format!("NOTIFY response failed TSIG validation: {}", e))
+            })?;
+    }
+
     Ok(())
 }
 
@@ -13,34 +13,39 @@ use domain::{
 
 use crate::{config, error::XfrError};
 
-/// Result of probing one configured secondary: the serial its SOA answer
-/// carries, or the reason the probe failed.
+/// Result of probing one NOTIFY target: the serial its SOA answer carries,
+/// or the reason the probe failed.
 pub struct SecondaryProbe {
+    /// The target probed, as the zone or `secondary_addrs` lists it.
+    pub secondary: String,
+    /// The address that answered; on failure, the last one tried.
     pub address: String,
     pub result: Result<u32, String>,
 }
 
-/// Query every configured secondary for the zone's SOA serial, in parallel.
-/// One probe per configured entry; a hostname entry is tried at each resolved
-/// address until one answers. An empty `secondary_addrs` yields an empty list.
+/// Query every NOTIFY target of the zone (see
+/// [`notify_secondaries`](super::notify::notify_secondaries)) for its SOA
+/// serial, in parallel. One probe per target; a hostname is tried at each
+/// resolved address until one answers. No targets yield an empty list.
 pub async fn probe_secondaries(zone_name: &str) -> Result<Vec<SecondaryProbe>, XfrError> {
-    let dns_config = &config::get_bindizr_config().dns;
-    let raw = dns_config.secondary_addrs.clone();
-    if raw.trim().is_empty() {
+    let secondaries = super::notify::notify_targets(zone_name).await?;
+    if secondaries.is_empty() {
         return Ok(Vec::new());
     }
-    let timeout = Duration::from_secs(dns_config.notify_timeout_secs);
+    let timeout = Duration::from_secs(config::get_bindizr_config().dns.notify_timeout_secs);
 
     let qname = Name::<Vec<u8>>::from_str(zone_name)
         .map_err(|e| XfrError::ProtocolError(format!("Invalid zone name: {}", e)))?;
 
     let mut probes = Vec::new();
     let mut tasks = Vec::new();
-    for (entry, result) in super::resolve_secondary_entries(&raw, timeout).await {
+    for (entry, result) in super::resolve_secondary_entries(&secondaries, timeout).await {
+        let entry = entry.address;
         let addrs = match result {
             Ok(addrs) => addrs,
             Err(e) => {
                 probes.push(SecondaryProbe {
+                    secondary: entry.clone(),
                     address: entry,
                     result: Err(format!("failed to resolve: {}", e)),
                 });
@@ -57,8 +62,13 @@ pub async fn probe_secondaries(zone_name: &str) -> Result<Vec<SecondaryProbe>, X
 
     for (entry, task) in tasks {
         match task.await {
-            Ok((address, result)) => probes.push(SecondaryProbe { address, result }),
+            Ok((address, result)) => probes.push(SecondaryProbe {
+                secondary: entry,
+                address,
+                result,
+            }),
             Err(e) => probes.push(SecondaryProbe {
+                secondary: entry.clone(),
                 address: entry,
                 result: Err(format!("probe task failed: {}", e)),
             }),
@@ -1,27 +1,33 @@
-//! DNS front end: the authoritative TCP/UDP server plus zone transfer
-//! (AXFR/IXFR), NOTIFY, SOA queries, RFC 2136 nsupdate, and secondary ACLs.
+//! DNS front end: the authoritative TCP/UDP server and its optional TLS
+//! listener, plus zone transfer (AXFR/IXFR), NOTIFY, SOA queries, RFC 2136
+//! nsupdate, secondary ACLs, and optionally answers to ordinary queries.
 
 pub(crate) mod address;
 pub mod client;
 pub(crate) mod error;
+pub mod monitor;
+pub mod pull;
 pub(crate) mod server;
 pub mod status;
+pub(crate) mod tsig;
 pub(crate) mod wire;
 
 use std::{io::ErrorKind, net::SocketAddr, time::Duration};
 
-pub(crate) use bindizr_core::{config, log_error, log_info, log_warn, metrics, model};
+pub(crate) use bindizr_core::{config, log_debug, log_error, log_info, log_warn, metrics, model};
 pub(crate) use bindizr_service as service;
 use domain::base::iana::Rtype;
-use server::acl::SecondaryAcl;
 use tokio::{
-    net::{TcpListener, TcpStream, UdpSocket},
+    net::{TcpListener, UdpSocket},
     time::timeout,
 };
+use tokio_rustls::{TlsAcceptor, rustls::pki_types::CertificateDer};
+use wire::DnsStream;
 
 const TCP_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
 
-/// Initializes the DNS service: prepares the catalog zone and spawns the TCP and UDP servers.
+/// Initializes the DNS service: prepares the catalog zone and spawns the TCP
+/// and UDP servers, and the TLS server when enabled.
 pub async fn initialize() {
     server::initialize().await;
 
@@ -31,26 +37,37 @@ pub async fn initialize() {
         bindizr_config.dns.listen_port,
     );
 
-    let secondary_acl = server::acl::secondary_acl_from_config();
-    let tcp_secondary_acl = secondary_acl.clone();
+    if bindizr_config.dns.tls_enabled {
+        let tls_listen_addr = SocketAddr::new(
+            bindizr_config.dns.listen_addr,
+            bindizr_config.dns.tls_listen_port,
+        );
+        match server::tls::acceptor_from_config(&bindizr_config.dns) {
+            Ok(acceptor) => {
+                tokio::spawn(async move {
+                    if let Err(e) = run_tls_server(tls_listen_addr, acceptor).await {
+                        log_error!("DNS TLS server error: {}", e);
+                    }
+                });
+            }
+            Err(e) => log_error!("DNS TLS server not started: {}", e),
+        }
+    }
 
     tokio::spawn(async move {
-        if let Err(e) = run_tcp_server(listen_addr, tcp_secondary_acl).await {
+        if let Err(e) = run_tcp_server(listen_addr).await {
             log_error!("DNS TCP server error: {}", e);
         }
     });
 
     tokio::spawn(async move {
-        if let Err(e) = run_udp_server(listen_addr, secondary_acl).await {
+        if let Err(e) = run_udp_server(listen_addr).await {
             log_error!("DNS UDP server error: {}", e);
         }
     });
 }
 
-async fn run_tcp_server(
-    listen_addr: SocketAddr,
-    secondary_acl: SecondaryAcl,
-) -> Result<(), String> {
+async fn run_tcp_server(listen_addr: SocketAddr) -> Result<(), String> {
     let listener = TcpListener::bind(listen_addr)
         .await
         .map_err(|e| format!("Failed to bind DNS TCP listener on {}: {}", listen_addr, e))?;
@@ -60,9 +77,8 @@ async fn run_tcp_server(
     loop {
         match listener.accept().await {
             Ok((stream, client_addr)) => {
-                let allowed = secondary_acl.clone();
                 tokio::spawn(async move {
-                    if let Err(e) = handle_tcp_connection(stream, client_addr, allowed).await {
+                    if let Err(e) = handle_tcp_connection(stream, client_addr, None).await {
                         log_error!("DNS TCP connection error from {}: {}", client_addr, e);
                     }
                 });
@@ -74,10 +90,56 @@ async fn run_tcp_server(
     }
 }
 
+async fn run_tls_server(listen_addr: SocketAddr, acceptor: TlsAcceptor) -> Result<(), String> {
+    let listener = TcpListener::bind(listen_addr)
+        .await
+        .map_err(|e| format!("Failed to bind DNS TLS listener on {}: {}", listen_addr, e))?;
+
+    log_info!("DNS TLS server listening on {}", listen_addr);
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, client_addr)) => {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let stream = match timeout(TCP_IDLE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(stream)) => stream,
+                        Ok(Err(e)) => {
+                            log_warn!("DNS TLS handshake with {} failed: {}", client_addr, e);
+                            return;
+                        }
+                        Err(_) => {
+                            log_warn!("DNS TLS handshake with {} timed out", client_addr);
+                            return;
+                        }
+                    };
+                    // Only a certificate the handshake verified against the
+                    // client CA is present here.
+                    let client_certificate = stream
+                        .get_ref()
+                        .1
+                        .peer_certificates()
+                        .and_then(|chain| chain.first())
+                        .map(|certificate| certificate.clone().into_owned());
+
+                    if let Err(e) =
+                        handle_tcp_connection(stream, client_addr, client_certificate).await
+                    {
+                        log_error!("DNS TLS connection error from {}: {}", client_addr, e);
+                    }
+                });
+            }
+            Err(e) => {
+                log_error!("Failed to accept DNS TLS connection: {}", e);
+            }
+        }
+    }
+}
+
 async fn handle_tcp_connection(
-    mut stream: TcpStream,
+    mut stream: impl DnsStream,
     client_addr: SocketAddr,
-    secondary_acl: SecondaryAcl,
+    client_certificate: Option<CertificateDer<'static>>,
 ) -> Result<(), String> {
     loop {
         let query_data = match timeout(TCP_IDLE_TIMEOUT, crate::wire::read_tcp_message(&mut stream))
@@ -98,16 +160,22 @@ async fn handle_tcp_connection(
             }
         };
 
-        handle_tcp_query(&mut stream, client_addr, &secondary_acl, &query_data).await?;
+        handle_tcp_query(
+            &mut stream,
+            client_addr,
+            client_certificate.as_ref(),
+            &query_data,
+        )
+        .await?;
     }
 
     Ok(())
 }
 
 async fn handle_tcp_query(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     client_addr: SocketAddr,
-    secondary_acl: &SecondaryAcl,
+    client_certificate: Option<&CertificateDer<'_>>,
     query_data: &[u8],
 ) -> Result<(), String> {
     // nsupdate owns its own parsing (including TSIG); everything else shares
@@ -129,9 +197,22 @@ async fn handle_tcp_query(
             .await
             .map_err(|e| format!("Failed to handle SOA TCP query: {}", e))?;
     } else if server::is_xfr_query_type(query.qtype) {
-        server::handle_tcp_query(stream, client_addr, secondary_acl, &query)
+        // Built per query so a config reload applies to the next transfer,
+        // while one already running keeps the ACL it was admitted under.
+        server::handle_tcp_query(
+            stream,
+            client_addr,
+            client_certificate,
+            &server::acl::secondary_acl_from_config(),
+            query_data,
+            &query,
+        )
+        .await
+        .map_err(|e| format!("Failed to handle XFR TCP query: {}", e))?;
+    } else if config::get_bindizr_config().dns.answer_queries {
+        server::answer::handle_tcp_query(stream, client_addr, &query)
             .await
-            .map_err(|e| format!("Failed to handle XFR TCP query: {}", e))?;
+            .map_err(|e| format!("Failed to answer DNS TCP query: {}", e))?;
     } else {
         log_info!(
             "Ignoring non-XFR DNS TCP query from {} (qtype={:?})",
@@ -143,10 +224,7 @@ async fn handle_tcp_query(
     Ok(())
 }
 
-async fn run_udp_server(
-    listen_addr: SocketAddr,
-    secondary_acl: SecondaryAcl,
-) -> Result<(), String> {
+async fn run_udp_server(listen_addr: SocketAddr) -> Result<(), String> {
     let socket = UdpSocket::bind(listen_addr)
         .await
         .map_err(|e| format!("Failed to bind DNS UDP socket on {}: {}", listen_addr, e))?;
@@ -185,9 +263,14 @@ async fn run_udp_server(
                 log_warn!("Failed to handle SOA UDP query from {}: {}", client_addr, e);
             }
         } else if server::is_xfr_query_type(query.qtype) {
+            let secondary_acl = server::acl::secondary_acl_from_config();
             if let Err(e) = server::handle_udp_query(client_addr, &secondary_acl, &query).await {
                 log_warn!("Failed to handle XFR UDP query from {}: {}", client_addr, e);
             }
+        } else if config::get_bindizr_config().dns.answer_queries
+            && let Err(e) = server::answer::handle_udp_query(&socket, client_addr, &query).await
+        {
+            log_warn!("Failed to answer DNS UDP query from {}: {}", client_addr, e);
         }
     }
 }
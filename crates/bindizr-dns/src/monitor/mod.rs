@@ -0,0 +1,289 @@
+//! Continuous secondary sync monitoring: [`status`](crate::status) probes on
+//! demand, this probes on a timer. Every `dns.sync_monitor_interval_secs` a
+//! background task asks each configured secondary for every zone's SOA serial,
+//! keeps the last result per zone and secondary in memory and in Prometheus,
+//! and re-NOTIFYs a secondary that stays behind for
+//! `dns.sync_renotify_after_secs`.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+    time::Duration,
+};
+
+use bindizr_core::dns::serial;
+use chrono::{DateTime, TimeDelta, Utc};
+use tokio::{sync::Semaphore, task::JoinSet, time::sleep};
+
+use crate::{
+    client::{
+        notify,
+        probe::{self, SecondaryProbe},
+    },
+    config, log_error, log_info, log_warn,
+    metrics::metrics,
+    service::{
+        authorization::{Caller, TokenRole},
+        error::ServiceError,
+        types::{SecondariesStatusResponse, SecondaryStatusResponse, SecondarySyncResponse},
+        zone::ZoneService,
+    },
+};
+
+/// Zones probed at once. Each probes all of its secondaries in parallel, and
+/// an unreachable one holds its zone for the whole probe timeout.
+const ZONE_CONCURRENCY: usize = 16;
+
+/// How often a disabled monitor checks whether a reload turned it on.
+const DISABLED_RECHECK: Duration = Duration::from_secs(60);
+
+/// Zone name, then the NOTIFY target probed.
+type PairKey = (String, String);
+
+#[derive(Default)]
+struct MonitorState {
+    last_round_at: Option<DateTime<Utc>>,
+    pairs: BTreeMap<PairKey, SecondarySyncResponse>,
+}
+
+static STATE: OnceLock<Mutex<MonitorState>> = OnceLock::new();
+static STARTED: OnceLock<()> = OnceLock::new();
+
+/// The state holds no invariant a panicking thread could leave broken, so a
+/// poisoned lock is recovered rather than failing every later read.
+fn state() -> MutexGuard<'static, MonitorState> {
+    STATE
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Spawn the background monitor. First call wins; later calls are no-ops.
+/// Settings are read every round, so a reload applies from the next one.
+pub fn init_sync_monitor() {
+    if STARTED.set(()).is_err() {
+        return;
+    }
+
+    tokio::spawn(async {
+        loop {
+            let interval = config::get_bindizr_config().dns.sync_monitor_interval_secs;
+            if interval == 0 {
+                clear();
+                sleep(DISABLED_RECHECK).await;
+                continue;
+            }
+
+            sleep(Duration::from_secs(interval)).await;
+            if let Err(e) = run_round().await {
+                log_error!("Secondary sync monitor round failed: {}", e);
+            }
+        }
+    });
+}
+
+/// The monitor's last view of every zone on every secondary. Fleet-wide, so
+/// scoped tokens, which see only their granted zones, are refused.
+pub fn secondaries_status(caller: &Caller) -> Result<SecondariesStatusResponse, ServiceError> {
+    caller.require_role(TokenRole::Viewer, "view secondary sync status")?;
+
+    let state = state();
+    Ok(SecondariesStatusResponse {
+        interval_secs: config::get_bindizr_config().dns.sync_monitor_interval_secs,
+        last_round_at: state.last_round_at,
+        secondaries: state.pairs.values().cloned().collect(),
+    })
+}
+
+/// Probe every zone and fold the results into the state. Pairs not probed
+/// this round — a deleted zone, a removed secondary — are dropped, and their
+/// series with them.
+async fn run_round() -> Result<(), ServiceError> {
+    let zones = ZoneService::list().await?;
+    let renotify_after = config::get_bindizr_config().dns.sync_renotify_after_secs;
+
+    let permits = Arc::new(Semaphore::new(ZONE_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for zone in zones {
+        let permits = permits.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire().await;
+            let zone_name = zone.name.to_string();
+            let probes = probe::probe_secondaries(&zone_name).await;
+            (zone_name, zone.serial, probes)
+        });
+    }
+
+    // Cloned, not taken: the fleet view keeps serving the last round meanwhile.
+    let previous = state().pairs.clone();
+    let mut pairs = BTreeMap::new();
+    while let Some(joined) = tasks.join_next().await {
+        let (zone_name, zone_serial, probes) = match joined {
+            Ok(result) => result,
+            Err(e) => {
+                log_error!("Secondary sync probe task failed: {}", e);
+                continue;
+            }
+        };
+        let probes = match probes {
+            Ok(probes) => probes,
+            Err(e) => {
+                log_warn!("Cannot probe secondaries for zone {}: {}", zone_name, e);
+                continue;
+            }
+        };
+
+        let now = Utc::now();
+        for probe in probes {
+            let key = (zone_name.clone(), probe.secondary.clone());
+            let mut sync = observe(
+                &zone_name,
+                serial::to_wire(zone_serial),
+                probe,
+                previous.get(&key),
+                now,
+            );
+            record_metrics(&sync);
+
+            if renotify_due(&sync, renotify_after, now) {
+                renotify(&sync).await;
+                sync.last_notified_at = Some(now);
+            }
+            pairs.insert(key, sync);
+        }
+    }
+
+    for (zone, secondary) in previous.keys().filter(|key| !pairs.contains_key(*key)) {
+        remove_series(zone, secondary);
+    }
+
+    let mut state = state();
+    state.pairs = pairs;
+    state.last_round_at = Some(Utc::now());
+    Ok(())
+}
+
+/// Fold one probe of a zone at `zone_serial` into the pair's state, carrying
+/// the lag start, failure streak and last re-NOTIFY over from `previous`.
+fn observe(
+    zone_name: &str,
+    zone_serial: u32,
+    probe: SecondaryProbe,
+    previous: Option<&SecondarySyncResponse>,
+    now: DateTime<Utc>,
+) -> SecondarySyncResponse {
+    let mut sync = SecondarySyncResponse {
+        zone: zone_name.to_string(),
+        serial: zone_serial,
+        secondary: probe.secondary,
+        address: probe.address,
+        status: "unreachable".to_string(),
+        visible_serial: None,
+        lag: None,
+        lagging_since: previous.and_then(|p| p.lagging_since),
+        last_notified_at: previous.and_then(|p| p.last_notified_at),
+        consecutive_failures: 0,
+        error: None,
+        probed_at: now,
+    };
+
+    match probe.result {
+        Ok(visible) => {
+            let status = SecondaryStatusResponse::classify(visible, zone_serial);
+            sync.status = status.to_string();
+            sync.visible_serial = Some(visible);
+            // RFC 1982 distance read as signed, so a secondary ahead is negative.
+            sync.lag = Some(i64::from(serial::distance(visible, zone_serial) as i32));
+            sync.lagging_since = match status {
+                "lagging" => sync.lagging_since.or(Some(now)),
+                _ => None,
+            };
+        }
+        Err(error) => {
+            sync.consecutive_failures = previous
+                .map_or(0, |p| p.consecutive_failures)
+                .saturating_add(1);
+            sync.error = Some(error);
+        }
+    }
+
+    sync
+}
+
+/// Whether a lagging secondary has been behind for `renotify_after_secs` and
+/// was not re-NOTIFYed within as long; 0 never re-NOTIFYs.
+fn renotify_due(
+    sync: &SecondarySyncResponse,
+    renotify_after_secs: u64,
+    now: DateTime<Utc>,
+) -> bool {
+    if renotify_after_secs == 0 || sync.status != "lagging" {
+        return false;
+    }
+    let after = TimeDelta::seconds(i64::try_from(renotify_after_secs).unwrap_or(i64::MAX));
+    sync.lagging_since.is_some_and(|since| now - since >= after)
+        && sync.last_notified_at.is_none_or(|at| now - at >= after)
+}
+
+async fn renotify(sync: &SecondarySyncResponse) {
+    log_info!(
+        "Secondary {} is {} serial(s) behind zone {}; sending NOTIFY again",
+        sync.secondary,
+        sync.lag.unwrap_or_default(),
+        sync.zone
+    );
+    match notify::notify_secondary(&sync.zone, &sync.secondary).await {
+        Ok(reports) => {
+            for report in reports {
+                if let Err(e) = report.result {
+                    log_warn!(
+                        "Re-NOTIFY of zone {} to {} failed: {}",
+                        sync.zone,
+                        report.address,
+                        e
+                    );
+                }
+            }
+        }
+        Err(e) => log_warn!("Re-NOTIFY of zone {} failed: {}", sync.zone, e),
+    }
+}
+
+/// The lag gauge keeps the last successful probe's value through failures,
+/// which count separately.
+fn record_metrics(sync: &SecondarySyncResponse) {
+    let labels = [sync.zone.as_str(), sync.secondary.as_str()];
+    match sync.lag {
+        Some(lag) => metrics()
+            .secondary_serial_lag
+            .with_label_values(&labels)
+            .set(lag),
+        None => metrics()
+            .secondary_probe_failures_total
+            .with_label_values(&labels)
+            .inc(),
+    }
+}
+
+fn remove_series(zone: &str, secondary: &str) {
+    // Absent when the pair never succeeded (or never failed); nothing to drop.
+    let _ = metrics()
+        .secondary_serial_lag
+        .remove_label_values(&[zone, secondary]);
+    let _ = metrics()
+        .secondary_probe_failures_total
+        .remove_label_values(&[zone, secondary]);
+}
+
+/// Forget every pair, so a monitor turned off by a reload stops reporting.
+fn clear() {
+    let mut state = state();
+    for (zone, secondary) in state.pairs.keys() {
+        remove_series(zone, secondary);
+    }
+    state.pairs.clear();
+    state.last_round_at = None;
+}
+
+#[cfg(test)]
+mod tests;
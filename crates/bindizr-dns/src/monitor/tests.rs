@@ -0,0 +1,103 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+use super::{observe, renotify_due};
+use crate::client::probe::SecondaryProbe;
+
+fn probe(result: Result<u32, String>) -> SecondaryProbe {
+    SecondaryProbe {
+        secondary: "ns2.example.net:53".to_string(),
+        address: "192.0.2.53:53".to_string(),
+        result,
+    }
+}
+
+fn at(secs: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp(1_800_000_000 + secs, 0).unwrap()
+}
+
+#[test]
+fn observe_classifies_a_secondary_and_measures_its_lag() {
+    let sync = observe("example.com", 42, probe(Ok(40)), None, at(0));
+    assert_eq!(sync.status, "lagging");
+    assert_eq!(sync.visible_serial, Some(40));
+    assert_eq!(sync.lag, Some(2));
+    assert_eq!(sync.lagging_since, Some(at(0)));
+    assert_eq!(sync.secondary, "ns2.example.net:53");
+    assert_eq!(sync.address, "192.0.2.53:53");
+
+    let sync = observe("example.com", 42, probe(Ok(42)), None, at(0));
+    assert_eq!(sync.status, "in_sync");
+    assert_eq!(sync.lag, Some(0));
+    assert_eq!(sync.lagging_since, None);
+
+    // Across the wrap: u32::MAX is one behind 0, and 1 is one ahead of it.
+    let sync = observe("example.com", 0, probe(Ok(u32::MAX)), None, at(0));
+    assert_eq!(sync.status, "lagging");
+    assert_eq!(sync.lag, Some(1));
+    let sync = observe("example.com", 0, probe(Ok(1)), None, at(0));
+    assert_eq!(sync.status, "ahead");
+    assert_eq!(sync.lag, Some(-1));
+}
+
+#[test]
+fn observe_keeps_the_lag_start_until_the_secondary_catches_up() {
+    let first = observe("example.com", 42, probe(Ok(40)), None, at(0));
+    let second = observe("example.com", 43, probe(Ok(41)), Some(&first), at(60));
+    assert_eq!(second.lagging_since, Some(at(0)));
+
+    // A failed probe says nothing about the lag, so the start carries over.
+    let failed = observe(
+        "example.com",
+        43,
+        probe(Err("timeout".to_string())),
+        Some(&second),
+        at(120),
+    );
+    assert_eq!(failed.lagging_since, Some(at(0)));
+
+    let caught_up = observe("example.com", 43, probe(Ok(43)), Some(&failed), at(180));
+    assert_eq!(caught_up.lagging_since, None);
+}
+
+#[test]
+fn observe_counts_failures_in_a_row() {
+    let first = observe(
+        "example.com",
+        42,
+        probe(Err("timeout".to_string())),
+        None,
+        at(0),
+    );
+    assert_eq!(first.status, "unreachable");
+    assert_eq!(first.consecutive_failures, 1);
+    assert_eq!(first.error.as_deref(), Some("timeout"));
+    assert_eq!(first.lag, None);
+
+    let second = observe(
+        "example.com",
+        42,
+        probe(Err("timeout".to_string())),
+        Some(&first),
+        at(60),
+    );
+    assert_eq!(second.consecutive_failures, 2);
+
+    let answered = observe("example.com", 42, probe(Ok(42)), Some(&second), at(120));
+    assert_eq!(answered.consecutive_failures, 0);
+    assert_eq!(answered.error, None);
+}
+
+#[test]
+fn renotify_waits_for_the_lag_and_between_notifies() {
+    let mut sync = observe("example.com", 42, probe(Ok(40)), None, at(0));
+    assert!(!renotify_due(&sync, 300, at(299)));
+    assert!(renotify_due(&sync, 300, at(300)));
+    assert!(!renotify_due(&sync, 0, at(3600)));
+
+    sync.last_notified_at = Some(at(300));
+    assert!(!renotify_due(&sync, 300, at(599)));
+    assert!(renotify_due(&sync, 300, at(600)));
+
+    let in_sync = observe("example.com", 42, probe(Ok(42)), Some(&sync), at(900));
+    assert!(!renotify_due(&in_sync, 300, at(900) + TimeDelta::days(1)));
+}
@@ -0,0 +1,68 @@
+//! Pulling a zone from another primary: an AXFR from the primary, fed
+//! through the same reconciliation as a zone-file import. The transfer client
+//! lives in this crate and the importer in the service layer below it, so the
+//! HTTP API and the daemon socket both call in here.
+
+use crate::{
+    client::axfr,
+    service::{
+        authorization::{Caller, TokenRole},
+        error::ServiceError,
+        precondition::Precondition,
+        record::RecordService,
+        tsig_key::TsigKeyService,
+        types::{ImportZoneFileRequest, PullZoneRequest, PullZoneResponse},
+        zone::ZoneService,
+    },
+};
+
+/// Transfer `zone_name` from `request.server` and reconcile it with the zone
+/// by `request.mode`, or only preview the result with `dry_run`. The zone must
+/// already exist; its SOA fields and serial stay bindizr's own.
+pub async fn pull_zone(
+    caller: &Caller,
+    zone_name: &str,
+    request: &PullZoneRequest,
+    precondition: &Precondition,
+) -> Result<PullZoneResponse, ServiceError> {
+    // Checked before connecting anywhere, not just by the import.
+    caller.require_role(TokenRole::ZoneAdmin, "pull zones")?;
+    let zone = ZoneService::get_by_name(caller, zone_name).await?;
+
+    let server = request.server.trim();
+    if server.is_empty() {
+        return Err(ServiceError::invalid_input("server must not be empty"));
+    }
+    let key = match &request.tsig_key {
+        Some(name) => Some(TsigKeyService::get(caller, name).await?),
+        None => None,
+    };
+
+    let transferred = axfr::transfer_zone(zone.name.as_str(), server, key.as_ref())
+        .await
+        .map_err(|e| {
+            ServiceError::transfer_failed(format!(
+                "failed to transfer zone {} from {}: {}",
+                zone.name, server, e
+            ))
+        })?;
+
+    let import = RecordService::import_zone_file(
+        caller,
+        zone.name.as_str(),
+        &ImportZoneFileRequest {
+            content: transferred.content,
+            mode: request.mode,
+            dry_run: request.dry_run,
+        },
+        precondition,
+    )
+    .await?;
+
+    Ok(PullZoneResponse {
+        server: server.to_string(),
+        serial: transferred.serial,
+        transferred: transferred.records,
+        import,
+    })
+}
@@ -1,13 +1,28 @@
-//! Access control for zone transfers: matches client addresses against the
-//! configured secondary servers.
+//! Access control for zone transfers: matches client addresses (or, over
+//! mutual TLS, client certificates) against the configured secondary servers
+//! and reports which TSIG keys, if any, the transfer must then be signed with.
+//!
+//! A zone with transfer ACL entries of its own is governed by those instead
+//! of the global list; the catalog zone admits anyone some member zone
+//! admits, and lists each client only the members it may transfer.
 
-use std::net::IpAddr;
+use std::{collections::HashMap, net::IpAddr, str::FromStr};
 
+use ipnet::IpNet;
 use tokio::net::lookup_host;
+use tokio_rustls::rustls::{
+    client::verify_server_name,
+    pki_types::{CertificateDer, ServerName},
+    server::ParsedCertificate,
+};
 
 use crate::{
     address::{ParsedAddress, parse_address_target},
-    config, log_warn,
+    config::{self, SecondaryEntry},
+    error::XfrError,
+    log_warn,
+    model::zone_transfer_acl::{TransferAclAction, ZoneTransferAcl},
+    service::zone::transfer_acl::ZoneTransferAclService,
 };
 
 #[derive(Clone)]
@@ -16,53 +31,287 @@ pub(crate) struct SecondaryAcl {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-enum SecondaryAclEntry {
+struct SecondaryAclEntry {
+    target: SecondaryAclTarget,
+    key: Option<String>,
+    deny: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum SecondaryAclTarget {
     Ip(IpAddr),
+    Network(IpNet),
     HostPort(String),
 }
 
+/// The zones' own ACLs, keyed by lowercase zone name, as loaded for one
+/// transfer request.
+pub(crate) struct ZoneAcls {
+    zones: HashMap<String, SecondaryAcl>,
+}
+
+/// How a client address is admitted to transfer zones.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TransferAdmission {
+    Denied,
+    /// A keyless entry matches: the address alone is enough.
+    Address,
+    /// Only keyed entries match: the request must be signed with one of
+    /// these keys.
+    Keys(Vec<String>),
+}
+
+impl TransferAdmission {
+    /// Admission by either ACL: an address admission wins, keys accumulate.
+    fn union(self, other: TransferAdmission) -> TransferAdmission {
+        match (self, other) {
+            (TransferAdmission::Address, _) | (_, TransferAdmission::Address) => {
+                TransferAdmission::Address
+            }
+            (TransferAdmission::Denied, admission) | (admission, TransferAdmission::Denied) => {
+                admission
+            }
+            (TransferAdmission::Keys(mut keys), TransferAdmission::Keys(other)) => {
+                for key in other {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    }
+                }
+                TransferAdmission::Keys(keys)
+            }
+        }
+    }
+
+    /// Whether a request verified with `key_name` (or unsigned, for `None`)
+    /// gets the transfer.
+    fn permits(&self, key_name: Option<&str>) -> bool {
+        match self {
+            TransferAdmission::Denied => false,
+            TransferAdmission::Address => true,
+            TransferAdmission::Keys(keys) => {
+                key_name.is_some_and(|key_name| keys.iter().any(|key| key == key_name))
+            }
+        }
+    }
+}
+
+/// The catalog members one client is shown: the zones it may transfer.
+pub(crate) struct CatalogView {
+    /// For zones governed by the global ACL.
+    global: bool,
+    zones: HashMap<String, bool>,
+}
+
+impl CatalogView {
+    pub(crate) fn includes(&self, zone_name: &str) -> bool {
+        self.zones
+            .get(&zone_name.to_ascii_lowercase())
+            .copied()
+            .unwrap_or(self.global)
+    }
+}
+
 pub(crate) fn secondary_acl_from_config() -> SecondaryAcl {
     SecondaryAcl {
-        entries: parse_secondary_acl_entries(&config::get_bindizr_config().dns.secondary_addrs),
+        entries: secondary_acl_entries(&config::get_bindizr_config().dns.secondary_entries()),
+    }
+}
+
+/// Load every zone's own transfer ACL.
+pub(crate) async fn zone_acls() -> Result<ZoneAcls, XfrError> {
+    let entries = ZoneTransferAclService::list_by_zone_name()
+        .await
+        .map_err(|e| XfrError::DatabaseError(e.to_string()))?;
+
+    Ok(ZoneAcls {
+        zones: entries
+            .into_iter()
+            .map(|(zone_name, entries)| {
+                let acl = SecondaryAcl {
+                    entries: zone_acl_entries(&entries),
+                };
+                (zone_name.to_ascii_lowercase(), acl)
+            })
+            .collect(),
+    })
+}
+
+/// Decide how a client is admitted to transfer `zone_name`: by the zone's
+/// own ACL if it has one, otherwise by the global ACL.
+pub(crate) async fn admit_to_zone(
+    client_ip: IpAddr,
+    client_certificate: Option<&CertificateDer<'_>>,
+    global: &SecondaryAcl,
+    zone_acls: &ZoneAcls,
+    zone_name: &str,
+) -> TransferAdmission {
+    let acl = zone_acls
+        .zones
+        .get(&zone_name.to_ascii_lowercase())
+        .unwrap_or(global);
+    admit_client(client_ip, client_certificate, acl).await
+}
+
+/// Decide how a client is admitted to the catalog zone: by the global ACL
+/// or by any zone's own ACL, so a secondary entitled to a single zone can
+/// still discover it.
+pub(crate) async fn admit_to_catalog(
+    client_ip: IpAddr,
+    client_certificate: Option<&CertificateDer<'_>>,
+    global: &SecondaryAcl,
+    zone_acls: &ZoneAcls,
+) -> TransferAdmission {
+    let mut admission = admit_client(client_ip, client_certificate, global).await;
+    for acl in zone_acls.zones.values() {
+        admission = admission.union(admit_client(client_ip, client_certificate, acl).await);
     }
+    admission
 }
 
-pub(crate) async fn is_client_allowed(client_ip: IpAddr, acl: &SecondaryAcl) -> bool {
+/// The catalog members a client may see given the key its request was
+/// verified with, if any.
+pub(crate) async fn catalog_view(
+    client_ip: IpAddr,
+    client_certificate: Option<&CertificateDer<'_>>,
+    global: &SecondaryAcl,
+    zone_acls: &ZoneAcls,
+    key_name: Option<&str>,
+) -> CatalogView {
+    let mut zones = HashMap::with_capacity(zone_acls.zones.len());
+    for (zone_name, acl) in &zone_acls.zones {
+        let admission = admit_client(client_ip, client_certificate, acl).await;
+        zones.insert(zone_name.clone(), admission.permits(key_name));
+    }
+
+    CatalogView {
+        global: admit_client(client_ip, client_certificate, global)
+            .await
+            .permits(key_name),
+        zones,
+    }
+}
+
+/// Decide how a client is admitted by one ACL. An entry matches the client's
+/// address (or the prefix containing it), or a verified mutual-TLS client
+/// certificate issued for the entry's host or address. A matching deny entry
+/// refuses the client whatever else matches.
+pub(crate) async fn admit_client(
+    client_ip: IpAddr,
+    client_certificate: Option<&CertificateDer<'_>>,
+    acl: &SecondaryAcl,
+) -> TransferAdmission {
+    let mut address = false;
+    let mut keys = Vec::new();
+
     for entry in &acl.entries {
-        match entry {
-            SecondaryAclEntry::Ip(ip) if *ip == client_ip => return true,
-            SecondaryAclEntry::Ip(_) => {}
-            SecondaryAclEntry::HostPort(host_port) => match lookup_host(host_port).await {
-                Ok(addrs) => {
-                    if addrs.into_iter().any(|addr| addr.ip() == client_ip) {
-                        return true;
+        let certified = client_certificate
+            .is_some_and(|certificate| certificate_names(certificate, &entry.target));
+        let matches = certified
+            || match &entry.target {
+                SecondaryAclTarget::Ip(ip) => *ip == client_ip,
+                SecondaryAclTarget::Network(network) => network.contains(&client_ip),
+                SecondaryAclTarget::HostPort(host_port) => match lookup_host(host_port).await {
+                    Ok(addrs) => addrs.into_iter().any(|addr| addr.ip() == client_ip),
+                    Err(e) => {
+                        log_warn!("Failed to resolve DNS ACL host '{}': {}", host_port, e);
+                        false
                     }
-                }
-                Err(e) => {
-                    log_warn!("Failed to resolve DNS ACL host '{}': {}", host_port, e);
-                }
-            },
+                },
+            };
+        if !matches {
+            continue;
+        }
+        if entry.deny {
+            return TransferAdmission::Denied;
+        }
+
+        match &entry.key {
+            None => address = true,
+            Some(key) if !keys.contains(key) => keys.push(key.clone()),
+            Some(_) => {}
         }
     }
 
-    false
+    if address {
+        TransferAdmission::Address
+    } else if keys.is_empty() {
+        TransferAdmission::Denied
+    } else {
+        TransferAdmission::Keys(keys)
+    }
 }
 
-fn parse_secondary_acl_entries(raw: &str) -> Vec<SecondaryAclEntry> {
-    raw.split(',')
-        .filter_map(|item| {
-            let trimmed = item.trim();
-            if trimmed.is_empty() {
-                return None;
+/// Whether the certificate is valid for the entry: a DNS-name SAN for a
+/// hostname entry, an IP-address SAN for an address entry. A prefix names no
+/// single peer, so no certificate matches it.
+fn certificate_names(certificate: &CertificateDer<'_>, target: &SecondaryAclTarget) -> bool {
+    let name = match target {
+        SecondaryAclTarget::Ip(ip) => ServerName::IpAddress((*ip).into()),
+        SecondaryAclTarget::Network(_) => return false,
+        SecondaryAclTarget::HostPort(host_port) => {
+            let host = host_port
+                .rsplit_once(':')
+                .map_or(host_port.as_str(), |(host, _)| host);
+            match ServerName::try_from(host) {
+                Ok(name) => name,
+                Err(_) => return false,
             }
+        }
+    };
 
-            match parse_address_target(trimmed, 53) {
-                ParsedAddress::SocketAddr(addr) => Some(SecondaryAclEntry::Ip(addr.ip())),
-                ParsedAddress::HostPort(host_port) => Some(SecondaryAclEntry::HostPort(host_port)),
+    ParsedCertificate::try_from(certificate)
+        .and_then(|certificate| verify_server_name(&certificate, &name))
+        .is_ok()
+}
+
+fn secondary_acl_entries(entries: &[SecondaryEntry]) -> Vec<SecondaryAclEntry> {
+    entries
+        .iter()
+        .map(|entry| {
+            let target = match entry.address.parse::<IpNet>() {
+                Ok(network) => SecondaryAclTarget::Network(network),
+                Err(_) => match parse_address_target(&entry.address, 53) {
+                    ParsedAddress::SocketAddr(addr) => SecondaryAclTarget::Ip(addr.ip()),
+                    ParsedAddress::HostPort(host_port) => SecondaryAclTarget::HostPort(host_port),
+                },
+            };
+            SecondaryAclEntry {
+                target,
+                key: entry.key.clone(),
+                deny: entry.deny,
             }
         })
         .collect()
 }
 
+/// Stored entries were normalized on the way in; one that no longer parses
+/// is skipped rather than failing every transfer of the zone.
+fn zone_acl_entries(entries: &[ZoneTransferAcl]) -> Vec<SecondaryAclEntry> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let target = match IpNet::from_str(&entry.address) {
+                Ok(network) => SecondaryAclTarget::Network(network),
+                Err(_) => match IpAddr::from_str(&entry.address) {
+                    Ok(ip) => SecondaryAclTarget::Ip(ip),
+                    Err(_) => {
+                        log_warn!(
+                            "Skipping transfer ACL entry {} with invalid address '{}'",
+                            entry.id,
+                            entry.address
+                        );
+                        return None;
+                    }
+                },
+            };
+            Some(SecondaryAclEntry {
+                target,
+                key: entry.tsig_key_name.clone(),
+                deny: entry.action == TransferAclAction::Deny,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests;
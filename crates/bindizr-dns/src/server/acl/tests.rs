@@ -1,12 +1,58 @@
 use super::*;
 
+fn acl(raw: &str) -> SecondaryAcl {
+    SecondaryAcl {
+        entries: secondary_acl_entries(
+            &raw.split(',')
+                .map(|entry| SecondaryEntry::parse(entry).unwrap())
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+fn entry(target: SecondaryAclTarget, key: Option<&str>) -> SecondaryAclEntry {
+    SecondaryAclEntry {
+        target,
+        key: key.map(str::to_string),
+        deny: false,
+    }
+}
+
+fn zone_entry(action: TransferAclAction, address: &str, key: Option<&str>) -> ZoneTransferAcl {
+    ZoneTransferAcl {
+        id: 0,
+        zone_id: 1,
+        action,
+        address: address.to_string(),
+        tsig_key_name: key.map(str::to_string),
+        created_at: chrono::Utc::now(),
+    }
+}
+
+fn zone_acls(zones: &[(&str, Vec<ZoneTransferAcl>)]) -> ZoneAcls {
+    ZoneAcls {
+        zones: zones
+            .iter()
+            .map(|(zone_name, entries)| {
+                let acl = SecondaryAcl {
+                    entries: zone_acl_entries(entries),
+                };
+                (zone_name.to_string(), acl)
+            })
+            .collect(),
+    }
+}
+
 #[test]
 fn secondary_acl_keeps_hostnames_for_runtime_resolution() {
     assert_eq!(
-        parse_secondary_acl_entries("192.0.2.10:53, bind9-0.bind9-headless:53"),
+        acl("192.0.2.10:53, bind9-0.bind9-headless:53").entries,
         vec![
-            SecondaryAclEntry::Ip("192.0.2.10".parse().unwrap()),
-            SecondaryAclEntry::HostPort("bind9-0.bind9-headless:53".to_string()),
+            entry(SecondaryAclTarget::Ip("192.0.2.10".parse().unwrap()), None),
+            entry(
+                SecondaryAclTarget::HostPort("bind9-0.bind9-headless:53".to_string()),
+                None
+            ),
         ]
     );
 }
@@ -14,9 +60,160 @@ fn secondary_acl_keeps_hostnames_for_runtime_resolution() {
 #[test]
 fn secondary_acl_defaults_hostname_ports() {
     assert_eq!(
-        parse_secondary_acl_entries("bind9-0.bind9-headless"),
-        vec![SecondaryAclEntry::HostPort(
-            "bind9-0.bind9-headless:53".to_string()
+        acl("bind9-0.bind9-headless").entries,
+        vec![entry(
+            SecondaryAclTarget::HostPort("bind9-0.bind9-headless:53".to_string()),
+            None
+        )]
+    );
+}
+
+#[test]
+fn secondary_acl_carries_entry_keys() {
+    assert_eq!(
+        acl("192.0.2.10:53 key xfr-key").entries,
+        vec![entry(
+            SecondaryAclTarget::Ip("192.0.2.10".parse().unwrap()),
+            Some("xfr-key")
         )]
     );
 }
+
+#[tokio::test]
+async fn admit_client_requires_a_key_only_when_every_match_is_keyed() {
+    let acl = acl("192.0.2.10 key a, 192.0.2.10 key b, 192.0.2.20, 192.0.2.20 key c");
+
+    assert_eq!(
+        admit_client("192.0.2.10".parse().unwrap(), None, &acl).await,
+        TransferAdmission::Keys(vec!["a".to_string(), "b".to_string()])
+    );
+    assert_eq!(
+        admit_client("192.0.2.20".parse().unwrap(), None, &acl).await,
+        TransferAdmission::Address
+    );
+    assert_eq!(
+        admit_client("192.0.2.30".parse().unwrap(), None, &acl).await,
+        TransferAdmission::Denied
+    );
+}
+
+#[tokio::test]
+async fn admit_client_falls_back_to_the_address_for_an_unusable_certificate() {
+    let acl = acl("192.0.2.10");
+    let certificate = CertificateDer::from(b"not a certificate".to_vec());
+
+    assert_eq!(
+        admit_client("192.0.2.10".parse().unwrap(), Some(&certificate), &acl).await,
+        TransferAdmission::Address
+    );
+    assert_eq!(
+        admit_client("192.0.2.20".parse().unwrap(), Some(&certificate), &acl).await,
+        TransferAdmission::Denied
+    );
+}
+
+#[test]
+fn secondary_acl_reads_prefix_and_deny_entries() {
+    let mut denied = entry(SecondaryAclTarget::Ip("192.0.2.10".parse().unwrap()), None);
+    denied.deny = true;
+
+    assert_eq!(
+        acl("192.0.2.0/24, !192.0.2.10").entries,
+        vec![
+            entry(
+                SecondaryAclTarget::Network("192.0.2.0/24".parse().unwrap()),
+                None
+            ),
+            denied,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn admit_client_matches_prefixes_and_lets_deny_win() {
+    let acl = acl("192.0.2.0/24, 2001:db8::/32 key v6, !192.0.2.66");
+
+    assert_eq!(
+        admit_client("192.0.2.10".parse().unwrap(), None, &acl).await,
+        TransferAdmission::Address
+    );
+    assert_eq!(
+        admit_client("2001:db8::53".parse().unwrap(), None, &acl).await,
+        TransferAdmission::Keys(vec!["v6".to_string()])
+    );
+    // The deny entry comes last and still wins over the prefix.
+    assert_eq!(
+        admit_client("192.0.2.66".parse().unwrap(), None, &acl).await,
+        TransferAdmission::Denied
+    );
+    assert_eq!(
+        admit_client("198.51.100.1".parse().unwrap(), None, &acl).await,
+        TransferAdmission::Denied
+    );
+}
+
+#[tokio::test]
+async fn zone_acl_replaces_the_global_acl_for_its_zone() {
+    let global = acl("192.0.2.0/24");
+    let zones = zone_acls(&[(
+        "customer.example",
+        vec![
+            zone_entry(
+                TransferAclAction::Allow,
+                "198.51.100.7",
+                Some("customer-key"),
+            ),
+            zone_entry(TransferAclAction::Deny, "192.0.2.0/24", None),
+        ],
+    )]);
+    let shared: IpAddr = "192.0.2.10".parse().unwrap();
+    let customer: IpAddr = "198.51.100.7".parse().unwrap();
+
+    assert_eq!(
+        admit_to_zone(shared, None, &global, &zones, "other.example").await,
+        TransferAdmission::Address
+    );
+    assert_eq!(
+        admit_to_zone(shared, None, &global, &zones, "Customer.Example").await,
+        TransferAdmission::Denied
+    );
+    assert_eq!(
+        admit_to_zone(customer, None, &global, &zones, "customer.example").await,
+        TransferAdmission::Keys(vec!["customer-key".to_string()])
+    );
+    assert_eq!(
+        admit_to_zone(customer, None, &global, &zones, "other.example").await,
+        TransferAdmission::Denied
+    );
+}
+
+#[tokio::test]
+async fn catalog_admits_any_zone_secondary_and_lists_only_its_zones() {
+    let global = acl("192.0.2.0/24");
+    let zones = zone_acls(&[(
+        "customer.example",
+        vec![zone_entry(
+            TransferAclAction::Allow,
+            "198.51.100.7",
+            Some("customer-key"),
+        )],
+    )]);
+    let shared: IpAddr = "192.0.2.10".parse().unwrap();
+    let customer: IpAddr = "198.51.100.7".parse().unwrap();
+
+    assert_eq!(
+        admit_to_catalog(customer, None, &global, &zones).await,
+        TransferAdmission::Keys(vec!["customer-key".to_string()])
+    );
+
+    let view = catalog_view(customer, None, &global, &zones, Some("customer-key")).await;
+    assert!(view.includes("customer.example"));
+    assert!(!view.includes("other.example"));
+
+    let unsigned = catalog_view(customer, None, &global, &zones, None).await;
+    assert!(!unsigned.includes("customer.example"));
+
+    let view = catalog_view(shared, None, &global, &zones, None).await;
+    assert!(!view.includes("customer.example"));
+    assert!(view.includes("other.example"));
+}
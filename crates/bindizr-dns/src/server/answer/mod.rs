@@ -0,0 +1,349 @@
+//! Authoritative answers to ordinary queries (`dns.answer_queries`): the
+//! RFC 1034, Section 4.3.2 lookup over a zone's cached record set, with
+//! referrals at delegations, CNAME chasing, and wildcard synthesis (RFC 4592).
+
+use std::net::SocketAddr;
+
+use bindizr_core::dns::{
+    name::{OwnerName, ZoneName, decode_name_labels, join_labels},
+    serial,
+};
+use domain::base::iana::{Rcode, Rtype};
+use tokio::net::UdpSocket;
+
+use crate::{
+    config,
+    error::XfrError,
+    log_debug,
+    model::{
+        record::{Record, RecordType},
+        zone::Zone,
+    },
+    server::{zone_cache, zone_index},
+    wire::{self, DnsStream, Section},
+};
+
+/// A reply to a query without EDNS must fit the classic UDP payload
+/// (RFC 1035, Section 4.2.1); a longer one is sent truncated.
+const UDP_MAX_SIZE: usize = 512;
+
+/// CNAME links followed before the answer is returned as it stands, which
+/// also ends a chain that loops.
+const MAX_CNAME_CHAIN: usize = 8;
+
+pub(crate) async fn handle_tcp_query(
+    stream: &mut impl DnsStream,
+    client_addr: SocketAddr,
+    query: &wire::ParsedQuery,
+) -> Result<(), XfrError> {
+    let response = response_bytes(query, client_addr, usize::from(u16::MAX)).await?;
+    wire::write_tcp_message(stream, &response).await
+}
+
+pub(crate) async fn handle_udp_query(
+    socket: &UdpSocket,
+    client_addr: SocketAddr,
+    query: &wire::ParsedQuery,
+) -> Result<(), XfrError> {
+    let response = response_bytes(query, client_addr, udp_max_size(query)).await?;
+    socket.send_to(&response, client_addr).await?;
+    Ok(())
+}
+
+/// How large a UDP reply to `query` may be: the classic 512 bytes without
+/// EDNS, otherwise the requester's payload size capped at
+/// `dns.edns_udp_payload_size`. Sizes below 512 count as 512 (RFC 6891,
+/// Section 6.2.5).
+pub(crate) fn udp_max_size(query: &wire::ParsedQuery) -> usize {
+    match query.udp_payload_size {
+        Some(requested) => {
+            let cap = config::get_bindizr_config().dns.edns_udp_payload_size;
+            usize::from(requested.min(cap)).max(UDP_MAX_SIZE)
+        }
+        None => UDP_MAX_SIZE,
+    }
+}
+
+/// The payload size to advertise in a reply's OPT record: this server's own
+/// limit, for a query that carried EDNS (RFC 6891, Section 7).
+pub(crate) fn edns_payload_size(query: &wire::ParsedQuery) -> Option<u16> {
+    query
+        .udp_payload_size
+        .map(|_| config::get_bindizr_config().dns.edns_udp_payload_size)
+}
+
+/// A reply with no records and `rcode`, carrying OPT when the query did.
+pub(crate) fn error_response(query: &wire::ParsedQuery, rcode: Rcode) -> Vec<u8> {
+    let mut builder = wire::DnsMessageBuilder::new(query.query_id, &query.qname, query.qtype);
+    builder.set_rcode(rcode);
+    builder.set_authoritative(false);
+    builder.set_edns(edns_payload_size(query));
+    builder.build()
+}
+
+/// Answer `query` from zone data, truncating to a bare TC reply when the
+/// answer exceeds `max_len`. OPT is echoed when the query carried EDNS. A name in no hosted zone is REFUSED.
+pub(crate) async fn response_bytes(
+    query: &wire::ParsedQuery,
+    client_addr: SocketAddr,
+    max_len: usize,
+) -> Result<Vec<u8>, XfrError> {
+    log_debug!(
+        "Query for {:?} (qtype={:?}) from {}",
+        query.zone_name,
+        query.qtype,
+        client_addr
+    );
+
+    let refused = || error_response(query, Rcode::REFUSED);
+
+    let Ok((mut labels, _)) = decode_name_labels(&query.zone_name) else {
+        return Ok(refused());
+    };
+    let mut builder = wire::DnsMessageBuilder::new(query.query_id, &query.qname, query.qtype);
+    builder.set_edns(edns_payload_size(query));
+    // One index serves every link of the chain.
+    let zones = zone_index::current()
+        .await
+        .map_err(|e| XfrError::DatabaseError(e.to_string()))?;
+
+    for link in 0..=MAX_CNAME_CHAIN {
+        let Some(zone) = zones.enclosing(&labels) else {
+            // A chain that leaves the hosted zones ends with what it has; the
+            // resolver follows the last target itself.
+            if link == 0 {
+                return Ok(refused());
+            }
+            break;
+        };
+        let Ok(name) = OwnerName::parse_absolute_in_zone(&join_labels(&labels), &zone.name) else {
+            return Ok(refused());
+        };
+
+        let records = zone_cache::list_records(zone.id, zone.serial)
+            .await
+            .map_err(|e| XfrError::DatabaseError(e.to_string()))?;
+        let serial = serial::to_wire(zone.serial);
+
+        match lookup(&records, &zone.name, &name, query.qtype) {
+            Lookup::Answer {
+                owner,
+                records,
+                with_soa,
+            } => {
+                if with_soa {
+                    builder.add_soa(zone, serial)?;
+                }
+                for record in records {
+                    add_record(&mut builder, zone, &owner, record)?;
+                }
+            }
+            Lookup::Cname { owner, record } => {
+                add_record(&mut builder, zone, &owner, record)?;
+                match decode_name_labels(&record.value) {
+                    Ok((target, _)) => {
+                        labels = target;
+                        continue;
+                    }
+                    Err(_) => break,
+                }
+            }
+            Lookup::NoData => {
+                builder.set_section(Section::Authority);
+                builder.add_negative_soa(zone, serial)?;
+            }
+            Lookup::NxDomain => {
+                builder.set_rcode(Rcode::NXDOMAIN);
+                builder.set_section(Section::Authority);
+                builder.add_negative_soa(zone, serial)?;
+            }
+            Lookup::Referral { ns, glue } => {
+                // Only a CNAME that led here is this server's own data.
+                if builder.answer_count() == 0 {
+                    builder.set_authoritative(false);
+                }
+                builder.set_section(Section::Authority);
+                for record in ns {
+                    add_record(&mut builder, zone, &record.name, record)?;
+                }
+                builder.set_section(Section::Additional);
+                for record in glue {
+                    add_record(&mut builder, zone, &record.name, record)?;
+                }
+            }
+        }
+        break;
+    }
+
+    if builder.message_len() > max_len {
+        builder.truncate();
+    }
+    Ok(builder.build())
+}
+
+fn add_record(
+    builder: &mut wire::DnsMessageBuilder,
+    zone: &Zone,
+    owner: &OwnerName,
+    record: &Record,
+) -> Result<(), XfrError> {
+    builder.add_record_parts(
+        &zone.name,
+        owner,
+        &record.record_type.as_str(),
+        &record.value,
+        record.ttl,
+        record.priority,
+    )
+}
+
+/// What a zone's data says about one name and type.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Lookup<'a> {
+    /// The matching records, written under `owner`: the query name, which
+    /// differs from the records' own owner when a wildcard matched.
+    /// `with_soa` adds the apex SOA, which is held on the zone, not as a row.
+    Answer {
+        owner: OwnerName,
+        records: Vec<&'a Record>,
+        with_soa: bool,
+    },
+    /// A CNAME at the name for a query of another type; the lookup restarts
+    /// at its target.
+    Cname {
+        owner: OwnerName,
+        record: &'a Record,
+    },
+    /// The name exists but holds nothing of the type.
+    NoData,
+    NxDomain,
+    /// The name is at or under a delegation: the cut's NS records and the
+    /// in-zone addresses of their targets.
+    Referral {
+        ns: Vec<&'a Record>,
+        glue: Vec<&'a Record>,
+    },
+}
+
+/// Look `name` up in a zone's `records` (RFC 1034, Section 4.3.2, step 3).
+pub(crate) fn lookup<'a>(
+    records: &'a [Record],
+    zone: &ZoneName,
+    name: &OwnerName,
+    qtype: Rtype,
+) -> Lookup<'a> {
+    let labels = name.labels();
+
+    // A delegation anywhere between the apex and the name hands the query to
+    // the child. At the cut itself DS is the parent's data (RFC 4035,
+    // Section 3.1.4.1).
+    for depth in 1..=labels.len() {
+        if depth == labels.len() && qtype == Rtype::DS {
+            break;
+        }
+        let node = &labels[labels.len() - depth..];
+        let ns = records_at(records, node)
+            .filter(|record| record.record_type == RecordType::NS)
+            .collect::<Vec<_>>();
+        if !ns.is_empty() {
+            let glue = glue_for(records, zone, &ns);
+            return Lookup::Referral { ns, glue };
+        }
+    }
+
+    if node_exists(records, labels) {
+        return answer_at(records, labels, name, qtype);
+    }
+
+    // The closest encloser always exists, the apex at worst; a wildcard
+    // directly under it stands in for the missing name.
+    let encloser = (1..labels.len())
+        .map(|start| &labels[start..])
+        .find(|ancestor| node_exists(records, ancestor))
+        .unwrap_or(&[]);
+    let mut wildcard = vec!["*".to_string()];
+    wildcard.extend_from_slice(encloser);
+    if node_exists(records, &wildcard) {
+        return answer_at(records, &wildcard, name, qtype);
+    }
+
+    Lookup::NxDomain
+}
+
+/// The answer from an existing node at `node`, written under `owner`.
+fn answer_at<'a>(
+    records: &'a [Record],
+    node: &[String],
+    owner: &OwnerName,
+    qtype: Rtype,
+) -> Lookup<'a> {
+    let at_node = records_at(records, node).collect::<Vec<_>>();
+    let is_apex = node.is_empty();
+
+    let answer = |records: Vec<&'a Record>, with_soa: bool| Lookup::Answer {
+        owner: owner.clone(),
+        records,
+        with_soa,
+    };
+
+    if qtype == Rtype::ANY {
+        return answer(at_node, is_apex);
+    }
+    if qtype == Rtype::SOA && is_apex {
+        return answer(Vec::new(), true);
+    }
+
+    let matching = at_node
+        .iter()
+        .copied()
+        .filter(|record| record.record_type.type_code() == qtype.to_int())
+        .collect::<Vec<_>>();
+    if !matching.is_empty() {
+        return answer(matching, false);
+    }
+
+    if let Some(cname) = at_node
+        .iter()
+        .find(|record| record.record_type == RecordType::CNAME)
+    {
+        return Lookup::Cname {
+            owner: owner.clone(),
+            record: cname,
+        };
+    }
+
+    Lookup::NoData
+}
+
+fn records_at<'a>(records: &'a [Record], node: &[String]) -> impl Iterator<Item = &'a Record> {
+    records
+        .iter()
+        .filter(move |record| record.name.labels() == node)
+}
+
+/// Whether `node` holds records or has descendants that do; an empty
+/// non-terminal exists and answers NODATA (RFC 8020).
+fn node_exists(records: &[Record], node: &[String]) -> bool {
+    node.is_empty()
+        || records.iter().any(|record| {
+            let labels = record.name.labels();
+            labels.len() >= node.len() && labels[labels.len() - node.len()..] == *node
+        })
+}
+
+/// The zone's A and AAAA records for the NS targets inside it.
+fn glue_for<'a>(records: &'a [Record], zone: &ZoneName, ns: &[&Record]) -> Vec<&'a Record> {
+    let targets = ns
+        .iter()
+        .filter_map(|record| OwnerName::parse_absolute_in_zone(&record.value, zone).ok())
+        .collect::<Vec<_>>();
+
+    records
+        .iter()
+        .filter(|record| matches!(record.record_type, RecordType::A | RecordType::AAAA))
+        .filter(|record| targets.contains(&record.name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
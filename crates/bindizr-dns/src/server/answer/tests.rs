@@ -0,0 +1,164 @@
+use chrono::Utc;
+
+use super::*;
+
+fn zone() -> ZoneName {
+    ZoneName::parse("example.com").unwrap()
+}
+
+fn name(input: &str) -> OwnerName {
+    OwnerName::parse_in_zone(input, &zone()).unwrap()
+}
+
+fn record(owner: &str, record_type: RecordType, value: &str) -> Record {
+    Record {
+        id: 0,
+        name: name(owner),
+        record_type,
+        value: value.to_string(),
+        ttl: 300,
+        priority: None,
+        created_at: Utc::now(),
+        zone_id: 1,
+    }
+}
+
+fn zone_records() -> Vec<Record> {
+    vec![
+        record("@", RecordType::NS, "ns1.example.com."),
+        record("ns1", RecordType::A, "192.0.2.53"),
+        record("www", RecordType::A, "192.0.2.1"),
+        record("www", RecordType::AAAA, "2001:db8::1"),
+        record("alias", RecordType::CNAME, "www.example.com."),
+        record("a.b", RecordType::TXT, "deep"),
+        record("*.wild", RecordType::A, "192.0.2.9"),
+        record("sub", RecordType::NS, "ns.sub.example.com."),
+        record("ns.sub", RecordType::A, "192.0.2.77"),
+    ]
+}
+
+fn owner_and_values(lookup: Lookup<'_>) -> (OwnerName, Vec<String>) {
+    match lookup {
+        Lookup::Answer { owner, records, .. } => (
+            owner,
+            records.iter().map(|record| record.value.clone()).collect(),
+        ),
+        other => panic!("expected an answer, got {other:?}"),
+    }
+}
+
+#[test]
+fn lookup_answers_the_rrset_of_the_query_type() {
+    let records = zone_records();
+
+    let (owner, values) = owner_and_values(lookup(&records, &zone(), &name("www"), Rtype::AAAA));
+
+    assert_eq!(owner, name("www"));
+    assert_eq!(values, ["2001:db8::1"]);
+}
+
+#[test]
+fn lookup_tells_nodata_from_nxdomain() {
+    let records = zone_records();
+
+    assert_eq!(
+        lookup(&records, &zone(), &name("www"), Rtype::MX),
+        Lookup::NoData
+    );
+    // `b` holds nothing itself but has a child: an empty non-terminal.
+    assert_eq!(
+        lookup(&records, &zone(), &name("b"), Rtype::A),
+        Lookup::NoData
+    );
+    assert_eq!(
+        lookup(&records, &zone(), &name("missing"), Rtype::A),
+        Lookup::NxDomain
+    );
+}
+
+#[test]
+fn lookup_returns_the_cname_for_other_types_and_itself_for_cname() {
+    let records = zone_records();
+
+    match lookup(&records, &zone(), &name("alias"), Rtype::A) {
+        Lookup::Cname { owner, record } => {
+            assert_eq!(owner, name("alias"));
+            assert_eq!(record.value, "www.example.com.");
+        }
+        other => panic!("expected a CNAME, got {other:?}"),
+    }
+
+    let (_, values) = owner_and_values(lookup(&records, &zone(), &name("alias"), Rtype::CNAME));
+    assert_eq!(values, ["www.example.com."]);
+}
+
+#[test]
+fn lookup_refers_names_at_and_under_a_delegation_with_glue() {
+    let records = zone_records();
+
+    for owner in ["sub", "host.sub", "ns.sub"] {
+        match lookup(&records, &zone(), &name(owner), Rtype::A) {
+            Lookup::Referral { ns, glue } => {
+                assert_eq!(ns.len(), 1, "{owner}");
+                assert_eq!(ns[0].value, "ns.sub.example.com.");
+                assert_eq!(glue.len(), 1, "{owner}");
+                assert_eq!(glue[0].value, "192.0.2.77");
+            }
+            other => panic!("expected a referral for {owner}, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn lookup_answers_ds_at_the_cut_from_the_parent_side() {
+    let records = zone_records();
+
+    assert_eq!(
+        lookup(&records, &zone(), &name("sub"), Rtype::DS),
+        Lookup::NoData
+    );
+}
+
+#[test]
+fn lookup_does_not_treat_apex_ns_as_a_delegation() {
+    let records = zone_records();
+
+    let (owner, values) =
+        owner_and_values(lookup(&records, &zone(), &OwnerName::apex(), Rtype::NS));
+
+    assert!(owner.is_apex());
+    assert_eq!(values, ["ns1.example.com."]);
+}
+
+#[test]
+fn lookup_answers_apex_soa_from_the_zone() {
+    let records = zone_records();
+
+    assert_eq!(
+        lookup(&records, &zone(), &OwnerName::apex(), Rtype::SOA),
+        Lookup::Answer {
+            owner: OwnerName::apex(),
+            records: Vec::new(),
+            with_soa: true,
+        }
+    );
+}
+
+#[test]
+fn lookup_synthesizes_wildcard_answers_under_the_query_name() {
+    let records = zone_records();
+
+    let (owner, values) = owner_and_values(lookup(&records, &zone(), &name("x.y.wild"), Rtype::A));
+    assert_eq!(owner, name("x.y.wild"));
+    assert_eq!(values, ["192.0.2.9"]);
+
+    assert_eq!(
+        lookup(&records, &zone(), &name("host.wild"), Rtype::TXT),
+        Lookup::NoData
+    );
+    // A wildcard only covers names under its own parent.
+    assert_eq!(
+        lookup(&records, &zone(), &name("other"), Rtype::A),
+        Lookup::NxDomain
+    );
+}
@@ -1,18 +1,25 @@
 use std::net::IpAddr;
 
+use bindizr_core::dns::serial;
 use domain::base::iana::Rtype;
-use tokio::net::TcpStream;
 
-use super::{catalog, delta, zone_cache};
-use crate::{error::XfrError, log_info, service::zone::ZoneService, wire};
+use super::zone_cache;
+use crate::{
+    error::XfrError,
+    log_info,
+    service::zone::ZoneService,
+    tsig::TransferSigner,
+    wire::{self, DnsStream},
+};
 
 /// Handles an AXFR payload under `response_qtype`: the IXFR fallback keeps
 /// QTYPE=IXFR to match the original query.
 pub(crate) async fn handle_axfr(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     query: &wire::ParsedQuery,
     client_ip: IpAddr,
     response_qtype: Rtype,
+    signer: Option<TransferSigner>,
 ) -> Result<(), XfrError> {
     let zone_name_str = query.zone_name.as_str();
 
@@ -22,10 +29,6 @@ pub(crate) async fn handle_axfr(
         client_ip
     );
 
-    if catalog::is_catalog_zone(zone_name_str) {
-        return catalog::handle_catalog_axfr_with_qtype(stream, query, response_qtype).await;
-    }
-
     let zone = ZoneService::find_by_name(zone_name_str)
         .await
         .map_err(|e| XfrError::DatabaseError(e.to_string()))?
@@ -39,13 +42,14 @@ pub(crate) async fn handle_axfr(
         "AXFR: zone {} has {} records, serial={}",
         zone_name_str,
         records.len(),
-        zone.serial
+        serial::to_wire(zone.serial)
     );
 
     let mut builder = wire::DnsMessageBuilder::new(query.query_id, &query.qname, response_qtype);
+    builder.set_signer(signer);
     let mut messages_sent = 0usize;
 
-    let serial = delta::serial_to_u32(zone.serial)?;
+    let serial = serial::to_wire(zone.serial);
     wire::add_answer_and_flush_if_needed(stream, &mut builder, &mut messages_sent, |builder| {
         builder.add_soa(&zone, serial)
     })
@@ -1,43 +1,79 @@
 use std::collections::HashMap;
 
-use bindizr_core::dns::name::ZoneName;
 pub(crate) use bindizr_core::dns::{CATALOG_ZONE_NAME, is_catalog_zone};
+use bindizr_core::dns::{name::ZoneName, serial};
 use chrono::Utc;
 use domain::base::iana::Rtype;
 use sha2::{Digest, Sha256};
-use tokio::net::TcpStream;
 
-use super::delta;
-use crate::{error::XfrError, log_info, model::zone::Zone, service::zone::ZoneService, wire};
+use super::acl::CatalogView;
+use crate::{
+    error::XfrError,
+    log_info,
+    model::{
+        zone::{SerialPolicy, Zone},
+        zone_transfer_acl::ZoneTransferAcl,
+    },
+    service::{
+        catalog::{CatalogMember, CatalogService},
+        zone::{ZoneService, transfer_acl::ZoneTransferAclService},
+    },
+    tsig::TransferSigner,
+    wire::{self, DnsStream},
+};
+
+/// The canonical name of the catalog zone `zone_name` names, if it is
+/// `catalog.bind` or a named catalog.
+pub(crate) async fn resolve_catalog(zone_name: &str) -> Result<Option<String>, XfrError> {
+    CatalogService::resolve(zone_name)
+        .await
+        .map_err(|e| XfrError::DatabaseError(e.to_string()))
+}
+
+/// Every catalog zone served, `catalog.bind` first.
+pub(crate) async fn catalog_names() -> Result<Vec<String>, XfrError> {
+    CatalogService::catalog_names()
+        .await
+        .map_err(|e| XfrError::DatabaseError(e.to_string()))
+}
 
-/// Generates the catalog zone and its member zone list.
-pub(crate) async fn generate_catalog_zone() -> Result<(Zone, Vec<String>), XfrError> {
-    log_info!("Generating catalog zone: {}", CATALOG_ZONE_NAME);
+/// Generates catalog zone `catalog_name` and its members.
+pub(crate) async fn generate_catalog_zone(
+    catalog_name: &str,
+) -> Result<(Zone, Vec<CatalogMember>), XfrError> {
+    log_info!("Generating catalog zone: {}", catalog_name);
 
     let all_zones = ZoneService::list()
         .await
         .map_err(|e| XfrError::DatabaseError(e.to_string()))?;
+    let member_zones = CatalogService::members(catalog_name)
+        .await
+        .map_err(|e| XfrError::DatabaseError(e.to_string()))?;
 
-    // The catalog zone is not a member of itself.
-    let member_zones: Vec<String> = all_zones
-        .iter()
-        .map(|z| z.name.clone())
-        .filter(|name| name.as_str() != CATALOG_ZONE_NAME)
-        .map(|name| name.to_string())
-        .collect();
+    log_info!(
+        "Catalog zone {} contains {} member zones",
+        catalog_name,
+        member_zones.len()
+    );
 
-    log_info!("Catalog zone contains {} member zones", member_zones.len());
+    // What each secondary is shown depends on the transfer ACLs, so they
+    // are part of what the serial tracks.
+    let zone_acls = ZoneTransferAclService::list_by_zone_name()
+        .await
+        .map_err(|e| XfrError::DatabaseError(e.to_string()))?;
 
     // The catalog zone is virtual (no DB row); build its metadata in memory.
-    let serial = generate_catalog_serial(&member_zones, &all_zones).await?;
+    let serial =
+        generate_catalog_serial(catalog_name, &member_zones, &all_zones, &zone_acls).await?;
 
     let catalog_zone = Zone {
         id: 0,
-        name: ZoneName::from_row(CATALOG_ZONE_NAME),
+        name: ZoneName::from_row(catalog_name),
         primary_ns: "invalid".to_string(),
         admin_email: "invalid".to_string(),
         ttl: 3600,
         serial,
+        serial_policy: SerialPolicy::Increment,
         refresh: 3600,
         retry: 600,
         expire: 86400,
@@ -48,35 +84,73 @@ pub(crate) async fn generate_catalog_zone() -> Result<(Zone, Vec<String>), XfrEr
     Ok((catalog_zone, member_zones))
 }
 
-async fn generate_catalog_serial(member_zones: &[String], zones: &[Zone]) -> Result<i32, XfrError> {
-    let signature = catalog_signature(member_zones, zones);
+async fn generate_catalog_serial(
+    catalog_name: &str,
+    member_zones: &[CatalogMember],
+    zones: &[Zone],
+    zone_acls: &HashMap<String, Vec<ZoneTransferAcl>>,
+) -> Result<i32, XfrError> {
+    let signature = catalog_signature(member_zones, zones, zone_acls);
     let base_serial = zones.iter().map(|z| z.serial).max().unwrap_or(1);
-    ZoneService::update_catalog_serial_for_signature(CATALOG_ZONE_NAME, &signature, base_serial)
+    ZoneService::update_catalog_serial_for_signature(catalog_name, &signature, base_serial)
         .await
         .map_err(|e| XfrError::DatabaseError(e.to_string()))
 }
 
-fn catalog_signature(member_zones: &[String], zones: &[Zone]) -> String {
+fn catalog_signature(
+    member_zones: &[CatalogMember],
+    zones: &[Zone],
+    zone_acls: &HashMap<String, Vec<ZoneTransferAcl>>,
+) -> String {
     // Index serials by lowercased name so the per-member lookup is O(1).
     let serial_by_name: HashMap<String, i32> = zones
         .iter()
         .map(|z| (z.name.to_string(), z.serial))
         .collect();
 
-    let mut members = member_zones
-        .iter()
-        .map(|member| member.to_ascii_lowercase())
-        .collect::<Vec<_>>();
-    members.sort();
+    let mut members = member_zones.iter().collect::<Vec<_>>();
+    members.sort_by(|a, b| a.zone_name.cmp(&b.zone_name));
 
     let mut hasher = Sha256::new();
     for member in members {
-        if let Some(serial) = serial_by_name.get(&member) {
-            hasher.update(member.as_bytes());
+        let name = member.zone_name.to_ascii_lowercase();
+        if let Some(serial) = serial_by_name.get(&name) {
+            hasher.update(name.as_bytes());
             hasher.update(b"\0");
             hasher.update(serial.to_string().as_bytes());
             hasher.update(b"\n");
         }
+        // Member properties are catalog content too.
+        let properties = member
+            .group
+            .iter()
+            .map(|group| ("group", "", group))
+            .chain(member.coo.iter().map(|coo| ("coo", "", coo)))
+            .chain(
+                member
+                    .ext
+                    .iter()
+                    .map(|(name, value)| ("ext", name.as_str(), value)),
+            );
+        for (kind, name, value) in properties {
+            hasher.update(kind.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(name.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(value.as_bytes());
+            hasher.update(b"\n");
+        }
+        for entry in zone_acls.get(&name).into_iter().flatten() {
+            hasher.update(b"acl\0");
+            hasher.update(entry.id.to_string().as_bytes());
+            hasher.update(b"\0");
+            hasher.update(entry.action.as_str().as_bytes());
+            hasher.update(b"\0");
+            hasher.update(entry.address.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(entry.tsig_key_name.as_deref().unwrap_or("").as_bytes());
+            hasher.update(b"\n");
+        }
     }
 
     hasher
@@ -87,17 +161,25 @@ fn catalog_signature(member_zones: &[String], zones: &[Zone]) -> String {
 }
 
 pub(crate) async fn handle_catalog_axfr_with_qtype(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     query: &wire::ParsedQuery,
     response_qtype: Rtype,
+    signer: Option<TransferSigner>,
+    catalog_name: &str,
+    view: &CatalogView,
 ) -> Result<(), XfrError> {
-    log_info!("AXFR request for catalog zone: {}", CATALOG_ZONE_NAME);
+    log_info!("AXFR request for catalog zone: {}", catalog_name);
 
-    let (catalog_zone, member_zones) = generate_catalog_zone().await?;
+    let (catalog_zone, member_zones) = generate_catalog_zone(catalog_name).await?;
+    let member_zones: Vec<CatalogMember> = member_zones
+        .into_iter()
+        .filter(|member| view.includes(&member.zone_name))
+        .collect();
 
     let mut builder = wire::DnsMessageBuilder::new(query.query_id, &query.qname, response_qtype);
+    builder.set_signer(signer);
     let mut messages_sent = 0usize;
-    let serial = delta::serial_to_u32(catalog_zone.serial)?;
+    let serial = serial::to_wire(catalog_zone.serial);
 
     wire::add_answer_and_flush_if_needed(stream, &mut builder, &mut messages_sent, |builder| {
         builder.add_catalog_soa(&catalog_zone, serial)
@@ -113,11 +195,40 @@ pub(crate) async fn handle_catalog_axfr_with_qtype(
     })
     .await?;
 
-    for member_zone in &member_zones {
+    for member in &member_zones {
         wire::add_answer_and_flush_if_needed(stream, &mut builder, &mut messages_sent, |builder| {
-            builder.add_catalog_ptr(&catalog_zone, member_zone)
+            builder.add_catalog_ptr(&catalog_zone, &member.zone_name)
         })
         .await?;
+        if let Some(group) = &member.group {
+            wire::add_answer_and_flush_if_needed(
+                stream,
+                &mut builder,
+                &mut messages_sent,
+                |builder| builder.add_catalog_group(&catalog_zone, &member.zone_name, group),
+            )
+            .await?;
+        }
+        if let Some(coo) = &member.coo {
+            wire::add_answer_and_flush_if_needed(
+                stream,
+                &mut builder,
+                &mut messages_sent,
+                |builder| builder.add_catalog_coo(&catalog_zone, &member.zone_name, coo),
+            )
+            .await?;
+        }
+        for (property, value) in &member.ext {
+            wire::add_answer_and_flush_if_needed(
+                stream,
+                &mut builder,
+                &mut messages_sent,
+                |builder| {
+                    builder.add_catalog_ext(&catalog_zone, &member.zone_name, property, value)
+                },
+            )
+            .await?;
+        }
     }
 
     wire::add_answer_and_flush_if_needed(stream, &mut builder, &mut messages_sent, |builder| {
@@ -1,7 +1,18 @@
+use std::collections::BTreeMap;
+
 use bindizr_core::dns::name::ZoneName;
 
 use super::*;
 
+fn member(zone_name: &str) -> CatalogMember {
+    CatalogMember {
+        zone_name: zone_name.to_string(),
+        group: None,
+        coo: None,
+        ext: BTreeMap::new(),
+    }
+}
+
 #[test]
 fn zone_name_to_member_id_is_stable_and_dns_safe() {
     assert_eq!(zone_name_to_member_id("example.com"), "example-com");
@@ -19,6 +30,7 @@ fn catalog_signature_changes_when_members_change() {
             admin_email: "admin.example.com".to_string(),
             ttl: 3600,
             serial: 100,
+            serial_policy: SerialPolicy::Increment,
             refresh: 3600,
             retry: 3600,
             expire: 604800,
@@ -32,6 +44,7 @@ fn catalog_signature_changes_when_members_change() {
             admin_email: "admin.test.com".to_string(),
             ttl: 3600,
             serial: 200,
+            serial_policy: SerialPolicy::Increment,
             refresh: 3600,
             retry: 3600,
             expire: 604800,
@@ -42,10 +55,82 @@ fn catalog_signature_changes_when_members_change() {
 
     let member_zones = zones
         .iter()
-        .map(|zone| zone.name.to_string())
+        .map(|zone| member(zone.name.as_str()))
         .collect::<Vec<_>>();
-    let original = catalog_signature(&member_zones, &zones);
-    let updated_members = vec!["example.com".to_string()];
+    let original = catalog_signature(&member_zones, &zones, &HashMap::new());
+    let updated_members = vec![member("example.com")];
+
+    assert_ne!(
+        original,
+        catalog_signature(&updated_members, &zones, &HashMap::new())
+    );
+}
+
+#[test]
+fn catalog_signature_changes_when_a_member_acl_changes() {
+    let zones = vec![Zone {
+        id: 1,
+        name: ZoneName::from_row("example.com"),
+        primary_ns: "ns1.example.com".to_string(),
+        admin_email: "admin.example.com".to_string(),
+        ttl: 3600,
+        serial: 100,
+        serial_policy: SerialPolicy::Increment,
+        refresh: 3600,
+        retry: 3600,
+        expire: 604800,
+        minimum_ttl: 3600,
+        created_at: Utc::now(),
+    }];
+    let member_zones = vec![member("example.com")];
+    let entry = ZoneTransferAcl {
+        id: 1,
+        zone_id: 1,
+        action: crate::model::zone_transfer_acl::TransferAclAction::Allow,
+        address: "192.0.2.0/24".to_string(),
+        tsig_key_name: None,
+        created_at: Utc::now(),
+    };
+
+    let unrestricted = catalog_signature(&member_zones, &zones, &HashMap::new());
+    let restricted = catalog_signature(
+        &member_zones,
+        &zones,
+        &HashMap::from([("example.com".to_string(), vec![entry])]),
+    );
+
+    assert_ne!(unrestricted, restricted);
+}
+
+#[test]
+fn catalog_signature_changes_when_a_member_property_changes() {
+    let zones = vec![Zone {
+        id: 1,
+        name: ZoneName::from_row("example.com"),
+        primary_ns: "ns1.example.com".to_string(),
+        admin_email: "admin.example.com".to_string(),
+        ttl: 3600,
+        serial: 100,
+        serial_policy: SerialPolicy::Increment,
+        refresh: 3600,
+        retry: 3600,
+        expire: 604800,
+        minimum_ttl: 3600,
+        created_at: Utc::now(),
+    }];
+    let plain = vec![member("example.com")];
+    let grouped = vec![CatalogMember {
+        group: Some("signed".to_string()),
+        ..member("example.com")
+    }];
+    let extended = vec![CatalogMember {
+        ext: BTreeMap::from([("tier".to_string(), "gold".to_string())]),
+        ..member("example.com")
+    }];
 
-    assert_ne!(original, catalog_signature(&updated_members, &zones));
+    let signatures = [&plain, &grouped, &extended]
+        .map(|members| catalog_signature(members, &zones, &HashMap::new()));
+    assert_ne!(signatures[0], signatures[1]);
+    assert_ne!(signatures[0], signatures[2]);
+    assert_ne!(signatures[1], signatures[2]);
 }
@@ -1,17 +1,23 @@
 use std::{collections::HashMap, net::IpAddr};
 
-use bindizr_core::dns::name::ZoneName;
+use bindizr_core::dns::{name::ZoneName, serial};
 use domain::base::iana::Rtype;
-use tokio::net::TcpStream;
 
-use super::{axfr, catalog, delta};
-use crate::{error::XfrError, log_info, log_warn, service::zone::ZoneService, wire};
+use super::{axfr, delta};
+use crate::{
+    error::XfrError,
+    log_info, log_warn,
+    service::zone::ZoneService,
+    tsig::TransferSigner,
+    wire::{self, DnsStream},
+};
 
 /// Handles an IXFR request.
 pub(crate) async fn handle_ixfr(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     query: &wire::ParsedQuery,
     client_ip: IpAddr,
+    signer: Option<TransferSigner>,
 ) -> Result<(), XfrError> {
     let zone_name_str = query.zone_name.as_str();
 
@@ -22,23 +28,18 @@ pub(crate) async fn handle_ixfr(
         query.client_serial
     );
 
-    if catalog::is_catalog_zone(zone_name_str) {
-        log_info!("IXFR: Catalog zone requested, falling back to AXFR");
-        return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
-    }
-
     let zone = ZoneService::find_by_name(zone_name_str)
         .await
         .map_err(|e| XfrError::DatabaseError(e.to_string()))?
         .ok_or_else(|| XfrError::ZoneNotFound(zone_name_str.to_string()))?;
 
-    let current_serial = delta::serial_to_u32(zone.serial)?;
+    let current_serial = serial::to_wire(zone.serial);
 
     let client_serial = match query.client_serial {
         Some(s) => s,
         None => {
             log_warn!("IXFR: No client serial provided, falling back to AXFR");
-            return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
+            return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
         }
     };
 
@@ -48,19 +49,19 @@ pub(crate) async fn handle_ixfr(
             Some(snapshot) => snapshot,
             None => {
                 log_warn!("IXFR: Missing SOA snapshot, falling back to AXFR");
-                return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
+                return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
             }
         };
-        return send_up_to_date_response(stream, query, &current_soa).await;
+        return send_up_to_date_response(stream, query, &current_soa, signer).await;
     }
 
-    if client_serial > current_serial {
+    if !serial::is_newer(current_serial, client_serial) {
         log_warn!(
-            "IXFR: Client serial {} > current serial {}, falling back to AXFR",
+            "IXFR: Client serial {} is not older than current serial {}, falling back to AXFR",
             client_serial,
             current_serial
         );
-        return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
+        return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
     }
 
     let changes = delta::list_zone_changes(zone.id, client_serial, current_serial).await?;
@@ -71,27 +72,26 @@ pub(crate) async fn handle_ixfr(
             client_serial,
             current_serial
         );
-        return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
+        return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
     }
 
-    let mut serials_in_changes: Vec<u32> = changes
-        .iter()
-        .map(|c| delta::serial_to_u32(c.serial))
-        .collect::<Result<_, _>>()?;
-    serials_in_changes.sort_unstable();
+    // Changes arrive in RFC 1982 order from the client's serial, so a numeric
+    // sort would misplace serials past a wrap.
+    let mut serials_in_changes: Vec<u32> =
+        changes.iter().map(|c| serial::to_wire(c.serial)).collect();
     serials_in_changes.dedup();
 
     let mut previous_serial = client_serial;
-    for &serial in &serials_in_changes {
-        if serial <= previous_serial {
+    for &next_serial in &serials_in_changes {
+        if !serial::is_newer(next_serial, previous_serial) {
             log_warn!(
                 "IXFR: Non-monotonic serial chain (previous {}, got {}), falling back to AXFR",
                 previous_serial,
-                serial
+                next_serial
             );
-            return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
+            return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
         }
-        previous_serial = serial;
+        previous_serial = next_serial;
     }
 
     if let Some(&last_serial) = serials_in_changes.last()
@@ -102,7 +102,7 @@ pub(crate) async fn handle_ixfr(
             last_serial,
             current_serial
         );
-        return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
+        return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
     }
 
     let mut snapshots_by_serial: HashMap<u32, delta::ZoneSnapshot> = HashMap::new();
@@ -111,9 +111,18 @@ pub(crate) async fn handle_ixfr(
     // Fetch the whole serial span in one query; missing snapshots are caught
     // by the chain validation below.
     for snapshot in delta::list_zone_snapshots(zone.id, client_serial, current_serial).await? {
-        if let Ok(serial) = delta::serial_to_u32(snapshot.serial) {
-            snapshots_by_serial.insert(serial, snapshot);
-        }
+        snapshots_by_serial.insert(serial::to_wire(snapshot.serial), snapshot);
+    }
+
+    // History pruning drops a serial's snapshot before the journal rows that
+    // lead away from it, so a missing base snapshot means the client is
+    // further behind than the retained window.
+    if !snapshots_by_serial.contains_key(&client_serial) {
+        log_info!(
+            "IXFR: Serial {} is older than the retained history, falling back to AXFR",
+            client_serial
+        );
+        return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
     }
 
     // Every delta step needs both its old and new SOA snapshots.
@@ -128,7 +137,7 @@ pub(crate) async fn handle_ixfr(
             || !snapshots_by_serial.contains_key(&serial)
         {
             log_warn!("IXFR: Missing SOA snapshot, falling back to AXFR");
-            return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
+            return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
         }
     }
 
@@ -140,6 +149,8 @@ pub(crate) async fn handle_ixfr(
         current_serial
     );
 
+    // The stream signs from a copy: after a failure that sent nothing, the
+    // AXFR fallback still signs as the first message of the exchange.
     match send_ixfr_response(
         stream,
         query,
@@ -147,6 +158,7 @@ pub(crate) async fn handle_ixfr(
         client_serial,
         &changes,
         &snapshots_by_serial,
+        signer.clone(),
     )
     .await
     {
@@ -157,7 +169,7 @@ pub(crate) async fn handle_ixfr(
                 "IXFR: Failed to build incremental response ({}), falling back to AXFR",
                 err
             );
-            return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR).await;
+            return axfr::handle_axfr(stream, query, client_ip, Rtype::IXFR, signer).await;
         }
         // Bytes already sent; a fallback AXFR would corrupt the partial IXFR.
         Err(IxfrSendError::Partial(err)) => {
@@ -176,11 +188,13 @@ pub(crate) async fn handle_ixfr(
 
 /// Sends a single-SOA response when the client is already up-to-date.
 async fn send_up_to_date_response(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     query: &wire::ParsedQuery,
     current_soa: &delta::ZoneSnapshot,
+    signer: Option<TransferSigner>,
 ) -> Result<(), XfrError> {
     let mut builder = wire::DnsMessageBuilder::new(query.query_id, &query.qname, Rtype::IXFR);
+    builder.set_signer(signer);
 
     builder.add_soa_from_snapshot(current_soa)?;
     wire::flush_message_if_not_empty(stream, &mut builder).await?;
@@ -199,14 +213,16 @@ enum IxfrSendError {
 /// Sends an IXFR response with incremental changes, reporting whether a failure
 /// left the stream dirty so the caller can decide about AXFR fallback.
 async fn send_ixfr_response(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     query: &wire::ParsedQuery,
     zone: &crate::model::zone::Zone,
     client_serial: u32,
     changes: &[delta::ZoneChange],
     snapshots_by_serial: &HashMap<u32, delta::ZoneSnapshot>,
+    signer: Option<TransferSigner>,
 ) -> Result<(), IxfrSendError> {
     let mut builder = wire::DnsMessageBuilder::new(query.query_id, &query.qname, Rtype::IXFR);
+    builder.set_signer(signer);
     let mut messages_sent = 0usize;
 
     let result = stream_ixfr_body(
@@ -235,7 +251,7 @@ async fn send_ixfr_response(
 /// 64 KiB wire limit. `messages_sent` distinguishes a pre-write failure from a
 /// mid-stream one.
 async fn stream_ixfr_body(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     builder: &mut wire::DnsMessageBuilder,
     messages_sent: &mut usize,
     zone: &crate::model::zone::Zone,
@@ -244,7 +260,7 @@ async fn stream_ixfr_body(
     snapshots_by_serial: &HashMap<u32, delta::ZoneSnapshot>,
 ) -> Result<(), XfrError> {
     let current_snapshot = snapshots_by_serial
-        .get(&delta::serial_to_u32(zone.serial)?)
+        .get(&serial::to_wire(zone.serial))
         .ok_or_else(|| {
             XfrError::ProtocolError("Missing current serial SOA snapshot for IXFR".to_string())
         })?;
@@ -255,15 +271,20 @@ async fn stream_ixfr_body(
     })
     .await?;
 
+    // Group by serial, keeping the RFC 1982 order the changes arrived in.
     let mut changes_by_serial: HashMap<u32, Vec<&delta::ZoneChange>> = HashMap::new();
+    let mut serials: Vec<u32> = Vec::new();
     for change in changes {
-        let serial = delta::serial_to_u32(change.serial)?;
-        changes_by_serial.entry(serial).or_default().push(change);
+        let change_serial = serial::to_wire(change.serial);
+        if serials.last() != Some(&change_serial) {
+            serials.push(change_serial);
+        }
+        changes_by_serial
+            .entry(change_serial)
+            .or_default()
+            .push(change);
     }
 
-    let mut serials: Vec<u32> = changes_by_serial.keys().copied().collect();
-    serials.sort();
-
     for (idx, &serial) in serials.iter().enumerate() {
         let serial_changes = &changes_by_serial[&serial];
 
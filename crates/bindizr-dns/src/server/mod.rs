@@ -1,35 +1,63 @@
 //! Inbound DNS serving: AXFR/IXFR dispatch with ACL gating, SOA responses,
-//! catalog-zone generation, and RFC 2136 nsupdate handling.
+//! optional authoritative query answers, catalog-zone generation, and RFC 2136
+//! nsupdate handling.
 
 pub(crate) mod acl;
+pub(crate) mod answer;
 pub(crate) mod axfr;
 pub(crate) mod catalog;
 pub(crate) mod delta;
 pub(crate) mod ixfr;
 pub(crate) mod nsupdate;
 pub(crate) mod soa;
+pub(crate) mod tls;
 pub(crate) mod zone_cache;
+pub(crate) mod zone_index;
 
 use std::net::{IpAddr, SocketAddr};
 
+use acl::TransferAdmission;
 use catalog::generate_catalog_zone;
-use domain::base::iana::{Rcode, Rtype};
-use tokio::net::TcpStream;
+use domain::{
+    base::{
+        Message,
+        iana::{Rcode, Rtype},
+    },
+    rdata::tsig::Time48,
+};
+use tokio::io::AsyncWriteExt;
+use tokio_rustls::rustls::pki_types::CertificateDer;
 
-use crate::{error::XfrError, log_info, log_warn, metrics::metrics, wire};
+use crate::{
+    error::XfrError,
+    log_info, log_warn,
+    metrics::metrics,
+    service::tsig_key::TsigKeyService,
+    tsig::{self, SingleKeyStore, TransferSigner},
+    wire::{self, DnsStream},
+};
 
-/// Initializes XFR support by ensuring the catalog zone exists.
+/// Initializes XFR support by ensuring every catalog zone exists.
 pub(crate) async fn initialize() {
-    match generate_catalog_zone().await {
-        Ok((catalog, _)) => {
-            log_info!(
-                "Catalog zone '{}' is ready (serial: {})",
-                catalog::CATALOG_ZONE_NAME,
-                catalog.serial
-            );
-        }
+    let catalog_names = match catalog::catalog_names().await {
+        Ok(names) => names,
         Err(e) => {
-            log_warn!("Failed to generate catalog zone: {}", e);
+            log_warn!("Failed to list catalog zones: {}", e);
+            vec![catalog::CATALOG_ZONE_NAME.to_string()]
+        }
+    };
+    for catalog_name in catalog_names {
+        match generate_catalog_zone(&catalog_name).await {
+            Ok((catalog, _)) => {
+                log_info!(
+                    "Catalog zone '{}' is ready (serial: {})",
+                    catalog_name,
+                    catalog.serial
+                );
+            }
+            Err(e) => {
+                log_warn!("Failed to generate catalog zone {}: {}", catalog_name, e);
+            }
         }
     }
 }
@@ -39,10 +67,14 @@ pub(crate) fn is_xfr_query_type(qtype: Rtype) -> bool {
     matches!(qtype, Rtype::AXFR | Rtype::IXFR)
 }
 
+/// Serves a transfer query over TCP or TLS; `client_certificate` is the
+/// verified mutual-TLS client certificate, if the connection has one.
 pub(crate) async fn handle_tcp_query(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     client_addr: SocketAddr,
+    client_certificate: Option<&CertificateDer<'_>>,
     secondary_acl: &acl::SecondaryAcl,
+    query_data: &[u8],
     query: &wire::ParsedQuery,
 ) -> Result<(), XfrError> {
     let client_ip = client_addr.ip();
@@ -63,10 +95,49 @@ pub(crate) async fn handle_tcp_query(
         }
     };
 
-    if let Err(err) = validate_secondary_acl(client_ip, secondary_acl).await {
-        count_xfr("refused");
-        return Err(err);
-    }
+    let catalog_name = match catalog::resolve_catalog(&query.zone_name).await {
+        Ok(catalog_name) => catalog_name,
+        Err(err) => {
+            count_xfr("error");
+            return Err(err);
+        }
+    };
+    let zone_acls = match acl::zone_acls().await {
+        Ok(zone_acls) => zone_acls,
+        Err(err) => {
+            count_xfr("error");
+            return Err(err);
+        }
+    };
+    let admission = if catalog_name.is_some() {
+        acl::admit_to_catalog(client_ip, client_certificate, secondary_acl, &zone_acls).await
+    } else {
+        acl::admit_to_zone(
+            client_ip,
+            client_certificate,
+            secondary_acl,
+            &zone_acls,
+            &query.zone_name,
+        )
+        .await
+    };
+    let admission = match refuse_denied(client_ip, admission) {
+        Ok(admission) => admission,
+        Err(err) => {
+            count_xfr("refused");
+            return Err(err);
+        }
+    };
+    let signer = match authenticate_transfer(stream, query_data, query, &admission).await {
+        Ok(signer) => signer,
+        Err(err) => {
+            count_xfr("refused");
+            return Err(err);
+        }
+    };
+    // Nothing is signed before the handler finds the zone, so this copy
+    // still signs a NOTAUTH reply as the first message of the exchange.
+    let notauth_signer = signer.clone();
 
     log_info!(
         "XFR TCP query: zone={:?}, qtype={:?}, from={}",
@@ -75,9 +146,36 @@ pub(crate) async fn handle_tcp_query(
         client_ip
     );
 
-    let result = match query.qtype {
-        Rtype::AXFR => axfr::handle_axfr(stream, query, client_ip, Rtype::AXFR).await,
-        Rtype::IXFR => ixfr::handle_ixfr(stream, query, client_ip).await,
+    let result = match (query.qtype, catalog_name.as_deref()) {
+        // Catalog IXFR is always answered with the full member list, cut
+        // down to the zones this client may transfer.
+        (Rtype::AXFR | Rtype::IXFR, Some(catalog_name)) => {
+            let key_name = signer
+                .is_some()
+                .then(|| tsig::request_key_name(query_data))
+                .flatten();
+            let view = acl::catalog_view(
+                client_ip,
+                client_certificate,
+                secondary_acl,
+                &zone_acls,
+                key_name.as_deref(),
+            )
+            .await;
+            catalog::handle_catalog_axfr_with_qtype(
+                stream,
+                query,
+                query.qtype,
+                signer,
+                catalog_name,
+                &view,
+            )
+            .await
+        }
+        (Rtype::AXFR, None) => {
+            axfr::handle_axfr(stream, query, client_ip, Rtype::AXFR, signer).await
+        }
+        (Rtype::IXFR, None) => ixfr::handle_ixfr(stream, query, client_ip, signer).await,
         _ => {
             log_warn!("Unsupported query type: {:?}", query.qtype);
             return Err(XfrError::InvalidQuery(format!(
@@ -90,13 +188,24 @@ pub(crate) async fn handle_tcp_query(
     if let Err(err) = result {
         if matches!(err, XfrError::ZoneNotFound(_)) {
             count_xfr("notauth");
-            let response = wire::build_error_response(
-                query.query_id,
-                &query.qname,
-                query.qtype,
-                Rcode::NOTAUTH,
-            );
-            wire::write_tcp_message(stream, &response).await?;
+            if notauth_signer.is_some() {
+                let mut builder =
+                    wire::DnsMessageBuilder::new(query.query_id, &query.qname, query.qtype);
+                builder.set_rcode(Rcode::NOTAUTH);
+                builder.set_authoritative(false);
+                builder.set_signer(notauth_signer);
+                let frame = builder.build_tcp_frame()?;
+                stream.write_all(&frame).await?;
+                stream.flush().await?;
+            } else {
+                let response = wire::build_error_response(
+                    query.query_id,
+                    &query.qname,
+                    query.qtype,
+                    Rcode::NOTAUTH,
+                );
+                wire::write_tcp_message(stream, &response).await?;
+            }
             return Ok(());
         }
 
@@ -116,7 +225,10 @@ pub(crate) async fn handle_udp_query(
 ) -> Result<(), XfrError> {
     let client_ip = client_addr.ip();
 
-    validate_secondary_acl(client_ip, secondary_acl).await?;
+    refuse_denied(
+        client_ip,
+        acl::admit_client(client_ip, None, secondary_acl).await,
+    )?;
 
     log_warn!(
         "XFR-like UDP query is not supported (zone={:?}, qtype={:?}, from={})",
@@ -130,11 +242,11 @@ pub(crate) async fn handle_udp_query(
     ))
 }
 
-async fn validate_secondary_acl(
+fn refuse_denied(
     client_ip: IpAddr,
-    secondary_acl: &acl::SecondaryAcl,
-) -> Result<(), XfrError> {
-    if !acl::is_client_allowed(client_ip, secondary_acl).await {
+    admission: TransferAdmission,
+) -> Result<TransferAdmission, XfrError> {
+    if admission == TransferAdmission::Denied {
         log_warn!(
             "XFR request denied from {} (not a configured secondary server)",
             client_ip
@@ -145,5 +257,85 @@ async fn validate_secondary_acl(
         )));
     }
 
-    Ok(())
+    Ok(admission)
+}
+
+/// Verify the transfer request's TSIG (RFC 8945) and return the signer for
+/// the response stream. An address admitted only through keyed entries must
+/// sign with one of those keys; a keyless entry still has any TSIG the
+/// request carries verified against the stored key. A refused request gets
+/// its error reply here, before the `AccessDenied` error closes the stream.
+async fn authenticate_transfer(
+    stream: &mut impl DnsStream,
+    query_data: &[u8],
+    query: &wire::ParsedQuery,
+    admission: &TransferAdmission,
+) -> Result<Option<TransferSigner>, XfrError> {
+    let required = match admission {
+        TransferAdmission::Keys(keys) => Some(keys),
+        _ => None,
+    };
+
+    let Some(key_name) = tsig::request_key_name(query_data) else {
+        let Some(keys) = required else {
+            return Ok(None);
+        };
+        log_warn!(
+            "XFR request for {:?} refused: unsigned, but the secondary requires key {}",
+            query.zone_name,
+            keys.join(" or ")
+        );
+        let response =
+            wire::build_error_response(query.query_id, &query.qname, query.qtype, Rcode::REFUSED);
+        wire::write_tcp_message(stream, &response).await?;
+        return Err(XfrError::AccessDenied(
+            "transfer requires a TSIG-signed request".to_string(),
+        ));
+    };
+
+    // A key the ACL does not list for this address is answered as unknown.
+    let key = match required {
+        Some(keys) if !keys.contains(&key_name) => None,
+        _ => TsigKeyService::find_by_wire_name(&key_name)
+            .await
+            .map_err(|e| XfrError::DatabaseError(e.to_string()))?
+            .map(|key| tsig::domain_key(&key))
+            .transpose()
+            .map_err(XfrError::ProtocolError)?,
+    };
+
+    let mut message = Message::from_octets(query_data.to_vec())
+        .map_err(|e| XfrError::ProtocolError(format!("Failed to parse DNS message: {}", e)))?;
+    match TransferSigner::request(&SingleKeyStore(key), &mut message, Time48::now()) {
+        Ok(Some(signer)) => Ok(Some(signer)),
+        // The TSIG record is present but not where RFC 8945 requires it.
+        Ok(None) => {
+            let response = wire::build_error_response(
+                query.query_id,
+                &query.qname,
+                query.qtype,
+                Rcode::FORMERR,
+            );
+            wire::write_tcp_message(stream, &response).await?;
+            Err(XfrError::AccessDenied(
+                "TSIG record is not the last additional record".to_string(),
+            ))
+        }
+        Err(err) => {
+            let error = err.error();
+            log_warn!(
+                "XFR request for {:?} failed TSIG validation with key '{}': {}",
+                query.zone_name,
+                key_name,
+                error
+            );
+            if let Some(response) = tsig::error_response(query_data, err) {
+                wire::write_tcp_message(stream, &response).await?;
+            }
+            Err(XfrError::AccessDenied(format!(
+                "TSIG validation failed: {}",
+                error
+            )))
+        }
+    }
 }
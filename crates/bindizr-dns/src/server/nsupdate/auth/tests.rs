@@ -1,13 +1,21 @@
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use base64::Engine;
 use chrono::Utc;
-use domain::base::iana::{Class, Rtype};
+use domain::{
+    base::iana::{Class, Rcode, Rtype, TsigRcode},
+    rdata::tsig::Tsig,
+};
 use hmac::{Hmac, KeyInit, Mac};
 use sha2::{Sha256, Sha384, Sha512};
 
 use super::*;
-use crate::server::nsupdate::parser::tests::minimal_update_with_ztype;
+use crate::{
+    model::tsig_key::TsigAlgorithm, server::nsupdate::parser::tests::minimal_update_with_ztype,
+};
 
 pub(crate) const SECRET: &[u8] = b"a-very-secret-test-key-material!";
 
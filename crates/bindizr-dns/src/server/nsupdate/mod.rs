@@ -14,9 +14,9 @@ use domain::{
     },
     rdata::tsig::Time48,
 };
-use tokio::net::{TcpStream, UdpSocket};
+use tokio::net::UdpSocket;
 
-use crate::{log_info, log_warn, metrics::metrics};
+use crate::{log_info, log_warn, metrics::metrics, wire::DnsStream};
 
 /// Response-TSIG fudge for requests whose own fudge is unavailable
 /// (RFC 8945, Section 10 suggested default).
@@ -27,7 +27,7 @@ pub(crate) fn is_nsupdate(message: &[u8]) -> bool {
 }
 
 pub(crate) async fn handle_tcp_nsupdate(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     query_data: &[u8],
     client_addr: SocketAddr,
 ) -> Result<(), String> {
@@ -82,7 +82,7 @@ async fn handle_nsupdate_request(query_data: &[u8], client_addr: SocketAddr) ->
         .tsig
         .as_ref()
         .map_or(DEFAULT_FUDGE, |tsig| tsig.fudge);
-    let (result, signer) = update::apply_update(parsed, query_data).await;
+    let (result, signer) = update::apply_update(parsed, query_data, client_addr).await;
 
     let rcode = match result {
         Ok(changed) => {
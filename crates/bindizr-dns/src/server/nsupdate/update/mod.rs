@@ -2,7 +2,15 @@
 //! TSIG verification, the wire shapes RFC 2136 fixes for each section, and
 //! rdata parsing. Everything that touches zone data lives in the service.
 
-use bindizr_core::{config, dns::record::TxtRecordValue};
+use std::net::SocketAddr;
+
+use bindizr_core::{
+    config,
+    dns::record::{
+        CaaRecordValue, SshfpRecordValue, SvcbRecordValue, TlsaRecordValue, TxtRecordValue,
+        UnknownRecordValue,
+    },
+};
 use domain::{
     base::{
         iana::{Class, Rtype},
@@ -19,6 +27,7 @@ use super::{
 use crate::{
     model::{record::RecordType, tsig_key::TsigKey},
     service::{
+        audit::{self, AuditContext, Transport},
         dynamic_update::{
             DynamicUpdate, DynamicUpdateError, DynamicUpdateService, Prerequisite, UpdateOp,
         },
@@ -64,15 +73,17 @@ impl From<DynamicUpdateError> for UpdateError {
 pub(super) async fn apply_update(
     request: UpdateRequest,
     query_data: &[u8],
+    client_addr: SocketAddr,
 ) -> (Result<bool, UpdateError>, Option<ResponseSigner>) {
     let mut signer = None;
-    let result = apply_update_inner(request, query_data, &mut signer).await;
+    let result = apply_update_inner(request, query_data, client_addr, &mut signer).await;
     (result, signer)
 }
 
 async fn apply_update_inner(
     request: UpdateRequest,
     query_data: &[u8],
+    client_addr: SocketAddr,
     signer: &mut Option<ResponseSigner>,
 ) -> Result<bool, UpdateError> {
     let zone_name = request.zone_name.trim_end_matches('.');
@@ -86,6 +97,18 @@ async fn apply_update_inner(
     // and this lets even NOTZONE/REFUSED responses be signed.
     let key = authenticate_request(&request, query_data, signer).await?;
 
+    // The key name is captured before the key moves into the update.
+    let audit_context = AuditContext {
+        actor: key.as_ref().map_or_else(
+            || "unsigned".to_string(),
+            |key| format!("tsig:{}", key.name),
+        ),
+        tsig_key: key.as_ref().map(|key| key.name.clone()),
+        source_address: Some(client_addr.ip()),
+        transport: Transport::Nsupdate,
+        request_id: None,
+    };
+
     let update = DynamicUpdate {
         zone_name: zone_name.to_string(),
         key,
@@ -93,7 +116,7 @@ async fn apply_update_inner(
         updates: decode_updates(&request.updates, query_data)?,
     };
 
-    let changed = DynamicUpdateService::apply(update).await?;
+    let changed = audit::scope(audit_context, DynamicUpdateService::apply(update)).await?;
     Ok(changed)
 }
 
@@ -316,7 +339,7 @@ fn rr_to_record_value(
             Ok((RecordType::AAAA, data.addr().to_string(), None))
         }
         record_type @ (RecordType::CNAME | RecordType::NS | RecordType::PTR) => {
-            let name = parse_rdata(message, update, record_type.as_str(), |parser| {
+            let name = parse_rdata(message, update, &record_type.as_str(), |parser| {
                 ParsedName::parse(parser).ok()
             })?;
             let value = presentation_name(&name).map_err(|e| {
@@ -357,6 +380,37 @@ fn rr_to_record_value(
                 Some(i32::from(data.priority())),
             ))
         }
+        RecordType::CAA => {
+            let data = CaaRecordValue::from_rdata(&update.rdata)
+                .map_err(|e| UpdateError::Refused(format!("invalid CAA rdata: {}", e)))?;
+            Ok((RecordType::CAA, data.canonical(), None))
+        }
+        record_type @ (RecordType::SVCB | RecordType::HTTPS) => {
+            let data = SvcbRecordValue::from_rdata(&update.rdata).map_err(|e| {
+                UpdateError::Refused(format!("invalid {} rdata: {}", record_type.as_str(), e))
+            })?;
+            Ok((
+                record_type,
+                data.encoded(),
+                Some(i32::from(data.priority())),
+            ))
+        }
+        RecordType::TLSA => {
+            let data = TlsaRecordValue::from_rdata(&update.rdata)
+                .map_err(|e| UpdateError::Refused(format!("invalid TLSA rdata: {}", e)))?;
+            Ok((RecordType::TLSA, data.canonical(), None))
+        }
+        RecordType::SSHFP => {
+            let data = SshfpRecordValue::from_rdata(&update.rdata)
+                .map_err(|e| UpdateError::Refused(format!("invalid SSHFP rdata: {}", e)))?;
+            Ok((RecordType::SSHFP, data.canonical(), None))
+        }
+        record_type @ RecordType::Unknown(_) => {
+            let data = UnknownRecordValue::from_rdata(&update.rdata).map_err(|e| {
+                UpdateError::Refused(format!("invalid {} rdata: {}", record_type.as_str(), e))
+            })?;
+            Ok((record_type, data.canonical(), None))
+        }
         _ => Err(UpdateError::Refused(format!(
             "unsupported rr type: {}",
             update.rr_type
@@ -365,7 +419,7 @@ fn rr_to_record_value(
 }
 
 /// Record types updatable via nsupdate. SOA is excluded because it is managed
-/// through the zone's own fields.
+/// through the zone's own fields, as are the meta types.
 fn rr_type_to_record_type(rr_type: Rtype) -> Result<RecordType, UpdateError> {
     match rr_type {
         Rtype::A => Ok(RecordType::A),
@@ -376,10 +430,19 @@ fn rr_type_to_record_type(rr_type: Rtype) -> Result<RecordType, UpdateError> {
         Rtype::TXT => Ok(RecordType::TXT),
         Rtype::AAAA => Ok(RecordType::AAAA),
         Rtype::SRV => Ok(RecordType::SRV),
-        _ => Err(UpdateError::Refused(format!(
-            "unsupported rr type: {}",
-            rr_type
-        ))),
+        Rtype::CAA => Ok(RecordType::CAA),
+        Rtype::SVCB => Ok(RecordType::SVCB),
+        Rtype::HTTPS => Ok(RecordType::HTTPS),
+        Rtype::TLSA => Ok(RecordType::TLSA),
+        Rtype::SSHFP => Ok(RecordType::SSHFP),
+        // Any other storable type is carried as opaque RFC 3597 rdata.
+        _ => match RecordType::from_type_code(rr_type.to_int()) {
+            Ok(record_type @ RecordType::Unknown(_)) => Ok(record_type),
+            _ => Err(UpdateError::Refused(format!(
+                "unsupported rr type: {}",
+                rr_type
+            ))),
+        },
     }
 }
 
@@ -160,6 +160,64 @@ fn rr_to_record_value_splits_srv_priority_into_its_own_column() {
     assert_eq!(priority, Some(10));
 }
 
+#[test]
+fn rr_to_record_value_decodes_caa_rdata_into_canonical_form() {
+    let mut rdata = vec![0, 5];
+    rdata.extend_from_slice(b"ISSUEletsencrypt.org");
+    let update = update_record(Rtype::CAA, Class::IN, 300, rdata.clone());
+
+    let (record_type, value, priority) = rr_to_record_value(&update, &rdata).unwrap();
+
+    assert_eq!(record_type, RecordType::CAA);
+    assert_eq!(value, "0 issue \"letsencrypt.org\"");
+    assert_eq!(priority, None);
+}
+
+#[test]
+fn rr_to_record_value_moves_svcb_priority_into_its_column() {
+    // priority 1, target "svc.", port=443
+    let rdata = vec![0, 1, 3, b's', b'v', b'c', 0, 0, 3, 0, 2, 1, 187];
+    let update = update_record(Rtype::HTTPS, Class::IN, 300, rdata.clone());
+
+    let (record_type, value, priority) = rr_to_record_value(&update, &rdata).unwrap();
+
+    assert_eq!(record_type, RecordType::HTTPS);
+    assert_eq!(value, "svc. port=443");
+    assert_eq!(priority, Some(1));
+}
+
+#[test]
+fn rr_to_record_value_decodes_sshfp_rdata_into_hex() {
+    let mut rdata = vec![4, 1];
+    rdata.extend_from_slice(&[0xab; 20]);
+    let update = update_record(Rtype::SSHFP, Class::IN, 300, rdata.clone());
+
+    let (record_type, value, priority) = rr_to_record_value(&update, &rdata).unwrap();
+
+    assert_eq!(record_type, RecordType::SSHFP);
+    assert_eq!(value, format!("4 1 {}", "ab".repeat(20)));
+    assert_eq!(priority, None);
+}
+
+#[test]
+fn rr_to_record_value_carries_unmodeled_types_as_generic_rdata() {
+    let rdata = vec![0xc0, 0x00, 0x02, 0x01];
+    let update = update_record(Rtype::from_int(65280), Class::IN, 300, rdata.clone());
+
+    let (record_type, value, priority) = rr_to_record_value(&update, &rdata).unwrap();
+
+    assert_eq!(record_type, RecordType::Unknown(65280));
+    assert_eq!(value, r"\# 4 c0000201");
+    assert_eq!(priority, None);
+}
+
+#[test]
+fn rr_to_record_value_refuses_meta_types() {
+    let update = update_record(Rtype::OPT, Class::IN, 300, Vec::new());
+
+    assert!(rr_to_record_value(&update, &[]).is_err());
+}
+
 fn update_record(rr_type: Rtype, class: Class, ttl: u32, rdata: Vec<u8>) -> UpdateRecord {
     UpdateRecord {
         name: "www.example.com.".to_string(),
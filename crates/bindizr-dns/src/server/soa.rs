@@ -1,25 +1,27 @@
 //! Serves SOA queries over TCP and UDP, used by secondaries to poll the
 //! primary's serial.
 
-use std::net::{IpAddr, SocketAddr};
+use std::net::SocketAddr;
 
+use bindizr_core::dns::serial;
 use domain::base::iana::{Rcode, Rtype};
-use tokio::net::{TcpStream, UdpSocket};
+use tokio::net::UdpSocket;
 
 use crate::{
+    config,
     error::XfrError,
     log_info,
-    server::{catalog, delta},
+    server::{answer, catalog},
     service::zone::ZoneService,
-    wire,
+    wire::{self, DnsStream},
 };
 
 pub(crate) async fn handle_tcp_soa(
-    stream: &mut TcpStream,
+    stream: &mut impl DnsStream,
     client_addr: SocketAddr,
     query: &wire::ParsedQuery,
 ) -> Result<(), XfrError> {
-    let response = soa_response_bytes(query, client_addr.ip()).await?;
+    let response = soa_response_bytes(query, client_addr, usize::from(u16::MAX)).await?;
     wire::write_tcp_message(stream, &response).await?;
     Ok(())
 }
@@ -29,43 +31,45 @@ pub(crate) async fn handle_udp_soa(
     client_addr: SocketAddr,
     query: &wire::ParsedQuery,
 ) -> Result<(), XfrError> {
-    let response = soa_response_bytes(query, client_addr.ip()).await?;
+    let response = soa_response_bytes(query, client_addr, answer::udp_max_size(query)).await?;
     socket.send_to(&response, client_addr).await?;
     Ok(())
 }
 
-/// Build the SOA response bytes, mapping an unknown zone to a NOTAUTH response
-/// (TCP and UDP send identical bytes).
+/// Build the SOA response bytes. A name that is not a zone apex is answered
+/// like any other query when `dns.answer_queries` is on, and is NOTAUTH
+/// otherwise.
 async fn soa_response_bytes(
     query: &wire::ParsedQuery,
-    client_ip: IpAddr,
+    client_addr: SocketAddr,
+    max_len: usize,
 ) -> Result<Vec<u8>, XfrError> {
-    match build_soa_response(query, client_ip).await {
+    match build_soa_response(query, client_addr).await {
         Ok(response) => Ok(response),
-        Err(XfrError::ZoneNotFound(_)) => Ok(wire::build_error_response(
-            query.query_id,
-            &query.qname,
-            query.qtype,
-            Rcode::NOTAUTH,
-        )),
+        Err(XfrError::ZoneNotFound(_)) if config::get_bindizr_config().dns.answer_queries => {
+            answer::response_bytes(query, client_addr, max_len).await
+        }
+        Err(XfrError::ZoneNotFound(_)) => Ok(answer::error_response(query, Rcode::NOTAUTH)),
         Err(err) => Err(err),
     }
 }
 
 async fn build_soa_response(
     query: &wire::ParsedQuery,
-    client_ip: IpAddr,
+    client_addr: SocketAddr,
 ) -> Result<Vec<u8>, XfrError> {
+    let client_ip = client_addr.ip();
     let zone_name_str = query.zone_name.as_str();
 
     log_info!("SOA query for zone {:?} from {}", zone_name_str, client_ip);
 
-    if catalog::is_catalog_zone(zone_name_str) {
-        log_info!("SOA query for catalog zone: {}", catalog::CATALOG_ZONE_NAME);
-        let (catalog_zone, _) = catalog::generate_catalog_zone().await?;
+    if let Some(catalog_name) = catalog::resolve_catalog(zone_name_str).await? {
+        log_info!("SOA query for catalog zone: {}", catalog_name);
+        let (catalog_zone, _) = catalog::generate_catalog_zone(&catalog_name).await?;
 
         let mut builder = wire::DnsMessageBuilder::new(query.query_id, &query.qname, Rtype::SOA);
-        builder.add_catalog_soa(&catalog_zone, delta::serial_to_u32(catalog_zone.serial)?)?;
+        builder.set_edns(answer::edns_payload_size(query));
+        builder.add_catalog_soa(&catalog_zone, serial::to_wire(catalog_zone.serial))?;
         return Ok(builder.build());
     }
 
@@ -77,11 +81,12 @@ async fn build_soa_response(
     log_info!(
         "SOA response: zone {} serial={}",
         zone_name_str,
-        zone.serial
+        serial::to_wire(zone.serial)
     );
 
     let mut builder = wire::DnsMessageBuilder::new(query.query_id, &query.qname, Rtype::SOA);
-    builder.add_soa(&zone, delta::serial_to_u32(zone.serial)?)?;
+    builder.set_edns(answer::edns_payload_size(query));
+    builder.add_soa(&zone, serial::to_wire(zone.serial))?;
 
     Ok(builder.build())
 }
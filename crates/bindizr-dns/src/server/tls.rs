@@ -0,0 +1,82 @@
+//! TLS setup for the XoT listener (RFC 9103): the server certificate, and for
+//! mutual TLS the CA that client certificates must chain to.
+
+use std::sync::Arc;
+
+use tokio_rustls::{
+    TlsAcceptor,
+    rustls::{
+        RootCertStore, ServerConfig,
+        crypto::aws_lc_rs,
+        pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+        server::WebPkiClientVerifier,
+    },
+};
+
+use crate::config::DnsConfig;
+
+/// ALPN protocol id a zone transfer over TLS negotiates (RFC 9103, Section 7.1).
+const DOT_ALPN: &[u8] = b"dot";
+
+/// Builds the acceptor for the TLS listener from the configured PEM files.
+pub(crate) fn acceptor_from_config(config: &DnsConfig) -> Result<TlsAcceptor, String> {
+    let certificates = CertificateDer::pem_file_iter(&config.tls_cert_path)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| {
+            format!(
+                "Failed to read TLS certificate chain '{}': {}",
+                config.tls_cert_path, e
+            )
+        })?;
+    if certificates.is_empty() {
+        return Err(format!(
+            "TLS certificate file '{}' contains no certificates",
+            config.tls_cert_path
+        ));
+    }
+    let key = PrivateKeyDer::from_pem_file(&config.tls_key_path).map_err(|e| {
+        format!(
+            "Failed to read TLS private key '{}': {}",
+            config.tls_key_path, e
+        )
+    })?;
+
+    let builder = ServerConfig::builder_with_provider(Arc::new(aws_lc_rs::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("Failed to configure TLS protocol versions: {}", e))?;
+    let builder = if config.tls_client_ca_path.is_empty() {
+        builder.with_no_client_auth()
+    } else {
+        builder.with_client_cert_verifier(client_verifier(&config.tls_client_ca_path)?)
+    };
+
+    let mut server_config = builder
+        .with_single_cert(certificates, key)
+        .map_err(|e| format!("Invalid TLS certificate or private key: {}", e))?;
+    server_config.alpn_protocols = vec![DOT_ALPN.to_vec()];
+
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+/// Requires every client to present a certificate issued by the CA bundle.
+fn client_verifier(
+    ca_path: &str,
+) -> Result<Arc<dyn tokio_rustls::rustls::server::danger::ClientCertVerifier>, String> {
+    let mut roots = RootCertStore::empty();
+    for certificate in CertificateDer::pem_file_iter(ca_path)
+        .map_err(|e| format!("Failed to read TLS client CA '{}': {}", ca_path, e))?
+    {
+        let certificate = certificate
+            .map_err(|e| format!("Failed to read TLS client CA '{}': {}", ca_path, e))?;
+        roots
+            .add(certificate)
+            .map_err(|e| format!("Invalid TLS client CA certificate in '{}': {}", ca_path, e))?;
+    }
+
+    WebPkiClientVerifier::builder_with_provider(
+        Arc::new(roots),
+        Arc::new(aws_lc_rs::default_provider()),
+    )
+    .build()
+    .map_err(|e| format!("Failed to configure TLS client verification: {}", e))
+}
@@ -0,0 +1,119 @@
+//! In-memory index of the hosted zones by name, so finding the zone that
+//! encloses a query name is a map lookup per label rather than a database
+//! read per label.
+//!
+//! The index is rebuilt from one zone listing whenever this process commits a
+//! write (`ZoneService::write_generation` moves), and at least every
+//! `MAX_AGE`, which bounds how long a change made through another instance
+//! sharing the database goes unseen. Each entry carries the zone's serial, so
+//! `zone_cache` serves the records matching it.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+    time::Duration,
+};
+
+use bindizr_core::dns::name::{ZoneName, join_labels};
+use tokio::time::Instant;
+
+use crate::{
+    model::zone::Zone,
+    server::catalog,
+    service::{error::ServiceError, zone::ZoneService},
+};
+
+/// Longest an index is trusted without a local write invalidating it.
+const MAX_AGE: Duration = Duration::from_secs(5);
+
+/// The hosted zones by name; the catalog zone is served only by transfer and
+/// is left out.
+pub(crate) struct ZoneIndex {
+    zones: HashMap<String, Zone>,
+}
+
+impl ZoneIndex {
+    fn new(zones: Vec<Zone>) -> Self {
+        let zones = zones
+            .into_iter()
+            .filter(|zone| !catalog::is_catalog_zone(zone.name.as_str()))
+            .map(|zone| (zone.name.as_str().to_string(), zone))
+            .collect();
+        Self { zones }
+    }
+
+    /// The hosted zone closest to the name `labels` spell, if any.
+    pub(crate) fn enclosing(&self, labels: &[String]) -> Option<&Zone> {
+        (0..labels.len()).find_map(|start| {
+            // Suffixes that are not valid zone names (`_tcp`, `*`) cannot be zones.
+            let candidate = ZoneName::parse(&join_labels(&labels[start..])).ok()?;
+            self.zones.get(candidate.as_str())
+        })
+    }
+}
+
+struct LoadedIndex {
+    generation: u64,
+    loaded_at: Instant,
+    index: Arc<ZoneIndex>,
+}
+
+/// The most recently loaded index and when it was taken.
+#[derive(Default)]
+struct IndexCache {
+    loaded: Mutex<Option<LoadedIndex>>,
+}
+
+impl IndexCache {
+    /// The index as of `generation`, loaded with `load` only when the held
+    /// one is older than that or than `MAX_AGE`. Concurrent misses may both
+    /// load; either result is current.
+    async fn get<F, Fut>(&self, generation: u64, load: F) -> Result<Arc<ZoneIndex>, ServiceError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<Zone>, ServiceError>>,
+    {
+        if let Some(index) = self.fresh(generation) {
+            return Ok(index);
+        }
+
+        let loaded_at = Instant::now();
+        let index = Arc::new(ZoneIndex::new(load().await?));
+        *self.locked() = Some(LoadedIndex {
+            generation,
+            loaded_at,
+            index: index.clone(),
+        });
+        Ok(index)
+    }
+
+    fn fresh(&self, generation: u64) -> Option<Arc<ZoneIndex>> {
+        self.locked()
+            .as_ref()
+            .filter(|loaded| loaded.generation == generation)
+            .filter(|loaded| loaded.loaded_at.elapsed() < MAX_AGE)
+            .map(|loaded| loaded.index.clone())
+    }
+
+    /// Holds no invariant a panicking thread could break, so a poisoned lock
+    /// is recovered.
+    fn locked(&self) -> std::sync::MutexGuard<'_, Option<LoadedIndex>> {
+        self.loaded
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+static CACHE: OnceLock<IndexCache> = OnceLock::new();
+
+/// The current index of hosted zones, read from the database at most once
+/// per local write or `MAX_AGE`.
+pub(crate) async fn current() -> Result<Arc<ZoneIndex>, ServiceError> {
+    CACHE
+        .get_or_init(IndexCache::default)
+        .get(ZoneService::write_generation(), ZoneService::list)
+        .await
+}
+
+#[cfg(test)]
+mod tests;
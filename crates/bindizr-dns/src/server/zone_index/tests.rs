@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use chrono::Utc;
+
+use super::*;
+use crate::model::zone::SerialPolicy;
+
+fn zone(id: i32, name: &str) -> Zone {
+    Zone {
+        id,
+        name: ZoneName::parse(name).unwrap(),
+        primary_ns: format!("ns1.{name}."),
+        admin_email: format!("admin@{name}"),
+        ttl: 3600,
+        serial: 1,
+        serial_policy: SerialPolicy::Increment,
+        refresh: 300,
+        retry: 60,
+        expire: 3_600_000,
+        minimum_ttl: 300,
+        created_at: Utc::now(),
+    }
+}
+
+fn labels(name: &str) -> Vec<String> {
+    name.split('.').map(str::to_string).collect()
+}
+
+/// A loader over a fixed zone list that counts how often it is called.
+fn counting_loader(
+    loads: &AtomicUsize,
+) -> impl Fn() -> std::future::Ready<Result<Vec<Zone>, ServiceError>> + '_ {
+    move || {
+        loads.fetch_add(1, Ordering::Relaxed);
+        std::future::ready(Ok(vec![
+            zone(1, "example.com"),
+            zone(2, "sub.example.com"),
+            zone(3, catalog::CATALOG_ZONE_NAME),
+        ]))
+    }
+}
+
+#[test]
+fn enclosing_finds_the_closest_hosted_zone() {
+    let index = ZoneIndex::new(vec![zone(1, "example.com"), zone(2, "sub.example.com")]);
+
+    let enclosing = |name: &str| index.enclosing(&labels(name)).map(|zone| zone.id);
+
+    assert_eq!(enclosing("www.example.com"), Some(1));
+    assert_eq!(enclosing("example.com"), Some(1));
+    assert_eq!(enclosing("a.b.sub.example.com"), Some(2));
+    assert_eq!(enclosing("_sip._tcp.Sub.Example.com"), Some(2));
+    assert_eq!(enclosing("example.org"), None);
+}
+
+#[tokio::test]
+async fn a_deep_query_name_costs_no_extra_database_reads() {
+    let cache = IndexCache::default();
+    let loads = AtomicUsize::new(0);
+    let load = counting_loader(&loads);
+
+    let index = cache.get(0, &load).await.unwrap();
+    let deep = format!("{}sub.example.com", "x.".repeat(120));
+    assert_eq!(index.enclosing(&labels(&deep)).map(|zone| zone.id), Some(2));
+    assert!(
+        index
+            .enclosing(&labels(catalog::CATALOG_ZONE_NAME))
+            .is_none()
+    );
+
+    // Every later query at the same generation, CNAME links included,
+    // resolves from the same index.
+    for _ in 0..10 {
+        let index = cache.get(0, &load).await.unwrap();
+        assert!(index.enclosing(&labels(&deep)).is_some());
+    }
+    assert_eq!(loads.load(Ordering::Relaxed), 1);
+
+    // A committed write reloads it once.
+    cache.get(1, &load).await.unwrap();
+    cache.get(1, &load).await.unwrap();
+    assert_eq!(loads.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,58 @@
+use chrono::Utc;
+use domain::{
+    base::{MessageBuilder, Name},
+    rdata::tsig::Time48,
+    tsig::ClientTransaction,
+};
+
+use super::*;
+
+fn test_key(algorithm: TsigAlgorithm) -> TsigKey {
+    TsigKey {
+        id: 1,
+        name: "xfr-key".to_string(),
+        algorithm,
+        secret: base64::engine::general_purpose::STANDARD.encode(b"a-very-secret-test-key"),
+        is_global: false,
+        created_at: Utc::now(),
+    }
+}
+
+#[test]
+fn domain_key_maps_every_stored_algorithm() {
+    for (algorithm, expected) in [
+        (TsigAlgorithm::HmacSha256, Algorithm::Sha256),
+        (TsigAlgorithm::HmacSha384, Algorithm::Sha384),
+        (TsigAlgorithm::HmacSha512, Algorithm::Sha512),
+    ] {
+        let key = domain_key(&test_key(algorithm)).unwrap();
+
+        assert_eq!(key.algorithm(), expected);
+        assert_eq!(key.name().to_string(), "xfr-key");
+    }
+}
+
+#[test]
+fn domain_key_rejects_an_empty_secret() {
+    let mut key = test_key(TsigAlgorithm::HmacSha256);
+    key.secret = String::new();
+
+    assert!(domain_key(&key).unwrap_err().contains("empty key"));
+}
+
+#[test]
+fn request_key_name_reads_the_tsig_owner() {
+    let key = domain_key(&test_key(TsigAlgorithm::HmacSha256)).unwrap();
+    let qname = Name::<Vec<u8>>::from_str("example.com.").unwrap();
+    let request = MessageBuilder::new_vec().request_axfr(&qname).unwrap();
+    let unsigned = request.clone().finish();
+
+    let mut signed = request.additional();
+    ClientTransaction::request(key, &mut signed, Time48::now()).unwrap();
+
+    assert_eq!(
+        request_key_name(&signed.finish()).as_deref(),
+        Some("xfr-key")
+    );
+    assert_eq!(request_key_name(&unsigned), None);
+}
@@ -1,5 +1,5 @@
-//! DNS wire-format encoding for zone-transfer responses: message framing and
-//! record/SOA serialization.
+//! DNS wire-format encoding for zone-transfer and query responses: message
+//! framing and record/SOA serialization.
 
 use std::{
     net::{Ipv4Addr, Ipv6Addr},
@@ -9,6 +9,7 @@ use std::{
 use bindizr_core::dns::{
     name::{OwnerName, ZoneName, to_fqdn},
     record::{SoaMailbox, TxtRecordValue},
+    serial,
 };
 use domain::{
     base::{
@@ -16,8 +17,9 @@ use domain::{
         iana::{Class, Rcode, Rtype},
         rdata::ComposeRecordData,
         record::ComposeRecord,
+        wire::Composer,
     },
-    rdata::{A, Aaaa, Cname, Mx, Ns, Ptr, Soa, Srv, Txt},
+    rdata::{A, Aaaa, Cname, Mx, Ns, Ptr, Soa, Srv, Txt, tsig::Time48},
 };
 
 use crate::{
@@ -27,11 +29,25 @@ use crate::{
         record::{Record, RecordType},
         zone::Zone,
     },
+    tsig::TransferSigner,
 };
 
 /// Maximum size of a DNS message carried over TCP (16-bit length prefix).
 const DNS_TCP_MAX_SIZE: usize = 65535;
 
+/// Wire length of an OPT record with no options: root owner, TYPE, CLASS,
+/// TTL and an empty RDLENGTH (RFC 6891, Section 6.1.2).
+const OPT_RECORD_LEN: usize = 11;
+
+/// The message section the `add_*` methods write to. Transfers and SOA
+/// replies only fill the answer section; query answers use all three.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Section {
+    Answer,
+    Authority,
+    Additional,
+}
+
 pub(crate) struct DnsMessageBuilder {
     query_id: u16,
     qname: Name<Vec<u8>>,
@@ -39,6 +55,19 @@ pub(crate) struct DnsMessageBuilder {
     answers: Vec<Vec<u8>>,
     /// Total byte length of `answers`, maintained incrementally for `message_len`.
     answers_len: usize,
+    authority: Vec<Vec<u8>>,
+    additional: Vec<Vec<u8>>,
+    /// Total byte length of `authority` and `additional`.
+    trailing_len: usize,
+    section: Section,
+    rcode: Rcode,
+    authoritative: bool,
+    truncated: bool,
+    /// The UDP payload size advertised in an OPT record, when the query
+    /// carried EDNS.
+    edns_payload_size: Option<u16>,
+    /// Signs each TCP frame when the request was TSIG-verified.
+    signer: Option<TransferSigner>,
 }
 
 impl DnsMessageBuilder {
@@ -49,25 +78,70 @@ impl DnsMessageBuilder {
             qtype: qtype.to_int(),
             answers: Vec::new(),
             answers_len: 0,
+            authority: Vec::new(),
+            additional: Vec::new(),
+            trailing_len: 0,
+            section: Section::Answer,
+            rcode: Rcode::NOERROR,
+            authoritative: true,
+            truncated: false,
+            edns_payload_size: None,
+            signer: None,
         }
     }
 
+    /// Sign every frame `build_tcp_frame` produces from here on, continuing
+    /// the signer's MAC chain across the messages of one transfer.
+    pub(crate) fn set_signer(&mut self, signer: Option<TransferSigner>) {
+        self.signer = signer;
+    }
+
+    /// Direct the following `add_*` calls to `section`.
+    pub(crate) fn set_section(&mut self, section: Section) {
+        self.section = section;
+    }
+
+    pub(crate) fn set_rcode(&mut self, rcode: Rcode) {
+        self.rcode = rcode;
+    }
+
+    /// Clear the AA bit, as a referral does: the data it carries belongs to
+    /// the child zone (RFC 1034, Section 4.3.2).
+    pub(crate) fn set_authoritative(&mut self, authoritative: bool) {
+        self.authoritative = authoritative;
+    }
+
+    /// End the additional section with an OPT record advertising
+    /// `udp_payload_size` (RFC 6891, Section 6.1). It is kept by `truncate`.
+    pub(crate) fn set_edns(&mut self, udp_payload_size: Option<u16>) {
+        self.edns_payload_size = udp_payload_size;
+    }
+
+    /// Drop every record and set TC, for a reply that does not fit the
+    /// transport; the client retries over TCP (RFC 1035, Section 4.2.1).
+    pub(crate) fn truncate(&mut self) {
+        self.clear_answers();
+        self.authority.clear();
+        self.additional.clear();
+        self.trailing_len = 0;
+        self.truncated = true;
+    }
+
     pub(crate) fn add_soa(&mut self, zone: &Zone, serial: u32) -> Result<(), XfrError> {
-        let admin_email =
-            SoaMailbox::from_email(&zone.admin_email).map_err(XfrError::ProtocolError)?;
-        let soa = Soa::new(
-            parse_name(&zone.primary_ns)?,
-            parse_name(admin_email.as_str())?,
-            Serial(serial),
-            Ttl::from_secs(zone.refresh as u32),
-            Ttl::from_secs(zone.retry as u32),
-            Ttl::from_secs(zone.expire as u32),
-            Ttl::from_secs(zone.minimum_ttl as u32),
-        );
+        let soa = zone_soa(zone, serial)?;
         self.add_answer(parse_name(zone.name.as_str())?, zone.ttl as u32, soa);
         Ok(())
     }
 
+    /// Adds the SOA a negative answer carries, whose TTL is the lesser of the
+    /// SOA's own TTL and its MINIMUM field (RFC 2308, Section 3).
+    pub(crate) fn add_negative_soa(&mut self, zone: &Zone, serial: u32) -> Result<(), XfrError> {
+        let soa = zone_soa(zone, serial)?;
+        let ttl = zone.ttl.min(zone.minimum_ttl) as u32;
+        self.add_answer(parse_name(zone.name.as_str())?, ttl, soa);
+        Ok(())
+    }
+
     /// Adds a catalog-zone SOA with placeholder `invalid` MNAME/RNAME.
     pub(crate) fn add_catalog_soa(&mut self, zone: &Zone, serial: u32) -> Result<(), XfrError> {
         let soa = Soa::new(
@@ -88,7 +162,7 @@ impl DnsMessageBuilder {
         &mut self,
         soa: &crate::server::delta::ZoneSnapshot,
     ) -> Result<(), XfrError> {
-        let serial = crate::server::delta::serial_to_u32(soa.serial)?;
+        let serial = serial::to_wire(soa.serial);
         let rdata = Soa::new(
             parse_name(&soa.primary_ns)?,
             parse_name(&soa.admin_email)?,
@@ -205,6 +279,21 @@ impl DnsMessageBuilder {
         Ok(())
     }
 
+    /// Adds a record of `rtype` from its already-encoded wire rdata, for the
+    /// types whose value type builds the rdata itself (CAA, SVCB, TLSA, ...).
+    pub(crate) fn add_rdata_record(
+        &mut self,
+        name: &str,
+        ttl: u32,
+        rtype: Rtype,
+        rdata: Vec<u8>,
+    ) -> Result<(), XfrError> {
+        let data = UnknownRecordData::from_octets(rtype, rdata)
+            .map_err(|e| XfrError::ProtocolError(format!("Invalid {} rdata: {}", rtype, e)))?;
+        self.add_answer(parse_name(name)?, ttl, data);
+        Ok(())
+    }
+
     /// Adds the catalog-zone NS record, which is the placeholder "invalid".
     pub(crate) fn add_catalog_ns(&mut self, zone: &Zone) -> Result<(), XfrError> {
         let owner_name = zone.name.to_fqdn();
@@ -233,6 +322,47 @@ impl DnsMessageBuilder {
         Ok(())
     }
 
+    /// Adds a member's RFC 9432 `group` property TXT record.
+    pub(crate) fn add_catalog_group(
+        &mut self,
+        zone: &Zone,
+        member_zone: &str,
+        group: &str,
+    ) -> Result<(), XfrError> {
+        let member_id = crate::server::catalog::zone_name_to_member_id(member_zone);
+        let group_name = format!("group.{}.zones.{}.", member_id, zone.name);
+        self.add_txt_record(&group_name, zone.ttl as u32, group)?;
+        Ok(())
+    }
+
+    /// Adds a member's RFC 9432 `coo` property PTR, naming the catalog that
+    /// takes the member over.
+    pub(crate) fn add_catalog_coo(
+        &mut self,
+        zone: &Zone,
+        member_zone: &str,
+        new_catalog: &str,
+    ) -> Result<(), XfrError> {
+        let member_id = crate::server::catalog::zone_name_to_member_id(member_zone);
+        let coo_name = format!("coo.{}.zones.{}.", member_id, zone.name);
+        self.add_ptr_record(&coo_name, zone.ttl as u32, &to_fqdn(new_catalog))?;
+        Ok(())
+    }
+
+    /// Adds a member's custom property as a TXT record under `ext`.
+    pub(crate) fn add_catalog_ext(
+        &mut self,
+        zone: &Zone,
+        member_zone: &str,
+        property: &str,
+        value: &str,
+    ) -> Result<(), XfrError> {
+        let member_id = crate::server::catalog::zone_name_to_member_id(member_zone);
+        let ext_name = format!("{}.ext.{}.zones.{}.", property, member_id, zone.name);
+        self.add_txt_record(&ext_name, zone.ttl as u32, value)?;
+        Ok(())
+    }
+
     /// Adds an answer from a database Record model.
     pub(crate) fn add_record(
         &mut self,
@@ -242,7 +372,7 @@ impl DnsMessageBuilder {
         self.add_record_parts(
             zone_name,
             &record.name,
-            record.record_type.as_str(),
+            &record.record_type.as_str(),
             &record.value,
             record.ttl,
             record.priority,
@@ -291,15 +421,45 @@ impl DnsMessageBuilder {
                 self.add_srv_record(&owner_name, ttl, srv_priority, weight, port, target)
             }
             "TXT" => self.add_txt_record(&owner_name, ttl, value),
-            other => {
-                log_info!("Skipping unsupported record type: {}", other);
-                Ok(())
+            "CAA" => {
+                let rdata = RecordType::caa_wire_rdata(value).map_err(XfrError::ProtocolError)?;
+                self.add_rdata_record(&owner_name, ttl, Rtype::CAA, rdata)
+            }
+            "SVCB" | "HTTPS" => {
+                let rtype = if record_type == "SVCB" {
+                    Rtype::SVCB
+                } else {
+                    Rtype::HTTPS
+                };
+                let rdata = RecordType::svcb_wire_rdata(value, priority)
+                    .map_err(XfrError::ProtocolError)?;
+                self.add_rdata_record(&owner_name, ttl, rtype, rdata)
             }
+            "TLSA" => {
+                let rdata = RecordType::tlsa_wire_rdata(value).map_err(XfrError::ProtocolError)?;
+                self.add_rdata_record(&owner_name, ttl, Rtype::TLSA, rdata)
+            }
+            "SSHFP" => {
+                let rdata = RecordType::sshfp_wire_rdata(value).map_err(XfrError::ProtocolError)?;
+                self.add_rdata_record(&owner_name, ttl, Rtype::SSHFP, rdata)
+            }
+            other => match other.parse::<RecordType>() {
+                Ok(RecordType::Unknown(code)) => {
+                    let rdata =
+                        RecordType::unknown_wire_rdata(value).map_err(XfrError::ProtocolError)?;
+                    self.add_rdata_record(&owner_name, ttl, Rtype::from_int(code), rdata)
+                }
+                _ => {
+                    log_info!("Skipping unsupported record type: {}", other);
+                    Ok(())
+                }
+            },
         }
     }
 
-    /// Composes one class-IN answer RR into its own buffer so it can be
-    /// popped/reflushed by the chunked TCP writer.
+    /// Composes one class-IN RR into its own buffer so it can be
+    /// popped/reflushed by the chunked TCP writer, then files it under the
+    /// current section.
     fn add_answer<N: ToName, D: ComposeRecordData>(&mut self, owner: N, ttl: u32, data: D) {
         let record = domain::base::Record::new(owner, Class::IN, Ttl::from_secs(ttl), data);
         let mut answer = Vec::new();
@@ -307,7 +467,17 @@ impl DnsMessageBuilder {
         record
             .compose_record(&mut answer)
             .expect("composing into a Vec cannot run out of space");
-        self.push_answer(answer);
+        match self.section {
+            Section::Answer => self.push_answer(answer),
+            Section::Authority => {
+                self.trailing_len += answer.len();
+                self.authority.push(answer);
+            }
+            Section::Additional => {
+                self.trailing_len += answer.len();
+                self.additional.push(answer);
+            }
+        }
     }
 
     pub(crate) fn answer_count(&self) -> usize {
@@ -315,7 +485,12 @@ impl DnsMessageBuilder {
     }
 
     pub(crate) fn message_len(&self) -> usize {
-        12 + self.qname.len() + 4 + self.answers_len
+        let tsig_len = self
+            .signer
+            .as_ref()
+            .map_or(0, |signer| usize::from(signer.key().compose_len()));
+        let opt_len = self.edns_payload_size.map_or(0, |_| OPT_RECORD_LEN);
+        12 + self.qname.len() + 4 + self.answers_len + self.trailing_len + opt_len + tsig_len
     }
 
     pub(crate) fn pop_last_answer(&mut self) -> Option<Vec<u8>> {
@@ -337,26 +512,45 @@ impl DnsMessageBuilder {
     }
 
     fn build_message_into(&self, message: &mut Vec<u8>) {
+        let mut flags = 0x80; // QR=1, Opcode=0, RD=0
+        if self.authoritative {
+            flags |= 0x04; // AA
+        }
+        if self.truncated {
+            flags |= 0x02; // TC
+        }
+
         message.extend_from_slice(&self.query_id.to_be_bytes()); // ID
-        message.push(0x84); // QR=1, Opcode=0, AA=1, TC=0, RD=0
-        message.push(0x00); // RA=0, Z=0, RCODE=0 (NOERROR)
+        message.push(flags);
+        message.push(self.rcode.to_int()); // RA=0, Z=0, RCODE
         message.extend_from_slice(&1u16.to_be_bytes()); // QDCOUNT=1
         message.extend_from_slice(&(self.answers.len() as u16).to_be_bytes()); // ANCOUNT
-        message.extend_from_slice(&0u16.to_be_bytes()); // NSCOUNT=0
-        message.extend_from_slice(&0u16.to_be_bytes()); // ARCOUNT=0
+        message.extend_from_slice(&(self.authority.len() as u16).to_be_bytes()); // NSCOUNT
+        let additional_count =
+            self.additional.len() + usize::from(self.edns_payload_size.is_some());
+        message.extend_from_slice(&(additional_count as u16).to_be_bytes()); // ARCOUNT
 
         message.extend_from_slice(self.qname.as_slice());
         message.extend_from_slice(&self.qtype.to_be_bytes()); // QTYPE
         message.extend_from_slice(&1u16.to_be_bytes()); // QCLASS (IN)
 
-        for answer in &self.answers {
-            message.extend_from_slice(answer);
+        for record in self
+            .answers
+            .iter()
+            .chain(&self.authority)
+            .chain(&self.additional)
+        {
+            message.extend_from_slice(record);
+        }
+        if let Some(udp_payload_size) = self.edns_payload_size {
+            message.extend_from_slice(&opt_record(udp_payload_size));
         }
     }
 
     /// Serializes into a length-prefixed TCP frame in one buffer, with no
-    /// intermediate message copy.
-    pub(crate) fn build_tcp_frame(&self) -> Result<Vec<u8>, XfrError> {
+    /// intermediate message copy. A signed frame is composed through
+    /// `domain`, which appends the TSIG record.
+    pub(crate) fn build_tcp_frame(&mut self) -> Result<Vec<u8>, XfrError> {
         let len = self.message_len();
         if len > DNS_TCP_MAX_SIZE {
             return Err(XfrError::ProtocolError(format!(
@@ -365,12 +559,59 @@ impl DnsMessageBuilder {
             )));
         }
 
+        if let Some(mut signer) = self.signer.take() {
+            let message = self.build_signed_message(&mut signer);
+            self.signer = Some(signer);
+            return encode_tcp_message(&message?);
+        }
+
         let mut frame = Vec::with_capacity(2 + len);
         frame.extend_from_slice(&(len as u16).to_be_bytes());
         self.build_message_into(&mut frame);
         Ok(frame)
     }
 
+    fn build_signed_message(&self, signer: &mut TransferSigner) -> Result<Vec<u8>, XfrError> {
+        // Composing into a Vec cannot run out of space.
+        const INFALLIBLE: &str = "composing into a Vec cannot run out of space";
+
+        let mut builder = MessageBuilder::new_vec();
+        let header = builder.header_mut();
+        header.set_id(self.query_id);
+        header.set_qr(true);
+        header.set_aa(self.authoritative);
+        header.set_tc(self.truncated);
+        header.set_rcode(self.rcode);
+
+        let mut question = builder.question();
+        question
+            .push((&self.qname, Rtype::from_int(self.qtype)))
+            .expect(INFALLIBLE);
+
+        let mut answer = question.answer();
+        for record in &self.answers {
+            answer.push(ComposedRecord(record)).expect(INFALLIBLE);
+        }
+        let mut authority = answer.authority();
+        for record in &self.authority {
+            authority.push(ComposedRecord(record)).expect(INFALLIBLE);
+        }
+        let mut additional = authority.additional();
+        for record in &self.additional {
+            additional.push(ComposedRecord(record)).expect(INFALLIBLE);
+        }
+        if let Some(udp_payload_size) = self.edns_payload_size {
+            additional
+                .push(ComposedRecord(&opt_record(udp_payload_size)))
+                .expect(INFALLIBLE);
+        }
+
+        signer
+            .answer(&mut additional, Time48::now())
+            .map_err(|e| XfrError::ProtocolError(format!("Failed to sign response: {}", e)))?;
+        Ok(additional.finish())
+    }
+
     /// Consumes the builder and returns the serialized DNS message.
     pub(crate) fn build(self) -> Vec<u8> {
         let mut message = Vec::with_capacity(self.message_len());
@@ -379,6 +620,28 @@ impl DnsMessageBuilder {
     }
 }
 
+/// An OPT pseudo-record advertising `udp_payload_size`, with extended RCODE
+/// 0, version 0, no flags and no options.
+fn opt_record(udp_payload_size: u16) -> [u8; OPT_RECORD_LEN] {
+    let mut record = [0u8; OPT_RECORD_LEN];
+    record[1..3].copy_from_slice(&Rtype::OPT.to_int().to_be_bytes());
+    record[3..5].copy_from_slice(&udp_payload_size.to_be_bytes());
+    record
+}
+
+/// A record already composed by `add_answer`, appended to a `domain`
+/// builder unchanged.
+struct ComposedRecord<'a>(&'a [u8]);
+
+impl ComposeRecord for ComposedRecord<'_> {
+    fn compose_record<Target: Composer + ?Sized>(
+        &self,
+        target: &mut Target,
+    ) -> Result<(), Target::AppendError> {
+        target.append_slice(self.0)
+    }
+}
+
 pub(crate) async fn add_answer_and_flush_if_needed<W, F>(
     writer: &mut W,
     builder: &mut DnsMessageBuilder,
@@ -463,6 +726,20 @@ pub(crate) fn build_error_response(
     question.finish()
 }
 
+/// The SOA rdata for `zone` at `serial`.
+fn zone_soa(zone: &Zone, serial: u32) -> Result<Soa<Name<Vec<u8>>>, XfrError> {
+    let admin_email = SoaMailbox::from_email(&zone.admin_email).map_err(XfrError::ProtocolError)?;
+    Ok(Soa::new(
+        parse_name(&zone.primary_ns)?,
+        parse_name(admin_email.as_str())?,
+        Serial(serial),
+        Ttl::from_secs(zone.refresh as u32),
+        Ttl::from_secs(zone.retry as u32),
+        Ttl::from_secs(zone.expire as u32),
+        Ttl::from_secs(zone.minimum_ttl as u32),
+    ))
+}
+
 /// Parses a presentation-form name, mapping empty/root input to the root name.
 fn parse_name(name: &str) -> Result<Name<Vec<u8>>, XfrError> {
     if name.trim_end_matches('.').is_empty() {
@@ -481,6 +758,9 @@ pub(crate) struct ParsedQuery {
     pub(crate) qtype: Rtype,
     pub(crate) client_serial: Option<u32>,
     pub(crate) query_id: u16,
+    /// The UDP payload size the requester advertised in an OPT record
+    /// (RFC 6891, Section 6.2.3); `None` for a query without EDNS.
+    pub(crate) udp_payload_size: Option<u16>,
 }
 
 pub(crate) fn parse_query(data: &[u8]) -> Result<ParsedQuery, XfrError> {
@@ -507,12 +787,15 @@ pub(crate) fn parse_query(data: &[u8]) -> Result<ParsedQuery, XfrError> {
         None
     };
 
+    let udp_payload_size = message.opt().map(|opt| opt.udp_payload_size());
+
     Ok(ParsedQuery {
         qname,
         zone_name,
         qtype,
         client_serial,
         query_id,
+        udp_payload_size,
     })
 }
 
@@ -540,6 +823,15 @@ pub(crate) fn encode_tcp_message(message: &[u8]) -> Result<Vec<u8>, XfrError> {
     Ok(result)
 }
 
+/// A connected DNS byte stream, plain TCP or TLS: both carry the same
+/// length-prefixed messages (RFC 7766, RFC 7858).
+pub(crate) trait DnsStream:
+    tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send
+{
+}
+
+impl<T: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send> DnsStream for T {}
+
 pub(crate) async fn read_tcp_message<R: tokio::io::AsyncReadExt + Unpin>(
     reader: &mut R,
 ) -> Result<Vec<u8>, XfrError> {
@@ -1,11 +1,19 @@
-use std::{net::Ipv4Addr, str::FromStr};
+use std::{net::Ipv4Addr, str::FromStr, sync::Arc};
 
-use domain::base::{Name, iana::Rtype};
+use domain::{
+    base::{
+        Message, MessageBuilder, Name,
+        iana::{Rcode, Rtype},
+    },
+    rdata::tsig::Time48,
+    tsig::{Algorithm, ClientSequence, Key, KeyName},
+};
 
 use super::{
-    DNS_TCP_MAX_SIZE, DnsMessageBuilder, XfrError, add_answer_and_flush_if_needed,
-    encode_tcp_message, flush_message_if_not_empty, parse_name,
+    DNS_TCP_MAX_SIZE, DnsMessageBuilder, Section, XfrError, add_answer_and_flush_if_needed,
+    encode_tcp_message, flush_message_if_not_empty, parse_name, parse_query,
 };
+use crate::tsig::{SingleKeyStore, TransferSigner};
 
 #[test]
 fn encode_tcp_message_rejects_oversized_payload() {
@@ -70,3 +78,162 @@ async fn chunked_tcp_writer_splits_large_answer_sets() {
     assert_eq!(answer_count, 4000);
     assert!(frame_count > 1);
 }
+
+#[test]
+fn builder_files_records_under_the_selected_section() {
+    let qname = Name::<Vec<u8>>::from_str("host.sub.example.com.").unwrap();
+    let mut builder = DnsMessageBuilder::new(7, &qname, Rtype::A);
+    builder.set_authoritative(false);
+    builder.set_section(Section::Authority);
+    builder
+        .add_ns_record("sub.example.com.", 300, "ns.sub.example.com.")
+        .unwrap();
+    builder.set_section(Section::Additional);
+    builder
+        .add_a_record("ns.sub.example.com.", 300, Ipv4Addr::new(192, 0, 2, 53))
+        .unwrap();
+
+    let message = Message::from_octets(builder.build()).unwrap();
+    let header = message.header();
+    let counts = message.header_counts();
+
+    assert!(header.qr() && !header.aa() && !header.tc());
+    assert_eq!(header.rcode(), Rcode::NOERROR);
+    assert_eq!(
+        (counts.ancount(), counts.nscount(), counts.arcount()),
+        (0, 1, 1)
+    );
+}
+
+#[test]
+fn truncate_keeps_only_the_question_and_sets_tc() {
+    let qname = Name::<Vec<u8>>::from_str("example.com.").unwrap();
+    let mut builder = DnsMessageBuilder::new(7, &qname, Rtype::A);
+    builder.set_rcode(Rcode::NXDOMAIN);
+    builder
+        .add_a_record("example.com.", 300, Ipv4Addr::new(192, 0, 2, 1))
+        .unwrap();
+
+    builder.truncate();
+    let message = Message::from_octets(builder.build()).unwrap();
+    let counts = message.header_counts();
+
+    assert!(message.header().tc());
+    assert_eq!(message.header().rcode(), Rcode::NXDOMAIN);
+    assert_eq!(counts.qdcount(), 1);
+    assert_eq!(
+        (counts.ancount(), counts.nscount(), counts.arcount()),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn parse_query_reads_the_edns_udp_payload_size() {
+    let qname = Name::<Vec<u8>>::from_str("example.com.").unwrap();
+    let mut question = MessageBuilder::new_vec().question();
+    question.push((&qname, Rtype::A)).unwrap();
+    let plain = question.clone().finish();
+    let mut additional = question.additional();
+    additional
+        .opt(|opt| {
+            opt.set_udp_payload_size(4096);
+            Ok(())
+        })
+        .unwrap();
+    let with_edns = additional.finish();
+
+    assert_eq!(parse_query(&plain).unwrap().udp_payload_size, None);
+    assert_eq!(
+        parse_query(&with_edns).unwrap().udp_payload_size,
+        Some(4096)
+    );
+}
+
+#[test]
+fn edns_reply_ends_with_an_opt_record_that_survives_truncation() {
+    let qname = Name::<Vec<u8>>::from_str("example.com.").unwrap();
+    let mut builder = DnsMessageBuilder::new(7, &qname, Rtype::A);
+    builder.set_edns(Some(1232));
+    builder.set_section(Section::Additional);
+    builder
+        .add_a_record("ns.example.com.", 300, Ipv4Addr::new(192, 0, 2, 53))
+        .unwrap();
+    let len = builder.message_len();
+
+    let built = builder.build();
+    assert_eq!(built.len(), len);
+    let message = Message::from_octets(built).unwrap();
+    assert_eq!(message.header_counts().arcount(), 2);
+    assert_eq!(message.opt().unwrap().udp_payload_size(), 1232);
+
+    let mut builder = DnsMessageBuilder::new(7, &qname, Rtype::A);
+    builder.set_edns(Some(1232));
+    builder
+        .add_a_record("example.com.", 300, Ipv4Addr::new(192, 0, 2, 1))
+        .unwrap();
+    builder.truncate();
+    let message = Message::from_octets(builder.build()).unwrap();
+    assert!(message.header().tc());
+    assert_eq!(message.header_counts().ancount(), 0);
+    assert_eq!(message.opt().unwrap().udp_payload_size(), 1232);
+}
+
+#[tokio::test]
+async fn signed_transfer_frames_verify_as_one_tsig_sequence() {
+    let key = Arc::new(
+        Key::new(
+            Algorithm::Sha256,
+            b"a-very-secret-test-key-material!",
+            KeyName::from_str("xfr-key").unwrap(),
+            None,
+            None,
+        )
+        .unwrap(),
+    );
+    let qname = Name::<Vec<u8>>::from_str("example.com.").unwrap();
+
+    let mut request = MessageBuilder::new_vec()
+        .request_axfr(&qname)
+        .unwrap()
+        .additional();
+    let mut client = ClientSequence::request(key.clone(), &mut request, Time48::now()).unwrap();
+    let mut request = Message::from_octets(request.finish()).unwrap();
+    let query_id = request.header().id();
+    let signer = TransferSigner::request(&SingleKeyStore(Some(key)), &mut request, Time48::now())
+        .unwrap()
+        .unwrap();
+
+    let mut builder = DnsMessageBuilder::new(query_id, &qname, Rtype::AXFR);
+    builder.set_signer(Some(signer));
+    let mut writer = Vec::new();
+    let mut sent = 0usize;
+    for index in 0..4000 {
+        add_answer_and_flush_if_needed(&mut writer, &mut builder, &mut sent, |builder| {
+            builder.add_a_record(
+                &format!("host-{}.example.com.", index),
+                3600,
+                Ipv4Addr::new(192, 0, 2, (index % 255) as u8),
+            )
+        })
+        .await
+        .unwrap();
+    }
+    sent += flush_message_if_not_empty(&mut writer, &mut builder)
+        .await
+        .unwrap();
+
+    let mut frames = 0;
+    let mut pos = 0;
+    while pos < writer.len() {
+        let len = u16::from_be_bytes([writer[pos], writer[pos + 1]]) as usize;
+        assert!(len <= DNS_TCP_MAX_SIZE);
+        let mut message = Message::from_octets(writer[pos + 2..pos + 2 + len].to_vec()).unwrap();
+        client.answer(&mut message, Time48::now()).unwrap();
+        frames += 1;
+        pos += 2 + len;
+    }
+
+    assert!(frames > 1);
+    assert_eq!(frames, sent);
+    client.done().unwrap();
+}
@@ -0,0 +1,111 @@
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_moves_into_a_named_catalog_with_properties() {
+    let app = TestApp::start().await;
+    let catalog = app.zone_name("pool-a.catalog.example");
+    let zone_name = app.zone_name("catalog-member.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let path = format!("/zones/{zone_name}/catalog");
+
+    let (status, body) = app.request(Method::GET, &path, None).await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["catalog_properties"]["catalog"], "catalog.bind");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/catalogs",
+            Some(json!({ "name": catalog, "description": "EU pool" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    assert_eq!(body["catalog"]["name"], catalog);
+
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &path,
+            Some(json!({
+                "catalog": catalog,
+                "group": "signed",
+                "ext": { "serial-policy": "unixtime" }
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+
+    let (status, body) = app.request(Method::GET, &path, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let properties = &body["catalog_properties"];
+    assert_eq!(properties["catalog"], catalog);
+    assert_eq!(properties["group"], "signed");
+    assert!(properties["coo"].is_null());
+    assert_eq!(properties["ext"]["serial-policy"], "unixtime");
+
+    let (status, body) = app
+        .request(Method::DELETE, &format!("/catalogs/{catalog}"), None)
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body:#?}");
+    assert_eq!(body["code"], "CATALOG_IN_USE");
+
+    // An empty body returns the zone to catalog.bind and frees the catalog.
+    let (status, body) = app.request(Method::PUT, &path, Some(json!({}))).await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["catalog_properties"]["catalog"], "catalog.bind");
+
+    let (status, _) = app
+        .request(Method::DELETE, &format!("/catalogs/{catalog}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn catalog_properties_reject_invalid_values() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("catalog-invalid.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let path = format!("/zones/{zone_name}/catalog");
+
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &path,
+            Some(json!({ "catalog": app.zone_name("missing.catalog.example") })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body:#?}");
+    assert_eq!(body["code"], "CATALOG_NOT_FOUND");
+
+    for properties in [
+        json!({ "coo": "catalog.bind" }),
+        json!({ "group": "x".repeat(256) }),
+        json!({ "ext": { "not a label": "value" } }),
+    ] {
+        let (status, body) = app.request(Method::PUT, &path, Some(properties)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body:#?}");
+    }
+
+    // A catalog cannot take a hosted zone's name, nor a zone a catalog's.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/catalogs",
+            Some(json!({ "name": zone_name })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body:#?}");
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/catalogs",
+            Some(json!({ "name": "catalog.bind" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body:#?}");
+}
@@ -0,0 +1,168 @@
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+use crate::common::{TestApp, TestAppOptions, secondary::StandInSecondary};
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_notify_target_create_list_delete() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("notify-target.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let path = format!("/zones/{zone_name}/notify-targets");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &path,
+            Some(json!({ "address": "NS2.Example.NET:5353" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    assert_eq!(body["notify_target"]["kind"], "notify");
+    assert_eq!(body["notify_target"]["address"], "ns2.example.net:5353");
+    assert!(body["notify_target"]["tsig_key"].is_null());
+    let id = body["notify_target"]["id"].as_i64().unwrap();
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &path,
+            Some(json!({ "kind": "also-notify", "address": "198.51.100.5" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+
+    let (status, body) = app.request(Method::GET, &path, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["notify_targets"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1]["kind"], "also-notify");
+
+    let (status, _) = app
+        .request(Method::DELETE, &format!("{path}/{id}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app
+        .request(Method::DELETE, &format!("{path}/{id}"), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "NOTIFY_TARGET_NOT_FOUND");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_notify_target_rejects_invalid_entries() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("notify-target-invalid.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let path = format!("/zones/{zone_name}/notify-targets");
+
+    for body in [
+        json!({ "address": "198.51.100.0/24" }),
+        json!({ "address": "192.0.2.1:0" }),
+        json!({ "kind": "sometimes", "address": "192.0.2.1" }),
+    ] {
+        let (status, response) = app.request(Method::POST, &path, Some(body.clone())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body} -> {response:#?}");
+    }
+
+    let (status, _) = app
+        .request(Method::POST, &path, Some(json!({ "address": "192.0.2.1" })))
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, body) = app
+        .request(Method::POST, &path, Some(json!({ "address": "192.0.2.1" })))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("already"));
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &path,
+            Some(json!({ "address": "192.0.2.2", "tsig_key": "missing-key" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "TSIG_KEY_NOT_FOUND");
+
+    let missing = app.zone_name("missing.example");
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{missing}/notify-targets"),
+            Some(json!({ "address": "192.0.2.1" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "ZONE_NOT_FOUND");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn notify_and_status_use_the_zone_targets() {
+    let global = StandInSecondary::spawn(1);
+    let pool = StandInSecondary::spawn(1);
+    let also = StandInSecondary::spawn(1);
+    let app = TestApp::start_with_options(TestAppOptions {
+        secondary_addrs: format!("127.0.0.1:{}", global.port()),
+        ..TestAppOptions::default()
+    })
+    .await;
+    let pooled = app.zone_name("pooled.example");
+    let plain = app.zone_name("plain.example");
+    app.create_zone_cli(&pooled, "3600").await;
+    app.create_zone_cli(&plain, "3600").await;
+
+    let path = format!("/zones/{pooled}/notify-targets");
+    let pool_address = format!("127.0.0.1:{}", pool.port());
+    let also_address = format!("127.0.0.1:{}", also.port());
+    for body in [
+        json!({ "address": pool_address }),
+        json!({ "kind": "also-notify", "address": also_address }),
+    ] {
+        let (status, response) = app.request(Method::POST, &path, Some(body)).await;
+        assert_eq!(status, StatusCode::CREATED, "{response:#?}");
+    }
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/notify/zones",
+            Some(json!({ "zone_name": pooled })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(
+        (global.notifies(), pool.notifies(), also.notifies()),
+        (0, 1, 1)
+    );
+
+    // A zone without targets keeps notifying the global list.
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/notify/zones",
+            Some(json!({ "zone_name": plain })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        (global.notifies(), pool.notifies(), also.notifies()),
+        (1, 1, 1)
+    );
+
+    let (status, body) = app
+        .request(Method::GET, &format!("/zones/{pooled}/status"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let probed: Vec<&str> = body["secondaries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|secondary| secondary["address"].as_str().unwrap())
+        .collect();
+    assert_eq!(probed, [pool_address.as_str(), also_address.as_str()]);
+}
@@ -644,6 +644,21 @@ async fn record_create_supported_types() {
         ("@", "TXT", "v=spf1 include:_spf.google.com ~all", None),
         ("ipv6", "AAAA", "2001:db8::1", None),
         ("alias", "CNAME", "www.example.com", None),
+        ("@", "CAA", "0 ISSUE letsencrypt.org", None),
+        ("@", "HTTPS", "svc.example.com port=443 alpn=h2", Some(1)),
+        (
+            "_25._tcp.mail",
+            "TLSA",
+            "3 1 1 8CB0FC6C527506A053F4F14C8464BEBBD6DEDE2738D11468DD953D7D6A3021F1",
+            None,
+        ),
+        (
+            "host",
+            "SSHFP",
+            "4 1 123456789ABCDEF67890123456789ABCDEF67890",
+            None,
+        ),
+        ("opaque", "TYPE65280", r"\# 3 AB CDEF", None),
     ];
 
     for (name, record_type, value, priority) in record_types {
@@ -665,6 +680,11 @@ async fn record_create_supported_types() {
             "MX" => "mail.example.com.",
             "SRV" => "5 5060 sip.example.com.",
             "CNAME" => "www.example.com.",
+            "CAA" => "0 issue \"letsencrypt.org\"",
+            "HTTPS" => "svc.example.com. alpn=\"h2\" port=443",
+            "TLSA" => "3 1 1 8cb0fc6c527506a053f4f14c8464bebbd6dede2738d11468dd953d7d6a3021f1",
+            "SSHFP" => "4 1 123456789abcdef67890123456789abcdef67890",
+            "TYPE65280" => r"\# 3 abcdef",
             _ => value,
         };
         assert_eq!(body["record"]["value"], expected_value);
@@ -683,9 +703,20 @@ async fn record_create_supported_types() {
         .await;
     assert_eq!(status, StatusCode::OK);
     let records = body["items"].as_array().unwrap();
-    // 5 created here + the apex NS record auto-created with the zone.
-    assert_eq!(records.len(), 6);
-    for record_type in ["MX", "SRV", "TXT", "AAAA", "CNAME"] {
+    // 10 created here + the apex NS record auto-created with the zone.
+    assert_eq!(records.len(), 11);
+    for record_type in [
+        "MX",
+        "SRV",
+        "TXT",
+        "AAAA",
+        "CNAME",
+        "CAA",
+        "HTTPS",
+        "TLSA",
+        "SSHFP",
+        "TYPE65280",
+    ] {
         assert!(
             records
                 .iter()
@@ -695,6 +726,66 @@ async fn record_create_supported_types() {
     }
 }
 
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn record_create_known_types_in_generic_form() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+
+    // RFC 3597, Section 5: generic rdata for a known type is that type's record.
+    let cases = vec![
+        ("generic", "TYPE1", r"\# 4 c0000201", "A", "192.0.2.1", None),
+        (
+            "@",
+            "MX",
+            r"\# 16 0014 046d61696c076578616d706c6500",
+            "MX",
+            "mail.example.",
+            Some(20),
+        ),
+    ];
+    for (name, record_type, value, stored_type, stored_value, stored_priority) in cases {
+        let (status, body) = app
+            .request(
+                Method::POST,
+                "/records",
+                Some(json!({
+                    "name": name,
+                    "record_type": record_type,
+                    "value": value,
+                    "zone_name": zone["name"]
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{record_type} {value}: {body}");
+        assert_eq!(body["record"]["record_type"], stored_type);
+        assert_eq!(body["record"]["value"], stored_value);
+        assert_eq!(body["record"]["priority"], json!(stored_priority));
+    }
+
+    // The rdata still has to be valid for the type, and agree with a priority
+    // given alongside it.
+    for (record_type, value, priority) in [
+        ("TYPE1", r"\# 3 c00002", None),
+        ("MX", r"\# 16 0014 046d61696c076578616d706c6500", Some(10)),
+    ] {
+        let (status, _) = app
+            .request(
+                Method::POST,
+                "/records",
+                Some(json!({
+                    "name": "bad",
+                    "record_type": record_type,
+                    "value": value,
+                    "priority": priority,
+                    "zone_name": zone["name"]
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{record_type} {value}");
+    }
+}
+
 #[tokio::test]
 #[serial_test::serial(bindizr_e2e)]
 async fn record_reject_cname_conflicts() {
@@ -926,6 +1017,373 @@ async fn record_bulk_insert_dry_run_then_apply() {
     assert_eq!(body["items"].as_array().unwrap().len(), 2);
 }
 
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn record_changeset_applies_ordered_operations_with_one_serial_bump() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/records/bulk"),
+            Some(json!({
+                "records": [
+                    { "name": "www", "record_type": "A", "value": "192.0.2.1" },
+                    { "name": "alias", "record_type": "CNAME", "value": "www" }
+                ]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let www_id = body["records"][0]["id"].as_i64().unwrap();
+    let alias_id = body["records"][1]["id"].as_i64().unwrap();
+    let serial_before = app.zone_serial(zone_name).await;
+
+    // The add at `alias` is only valid because the CNAME is deleted first.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/records/changeset"),
+            Some(json!({
+                "operations": [
+                    { "op": "delete", "id": alias_id },
+                    { "op": "add", "record": { "name": "alias", "record_type": "A", "value": "192.0.2.2" } },
+                    { "op": "update", "id": www_id, "record": { "name": "www", "record_type": "A", "value": "192.0.2.3", "ttl": 600 } }
+                ]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["applied"], true);
+    assert_eq!(body["added"], 1);
+    assert_eq!(body["updated"], 1);
+    assert_eq!(body["deleted"], 1);
+    assert_eq!(body["records"].as_array().unwrap().len(), 2);
+    assert_eq!(app.zone_serial(zone_name).await, serial_before + 1);
+    assert_eq!(body["serial"], serial_before + 1);
+
+    let records = app.list_records(zone_name).await;
+    let mut rows: Vec<(String, String, String)> = records
+        .iter()
+        .filter(|r| r["record_type"] != "NS")
+        .map(|r| {
+            (
+                r["name"].as_str().unwrap().to_string(),
+                r["record_type"].as_str().unwrap().to_string(),
+                r["value"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            (
+                format!("alias.{zone_name}."),
+                "A".to_string(),
+                "192.0.2.2".to_string()
+            ),
+            (
+                format!("www.{zone_name}."),
+                "A".to_string(),
+                "192.0.2.3".to_string()
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn record_changeset_is_all_or_nothing() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/records/bulk"),
+            Some(json!({
+                "records": [ { "name": "www", "record_type": "A", "value": "192.0.2.1" } ]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let www_id = body["records"][0]["id"].as_i64().unwrap();
+    let serial_before = app.zone_serial(zone_name).await;
+
+    // A later operation conflicting with an earlier one fails the whole set.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/records/changeset"),
+            Some(json!({
+                "operations": [
+                    { "op": "delete", "id": www_id },
+                    { "op": "add", "record": { "name": "ftp", "record_type": "A", "value": "192.0.2.9" } },
+                    { "op": "add", "record": { "name": "ftp", "record_type": "CNAME", "value": "www" } }
+                ]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["code"], "RECORD_CONFLICT");
+    assert!(
+        body["error"].as_str().unwrap().starts_with("operation 3: "),
+        "{body}"
+    );
+
+    // So does an id that is not in the zone.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/records/changeset"),
+            Some(json!({
+                "operations": [
+                    { "op": "delete", "id": www_id },
+                    { "op": "update", "id": 999999, "record": { "name": "x", "record_type": "A", "value": "192.0.2.7" } }
+                ]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "RECORD_NOT_FOUND");
+
+    // Deleting a record twice is caught on the second delete.
+    let (status, _) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/records/changeset"),
+            Some(json!({
+                "operations": [
+                    { "op": "delete", "id": www_id },
+                    { "op": "delete", "id": www_id }
+                ]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let records = app.list_records(zone_name).await;
+    assert!(records.iter().any(|r| r["id"] == www_id));
+    assert!(
+        records
+            .iter()
+            .all(|r| !r["name"].as_str().unwrap().starts_with("ftp."))
+    );
+    assert_eq!(app.zone_serial(zone_name).await, serial_before);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn record_changeset_dry_run_returns_the_diff_without_applying() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/records/bulk"),
+            Some(json!({
+                "records": [ { "name": "www", "record_type": "A", "value": "192.0.2.1" } ]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let www_id = body["records"][0]["id"].as_i64().unwrap();
+    let serial_before = app.zone_serial(zone_name).await;
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/records/changeset"),
+            Some(json!({
+                "operations": [
+                    { "op": "update", "id": www_id, "record": { "name": "www", "record_type": "A", "value": "192.0.2.2" } },
+                    { "op": "add", "record": { "name": "mail", "record_type": "A", "value": "192.0.2.3" } }
+                ],
+                "dry_run": true
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["applied"], false);
+    assert_eq!(body["dry_run"], true);
+    assert_eq!(body["added"], 1);
+    assert_eq!(body["updated"], 1);
+    assert_eq!(body["serial"], serial_before);
+    assert_eq!(body["diff"]["summary"]["added"], 1);
+    assert_eq!(body["diff"]["summary"]["changed"], 1);
+
+    assert_eq!(app.zone_serial(zone_name).await, serial_before);
+    let records = app.list_records(zone_name).await;
+    let www = records.iter().find(|r| r["id"] == www_id).unwrap();
+    assert_eq!(www["value"], "192.0.2.1");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn rrset_replace_read_and_delete() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+    let path = format!("/zones/{zone_name}/rrsets/@/MX");
+
+    let (status, body) = app.request(Method::GET, &path, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "RECORD_NOT_FOUND");
+
+    let serial_before = app.zone_serial(zone_name).await;
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &path,
+            Some(json!({
+                "ttl": 600,
+                "values": [format!("20 mx2.{zone_name}"), format!("10 MX1.{zone_name}.")]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["name"], format!("{zone_name}."));
+    assert_eq!(body["record_type"], "MX");
+    assert_eq!(body["ttl"], 600);
+    assert_eq!(
+        body["values"],
+        json!([
+            format!("10 mx1.{zone_name}."),
+            format!("20 mx2.{zone_name}.")
+        ])
+    );
+    assert_eq!(app.zone_serial(zone_name).await, serial_before + 1);
+
+    let records = app.list_records(zone_name).await;
+    let mut mx: Vec<(i64, i64)> = records
+        .iter()
+        .filter(|r| r["record_type"] == "MX")
+        .map(|r| (r["priority"].as_i64().unwrap(), r["ttl"].as_i64().unwrap()))
+        .collect();
+    mx.sort();
+    assert_eq!(mx, vec![(10, 600), (20, 600)]);
+
+    // Replacing the set with what it already holds is a no-op.
+    let (status, _) = app
+        .request(
+            Method::PUT,
+            &path,
+            Some(json!({
+                "ttl": 600,
+                "values": [format!("10 mx1.{zone_name}."), format!("20 mx2.{zone_name}.")]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(app.zone_serial(zone_name).await, serial_before + 1);
+
+    // A new TTL and a dropped value land as one change.
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &path,
+            Some(json!({ "ttl": 300, "values": [format!("10 mx1.{zone_name}.")] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["ttl"], 300);
+    assert_eq!(body["values"], json!([format!("10 mx1.{zone_name}.")]));
+    assert_eq!(app.zone_serial(zone_name).await, serial_before + 2);
+
+    let (status, body) = app.request(Method::GET, &path, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["ttl"], 300);
+
+    let (status, _) = app.request(Method::DELETE, &path, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(app.zone_serial(zone_name).await, serial_before + 3);
+    let (status, _) = app.request(Method::GET, &path, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = app.request(Method::DELETE, &path, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn rrset_replace_rejects_invalid_sets() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    let (status, _) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{zone_name}/rrsets/www/A"),
+            Some(json!({ "values": ["192.0.2.1"] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let serial_before = app.zone_serial(zone_name).await;
+
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{zone_name}/rrsets/www/CNAME"),
+            Some(json!({ "values": ["target"] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+    assert_eq!(body["code"], "RECORD_CONFLICT");
+
+    let (status, _) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{zone_name}/rrsets/alias/CNAME"),
+            Some(json!({ "values": ["a", "b"] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{zone_name}/rrsets/www/A"),
+            Some(json!({ "values": ["not-an-ip"] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{zone_name}/rrsets/@/SOA"),
+            Some(json!({ "values": ["x"] })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // The zone's own NS set has to keep the primary nameserver.
+    let (status, body) = app
+        .request(
+            Method::DELETE,
+            &format!("/zones/{zone_name}/rrsets/@/NS"),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+
+    assert_eq!(app.zone_serial(zone_name).await, serial_before);
+    let (_, body) = app
+        .request(
+            Method::GET,
+            &format!("/zones/{zone_name}/rrsets/www/A"),
+            None,
+        )
+        .await;
+    assert_eq!(body["values"], json!(["192.0.2.1"]));
+}
+
 // Rows hold one canonical spelling, so the filter has to reach it however the
 // request spells the name.
 #[tokio::test]
@@ -1129,3 +1587,116 @@ async fn search_treats_like_wildcards_as_literal_text() {
     assert_eq!(search(&app, zone_name, "%").await, ["pct"]);
     assert_eq!(search(&app, zone_name, "_").await, ["under"]);
 }
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn record_and_rrset_writes_honor_etag_preconditions() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    let (status, headers, body) = app
+        .request_with_headers(
+            Method::POST,
+            "/records",
+            &[("if-match", "\"10\"")],
+            Some(json!({
+                "name": "www", "record_type": "A", "value": "192.0.2.10",
+                "ttl": 300, "zone_name": zone_name
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    let record_id = body["record"]["id"].as_i64().unwrap();
+    let record_etag = headers["etag"].to_str().unwrap().to_string();
+    let record_path = format!("/records/{record_id}");
+
+    // A record write accepts the record's own tag even after the zone moves on.
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/records",
+            Some(json!({
+                "name": "mail", "record_type": "A", "value": "192.0.2.11",
+                "ttl": 300, "zone_name": zone_name
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let update = json!({
+        "name": "www", "record_type": "A", "value": "192.0.2.12", "ttl": 300
+    });
+    let (status, _, body) = app
+        .request_with_headers(
+            Method::PUT,
+            &record_path,
+            &[("if-match", "\"10\"")],
+            Some(update.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains(&format!("record '{record_id}' has changed")),
+        "{body}"
+    );
+
+    let (status, headers, _) = app
+        .request_with_headers(
+            Method::PUT,
+            &record_path,
+            &[("if-match", &record_etag)],
+            Some(update),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let updated_etag = headers["etag"].to_str().unwrap().to_string();
+    assert_ne!(updated_etag, record_etag);
+
+    let (status, headers, _) = app
+        .request_with_headers(Method::GET, &record_path, &[], None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["etag"], updated_etag.as_str());
+
+    let (status, _, _) = app
+        .request_with_headers(
+            Method::DELETE,
+            &record_path,
+            &[("if-match", &record_etag)],
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    // `If-None-Match: *` only creates an RRset that does not exist yet.
+    let rrset_path = format!("/zones/{zone_name}/rrsets/api/A");
+    let rrset = json!({ "ttl": 300, "values": ["192.0.2.20"] });
+    let (status, headers, body) = app
+        .request_with_headers(
+            Method::PUT,
+            &rrset_path,
+            &[("if-none-match", "*")],
+            Some(rrset.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(
+        headers["etag"],
+        format!("\"{}\"", app.zone_serial(zone_name).await).as_str()
+    );
+
+    let (status, _, body) = app
+        .request_with_headers(
+            Method::PUT,
+            &rrset_path,
+            &[("if-none-match", "*")],
+            Some(rrset),
+        )
+        .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert!(body["error"].as_str().unwrap().contains("the RRset exists"));
+}
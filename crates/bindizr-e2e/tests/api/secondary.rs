@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+
+use crate::common::{TestApp, TestAppOptions, secondary::StandInSecondary};
+
+/// Poll `/secondaries/status` until `done` holds, for up to ten seconds.
+async fn wait_for_status(app: &TestApp, done: impl Fn(&Value) -> bool) -> Value {
+    for _ in 0..50 {
+        let (status, body) = app.request(Method::GET, "/secondaries/status", None).await;
+        assert_eq!(status, StatusCode::OK, "{body:#?}");
+        if done(&body) {
+            return body;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    panic!("sync monitor never reached the expected state");
+}
+
+fn pair<'a>(body: &'a Value, secondary: &str) -> &'a Value {
+    body["secondaries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|pair| pair["secondary"] == secondary)
+        .unwrap_or_else(|| panic!("no entry for {secondary}: {body:#?}"))
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn sync_monitor_tracks_lag_and_renotifies() {
+    let stand_in = StandInSecondary::spawn(0);
+    let reachable = format!("127.0.0.1:{}", stand_in.port());
+    // The discard port: every probe fails.
+    let unreachable = "127.0.0.1:9";
+    let app = TestApp::start_with_options(TestAppOptions {
+        secondary_addrs: format!("{reachable}, {unreachable}"),
+        sync_monitor_interval_secs: 1,
+        sync_renotify_after_secs: 1,
+        ..TestAppOptions::default()
+    })
+    .await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap().to_string();
+    let serial = zone["serial"].as_u64().unwrap() as u32;
+    stand_in.set_serial(serial - 2);
+
+    let body = wait_for_status(&app, |body| {
+        body["secondaries"].as_array().unwrap().iter().any(|pair| {
+            pair["secondary"] == reachable
+                && pair["status"] == "lagging"
+                && !pair["last_notified_at"].is_null()
+        }) && pair(body, unreachable)["consecutive_failures"].as_u64() >= Some(2)
+    })
+    .await;
+    assert_eq!(body["interval_secs"], 1);
+    assert!(!body["last_round_at"].is_null());
+
+    let lagging = pair(&body, &reachable);
+    assert_eq!(lagging["zone"], zone_name);
+    assert_eq!(lagging["serial"].as_u64(), Some(u64::from(serial)));
+    assert_eq!(
+        lagging["visible_serial"].as_u64(),
+        Some(u64::from(serial - 2))
+    );
+    assert_eq!(lagging["lag"], 2);
+    assert!(!lagging["lagging_since"].is_null());
+    assert!(stand_in.notifies() >= 1);
+
+    let failing = pair(&body, unreachable);
+    assert_eq!(failing["status"], "unreachable");
+    assert!(failing["visible_serial"].is_null());
+    assert!(failing["error"].is_string());
+
+    let (_, metrics) = app.request(Method::GET, "/metrics", None).await;
+    let text = metrics.as_str().expect("metrics body is prometheus text");
+    assert!(text.contains(&format!(
+        r#"bindizr_secondary_serial_lag{{secondary="{reachable}",zone="{zone_name}"}} 2"#
+    )));
+    assert!(text.contains(&format!(
+        r#"bindizr_secondary_probe_failures_total{{secondary="{unreachable}",zone="{zone_name}"}}"#
+    )));
+
+    // The transfer lands: the next round clears the lag.
+    stand_in.set_serial(serial);
+    let body = wait_for_status(&app, |body| pair(body, &reachable)["status"] == "in_sync").await;
+    let caught_up = pair(&body, &reachable);
+    assert_eq!(caught_up["lag"], 0);
+    assert!(caught_up["lagging_since"].is_null());
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn secondaries_status_is_empty_while_the_monitor_is_off() {
+    let app = TestApp::start_with_options(TestAppOptions::default()).await;
+
+    let (status, body) = app.request(Method::GET, "/secondaries/status", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["interval_secs"], 0);
+    assert!(body["last_round_at"].is_null());
+    assert_eq!(body["secondaries"].as_array().unwrap().len(), 0);
+
+    let output = app.run_cli_success(&["secondary", "status"]).await;
+    assert!(output.contains("disabled"), "{output}");
+}
@@ -0,0 +1,214 @@
+use reqwest::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use crate::common::TestApp;
+
+fn template_records() -> Value {
+    json!([
+        { "name": "@", "record_type": "MX", "value": "{{mail_host}}.{{zone}}.", "priority": 10 },
+        { "name": "@", "record_type": "TXT", "value": "v=spf1 mx -all", "ttl": 300 }
+    ])
+}
+
+fn zone_request(zone_name: &str, template: &str, variables: Value) -> Value {
+    json!({
+        "name": zone_name,
+        "primary_ns": format!("ns1.{zone_name}"),
+        "admin_email": "admin@example.com",
+        "ttl": 3600,
+        "template": template,
+        "template_variables": variables
+    })
+}
+
+fn has_record(records: &[Value], record_type: &str, value: &str, ttl: i64) -> bool {
+    records.iter().any(|record| {
+        record["record_type"] == record_type && record["value"] == value && record["ttl"] == ttl
+    })
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn template_provisions_a_zone_and_applies_later_changes() {
+    let app = TestApp::start().await;
+    let template = format!("{}-baseline", app.namespace());
+    let zone_name = app.zone_name("templated.example");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/templates",
+            Some(json!({ "name": template, "records": template_records() })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    assert_eq!(body["template"]["variables"], json!(["mail_host"]));
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/zones",
+            Some(zone_request(
+                &zone_name,
+                &template,
+                json!({ "mail_host": "mx1" }),
+            )),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    let records = app.list_records(&zone_name).await;
+    assert!(
+        has_record(&records, "MX", &format!("mx1.{zone_name}."), 3600),
+        "{records:#?}"
+    );
+    assert!(
+        has_record(&records, "TXT", "v=spf1 mx -all", 300),
+        "{records:#?}"
+    );
+
+    let (status, body) = app
+        .request(Method::GET, &format!("/templates/{template}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["template"]["zones"], json!([zone_name]));
+
+    // Saving the change only previews it on the linked zone.
+    let serial = app.zone_serial(&zone_name).await;
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/templates/{template}"),
+            Some(json!({
+                "records": [
+                    { "name": "@", "record_type": "MX", "value": "{{mail_host}}.{{zone}}.", "priority": 10 },
+                    { "name": "@", "record_type": "TXT", "value": "v=spf1 mx -all", "ttl": 600 },
+                    { "name": "@", "record_type": "CAA", "value": "0 issue \"letsencrypt.org\"" }
+                ]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["zones"][0]["zone"], zone_name);
+    assert_eq!(body["zones"][0]["status"], "pending");
+    assert_eq!(body["zones"][0]["diff"]["summary"]["added"], 1);
+    assert_eq!(body["zones"][0]["diff"]["summary"]["changed"], 1);
+    assert_eq!(app.zone_serial(&zone_name).await, serial);
+
+    let apply = format!("/templates/{template}/apply");
+    let (status, body) = app
+        .request(Method::POST, &apply, Some(json!({ "dry_run": true })))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["zones"][0]["status"], "pending");
+    assert_eq!(app.zone_serial(&zone_name).await, serial);
+
+    let (status, body) = app.request(Method::POST, &apply, Some(json!({}))).await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["zones"][0]["status"], "applied");
+    assert!(app.zone_serial(&zone_name).await > serial);
+    let records = app.list_records(&zone_name).await;
+    assert!(
+        has_record(&records, "TXT", "v=spf1 mx -all", 600),
+        "{records:#?}"
+    );
+    assert!(
+        records.iter().any(|record| record["record_type"] == "CAA"),
+        "{records:#?}"
+    );
+
+    let (status, body) = app.request(Method::POST, &apply, Some(json!({}))).await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["zones"][0]["status"], "in_sync");
+
+    // The zone has no value for a variable the template did not use before.
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/templates/{template}"),
+            Some(json!({
+                "records": [{ "name": "@", "record_type": "A", "value": "{{web_ip}}" }]
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body:#?}");
+    assert!(
+        body["error"].as_str().unwrap().contains("web_ip"),
+        "{body:#?}"
+    );
+
+    let (status, body) = app
+        .request(Method::DELETE, &format!("/templates/{template}"), None)
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body:#?}");
+    assert_eq!(body["code"], "TEMPLATE_IN_USE");
+
+    let (status, _) = app
+        .request(Method::DELETE, &format!("/zones/{zone_name}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = app
+        .request(Method::DELETE, &format!("/templates/{template}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_creation_rejects_bad_template_references() {
+    let app = TestApp::start().await;
+    let template = format!("{}-strict", app.namespace());
+    let zone_name = app.zone_name("template-invalid.example");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/templates",
+            Some(json!({ "name": template, "records": template_records() })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/zones",
+            Some(zone_request(
+                &zone_name,
+                &format!("{}-missing", app.namespace()),
+                json!({}),
+            )),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body:#?}");
+    assert_eq!(body["code"], "TEMPLATE_NOT_FOUND");
+
+    for variables in [json!({}), json!({ "mail_host": "mx1", "extra": "x" })] {
+        let (status, body) = app
+            .request(
+                Method::POST,
+                "/zones",
+                Some(zone_request(&zone_name, &template, variables)),
+            )
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body:#?}");
+    }
+
+    // The zone was never created, so nothing links to the template.
+    let (status, _) = app
+        .request(Method::GET, &format!("/zones/{zone_name}"), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/templates",
+            Some(json!({ "name": template, "records": template_records() })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body:#?}");
+    let (status, _) = app
+        .request(Method::DELETE, &format!("/templates/{template}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,286 @@
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+use crate::common::{TestApp, TestAppOptions};
+
+fn authed_options() -> TestAppOptions {
+    TestAppOptions {
+        require_authentication: true,
+        ..TestAppOptions::default()
+    }
+}
+
+/// Status of an authenticated `GET /zones` made with `token`.
+async fn status_with_token(app: &TestApp, token: &str) -> StatusCode {
+    reqwest::Client::new()
+        .get(format!("{}/zones", app.base_url()))
+        .bearer_auth(token)
+        .send()
+        .await
+        .expect("failed to send HTTP request")
+        .status()
+}
+
+/// Status of a request made with `token` instead of the app's own.
+async fn status_as(
+    app: &TestApp,
+    token: &str,
+    method: Method,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> StatusCode {
+    let mut request = reqwest::Client::new()
+        .request(method, format!("{}{}", app.base_url(), path))
+        .bearer_auth(token);
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    request
+        .send()
+        .await
+        .expect("failed to send HTTP request")
+        .status()
+}
+
+/// Create a token with `role` and return its secret.
+async fn create_role_token(app: &TestApp, role: &str) -> String {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/tokens",
+            Some(json!({ "name": format!("{}-{role}", app.namespace()), "role": role })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["token"]["role"], role);
+    body["token"]["token"].as_str().unwrap().to_string()
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn token_create_list_rotate_and_revoke_via_api() {
+    let mut app = TestApp::start_with_options(authed_options()).await;
+    let (_, global_token) = app.create_api_token().await;
+    app.set_auth_token(global_token);
+    let name = format!("{}-deploy", app.namespace());
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/tokens",
+            Some(json!({ "name": name, "description": "deploys", "expires_in_days": 30 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body}");
+    assert_eq!(body["token"]["name"], name);
+    assert_eq!(body["token"]["global"], false);
+    let first_secret = body["token"]["token"].as_str().unwrap().to_string();
+    assert_eq!(status_with_token(&app, &first_secret).await, StatusCode::OK);
+
+    let (status, body) = app
+        .request(Method::POST, "/tokens", Some(json!({ "name": name })))
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body}");
+
+    let (status, body) = app.request(Method::GET, "/tokens", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed = body["tokens"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|token| token["name"] == name.as_str())
+        .expect("created token is listed");
+    assert!(
+        listed.get("token").is_none(),
+        "list must not expose secrets"
+    );
+    assert_eq!(listed["description"], "deploys");
+
+    // Both secrets work during the overlap.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/tokens/{name}/rotate"),
+            Some(json!({ "overlap_minutes": 30 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let second_secret = body["token"]["token"].as_str().unwrap().to_string();
+    assert_ne!(second_secret, first_secret);
+    assert!(body["token"]["previous_token_expires_at"].is_string());
+    assert_eq!(status_with_token(&app, &first_secret).await, StatusCode::OK);
+    assert_eq!(
+        status_with_token(&app, &second_secret).await,
+        StatusCode::OK
+    );
+
+    // Rotating without an overlap retires every earlier secret at once.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/tokens/{name}/rotate"),
+            Some(json!({ "overlap_minutes": 0 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let third_secret = body["token"]["token"].as_str().unwrap().to_string();
+    assert!(body["token"].get("previous_token_expires_at").is_none());
+    assert_eq!(
+        status_with_token(&app, &first_secret).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        status_with_token(&app, &second_secret).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(status_with_token(&app, &third_secret).await, StatusCode::OK);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/tokens/{name}/rotate"),
+            Some(json!({ "overlap_minutes": -5 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body}");
+
+    let (status, _) = app
+        .request(Method::DELETE, &format!("/tokens/{name}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        status_with_token(&app, &third_secret).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/tokens/{name}/rotate"),
+            Some(json!({})),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "TOKEN_NOT_FOUND");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn token_endpoints_require_a_global_token() {
+    let mut app = TestApp::start_with_options(authed_options()).await;
+    let (scoped_name, scoped_token) = app.create_scoped_api_token().await;
+    app.set_auth_token(scoped_token);
+
+    let (status, _) = app.request(Method::GET, "/tokens", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Not even its own secret: rotation is a token-management right.
+    let (status, _) = app
+        .request(
+            Method::POST,
+            &format!("/tokens/{scoped_name}/rotate"),
+            Some(json!({})),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/tokens",
+            Some(json!({ "name": format!("{scoped_name}-escalated"), "global": true })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn token_roles_grant_least_privilege() {
+    let mut app = TestApp::start_with_options(authed_options()).await;
+    let (_, global_token) = app.create_api_token().await;
+    app.set_auth_token(global_token);
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    let viewer = create_role_token(&app, "viewer").await;
+    let editor = create_role_token(&app, "record-editor").await;
+    let zone_admin = create_role_token(&app, "zone-admin").await;
+
+    let record = |value: &str| {
+        Some(json!({
+            "name": "www", "record_type": "A", "value": value,
+            "ttl": 300, "zone_name": zone_name
+        }))
+    };
+    let new_zone = |base: &str| {
+        let name = app.zone_name(base);
+        Some(json!({
+            "name": name,
+            "primary_ns": format!("ns1.{name}"),
+            "admin_email": "admin@example.com",
+            "ttl": 3600
+        }))
+    };
+    let zone_path = format!("/zones/{zone_name}");
+
+    // A viewer reads every zone and its history, and writes nothing.
+    for path in [&zone_path, "/zones", &format!("{zone_path}/snapshots")] {
+        let status = status_as(&app, &viewer, Method::GET, path, None).await;
+        assert_eq!(status, StatusCode::OK, "viewer GET {path}");
+    }
+    let status = status_as(&app, &viewer, Method::POST, "/records", record("192.0.2.1")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // A record editor writes records in any zone, but not zones.
+    let status = status_as(&app, &editor, Method::POST, "/records", record("192.0.2.2")).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let status = status_as(
+        &app,
+        &editor,
+        Method::POST,
+        "/zones",
+        new_zone("editor.com"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // A zone admin manages zones, but not tokens.
+    let status = status_as(
+        &app,
+        &zone_admin,
+        Method::POST,
+        "/zones",
+        new_zone("admin.com"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let status = status_as(&app, &zone_admin, Method::DELETE, &zone_path, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let status = status_as(&app, &zone_admin, Method::GET, "/tokens", None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Roles span every zone, so policies are only for scoped tokens.
+    let other_zone = app.create_test_zone().await;
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!(
+                "/zones/{}/token-policies",
+                other_zone["name"].as_str().unwrap()
+            ),
+            Some(json!({ "api_token": format!("{}-viewer", app.namespace()) })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("'viewer' role"));
+
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/tokens",
+            Some(json!({ "name": format!("{}-bad", app.namespace()), "role": "root" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,80 @@
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+use crate::common::TestApp;
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_transfer_acl_create_list_delete() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("transfer-acl.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let path = format!("/zones/{zone_name}/transfer-acls");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &path,
+            Some(json!({ "address": "198.51.100.0/24" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    assert_eq!(body["transfer_acl"]["action"], "allow");
+    assert_eq!(body["transfer_acl"]["address"], "198.51.100.0/24");
+    assert!(body["transfer_acl"]["tsig_key"].is_null());
+    let id = body["transfer_acl"]["id"].as_i64().unwrap();
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &path,
+            Some(json!({ "action": "deny", "address": "198.51.100.66" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+
+    let (status, body) = app.request(Method::GET, &path, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let entries = body["transfer_acls"].as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1]["action"], "deny");
+
+    let (status, _) = app
+        .request(Method::DELETE, &format!("{path}/{id}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = app
+        .request(Method::DELETE, &format!("{path}/{id}"), None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "TRANSFER_ACL_NOT_FOUND");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_transfer_acl_rejects_invalid_entries() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("transfer-acl-invalid.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let path = format!("/zones/{zone_name}/transfer-acls");
+
+    for entry in [
+        json!({ "address": "secondary.example" }),
+        json!({ "address": "192.0.2.10/24" }),
+        json!({ "action": "maybe", "address": "192.0.2.10" }),
+        json!({ "action": "deny", "address": "192.0.2.10", "tsig_key": "xfr-key" }),
+    ] {
+        let (status, body) = app.request(Method::POST, &path, Some(entry)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{body:#?}");
+    }
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &path,
+            Some(json!({ "address": "192.0.2.10", "tsig_key": "missing-key" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body:#?}");
+}
@@ -0,0 +1,333 @@
+use std::{collections::HashMap, time::Duration};
+
+use hmac::{Hmac, KeyInit, Mac};
+use reqwest::{Method, StatusCode};
+use serde_json::{Value, json};
+use sha2::Sha256;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::common::{TestApp, TestAppOptions};
+
+/// One HTTP request as the webhook receiver saw it; header names lowercased.
+struct ReceivedRequest {
+    headers: HashMap<String, String>,
+    body: String,
+}
+
+/// Accept one connection on `listener`, answer it 200, and return the request.
+async fn receive_one(listener: &TcpListener) -> ReceivedRequest {
+    let (stream, request) = accept_one(listener).await;
+    answer_ok(stream).await;
+    request
+}
+
+/// Accept one connection on `listener` and read its request, leaving the
+/// sender waiting for an answer.
+async fn accept_one(listener: &TcpListener) -> (TcpStream, ReceivedRequest) {
+    let (mut stream, _) = tokio::time::timeout(Duration::from_secs(15), listener.accept())
+        .await
+        .expect("no webhook delivery arrived")
+        .expect("failed to accept webhook connection");
+
+    let mut buf = Vec::new();
+    let (head_len, content_length) = loop {
+        let mut chunk = [0u8; 4096];
+        let n = stream
+            .read(&mut chunk)
+            .await
+            .expect("failed to read request");
+        assert!(n > 0, "connection closed before the request was complete");
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&buf[..pos]).to_ascii_lowercase();
+            let content_length = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map_or(0, |value| value.trim().parse::<usize>().unwrap());
+            break (pos + 4, content_length);
+        }
+    };
+    while buf.len() < head_len + content_length {
+        let mut chunk = [0u8; 4096];
+        let n = stream.read(&mut chunk).await.expect("failed to read body");
+        assert!(n > 0, "connection closed before the body was complete");
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&buf[..head_len]).to_string();
+    let headers = head
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let body = String::from_utf8(buf[head_len..head_len + content_length].to_vec()).unwrap();
+    (stream, ReceivedRequest { headers, body })
+}
+
+/// Answer a request taken with [`accept_one`] with 200.
+async fn answer_ok(mut stream: TcpStream) {
+    stream
+        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+        .await
+        .expect("failed to answer webhook");
+}
+
+/// Poll the webhook's newest delivery until `done` accepts it.
+async fn wait_for_delivery(app: &TestApp, webhook: &str, done: impl Fn(&Value) -> bool) -> Value {
+    let path = format!("/webhooks/{webhook}/deliveries?limit=1");
+    for _ in 0..100 {
+        let (status, body) = app.request(Method::GET, &path, None).await;
+        assert_eq!(status, StatusCode::OK, "{body:#?}");
+        if let Some(delivery) = body["deliveries"].get(0)
+            && done(delivery)
+        {
+            return delivery.clone();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("webhook '{webhook}' delivery never reached the expected state");
+}
+
+// The receiver listens on the test host's loopback, which only a locally
+// spawned daemon can reach.
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn record_change_posts_a_signed_diff_to_the_zone_webhook() {
+    let app = TestApp::start_with_options(TestAppOptions::default()).await;
+    let zone_name = app.zone_name("webhook.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/webhooks",
+            Some(json!({
+                "name": "zone-sink",
+                "url": url,
+                "zone": zone_name,
+                "secret": "s3cret-key"
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    assert_eq!(body["webhook"]["zone"], zone_name);
+    assert_eq!(body["webhook"]["secret"], "s3cret-key");
+
+    let previous_serial = app.zone_serial(&zone_name).await;
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/records",
+            Some(json!({
+                "name": "www",
+                "record_type": "A",
+                "value": "192.0.2.80",
+                "ttl": 300,
+                "zone_name": zone_name
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+
+    let request = receive_one(&listener).await;
+    assert_eq!(request.headers["x-bindizr-event"], "zone.updated");
+    assert_eq!(request.headers["content-type"], "application/json");
+
+    let timestamp = &request.headers["x-bindizr-timestamp"];
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret-key").unwrap();
+    mac.update(format!("{timestamp}.{}", request.body).as_bytes());
+    assert_eq!(
+        request.headers["x-bindizr-signature"],
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    );
+
+    let payload: Value = serde_json::from_str(&request.body).unwrap();
+    assert_eq!(payload["event"], "zone.updated");
+    assert_eq!(payload["webhook"], "zone-sink");
+    assert_eq!(payload["zone"], zone_name);
+    assert_eq!(payload["previous_serial"], previous_serial);
+    assert_eq!(payload["serial"], app.zone_serial(&zone_name).await);
+    assert_eq!(
+        payload["delivery_id"].to_string(),
+        request.headers["x-bindizr-delivery"]
+    );
+    assert_eq!(payload["diff"]["summary"]["added"], 1, "{payload:#?}");
+
+    let delivery = wait_for_delivery(&app, "zone-sink", |d| d["status"] != "pending").await;
+    assert_eq!(delivery["status"], "delivered", "{delivery:#?}");
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["event"], "zone.updated");
+
+    // Listing never shows the secret again.
+    let (status, body) = app.request(Method::GET, "/webhooks", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["webhooks"][0]["name"], "zone-sink");
+    assert!(body["webhooks"][0].get("secret").is_none(), "{body:#?}");
+
+    let (status, _) = app
+        .request(Method::DELETE, "/webhooks/zone-sink", None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = app
+        .request(Method::GET, "/webhooks/zone-sink/deliveries", None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "WEBHOOK_NOT_FOUND");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn unreachable_webhook_keeps_the_delivery_queued_for_retry() {
+    let app = TestApp::start_with_options(TestAppOptions::default()).await;
+    // Bind and release a port so nothing is listening on it.
+    let closed_addr = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/webhooks",
+            Some(json!({ "name": "down-sink", "url": format!("http://{closed_addr}/") })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    assert!(body["webhook"].get("zone").is_none());
+    assert_eq!(
+        body["webhook"]["secret"].as_str().map(str::len),
+        Some(32),
+        "a secret is generated when none is given"
+    );
+
+    let zone_name = app.zone_name("webhook-retry.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    let delivery = wait_for_delivery(&app, "down-sink", |d| d["attempts"] == 1).await;
+    assert_eq!(delivery["event"], "zone.created");
+    assert_eq!(delivery["zone"], zone_name);
+    assert_eq!(delivery["status"], "pending");
+    assert!(delivery.get("previous_serial").is_none());
+    assert!(delivery["last_error"].is_string(), "{delivery:#?}");
+    assert!(delivery["delivered_at"].is_null());
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/webhooks",
+            Some(json!({ "name": "down-sink", "url": "ftp://example.com/" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{body:#?}");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/webhooks",
+            Some(json!({ "name": "Down-Sink", "url": "https://example.com/" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT, "{body:#?}");
+    assert_eq!(body["code"], "WEBHOOK_CONFLICT");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn stalled_webhook_does_not_hold_up_the_others() {
+    let app = TestApp::start_with_options(TestAppOptions::default()).await;
+    // Never accepted: connections complete in the backlog and then hang until
+    // the delivery timeout.
+    let stalled = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let live = TcpListener::bind("127.0.0.1:0").await.unwrap();
+
+    // Queued first, so a sequential worker would wait on it before the other.
+    for (name, listener) in [("stalled-sink", &stalled), ("live-sink", &live)] {
+        let (status, body) = app
+            .request(
+                Method::POST,
+                "/webhooks",
+                Some(json!({
+                    "name": name,
+                    "url": format!("http://{}/hook", listener.local_addr().unwrap())
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    }
+
+    let zone_name = app.zone_name("webhook-stalled.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    // Well inside the 10s delivery timeout the stalled endpoint is waiting out.
+    let request = tokio::time::timeout(Duration::from_secs(5), receive_one(&live))
+        .await
+        .expect("the live webhook waited on the stalled one");
+    assert_eq!(request.headers["x-bindizr-event"], "zone.created");
+    drop(stalled);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn update_payload_keeps_its_diff_after_the_zone_is_deleted() {
+    let app = TestApp::start_with_options(TestAppOptions::default()).await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/webhooks",
+            Some(json!({
+                "name": "history-sink",
+                "url": format!("http://{}/hook", listener.local_addr().unwrap())
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+
+    let zone_name = app.zone_name("webhook-deleted.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    // Hold the zone.created delivery open so the update is not attempted
+    // until its zone, and the history its diff comes from, are gone.
+    let (created_stream, created) = accept_one(&listener).await;
+    assert_eq!(created.headers["x-bindizr-event"], "zone.created");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/records",
+            Some(json!({
+                "name": "www",
+                "record_type": "A",
+                "value": "192.0.2.81",
+                "ttl": 300,
+                "zone_name": zone_name
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    let (status, body) = app
+        .request(Method::DELETE, &format!("/zones/{zone_name}"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    answer_ok(created_stream).await;
+
+    let updated = receive_one(&listener).await;
+    assert_eq!(updated.headers["x-bindizr-event"], "zone.updated");
+    let payload: Value = serde_json::from_str(&updated.body).unwrap();
+    assert_eq!(payload["webhook"], "history-sink");
+    assert_eq!(
+        payload["delivery_id"].to_string(),
+        updated.headers["x-bindizr-delivery"]
+    );
+    assert_eq!(payload["diff"]["summary"]["added"], 1, "{payload:#?}");
+
+    let deleted = receive_one(&listener).await;
+    assert_eq!(deleted.headers["x-bindizr-event"], "zone.deleted");
+}
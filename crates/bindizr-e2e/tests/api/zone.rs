@@ -1,7 +1,17 @@
+use std::{net::Ipv4Addr, str::FromStr};
+
+use domain::{
+    base::Name,
+    rdata::{A, Mx, Ns, Txt},
+};
 use reqwest::{Method, StatusCode};
 use serde_json::{Value, json};
 
-use crate::common::TestApp;
+use crate::common::{
+    TestApp, TestAppOptions,
+    nsupdate::{SigningKey, create_key},
+    xfr::{PrimaryRecord, spawn_primary},
+};
 
 /// Seed records directly in the DB via the bulk endpoint.
 async fn seed_records(app: &TestApp, zone_name: &str, records: Value) {
@@ -378,9 +388,8 @@ async fn zone_seed_and_reject_out_of_range_serial() {
     assert_eq!(status, StatusCode::OK);
     assert_eq!(body["zone"]["serial"], 2026072502i64);
 
-    // Past MAX_INITIAL_SERIAL (i32::MAX - 10_000_000) the counter would
-    // saturate while the zone is still in use.
-    for out_of_range_serial in [0i64, -1, 2_137_483_648, i32::MAX as i64] {
+    // Any 32-bit serial but 0 is a valid starting point; the counter wraps.
+    for out_of_range_serial in [0i64, -1, u32::MAX as i64 + 1] {
         let out_of_range_zone = json!({
             "name": app.zone_name("out-of-range-serial.example.com"),
             "primary_ns": "ns1.example.com",
@@ -395,6 +404,196 @@ async fn zone_seed_and_reject_out_of_range_serial() {
     }
 }
 
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_serial_wraps_past_u32_max() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("wrap.example.com");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/zones",
+            Some(json!({
+                "name": zone_name,
+                "primary_ns": format!("ns1.{zone_name}"),
+                "admin_email": "hostmaster@example.com",
+                "ttl": 3600,
+                "serial": u32::MAX
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    assert_eq!(body["zone"]["serial"], u32::MAX);
+    let initial_records = app.list_records(&zone_name).await.len();
+
+    // RFC 1982 addition: the serial after 4294967295 is 0, then 1.
+    for (host, expected_serial) in [("a", 0), ("b", 1)] {
+        let (status, body) = app
+            .request(
+                Method::POST,
+                "/records",
+                Some(json!({
+                    "name": host,
+                    "record_type": "A",
+                    "value": "192.0.2.1",
+                    "ttl": 300,
+                    "zone_name": zone_name
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+        assert_eq!(app.zone_serial(&zone_name).await, expected_serial);
+    }
+
+    // History stays in serial order across the wrap.
+    let (status, body) = app
+        .request(Method::GET, &format!("/zones/{zone_name}/snapshots"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let serials: Vec<i64> = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["serial"].as_i64().unwrap())
+        .collect();
+    assert_eq!(serials, [1, 0, u32::MAX as i64]);
+
+    let (status, body) = app
+        .request(
+            Method::GET,
+            &format!("/zones/{zone_name}/snapshots/diff?from={}", u32::MAX),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["diff"]["summary"]["added"], 2);
+
+    // Rolling back to the pre-wrap serial still moves the serial forward.
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/rollback"),
+            Some(json!({ "serial": u32::MAX })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["new_serial"], 2);
+    assert_eq!(app.list_records(&zone_name).await.len(), initial_records);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_serial_policy_is_chosen_at_create_and_changed_on_update() {
+    let app = TestApp::start().await;
+    let today: i64 = chrono::Utc::now()
+        .format("%Y%m%d")
+        .to_string()
+        .parse()
+        .unwrap();
+
+    let zone_name = app.zone_name("dated.example.com");
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/zones",
+            Some(json!({
+                "name": zone_name,
+                "primary_ns": format!("ns1.{zone_name}"),
+                "admin_email": "hostmaster@example.com",
+                "ttl": 3600,
+                "serial_policy": "date"
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    assert_eq!(body["zone"]["serial_policy"], "date");
+    assert_eq!(body["zone"]["serial"], today * 100);
+
+    // Each change the same day takes the next two-digit counter.
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{zone_name}"),
+            Some(json!({
+                "name": zone_name,
+                "primary_ns": format!("ns1.{zone_name}"),
+                "admin_email": "hostmaster@example.com",
+                "ttl": 7200
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["zone"]["serial_policy"], "date");
+    assert_eq!(body["zone"]["serial"], today * 100 + 1);
+
+    // Today's YYYYMMDDnn is numerically past the current Unix time, so after
+    // switching to unixtime the serial keeps counting rather than step back.
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{zone_name}"),
+            Some(json!({
+                "name": zone_name,
+                "primary_ns": format!("ns1.{zone_name}"),
+                "admin_email": "hostmaster@example.com",
+                "ttl": 7200,
+                "serial_policy": "unixtime"
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["zone"]["serial_policy"], "unixtime");
+    assert_eq!(body["zone"]["serial"], today * 100 + 2);
+
+    let stamped_name = app.zone_name("stamped.example.com");
+    let before = chrono::Utc::now().timestamp();
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/zones",
+            Some(json!({
+                "name": stamped_name,
+                "primary_ns": format!("ns1.{stamped_name}"),
+                "admin_email": "hostmaster@example.com",
+                "ttl": 3600,
+                "serial_policy": "unixtime"
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    let serial = body["zone"]["serial"].as_i64().unwrap();
+    assert!(
+        serial >= before && serial <= chrono::Utc::now().timestamp(),
+        "{serial}"
+    );
+
+    let default_zone = app.create_test_zone().await;
+    assert_eq!(default_zone["serial_policy"], "increment");
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/zones",
+            Some(json!({
+                "name": app.zone_name("bad-policy.example.com"),
+                "primary_ns": "ns1.example.com",
+                "admin_email": "hostmaster@example.com",
+                "ttl": 3600,
+                "serial_policy": "weekly"
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("unsupported serial policy"),
+        "{body:#?}"
+    );
+}
+
 #[tokio::test]
 #[serial_test::serial(bindizr_e2e)]
 async fn zone_import_zone_file_dry_run_then_apply() {
@@ -1122,8 +1321,9 @@ async fn zone_rollback_rejects_bad_serials() {
     let zone_name = zone["name"].as_str().unwrap();
     let current_serial = zone["serial"].as_i64().unwrap();
 
-    // Serials >= current and non-positive ones are invalid input; a serial in
-    // the valid range that predates the first stored snapshot is a 404.
+    // Serials not behind the current one (RFC 1982) are invalid input and a
+    // negative one is not a serial at all; a serial behind the current one
+    // that predates the first stored snapshot is a 404, including 0.
     for (serial, expected_status, expected_code) in [
         (current_serial, StatusCode::BAD_REQUEST, "INVALID_INPUT"),
         (
@@ -1131,8 +1331,8 @@ async fn zone_rollback_rejects_bad_serials() {
             StatusCode::BAD_REQUEST,
             "INVALID_INPUT",
         ),
-        (0, StatusCode::BAD_REQUEST, "INVALID_INPUT"),
-        (-5, StatusCode::BAD_REQUEST, "INVALID_INPUT"),
+        (-5, StatusCode::BAD_REQUEST, "INVALID_JSON_BODY"),
+        (0, StatusCode::NOT_FOUND, "SNAPSHOT_NOT_FOUND"),
         (
             current_serial - 1,
             StatusCode::NOT_FOUND,
@@ -1298,3 +1498,395 @@ async fn apex_rows_render_and_update_through_their_presentation_name() {
         assert_eq!(status, StatusCode::OK, "{spelling}: {body}");
     }
 }
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_snapshots_attribute_changes_to_the_token_and_request() {
+    let mut app = TestApp::start_with_options(TestAppOptions {
+        require_authentication: true,
+        ..TestAppOptions::default()
+    })
+    .await;
+    let (token_name, token) = app.create_api_token().await;
+    app.set_auth_token(token.clone());
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    // A client-chosen request id is kept and echoed back.
+    let response = reqwest::Client::new()
+        .post(format!("{}/records", app.base_url()))
+        .bearer_auth(&token)
+        .header("x-request-id", "deploy-42")
+        .json(&json!({
+            "name": "www", "record_type": "A", "value": "192.0.2.60",
+            "ttl": 300, "zone_name": zone_name
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()["x-request-id"], "deploy-42");
+
+    let (status, body) = app
+        .request(Method::GET, &format!("/zones/{zone_name}/snapshots"), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let latest = &body["items"][0];
+    assert_eq!(latest["actor"], format!("token:{token_name}"));
+    assert_eq!(latest["transport"], "http");
+    assert_eq!(latest["request_id"], "deploy-42");
+    assert_eq!(latest["source_address"], "127.0.0.1");
+    assert!(latest["tsig_key"].is_null());
+
+    // One that is not a plain token is replaced with a generated id.
+    let response = reqwest::Client::new()
+        .get(format!("{}/zones", app.base_url()))
+        .bearer_auth(&token)
+        .header("x-request-id", "has spaces")
+        .send()
+        .await
+        .unwrap();
+    let generated = response.headers()["x-request-id"].to_str().unwrap();
+    assert_eq!(generated.len(), 16);
+    assert!(generated.chars().all(|c| c.is_ascii_hexdigit()));
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_writes_honor_etag_preconditions() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+    let path = format!("/zones/{zone_name}");
+
+    let (status, headers, _) = app
+        .request_with_headers(Method::GET, &path, &[], None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers["etag"], "\"10\"");
+
+    let update = json!({
+        "name": zone_name,
+        "primary_ns": format!("ns1.{zone_name}"),
+        "admin_email": "admin@example.com",
+        "ttl": 1800,
+        "refresh": 7200,
+        "retry": 3600,
+        "expire": 604800,
+        "minimum_ttl": 86400
+    });
+
+    let (status, _, body) = app
+        .request_with_headers(
+            Method::PUT,
+            &path,
+            &[("if-match", "\"9\"")],
+            Some(update.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(body["code"], "PRECONDITION_FAILED");
+    assert!(
+        body["error"].as_str().unwrap().contains("is at serial 10"),
+        "{body}"
+    );
+    assert_eq!(app.zone_serial(zone_name).await, 10);
+
+    let (status, _, body) = app
+        .request_with_headers(
+            Method::PUT,
+            &path,
+            &[("if-match", "12")],
+            Some(update.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(
+        body["error"]
+            .as_str()
+            .unwrap()
+            .contains("Invalid if-match header")
+    );
+
+    let (status, headers, body) = app
+        .request_with_headers(
+            Method::PUT,
+            &path,
+            &[("if-match", "\"10\"")],
+            Some(update.clone()),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let serial = body["zone"]["serial"].as_i64().unwrap();
+    assert!(serial > 10);
+    assert_eq!(headers["etag"], format!("\"{serial}\""));
+
+    // Creating over an existing zone is a failed precondition rather than a conflict.
+    let (status, _, body) = app
+        .request_with_headers(
+            Method::POST,
+            "/zones",
+            &[("if-none-match", "*")],
+            Some(update),
+        )
+        .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(body["code"], "PRECONDITION_FAILED");
+
+    let (status, _, _) = app
+        .request_with_headers(Method::DELETE, &path, &[("if-match", "\"10\"")], None)
+        .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    let (status, _, _) = app
+        .request_with_headers(Method::DELETE, &path, &[("if-match", "*")], None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+/// What the stand-in primary serves for `zone_name` besides its SOA.
+fn primary_records(zone_name: &str) -> Vec<PrimaryRecord> {
+    vec![
+        (
+            format!("{zone_name}."),
+            3600,
+            Ns::new(Name::from_str(&format!("ns1.{zone_name}.")).unwrap()).into(),
+        ),
+        (
+            format!("www.{zone_name}."),
+            300,
+            A::new(Ipv4Addr::new(192, 0, 2, 10)).into(),
+        ),
+        (
+            format!("{zone_name}."),
+            3600,
+            Mx::new(10, Name::from_str(&format!("mail.{zone_name}.")).unwrap()).into(),
+        ),
+        (
+            format!("mail.{zone_name}."),
+            3600,
+            A::new(Ipv4Addr::new(192, 0, 2, 11)).into(),
+        ),
+        (
+            format!("{zone_name}."),
+            3600,
+            Txt::build_from_slice(b"v=spf1 -all").unwrap().into(),
+        ),
+    ]
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_pull_transfers_records_from_another_primary() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    let (port, primary) = spawn_primary(zone_name, 2026101801, primary_records(zone_name), None);
+    let server = format!("127.0.0.1:{port}");
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/pull"),
+            Some(json!({ "server": server, "dry_run": true })),
+        )
+        .await;
+    primary.join().unwrap().unwrap();
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["server"], server.as_str());
+    assert_eq!(body["serial"], 2026101801);
+    assert_eq!(body["transferred"], 5);
+    assert_eq!(body["import"]["applied"], false);
+    // The NS matches the zone's own primary NS, so only four are new.
+    assert_eq!(body["import"]["summary"]["added"], 4);
+    assert_eq!(body["import"]["errors"].as_array().unwrap().len(), 0);
+    let www = format!("www.{zone_name}.");
+    assert!(
+        app.list_records(zone_name)
+            .await
+            .iter()
+            .all(|record| record["name"] != www.as_str())
+    );
+
+    let (port, primary) = spawn_primary(zone_name, 2026101801, primary_records(zone_name), None);
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/pull"),
+            Some(json!({ "server": format!("127.0.0.1:{port}"), "mode": "replace" })),
+        )
+        .await;
+    primary.join().unwrap().unwrap();
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["import"]["applied"], true);
+
+    let records = app.list_records(zone_name).await;
+    let www = records
+        .iter()
+        .find(|record| record["name"] == www.as_str())
+        .expect("www was pulled");
+    assert_eq!(www["value"], "192.0.2.10");
+    assert_eq!(www["ttl"], 300);
+    assert!(
+        records
+            .iter()
+            .any(|record| record["record_type"] == "TXT" && record["value"] == "v=spf1 -all"),
+        "{records:?}"
+    );
+    // The zone keeps its own serial line; the pull bumps it like any write.
+    assert_ne!(app.zone_serial(zone_name).await, 2026101801);
+
+    // Pulling again finds nothing to change.
+    let (port, primary) = spawn_primary(zone_name, 2026101801, primary_records(zone_name), None);
+    let (_, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/pull"),
+            Some(json!({ "server": format!("127.0.0.1:{port}") })),
+        )
+        .await;
+    primary.join().unwrap().unwrap();
+    assert_eq!(body["import"]["summary"]["added"], 0);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_pull_reports_a_refused_transfer() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+    let records_before = app.list_records(zone_name).await.len();
+
+    // A primary that wants TSIG refuses the unsigned request.
+    let key = SigningKey {
+        name: "xfr-key".to_string(),
+        secret: "c2VjcmV0LXRyYW5zZmVyLWtleS0wMTIzNDU2Nzg5YWI=".to_string(),
+    };
+    let (port, primary) = spawn_primary(zone_name, 1, primary_records(zone_name), Some(&key));
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/pull"),
+            Some(json!({ "server": format!("127.0.0.1:{port}") })),
+        )
+        .await;
+    primary.join().unwrap().unwrap();
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(body["code"], "TRANSFER_FAILED");
+    assert!(
+        body["error"].as_str().unwrap().contains("REFUSED"),
+        "{body}"
+    );
+    assert_eq!(app.list_records(zone_name).await.len(), records_before);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/zones/missing.example/pull",
+            Some(json!({ "server": "127.0.0.1" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "ZONE_NOT_FOUND");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_pull_signs_the_transfer_with_a_tsig_key() {
+    let app = TestApp::start().await;
+    let key = create_key(&app, "xfr-key").await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+
+    let (port, primary) = spawn_primary(zone_name, 7, primary_records(zone_name), Some(&key));
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/pull"),
+            Some(json!({
+                "server": format!("127.0.0.1:{port}"),
+                "tsig_key": "xfr-key",
+                "dry_run": true,
+            })),
+        )
+        .await;
+    primary.join().unwrap().unwrap();
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["serial"], 7);
+    assert_eq!(body["transferred"], 5);
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/pull"),
+            Some(json!({ "server": "127.0.0.1:53", "tsig_key": "missing-key" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_lint_reports_cross_record_problems() {
+    let app = TestApp::start().await;
+    let zone = app.create_test_zone().await;
+    let zone_name = zone["name"].as_str().unwrap();
+    let lint_path = format!("/zones/{zone_name}/lint");
+
+    // A fresh zone's synthesized apex NS names a host the zone has no record for.
+    let (status, body) = app.request(Method::GET, &lint_path, None).await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["errors"], 1, "{body:#?}");
+    assert_eq!(body["findings"][0]["severity"], "error");
+    assert_eq!(body["findings"][0]["code"], "missing_target");
+    assert_eq!(body["findings"][0]["record_type"], "NS");
+
+    seed_records(
+        &app,
+        zone_name,
+        json!([
+            { "name": "ns1", "record_type": "A", "value": "192.0.2.1" },
+            { "name": "www", "record_type": "A", "value": "192.0.2.80" },
+            { "name": "alias", "record_type": "CNAME", "value": format!("www.{zone_name}.") },
+            { "name": "@", "record_type": "MX", "value": format!("alias.{zone_name}."), "priority": 10 },
+            { "name": "sub", "record_type": "NS", "value": format!("ns1.sub.{zone_name}.") },
+            { "name": "stale.sub", "record_type": "TXT", "value": "hidden by the delegation" }
+        ]),
+    )
+    .await;
+
+    let (status, body) = app.request(Method::GET, &lint_path, None).await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    assert_eq!(body["zone"], zone_name);
+    assert_eq!(body["errors"], 2, "{body:#?}");
+    assert_eq!(body["warnings"], 1, "{body:#?}");
+    let findings = body["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            (
+                f["severity"].as_str().unwrap(),
+                f["code"].as_str().unwrap(),
+                f["name"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        findings,
+        [
+            ("error", "target_is_cname", format!("{zone_name}.")),
+            ("error", "missing_glue", format!("sub.{zone_name}.")),
+            (
+                "warning",
+                "occluded_record",
+                format!("stale.sub.{zone_name}.")
+            ),
+        ]
+    );
+
+    let (status, body) = app
+        .request(Method::GET, "/zones/missing.example/lint", None)
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body:#?}");
+}
@@ -0,0 +1,116 @@
+use reqwest::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use crate::common::{TestApp, assert_cli_failure_contains};
+
+async fn audit_entries(app: &TestApp, args: &[&str]) -> Vec<Value> {
+    let mut full = vec!["audit"];
+    full.extend_from_slice(args);
+    full.extend_from_slice(&["-o", "json"]);
+    let stdout = app.run_cli_success(&full).await;
+    serde_json::from_str::<Value>(&stdout)
+        .expect("CLI did not return valid JSON")
+        .as_array()
+        .expect("audit returns a list")
+        .clone()
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn audit_filters_changes_by_actor_transport_and_time() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("audit.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    // Authentication is off, so the HTTP change is anonymous.
+    let (status, _) = app
+        .request(
+            Method::POST,
+            "/records",
+            Some(json!({
+                "name": "www", "record_type": "A", "value": "192.0.2.70",
+                "ttl": 300, "zone_name": zone_name
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let all = audit_entries(&app, &["--zone", &zone_name]).await;
+    assert_eq!(all.len(), 2, "{all:?}");
+    assert_eq!(all[0]["actor"], "anonymous");
+    assert_eq!(all[0]["transport"], "http");
+    assert!(all[1]["actor"].as_str().unwrap().starts_with("uid:"));
+    assert_eq!(all[1]["transport"], "socket");
+
+    let http = audit_entries(&app, &["--zone", &zone_name, "--actor", "anonymous"]).await;
+    assert_eq!(http.len(), 1);
+    assert_eq!(http[0]["serial"], all[0]["serial"]);
+
+    let socket = audit_entries(&app, &["--zone", &zone_name, "--transport", "socket"]).await;
+    assert_eq!(socket.len(), 1);
+    assert_eq!(socket[0]["serial"], all[1]["serial"]);
+
+    let created_at = all[1]["created_at"].as_str().unwrap();
+    let since = audit_entries(&app, &["--zone", &zone_name, "--since", created_at]).await;
+    assert_eq!(since.len(), 2);
+    let future = audit_entries(&app, &["--zone", &zone_name, "--since", "2999-01-01"]).await;
+    assert!(future.is_empty());
+    let past = audit_entries(&app, &["--zone", &zone_name, "--until", "2000-01-01"]).await;
+    assert!(past.is_empty());
+
+    let args = ["audit", "--since", "yesterday"];
+    let output = app.run_cli(&args).await;
+    assert_cli_failure_contains(&args, &output, "neither an RFC 3339 time");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn audit_keeps_a_zones_entries_through_history_pruning_and_deletion() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("audit-kept.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    for (name, address) in [("www", "192.0.2.71"), ("mail", "192.0.2.72")] {
+        let (status, _) = app
+            .request(
+                Method::POST,
+                "/records",
+                Some(json!({
+                    "name": name, "record_type": "A", "value": address,
+                    "ttl": 300, "zone_name": zone_name
+                })),
+            )
+            .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    let before = audit_entries(&app, &["--zone", &zone_name]).await;
+    let actions: Vec<&str> = before
+        .iter()
+        .map(|entry| entry["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["updated", "updated", "created"], "{before:?}");
+
+    let pruned = app
+        .run_cli_success(&[
+            "zone",
+            "history",
+            "prune",
+            &zone_name,
+            "--keep-serials",
+            "1",
+        ])
+        .await;
+    assert!(pruned.contains("Pruned 2 snapshot(s)"), "{pruned}");
+    assert_eq!(audit_entries(&app, &["--zone", &zone_name]).await, before);
+
+    app.run_cli_success(&["zone", "delete", &zone_name]).await;
+
+    let after = audit_entries(&app, &["--zone", &zone_name]).await;
+    assert_eq!(after.len(), 4, "{after:?}");
+    assert_eq!(after[0]["action"], "deleted");
+    assert_eq!(after[0]["zone_name"], zone_name.as_str());
+    assert_eq!(after[0]["serial"], before[0]["serial"]);
+    assert_eq!(after[0]["transport"], "socket");
+    assert!(after[0]["actor"].as_str().unwrap().starts_with("uid:"));
+    assert_eq!(after[1..], before[..]);
+}
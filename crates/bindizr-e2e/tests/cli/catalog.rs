@@ -0,0 +1,45 @@
+use crate::common::TestApp;
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn catalog_commands_assign_a_zone_and_guard_deletion() {
+    let app = TestApp::start().await;
+    let catalog = app.zone_name("pool-cli.catalog.example");
+    let zone_name = app.zone_name("catalog-cli.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    let output = app
+        .run_cli_success(&["catalog", "create", "--name", &catalog])
+        .await;
+    assert!(output.contains("Catalog created successfully"));
+    let output = app.run_cli_success(&["catalog", "list"]).await;
+    assert!(output.contains(&catalog));
+
+    let output = app
+        .run_cli_success(&[
+            "zone",
+            "catalog",
+            "set",
+            &zone_name,
+            "--catalog",
+            &catalog,
+            "--group",
+            "signed",
+            "--ext",
+            "tier=gold",
+        ])
+        .await;
+    assert!(output.contains(&format!("Catalog: {catalog}")));
+    assert!(output.contains("Group: signed"));
+    assert!(output.contains("ext.tier: gold"));
+
+    let args = ["catalog", "delete", catalog.as_str()];
+    let output = app.run_cli(&args).await;
+    crate::common::assert_cli_failure_contains(&args, &output, "still lists 1 zone");
+
+    let output = app
+        .run_cli_success(&["zone", "catalog", "set", &zone_name])
+        .await;
+    assert!(output.contains("Catalog: catalog.bind"));
+    app.run_cli_success(&["catalog", "delete", &catalog]).await;
+}
@@ -1,4 +1,6 @@
-use crate::common::TestApp;
+use std::time::Duration;
+
+use crate::common::{TestApp, TestAppOptions, assert_cli_failure_contains};
 
 // Local mode only: in compose the daemon is the container's PID 1, so
 // stopping it would recycle the shared stack under the remaining tests.
@@ -43,3 +45,81 @@ async fn stop_shuts_down_daemon() {
     let after = app.run_cli(&args).await;
     assert!(!after.status.success());
 }
+
+async fn config_value(app: &TestApp, key: &str) -> String {
+    app.run_cli_success(&["config", "get", key])
+        .await
+        .trim()
+        .to_string()
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn reload_applies_the_edited_file_and_reports_startup_settings() {
+    let app = TestApp::start_with_options(TestAppOptions::default()).await;
+    app.edit_config(|config| {
+        config
+            .replace(
+                r#"secondary_addrs = """#,
+                r#"secondary_addrs = "192.0.2.1""#,
+            )
+            .replace("openapi_enabled = false", "openapi_enabled = true")
+    });
+
+    let output = app.run_cli_success(&["reload"]).await;
+    assert!(output.contains("Configuration reloaded"), "{output}");
+    assert!(output.contains("api.openapi_enabled"), "{output}");
+
+    assert_eq!(config_value(&app, "dns.secondary_addrs").await, "192.0.2.1");
+    // Startup-only settings keep describing the running process.
+    assert_eq!(config_value(&app, "api.openapi_enabled").await, "false");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn reload_rejects_an_invalid_file_and_keeps_the_running_config() {
+    let app = TestApp::start_with_options(TestAppOptions {
+        secondary_addrs: "192.0.2.1".to_string(),
+        ..TestAppOptions::default()
+    })
+    .await;
+    // Unquoted, so the file no longer parses.
+    app.edit_config(|config| {
+        config.replace(
+            r#"secondary_addrs = "192.0.2.1""#,
+            "secondary_addrs = 192.0.2.2",
+        )
+    });
+
+    let args = ["reload"];
+    let output = app.run_cli(&args).await;
+    assert_cli_failure_contains(&args, &output, "Configuration reload failed");
+    assert_eq!(config_value(&app, "dns.secondary_addrs").await, "192.0.2.1");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn sighup_reloads_the_config_file() {
+    let app = TestApp::start_with_options(TestAppOptions::default()).await;
+    app.edit_config(|config| {
+        config.replace(
+            r#"secondary_addrs = """#,
+            r#"secondary_addrs = "192.0.2.1""#,
+        )
+    });
+
+    let pid = app.daemon_pid().expect("local daemon").to_string();
+    let status = std::process::Command::new("kill")
+        .args(["-HUP", &pid])
+        .status()
+        .expect("failed to run kill");
+    assert!(status.success());
+
+    for _ in 0..50 {
+        if config_value(&app, "dns.secondary_addrs").await == "192.0.2.1" {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("SIGHUP did not reload the config file");
+}
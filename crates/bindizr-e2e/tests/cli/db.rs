@@ -0,0 +1,141 @@
+use std::{
+    path::Path,
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use crate::common::{assert_cli_failure_contains, assert_cli_success};
+
+/// A config on a fresh SQLite file in `dir`. The ports are never bound:
+/// startup fails on the schema check before any listener comes up.
+fn write_config(dir: &Path, auto_migrate: bool) -> String {
+    let db_path = dir.join("bindizr.sqlite");
+    std::fs::File::create(&db_path).expect("failed to create sqlite file");
+    let config_path = dir.join("bindizr.conf.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            r#"
+[api]
+listen_addr = "127.0.0.1"
+listen_port = 8000
+require_authentication = false
+
+[database]
+type = "sqlite"
+auto_migrate = {auto_migrate}
+
+[database.sqlite]
+file_path = "{}"
+
+[dns]
+listen_addr = "127.0.0.1"
+listen_port = 5300
+secondary_addrs = ""
+
+[logging]
+log_level = "error"
+"#,
+            db_path.display()
+        ),
+    )
+    .expect("failed to write config");
+    config_path
+        .to_str()
+        .expect("config path was not UTF-8")
+        .to_string()
+}
+
+/// Run the offline `db` commands against the host binary; no daemon is involved.
+fn run_cli(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_bindizr-e2e-server"))
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .expect("failed to run bindizr CLI")
+}
+
+fn run_cli_success(args: &[&str]) -> String {
+    let output = run_cli(args);
+    assert_cli_success(args, &output);
+    String::from_utf8(output.stdout).expect("CLI stdout was not UTF-8")
+}
+
+/// Run `bindizr start`, which must exit on its own within a few seconds.
+fn run_start_expecting_exit(config_path: &str) -> std::process::Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bindizr-e2e-server"))
+        .args(["start", "-c", config_path])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start bindizr");
+
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while child.try_wait().expect("failed to poll bindizr").is_none() {
+        if Instant::now() > deadline {
+            let _ = child.kill();
+            panic!("bindizr started instead of refusing the database schema");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    child.wait_with_output().expect("failed to collect output")
+}
+
+#[test]
+#[serial_test::serial(bindizr_e2e)]
+fn db_migrate_brings_a_fresh_database_to_the_current_version() {
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let config_path = write_config(temp_dir.path(), true);
+
+    let status = run_cli_success(&["db", "status", "-c", &config_path]);
+    assert!(status.contains("Database schema version: 0"), "{status}");
+    assert!(status.contains("pending"), "{status}");
+
+    let migrated = run_cli_success(&["db", "migrate", "-c", &config_path]);
+    assert!(
+        migrated.contains("Applied migration 1: initial schema"),
+        "{migrated}"
+    );
+
+    let again = run_cli_success(&["db", "migrate", "-c", &config_path]);
+    assert!(again.contains("up to date"), "{again}");
+
+    let status = run_cli_success(&["db", "status", "-c", &config_path]);
+    assert!(!status.contains("pending"), "{status}");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn start_refuses_an_unmigrated_or_newer_database() {
+    let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
+    let config_path = write_config(temp_dir.path(), false);
+
+    // With auto_migrate off, an outdated schema waits for `db migrate`.
+    let output = run_start_expecting_exit(&config_path);
+    assert_cli_failure_contains(&["start"], &output, "bindizr db migrate");
+
+    run_cli_success(&["db", "migrate", "-c", &config_path]);
+
+    // A newer bindizr recorded a migration this binary does not know.
+    let db_url = format!(
+        "sqlite:{}",
+        temp_dir.path().join("bindizr.sqlite").display()
+    );
+    let pool = sqlx::SqlitePool::connect(&db_url)
+        .await
+        .expect("failed to open sqlite database");
+    sqlx::query("INSERT INTO schema_migrations (version, description) VALUES (9999, 'future')")
+        .execute(&pool)
+        .await
+        .expect("failed to record a future migration");
+    pool.close().await;
+
+    let output = run_start_expecting_exit(&config_path);
+    assert_cli_failure_contains(&["start"], &output, "newer than the");
+
+    let args = ["db", "migrate", "-c", &config_path];
+    assert_cli_failure_contains(&args, &run_cli(&args), "upgrade bindizr");
+    let status = run_cli_success(&["db", "status", "-c", &config_path]);
+    assert!(status.contains("future"), "{status}");
+}
@@ -382,3 +382,142 @@ async fn record_bulk_insert_from_stdin() {
     assert!(names.contains(&format!("mail.{zone_name}.")));
     assert!(names.contains(&format!("ftp.{zone_name}.")));
 }
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn record_changeset_preview_then_apply_via_cli() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("changeset.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    let records = serde_json::json!([
+        { "name": "www", "record_type": "A", "value": "192.0.2.30" },
+        { "name": "old", "record_type": "A", "value": "192.0.2.31" },
+    ])
+    .to_string();
+    let inserted = app
+        .run_cli_success_with_input(
+            &[
+                "record", "bulk", "-", "--zone", &zone_name, "--output", "json",
+            ],
+            &records,
+        )
+        .await;
+    let inserted: Value = serde_json::from_str(&inserted).expect("CLI did not return valid JSON");
+    let www_id = inserted["records"][0]["id"].as_i64().unwrap();
+    let old_id = inserted["records"][1]["id"].as_i64().unwrap();
+
+    let changeset = format!(
+        "operations:\n\
+         - op: delete\n  id: {old_id}\n\
+         - op: update\n  id: {www_id}\n  record:\n    name: www\n    record_type: A\n    value: 192.0.2.32\n\
+         - op: add\n  record:\n    name: new\n    record_type: A\n    value: 192.0.2.33\n"
+    );
+    let preview = app
+        .run_cli_success_with_input(
+            &[
+                "record",
+                "changeset",
+                "-",
+                "--zone",
+                &zone_name,
+                "--preview",
+            ],
+            &changeset,
+        )
+        .await;
+    assert!(
+        preview.contains("Records: +1 -1 ~1"),
+        "preview was: {preview}"
+    );
+
+    let applied = app
+        .run_cli_success_with_input(
+            &["record", "changeset", "-", "--zone", &zone_name],
+            &changeset,
+        )
+        .await;
+    assert!(
+        applied.contains("Applied changeset: 1 added, 1 updated, 1 deleted"),
+        "output was: {applied}"
+    );
+
+    let listed = app
+        .run_cli_success(&[
+            "record", "list", "--zone", &zone_name, "--type", "A", "--output", "json",
+        ])
+        .await;
+    let listed: Value = serde_json::from_str(&listed).expect("CLI did not return valid JSON");
+    let mut values: Vec<&str> = listed["items"]
+        .as_array()
+        .expect("missing record items")
+        .iter()
+        .map(|record| record["value"].as_str().unwrap())
+        .collect();
+    values.sort();
+    assert_eq!(values, vec!["192.0.2.32", "192.0.2.33"]);
+
+    // An operation the zone cannot take is reported and nothing applies.
+    let args = [
+        "record",
+        "changeset",
+        "-",
+        "--zone",
+        &zone_name,
+        "--output",
+        "json",
+    ];
+    let output = app
+        .run_cli_with_input(
+            &args,
+            Some(&format!("[{{\"op\": \"delete\", \"id\": {old_id}}}]")),
+        )
+        .await;
+    assert_cli_failure_contains(&args, &output, "operation 1:");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn record_rrset_set_get_delete_via_cli() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("rrset.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    let mx1 = format!("10 mx1.{zone_name}.");
+    let mx2 = format!("20 mx2.{zone_name}.");
+    let set = app
+        .run_cli_success(&[
+            "record", "rrset", "set", "@", "MX", "--zone", &zone_name, "--ttl", "600", "--value",
+            &mx1, "--value", &mx2,
+        ])
+        .await;
+    assert!(
+        set.contains(&format!("RRset '{zone_name}.' MX replaced with 2 value(s)")),
+        "output was: {set}"
+    );
+    assert!(set.contains(&mx1), "{set}");
+
+    let got = app
+        .run_cli_success(&[
+            "record", "rrset", "get", "@", "MX", "--zone", &zone_name, "--output", "json",
+        ])
+        .await;
+    let got: Value = serde_json::from_str(&got).expect("CLI did not return valid JSON");
+    assert_eq!(got["ttl"], 600);
+    assert_eq!(
+        got["values"],
+        serde_json::json!([
+            format!("10 mx1.{zone_name}."),
+            format!("20 mx2.{zone_name}.")
+        ])
+    );
+
+    let deleted = app
+        .run_cli_success(&["record", "rrset", "delete", "@", "MX", "--zone", &zone_name])
+        .await;
+    assert!(deleted.contains("deleted successfully"), "{deleted}");
+
+    let args = ["record", "rrset", "get", "@", "MX", "--zone", &zone_name];
+    let output = app.run_cli(&args).await;
+    assert_cli_failure_contains(&args, &output, "not found");
+}
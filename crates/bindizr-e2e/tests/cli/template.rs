@@ -0,0 +1,81 @@
+use crate::common::TestApp;
+
+const RECORDS: &str = "\
+- name: '@'
+  record_type: MX
+  value: '{{mail_host}}.{{zone}}.'
+  priority: 10
+- name: www
+  record_type: A
+  value: 192.0.2.10
+";
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn template_commands_create_zones_and_apply_updates() {
+    let app = TestApp::start().await;
+    let template = format!("{}-cli", app.namespace());
+    let zone_name = app.zone_name("template-cli.example");
+
+    let output = app
+        .run_cli_success_with_input(
+            &["template", "create", "--name", &template, "--file", "-"],
+            RECORDS,
+        )
+        .await;
+    assert!(output.contains("Template created successfully"));
+
+    let primary_ns = format!("ns1.{zone_name}");
+    let admin_email = format!("hostmaster@{zone_name}");
+    app.run_cli_success(&[
+        "zone",
+        "create",
+        "--name",
+        &zone_name,
+        "--primary-ns",
+        &primary_ns,
+        "--admin-email",
+        &admin_email,
+        "--ttl",
+        "3600",
+        "--template",
+        &template,
+        "--var",
+        "mail_host=mx1",
+    ])
+    .await;
+
+    let output = app.run_cli_success(&["template", "get", &template]).await;
+    assert!(output.contains("Variables: mail_host"));
+    assert!(output.contains(&format!("Zones: {zone_name}")));
+
+    let updated = RECORDS.replace("192.0.2.10", "192.0.2.20");
+    let output = app
+        .run_cli_success_with_input(
+            &["template", "update", &template, "--file", "-", "--apply"],
+            &updated,
+        )
+        .await;
+    assert!(
+        output.contains(&format!("{zone_name}: applied")),
+        "{output}"
+    );
+    assert!(output.contains("192.0.2.20"), "{output}");
+
+    let www = format!("www.{zone_name}.");
+    let records = app.list_records(&zone_name).await;
+    assert!(
+        records
+            .iter()
+            .any(|record| record["name"] == www.as_str() && record["value"] == "192.0.2.20"),
+        "{records:#?}"
+    );
+
+    let args = ["template", "delete", template.as_str()];
+    let output = app.run_cli(&args).await;
+    crate::common::assert_cli_failure_contains(&args, &output, "still linked to 1 zone");
+
+    app.run_cli_success(&["zone", "delete", &zone_name]).await;
+    app.run_cli_success(&["template", "delete", &template])
+        .await;
+}
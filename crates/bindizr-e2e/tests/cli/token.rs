@@ -9,3 +9,26 @@ async fn token_create_rejects_duplicate_name() {
     let output = app.run_cli(&["token", "create", "--name", &name]).await;
     assert!(!output.status.success());
 }
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn token_rotate_prints_the_new_secret_and_overlap() {
+    let app = TestApp::start().await;
+    let (name, first_secret) = app.create_api_token().await;
+
+    let output = app
+        .run_cli_success(&["token", "rotate", &name, "--overlap-minutes", "15"])
+        .await;
+    assert!(output.contains("API token rotated successfully"));
+    assert!(output.contains("Previous token valid until: "));
+    let secret = output
+        .lines()
+        .find_map(|line| line.strip_prefix("Token: "))
+        .expect("rotate output carries the new secret");
+    assert_ne!(secret.trim(), first_secret);
+
+    let output = app
+        .run_cli_success(&["token", "rotate", &name, "--overlap-minutes", "0"])
+        .await;
+    assert!(output.contains("Previous token revoked"));
+}
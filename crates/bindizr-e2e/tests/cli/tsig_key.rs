@@ -110,3 +110,62 @@ async fn global_tsig_key_create_list_delete() {
     app.run_cli_success(&["tsig-key", "delete", "cli-global-key"])
         .await;
 }
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_transfer_acl_add_list_remove() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("cli-transfer-acl.example");
+
+    app.create_zone_cli(&zone_name, "3600").await;
+    app.run_cli_success(&["tsig-key", "create", "--name", "cli-xfr-key"])
+        .await;
+
+    let added = app
+        .run_cli_success(&[
+            "zone",
+            "transfer-acl",
+            "add",
+            &zone_name,
+            "--address",
+            "198.51.100.7",
+            "--key",
+            "cli-xfr-key",
+        ])
+        .await;
+    assert!(added.contains("Transfer ACL entry created successfully"));
+    app.run_cli_success(&[
+        "zone",
+        "transfer-acl",
+        "add",
+        &zone_name,
+        "--address",
+        "192.0.2.0/24",
+        "--deny",
+    ])
+    .await;
+
+    let listed = app
+        .run_cli_success(&["zone", "transfer-acl", "list", &zone_name])
+        .await;
+    assert!(listed.contains("cli-xfr-key"));
+    assert!(listed.contains("deny"));
+    assert!(listed.contains("192.0.2.0/24"));
+
+    let entry_id = listed
+        .lines()
+        .find(|line| line.contains("cli-xfr-key"))
+        .and_then(|line| line.split_whitespace().next())
+        .expect("entry row not found")
+        .to_string();
+
+    let removed = app
+        .run_cli_success(&["zone", "transfer-acl", "remove", &zone_name, &entry_id])
+        .await;
+    assert!(removed.contains("Transfer ACL entry deleted successfully"));
+
+    let listed = app
+        .run_cli_success(&["zone", "transfer-acl", "list", &zone_name])
+        .await;
+    assert!(!listed.contains("cli-xfr-key"));
+}
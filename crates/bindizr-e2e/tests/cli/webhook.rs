@@ -0,0 +1,53 @@
+use crate::common::TestApp;
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn webhook_commands_manage_subscriptions_and_list_deliveries() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("webhook-cli.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let webhook = app.zone_name("cli-sink");
+
+    let output = app
+        .run_cli_success(&[
+            "webhook",
+            "create",
+            "--name",
+            &webhook,
+            "--url",
+            "http://127.0.0.1:9/hook",
+            "--zone",
+            &zone_name,
+            "--secret",
+            "cli-secret",
+        ])
+        .await;
+    assert!(output.contains("Webhook created successfully"), "{output}");
+    assert!(output.contains(&format!("Zone: {zone_name}")), "{output}");
+    assert!(output.contains("Secret: cli-secret"), "{output}");
+
+    let output = app.run_cli_success(&["webhook", "list"]).await;
+    assert!(output.contains(&webhook), "{output}");
+    assert!(!output.contains("cli-secret"), "{output}");
+
+    app.run_cli_success(&["zone", "update", &zone_name, "--ttl", "7200"])
+        .await;
+    let output = app
+        .run_cli_success(&["webhook", "deliveries", &webhook, "--limit", "5"])
+        .await;
+    assert!(output.contains("zone.updated"), "{output}");
+    assert!(output.contains(&zone_name), "{output}");
+
+    let args = [
+        "webhook", "create", "--name", "bad-url", "--url", "ftp://x/",
+    ];
+    let output = app.run_cli(&args).await;
+    crate::common::assert_cli_failure_contains(&args, &output, "http or https");
+
+    let output = app.run_cli_success(&["webhook", "delete", &webhook]).await;
+    assert!(output.contains("Webhook deleted successfully"), "{output}");
+
+    let args = ["webhook", "deliveries", webhook.as_str()];
+    let output = app.run_cli(&args).await;
+    crate::common::assert_cli_failure_contains(&args, &output, "not found");
+}
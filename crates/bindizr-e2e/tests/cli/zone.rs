@@ -1,6 +1,12 @@
+use std::net::Ipv4Addr;
+
+use domain::rdata::A;
 use serde_json::{Value, json};
 
-use crate::common::{TestApp, assert_cli_failure_contains, assert_cli_success};
+use crate::common::{
+    TestApp, assert_cli_failure_contains, assert_cli_success,
+    xfr::{PrimaryRecord, spawn_primary},
+};
 
 #[tokio::test]
 #[serial_test::serial(bindizr_e2e)]
@@ -266,6 +272,94 @@ async fn zone_export_via_cli() {
     assert_eq!(reimport["summary"]["deleted"], 0, "{reimport}");
 }
 
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_lint_via_cli() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("lint.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    // The apex NS names ns1 in the zone, which has no record yet: an error,
+    // so the command fails after printing the findings.
+    let args = ["zone", "lint", &zone_name];
+    let output = app.run_cli(&args).await;
+    assert_cli_failure_contains(&args, &output, "1 lint error(s)");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("missing_target"), "{stdout}");
+
+    app.run_cli_success(&[
+        "record",
+        "create",
+        "--name",
+        "ns1",
+        "--type",
+        "A",
+        "--value",
+        "192.0.2.53",
+        "--zone",
+        &zone_name,
+    ])
+    .await;
+
+    let output = app.run_cli_success(&["zone", "lint", &zone_name]).await;
+    assert!(output.contains("has no lint findings"), "{output}");
+
+    let output = app
+        .run_cli_success(&["zone", "lint", &zone_name, "--output", "json"])
+        .await;
+    let lint: Value = serde_json::from_str(&output).expect("CLI did not return valid JSON");
+    assert_eq!(lint["errors"], 0, "{lint}");
+    assert_eq!(lint["findings"], json!([]), "{lint}");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_export_round_trips_generic_type_records() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("export-generic.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    app.run_cli_success(&[
+        "record",
+        "create",
+        "--name",
+        "opaque",
+        "--type",
+        "TYPE65280",
+        "--value",
+        r"\# 3 ABCDEF",
+        "--zone",
+        &zone_name,
+    ])
+    .await;
+    // A type bindizr does not model, imported by its mnemonic.
+    app.run_cli_success_with_input(
+        &["zone", "import", &zone_name, "-"],
+        "alias IN DNAME target.example.net.\n",
+    )
+    .await;
+
+    let exported = app.run_cli_success(&["zone", "export", &zone_name]).await;
+    assert!(
+        exported.contains("opaque\t3600\tIN\tTYPE65280\t\\# 3 abcdef"),
+        "{exported}"
+    );
+    assert!(
+        exported
+            .contains("alias\t3600\tIN\tTYPE39\t\\# 20 06746172676574076578616d706c65036e657400"),
+        "{exported}"
+    );
+
+    let reimport = app
+        .run_cli_success_with_input(
+            &["zone", "import", &zone_name, "-", "--output", "json"],
+            &exported,
+        )
+        .await;
+    let reimport: Value = serde_json::from_str(&reimport).expect("CLI did not return valid JSON");
+    assert_eq!(reimport["summary"]["added"], 0, "{reimport}");
+    assert_eq!(reimport["summary"]["deleted"], 0, "{reimport}");
+}
+
 #[tokio::test]
 #[serial_test::serial(bindizr_e2e)]
 async fn zone_export_orders_by_name_then_type_then_rdata() {
@@ -507,3 +601,164 @@ async fn zone_status_via_cli() {
         assert!(parsed["secondaries"].as_array().unwrap().is_empty());
     }
 }
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_notify_target_add_list_remove() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("cli-notify-target.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    let listed = app
+        .run_cli_success(&["zone", "notify-target", "list", &zone_name])
+        .await;
+    assert!(listed.contains("the global secondary list applies"));
+
+    let added = app
+        .run_cli_success(&[
+            "zone",
+            "notify-target",
+            "add",
+            &zone_name,
+            "--address",
+            "192.0.2.30:5353",
+        ])
+        .await;
+    assert!(added.contains("NOTIFY target created successfully"));
+    app.run_cli_success(&[
+        "zone",
+        "notify-target",
+        "add",
+        &zone_name,
+        "--address",
+        "ns3.example.net",
+        "--also",
+    ])
+    .await;
+
+    let listed = app
+        .run_cli_success(&["zone", "notify-target", "list", &zone_name])
+        .await;
+    assert!(listed.contains("192.0.2.30:5353"));
+    assert!(listed.contains("also-notify"));
+
+    let target_id = listed
+        .lines()
+        .find(|line| line.contains("192.0.2.30:5353"))
+        .and_then(|line| line.split_whitespace().next())
+        .expect("target row not found")
+        .to_string();
+
+    let removed = app
+        .run_cli_success(&["zone", "notify-target", "remove", &zone_name, &target_id])
+        .await;
+    assert!(removed.contains("NOTIFY target deleted successfully"));
+
+    let listed = app
+        .run_cli_success(&["zone", "notify-target", "list", &zone_name])
+        .await;
+    assert!(!listed.contains("192.0.2.30:5353"));
+    assert!(listed.contains("ns3.example.net"));
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_and_record_writes_honor_expect_serial_via_cli() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("expect-serial.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    let args = [
+        "zone",
+        "update",
+        &zone_name,
+        "--refresh",
+        "300",
+        "--expect-serial",
+        "7",
+    ];
+    let output = app.run_cli(&args).await;
+    assert_cli_failure_contains(
+        &args,
+        &output,
+        &format!("Precondition failed: zone '{zone_name}' is at serial 1"),
+    );
+
+    app.run_cli_success(&[
+        "zone",
+        "update",
+        &zone_name,
+        "--refresh",
+        "300",
+        "--expect-serial",
+        "1",
+    ])
+    .await;
+
+    // The update moved the zone on, so a write pinned to the old serial fails.
+    let args = [
+        "record",
+        "create",
+        "--zone",
+        &zone_name,
+        "--name",
+        "www",
+        "--type",
+        "A",
+        "--value",
+        "192.0.2.30",
+        "--expect-serial",
+        "1",
+    ];
+    let output = app.run_cli(&args).await;
+    assert_cli_failure_contains(&args, &output, "Precondition failed");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_pull_previews_then_imports_from_another_primary() {
+    let app = TestApp::start().await;
+    let zone_name = app.zone_name("pull.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let www = format!("www.{zone_name}.");
+    let records = || -> Vec<PrimaryRecord> {
+        vec![(
+            www.clone(),
+            3600,
+            A::new(Ipv4Addr::new(192, 0, 2, 40)).into(),
+        )]
+    };
+
+    let (port, primary) = spawn_primary(&zone_name, 1, records(), None);
+    let server = format!("127.0.0.1:{port}");
+    let preview = app
+        .run_cli_success(&["zone", "pull", &zone_name, "--from", &server, "--preview"])
+        .await;
+    primary.join().unwrap().unwrap();
+    assert!(preview.contains("dry run"), "{preview}");
+    assert!(preview.contains("+ www"), "{preview}");
+    assert!(
+        app.list_records(&zone_name)
+            .await
+            .iter()
+            .all(|r| r["name"] != www.as_str())
+    );
+
+    let (port, primary) = spawn_primary(&zone_name, 1, records(), None);
+    let server = format!("127.0.0.1:{port}");
+    let pulled = app
+        .run_cli_success(&[
+            "zone", "pull", &zone_name, "--from", &server, "--output", "json",
+        ])
+        .await;
+    primary.join().unwrap().unwrap();
+    let pulled: Value = serde_json::from_str(&pulled).expect("CLI did not return valid JSON");
+    assert_eq!(pulled["import"]["applied"], true);
+    assert_eq!(pulled["import"]["summary"]["added"], 1);
+    assert!(
+        app.list_records(&zone_name)
+            .await
+            .iter()
+            .any(|r| r["name"] == www.as_str())
+    );
+}
@@ -12,13 +12,16 @@ use std::{
     time::{Duration, Instant},
 };
 
-use reqwest::{Client, Method, StatusCode};
+use reqwest::{Client, Method, StatusCode, header::HeaderMap};
 use serde_json::{Value, json};
 use tempfile::TempDir;
 
 mod assertions;
 mod dns;
 pub(crate) mod nsupdate;
+pub(crate) mod query;
+pub(crate) mod secondary;
+pub(crate) mod xfr;
 
 pub(crate) use assertions::{assert_cli_failure_contains, assert_cli_success};
 use dns::{dns_expected_value, dns_key_from_record, dns_record_type, wait_for_dns_records};
@@ -40,6 +43,7 @@ pub(crate) struct TestApp {
     client: Client,
     base_url: String,
     dns_port: Option<u16>,
+    tls_port: Option<u16>,
     dns_secondary_ports: Vec<u16>,
     namespace: String,
     auth_token: Option<String>,
@@ -51,7 +55,18 @@ pub(crate) struct TestAppOptions {
     pub require_authentication: bool,
     pub external_dns_enabled: bool,
     pub nsupdate_allow_unsigned: bool,
+    pub answer_queries: bool,
     pub openapi_enabled: bool,
+    /// The `dns.secondary_addrs` value; empty by default.
+    pub secondary_addrs: String,
+    /// The `dns.sync_monitor_interval_secs` value; 0 (off) by default.
+    pub sync_monitor_interval_secs: u64,
+    /// The `dns.sync_renotify_after_secs` value.
+    pub sync_renotify_after_secs: u64,
+    /// Start the TLS listener with the fixture server certificate.
+    pub tls_enabled: bool,
+    /// Require fixture-CA client certificates on the TLS listener.
+    pub tls_mutual: bool,
 }
 
 enum TestRuntime {
@@ -78,11 +93,19 @@ impl TestApp {
         let temp_dir = tempfile::tempdir().expect("failed to create temp dir");
         let api_port = reserve_tcp_port();
         let dns_port = reserve_dns_port();
+        let tls_port = reserve_tcp_port();
         let db_path = temp_dir.path().join("bindizr.sqlite");
         fs::File::create(&db_path).expect("failed to create sqlite file");
 
         let config_path = temp_dir.path().join("bindizr.conf.toml");
-        write_config(&config_path, api_port, dns_port, &db_path, &options);
+        write_config(
+            &config_path,
+            api_port,
+            dns_port,
+            tls_port,
+            &db_path,
+            &options,
+        );
 
         let mut child = Command::new(env!("CARGO_BIN_EXE_bindizr-e2e-server"))
             .arg("start")
@@ -103,6 +126,7 @@ impl TestApp {
             client,
             base_url,
             dns_port: Some(dns_port),
+            tls_port: options.tls_enabled.then_some(tls_port),
             dns_secondary_ports: Vec::new(),
             namespace: test_namespace(),
             auth_token: None,
@@ -119,6 +143,7 @@ impl TestApp {
             client,
             base_url: COMPOSE_API_BASE_URL.to_string(),
             dns_port: None,
+            tls_port: None,
             dns_secondary_ports: SECONDARY_PORTS.to_vec(),
             namespace: test_namespace(),
             auth_token: None,
@@ -135,6 +160,12 @@ impl TestApp {
         self.dns_port.expect("local runtime binds a DNS port")
     }
 
+    /// Port of bindizr's TLS listener, when started with `tls_enabled`.
+    pub(crate) fn tls_port(&self) -> u16 {
+        self.tls_port
+            .expect("started with the TLS listener enabled")
+    }
+
     /// Bearer token attached to every subsequent HTTP request.
     pub(crate) fn set_auth_token(&mut self, token: String) {
         self.auth_token = Some(token);
@@ -177,6 +208,25 @@ impl TestApp {
         &self.namespace
     }
 
+    /// Rewrite the local daemon's config file in place; a later reload picks
+    /// it up. Panics under compose, whose config is fixed.
+    pub(crate) fn edit_config(&self, edit: impl FnOnce(String) -> String) {
+        let Some(TestRuntime::Local { temp_dir, .. }) = &self.runtime else {
+            panic!("the compose stack's config cannot be edited");
+        };
+        let path = temp_dir.path().join("bindizr.conf.toml");
+        let config = fs::read_to_string(&path).expect("failed to read bindizr config");
+        fs::write(&path, edit(config)).expect("failed to write bindizr config");
+    }
+
+    /// PID of the locally spawned daemon; `None` under compose.
+    pub(crate) fn daemon_pid(&self) -> Option<u32> {
+        match &self.runtime {
+            Some(TestRuntime::Local { child, .. }) => Some(child.id()),
+            _ => None,
+        }
+    }
+
     pub(crate) fn has_dns_secondaries(&self) -> bool {
         !self.dns_secondary_ports.is_empty()
     }
@@ -187,16 +237,30 @@ impl TestApp {
         path: &str,
         body: Option<Value>,
     ) -> (StatusCode, Value) {
+        let (status, _, body) = self.request_with_headers(method, path, &[], body).await;
+        (status, body)
+    }
+
+    /// `request` with extra request headers, also returning the response headers.
+    pub(crate) async fn request_with_headers(
+        &self,
+        method: Method,
+        path: &str,
+        headers: &[(&str, &str)],
+        body: Option<Value>,
+    ) -> (StatusCode, HeaderMap, Value) {
         let should_verify_dns = method != Method::GET;
         let mut previous_dns_key = self.previous_dns_key(&method, path).await;
         let updated_zone_name = (method == Method::PUT)
             .then(|| path.strip_prefix("/zones/"))
             .flatten();
-        let response = self.send_request(method, path, body).await;
+        let response = self
+            .send_request_with_headers(method, path, headers, body)
+            .await;
 
         if let Some(previous_zone_name) = updated_zone_name
             && response.0.is_success()
-            && response.1["zone"]["name"].as_str() == Some(previous_zone_name)
+            && response.2["zone"]["name"].as_str() == Some(previous_zone_name)
         {
             previous_dns_key = None;
         }
@@ -214,17 +278,34 @@ impl TestApp {
         path: &str,
         body: Option<Value>,
     ) -> (StatusCode, Value) {
+        let (status, _, body) = self
+            .send_request_with_headers(method, path, &[], body)
+            .await;
+        (status, body)
+    }
+
+    async fn send_request_with_headers(
+        &self,
+        method: Method,
+        path: &str,
+        headers: &[(&str, &str)],
+        body: Option<Value>,
+    ) -> (StatusCode, HeaderMap, Value) {
         let url = format!("{}{}", self.base_url, path);
         let mut request = self.client.request(method, url);
         if let Some(token) = &self.auth_token {
             request = request.bearer_auth(token);
         }
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
         if let Some(body) = body {
             request = request.json(&body);
         }
 
         let response = request.send().await.expect("failed to send HTTP request");
         let status = response.status();
+        let response_headers = response.headers().clone();
         let bytes = response
             .bytes()
             .await
@@ -237,7 +318,7 @@ impl TestApp {
                 .unwrap_or_else(|_| json!(String::from_utf8_lossy(&bytes)))
         };
 
-        (status, body)
+        (status, response_headers, body)
     }
 
     /// A zone's records as the API reports them.
@@ -310,7 +391,11 @@ impl TestApp {
     }
 
     /// Run the CLI, optionally piping `input` to its stdin (for `-` file args).
-    async fn run_cli_with_input(&self, args: &[&str], input: Option<&str>) -> std::process::Output {
+    pub(crate) async fn run_cli_with_input(
+        &self,
+        args: &[&str],
+        input: Option<&str>,
+    ) -> std::process::Output {
         let previous_dns_key = match args {
             ["record", "delete", record_id, ..] => {
                 self.previous_dns_key(&Method::DELETE, &format!("/records/{record_id}"))
@@ -352,7 +437,14 @@ impl TestApp {
                 args,
                 [
                     "zone" | "record",
-                    "create" | "bulk" | "delete" | "import" | "notify" | "rollback",
+                    "create"
+                        | "bulk"
+                        | "changeset"
+                        | "rrset"
+                        | "delete"
+                        | "import"
+                        | "notify"
+                        | "rollback",
                     ..
                 ]
             )
@@ -622,10 +714,18 @@ fn reserve_dns_port() -> u16 {
     panic!("failed to reserve a DNS port available for both TCP and UDP");
 }
 
+/// Path of a certificate or key from `tests/fixtures/tls`.
+pub(crate) fn tls_fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/tls")
+        .join(name)
+}
+
 fn write_config(
     config_path: &Path,
     api_port: u16,
     dns_port: u16,
+    tls_port: u16,
     db_path: &Path,
     options: &TestAppOptions,
 ) {
@@ -653,12 +753,20 @@ server_url = ""
 [dns]
 listen_addr = "127.0.0.1"
 listen_port = {dns_port}
-secondary_addrs = ""
+secondary_addrs = "{secondary_addrs}"
 notify_after_update = false
 notify_on_startup = false
 notify_retries = 0
 notify_timeout_secs = 1
+sync_monitor_interval_secs = {sync_monitor_interval_secs}
+sync_renotify_after_secs = {sync_renotify_after_secs}
 nsupdate_allow_unsigned = {nsupdate_allow_unsigned}
+answer_queries = {answer_queries}
+tls_enabled = {tls_enabled}
+tls_listen_port = {tls_port}
+tls_cert_path = "{tls_cert_path}"
+tls_key_path = "{tls_key_path}"
+tls_client_ca_path = "{tls_client_ca_path}"
 
 [logging]
 log_level = "error"
@@ -667,7 +775,19 @@ log_level = "error"
         require_authentication = options.require_authentication,
         external_dns_enabled = options.external_dns_enabled,
         nsupdate_allow_unsigned = options.nsupdate_allow_unsigned,
+        answer_queries = options.answer_queries,
+        secondary_addrs = options.secondary_addrs,
+        sync_monitor_interval_secs = options.sync_monitor_interval_secs,
+        sync_renotify_after_secs = options.sync_renotify_after_secs,
         openapi_enabled = options.openapi_enabled,
+        tls_enabled = options.tls_enabled,
+        tls_cert_path = tls_fixture("server.pem").display(),
+        tls_key_path = tls_fixture("server.key").display(),
+        tls_client_ca_path = if options.tls_mutual {
+            tls_fixture("ca.pem").display().to_string()
+        } else {
+            String::new()
+        },
     );
 
     fs::write(config_path, config).expect("failed to write bindizr config");
@@ -14,12 +14,26 @@ use domain::{
     tsig::{Algorithm, ClientTransaction, Key, KeyName},
 };
 
+use super::TestApp;
+
 /// The key an update is signed with, as `tsig-key get` reports it.
 pub(crate) struct SigningKey {
     pub name: String,
     pub secret: String,
 }
 
+impl SigningKey {
+    /// The key as `domain` signs and verifies with it (`tsig-key create`
+    /// defaults to hmac-sha256).
+    pub(crate) fn to_domain_key(&self) -> Result<Key, String> {
+        let secret = base64::engine::general_purpose::STANDARD
+            .decode(&self.secret)
+            .map_err(|e| format!("TSIG secret is not base64: {e}"))?;
+        let key_name = KeyName::from_str(&self.name).map_err(|e| e.to_string())?;
+        Key::new(Algorithm::Sha256, &secret, key_name, None, None).map_err(|e| e.to_string())
+    }
+}
+
 /// One RR of an update section, in the class that gives it its meaning
 /// (RFC 2136, Section 2.5).
 pub(crate) enum UpdateRr {
@@ -43,6 +57,23 @@ pub(crate) enum PrereqRr {
     NameNotInUse { name: String },
 }
 
+/// Create a TSIG key through the CLI and read its generated secret back.
+pub(crate) async fn create_key(app: &TestApp, name: &str) -> SigningKey {
+    app.run_cli_success(&["tsig-key", "create", "--name", name])
+        .await;
+    let fetched = app.run_cli_success(&["tsig-key", "get", name]).await;
+    let secret = fetched
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Secret: "))
+        .expect("tsig-key get prints the secret")
+        .trim()
+        .to_string();
+    SigningKey {
+        name: name.to_string(),
+        secret,
+    }
+}
+
 /// Send an unsigned UPDATE for `zone` and return the response RCODE.
 pub(crate) fn send_update(
     port: u16,
@@ -172,14 +203,8 @@ fn empty_record(owner: &str, rtype: Rtype, class: Class) -> Result<EmptyRecord,
 
 /// Append the request TSIG, the way `domain`'s client transaction does it.
 fn sign(builder: &mut AdditionalBuilder<Vec<u8>>, key: &SigningKey) -> Result<(), String> {
-    let secret = base64::engine::general_purpose::STANDARD
-        .decode(&key.secret)
-        .map_err(|e| format!("TSIG secret is not base64: {e}"))?;
-    let key_name = KeyName::from_str(&key.name).map_err(|e| e.to_string())?;
-    let signing_key =
-        Key::new(Algorithm::Sha256, &secret, key_name, None, None).map_err(|e| e.to_string())?;
-
-    ClientTransaction::request(signing_key, builder, Time48::now()).map_err(|e| e.to_string())?;
+    ClientTransaction::request(key.to_domain_key()?, builder, Time48::now())
+        .map_err(|e| e.to_string())?;
     Ok(())
 }
 
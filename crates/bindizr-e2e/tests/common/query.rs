@@ -0,0 +1,56 @@
+//! Sending plain queries to bindizr's own listener, for the answers
+//! `dns.answer_queries` serves.
+
+use std::{net::UdpSocket, str::FromStr, time::Duration};
+
+use domain::base::{Message, MessageBuilder, Name, Rtype};
+
+/// Send one UDP query and return the parsed response.
+pub(crate) fn query(port: u16, name: &str, rtype: Rtype) -> Result<Message<Vec<u8>>, String> {
+    query_with_edns(port, name, rtype, None)
+}
+
+/// Send one UDP query, with an OPT record advertising `udp_payload_size`
+/// when given, and return the parsed response.
+pub(crate) fn query_with_edns(
+    port: u16,
+    name: &str,
+    rtype: Rtype,
+    udp_payload_size: Option<u16>,
+) -> Result<Message<Vec<u8>>, String> {
+    let query_id = (std::process::id() as u16)
+        .wrapping_add(port)
+        .wrapping_add(2);
+    let mut builder = MessageBuilder::new_vec();
+    builder.header_mut().set_id(query_id);
+    let mut question = builder.question();
+    let qname = Name::<Vec<u8>>::from_str(name).map_err(|e| e.to_string())?;
+    question.push((&qname, rtype)).map_err(|e| e.to_string())?;
+    let mut additional = question.additional();
+    if let Some(udp_payload_size) = udp_payload_size {
+        additional
+            .opt(|opt| {
+                opt.set_udp_payload_size(udp_payload_size);
+                Ok(())
+            })
+            .map_err(|e| e.to_string())?;
+    }
+    let message = additional.finish();
+
+    let socket = UdpSocket::bind(("127.0.0.1", 0)).map_err(|e| e.to_string())?;
+    socket
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .map_err(|e| e.to_string())?;
+    socket
+        .send_to(&message, ("127.0.0.1", port))
+        .map_err(|e| e.to_string())?;
+
+    let mut response = [0_u8; 4096];
+    let (len, _) = socket.recv_from(&mut response).map_err(|e| e.to_string())?;
+
+    let message = Message::from_octets(response[..len].to_vec()).map_err(|e| e.to_string())?;
+    if message.header().id() != query_id {
+        return Err("query response id mismatch".to_string());
+    }
+    Ok(message)
+}
@@ -0,0 +1,114 @@
+//! A stand-in secondary on a loopback UDP port for the sync monitor: it
+//! answers SOA queries at a serial the test sets and counts the NOTIFYs it
+//! receives.
+
+use std::{
+    net::UdpSocket,
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use domain::{
+    base::{Message, MessageBuilder, Name, Serial, Ttl, iana::Opcode, iana::Rcode},
+    rdata::Soa,
+};
+
+pub(crate) struct StandInSecondary {
+    port: u16,
+    serial: Arc<AtomicU32>,
+    notifies: Arc<AtomicUsize>,
+    stop: Arc<AtomicBool>,
+}
+
+impl StandInSecondary {
+    /// Serve every zone's SOA at `serial` until dropped.
+    pub(crate) fn spawn(serial: u32) -> Self {
+        let socket = UdpSocket::bind(("127.0.0.1", 0)).expect("failed to bind stand-in secondary");
+        socket
+            .set_read_timeout(Some(Duration::from_millis(100)))
+            .unwrap();
+        let secondary = Self {
+            port: socket.local_addr().unwrap().port(),
+            serial: Arc::new(AtomicU32::new(serial)),
+            notifies: Arc::new(AtomicUsize::new(0)),
+            stop: Arc::new(AtomicBool::new(false)),
+        };
+
+        let (serial, notifies, stop) = (
+            secondary.serial.clone(),
+            secondary.notifies.clone(),
+            secondary.stop.clone(),
+        );
+        thread::spawn(move || {
+            let mut buf = [0_u8; 1500];
+            while !stop.load(Ordering::Relaxed) {
+                // Timeouts only bring the stop flag round again.
+                let Ok((len, peer)) = socket.recv_from(&mut buf) else {
+                    continue;
+                };
+                let Some((reply, is_notify)) = answer(&buf[..len], serial.load(Ordering::Relaxed))
+                else {
+                    continue;
+                };
+                if is_notify {
+                    notifies.fetch_add(1, Ordering::Relaxed);
+                }
+                let _ = socket.send_to(&reply, peer);
+            }
+        });
+
+        secondary
+    }
+
+    pub(crate) fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Serve `serial` from now on, as if a transfer had completed.
+    pub(crate) fn set_serial(&self, serial: u32) {
+        self.serial.store(serial, Ordering::Relaxed);
+    }
+
+    /// NOTIFYs received so far.
+    pub(crate) fn notifies(&self) -> usize {
+        self.notifies.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for StandInSecondary {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// The reply to one request, and whether the request was a NOTIFY. A NOTIFY
+/// is acknowledged; a query is answered with the SOA of the zone asked for.
+fn answer(request: &[u8], serial: u32) -> Option<(Vec<u8>, bool)> {
+    let request = Message::from_octets(request.to_vec()).ok()?;
+    let is_notify = request.header().opcode() == Opcode::NOTIFY;
+    let qname = request.sole_question().ok()?.into_qname();
+
+    let mut response = MessageBuilder::new_vec()
+        .start_answer(&request, Rcode::NOERROR)
+        .ok()?;
+    response.header_mut().set_aa(true);
+    if !is_notify {
+        let apex = Name::<Vec<u8>>::from_str(&qname.to_string()).ok()?;
+        let soa = Soa::new(
+            Name::<Vec<u8>>::from_str(&format!("ns1.{apex}")).ok()?,
+            Name::<Vec<u8>>::from_str(&format!("hostmaster.{apex}")).ok()?,
+            Serial(serial),
+            Ttl::from_secs(7200),
+            Ttl::from_secs(3600),
+            Ttl::from_secs(604800),
+            Ttl::from_secs(300),
+        );
+        response.push((&apex, 3600, soa)).ok()?;
+    }
+    Some((response.finish(), is_notify))
+}
@@ -0,0 +1,273 @@
+//! Zone transfers from bindizr's own listeners over TCP or TLS, optionally
+//! TSIG-signed, for the transfer ACL and signing paths; and a stand-in primary
+//! for bindizr to pull a zone from.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+    sync::Arc,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use domain::{
+    base::{Message, MessageBuilder, Name, Rtype, Serial, Ttl, iana::Rcode},
+    rdata::{Soa, ZoneRecordData, tsig::Time48},
+    tsig::{ClientSequence, Key, ServerSequence},
+};
+use rustls::{
+    ClientConfig, ClientConnection, RootCertStore, StreamOwned,
+    crypto::aws_lc_rs,
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, pem::PemObject},
+};
+
+use super::{nsupdate::SigningKey, tls_fixture};
+
+/// Every message of an AXFR response, or the first one when it carries an
+/// error RCODE. With `key`, the request is signed and each response message
+/// must verify as part of one TSIG sequence.
+pub(crate) fn axfr(
+    port: u16,
+    zone: &str,
+    key: Option<&SigningKey>,
+) -> Result<Vec<Message<Vec<u8>>>, String> {
+    transfer(connect(port)?, zone, None, key)
+}
+
+/// Every message of an unsigned IXFR response to a secondary at `serial`.
+/// Reading stops at the second SOA, which is enough to tell an incremental
+/// answer (SOA, SOA, ...) from an AXFR-style one (SOA, records, ..., SOA).
+pub(crate) fn ixfr(port: u16, zone: &str, serial: u32) -> Result<Vec<Message<Vec<u8>>>, String> {
+    transfer(connect(port)?, zone, Some(serial), None)
+}
+
+/// An unsigned [`axfr`] over TLS, trusting the fixture CA; with
+/// `client_certificate` the fixture client certificate (for
+/// `secondary.test`) is presented.
+pub(crate) fn axfr_over_tls(
+    port: u16,
+    zone: &str,
+    client_certificate: bool,
+) -> Result<Vec<Message<Vec<u8>>>, String> {
+    let connection = ClientConnection::new(
+        Arc::new(tls_client_config(client_certificate)?),
+        ServerName::try_from("localhost").map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())?;
+    transfer(
+        StreamOwned::new(connection, connect(port)?),
+        zone,
+        None,
+        None,
+    )
+}
+
+fn tls_client_config(client_certificate: bool) -> Result<ClientConfig, String> {
+    let mut roots = RootCertStore::empty();
+    roots
+        .add(CertificateDer::from_pem_file(tls_fixture("ca.pem")).map_err(|e| e.to_string())?)
+        .map_err(|e| e.to_string())?;
+
+    let builder = ClientConfig::builder_with_provider(Arc::new(aws_lc_rs::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .with_root_certificates(roots);
+    let mut config = if client_certificate {
+        let chain = vec![
+            CertificateDer::from_pem_file(tls_fixture("client.pem")).map_err(|e| e.to_string())?,
+        ];
+        let key =
+            PrivateKeyDer::from_pem_file(tls_fixture("client.key")).map_err(|e| e.to_string())?;
+        builder
+            .with_client_auth_cert(chain, key)
+            .map_err(|e| e.to_string())?
+    } else {
+        builder.with_no_client_auth()
+    };
+    config.alpn_protocols = vec![b"dot".to_vec()];
+    Ok(config)
+}
+
+fn connect(port: u16) -> Result<TcpStream, String> {
+    let stream = TcpStream::connect(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .map_err(|e| e.to_string())?;
+    Ok(stream)
+}
+
+fn transfer(
+    mut stream: impl Read + Write,
+    zone: &str,
+    ixfr_serial: Option<u32>,
+    key: Option<&SigningKey>,
+) -> Result<Vec<Message<Vec<u8>>>, String> {
+    let qname = Name::<Vec<u8>>::from_str(zone).map_err(|e| e.to_string())?;
+    let mut request = match ixfr_serial {
+        None => MessageBuilder::new_vec()
+            .request_axfr(&qname)
+            .map_err(|e| e.to_string())?
+            .additional(),
+        Some(serial) => {
+            // RFC 1995: the client's SOA goes in the authority section; only
+            // its serial matters to the server.
+            let mut question = MessageBuilder::new_vec().question();
+            question
+                .push((&qname, Rtype::IXFR))
+                .map_err(|e| e.to_string())?;
+            let mut authority = question.authority();
+            let soa = Soa::new(
+                qname.clone(),
+                qname.clone(),
+                Serial(serial),
+                Ttl::ZERO,
+                Ttl::ZERO,
+                Ttl::ZERO,
+                Ttl::ZERO,
+            );
+            authority
+                .push((&qname, Ttl::ZERO, soa))
+                .map_err(|e| e.to_string())?;
+            authority.additional()
+        }
+    };
+    let mut sequence: Option<ClientSequence<Key>> = key
+        .map(|key| {
+            ClientSequence::request(key.to_domain_key()?, &mut request, Time48::now())
+                .map_err(|e| e.to_string())
+        })
+        .transpose()?;
+    let request = request.finish();
+
+    stream
+        .write_all(&(request.len() as u16).to_be_bytes())
+        .and_then(|()| stream.write_all(&request))
+        .map_err(|e| e.to_string())?;
+
+    let mut messages = Vec::new();
+    let mut soa_count = 0;
+    while soa_count < 2 {
+        let mut len = [0_u8; 2];
+        stream.read_exact(&mut len).map_err(|e| e.to_string())?;
+        let mut response = vec![0_u8; usize::from(u16::from_be_bytes(len))];
+        stream
+            .read_exact(&mut response)
+            .map_err(|e| e.to_string())?;
+        let mut message = Message::from_octets(response).map_err(|e| e.to_string())?;
+
+        if message.header().rcode() != Rcode::NOERROR {
+            messages.push(message);
+            return Ok(messages);
+        }
+        if let Some(sequence) = sequence.as_mut() {
+            sequence
+                .answer(&mut message, Time48::now())
+                .map_err(|e| format!("response failed TSIG validation: {e}"))?;
+        }
+
+        soa_count += message
+            .answer()
+            .map_err(|e| e.to_string())?
+            .filter(|record| {
+                record
+                    .as_ref()
+                    .is_ok_and(|record| record.rtype() == Rtype::SOA)
+            })
+            .count();
+        messages.push(message);
+    }
+
+    Ok(messages)
+}
+
+/// One record a [`spawn_primary`] serves: absolute owner, TTL, and data.
+pub(crate) type PrimaryRecord = (String, u32, ZoneRecordData<Vec<u8>, Name<Vec<u8>>>);
+
+/// A primary on a loopback port that answers one AXFR for `zone`: its SOA at
+/// `serial` and `records` in one message, the closing SOA in a second. With
+/// `key`, an unsigned request is REFUSED and both messages are signed. The
+/// thread ends after one connection and reports what went wrong, if anything.
+pub(crate) fn spawn_primary(
+    zone: &str,
+    serial: u32,
+    records: Vec<PrimaryRecord>,
+    key: Option<&SigningKey>,
+) -> (u16, JoinHandle<Result<(), String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind stand-in primary");
+    let port = listener.local_addr().unwrap().port();
+    let apex = Name::<Vec<u8>>::from_str(zone).expect("invalid zone name");
+    let key = key.map(|key| key.to_domain_key().expect("invalid signing key"));
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().map_err(|e| e.to_string())?;
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .map_err(|e| e.to_string())?;
+        let mut len = [0_u8; 2];
+        stream.read_exact(&mut len).map_err(|e| e.to_string())?;
+        let mut request = vec![0_u8; usize::from(u16::from_be_bytes(len))];
+        stream.read_exact(&mut request).map_err(|e| e.to_string())?;
+        let mut request = Message::from_octets(request).map_err(|e| e.to_string())?;
+
+        let mut sequence: Option<ServerSequence<Key>> = match &key {
+            Some(key) => ServerSequence::request(key, &mut request, Time48::now())
+                .map_err(|e| format!("request failed TSIG validation: {}", e.error()))?,
+            None => None,
+        };
+        if key.is_some() && sequence.is_none() {
+            let refused = MessageBuilder::new_vec()
+                .start_answer(&request, Rcode::REFUSED)
+                .map_err(|e| e.to_string())?;
+            return write_frame(&mut stream, &refused.finish());
+        }
+
+        let soa = Soa::new(
+            Name::<Vec<u8>>::from_str(&format!("ns1.{apex}")).map_err(|e| e.to_string())?,
+            Name::<Vec<u8>>::from_str(&format!("hostmaster.{apex}")).map_err(|e| e.to_string())?,
+            Serial(serial),
+            Ttl::from_secs(7200),
+            Ttl::from_secs(3600),
+            Ttl::from_secs(604800),
+            Ttl::from_secs(300),
+        );
+
+        let mut first = MessageBuilder::new_vec()
+            .start_answer(&request, Rcode::NOERROR)
+            .map_err(|e| e.to_string())?;
+        first
+            .push((&apex, 3600, soa.clone()))
+            .map_err(|e| e.to_string())?;
+        for (owner, ttl, data) in &records {
+            let owner = Name::<Vec<u8>>::from_str(owner).map_err(|e| e.to_string())?;
+            first
+                .push((&owner, *ttl, data))
+                .map_err(|e| e.to_string())?;
+        }
+
+        let mut last = MessageBuilder::new_vec()
+            .start_answer(&request, Rcode::NOERROR)
+            .map_err(|e| e.to_string())?;
+        last.push((&apex, 3600, soa)).map_err(|e| e.to_string())?;
+
+        for answer in [first, last] {
+            let mut answer = answer.additional();
+            if let Some(sequence) = sequence.as_mut() {
+                sequence
+                    .answer(&mut answer, Time48::now())
+                    .map_err(|e| e.to_string())?;
+            }
+            write_frame(&mut stream, &answer.finish())?;
+        }
+        Ok(())
+    });
+
+    (port, handle)
+}
+
+fn write_frame(stream: &mut impl Write, message: &[u8]) -> Result<(), String> {
+    stream
+        .write_all(&(message.len() as u16).to_be_bytes())
+        .and_then(|()| stream.write_all(message))
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,174 @@
+use domain::{
+    base::{Rtype, iana::Rcode, message::Message, name::ParsedName},
+    rdata::AllRecordData,
+};
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use serial_test::serial;
+
+use crate::common::{
+    TestApp, TestAppOptions,
+    query::{query, query_with_edns},
+};
+
+async fn answering_app() -> TestApp {
+    TestApp::start_with_options(TestAppOptions {
+        answer_queries: true,
+        ..TestAppOptions::default()
+    })
+    .await
+}
+
+async fn create_record(app: &TestApp, zone_name: &str, name: &str, record_type: &str, value: &str) {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/records",
+            Some(json!({
+                "name": name,
+                "record_type": record_type,
+                "value": value,
+                "ttl": 300,
+                "zone_name": zone_name,
+            })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+}
+
+/// The `(type, rdata)` pairs of one section, rdata in presentation form.
+fn section(records: domain::base::message::RecordSection<'_, Vec<u8>>) -> Vec<(Rtype, String)> {
+    records
+        .limit_to::<AllRecordData<_, ParsedName<_>>>()
+        .map(|record| {
+            let record = record.expect("record parses");
+            (record.rtype(), record.data().to_string())
+        })
+        .collect()
+}
+
+fn answers(message: &Message<Vec<u8>>) -> Vec<(Rtype, String)> {
+    section(message.answer().expect("answer section"))
+}
+
+fn authority(message: &Message<Vec<u8>>) -> Vec<(Rtype, String)> {
+    section(message.authority().expect("authority section"))
+}
+
+#[tokio::test]
+#[serial(bindizr_e2e)]
+async fn answer_queries_serves_zone_data_authoritatively() {
+    let app = answering_app().await;
+    let zone_name = app.zone_name("answer.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    create_record(&app, &zone_name, "www", "A", "192.0.2.10").await;
+    create_record(
+        &app,
+        &zone_name,
+        "alias",
+        "CNAME",
+        &format!("www.{zone_name}."),
+    )
+    .await;
+    let port = app.dns_port();
+
+    let response = query(port, &format!("www.{zone_name}"), Rtype::A).expect("A query");
+    assert_eq!(response.header().rcode(), Rcode::NOERROR);
+    assert!(response.header().aa());
+    assert_eq!(answers(&response), [(Rtype::A, "192.0.2.10".to_string())]);
+
+    // The CNAME comes first, then the in-zone target's RRset.
+    let response = query(port, &format!("alias.{zone_name}"), Rtype::A).expect("CNAME query");
+    assert_eq!(
+        answers(&response),
+        [
+            (Rtype::CNAME, format!("www.{zone_name}.")),
+            (Rtype::A, "192.0.2.10".to_string()),
+        ]
+    );
+
+    let response = query(port, &format!("www.{zone_name}"), Rtype::MX).expect("NODATA query");
+    assert_eq!(response.header().rcode(), Rcode::NOERROR);
+    assert!(answers(&response).is_empty());
+    assert_eq!(authority(&response)[0].0, Rtype::SOA);
+
+    let response = query(port, &format!("missing.{zone_name}"), Rtype::A).expect("NXDOMAIN query");
+    assert_eq!(response.header().rcode(), Rcode::NXDOMAIN);
+    assert_eq!(authority(&response)[0].0, Rtype::SOA);
+
+    let response = query(port, "not-hosted.invalid", Rtype::A).expect("out-of-zone query");
+    assert_eq!(response.header().rcode(), Rcode::REFUSED);
+}
+
+#[tokio::test]
+#[serial(bindizr_e2e)]
+async fn answer_queries_refers_delegated_names_with_glue() {
+    let app = answering_app().await;
+    let zone_name = app.zone_name("parent.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    create_record(
+        &app,
+        &zone_name,
+        "child",
+        "NS",
+        &format!("ns.child.{zone_name}."),
+    )
+    .await;
+    create_record(&app, &zone_name, "ns.child", "A", "192.0.2.53").await;
+
+    let response = query(app.dns_port(), &format!("host.child.{zone_name}"), Rtype::A)
+        .expect("referral query");
+
+    assert_eq!(response.header().rcode(), Rcode::NOERROR);
+    assert!(!response.header().aa());
+    assert!(answers(&response).is_empty());
+    assert_eq!(
+        authority(&response),
+        [(Rtype::NS, format!("ns.child.{zone_name}."))]
+    );
+    assert_eq!(
+        section(response.additional().expect("additional section")),
+        [(Rtype::A, "192.0.2.53".to_string())]
+    );
+}
+
+#[tokio::test]
+#[serial(bindizr_e2e)]
+async fn answer_queries_fits_udp_answers_to_the_edns_payload_size() {
+    let app = answering_app().await;
+    let zone_name = app.zone_name("edns.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    // About 900 bytes of TXT: over the classic 512, under the 1232 default cap.
+    for i in 0..8 {
+        create_record(
+            &app,
+            &zone_name,
+            "big",
+            "TXT",
+            &format!("{i}{}", "x".repeat(60)),
+        )
+        .await;
+    }
+    let port = app.dns_port();
+    let qname = format!("big.{zone_name}");
+
+    let response = query(port, &qname, Rtype::TXT).expect("plain query");
+    assert!(response.header().tc());
+    assert!(answers(&response).is_empty());
+    assert!(response.opt().is_none());
+
+    let response = query_with_edns(port, &qname, Rtype::TXT, Some(4096)).expect("EDNS query");
+    assert!(!response.header().tc());
+    assert_eq!(answers(&response).len(), 8);
+    assert_eq!(response.opt().expect("OPT echoed").udp_payload_size(), 1232);
+
+    // A requester advertising less than fits is still sent TC, with OPT.
+    let response = query_with_edns(port, &qname, Rtype::TXT, Some(600)).expect("small EDNS query");
+    assert!(response.header().tc());
+    assert!(response.opt().is_some());
+
+    let response = query_with_edns(port, "not-hosted.invalid", Rtype::A, Some(4096))
+        .expect("out-of-zone EDNS query");
+    assert_eq!(response.header().rcode(), Rcode::REFUSED);
+    assert!(response.opt().is_some());
+}
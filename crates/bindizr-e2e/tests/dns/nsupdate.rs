@@ -3,7 +3,7 @@ use serial_test::serial;
 
 use crate::common::{
     TestApp, TestAppOptions,
-    nsupdate::{PrereqRr, SigningKey, UpdateRr, send_signed_update, send_update},
+    nsupdate::{PrereqRr, UpdateRr, create_key, send_signed_update, send_update},
 };
 
 /// These drive bindizr's own DNS listener over UDP with unsigned updates, so
@@ -252,22 +252,6 @@ async fn signed_nsupdate_app() -> TestApp {
     TestApp::start_with_options(TestAppOptions::default()).await
 }
 
-async fn create_key(app: &TestApp, name: &str) -> SigningKey {
-    app.run_cli_success(&["tsig-key", "create", "--name", name])
-        .await;
-    let fetched = app.run_cli_success(&["tsig-key", "get", name]).await;
-    let secret = fetched
-        .lines()
-        .find_map(|line| line.trim().strip_prefix("Secret: "))
-        .expect("tsig-key get prints the secret")
-        .trim()
-        .to_string();
-    SigningKey {
-        name: name.to_string(),
-        secret,
-    }
-}
-
 #[tokio::test]
 #[serial]
 async fn signed_nsupdate_needs_a_policy_for_the_zone() {
@@ -316,6 +300,24 @@ async fn signed_nsupdate_needs_a_policy_for_the_zone() {
             .any(|record| record["name"] == format!("a.{zone_name}.")),
         "granted update was not applied"
     );
+
+    // The change is attributed to the signing key and the client address.
+    let audit = app
+        .run_cli_success(&[
+            "audit",
+            "--zone",
+            &zone_name,
+            "--transport",
+            "nsupdate",
+            "-o",
+            "json",
+        ])
+        .await;
+    let audit: serde_json::Value = serde_json::from_str(&audit).expect("audit JSON");
+    assert_eq!(audit.as_array().unwrap().len(), 1);
+    assert_eq!(audit[0]["actor"], format!("tsig:{}", key.name));
+    assert_eq!(audit[0]["tsig_key"], key.name);
+    assert_eq!(audit[0]["source_address"], "127.0.0.1");
 }
 
 // The apex is the empty owner in a row but `@` to the input parser, so an
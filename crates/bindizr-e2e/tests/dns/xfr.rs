@@ -0,0 +1,489 @@
+use std::{net::UdpSocket, time::Duration};
+
+use domain::{
+    base::{
+        Message, MessageBuilder, Rtype,
+        iana::{Opcode, Rcode, TsigRcode},
+    },
+    rdata::{
+        Ptr, Soa,
+        tsig::{Time48, Tsig},
+    },
+    tsig::ServerTransaction,
+};
+use reqwest::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use crate::common::{
+    TestApp, TestAppOptions, assert_cli_failure_contains,
+    nsupdate::{SigningKey, create_key},
+    xfr::{axfr, axfr_over_tls, ixfr},
+};
+
+/// A bindizr whose only secondary is this host, admitted for transfers only
+/// with `xfr-key`. The port is where NOTIFYs go.
+async fn keyed_secondary_app(notify_port: u16) -> (TestApp, SigningKey) {
+    let app = TestApp::start_with_options(TestAppOptions {
+        secondary_addrs: format!("127.0.0.1:{notify_port} key xfr-key"),
+        ..TestAppOptions::default()
+    })
+    .await;
+    let key = create_key(&app, "xfr-key").await;
+    (app, key)
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn keyed_secondary_transfers_only_with_its_key() {
+    // The discard port: nothing here triggers a NOTIFY.
+    let (app, key) = keyed_secondary_app(9).await;
+    let other_key = create_key(&app, "other-key").await;
+    let zone_name = app.zone_name("xfr-tsig.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    let port = app.dns_port();
+
+    let unsigned = axfr(port, &zone_name, None).expect("unsigned AXFR");
+    assert_eq!(unsigned[0].header().rcode(), Rcode::REFUSED);
+
+    let wrong_key = axfr(port, &zone_name, Some(&other_key)).expect("AXFR with another key");
+    let response = &wrong_key[0];
+    assert_eq!(response.header().rcode(), Rcode::NOTAUTH);
+    let tsig = response
+        .additional()
+        .unwrap()
+        .limit_to::<Tsig<_, _>>()
+        .next()
+        .expect("error response carries a TSIG record")
+        .unwrap();
+    assert_eq!(tsig.data().error(), TsigRcode::BADKEY);
+
+    // The helper verifies every message of the signed stream.
+    let signed = axfr(port, &zone_name, Some(&key)).expect("signed AXFR");
+    assert_eq!(soa_count(&signed), 2);
+}
+
+fn soa_count(messages: &[Message<Vec<u8>>]) -> usize {
+    messages
+        .iter()
+        .map(|message| message.answer().unwrap().limit_to::<Soa<_>>().count())
+        .sum()
+}
+
+/// The member zone names a catalog AXFR lists.
+fn catalog_members(messages: &[Message<Vec<u8>>]) -> Vec<String> {
+    messages
+        .iter()
+        .flat_map(|message| {
+            message
+                .answer()
+                .unwrap()
+                .limit_to::<Ptr<_>>()
+                .map(|record| {
+                    record
+                        .unwrap()
+                        .data()
+                        .ptrdname()
+                        .to_string()
+                        .trim_end_matches('.')
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+async fn add_transfer_acl(app: &TestApp, zone_name: &str, entry: serde_json::Value) {
+    let (status, body) = app
+        .request(
+            Method::POST,
+            &format!("/zones/{zone_name}/transfer-acls"),
+            Some(entry),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn zone_transfer_acl_overrides_the_global_list_and_filters_the_catalog() {
+    // This host is a global secondary, so every zone starts transferable.
+    let (app, key) = keyed_secondary_app(9).await;
+    let restricted = app.zone_name("acl-restricted.example");
+    let open = app.zone_name("acl-open.example");
+    app.create_zone_cli(&restricted, "3600").await;
+    app.create_zone_cli(&open, "3600").await;
+    let port = app.dns_port();
+
+    // Only signed requests from the loopback prefix get the restricted zone.
+    add_transfer_acl(
+        &app,
+        &restricted,
+        json!({ "address": "127.0.0.0/8", "tsig_key": "xfr-key" }),
+    )
+    .await;
+
+    let unsigned = axfr(port, &restricted, None).expect("unsigned AXFR");
+    assert_eq!(unsigned[0].header().rcode(), Rcode::REFUSED);
+    assert_eq!(soa_count(&axfr(port, &restricted, Some(&key)).unwrap()), 2);
+    // The global entry is keyed too; the open zone still follows it.
+    assert_eq!(soa_count(&axfr(port, &open, Some(&key)).unwrap()), 2);
+
+    let members = catalog_members(&axfr(port, "catalog.bind", Some(&key)).unwrap());
+    assert!(members.contains(&restricted), "{members:?}");
+    assert!(members.contains(&open), "{members:?}");
+
+    // A deny entry wins over the allow entry.
+    add_transfer_acl(
+        &app,
+        &restricted,
+        json!({ "action": "deny", "address": "127.0.0.1" }),
+    )
+    .await;
+
+    assert!(axfr(port, &restricted, Some(&key)).is_err());
+    let members = catalog_members(&axfr(port, "catalog.bind", Some(&key)).unwrap());
+    assert!(!members.contains(&restricted), "{members:?}");
+    assert!(members.contains(&open), "{members:?}");
+}
+
+/// The owner name and type of every record a transfer carries.
+fn record_owners(messages: &[Message<Vec<u8>>]) -> Vec<(String, Rtype)> {
+    messages
+        .iter()
+        .flat_map(|message| {
+            message
+                .answer()
+                .unwrap()
+                .map(|record| {
+                    let record = record.unwrap();
+                    (record.owner().to_string(), record.rtype())
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn named_catalog_lists_its_members_with_their_properties() {
+    let (app, key) = keyed_secondary_app(9).await;
+    let catalog = app.zone_name("pool-a.catalog.example");
+    let member = app.zone_name("pool-a-member.example");
+    let leaving = app.zone_name("pool-a-leaving.example");
+    app.create_zone_cli(&member, "3600").await;
+    app.create_zone_cli(&leaving, "3600").await;
+    let port = app.dns_port();
+
+    let (status, body) = app
+        .request(Method::POST, "/catalogs", Some(json!({ "name": catalog })))
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{body:#?}");
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{member}/catalog"),
+            Some(json!({ "catalog": catalog, "group": "signed", "ext": { "tier": "gold" } })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+    // Handing over to the named catalog lists the zone in both.
+    let (status, body) = app
+        .request(
+            Method::PUT,
+            &format!("/zones/{leaving}/catalog"),
+            Some(json!({ "coo": catalog })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+
+    let messages = axfr(port, &catalog, Some(&key)).expect("named catalog AXFR");
+    assert_eq!(messages[0].header().rcode(), Rcode::NOERROR);
+    let members = catalog_members(&messages);
+    assert!(members.contains(&member), "{members:?}");
+    assert!(members.contains(&leaving), "{members:?}");
+    let owners = record_owners(&messages);
+    let group = format!(".zones.{catalog}");
+    assert!(
+        owners
+            .iter()
+            .any(|(owner, rtype)| owner.starts_with("group.")
+                && owner.ends_with(&group)
+                && *rtype == Rtype::TXT),
+        "{owners:?}"
+    );
+    assert!(
+        owners
+            .iter()
+            .any(|(owner, rtype)| owner.starts_with("tier.ext.") && *rtype == Rtype::TXT),
+        "{owners:?}"
+    );
+    // Only the catalog the zone is leaving carries the coo property.
+    assert!(!owners.iter().any(|(owner, _)| owner.starts_with("coo.")));
+
+    let default = axfr(port, "catalog.bind", Some(&key)).expect("default catalog AXFR");
+    let members = catalog_members(&default);
+    assert!(!members.contains(&member), "{members:?}");
+    assert!(members.contains(&leaving), "{members:?}");
+    assert!(
+        record_owners(&default)
+            .iter()
+            .any(|(owner, rtype)| owner.starts_with("coo.") && *rtype == Rtype::PTR)
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn transfers_over_tls_to_an_admitted_address() {
+    let app = TestApp::start_with_options(TestAppOptions {
+        secondary_addrs: "127.0.0.1".to_string(),
+        tls_enabled: true,
+        ..TestAppOptions::default()
+    })
+    .await;
+    let zone_name = app.zone_name("xot.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    let messages = axfr_over_tls(app.tls_port(), &zone_name, false).expect("AXFR over TLS");
+    assert_eq!(messages[0].header().rcode(), Rcode::NOERROR);
+    assert_eq!(soa_count(&messages), 2);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn client_certificate_admits_its_secondary_over_mutual_tls() {
+    // `secondary.test` never resolves to this host: only the client
+    // certificate issued for it can match the entry.
+    let app = TestApp::start_with_options(TestAppOptions {
+        secondary_addrs: "secondary.test".to_string(),
+        tls_enabled: true,
+        tls_mutual: true,
+        ..TestAppOptions::default()
+    })
+    .await;
+    let zone_name = app.zone_name("xot-mtls.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    assert!(axfr(app.dns_port(), &zone_name, None).is_err());
+    assert!(axfr_over_tls(app.tls_port(), &zone_name, false).is_err());
+
+    let messages =
+        axfr_over_tls(app.tls_port(), &zone_name, true).expect("AXFR with a client certificate");
+    assert_eq!(soa_count(&messages), 2);
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn notify_to_a_keyed_secondary_is_signed() {
+    let secondary = UdpSocket::bind(("127.0.0.1", 0)).unwrap();
+    secondary
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    let (app, key) = keyed_secondary_app(secondary.local_addr().unwrap().port()).await;
+    let zone_name = app.zone_name("notify-tsig.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+
+    // Play the secondary: verify the NOTIFY and answer it signed.
+    let signing_key = key.to_domain_key().unwrap();
+    let responder = std::thread::spawn(move || -> Result<(), String> {
+        let mut buf = [0_u8; 1500];
+        let (len, peer) = secondary.recv_from(&mut buf).map_err(|e| e.to_string())?;
+        let mut request = Message::from_octets(buf[..len].to_vec()).map_err(|e| e.to_string())?;
+        if request.header().opcode() != Opcode::NOTIFY {
+            return Err("expected a NOTIFY".to_string());
+        }
+        let transaction = ServerTransaction::request(&signing_key, &mut request, Time48::now())
+            .map_err(|e| format!("NOTIFY failed TSIG validation: {}", e.error()))?
+            .ok_or_else(|| "NOTIFY is unsigned".to_string())?;
+
+        let mut response = MessageBuilder::new_vec()
+            .start_answer(&request, Rcode::NOERROR)
+            .map_err(|e| e.to_string())?;
+        response.header_mut().set_aa(true);
+        let mut response = response.additional();
+        transaction
+            .answer(&mut response, Time48::now())
+            .map_err(|e| e.to_string())?;
+        secondary
+            .send_to(&response.finish(), peer)
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    });
+
+    let (status, body) = app
+        .request(
+            Method::POST,
+            "/notify/zones",
+            Some(json!({ "zone_name": zone_name })),
+        )
+        .await;
+
+    responder.join().unwrap().unwrap();
+    assert_eq!(status, StatusCode::OK, "{body:#?}");
+}
+
+/// Whether a transfer answered incrementally: an IXFR delta opens with the
+/// current SOA followed by an old one, an AXFR-style answer with the current
+/// SOA followed by zone records.
+fn is_incremental(messages: &[Message<Vec<u8>>]) -> bool {
+    let rtypes: Vec<Rtype> = messages
+        .iter()
+        .flat_map(|message| {
+            message
+                .answer()
+                .unwrap()
+                .map(|record| record.unwrap().rtype())
+        })
+        .collect();
+    rtypes.get(1) == Some(&Rtype::SOA)
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn pruned_history_falls_back_to_axfr_for_older_serials() {
+    let app = TestApp::start_with_options(TestAppOptions {
+        secondary_addrs: "127.0.0.1".to_string(),
+        ..TestAppOptions::default()
+    })
+    .await;
+    let zone_name = app.zone_name("prune.example");
+    app.create_zone_cli(&zone_name, "3600").await;
+    for (index, name) in ["a", "b", "c"].iter().enumerate() {
+        let value = format!("192.0.2.{}", index + 1);
+        app.run_cli_success(&[
+            "record", "create", "--name", name, "--type", "A", "--value", &value, "--zone",
+            &zone_name,
+        ])
+        .await;
+    }
+    let port = app.dns_port();
+    assert!(is_incremental(&ixfr(port, &zone_name, 2).unwrap()));
+
+    let serials = || async {
+        let snapshots = app
+            .run_cli_success(&["zone", "snapshot", "list", &zone_name, "--output", "json"])
+            .await;
+        let snapshots: Value = serde_json::from_str(&snapshots).unwrap();
+        snapshots["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["serial"].as_i64().unwrap())
+            .collect::<Vec<_>>()
+    };
+
+    let preview = app
+        .run_cli_success(&[
+            "zone",
+            "history",
+            "prune",
+            &zone_name,
+            "--keep-serials",
+            "2",
+            "--dry-run",
+            "--output",
+            "json",
+        ])
+        .await;
+    let preview: Value = serde_json::from_str(&preview).unwrap();
+    assert_eq!(preview["dry_run"], true);
+    assert_eq!(preview["zones"][0]["oldest_retained_serial"], 3);
+    assert_eq!(preview["zones"][0]["snapshots_removed"], 2);
+    assert_eq!(serials().await, [4, 3, 2, 1]);
+
+    let pruned = app
+        .run_cli_success(&[
+            "zone",
+            "history",
+            "prune",
+            &zone_name,
+            "--keep-serials",
+            "2",
+        ])
+        .await;
+    assert!(pruned.contains("Pruned 2 snapshot(s)"), "{pruned}");
+    assert_eq!(serials().await, [4, 3]);
+
+    // Serial 2 is gone: its secondary gets the whole zone instead.
+    let fallback = ixfr(port, &zone_name, 2).unwrap();
+    assert_eq!(fallback[0].header().rcode(), Rcode::NOERROR);
+    assert!(!is_incremental(&fallback));
+    assert!(is_incremental(&ixfr(port, &zone_name, 3).unwrap()));
+
+    // With no [history] limits configured, a bare prune has nothing to apply.
+    let args = ["zone", "history", "prune", &zone_name];
+    let output = app.run_cli(&args).await;
+    assert_cli_failure_contains(&args, &output, "no history retention is configured");
+}
+
+#[tokio::test]
+#[serial_test::serial(bindizr_e2e)]
+async fn ixfr_follows_the_serial_across_a_wrap() {
+    let app = TestApp::start_with_options(TestAppOptions {
+        secondary_addrs: "127.0.0.1".to_string(),
+        ..TestAppOptions::default()
+    })
+    .await;
+    let zone_name = app.zone_name("wrap.example");
+    let primary_ns = format!("ns1.{zone_name}");
+    let start_serial = (u32::MAX - 1).to_string();
+    app.run_cli_success(&[
+        "zone",
+        "create",
+        "--name",
+        &zone_name,
+        "--primary-ns",
+        &primary_ns,
+        "--admin-email",
+        "hostmaster@example.com",
+        "--ttl",
+        "3600",
+        "--serial",
+        &start_serial,
+    ])
+    .await;
+    // 4294967295, then 0 and 1 across the wrap.
+    for name in ["a", "b", "c"] {
+        app.run_cli_success(&[
+            "record",
+            "create",
+            "--name",
+            name,
+            "--type",
+            "A",
+            "--value",
+            "192.0.2.1",
+            "--zone",
+            &zone_name,
+        ])
+        .await;
+    }
+    assert_eq!(app.zone_serial(&zone_name).await, 1);
+
+    let port = app.dns_port();
+    let soa_serials = |messages: &[Message<Vec<u8>>]| -> Vec<u32> {
+        messages
+            .iter()
+            .flat_map(|message| {
+                message
+                    .answer()
+                    .unwrap()
+                    .limit_to::<Soa<_>>()
+                    .map(|record| record.unwrap().data().serial().into_int())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    };
+
+    let delta = ixfr(port, &zone_name, u32::MAX - 1).unwrap();
+    assert!(is_incremental(&delta));
+    assert_eq!(
+        soa_serials(&delta),
+        [1, u32::MAX - 1, u32::MAX, u32::MAX, 0, 0, 1, 1]
+    );
+    assert!(is_incremental(&ixfr(port, &zone_name, 0).unwrap()));
+
+    // A client claiming a serial newer than the zone's gets the whole zone.
+    let ahead = ixfr(port, &zone_name, 2).unwrap();
+    assert_eq!(ahead[0].header().rcode(), Rcode::NOERROR);
+    assert!(!is_incremental(&ahead));
+}
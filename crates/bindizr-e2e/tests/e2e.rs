@@ -1,27 +1,41 @@
 mod common;
 
 mod api {
+    mod catalog;
     mod external_dns;
     mod health;
     mod metrics;
     mod notify;
+    mod notify_target;
     mod openapi;
     mod record;
+    mod secondary;
+    mod template;
+    mod token;
     mod token_policy;
+    mod transfer_acl;
     mod tsig_key;
+    mod webhook;
     mod zone;
 }
 
 mod dns {
+    mod answer;
     mod nsupdate;
+    mod xfr;
 }
 
 mod cli {
+    mod audit;
+    mod catalog;
     mod config;
     mod daemon;
+    mod db;
     mod doctor;
     mod record;
+    mod template;
     mod token;
     mod tsig_key;
+    mod webhook;
     mod zone;
 }
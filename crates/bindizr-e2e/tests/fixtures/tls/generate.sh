@@ -0,0 +1,29 @@
+#!/bin/sh
+# Regenerates the TLS test fixtures: a CA, a server certificate for
+# 127.0.0.1/localhost, and a client certificate for the secondary
+# "secondary.test". Long-lived on purpose; these only ever sign test traffic.
+set -eu
+cd "$(dirname "$0")"
+
+days=36500
+
+openssl req -x509 -newkey ec -pkeyopt ec_paramgen_curve:P-256 -nodes \
+    -keyout ca.key -out ca.pem -days "$days" -subj "/CN=bindizr test CA" \
+    -addext "basicConstraints=critical,CA:TRUE" \
+    -addext "keyUsage=critical,keyCertSign,cRLSign"
+
+issue() {
+    name=$1 subject=$2 san=$3 usage=$4
+    openssl req -newkey ec -pkeyopt ec_paramgen_curve:P-256 -nodes \
+        -keyout "$name.key" -out "$name.csr" -subj "/CN=$subject"
+    printf 'basicConstraints=critical,CA:FALSE\nkeyUsage=critical,digitalSignature\nextendedKeyUsage=%s\nsubjectAltName=%s\n' \
+        "$usage" "$san" > "$name.ext"
+    openssl x509 -req -in "$name.csr" -CA ca.pem -CAkey ca.key -CAcreateserial \
+        -out "$name.pem" -days "$days" -extfile "$name.ext"
+    rm "$name.csr" "$name.ext"
+}
+
+issue server localhost "IP:127.0.0.1,DNS:localhost" serverAuth
+issue client secondary.test "DNS:secondary.test" clientAuth
+
+rm -f ca.key ca.srl
@@ -0,0 +1,201 @@
+//! Change attribution. Each front end opens a scope naming who is acting and
+//! how they reached the daemon; the serial-advancing funnel
+//! ([`crate::zone::ZoneService`]'s snapshot write) stamps it on the serial it
+//! records, and zone deletion on its own entry. A task-local keeps the context
+//! off every write signature, since it only labels a change and never decides
+//! one: authorization stays with the explicit [`crate::authorization::Caller`].
+//!
+//! The audit log is its own table, keyed by zone name rather than zone id, so
+//! a zone's entries outlive the zone and its pruned history. `[history]
+//! audit_keep_days` ages them out separately.
+
+use std::{future::Future, net::IpAddr};
+
+use bindizr_core::config;
+use bindizr_db::repository::AuditFilter;
+use chrono::{DateTime, Utc};
+use rand::RngExt;
+
+use crate::{
+    RepositoryTx,
+    authorization::{Caller, TokenRole},
+    error::ServiceError,
+    log_error,
+    model::{zone::Zone, zone_audit::ZoneAuditEntry, zone_snapshot::ChangeAttribution},
+    repository::RepositoryService,
+    zone::validation::normalize_zone_name,
+};
+
+/// The front end a change arrived through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transport {
+    Http,
+    ExternalDns,
+    Socket,
+    Nsupdate,
+    /// Changes the daemon makes on its own.
+    Internal,
+}
+
+impl Transport {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Transport::Http => "http",
+            Transport::ExternalDns => "external-dns",
+            Transport::Socket => "socket",
+            Transport::Nsupdate => "nsupdate",
+            Transport::Internal => "internal",
+        }
+    }
+}
+
+/// What an audit entry records happening to its zone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AuditAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl AuditAction {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            AuditAction::Created => "created",
+            AuditAction::Updated => "updated",
+            AuditAction::Deleted => "deleted",
+        }
+    }
+}
+
+/// Who is acting in the current task and how they reached the daemon.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditContext {
+    pub actor: String,
+    pub tsig_key: Option<String>,
+    pub source_address: Option<IpAddr>,
+    pub transport: Transport,
+    pub request_id: Option<String>,
+}
+
+impl AuditContext {
+    /// Attribution for writes made outside any front end's scope.
+    pub fn internal() -> Self {
+        Self {
+            actor: "bindizr".to_string(),
+            tsig_key: None,
+            source_address: None,
+            transport: Transport::Internal,
+            request_id: None,
+        }
+    }
+
+    pub(crate) fn attribution(&self) -> ChangeAttribution {
+        ChangeAttribution {
+            actor: Some(self.actor.clone()),
+            tsig_key_name: self.tsig_key.clone(),
+            source_address: self.source_address.map(|addr| addr.to_string()),
+            transport: Some(self.transport.as_str().to_string()),
+            request_id: self.request_id.clone(),
+        }
+    }
+}
+
+tokio::task_local! {
+    static CURRENT: AuditContext;
+}
+
+/// Run `future` with `context` attributed to every change it makes.
+pub async fn scope<F: Future>(context: AuditContext, future: F) -> F::Output {
+    CURRENT.scope(context, future).await
+}
+
+/// The context of the enclosing [`scope`], or [`AuditContext::internal`].
+pub(crate) fn current() -> AuditContext {
+    CURRENT
+        .try_with(Clone::clone)
+        .unwrap_or_else(|_| AuditContext::internal())
+}
+
+/// A fresh request id, for front ends whose client did not send one.
+pub fn new_request_id() -> String {
+    hex::encode(rand::rng().random::<[u8; 8]>())
+}
+
+/// Criteria for [`AuditService::list`]; every field is optional.
+#[derive(Clone, Debug, Default)]
+pub struct AuditQuery {
+    pub zone_name: Option<String>,
+    pub actor: Option<String>,
+    pub transport: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: Option<u32>,
+}
+
+pub struct AuditService;
+
+impl AuditService {
+    /// Record `action` on `zone` at `serial`, attributed to the current
+    /// scope, in the transaction making the change.
+    pub(crate) async fn record_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone: &Zone,
+        serial: i32,
+        action: AuditAction,
+    ) -> Result<(), ServiceError> {
+        RepositoryService::insert_audit_entry_tx(
+            tx,
+            ZoneAuditEntry {
+                zone_name: zone.name.as_str().to_string(),
+                serial,
+                action: Some(action.as_str().to_string()),
+                attribution: current().attribution(),
+                created_at: Utc::now(),
+            },
+        )
+        .await
+        .map_err(|e| {
+            log_error!("Failed to record audit entry: {}", e);
+            ServiceError::internal("Failed to record audit entry".to_string())
+        })
+    }
+
+    /// List audit entries across zones, newest first, including those of
+    /// zones since deleted. The log spans every zone, so scoped tokens may
+    /// not read it.
+    pub async fn list(
+        caller: &Caller,
+        query: AuditQuery,
+    ) -> Result<Vec<ZoneAuditEntry>, ServiceError> {
+        caller.require_role(TokenRole::Viewer, "view the audit log")?;
+
+        let zone_name = match query.zone_name {
+            Some(zone_name) => Some(normalize_zone_name(&zone_name)?.as_str().to_string()),
+            None => None,
+        };
+
+        RepositoryService::list_audit_entries(AuditFilter {
+            zone_name,
+            actor: query.actor,
+            transport: query.transport,
+            since: query.since,
+            until: query.until,
+            limit: Some(query.limit.unwrap_or(100)),
+        })
+        .await
+    }
+
+    /// Delete the entries older than `[history] audit_keep_days`, returning
+    /// how many; a no-op while that is 0.
+    pub async fn prune_expired() -> Result<u64, ServiceError> {
+        let keep_days = config::get_bindizr_config().history.audit_keep_days;
+        if keep_days == 0 {
+            return Ok(0);
+        }
+        let before = Utc::now() - chrono::Duration::days(i64::from(keep_days));
+        RepositoryService::delete_audit_entries_before(before).await
+    }
+}
+
+#[cfg(test)]
+mod tests;
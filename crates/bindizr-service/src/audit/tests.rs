@@ -0,0 +1,49 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use super::*;
+
+fn http_context() -> AuditContext {
+    AuditContext {
+        actor: "token:ci".to_string(),
+        tsig_key: None,
+        source_address: Some(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 7))),
+        transport: Transport::Http,
+        request_id: Some("abc123".to_string()),
+    }
+}
+
+#[test]
+fn changes_outside_a_scope_are_attributed_to_the_daemon() {
+    let attribution = current().attribution();
+
+    assert_eq!(attribution.actor.as_deref(), Some("bindizr"));
+    assert_eq!(attribution.transport.as_deref(), Some("internal"));
+    assert_eq!(attribution.source_address, None);
+    assert_eq!(attribution.request_id, None);
+}
+
+#[test]
+fn a_scope_attributes_its_context_to_nested_work() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let inside = runtime.block_on(scope(http_context(), async { current() }));
+
+    assert_eq!(inside, http_context());
+    let attribution = inside.attribution();
+    assert_eq!(attribution.actor.as_deref(), Some("token:ci"));
+    assert_eq!(attribution.source_address.as_deref(), Some("192.0.2.7"));
+    assert_eq!(attribution.transport.as_deref(), Some("http"));
+    assert_eq!(attribution.request_id.as_deref(), Some("abc123"));
+    assert_eq!(current().actor, "bindizr");
+}
+
+#[test]
+fn request_ids_are_random_hex() {
+    let first = new_request_id();
+
+    assert_eq!(first.len(), 16);
+    assert!(first.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_ne!(first, new_request_id());
+}
@@ -1,7 +1,8 @@
 //! Caller identity and zone-scope authorization. Scoped tokens are the HTTP
 //! twin of non-global TSIG keys: record-plane only, within
 //! `zone_token_policies` grants matched by the nsupdate pattern/type rules.
-//! Invisible zones read as 404, denied writes as 403.
+//! Invisible zones read as 404, denied writes as 403. Tokens with any other
+//! [`TokenRole`] see every zone and may do what their role includes.
 //!
 //! Every service operation a front end can reach takes a [`Caller`] and
 //! decides its own authorization; a transport never gates on its own. The
@@ -15,6 +16,7 @@ use bindizr_core::dns::name::OwnerName;
 use bindizr_db::repository::LockLevel;
 use chrono::{Duration, Utc};
 
+pub use crate::model::api_token::TokenRole;
 use crate::{
     RepositoryTx,
     error::ServiceError,
@@ -27,12 +29,14 @@ use crate::{
     token::hash_token,
 };
 
-/// The identity a request acts as. The daemon socket and disabled
-/// authentication act as `Global`; scoped tokens carry their grants,
+/// The identity a request acts as. The daemon socket, disabled
+/// authentication and admin tokens act as `Global`; viewer, record-editor and
+/// zone-admin tokens as their `Role`; scoped tokens carry their grants,
 /// preloaded once per request by the auth middleware.
 #[derive(Debug, Clone)]
 pub enum Caller {
     Global,
+    Role(TokenRole),
     Token {
         id: i32,
         grants: Arc<[ZoneTokenPolicy]>,
@@ -47,40 +51,62 @@ pub(crate) struct RecordWrite<'a> {
 }
 
 impl Caller {
-    fn is_global(&self) -> bool {
-        matches!(self, Caller::Global)
+    /// Whether the caller holds every right of `role`.
+    fn has_role(&self, role: TokenRole) -> bool {
+        match self {
+            Caller::Global => true,
+            Caller::Role(held) => held.includes(role),
+            Caller::Token { .. } => false,
+        }
     }
 
     /// Resolve who a Bearer token acts as: validate the token, then preload a
     /// scoped token's grants so the rest of the request decides against one
-    /// read.
-    pub async fn authenticate(bearer_token: &str) -> Result<Caller, ServiceError> {
+    /// read. Also returns the token's name, which the request's changes are
+    /// attributed to.
+    pub async fn authenticate(bearer_token: &str) -> Result<(Caller, String), ServiceError> {
         let token = validate_token(bearer_token).await?;
-        if token.is_global {
-            return Ok(Caller::Global);
+        match token.role {
+            TokenRole::Admin => return Ok((Caller::Global, token.name)),
+            TokenRole::Scoped => {}
+            role => return Ok((Caller::Role(role), token.name)),
         }
         let grants = RepositoryService::list_zone_token_policies_by_token_id(token.id).await?;
-        Ok(Caller::Token {
-            id: token.id,
-            grants: grants.into(),
-        })
+        Ok((
+            Caller::Token {
+                id: token.id,
+                grants: grants.into(),
+            },
+            token.name,
+        ))
     }
 
-    /// Reject non-global callers for zone-plane and management operations.
-    pub(crate) fn require_global(&self, action: &str) -> Result<(), ServiceError> {
-        if self.is_global() {
+    /// Reject callers whose role does not include `role`. Scoped tokens hold
+    /// no role, so they fail every check here.
+    pub fn require_role(&self, role: TokenRole, action: &str) -> Result<(), ServiceError> {
+        if self.has_role(role) {
             return Ok(());
         }
+        let required = if role == TokenRole::Admin {
+            "a global (admin) API token".to_string()
+        } else {
+            format!("an API token with the '{}' role", role)
+        };
         Err(ServiceError::forbidden(format!(
-            "a global API token is required to {}",
-            action
+            "{} is required to {}",
+            required, action
         )))
     }
 
+    /// Reject all but global callers, for managing credentials and policies.
+    pub(crate) fn require_global(&self, action: &str) -> Result<(), ServiceError> {
+        self.require_role(TokenRole::Admin, action)
+    }
+
     /// Zone ids the caller may see; `None` means unrestricted.
     pub(crate) fn visible_zone_ids(&self) -> Option<HashSet<i32>> {
         match self {
-            Caller::Global => None,
+            Caller::Global | Caller::Role(_) => None,
             Caller::Token { grants, .. } => Some(grants.iter().map(|p| p.zone_id).collect()),
         }
     }
@@ -89,7 +115,7 @@ impl Caller {
     /// unrestricted. List queries join it against the policies in SQL.
     pub(crate) fn scope_token_id(&self) -> Option<i32> {
         match self {
-            Caller::Global => None,
+            Caller::Global | Caller::Role(_) => None,
             Caller::Token { id, .. } => Some(*id),
         }
     }
@@ -97,7 +123,7 @@ impl Caller {
     /// Whether the caller may see `zone_id`.
     pub(crate) fn zone_visible(&self, zone_id: i32) -> bool {
         match self {
-            Caller::Global => true,
+            Caller::Global | Caller::Role(_) => true,
             Caller::Token { grants, .. } => grants.iter().any(|p| p.zone_id == zone_id),
         }
     }
@@ -123,6 +149,10 @@ impl Caller {
     ) -> Result<(), ServiceError> {
         match self {
             Caller::Global => Ok(()),
+            Caller::Role(_) => self.require_role(
+                TokenRole::RecordEditor,
+                &format!("manage records in zone '{}'", zone.name),
+            ),
             Caller::Token { id, .. } => {
                 let policies = RepositoryService::list_zone_token_policies_by_zone_and_token_tx(
                     tx,
@@ -162,7 +192,7 @@ fn authorize_with_policies(
                 write
                     .record_type
                     .map(RecordType::as_str)
-                    .unwrap_or("records"),
+                    .unwrap_or("records".into()),
                 zone.name
             )));
         }
@@ -192,6 +222,17 @@ async fn validate_token(token_str: &str) -> Result<ApiToken, ServiceError> {
         }
     };
 
+    // Matched the secret a rotation replaced: good only through the overlap.
+    if stored_token.token != token_hash
+        && stored_token
+            .previous_token_expires_at
+            .is_none_or(|expires_at| Utc::now() >= expires_at)
+    {
+        return Err(ServiceError::invalid_token(
+            "Token has been rotated".to_string(),
+        ));
+    }
+
     if let Some(expires_at) = &stored_token.expires_at
         && Utc::now() >= *expires_at
     {
@@ -3,10 +3,14 @@ use std::sync::Arc;
 use bindizr_core::dns::name::{OwnerName, ZoneName};
 use chrono::Utc;
 
-use super::{Caller, RecordWrite, authorize_with_policies};
+use super::{Caller, RecordWrite, TokenRole, authorize_with_policies};
 use crate::{
     error::ErrorCode,
-    model::{record::RecordType, zone::Zone, zone_token_policy::ZoneTokenPolicy},
+    model::{
+        record::RecordType,
+        zone::{SerialPolicy, Zone},
+        zone_token_policy::ZoneTokenPolicy,
+    },
 };
 
 fn test_zone() -> Zone {
@@ -17,6 +21,7 @@ fn test_zone() -> Zone {
         admin_email: "hostmaster@example.com".to_string(),
         ttl: 3600,
         serial: 1,
+        serial_policy: SerialPolicy::Increment,
         refresh: 7200,
         retry: 3600,
         expire: 604800,
@@ -63,6 +68,43 @@ fn require_global_rejects_scoped_tokens() {
     assert!(err.message.contains("create zones"));
 }
 
+#[test]
+fn require_role_admits_the_role_and_those_above_it() {
+    let viewer = Caller::Role(TokenRole::Viewer);
+    let zone_admin = Caller::Role(TokenRole::ZoneAdmin);
+
+    assert!(viewer.require_role(TokenRole::Viewer, "read").is_ok());
+    assert!(
+        zone_admin
+            .require_role(TokenRole::RecordEditor, "edit")
+            .is_ok()
+    );
+    assert!(
+        Caller::Global
+            .require_role(TokenRole::ZoneAdmin, "create zones")
+            .is_ok()
+    );
+
+    let err = viewer
+        .require_role(TokenRole::ZoneAdmin, "create zones")
+        .unwrap_err();
+    assert_eq!(err.code, ErrorCode::Forbidden);
+    assert_eq!(
+        err.message,
+        "an API token with the 'zone-admin' role is required to create zones"
+    );
+    assert!(zone_admin.require_global("manage API tokens").is_err());
+}
+
+#[test]
+fn role_callers_see_every_zone() {
+    let viewer = Caller::Role(TokenRole::Viewer);
+
+    assert!(viewer.zone_visible(42));
+    assert!(viewer.visible_zone_ids().is_none());
+    assert!(viewer.scope_token_id().is_none());
+}
+
 #[test]
 fn authorize_grants_writes_matching_pattern_and_types() {
     let policies = [policy("*", "*")];
@@ -0,0 +1,405 @@
+//! Named catalog zones and the RFC 9432 properties of their member zones.
+//!
+//! Every zone is listed in exactly one catalog: `catalog.bind` unless its
+//! `catalog` property names another. A zone whose `coo` property names a
+//! second catalog is listed there too, which is how RFC 9432 hands a member
+//! from one catalog (and secondary pool) to another.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use bindizr_core::dns::{CATALOG_ZONE_NAME, is_catalog_zone, name::ZoneName};
+use chrono::Utc;
+
+use crate::{
+    authorization::{Caller, TokenRole},
+    error::ServiceError,
+    log_warn,
+    model::catalog::{Catalog, ZoneCatalogProperty},
+    repository::RepositoryService,
+    zone::ZoneService,
+};
+
+const CATALOG_PROPERTY: &str = "catalog";
+const GROUP_PROPERTY: &str = "group";
+const COO_PROPERTY: &str = "coo";
+const EXT_PREFIX: &str = "ext.";
+
+/// Longest property value: one TXT character-string.
+const MAX_PROPERTY_VALUE_LEN: usize = 255;
+
+/// Creates, lists, and deletes named catalogs, and sets the catalog
+/// properties of member zones.
+pub struct CatalogService;
+
+/// A zone's catalog membership and member properties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneCatalogProperties {
+    /// The catalog listing the zone.
+    pub catalog: String,
+    /// RFC 9432 `group`: how consumers should provision the zone.
+    pub group: Option<String>,
+    /// RFC 9432 `coo`: the catalog taking the zone over.
+    pub coo: Option<String>,
+    /// Custom properties served as TXT under `ext`, keyed by property name.
+    pub ext: BTreeMap<String, String>,
+}
+
+impl Default for ZoneCatalogProperties {
+    fn default() -> Self {
+        ZoneCatalogProperties {
+            catalog: CATALOG_ZONE_NAME.to_string(),
+            group: None,
+            coo: None,
+            ext: BTreeMap::new(),
+        }
+    }
+}
+
+/// One member zone as a catalog lists it. `coo` is only set in the catalog
+/// the zone is leaving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogMember {
+    pub zone_name: String,
+    pub group: Option<String>,
+    pub coo: Option<String>,
+    pub ext: BTreeMap<String, String>,
+}
+
+impl CatalogService {
+    /// Create a named catalog. Its name must be a valid zone name that is
+    /// neither `catalog.bind` nor a hosted zone.
+    pub async fn create(
+        caller: &Caller,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<Catalog, ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage catalogs")?;
+
+        let name = normalize_catalog_name(name)?;
+        if is_catalog_zone(&name) {
+            return Err(ServiceError::invalid_input(format!(
+                "'{}' is the default catalog and always exists",
+                CATALOG_ZONE_NAME
+            )));
+        }
+        if RepositoryService::get_catalog_by_name(&name)
+            .await?
+            .is_some()
+        {
+            return Err(ServiceError::catalog_conflict(format!(
+                "Catalog with name '{}' already exists",
+                name
+            )));
+        }
+        if ZoneService::find_by_name(&name).await?.is_some() {
+            return Err(ServiceError::catalog_conflict(format!(
+                "A zone named '{}' already exists",
+                name
+            )));
+        }
+
+        RepositoryService::create_catalog(Catalog {
+            id: 0,
+            name,
+            description: description
+                .map(str::trim)
+                .filter(|description| !description.is_empty())
+                .map(str::to_string),
+            created_at: Utc::now(),
+        })
+        .await
+    }
+
+    /// List the named catalogs; `catalog.bind` is implicit.
+    pub async fn list(caller: &Caller) -> Result<Vec<Catalog>, ServiceError> {
+        caller.require_role(TokenRole::Viewer, "view catalogs")?;
+        RepositoryService::list_catalogs().await
+    }
+
+    /// Delete a named catalog. Refused while any zone is listed in it or is
+    /// being handed over to it.
+    pub async fn delete(caller: &Caller, name: &str) -> Result<(), ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage catalogs")?;
+
+        let catalog = Self::lookup_by_name(name).await?;
+        let referencing: BTreeSet<i32> = RepositoryService::list_zone_catalog_properties()
+            .await?
+            .into_iter()
+            .filter(|property| {
+                (property.name == CATALOG_PROPERTY || property.name == COO_PROPERTY)
+                    && property.value == catalog.name
+            })
+            .map(|property| property.zone_id)
+            .collect();
+        if !referencing.is_empty() {
+            return Err(ServiceError::catalog_in_use(
+                &catalog.name,
+                referencing.len(),
+            ));
+        }
+
+        RepositoryService::delete_catalog(catalog.id).await
+    }
+
+    /// The catalog membership and properties of a zone.
+    pub async fn get_zone(
+        caller: &Caller,
+        zone_name: &str,
+    ) -> Result<ZoneCatalogProperties, ServiceError> {
+        caller.require_role(TokenRole::Viewer, "view catalog properties")?;
+
+        let zone = ZoneService::lookup_by_name(zone_name).await?;
+        let properties =
+            RepositoryService::list_zone_catalog_properties_by_zone_id(zone.id).await?;
+        Ok(from_properties(&properties))
+    }
+
+    /// Replace the catalog membership and properties of a zone. `catalog`
+    /// and `coo` must name `catalog.bind` or a named catalog; omitting
+    /// `catalog` returns the zone to `catalog.bind`. Every catalog the zone
+    /// enters or leaves is notified.
+    pub async fn set_zone(
+        caller: &Caller,
+        zone_name: &str,
+        properties: ZoneCatalogProperties,
+    ) -> Result<ZoneCatalogProperties, ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage catalog properties")?;
+
+        let zone = ZoneService::lookup_by_name(zone_name).await?;
+        let properties = Self::validate(properties).await?;
+
+        let previous = from_properties(
+            &RepositoryService::list_zone_catalog_properties_by_zone_id(zone.id).await?,
+        );
+        RepositoryService::replace_zone_catalog_properties(zone.id, &to_properties(&properties))
+            .await?;
+
+        notify_catalogs(catalogs_listing(&previous).chain(catalogs_listing(&properties))).await;
+        Ok(properties)
+    }
+
+    /// The canonical name of the catalog `name` refers to, if it is
+    /// `catalog.bind` or a named catalog. This is the unchecked lookup the
+    /// transfer path uses.
+    pub async fn resolve(name: &str) -> Result<Option<String>, ServiceError> {
+        if is_catalog_zone(name) {
+            return Ok(Some(CATALOG_ZONE_NAME.to_string()));
+        }
+        let Ok(name) = ZoneName::parse(name) else {
+            return Ok(None);
+        };
+        Ok(RepositoryService::get_catalog_by_name(name.as_str())
+            .await?
+            .map(|catalog| catalog.name))
+    }
+
+    /// Every catalog served: `catalog.bind` first, then the named ones.
+    pub async fn catalog_names() -> Result<Vec<String>, ServiceError> {
+        let mut names = vec![CATALOG_ZONE_NAME.to_string()];
+        names.extend(
+            RepositoryService::list_catalogs()
+                .await?
+                .into_iter()
+                .map(|catalog| catalog.name),
+        );
+        Ok(names)
+    }
+
+    /// The members of catalog `name` (as [`Self::resolve`] returns it), in
+    /// zone name order. The catalog zone is not a member of itself.
+    pub async fn members(name: &str) -> Result<Vec<CatalogMember>, ServiceError> {
+        let zones = ZoneService::list().await?;
+        let mut properties_by_zone: HashMap<i32, Vec<ZoneCatalogProperty>> = HashMap::new();
+        for property in RepositoryService::list_zone_catalog_properties().await? {
+            properties_by_zone
+                .entry(property.zone_id)
+                .or_default()
+                .push(property);
+        }
+
+        let mut members: Vec<CatalogMember> = zones
+            .into_iter()
+            .filter(|zone| zone.name.as_str() != name)
+            .filter_map(|zone| {
+                let properties = properties_by_zone
+                    .get(&zone.id)
+                    .map(|properties| from_properties(properties))
+                    .unwrap_or_default();
+                let coo = if properties.catalog == name {
+                    properties.coo
+                } else if properties.coo.as_deref() == Some(name) {
+                    None
+                } else {
+                    return None;
+                };
+                Some(CatalogMember {
+                    zone_name: zone.name.to_string(),
+                    group: properties.group,
+                    coo,
+                    ext: properties.ext,
+                })
+            })
+            .collect();
+        members.sort_by(|a, b| a.zone_name.cmp(&b.zone_name));
+        Ok(members)
+    }
+
+    /// The catalogs listing a zone, to notify when it changes.
+    pub(crate) async fn catalogs_of_zone(zone_id: i32) -> Result<Vec<String>, ServiceError> {
+        let properties =
+            RepositoryService::list_zone_catalog_properties_by_zone_id(zone_id).await?;
+        Ok(catalogs_listing(&from_properties(&properties)).collect())
+    }
+
+    async fn lookup_by_name(name: &str) -> Result<Catalog, ServiceError> {
+        let normalized = normalize_catalog_name(name)?;
+        RepositoryService::get_catalog_by_name(&normalized)
+            .await?
+            .ok_or_else(|| ServiceError::catalog_not_found(name))
+    }
+
+    /// Check and normalize properties before they are stored.
+    async fn validate(
+        properties: ZoneCatalogProperties,
+    ) -> Result<ZoneCatalogProperties, ServiceError> {
+        let catalog = Self::resolve_existing(&properties.catalog).await?;
+        let coo = match properties.coo.as_deref().map(str::trim) {
+            Some(coo) if !coo.is_empty() => {
+                let coo = Self::resolve_existing(coo).await?;
+                if coo == catalog {
+                    return Err(ServiceError::invalid_input(format!(
+                        "coo must name another catalog than '{}', which already lists the zone",
+                        catalog
+                    )));
+                }
+                Some(coo)
+            }
+            _ => None,
+        };
+        let group = match properties.group.as_deref().map(str::trim) {
+            Some(group) if !group.is_empty() => Some(validate_property_value("group", group)?),
+            _ => None,
+        };
+        let ext = properties
+            .ext
+            .iter()
+            .map(|(name, value)| {
+                let name = normalize_ext_name(name)?;
+                let value = validate_property_value(&format!("ext property '{}'", name), value)?;
+                Ok((name, value))
+            })
+            .collect::<Result<_, ServiceError>>()?;
+
+        Ok(ZoneCatalogProperties {
+            catalog,
+            group,
+            coo,
+            ext,
+        })
+    }
+
+    /// Like [`Self::resolve`], but an empty name means `catalog.bind` and an
+    /// unknown one is an error.
+    async fn resolve_existing(name: &str) -> Result<String, ServiceError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Ok(CATALOG_ZONE_NAME.to_string());
+        }
+        normalize_catalog_name(name)?;
+        Self::resolve(name)
+            .await?
+            .ok_or_else(|| ServiceError::catalog_not_found(name))
+    }
+}
+
+/// The catalogs a zone with `properties` is listed in.
+fn catalogs_listing(properties: &ZoneCatalogProperties) -> impl Iterator<Item = String> + '_ {
+    std::iter::once(properties.catalog.clone()).chain(properties.coo.clone())
+}
+
+/// What a catalog serves to a secondary changes with its members' properties.
+pub(crate) async fn notify_catalogs(catalogs: impl IntoIterator<Item = String>) {
+    let catalogs: BTreeSet<String> = catalogs.into_iter().collect();
+    for catalog in catalogs {
+        if let Err(e) = crate::notify::send_notify_after_update(Some(&catalog)).await {
+            log_warn!("Failed to send NOTIFY for {}: {}", catalog, e);
+        }
+    }
+}
+
+fn from_properties(properties: &[ZoneCatalogProperty]) -> ZoneCatalogProperties {
+    let mut parsed = ZoneCatalogProperties::default();
+    for property in properties {
+        match property.name.as_str() {
+            CATALOG_PROPERTY => parsed.catalog = property.value.clone(),
+            GROUP_PROPERTY => parsed.group = Some(property.value.clone()),
+            COO_PROPERTY => parsed.coo = Some(property.value.clone()),
+            name => {
+                if let Some(ext) = name.strip_prefix(EXT_PREFIX) {
+                    parsed.ext.insert(ext.to_string(), property.value.clone());
+                }
+            }
+        }
+    }
+    parsed
+}
+
+/// The stored rows for `properties`; membership in `catalog.bind` needs none.
+fn to_properties(properties: &ZoneCatalogProperties) -> Vec<(String, String)> {
+    let mut rows = Vec::new();
+    if !is_catalog_zone(&properties.catalog) {
+        rows.push((CATALOG_PROPERTY.to_string(), properties.catalog.clone()));
+    }
+    if let Some(group) = &properties.group {
+        rows.push((GROUP_PROPERTY.to_string(), group.clone()));
+    }
+    if let Some(coo) = &properties.coo {
+        rows.push((COO_PROPERTY.to_string(), coo.clone()));
+    }
+    for (name, value) in &properties.ext {
+        rows.push((format!("{}{}", EXT_PREFIX, name), value.clone()));
+    }
+    rows
+}
+
+fn normalize_catalog_name(name: &str) -> Result<String, ServiceError> {
+    ZoneName::parse(name)
+        .map(|name| name.to_string())
+        .map_err(|e| ServiceError::invalid_input(format!("invalid catalog name '{}': {}", name, e)))
+}
+
+/// An ext property name is one or more LDH labels, served as
+/// `<name>.ext.<member>.zones.<catalog>`.
+pub(crate) fn normalize_ext_name(name: &str) -> Result<String, ServiceError> {
+    ZoneName::parse(name)
+        .map(|name| name.to_string())
+        .map_err(|e| {
+            ServiceError::invalid_input(format!("invalid ext property name '{}': {}", name, e))
+        })
+}
+
+/// Property values are served as a single TXT string.
+pub(crate) fn validate_property_value(what: &str, value: &str) -> Result<String, ServiceError> {
+    if value.is_empty() {
+        return Err(ServiceError::invalid_input(format!(
+            "{} cannot be empty",
+            what
+        )));
+    }
+    if value.len() > MAX_PROPERTY_VALUE_LEN {
+        return Err(ServiceError::invalid_input(format!(
+            "{} is longer than {} bytes",
+            what, MAX_PROPERTY_VALUE_LEN
+        )));
+    }
+    if value.chars().any(char::is_control) {
+        return Err(ServiceError::invalid_input(format!(
+            "{} cannot contain control characters",
+            what
+        )));
+    }
+    Ok(value.to_string())
+}
+
+#[cfg(test)]
+mod tests;
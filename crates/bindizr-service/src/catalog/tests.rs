@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn properties_round_trip_through_rows() {
+    let properties = ZoneCatalogProperties {
+        catalog: "pool-a.catalog.example".to_string(),
+        group: Some("signed".to_string()),
+        coo: Some("pool-b.catalog.example".to_string()),
+        ext: BTreeMap::from([("serial-policy".to_string(), "unixtime".to_string())]),
+    };
+
+    let rows = to_properties(&properties);
+    assert!(rows.contains(&("ext.serial-policy".to_string(), "unixtime".to_string())));
+
+    let stored: Vec<ZoneCatalogProperty> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(id, (name, value))| ZoneCatalogProperty {
+            id: id as i32,
+            zone_id: 1,
+            name,
+            value,
+        })
+        .collect();
+    assert_eq!(from_properties(&stored), properties);
+}
+
+#[test]
+fn default_catalog_membership_stores_no_row() {
+    assert!(to_properties(&ZoneCatalogProperties::default()).is_empty());
+    assert_eq!(from_properties(&[]).catalog, CATALOG_ZONE_NAME);
+}
+
+#[test]
+fn ext_names_are_ldh_labels() {
+    assert_eq!(
+        normalize_ext_name("Allow-Transfer").unwrap(),
+        "allow-transfer"
+    );
+    assert_eq!(normalize_ext_name("a.b").unwrap(), "a.b");
+    for name in ["", "has space", "-dash", "under_score"] {
+        assert!(
+            normalize_ext_name(name).is_err(),
+            "{name:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn property_values_fit_one_txt_string() {
+    assert!(validate_property_value("group", "signed").is_ok());
+    assert!(validate_property_value("group", "").is_err());
+    assert!(validate_property_value("group", &"x".repeat(256)).is_err());
+    assert!(validate_property_value("group", "a\nb").is_err());
+}
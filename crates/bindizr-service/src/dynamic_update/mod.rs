@@ -161,7 +161,7 @@ impl DynamicUpdateService {
 
             // An exhausted serial cannot advance, so refuse rather than commit
             // changes secondaries could never detect.
-            let new_serial = generate_serial(Some(zone.serial))?;
+            let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
             let mut changed = false;
 
             for op in &update.updates {
@@ -240,7 +240,7 @@ async fn authorize_key(
                 "TSIG key '{}' is not authorized to update '{}' ({}) in zone '{}'",
                 key.name,
                 owner,
-                op.record_type().map_or("ANY", RecordType::as_str),
+                op.record_type().map_or("ANY".into(), RecordType::as_str),
                 zone.name
             )));
         }
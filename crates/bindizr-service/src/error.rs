@@ -1,5 +1,9 @@
 use std::fmt;
 
+use bindizr_core::dns::serial;
+
+use crate::model::record::RecordType;
+
 /// Machine-readable error codes exposed to API and CLI clients. Each code maps
 /// to one HTTP status; the SCREAMING_SNAKE_CASE wire name is the public
 /// contract.
@@ -22,12 +26,24 @@ pub enum ErrorCode {
     TsigKeyInUse,
     TsigPolicyNotFound,
     TokenPolicyNotFound,
+    TransferAclNotFound,
+    NotifyTargetNotFound,
+    CatalogNotFound,
+    CatalogConflict,
+    CatalogInUse,
+    WebhookNotFound,
+    WebhookConflict,
+    TemplateNotFound,
+    TemplateConflict,
+    TemplateInUse,
     Unauthorized,
     InvalidToken,
     Forbidden,
+    PreconditionFailed,
     PayloadTooLarge,
     UnsupportedMediaType,
     Internal,
+    TransferFailed,
 }
 
 impl ErrorCode {
@@ -50,12 +66,24 @@ impl ErrorCode {
             ErrorCode::TsigKeyInUse => "TSIG_KEY_IN_USE",
             ErrorCode::TsigPolicyNotFound => "TSIG_POLICY_NOT_FOUND",
             ErrorCode::TokenPolicyNotFound => "TOKEN_POLICY_NOT_FOUND",
+            ErrorCode::TransferAclNotFound => "TRANSFER_ACL_NOT_FOUND",
+            ErrorCode::NotifyTargetNotFound => "NOTIFY_TARGET_NOT_FOUND",
+            ErrorCode::CatalogNotFound => "CATALOG_NOT_FOUND",
+            ErrorCode::CatalogConflict => "CATALOG_CONFLICT",
+            ErrorCode::CatalogInUse => "CATALOG_IN_USE",
+            ErrorCode::WebhookNotFound => "WEBHOOK_NOT_FOUND",
+            ErrorCode::WebhookConflict => "WEBHOOK_CONFLICT",
+            ErrorCode::TemplateNotFound => "TEMPLATE_NOT_FOUND",
+            ErrorCode::TemplateConflict => "TEMPLATE_CONFLICT",
+            ErrorCode::TemplateInUse => "TEMPLATE_IN_USE",
             ErrorCode::Unauthorized => "UNAUTHORIZED",
             ErrorCode::InvalidToken => "INVALID_TOKEN",
             ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::PreconditionFailed => "PRECONDITION_FAILED",
             ErrorCode::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
             ErrorCode::UnsupportedMediaType => "UNSUPPORTED_MEDIA_TYPE",
             ErrorCode::Internal => "INTERNAL",
+            ErrorCode::TransferFailed => "TRANSFER_FAILED",
         }
     }
 
@@ -80,12 +108,24 @@ impl ErrorCode {
             "TSIG_KEY_IN_USE" => ErrorCode::TsigKeyInUse,
             "TSIG_POLICY_NOT_FOUND" => ErrorCode::TsigPolicyNotFound,
             "TOKEN_POLICY_NOT_FOUND" => ErrorCode::TokenPolicyNotFound,
+            "TRANSFER_ACL_NOT_FOUND" => ErrorCode::TransferAclNotFound,
+            "NOTIFY_TARGET_NOT_FOUND" => ErrorCode::NotifyTargetNotFound,
+            "CATALOG_NOT_FOUND" => ErrorCode::CatalogNotFound,
+            "CATALOG_CONFLICT" => ErrorCode::CatalogConflict,
+            "CATALOG_IN_USE" => ErrorCode::CatalogInUse,
+            "WEBHOOK_NOT_FOUND" => ErrorCode::WebhookNotFound,
+            "WEBHOOK_CONFLICT" => ErrorCode::WebhookConflict,
+            "TEMPLATE_NOT_FOUND" => ErrorCode::TemplateNotFound,
+            "TEMPLATE_CONFLICT" => ErrorCode::TemplateConflict,
+            "TEMPLATE_IN_USE" => ErrorCode::TemplateInUse,
             "UNAUTHORIZED" => ErrorCode::Unauthorized,
             "INVALID_TOKEN" => ErrorCode::InvalidToken,
             "FORBIDDEN" => ErrorCode::Forbidden,
+            "PRECONDITION_FAILED" => ErrorCode::PreconditionFailed,
             "PAYLOAD_TOO_LARGE" => ErrorCode::PayloadTooLarge,
             "UNSUPPORTED_MEDIA_TYPE" => ErrorCode::UnsupportedMediaType,
             "INTERNAL" => ErrorCode::Internal,
+            "TRANSFER_FAILED" => ErrorCode::TransferFailed,
             _ => return None,
         })
     }
@@ -105,15 +145,27 @@ impl ErrorCode {
             | ErrorCode::SnapshotNotFound
             | ErrorCode::TsigKeyNotFound
             | ErrorCode::TsigPolicyNotFound
-            | ErrorCode::TokenPolicyNotFound => 404,
+            | ErrorCode::TokenPolicyNotFound
+            | ErrorCode::TransferAclNotFound
+            | ErrorCode::NotifyTargetNotFound
+            | ErrorCode::CatalogNotFound
+            | ErrorCode::WebhookNotFound
+            | ErrorCode::TemplateNotFound => 404,
             ErrorCode::ZoneConflict
             | ErrorCode::RecordConflict
             | ErrorCode::TokenConflict
             | ErrorCode::TsigKeyConflict
-            | ErrorCode::TsigKeyInUse => 409,
+            | ErrorCode::TsigKeyInUse
+            | ErrorCode::CatalogConflict
+            | ErrorCode::CatalogInUse
+            | ErrorCode::WebhookConflict
+            | ErrorCode::TemplateConflict
+            | ErrorCode::TemplateInUse => 409,
+            ErrorCode::PreconditionFailed => 412,
             ErrorCode::PayloadTooLarge => 413,
             ErrorCode::UnsupportedMediaType => 415,
             ErrorCode::Internal => 500,
+            ErrorCode::TransferFailed => 502,
         }
     }
 }
@@ -179,10 +231,19 @@ impl ServiceError {
         Self::new(ErrorCode::Forbidden, message)
     }
 
+    pub(crate) fn precondition_failed(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::PreconditionFailed, message)
+    }
+
     pub fn internal(message: impl Into<String>) -> Self {
         Self::new(ErrorCode::Internal, message)
     }
 
+    /// A zone transfer from another server failed: the fault is upstream.
+    pub fn transfer_failed(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::TransferFailed, message)
+    }
+
     pub(crate) fn zone_not_found(name: &str) -> Self {
         Self::new(
             ErrorCode::ZoneNotFound,
@@ -197,6 +258,13 @@ impl ServiceError {
         )
     }
 
+    pub(crate) fn rrset_not_found(name: &str, record_type: &RecordType) -> Self {
+        Self::new(
+            ErrorCode::RecordNotFound,
+            format!("RRset '{}' {} not found", name, record_type),
+        )
+    }
+
     pub(crate) fn token_not_found(name: &str) -> Self {
         Self::new(
             ErrorCode::TokenNotFound,
@@ -251,12 +319,90 @@ impl ServiceError {
         )
     }
 
+    pub(crate) fn transfer_acl_not_found(id: i32) -> Self {
+        Self::new(
+            ErrorCode::TransferAclNotFound,
+            format!("Transfer ACL entry with id '{}' not found", id),
+        )
+    }
+
+    pub(crate) fn notify_target_not_found(id: i32) -> Self {
+        Self::new(
+            ErrorCode::NotifyTargetNotFound,
+            format!("NOTIFY target with id '{}' not found", id),
+        )
+    }
+
+    pub(crate) fn catalog_not_found(name: &str) -> Self {
+        Self::new(
+            ErrorCode::CatalogNotFound,
+            format!("Catalog with name '{}' not found", name),
+        )
+    }
+
+    pub(crate) fn catalog_conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::CatalogConflict, message)
+    }
+
+    pub(crate) fn catalog_in_use(name: &str, zone_count: usize) -> Self {
+        Self::new(
+            ErrorCode::CatalogInUse,
+            format!(
+                "Catalog '{}' still lists {} zone{}",
+                name,
+                zone_count,
+                if zone_count == 1 { "" } else { "s" }
+            ),
+        )
+    }
+
+    pub(crate) fn webhook_not_found(name: &str) -> Self {
+        Self::new(
+            ErrorCode::WebhookNotFound,
+            format!("Webhook with name '{}' not found", name),
+        )
+    }
+
+    pub(crate) fn webhook_conflict(name: &str) -> Self {
+        Self::new(
+            ErrorCode::WebhookConflict,
+            format!("Webhook with name '{}' already exists", name),
+        )
+    }
+
+    pub(crate) fn template_not_found(name: &str) -> Self {
+        Self::new(
+            ErrorCode::TemplateNotFound,
+            format!("Template with name '{}' not found", name),
+        )
+    }
+
+    pub(crate) fn template_conflict(name: &str) -> Self {
+        Self::new(
+            ErrorCode::TemplateConflict,
+            format!("Template with name '{}' already exists", name),
+        )
+    }
+
+    pub(crate) fn template_in_use(name: &str, zone_count: usize) -> Self {
+        Self::new(
+            ErrorCode::TemplateInUse,
+            format!(
+                "Template '{}' is still linked to {} zone{}",
+                name,
+                zone_count,
+                if zone_count == 1 { "" } else { "s" }
+            ),
+        )
+    }
+
     pub(crate) fn snapshot_not_found(zone_name: &str, serial: i32) -> Self {
         Self::new(
             ErrorCode::SnapshotNotFound,
             format!(
                 "No snapshot with serial '{}' for zone '{}'",
-                serial, zone_name
+                serial::to_wire(serial),
+                zone_name
             ),
         )
     }
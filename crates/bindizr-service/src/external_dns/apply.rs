@@ -5,7 +5,6 @@ use std::collections::BTreeMap;
 
 use bindizr_core::dns::name::{OwnerName, ZoneName};
 use bindizr_db::repository::LockLevel;
-use chrono::Utc;
 
 use super::{
     ExternalDnsService,
@@ -16,10 +15,13 @@ use crate::{
     error::{ErrorCode, ServiceError},
     log_info, log_warn,
     model::{
-        record::{EXTERNAL_DNS_RECORD_TYPES, Record, RecordType},
+        record::{EXTERNAL_DNS_RECORD_TYPES, RecordType},
         zone::Zone,
     },
-    record::{RecordService, parse_record_type, validate_record_add_constraints_normalized},
+    record::{
+        RecordService, RrsetValue, ZoneOps, ZoneRrsetOp, canonical_rrset_values,
+        compute_zone_change_set, encode_rrset_values, parse_record_type, validate_rrset_shape,
+    },
     repository::RepositoryService,
     serial::generate_serial,
     types::{ExternalDnsChangesRequest, ExternalDnsChangesResponse, ExternalDnsRrset},
@@ -48,30 +50,6 @@ pub(super) struct PendingOp {
     pub(crate) is_delete: bool,
 }
 
-/// The same operation once grouping has decided which zone owns it, so the
-/// owner is relative to that zone.
-#[derive(Debug)]
-pub(super) struct ZoneRrsetOp {
-    pub(crate) name: OwnerName,
-    pub(crate) record_type: RecordType,
-    pub(crate) ttl: Option<i32>,
-    pub(crate) values: Vec<String>,
-}
-
-/// Adds and deletes of one request that resolved to the same zone.
-#[derive(Debug, Default)]
-pub(super) struct ZoneOps {
-    pub(crate) adds: Vec<ZoneRrsetOp>,
-    pub(crate) dels: Vec<ZoneRrsetOp>,
-}
-
-/// The record rows one zone's operations resolve to.
-#[derive(Debug, Default)]
-pub(super) struct ZoneChangeSet {
-    pub(crate) deletes: Vec<Record>,
-    pub(crate) creates: Vec<Record>,
-}
-
 fn parse_supported_record_type(record_type: &str) -> Result<RecordType, ServiceError> {
     let parsed = parse_record_type(record_type)?;
     if !is_supported_record_type(&parsed) {
@@ -94,44 +72,18 @@ fn normalize_ttl(ttl: Option<i32>) -> Result<Option<i32>, ServiceError> {
     }
 }
 
-fn validate_rrset_shape(
-    rrset: &ExternalDnsRrset,
-    record_type: &RecordType,
-) -> Result<(), ServiceError> {
-    if rrset.values.is_empty() {
-        return Err(ServiceError::invalid_input(format!(
-            "RRset '{}' {} must have at least one value",
-            rrset.name, record_type
-        )));
-    }
-    if *record_type == RecordType::CNAME && rrset.values.len() > 1 {
-        return Err(ServiceError::invalid_record_value(format!(
-            "CNAME RRset '{}' must have exactly one value",
-            rrset.name
-        )));
-    }
-    Ok(())
-}
-
 pub(super) fn convert_rrset(rrset: &ExternalDnsRrset) -> Result<RrsetOp, ServiceError> {
     let record_type = parse_supported_record_type(&rrset.record_type)?;
     let name = normalize_lookup_name(&rrset.name)?;
     let ttl = normalize_ttl(rrset.ttl)?;
-    validate_rrset_shape(rrset, &record_type)?;
-
-    // Deduplicate values that normalize identically (e.g. IPv6 spellings).
-    let mut values: Vec<String> = Vec::with_capacity(rrset.values.len());
-    for value in &rrset.values {
-        let encoded = record_type
-            .encoded_value(value, None)
-            .map_err(ServiceError::invalid_record_value)?;
-        if !values
-            .iter()
-            .any(|existing| record_type.values_equal(existing, None, &encoded, None))
-        {
-            values.push(encoded);
-        }
-    }
+    validate_rrset_shape(&rrset.name, &record_type, &rrset.values)?;
+
+    // None of the supported types keeps a priority, so the values alone
+    // identify the rows.
+    let values = encode_rrset_values(&record_type, &rrset.values)?
+        .into_iter()
+        .map(|encoded| encoded.value)
+        .collect();
 
     Ok(RrsetOp {
         name,
@@ -147,25 +99,13 @@ pub(super) fn convert_rrset(rrset: &ExternalDnsRrset) -> Result<RrsetOp, Service
 pub(super) fn adjust_rrset(rrset: &ExternalDnsRrset) -> Result<ExternalDnsRrset, ServiceError> {
     let record_type = parse_supported_record_type(&rrset.record_type)?;
     let ttl = normalize_ttl(rrset.ttl)?;
-    validate_rrset_shape(rrset, &record_type)?;
-
-    let mut values: Vec<String> = Vec::with_capacity(rrset.values.len());
-    for value in &rrset.values {
-        let canonical = match record_type.encoded_value(value, None) {
-            Ok(encoded) => record_type.presentation_rdata(&encoded, None),
-            Err(_) => value.clone(),
-        };
-        if !values.contains(&canonical) {
-            values.push(canonical);
-        }
-    }
-    values.sort();
+    validate_rrset_shape(&rrset.name, &record_type, &rrset.values)?;
 
     Ok(ExternalDnsRrset {
         name: rrset.name.clone(),
         record_type: record_type.to_string(),
         ttl,
-        values,
+        values: canonical_rrset_values(&record_type, &rrset.values),
     })
 }
 
@@ -221,7 +161,15 @@ pub(super) fn group_ops_by_zone(
                 .expect("find_authoritative_zone matched the name inside this zone"),
             record_type: pending.op.record_type,
             ttl: pending.op.ttl,
-            values: pending.op.values,
+            values: pending
+                .op
+                .values
+                .into_iter()
+                .map(|value| RrsetValue {
+                    value,
+                    priority: None,
+                })
+                .collect(),
         };
         let entry = grouped.entry(zone.name.clone()).or_default();
         if pending.is_delete {
@@ -234,101 +182,6 @@ pub(super) fn group_ops_by_zone(
     Ok(grouped)
 }
 
-/// Resolve one zone's operations against its current records; idempotent
-/// operations cancel out, so an effect-free request yields an empty set.
-pub(super) fn compute_zone_change_set(
-    zone: &Zone,
-    existing: &[Record],
-    ops: &ZoneOps,
-) -> Result<ZoneChangeSet, ServiceError> {
-    let mut deletes: Vec<Record> = Vec::new();
-    for del in &ops.dels {
-        for value in &del.values {
-            for row in existing {
-                if row.name == del.name
-                    && row.record_type == del.record_type
-                    && del.record_type.values_equal(&row.value, None, value, None)
-                    && !deletes.iter().any(|d| d.id == row.id)
-                {
-                    deletes.push(row.clone());
-                }
-            }
-        }
-    }
-
-    let mut creates: Vec<Record> = Vec::new();
-    for add in &ops.adds {
-        let ttl = add.ttl.unwrap_or(zone.ttl);
-        for value in &add.values {
-            let same_rdata = |row: &Record| {
-                row.name == add.name
-                    && row.record_type == add.record_type
-                    && add.record_type.values_equal(&row.value, None, value, None)
-            };
-            let matches = |row: &Record| same_rdata(row) && row.ttl == ttl;
-
-            // An unchanged update cancels its own delete instead of rewriting
-            // the row. TTL-sensitive, so a TTL-only update is still a change.
-            if let Some(pos) = deletes.iter().position(&matches) {
-                deletes.remove(pos);
-                continue;
-            }
-            // Idempotent create: a surviving row already holds this rdata. TTL
-            // is excluded to match the duplicate check behind this one, which
-            // would otherwise reject the create as a conflict.
-            if existing
-                .iter()
-                .any(|row| deletes.iter().all(|d| d.id != row.id) && same_rdata(row))
-            {
-                continue;
-            }
-            // Intra-request duplicate create.
-            if creates.iter().any(matches) {
-                continue;
-            }
-
-            creates.push(Record {
-                id: 0,
-                name: add.name.clone(),
-                record_type: add.record_type.clone(),
-                value: value.clone(),
-                ttl,
-                priority: None,
-                zone_id: zone.id,
-                created_at: Utc::now(),
-            });
-        }
-    }
-
-    // Validate each insert against the post-delete state plus earlier inserts,
-    // so CNAME exclusivity and RRset TTL rules see the state they will land in.
-    for (index, create) in creates.iter().enumerate() {
-        let mut same_name: Vec<Record> = existing
-            .iter()
-            .filter(|row| deletes.iter().all(|d| d.id != row.id) && row.name == create.name)
-            .cloned()
-            .collect();
-        same_name.extend(
-            creates[..index]
-                .iter()
-                .filter(|row| row.name == create.name)
-                .cloned(),
-        );
-
-        validate_record_add_constraints_normalized(
-            &same_name,
-            &create.name,
-            &create.record_type,
-            &create.value,
-            create.ttl,
-            create.priority,
-            None,
-        )?;
-    }
-
-    Ok(ZoneChangeSet { deletes, creates })
-}
-
 impl ExternalDnsService {
     /// Apply an ExternalDNS change set atomically: every zone's changes commit
     /// together or none do. Only zones with a remaining delta advance their
@@ -406,11 +259,11 @@ impl ExternalDnsService {
                 .await?;
 
                 let change_set = compute_zone_change_set(&zone, &existing, ops)?;
-                if change_set.deletes.is_empty() && change_set.creates.is_empty() {
+                if change_set.is_empty() {
                     continue;
                 }
 
-                let new_serial = generate_serial(Some(zone.serial))?;
+                let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
                 RecordService::delete_records_with_changes_tx(
                     &mut tx,
                     zone.id,
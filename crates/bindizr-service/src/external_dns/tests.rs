@@ -2,18 +2,16 @@ use bindizr_core::dns::name::{OwnerName, ZoneName};
 use chrono::Utc;
 
 use super::{
-    apply::{
-        ZoneOps, adjust_rrset, compute_zone_change_set, convert_request, convert_rrset,
-        group_ops_by_zone,
-    },
+    apply::{adjust_rrset, convert_request, convert_rrset, group_ops_by_zone},
     policy::{find_authoritative_zone, normalize_lookup_name},
 };
 use crate::{
     error::ErrorCode,
     model::{
         record::{Record, RecordType},
-        zone::Zone,
+        zone::{SerialPolicy, Zone},
     },
+    record::{ZoneOps, compute_zone_change_set},
     types::{ExternalDnsChangesRequest, ExternalDnsRrset, ExternalDnsRrsetUpdate},
 };
 
@@ -25,6 +23,7 @@ fn test_zone(id: i32, name: &str) -> Zone {
         admin_email: format!("hostmaster@{}", name),
         ttl: 3600,
         serial: 1,
+        serial_policy: SerialPolicy::Increment,
         refresh: 7200,
         retry: 3600,
         expire: 604800,
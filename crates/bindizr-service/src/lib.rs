@@ -1,20 +1,25 @@
 //! Application services for bindizr: zone, record, token, and NOTIFY
 //! workflows built on the repository layer.
 
+pub mod audit;
 pub mod authorization;
+pub mod catalog;
 pub mod dynamic_update;
 pub mod error;
 pub mod external_dns;
 pub mod notify;
 mod pagination;
 pub(crate) mod policy_pattern;
+pub mod precondition;
 pub mod record;
 mod repository;
 pub(crate) mod serial;
+pub mod template;
 pub(crate) mod timing;
 pub mod token;
 pub mod tsig_key;
 pub mod types;
+pub mod webhook;
 pub mod zone;
 
 pub(crate) use bindizr_core::{
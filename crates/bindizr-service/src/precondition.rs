@@ -0,0 +1,195 @@
+//! Conditional writes: the entity tags zones and records are versioned by, and
+//! the `If-Match` / `If-None-Match` preconditions checked against them.
+//!
+//! A zone's tag is its SOA serial, which every zone or record change advances.
+//! A record's tag is a digest of its fields, so it changes only when that
+//! record does. Writes addressed to a single record accept either tag.
+
+use bindizr_core::dns::serial;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::ServiceError,
+    model::{record::Record, zone::Zone},
+};
+
+/// The entity tags an `If-Match` or `If-None-Match` condition names. Tags are
+/// opaque values, without the surrounding quotes or a `W/` prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityTags {
+    /// `*`: any current representation.
+    Any,
+    Tags(Vec<String>),
+}
+
+impl EntityTags {
+    fn matches(&self, current: &[String]) -> bool {
+        match self {
+            EntityTags::Any => !current.is_empty(),
+            EntityTags::Tags(tags) => tags.iter().any(|tag| current.contains(tag)),
+        }
+    }
+}
+
+/// The conditions a write must satisfy against the state it finds once its
+/// zone is locked. The default has none and always passes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Precondition {
+    pub if_match: Option<EntityTags>,
+    pub if_none_match: Option<EntityTags>,
+}
+
+impl Precondition {
+    /// Require the zone to still be at `serial` (the CLI's `--expect-serial`).
+    pub fn expect_serial(expected: Option<u32>) -> Self {
+        Precondition {
+            if_match: expected.map(|serial| EntityTags::Tags(vec![serial.to_string()])),
+            if_none_match: None,
+        }
+    }
+
+    /// Check the conditions against the target's current tags (empty when it
+    /// does not exist). `state` describes the target for the error message.
+    pub(crate) fn check(
+        &self,
+        current: &[String],
+        state: impl FnOnce() -> String,
+    ) -> Result<(), ServiceError> {
+        let failed = self
+            .if_match
+            .as_ref()
+            .is_some_and(|tags| !tags.matches(current))
+            || self
+                .if_none_match
+                .as_ref()
+                .is_some_and(|tags| tags.matches(current));
+        if failed {
+            return Err(ServiceError::precondition_failed(format!(
+                "Precondition failed: {}",
+                state()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Check a zone-scoped write against the locked zone.
+    pub(crate) fn check_zone(&self, zone: &Zone) -> Result<(), ServiceError> {
+        self.check(&[zone_etag(zone)], || zone_state(zone))
+    }
+
+    /// Check a write to an RRset, which carries its zone's tag while the zone
+    /// holds it and no tag before it is created.
+    pub(crate) fn check_rrset(&self, zone: &Zone, exists: bool) -> Result<(), ServiceError> {
+        let current = if exists {
+            vec![zone_etag(zone)]
+        } else {
+            Vec::new()
+        };
+        self.check(&current, || {
+            if exists {
+                format!("the RRset exists and {}", zone_state(zone))
+            } else {
+                "the RRset does not exist".to_string()
+            }
+        })
+    }
+
+    /// Check a write to one record against the record and its locked zone.
+    pub(crate) fn check_record(&self, zone: &Zone, record: &Record) -> Result<(), ServiceError> {
+        self.check(&[record_etag(record), zone_etag(zone)], || {
+            format!(
+                "record '{}' has changed and {}",
+                record.id,
+                zone_state(zone)
+            )
+        })
+    }
+}
+
+fn zone_state(zone: &Zone) -> String {
+    format!(
+        "zone '{}' is at serial {}",
+        zone.name,
+        serial::to_wire(zone.serial)
+    )
+}
+
+/// A zone's entity tag: its serial in wire form.
+pub fn zone_etag(zone: &Zone) -> String {
+    serial::to_wire(zone.serial).to_string()
+}
+
+/// A record's entity tag: its id and a digest of the fields a write can change.
+pub fn record_etag(record: &Record) -> String {
+    let mut hasher = Sha256::new();
+    for field in [
+        &record.name.to_string(),
+        &record.record_type.to_string(),
+        &record.value,
+        &record.ttl.to_string(),
+        &record.priority.map(|p| p.to_string()).unwrap_or_default(),
+        &record.zone_id.to_string(),
+    ] {
+        hasher.update(field.as_bytes());
+        hasher.update([0]);
+    }
+    let digest = hex::encode(hasher.finalize());
+    format!("{}-{}", record.id, &digest[..16])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    fn tags(values: &[&str]) -> EntityTags {
+        EntityTags::Tags(values.iter().map(|v| v.to_string()).collect())
+    }
+
+    #[test]
+    fn if_match_requires_a_current_tag() {
+        let current = vec!["12".to_string()];
+        let state = || "zone 'example.com' is at serial 12".to_string();
+
+        assert!(Precondition::default().check(&current, state).is_ok());
+        assert!(
+            Precondition::expect_serial(Some(12))
+                .check(&current, state)
+                .is_ok()
+        );
+        let err = Precondition::expect_serial(Some(11))
+            .check(&current, state)
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::PreconditionFailed);
+        assert_eq!(
+            err.message,
+            "Precondition failed: zone 'example.com' is at serial 12"
+        );
+
+        let any = Precondition {
+            if_match: Some(EntityTags::Any),
+            if_none_match: None,
+        };
+        assert!(any.check(&current, state).is_ok());
+        assert!(any.check(&[], state).is_err());
+    }
+
+    #[test]
+    fn if_none_match_rejects_a_current_tag() {
+        let current = vec!["12".to_string()];
+        let state = || String::new();
+        let none_match = |t: EntityTags| Precondition {
+            if_match: None,
+            if_none_match: Some(t),
+        };
+
+        assert!(none_match(tags(&["11"])).check(&current, state).is_ok());
+        assert!(
+            none_match(tags(&["11", "12"]))
+                .check(&current, state)
+                .is_err()
+        );
+        assert!(none_match(EntityTags::Any).check(&current, state).is_err());
+        assert!(none_match(EntityTags::Any).check(&[], state).is_ok());
+    }
+}
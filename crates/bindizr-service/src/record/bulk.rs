@@ -19,6 +19,7 @@ use crate::{
         record::{Record, RecordType},
         zone_change::ZoneChange,
     },
+    precondition::Precondition,
     repository::RepositoryService,
     serial::generate_serial,
     timing::{duration_ms, elapsed_ms},
@@ -43,7 +44,7 @@ struct BulkTimings {
 
 /// A record whose type and value are parsed and ready to insert. The owner name
 /// is kept raw so the constraint validator can normalize it against the zone.
-pub(super) struct PreparedRecord {
+pub(crate) struct PreparedRecord {
     pub(crate) owner_name: String,
     pub(crate) record_type: RecordType,
     pub(crate) value: String,
@@ -52,7 +53,7 @@ pub(super) struct PreparedRecord {
 }
 
 /// Parse the record type and encode the value into its record-row form.
-pub(super) fn prepare_record(
+pub(crate) fn prepare_record(
     name: &str,
     record_type: &str,
     value: &RecordValueRequest,
@@ -60,6 +61,9 @@ pub(super) fn prepare_record(
     priority: Option<i32>,
 ) -> Result<PreparedRecord, ServiceError> {
     let record_type = parse_record_type(record_type)?;
+    let priority = value
+        .resolve_priority(&record_type, priority)
+        .map_err(ServiceError::invalid_record_value)?;
     let value = value
         .to_encoded_value(&record_type, priority)
         .map_err(ServiceError::invalid_record_value)?;
@@ -145,6 +149,7 @@ impl RecordService {
         zone_name: &str,
         items: &[RecordItem],
         dry_run: bool,
+        precondition: &Precondition,
     ) -> Result<BulkRecordsResponse, ServiceError> {
         if items.is_empty() {
             return Err(ServiceError::invalid_input(
@@ -197,6 +202,7 @@ impl RecordService {
             caller
                 .authorize_record_writes_tx(&mut tx, &zone, &writes)
                 .await?;
+            precondition.check_zone(&zone)?;
 
             // Only records whose owner name appears in the batch can conflict, so
             // load just those instead of the whole zone.
@@ -224,7 +230,7 @@ impl RecordService {
             };
             timings.load_existing_ms = elapsed_ms(t);
 
-            let new_serial = generate_serial(Some(zone.serial))?;
+            let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
 
             // The diff is only shown on a dry-run preview, so keep the `before`
             // snapshot (and pay for building the diff) off the apply hot path.
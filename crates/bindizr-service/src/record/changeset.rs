@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+
+use bindizr_core::dns::{name::ZoneName, serial};
+use bindizr_db::repository::LockLevel;
+use chrono::Utc;
+
+use super::{
+    RecordService,
+    bulk::{PreparedRecord, prepare_record, zone_changes_for},
+    validation::{
+        normalize_record_owner_name, validate_delete_constraints,
+        validate_record_add_constraints_normalized, validate_record_update_constraints_normalized,
+    },
+};
+use crate::{
+    authorization::{Caller, RecordWrite},
+    error::ServiceError,
+    log_error, log_info, log_warn,
+    model::{record::Record, zone_change::ZoneChange},
+    precondition::Precondition,
+    repository::RepositoryService,
+    serial::generate_serial,
+    types::{ChangesetOperation, ChangesetResponse, GetRecordResponse, RecordDiff, RecordItem},
+    zone::{
+        ZoneService,
+        history::{ReconstructedRecord, build_record_diff},
+    },
+};
+
+/// A changeset operation with its record already parsed, so a malformed value
+/// fails before the transaction opens.
+enum PreparedOperation {
+    Add(PreparedRecord),
+    Update(i32, PreparedRecord),
+    Delete(i32),
+}
+
+/// The net effect of a changeset against the records it started from.
+#[derive(Default)]
+struct NetChange {
+    deleted: Vec<Record>,
+    /// `(stored, replacement)` pairs for records kept under the same id.
+    updated: Vec<(Record, Record)>,
+    added: Vec<Record>,
+}
+
+impl NetChange {
+    fn is_empty(&self) -> bool {
+        self.deleted.is_empty() && self.updated.is_empty() && self.added.is_empty()
+    }
+}
+
+/// Prefix an error with the 1-based position of the operation that raised it.
+fn at_operation(index: usize) -> impl Fn(ServiceError) -> ServiceError {
+    move |e| ServiceError::new(e.code, format!("operation {}: {}", index + 1, e.message))
+}
+
+fn prepare_item(item: &RecordItem) -> Result<PreparedRecord, ServiceError> {
+    prepare_record(
+        &item.name,
+        &item.record_type,
+        &item.value,
+        item.ttl,
+        item.priority,
+    )
+}
+
+/// Compare the records a changeset started from with where it ended up. Added
+/// records carry the negative placeholder ids handed out while replaying.
+fn net_change(original: &[Record], result: Vec<Record>) -> NetChange {
+    let mut remaining: HashMap<i32, Record> = result.into_iter().map(|r| (r.id, r)).collect();
+    let mut change = NetChange::default();
+
+    for stored in original {
+        match remaining.remove(&stored.id) {
+            None => change.deleted.push(stored.clone()),
+            Some(current) => {
+                if current.name != stored.name
+                    || current.record_type != stored.record_type
+                    || current.value != stored.value
+                    || current.ttl != stored.ttl
+                    || current.priority != stored.priority
+                {
+                    change.updated.push((stored.clone(), current));
+                }
+            }
+        }
+    }
+
+    change.added = remaining.into_values().collect();
+    // Placeholders count down from -1, so this restores request order.
+    change.added.sort_by_key(|r| std::cmp::Reverse(r.id));
+    change
+}
+
+impl RecordService {
+    /// Apply an ordered list of add/update/delete operations to a zone in one
+    /// transaction. Each operation is validated against the zone as the
+    /// operations before it left it, so a changeset may, for instance, delete a
+    /// CNAME and then add an A record at the same name. Either every operation
+    /// applies or none does; the serial moves once and a single NOTIFY follows
+    /// the commit. A changeset whose net effect is empty leaves the serial
+    /// alone. On `dry_run` the same validation runs and the diff is returned
+    /// without writing anything.
+    pub async fn apply_changeset(
+        caller: &Caller,
+        zone_name: &str,
+        operations: &[ChangesetOperation],
+        dry_run: bool,
+        precondition: &Precondition,
+    ) -> Result<ChangesetResponse, ServiceError> {
+        if operations.is_empty() {
+            return Err(ServiceError::invalid_input(
+                "no operations provided for changeset".to_string(),
+            ));
+        }
+
+        let prepared = operations
+            .iter()
+            .enumerate()
+            .map(|(index, operation)| {
+                match operation {
+                    ChangesetOperation::Add { record } => {
+                        prepare_item(record).map(PreparedOperation::Add)
+                    }
+                    ChangesetOperation::Update { id, record } => {
+                        prepare_item(record).map(|p| PreparedOperation::Update(*id, p))
+                    }
+                    ChangesetOperation::Delete { id } => Ok(PreparedOperation::Delete(*id)),
+                }
+                .map_err(at_operation(index))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut tx = RepositoryService::begin_tx("Failed to apply changeset").await?;
+
+        let apply_result = async {
+            let zone =
+                ZoneService::get_by_name_tx(&mut tx, zone_name, LockLevel::Exclusive).await?;
+
+            // Updates may move records between names, so load the whole zone
+            // rather than the names the request mentions.
+            let original = RepositoryService::list_records_by_zone_id_tx(
+                &mut tx,
+                zone.id,
+                LockLevel::Exclusive,
+            )
+            .await
+            .map_err(|e| {
+                log_error!("Failed to load zone records: {}", e);
+                ServiceError::internal("Failed to apply changeset".to_string())
+            })?;
+            let stored_by_id: HashMap<i32, &Record> = original.iter().map(|r| (r.id, r)).collect();
+
+            // Authorize every identity the changeset touches before checking
+            // any constraint, so an ungranted caller gets 403 rather than an
+            // error naming what the zone holds. Ids outside this zone read as
+            // missing, whichever zone they belong to.
+            let mut writes: Vec<RecordWrite<'_>> = Vec::with_capacity(prepared.len() * 2);
+            for (index, operation) in prepared.iter().enumerate() {
+                let (id, replacement) = match operation {
+                    PreparedOperation::Add(p) => (None, Some(p)),
+                    PreparedOperation::Update(id, p) => (Some(*id), Some(p)),
+                    PreparedOperation::Delete(id) => (Some(*id), None),
+                };
+                if let Some(id) = id {
+                    let stored = stored_by_id
+                        .get(&id)
+                        .ok_or_else(|| at_operation(index)(ServiceError::record_not_found(id)))?;
+                    writes.push(RecordWrite {
+                        relative_name: stored.name.clone(),
+                        record_type: Some(&stored.record_type),
+                    });
+                }
+                // A name that will not parse lists no write; validation reports it.
+                if let Some(p) = replacement
+                    && let Ok(name) = normalize_record_owner_name(&p.owner_name, &zone.name)
+                {
+                    writes.push(RecordWrite {
+                        relative_name: name,
+                        record_type: Some(&p.record_type),
+                    });
+                }
+            }
+            caller
+                .authorize_record_writes_tx(&mut tx, &zone, &writes)
+                .await?;
+            precondition.check_zone(&zone)?;
+
+            // Replay the operations on an in-memory copy of the zone.
+            let mut current = original.clone();
+            let mut next_placeholder = -1;
+            for (index, operation) in prepared.iter().enumerate() {
+                let at = at_operation(index);
+                match operation {
+                    PreparedOperation::Add(p) => {
+                        let owner_name =
+                            normalize_record_owner_name(&p.owner_name, &zone.name).map_err(&at)?;
+                        // Fixed at write time: a later zone TTL change will not move it.
+                        let ttl = p.ttl.unwrap_or(zone.ttl);
+                        validate_record_add_constraints_normalized(
+                            &current,
+                            &owner_name,
+                            &p.record_type,
+                            &p.value,
+                            ttl,
+                            p.priority,
+                            None,
+                        )
+                        .map_err(&at)?;
+                        current.push(Record {
+                            id: next_placeholder,
+                            name: owner_name,
+                            record_type: p.record_type.clone(),
+                            value: p.value.clone(),
+                            ttl,
+                            priority: p.priority,
+                            zone_id: zone.id,
+                            created_at: Utc::now(),
+                        });
+                        next_placeholder -= 1;
+                    }
+                    PreparedOperation::Update(id, p) => {
+                        let position = current
+                            .iter()
+                            .position(|r| r.id == *id)
+                            .ok_or_else(|| at(ServiceError::record_not_found(*id)))?;
+                        let existing = current[position].clone();
+                        let updated = Record {
+                            id: existing.id,
+                            name: normalize_record_owner_name(&p.owner_name, &zone.name)
+                                .map_err(&at)?,
+                            record_type: p.record_type.clone(),
+                            value: p.value.clone(),
+                            ttl: p.ttl.unwrap_or(zone.ttl),
+                            priority: p.priority,
+                            zone_id: zone.id,
+                            created_at: existing.created_at,
+                        };
+                        validate_record_update_constraints_normalized(
+                            &zone, &current, &existing, &updated,
+                        )
+                        .map_err(&at)?;
+                        current[position] = updated;
+                    }
+                    PreparedOperation::Delete(id) => {
+                        let position = current
+                            .iter()
+                            .position(|r| r.id == *id)
+                            .ok_or_else(|| at(ServiceError::record_not_found(*id)))?;
+                        validate_delete_constraints(
+                            &zone,
+                            std::slice::from_ref(&current[position]),
+                        )
+                        .map_err(&at)?;
+                        current.remove(position);
+                    }
+                }
+            }
+
+            let diff = if dry_run {
+                let before: Vec<ReconstructedRecord> = original
+                    .iter()
+                    .cloned()
+                    .map(ReconstructedRecord::from)
+                    .collect();
+                let after: Vec<ReconstructedRecord> = current
+                    .iter()
+                    .cloned()
+                    .map(ReconstructedRecord::from)
+                    .collect();
+                build_record_diff(&zone, &before, &after)
+            } else {
+                RecordDiff::default()
+            };
+
+            let change = net_change(&original, current);
+            if dry_run || change.is_empty() {
+                return Ok((change, zone.name, zone.serial, diff));
+            }
+
+            let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
+
+            Self::delete_records_with_changes_tx(&mut tx, zone.id, new_serial, &change.deleted)
+                .await?;
+
+            let mut updated = Vec::with_capacity(change.updated.len());
+            let mut changes = Vec::with_capacity(change.updated.len() * 2);
+            for (stored, replacement) in change.updated {
+                let written = RepositoryService::update_record_tx(&mut tx, replacement)
+                    .await
+                    .map_err(|e| {
+                        log_error!("Failed to update record: {}", e);
+                        ServiceError::internal("Failed to apply changeset".to_string())
+                    })?;
+                changes.extend(zone_changes_for(
+                    zone.id,
+                    new_serial,
+                    ZoneChange::OP_DEL,
+                    std::slice::from_ref(&stored),
+                ));
+                changes.extend(zone_changes_for(
+                    zone.id,
+                    new_serial,
+                    ZoneChange::OP_ADD,
+                    std::slice::from_ref(&written),
+                ));
+                updated.push((stored, written));
+            }
+            if !changes.is_empty() {
+                RepositoryService::create_zone_changes_tx(&mut tx, &changes).await?;
+            }
+
+            // Placeholder ids must not reach the insert.
+            let to_insert: Vec<Record> = change
+                .added
+                .into_iter()
+                .map(|record| Record { id: 0, ..record })
+                .collect();
+            let added =
+                Self::insert_records_with_changes_tx(&mut tx, zone.id, new_serial, &to_insert)
+                    .await?;
+
+            ZoneService::advance_serial_tx(&mut tx, &zone, new_serial).await?;
+
+            Ok::<(NetChange, ZoneName, i32, RecordDiff), ServiceError>((
+                NetChange {
+                    deleted: change.deleted,
+                    updated,
+                    added,
+                },
+                zone.name,
+                new_serial,
+                diff,
+            ))
+        }
+        .await;
+
+        let (change, zone_name, serial_after, diff) =
+            RepositoryService::finish_tx(tx, apply_result, "Failed to apply changeset").await?;
+
+        let changed = !change.is_empty();
+        log_info!(
+            "event=record_changeset zone={} operations={} added={} updated={} deleted={} dry_run={}",
+            zone_name,
+            operations.len(),
+            change.added.len(),
+            change.updated.len(),
+            change.deleted.len(),
+            dry_run
+        );
+
+        if !dry_run
+            && changed
+            && let Err(e) = crate::notify::send_notify_after_update(Some(zone_name.as_str())).await
+        {
+            log_warn!("Failed to send NOTIFY for zone {}: {}", zone_name, e);
+        }
+
+        let records = change
+            .added
+            .iter()
+            .chain(change.updated.iter().map(|(_, written)| written))
+            .map(|record| GetRecordResponse::from_record_and_zone_name(record, &zone_name))
+            .collect();
+        Ok(ChangesetResponse {
+            applied: !dry_run,
+            dry_run,
+            added: change.added.len(),
+            updated: change.updated.len(),
+            deleted: change.deleted.len(),
+            serial: serial::to_wire(serial_after),
+            records,
+            diff,
+        })
+    }
+}
@@ -12,6 +12,7 @@ use crate::{
     error::ServiceError,
     log_error, log_info, log_warn,
     model::record::{Record, RecordWithZone},
+    precondition::Precondition,
     repository::RepositoryService,
     serial::generate_serial,
     types::CreateRecordRequest,
@@ -25,10 +26,12 @@ impl RecordService {
     pub async fn create(
         caller: &Caller,
         create_record_request: &CreateRecordRequest,
+        precondition: &Precondition,
     ) -> Result<RecordWithZone, ServiceError> {
         let PreparedRecord {
             record_type,
             value: record_value,
+            priority,
             ..
         } = prepare_record(
             &create_record_request.name,
@@ -62,6 +65,7 @@ impl RecordService {
                     }],
                 )
                 .await?;
+            precondition.check_zone(&zone)?;
 
             let existing_records_with_name =
                 match RepositoryService::list_records_by_zone_id_and_name_tx(
@@ -90,11 +94,11 @@ impl RecordService {
                 &record_type,
                 &record_value,
                 ttl,
-                create_record_request.priority,
+                priority,
                 None,
             )?;
 
-            let new_serial = generate_serial(Some(zone.serial))?;
+            let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
 
             let created_record = Self::insert_records_with_changes_tx(
                 &mut tx,
@@ -106,7 +110,7 @@ impl RecordService {
                     record_type,
                     value: record_value,
                     ttl,
-                    priority: create_record_request.priority,
+                    priority,
                     zone_id: zone.id,
                     created_at: Utc::now(),
                 }],
@@ -135,9 +139,7 @@ impl RecordService {
             create_record_request
                 .ttl
                 .map_or("null".to_string(), |v| v.to_string()),
-            create_record_request
-                .priority
-                .map_or("null".to_string(), |v| v.to_string()),
+            priority.map_or("null".to_string(), |v| v.to_string()),
             created_record.id
         );
 
@@ -6,6 +6,7 @@ use crate::{
     authorization::{Caller, RecordWrite},
     error::{ErrorCode, ServiceError},
     log_error, log_info, log_warn,
+    precondition::Precondition,
     repository::RepositoryService,
     serial::generate_serial,
     zone::ZoneService,
@@ -24,7 +25,11 @@ impl RecordService {
     /// Delete a record by id, bumping the zone serial and recording a DEL
     /// change for IXFR. `caller` is authorized inside the delete transaction,
     /// so a concurrent rename cannot outrun the check.
-    pub async fn delete_by_id(caller: &Caller, record_id: i32) -> Result<(), ServiceError> {
+    pub async fn delete_by_id(
+        caller: &Caller,
+        record_id: i32,
+        precondition: &Precondition,
+    ) -> Result<(), ServiceError> {
         // Resolve zone_id with a non-locking read so the tx locks zone before
         // record (the create/bulk/import order); the reverse can deadlock.
         let zone_id = match RepositoryService::get_record_by_id(record_id).await {
@@ -89,8 +94,9 @@ impl RecordService {
                     }],
                 )
                 .await?;
+            precondition.check_record(&zone, &existing_record)?;
 
-            let new_serial = generate_serial(Some(zone.serial))?;
+            let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
 
             validate_delete_constraints(&zone, std::slice::from_ref(&existing_record))?;
 
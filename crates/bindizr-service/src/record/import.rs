@@ -17,13 +17,14 @@ use super::{
     zonefile::parse_zone_file,
 };
 use crate::{
-    authorization::Caller,
+    authorization::{Caller, TokenRole},
     error::ServiceError,
     log_debug, log_error, log_info, log_warn,
     model::{
         record::{Record, RecordType},
         zone::Zone,
     },
+    precondition::Precondition,
     repository::RepositoryService,
     serial::generate_serial,
     timing::elapsed_ms,
@@ -99,8 +100,9 @@ impl RecordService {
         caller: &Caller,
         zone_name: &str,
         request: &ImportZoneFileRequest,
+        precondition: &Precondition,
     ) -> Result<ImportZoneFileResponse, ServiceError> {
-        caller.require_global("import zone files")?;
+        caller.require_role(TokenRole::ZoneAdmin, "import zone files")?;
 
         let mode = request.mode;
         let dry_run = request.dry_run;
@@ -115,6 +117,7 @@ impl RecordService {
             let t = Instant::now();
             let zone =
                 ZoneService::get_by_name_tx(&mut tx, zone_name, LockLevel::Exclusive).await?;
+            precondition.check_zone(&zone)?;
             timings.load_zone_ms = elapsed_ms(t);
 
             let t = Instant::now();
@@ -379,7 +382,7 @@ impl RecordService {
             let has_changes = !dels.is_empty() || !adds.is_empty() || !ttl_dels.is_empty();
 
             if will_apply && has_changes {
-                let new_serial = generate_serial(Some(zone.serial))?;
+                let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
 
                 let t = Instant::now();
                 let mut all_dels = dels;
@@ -1,16 +1,25 @@
 mod bulk;
+mod changeset;
 mod create;
 mod delete;
 mod get;
 mod import;
+mod rrset;
 mod update;
 mod validation;
 mod zonefile;
 
+pub(crate) use rrset::{
+    RrsetValue, ZoneOps, ZoneRrsetOp, canonical_rrset_values, compute_zone_change_set,
+    encode_rrset_values, validate_rrset_shape,
+};
 pub(crate) use validation::{AddOutcome, validate_delete_constraints};
 
 /// Business logic for creating, updating, and querying DNS records.
 #[derive(Clone)]
 pub struct RecordService;
 
-pub(crate) use validation::{parse_record_type, validate_record_add_constraints_normalized};
+pub(crate) use bulk::prepare_record;
+pub(crate) use validation::{
+    normalize_record_owner_name, parse_record_type, validate_record_add_constraints_normalized,
+};
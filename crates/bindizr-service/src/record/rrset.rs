@@ -0,0 +1,639 @@
+//! RRset-level reads and replacements. An RRset is every record of one owner
+//! name and type; its values travel in presentation form (priority inline for
+//! MX, SRV, SVCB and HTTPS), and applying a desired set resolves to the minimal
+//! row delta against what the zone holds. The ExternalDNS provider API applies
+//! its change sets through the same resolution.
+
+use bindizr_core::dns::{
+    name::{OwnerName, ZoneName},
+    serial,
+};
+use bindizr_db::repository::LockLevel;
+use chrono::Utc;
+
+use super::{
+    RecordService,
+    validation::{
+        normalize_record_owner_name, parse_record_type, validate_delete_constraints,
+        validate_record_add_constraints_normalized,
+    },
+};
+use crate::{
+    RepositoryTx,
+    authorization::{Caller, RecordWrite},
+    error::ServiceError,
+    log_info, log_warn,
+    model::{
+        record::{Record, RecordType},
+        zone::Zone,
+    },
+    precondition::Precondition,
+    repository::RepositoryService,
+    serial::generate_serial,
+    types::{ReplaceRrsetRequest, RrsetResponse},
+    zone::ZoneService,
+};
+
+/// One RRset value in row form: the encoded value plus, for types that keep
+/// one, the priority column.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RrsetValue {
+    pub(crate) value: String,
+    pub(crate) priority: Option<i32>,
+}
+
+/// A desired RRset change within one zone, the owner relative to that zone.
+#[derive(Debug)]
+pub(crate) struct ZoneRrsetOp {
+    pub(crate) name: OwnerName,
+    pub(crate) record_type: RecordType,
+    /// Adds only; `None` resolves to the zone TTL at apply time.
+    pub(crate) ttl: Option<i32>,
+    pub(crate) values: Vec<RrsetValue>,
+}
+
+/// Adds and deletes bound for the same zone.
+#[derive(Debug, Default)]
+pub(crate) struct ZoneOps {
+    pub(crate) adds: Vec<ZoneRrsetOp>,
+    pub(crate) dels: Vec<ZoneRrsetOp>,
+}
+
+/// The record rows one zone's operations resolve to.
+#[derive(Debug, Default)]
+pub(crate) struct ZoneChangeSet {
+    pub(crate) deletes: Vec<Record>,
+    pub(crate) creates: Vec<Record>,
+}
+
+impl ZoneChangeSet {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.deletes.is_empty() && self.creates.is_empty()
+    }
+}
+
+/// Encode one presentation-form value. Types with a priority column take it
+/// from the leading field, the way a zone file spells it.
+fn encode_rrset_value(record_type: &RecordType, value: &str) -> Result<RrsetValue, String> {
+    let (value, priority) = if record_type.takes_priority() {
+        let trimmed = value.trim();
+        match trimmed
+            .split_once(char::is_whitespace)
+            .and_then(|(first, rest)| first.parse::<i32>().ok().map(|p| (rest.trim(), p)))
+        {
+            Some((rest, priority)) => (rest, Some(priority)),
+            None => (trimmed, None),
+        }
+    } else {
+        (value, None)
+    };
+
+    Ok(RrsetValue {
+        value: record_type.encoded_value(value, priority)?,
+        priority,
+    })
+}
+
+/// Encode an RRset's values, dropping those that normalize to one already
+/// seen (e.g. two IPv6 spellings).
+pub(crate) fn encode_rrset_values(
+    record_type: &RecordType,
+    values: &[String],
+) -> Result<Vec<RrsetValue>, ServiceError> {
+    let mut encoded: Vec<RrsetValue> = Vec::with_capacity(values.len());
+    for value in values {
+        let candidate =
+            encode_rrset_value(record_type, value).map_err(ServiceError::invalid_record_value)?;
+        if !encoded.iter().any(|existing| {
+            record_type.values_equal(
+                &existing.value,
+                existing.priority,
+                &candidate.value,
+                candidate.priority,
+            )
+        }) {
+            encoded.push(candidate);
+        }
+    }
+    Ok(encoded)
+}
+
+/// An RRset's values in the presentation form storing them would read back
+/// as: deduplicated and sorted. Unparseable values pass through unchanged so
+/// the write reports its ordinary error.
+pub(crate) fn canonical_rrset_values(record_type: &RecordType, values: &[String]) -> Vec<String> {
+    let mut canonical: Vec<String> = Vec::with_capacity(values.len());
+    for value in values {
+        let presented = match encode_rrset_value(record_type, value) {
+            Ok(encoded) => record_type.presentation_rdata(&encoded.value, encoded.priority),
+            Err(_) => value.clone(),
+        };
+        if !canonical.contains(&presented) {
+            canonical.push(presented);
+        }
+    }
+    canonical.sort();
+    canonical
+}
+
+/// An RRset needs at least one value, and a CNAME exactly one.
+pub(crate) fn validate_rrset_shape(
+    name: &str,
+    record_type: &RecordType,
+    values: &[String],
+) -> Result<(), ServiceError> {
+    if values.is_empty() {
+        return Err(ServiceError::invalid_input(format!(
+            "RRset '{}' {} must have at least one value",
+            name, record_type
+        )));
+    }
+    if *record_type == RecordType::CNAME && values.len() > 1 {
+        return Err(ServiceError::invalid_record_value(format!(
+            "CNAME RRset '{}' must have exactly one value",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Resolve one zone's operations against its current records; idempotent
+/// operations cancel out, so an effect-free request yields an empty set.
+pub(crate) fn compute_zone_change_set(
+    zone: &Zone,
+    existing: &[Record],
+    ops: &ZoneOps,
+) -> Result<ZoneChangeSet, ServiceError> {
+    let mut deletes: Vec<Record> = Vec::new();
+    for del in &ops.dels {
+        for value in &del.values {
+            for row in existing {
+                if row.name == del.name
+                    && row.record_type == del.record_type
+                    && del.record_type.values_equal(
+                        &row.value,
+                        row.priority,
+                        &value.value,
+                        value.priority,
+                    )
+                    && !deletes.iter().any(|d| d.id == row.id)
+                {
+                    deletes.push(row.clone());
+                }
+            }
+        }
+    }
+
+    let mut creates: Vec<Record> = Vec::new();
+    for add in &ops.adds {
+        let ttl = add.ttl.unwrap_or(zone.ttl);
+        for value in &add.values {
+            let same_rdata = |row: &Record| {
+                row.name == add.name
+                    && row.record_type == add.record_type
+                    && add.record_type.values_equal(
+                        &row.value,
+                        row.priority,
+                        &value.value,
+                        value.priority,
+                    )
+            };
+            let matches = |row: &Record| same_rdata(row) && row.ttl == ttl;
+
+            // An unchanged update cancels its own delete instead of rewriting
+            // the row. TTL-sensitive, so a TTL-only update is still a change.
+            if let Some(pos) = deletes.iter().position(&matches) {
+                deletes.remove(pos);
+                continue;
+            }
+            // Idempotent create: a surviving row already holds this rdata. TTL
+            // is excluded to match the duplicate check behind this one, which
+            // would otherwise reject the create as a conflict.
+            if existing
+                .iter()
+                .any(|row| deletes.iter().all(|d| d.id != row.id) && same_rdata(row))
+            {
+                continue;
+            }
+            // Intra-request duplicate create.
+            if creates.iter().any(matches) {
+                continue;
+            }
+
+            creates.push(Record {
+                id: 0,
+                name: add.name.clone(),
+                record_type: add.record_type.clone(),
+                value: value.value.clone(),
+                ttl,
+                priority: value.priority,
+                zone_id: zone.id,
+                created_at: Utc::now(),
+            });
+        }
+    }
+
+    // Validate each insert against the post-delete state plus earlier inserts,
+    // so CNAME exclusivity and RRset TTL rules see the state they will land in.
+    for (index, create) in creates.iter().enumerate() {
+        let mut same_name: Vec<Record> = existing
+            .iter()
+            .filter(|row| deletes.iter().all(|d| d.id != row.id) && row.name == create.name)
+            .cloned()
+            .collect();
+        same_name.extend(
+            creates[..index]
+                .iter()
+                .filter(|row| row.name == create.name)
+                .cloned(),
+        );
+
+        validate_record_add_constraints_normalized(
+            &same_name,
+            &create.name,
+            &create.record_type,
+            &create.value,
+            create.ttl,
+            create.priority,
+            None,
+        )?;
+    }
+
+    Ok(ZoneChangeSet { deletes, creates })
+}
+
+/// The RRset of `record_type` among `records`, as the API returns it.
+fn rrset_response(
+    zone_name: &ZoneName,
+    zone_serial: i32,
+    name: &OwnerName,
+    record_type: &RecordType,
+    records: &[Record],
+) -> Option<RrsetResponse> {
+    let rows: Vec<&Record> = records
+        .iter()
+        .filter(|r| r.name == *name && r.record_type == *record_type)
+        .collect();
+    let ttl = rows.first()?.ttl;
+    let mut values: Vec<String> = rows
+        .iter()
+        .map(|r| record_type.presentation_rdata(&r.value, r.priority))
+        .collect();
+    values.sort();
+
+    Some(RrsetResponse {
+        name: name.to_fqdn(zone_name),
+        record_type: record_type.to_string(),
+        ttl,
+        values,
+        serial: serial::to_wire(zone_serial),
+    })
+}
+
+/// Which RRset the request addresses, resolved inside its zone.
+struct RrsetKey {
+    zone: Zone,
+    name: OwnerName,
+    record_type: RecordType,
+}
+
+impl RecordService {
+    /// Lock the zone, check the caller may see it, and resolve the RRset's
+    /// owner name and type against it.
+    async fn resolve_rrset_tx(
+        tx: &mut RepositoryTx<'_>,
+        caller: &Caller,
+        zone_name: &str,
+        name: &str,
+        record_type: &str,
+        lock_level: LockLevel,
+    ) -> Result<RrsetKey, ServiceError> {
+        let zone = ZoneService::get_by_name_tx(tx, zone_name, lock_level).await?;
+        caller.ensure_zone_visible(&zone)?;
+        let record_type = parse_record_type(record_type)?;
+        if record_type == RecordType::SOA {
+            return Err(ServiceError::invalid_input(
+                "The SOA RRset is managed through the zone, not as records".to_string(),
+            ));
+        }
+        let name = normalize_record_owner_name(name, &zone.name)?;
+        Ok(RrsetKey {
+            zone,
+            name,
+            record_type,
+        })
+    }
+
+    /// Read one RRset: every record of `name` and `record_type` in the zone.
+    pub async fn get_rrset(
+        caller: &Caller,
+        zone_name: &str,
+        name: &str,
+        record_type: &str,
+    ) -> Result<RrsetResponse, ServiceError> {
+        let mut tx = RepositoryService::begin_tx("Failed to load RRset").await?;
+        let load_result = async {
+            let key = Self::resolve_rrset_tx(
+                &mut tx,
+                caller,
+                zone_name,
+                name,
+                record_type,
+                LockLevel::Shared,
+            )
+            .await?;
+            let records = RepositoryService::list_records_by_zone_id_and_name_tx(
+                &mut tx,
+                key.zone.id,
+                &key.name,
+                LockLevel::None,
+            )
+            .await?;
+            rrset_response(
+                &key.zone.name,
+                key.zone.serial,
+                &key.name,
+                &key.record_type,
+                &records,
+            )
+            .ok_or_else(|| {
+                ServiceError::rrset_not_found(&key.name.to_fqdn(&key.zone.name), &key.record_type)
+            })
+        }
+        .await;
+        RepositoryService::finish_tx(tx, load_result, "Failed to load RRset").await
+    }
+
+    /// Replace an RRset with `request.values` under one TTL, in one
+    /// transaction. Only the rows that differ are written: values already
+    /// present at that TTL stay untouched, and a request matching the stored
+    /// set changes nothing and leaves the serial alone.
+    pub async fn replace_rrset(
+        caller: &Caller,
+        zone_name: &str,
+        name: &str,
+        record_type: &str,
+        request: &ReplaceRrsetRequest,
+        precondition: &Precondition,
+    ) -> Result<RrsetResponse, ServiceError> {
+        if request.ttl.is_some_and(|ttl| ttl < 0) {
+            return Err(ServiceError::invalid_input(
+                "TTL must not be negative".to_string(),
+            ));
+        }
+
+        let mut tx = RepositoryService::begin_tx("Failed to replace RRset").await?;
+        let apply_result = async {
+            let key = Self::resolve_rrset_tx(
+                &mut tx,
+                caller,
+                zone_name,
+                name,
+                record_type,
+                LockLevel::Exclusive,
+            )
+            .await?;
+            let fqdn = key.name.to_fqdn(&key.zone.name);
+            validate_rrset_shape(&fqdn, &key.record_type, &request.values)?;
+            let values = encode_rrset_values(&key.record_type, &request.values)?;
+            caller
+                .authorize_record_writes_tx(
+                    &mut tx,
+                    &key.zone,
+                    &[RecordWrite {
+                        relative_name: key.name.clone(),
+                        record_type: Some(&key.record_type),
+                    }],
+                )
+                .await?;
+
+            // Other types at the name take part in CNAME exclusivity, so load
+            // the whole name.
+            let existing = RepositoryService::list_records_by_zone_id_and_name_tx(
+                &mut tx,
+                key.zone.id,
+                &key.name,
+                LockLevel::Exclusive,
+            )
+            .await?;
+            let stored: Vec<RrsetValue> = existing
+                .iter()
+                .filter(|r| r.record_type == key.record_type)
+                .map(|r| RrsetValue {
+                    value: r.value.clone(),
+                    priority: r.priority,
+                })
+                .collect();
+            precondition.check_rrset(&key.zone, !stored.is_empty())?;
+            let ops = ZoneOps {
+                dels: vec![ZoneRrsetOp {
+                    name: key.name.clone(),
+                    record_type: key.record_type.clone(),
+                    ttl: None,
+                    values: stored,
+                }],
+                adds: vec![ZoneRrsetOp {
+                    name: key.name.clone(),
+                    record_type: key.record_type.clone(),
+                    ttl: request.ttl,
+                    values,
+                }],
+            };
+            let change_set = compute_zone_change_set(&key.zone, &existing, &ops)?;
+            validate_delete_constraints(&key.zone, &change_set.deletes)?;
+
+            let mut zone_serial = key.zone.serial;
+            if !change_set.is_empty() {
+                let new_serial = generate_serial(key.zone.serial_policy, Some(key.zone.serial));
+                zone_serial = new_serial;
+                Self::delete_records_with_changes_tx(
+                    &mut tx,
+                    key.zone.id,
+                    new_serial,
+                    &change_set.deletes,
+                )
+                .await?;
+                Self::insert_records_with_changes_tx(
+                    &mut tx,
+                    key.zone.id,
+                    new_serial,
+                    &change_set.creates,
+                )
+                .await?;
+                ZoneService::advance_serial_tx(&mut tx, &key.zone, new_serial).await?;
+            }
+
+            let after: Vec<Record> = existing
+                .into_iter()
+                .filter(|r| change_set.deletes.iter().all(|d| d.id != r.id))
+                .chain(change_set.creates.iter().cloned())
+                .collect();
+            let rrset = rrset_response(
+                &key.zone.name,
+                zone_serial,
+                &key.name,
+                &key.record_type,
+                &after,
+            )
+            .expect("a replaced RRset holds at least one value");
+            Ok::<_, ServiceError>((rrset, key.zone.name, change_set))
+        }
+        .await;
+
+        let (rrset, zone_name, change_set) =
+            RepositoryService::finish_tx(tx, apply_result, "Failed to replace RRset").await?;
+
+        log_info!(
+            "event=rrset_replace zone={} name={} type={} added={} deleted={}",
+            zone_name,
+            rrset.name,
+            rrset.record_type,
+            change_set.creates.len(),
+            change_set.deletes.len()
+        );
+
+        if !change_set.is_empty()
+            && let Err(e) = crate::notify::send_notify_after_update(Some(zone_name.as_str())).await
+        {
+            log_warn!("Failed to send NOTIFY for zone {}: {}", zone_name, e);
+        }
+
+        Ok(rrset)
+    }
+
+    /// Delete every record of an RRset in one transaction, bumping the serial
+    /// once. Deleting an RRset the zone does not hold is a 404.
+    pub async fn delete_rrset(
+        caller: &Caller,
+        zone_name: &str,
+        name: &str,
+        record_type: &str,
+        precondition: &Precondition,
+    ) -> Result<(), ServiceError> {
+        let mut tx = RepositoryService::begin_tx("Failed to delete RRset").await?;
+        let apply_result = async {
+            let key = Self::resolve_rrset_tx(
+                &mut tx,
+                caller,
+                zone_name,
+                name,
+                record_type,
+                LockLevel::Exclusive,
+            )
+            .await?;
+            caller
+                .authorize_record_writes_tx(
+                    &mut tx,
+                    &key.zone,
+                    &[RecordWrite {
+                        relative_name: key.name.clone(),
+                        record_type: Some(&key.record_type),
+                    }],
+                )
+                .await?;
+
+            let deletes: Vec<Record> = RepositoryService::list_records_by_zone_id_and_name_tx(
+                &mut tx,
+                key.zone.id,
+                &key.name,
+                LockLevel::Exclusive,
+            )
+            .await?
+            .into_iter()
+            .filter(|r| r.record_type == key.record_type)
+            .collect();
+            let fqdn = key.name.to_fqdn(&key.zone.name);
+            if deletes.is_empty() {
+                return Err(ServiceError::rrset_not_found(&fqdn, &key.record_type));
+            }
+            precondition.check_rrset(&key.zone, true)?;
+            validate_delete_constraints(&key.zone, &deletes)?;
+
+            let new_serial = generate_serial(key.zone.serial_policy, Some(key.zone.serial));
+            Self::delete_records_with_changes_tx(&mut tx, key.zone.id, new_serial, &deletes)
+                .await?;
+            ZoneService::advance_serial_tx(&mut tx, &key.zone, new_serial).await?;
+
+            Ok::<_, ServiceError>((
+                key.zone.name,
+                fqdn,
+                key.record_type,
+                deletes.len(),
+                new_serial,
+            ))
+        }
+        .await;
+
+        let (zone_name, fqdn, record_type, deleted, new_serial) =
+            RepositoryService::finish_tx(tx, apply_result, "Failed to delete RRset").await?;
+
+        log_info!(
+            "event=rrset_delete zone={} name={} type={} deleted={} serial={}",
+            zone_name,
+            fqdn,
+            record_type,
+            deleted,
+            serial::to_wire(new_serial)
+        );
+
+        if let Err(e) = crate::notify::send_notify_after_update(Some(zone_name.as_str())).await {
+            log_warn!("Failed to send NOTIFY for zone {}: {}", zone_name, e);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RrsetValue, canonical_rrset_values, encode_rrset_values};
+    use crate::model::record::RecordType;
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn priority_moves_from_the_value_into_its_column() {
+        let encoded =
+            encode_rrset_values(&RecordType::MX, &values(&["10 mail.example.com."])).unwrap();
+        assert_eq!(
+            encoded,
+            vec![RrsetValue {
+                value: "mail.example.com.".to_string(),
+                priority: Some(10),
+            }]
+        );
+    }
+
+    #[test]
+    fn equal_values_collapse_whatever_their_spelling() {
+        let encoded = encode_rrset_values(
+            &RecordType::MX,
+            &values(&[
+                "10 Mail.Example.com.",
+                "10 mail.example.com",
+                "20 mail.example.com.",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(encoded.len(), 2);
+    }
+
+    #[test]
+    fn canonical_values_read_back_sorted_with_priority_inline() {
+        assert_eq!(
+            canonical_rrset_values(
+                &RecordType::MX,
+                &values(&["20 b.example.com", "10 a.example.com."])
+            ),
+            vec!["10 a.example.com.", "20 b.example.com."]
+        );
+    }
+
+    #[test]
+    fn a_malformed_value_is_rejected() {
+        assert!(encode_rrset_values(&RecordType::A, &values(&["not-an-ip"])).is_err());
+        assert!(encode_rrset_values(&RecordType::MX, &values(&["10 a b c"])).is_err());
+    }
+}
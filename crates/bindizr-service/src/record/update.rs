@@ -18,6 +18,7 @@ use crate::{
         zone::Zone,
         zone_change::ZoneChange,
     },
+    precondition::Precondition,
     repository::RepositoryService,
     serial::generate_serial,
     types::{RecordItem, UpdateRecordPatch},
@@ -42,12 +43,14 @@ impl RecordService {
         caller: &Caller,
         record_id: i32,
         request: &RecordItem,
+        precondition: &Precondition,
     ) -> Result<RecordWithZone, ServiceError> {
-        Self::update_locked(caller, record_id, |zone, _existing| {
+        Self::update_locked(caller, record_id, precondition, |zone, _existing| {
             let PreparedRecord {
                 owner_name,
                 record_type,
                 value: encoded_value,
+                priority,
                 ..
             } = prepare_record(
                 &request.name,
@@ -61,7 +64,7 @@ impl RecordService {
                 record_type,
                 encoded_value,
                 ttl: request.ttl.unwrap_or(zone.ttl),
-                priority: request.priority,
+                priority,
             })
         })
         .await
@@ -73,8 +76,9 @@ impl RecordService {
         caller: &Caller,
         record_id: i32,
         patch: &UpdateRecordPatch,
+        precondition: &Precondition,
     ) -> Result<RecordWithZone, ServiceError> {
-        Self::update_locked(caller, record_id, |_zone, existing| {
+        Self::update_locked(caller, record_id, precondition, |_zone, existing| {
             let record_type = match &patch.record_type {
                 Some(record_type) => parse_record_type(record_type)?,
                 None => existing.record_type.clone(),
@@ -86,12 +90,20 @@ impl RecordService {
                     "value is required when changing a record's type".to_string(),
                 ));
             }
-            // Only MX/SRV carry a priority, so retyping to any other type clears it.
-            let priority = if matches!(record_type, RecordType::MX | RecordType::SRV) {
+            // Only some types carry a priority, so retyping to any other type clears it.
+            let priority = if record_type.takes_priority() {
                 patch.priority.or(existing.priority)
             } else {
                 None
             };
+            // A generic value carries its own priority, which wins over the stored one.
+            let priority = match &patch.value {
+                Some(value) if record_type.takes_priority() => value
+                    .resolve_priority(&record_type, patch.priority)
+                    .map_err(ServiceError::invalid_record_value)?
+                    .or(priority),
+                _ => priority,
+            };
             let encoded_value = match &patch.value {
                 Some(value) => value
                     .to_encoded_value(&record_type, priority)
@@ -119,6 +131,7 @@ impl RecordService {
     async fn update_locked(
         caller: &Caller,
         record_id: i32,
+        precondition: &Precondition,
         resolve: impl FnOnce(&Zone, &Record) -> Result<ResolvedRecordUpdate, ServiceError>,
     ) -> Result<RecordWithZone, ServiceError> {
         // Resolve zone_id with a non-locking read so the tx locks zone before
@@ -194,6 +207,8 @@ impl RecordService {
                     ],
                 )
                 .await?;
+            precondition.check_record(&zone, &existing_record)?;
+
             // Only records sharing the new owner name can conflict, so load just
             // those instead of the whole zone.
             let zone_records = match RepositoryService::list_records_by_zone_id_and_name_tx(
@@ -231,7 +246,7 @@ impl RecordService {
                 &candidate_updated,
             )?;
 
-            let new_serial = generate_serial(Some(zone.serial))?;
+            let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
             let zone_name = zone.name.clone();
 
             let updated_record = RepositoryService::update_record_tx(&mut tx, candidate_updated)
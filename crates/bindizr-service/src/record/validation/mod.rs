@@ -140,12 +140,6 @@ pub(crate) fn validate_record_add_constraints_normalized(
         }
     }
 
-    if *record_type == RecordType::NS && !stored_name.is_apex() {
-        return Err(ServiceError::invalid_record_name(
-            "NS records must use apex owner name '@'".to_string(),
-        ));
-    }
-
     // RFC 2181, Section 5.2: one TTL per RRset.
     if let Some(conflicting) = existing_records_with_name
         .iter()
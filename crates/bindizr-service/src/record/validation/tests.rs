@@ -9,7 +9,7 @@ use crate::{
     error::{ErrorCode, ServiceError},
     model::{
         record::{Record, RecordType},
-        zone::Zone,
+        zone::{SerialPolicy, Zone},
     },
 };
 
@@ -190,7 +190,7 @@ fn validate_add(
 }
 
 #[test]
-fn add_rejects_cname_at_apex_and_ns_below_apex() {
+fn add_rejects_cname_at_apex_and_conflicting_cname() {
     let cname_at_apex = validate_add(
         &[],
         "",
@@ -204,29 +204,59 @@ fn add_rejects_cname_at_apex_and_ns_below_apex() {
         ErrorCode::InvalidRecordName
     );
 
-    let ns_below_apex = validate_add(
-        &[],
+    let existing_a = test_record(1, "www", RecordType::A, "192.0.2.10", None);
+    let cname_conflict = validate_add(
+        &[existing_a],
+        "www",
+        &RecordType::CNAME,
+        "target.example.com",
+        RRSET_TTL,
+        None,
+    );
+    assert_eq!(cname_conflict.unwrap_err().code, ErrorCode::RecordConflict);
+}
+
+#[test]
+fn add_accepts_ns_below_the_apex_as_a_delegation() {
+    // Referrals are built from these, so NS is no longer apex-only.
+    for owner in ["child", "deep.child"] {
+        let delegation = validate_add(
+            &[],
+            owner,
+            &RecordType::NS,
+            "ns1.child.example.com",
+            RRSET_TTL,
+            None,
+        );
+        assert!(delegation.is_ok(), "NS at {owner} was rejected");
+    }
+
+    // A delegation usually names more than one server.
+    let ns = test_record(1, "child", RecordType::NS, "ns1.child.example.com", None);
+    let second_ns = validate_add(
+        &[ns],
         "child",
         &RecordType::NS,
-        "ns.example.com",
+        "ns2.child.example.com",
         RRSET_TTL,
         None,
     );
-    assert_eq!(
-        ns_below_apex.unwrap_err().code,
-        ErrorCode::InvalidRecordName
-    );
+    assert!(second_ns.is_ok());
 
-    let existing_a = test_record(1, "www", RecordType::A, "192.0.2.10", None);
-    let cname_conflict = validate_add(
-        &[existing_a],
-        "www",
+    // The record rules still hold at a delegation point.
+    let ns = test_record(1, "child", RecordType::NS, "ns1.child.example.com", None);
+    let cname_at_delegation = validate_add(
+        &[ns],
+        "child",
         &RecordType::CNAME,
         "target.example.com",
         RRSET_TTL,
         None,
     );
-    assert_eq!(cname_conflict.unwrap_err().code, ErrorCode::RecordConflict);
+    assert_eq!(
+        cname_at_delegation.unwrap_err().code,
+        ErrorCode::RecordConflict
+    );
 }
 
 #[test]
@@ -384,6 +414,7 @@ fn test_zone() -> Zone {
         admin_email: "hostmaster@example.com".to_string(),
         ttl: 3600,
         serial: 2023010101,
+        serial_policy: SerialPolicy::Increment,
         refresh: 7200,
         retry: 3600,
         expire: 604800,
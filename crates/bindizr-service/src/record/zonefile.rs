@@ -1,6 +1,12 @@
-use bindizr_core::dns::name::to_fqdn_lowercase;
+use bindizr_core::dns::{
+    name::to_fqdn_lowercase,
+    record::{SshfpRecordValue, SvcbRecordValue, TlsaRecordValue, UnknownRecordValue},
+};
 use domain::{
-    base::iana::{Class, Rtype},
+    base::{
+        iana::{Class, Rtype},
+        rdata::ComposeRecordData,
+    },
     rdata::ZoneRecordData,
     zonefile::inplace::{Entry, Zonefile},
 };
@@ -58,22 +64,18 @@ pub(super) fn parse_zone_file(content: &str, zone_name: &str, default_ttl: i32)
 
                 let record_type = match record.rtype() {
                     Rtype::SOA => continue, // managed via zone fields
-                    Rtype::A => RecordType::A,
-                    Rtype::AAAA => RecordType::AAAA,
-                    Rtype::CNAME => RecordType::CNAME,
-                    Rtype::MX => RecordType::MX,
-                    Rtype::TXT => RecordType::TXT,
-                    Rtype::NS => RecordType::NS,
-                    Rtype::SRV => RecordType::SRV,
-                    Rtype::PTR => RecordType::PTR,
-                    other => {
-                        errors.push(format!(
-                            "unsupported record type '{}' for '{}'",
-                            other,
-                            record.owner()
-                        ));
-                        continue;
-                    }
+                    // Types bindizr does not model come back as `Unknown`.
+                    other => match RecordType::from_type_code(other.to_int()) {
+                        Ok(record_type) => record_type,
+                        Err(_) => {
+                            errors.push(format!(
+                                "unsupported record type '{}' for '{}'",
+                                other,
+                                record.owner()
+                            ));
+                            continue;
+                        }
+                    },
                 };
 
                 // Stored as i32; reject TTLs that would wrap negative (like the
@@ -114,12 +116,31 @@ pub(super) fn parse_zone_file(content: &str, zone_name: &str, default_ttl: i32)
                         }
                         (RecordValueRequest::Segments(segments), None)
                     }
+                    // domain's Display for these is not the presentation form
+                    // bindizr stores (SVCB params, parenthesized hex, or a type
+                    // bindizr keeps opaque), so go through the wire rdata instead.
+                    data if stored_from_rdata(&record_type) => {
+                        let mut rdata = Vec::new();
+                        let _ = data.compose_rdata(&mut rdata);
+                        match value_from_rdata(&record_type, &rdata) {
+                            Ok((value, priority)) => (RecordValueRequest::String(value), priority),
+                            Err(e) => {
+                                errors.push(format!(
+                                    "invalid {} value for '{}': {}",
+                                    record_type,
+                                    record.owner(),
+                                    e
+                                ));
+                                continue;
+                            }
+                        }
+                    }
                     other => {
                         let raw = other.to_string();
                         // Move the MX/SRV priority (first field) into the priority
                         // column like the JSON API; both forms canonicalize equal.
                         match record_type {
-                            RecordType::MX | RecordType::SRV => {
+                            _ if record_type.takes_priority() => {
                                 let mut fields = raw.split_whitespace();
                                 match fields.next().and_then(|p| p.parse::<i32>().ok()) {
                                     Some(prio) => {
@@ -156,6 +177,37 @@ pub(super) fn parse_zone_file(content: &str, zone_name: &str, default_ttl: i32)
     ParsedZoneFile { records, errors }
 }
 
+/// Whether the stored value of `record_type` is decoded from composed rdata
+/// rather than taken from domain's Display.
+fn stored_from_rdata(record_type: &RecordType) -> bool {
+    matches!(
+        record_type,
+        RecordType::SVCB
+            | RecordType::HTTPS
+            | RecordType::TLSA
+            | RecordType::SSHFP
+            | RecordType::Unknown(_)
+    )
+}
+
+/// Decode the rdata of a type whose stored value is built from the wire form,
+/// returning the value and priority columns.
+fn value_from_rdata(
+    record_type: &RecordType,
+    rdata: &[u8],
+) -> Result<(String, Option<i32>), String> {
+    match record_type {
+        RecordType::SVCB | RecordType::HTTPS => {
+            let parsed = SvcbRecordValue::from_rdata(rdata)?;
+            Ok((parsed.encoded(), Some(i32::from(parsed.priority()))))
+        }
+        RecordType::TLSA => Ok((TlsaRecordValue::from_rdata(rdata)?.canonical(), None)),
+        RecordType::SSHFP => Ok((SshfpRecordValue::from_rdata(rdata)?.canonical(), None)),
+        RecordType::Unknown(_) => Ok((UnknownRecordValue::from_rdata(rdata)?.canonical(), None)),
+        other => Err(format!("{other} is not decoded from rdata")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,4 +248,116 @@ mod tests {
             other => panic!("expected segments, got {other:?}"),
         }
     }
+
+    #[test]
+    fn caa_parses_into_presentation_value() {
+        let parsed = parse_zone_file(
+            "@ IN CAA 0 issue \"letsencrypt.org\"\n",
+            "example.com",
+            3600,
+        );
+        assert!(
+            parsed.errors.is_empty(),
+            "unexpected errors: {:?}",
+            parsed.errors
+        );
+        let rec = parsed
+            .records
+            .iter()
+            .find(|r| r.record_type == RecordType::CAA)
+            .expect("a CAA record");
+        let value = match &rec.value {
+            RecordValueRequest::String(value) => value,
+            other => panic!("expected a string value, got {other:?}"),
+        };
+        assert_eq!(
+            RecordType::CAA.encoded_value(value, None).as_deref(),
+            Ok("0 issue \"letsencrypt.org\"")
+        );
+    }
+    #[test]
+    fn https_params_come_through_wire_rdata_in_canonical_order() {
+        let parsed = parse_zone_file("@ IN HTTPS 1 . port=8443 alpn=h2,h3\n", "example.com", 3600);
+        assert!(
+            parsed.errors.is_empty(),
+            "unexpected errors: {:?}",
+            parsed.errors
+        );
+        let rec = parsed
+            .records
+            .iter()
+            .find(|r| r.record_type == RecordType::HTTPS)
+            .expect("an HTTPS record");
+        match &rec.value {
+            RecordValueRequest::String(value) => {
+                assert_eq!(value, ". alpn=\"h2,h3\" port=8443")
+            }
+            other => panic!("expected a string value, got {other:?}"),
+        }
+        assert_eq!(rec.priority, Some(1));
+    }
+    #[test]
+    fn tlsa_and_sshfp_hex_comes_through_as_one_lowercase_string() {
+        let parsed = parse_zone_file(
+            "_25._tcp.mail IN TLSA 3 1 1 ( 8CB0FC6C527506A053F4F14C8464BEBB\n\
+             D6DEDE2738D11468DD953D7D6A3021F1 )\n\
+             host IN SSHFP 4 1 123456789ABCDEF67890123456789ABCDEF67890\n",
+            "example.com",
+            3600,
+        );
+        assert!(
+            parsed.errors.is_empty(),
+            "unexpected errors: {:?}",
+            parsed.errors
+        );
+        let value_of = |record_type: RecordType| match &parsed
+            .records
+            .iter()
+            .find(|r| r.record_type == record_type)
+            .expect("a record of the type")
+            .value
+        {
+            RecordValueRequest::String(value) => value.clone(),
+            other => panic!("expected a string value, got {other:?}"),
+        };
+        assert_eq!(
+            value_of(RecordType::TLSA),
+            "3 1 1 8cb0fc6c527506a053f4f14c8464bebbd6dede2738d11468dd953d7d6a3021f1"
+        );
+        assert_eq!(
+            value_of(RecordType::SSHFP),
+            "4 1 123456789abcdef67890123456789abcdef67890"
+        );
+    }
+    #[test]
+    fn unmodeled_types_import_as_generic_rdata() {
+        let parsed = parse_zone_file(
+            "@ IN TYPE65280 \\# 3 ABCDEF\n\
+             alias IN DNAME target.example.net.\n\
+             bad IN TYPE41 \\# 0\n",
+            "example.com",
+            3600,
+        );
+        let value_of = |record_type: RecordType| match &parsed
+            .records
+            .iter()
+            .find(|r| r.record_type == record_type)
+            .expect("a record of the type")
+            .value
+        {
+            RecordValueRequest::String(value) => value.clone(),
+            other => panic!("expected a string value, got {other:?}"),
+        };
+        assert_eq!(value_of(RecordType::Unknown(65280)), r"\# 3 abcdef");
+        assert_eq!(
+            value_of(RecordType::Unknown(39)),
+            r"\# 20 06746172676574076578616d706c65036e657400"
+        );
+        assert_eq!(parsed.records.len(), 2);
+        assert!(
+            parsed.errors.iter().any(|e| e.contains("unsupported")),
+            "expected the OPT record to be refused, got: {:?}",
+            parsed.errors
+        );
+    }
 }
@@ -1,32 +1,46 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use bindizr_core::dns::name::OwnerName;
+use chrono::{DateTime, Utc};
 
 use super::error::{ErrorCode, ServiceError};
 pub(crate) use crate::database::repository::RepositoryTx;
 use crate::{
     database::{
         error::DatabaseError,
-        get_api_token_repository, get_catalog_zone_state_repository, get_record_repository,
-        get_tsig_key_repository, get_zone_change_repository, get_zone_repository,
-        get_zone_snapshot_repository, get_zone_token_policy_repository,
+        get_api_token_repository, get_audit_log_repository, get_catalog_repository,
+        get_catalog_zone_state_repository, get_record_repository, get_tsig_key_repository,
+        get_webhook_repository, get_zone_change_repository, get_zone_notify_target_repository,
+        get_zone_repository, get_zone_snapshot_repository, get_zone_template_repository,
+        get_zone_token_policy_repository, get_zone_transfer_acl_repository,
         get_zone_tsig_policy_repository,
         model::{
             api_token::ApiToken,
+            catalog::{Catalog, ZoneCatalogProperty},
             record::{Record, RecordWithZone},
             tsig_key::TsigKey,
+            webhook::{Webhook, WebhookDelivery},
             zone::Zone,
+            zone_audit::ZoneAuditEntry,
             zone_change::ZoneChange,
+            zone_notify_target::ZoneNotifyTarget,
             zone_snapshot::ZoneSnapshot,
+            zone_template::{ZoneTemplate, ZoneTemplateLink},
             zone_token_policy::ZoneTokenPolicy,
+            zone_transfer_acl::ZoneTransferAcl,
             zone_tsig_policy::ZoneTsigPolicy,
         },
         repository as db_repository,
-        repository::{LockLevel, RecordFilter, ZoneFilter},
+        repository::{AuditFilter, LockLevel, RecordFilter, ZoneFilter},
     },
     log_error,
 };
 
 pub(super) struct RepositoryService;
 
+/// Transactions this process has committed.
+static COMMITS: AtomicU64 = AtomicU64::new(0);
+
 /// Map a zone insert/update failure: the UNIQUE(name) backstop catches
 /// check-then-act races on the zone name and becomes the same conflict error
 /// the service-level pre-check produces; anything else stays internal.
@@ -62,6 +76,8 @@ impl RepositoryService {
                     log_error!("Failed to commit transaction: {}", e);
                     E::from(ServiceError::internal(internal_msg.to_string()))
                 })?;
+                COMMITS.fetch_add(1, Ordering::Release);
+                crate::webhook::wake_after_commit();
                 Ok(value)
             }
             Err(err) => {
@@ -73,6 +89,10 @@ impl RepositoryService {
         }
     }
 
+    pub(super) fn commit_count() -> u64 {
+        COMMITS.load(Ordering::Acquire)
+    }
+
     pub(super) async fn get_zone_by_name(name: &str) -> Result<Option<Zone>, ServiceError> {
         get_zone_repository()
             .get_by_name(name)
@@ -373,6 +393,34 @@ impl RepositoryService {
             .map_err(|e| ServiceError::internal(format!("failed to load snapshot: {}", e)))
     }
 
+    pub(super) async fn insert_audit_entry_tx(
+        tx: &mut RepositoryTx<'_>,
+        entry: ZoneAuditEntry,
+    ) -> Result<(), ServiceError> {
+        get_audit_log_repository()
+            .insert_tx(tx, entry)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to record audit entry: {}", e)))
+    }
+
+    pub(super) async fn list_audit_entries(
+        filter: AuditFilter,
+    ) -> Result<Vec<ZoneAuditEntry>, ServiceError> {
+        get_audit_log_repository()
+            .list(filter)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load audit log: {}", e)))
+    }
+
+    pub(super) async fn delete_audit_entries_before(
+        before: DateTime<Utc>,
+    ) -> Result<u64, ServiceError> {
+        get_audit_log_repository()
+            .delete_before(before)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to prune audit log: {}", e)))
+    }
+
     pub(super) async fn list_zone_snapshots_in_range(
         zone_id: i32,
         from_serial: i32,
@@ -427,6 +475,78 @@ impl RepositoryService {
             .map_err(|e| ServiceError::internal(format!("failed to load zone changes: {}", e)))
     }
 
+    pub(super) async fn find_zone_snapshot_serial_by_rank_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        rank: u64,
+    ) -> Result<Option<i32>, ServiceError> {
+        get_zone_snapshot_repository()
+            .find_serial_by_rank_tx(tx, zone_id, current_serial, rank)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load snapshots: {}", e)))
+    }
+
+    pub(super) async fn find_oldest_zone_snapshot_serial_since_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        since: DateTime<Utc>,
+    ) -> Result<Option<i32>, ServiceError> {
+        get_zone_snapshot_repository()
+            .find_oldest_serial_since_tx(tx, zone_id, current_serial, since)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load snapshots: {}", e)))
+    }
+
+    pub(super) async fn count_zone_snapshots_before_serial_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, ServiceError> {
+        get_zone_snapshot_repository()
+            .count_before_serial_tx(tx, zone_id, current_serial, serial)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to count snapshots: {}", e)))
+    }
+
+    pub(super) async fn delete_zone_snapshots_before_serial_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, ServiceError> {
+        get_zone_snapshot_repository()
+            .delete_before_serial_tx(tx, zone_id, current_serial, serial)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to prune snapshots: {}", e)))
+    }
+
+    pub(super) async fn count_zone_changes_through_serial_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, ServiceError> {
+        get_zone_change_repository()
+            .count_through_serial_tx(tx, zone_id, current_serial, serial)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to count zone changes: {}", e)))
+    }
+
+    pub(super) async fn delete_zone_changes_through_serial_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        current_serial: i32,
+        serial: i32,
+    ) -> Result<u64, ServiceError> {
+        get_zone_change_repository()
+            .delete_through_serial_tx(tx, zone_id, current_serial, serial)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to prune zone changes: {}", e)))
+    }
+
     pub(super) async fn create_tsig_key(key: TsigKey) -> Result<TsigKey, ServiceError> {
         let name = key.name.clone();
         get_tsig_key_repository().create(key).await.map_err(|e| {
@@ -595,6 +715,99 @@ impl RepositoryService {
             .map_err(|e| ServiceError::internal(format!("failed to delete token policy: {}", e)))
     }
 
+    pub(super) async fn create_zone_transfer_acl(
+        entry: ZoneTransferAcl,
+    ) -> Result<ZoneTransferAcl, ServiceError> {
+        get_zone_transfer_acl_repository()
+            .create(entry)
+            .await
+            .map_err(|e| {
+                // The zone can be deleted between the existence check and
+                // this insert; the FK reports it.
+                if e.is_foreign_key_violation() {
+                    ServiceError::new(ErrorCode::ZoneNotFound, "Zone no longer exists")
+                } else {
+                    ServiceError::internal(format!("failed to create transfer ACL entry: {}", e))
+                }
+            })
+    }
+
+    pub(super) async fn get_zone_transfer_acl_by_id(
+        id: i32,
+    ) -> Result<Option<ZoneTransferAcl>, ServiceError> {
+        get_zone_transfer_acl_repository()
+            .get_by_id(id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load transfer ACL: {}", e)))
+    }
+
+    pub(super) async fn list_zone_transfer_acls_by_zone_id(
+        zone_id: i32,
+    ) -> Result<Vec<ZoneTransferAcl>, ServiceError> {
+        get_zone_transfer_acl_repository()
+            .list_by_zone_id(zone_id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load transfer ACL: {}", e)))
+    }
+
+    pub(super) async fn list_zone_transfer_acls() -> Result<Vec<ZoneTransferAcl>, ServiceError> {
+        get_zone_transfer_acl_repository()
+            .list_all()
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load transfer ACLs: {}", e)))
+    }
+
+    pub(super) async fn delete_zone_transfer_acl(id: i32) -> Result<(), ServiceError> {
+        get_zone_transfer_acl_repository()
+            .delete(id)
+            .await
+            .map_err(|e| {
+                ServiceError::internal(format!("failed to delete transfer ACL entry: {}", e))
+            })
+    }
+
+    pub(super) async fn create_zone_notify_target(
+        entry: ZoneNotifyTarget,
+    ) -> Result<ZoneNotifyTarget, ServiceError> {
+        get_zone_notify_target_repository()
+            .create(entry)
+            .await
+            .map_err(|e| {
+                // The zone can be deleted between the existence check and
+                // this insert; the FK reports it.
+                if e.is_foreign_key_violation() {
+                    ServiceError::new(ErrorCode::ZoneNotFound, "Zone no longer exists")
+                } else {
+                    ServiceError::internal(format!("failed to create NOTIFY target: {}", e))
+                }
+            })
+    }
+
+    pub(super) async fn get_zone_notify_target_by_id(
+        id: i32,
+    ) -> Result<Option<ZoneNotifyTarget>, ServiceError> {
+        get_zone_notify_target_repository()
+            .get_by_id(id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load NOTIFY target: {}", e)))
+    }
+
+    pub(super) async fn list_zone_notify_targets_by_zone_id(
+        zone_id: i32,
+    ) -> Result<Vec<ZoneNotifyTarget>, ServiceError> {
+        get_zone_notify_target_repository()
+            .list_by_zone_id(zone_id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load NOTIFY targets: {}", e)))
+    }
+
+    pub(super) async fn delete_zone_notify_target(id: i32) -> Result<(), ServiceError> {
+        get_zone_notify_target_repository()
+            .delete(id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to delete NOTIFY target: {}", e)))
+    }
+
     pub(super) async fn create_api_token(token: ApiToken) -> Result<ApiToken, ServiceError> {
         let name = token.name.clone();
         get_api_token_repository().create(token).await.map_err(|e| {
@@ -640,10 +853,281 @@ impl RepositoryService {
             .map_err(|e| ServiceError::internal(format!("failed to update token: {}", e)))
     }
 
+    pub(super) async fn rotate_api_token(
+        id: i32,
+        token: &str,
+        previous_token: Option<&str>,
+        previous_token_expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), ServiceError> {
+        get_api_token_repository()
+            .rotate(id, token, previous_token, previous_token_expires_at)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to rotate token: {}", e)))
+    }
+
     pub(super) async fn delete_api_token(id: i32) -> Result<(), ServiceError> {
         get_api_token_repository()
             .delete(id)
             .await
             .map_err(|e| ServiceError::internal(format!("failed to delete token: {}", e)))
     }
+
+    pub(super) async fn create_webhook(webhook: Webhook) -> Result<Webhook, ServiceError> {
+        let name = webhook.name.clone();
+        get_webhook_repository().create(webhook).await.map_err(|e| {
+            // A concurrent create can slip past the service-level name check.
+            if e.is_unique_violation() {
+                ServiceError::webhook_conflict(&name)
+            } else {
+                ServiceError::internal(format!("failed to create webhook: {}", e))
+            }
+        })
+    }
+
+    pub(super) async fn get_webhook_by_name(name: &str) -> Result<Option<Webhook>, ServiceError> {
+        get_webhook_repository()
+            .get_by_name(name)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load webhook: {}", e)))
+    }
+
+    pub(super) async fn list_webhooks() -> Result<Vec<Webhook>, ServiceError> {
+        get_webhook_repository()
+            .list_all()
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load webhooks: {}", e)))
+    }
+
+    pub(super) async fn delete_webhook(id: i32) -> Result<(), ServiceError> {
+        get_webhook_repository()
+            .delete(id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to delete webhook: {}", e)))
+    }
+
+    pub(super) async fn count_watching_webhooks_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+    ) -> Result<u64, ServiceError> {
+        get_webhook_repository()
+            .count_watching_tx(tx, zone_id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load webhooks: {}", e)))
+    }
+
+    pub(super) async fn enqueue_webhook_deliveries_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone_id: i32,
+        delivery: &WebhookDelivery,
+    ) -> Result<u64, ServiceError> {
+        get_webhook_repository()
+            .enqueue_deliveries_tx(tx, zone_id, delivery)
+            .await
+            .map_err(|e| {
+                ServiceError::internal(format!("failed to queue webhook deliveries: {}", e))
+            })
+    }
+
+    pub(super) async fn list_due_webhook_deliveries(
+        now: DateTime<Utc>,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, ServiceError> {
+        get_webhook_repository()
+            .list_due_deliveries(now, limit)
+            .await
+            .map_err(|e| {
+                ServiceError::internal(format!("failed to load webhook deliveries: {}", e))
+            })
+    }
+
+    pub(super) async fn list_webhook_deliveries(
+        webhook_id: i32,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, ServiceError> {
+        get_webhook_repository()
+            .list_deliveries_by_webhook_id(webhook_id, limit)
+            .await
+            .map_err(|e| {
+                ServiceError::internal(format!("failed to load webhook deliveries: {}", e))
+            })
+    }
+
+    pub(super) async fn update_webhook_delivery(
+        delivery: &WebhookDelivery,
+    ) -> Result<(), ServiceError> {
+        get_webhook_repository()
+            .update_delivery(delivery)
+            .await
+            .map_err(|e| {
+                ServiceError::internal(format!("failed to update webhook delivery: {}", e))
+            })
+    }
+
+    pub(super) async fn delete_finished_webhook_deliveries_before(
+        before: DateTime<Utc>,
+    ) -> Result<u64, ServiceError> {
+        get_webhook_repository()
+            .delete_finished_deliveries_before(before)
+            .await
+            .map_err(|e| {
+                ServiceError::internal(format!("failed to prune webhook deliveries: {}", e))
+            })
+    }
+
+    pub(super) async fn create_catalog(catalog: Catalog) -> Result<Catalog, ServiceError> {
+        let name = catalog.name.clone();
+        get_catalog_repository().create(catalog).await.map_err(|e| {
+            // A concurrent create can slip past the service-level name check.
+            if e.is_unique_violation() {
+                ServiceError::catalog_conflict(format!(
+                    "Catalog with name '{}' already exists",
+                    name
+                ))
+            } else {
+                ServiceError::internal(format!("failed to create catalog: {}", e))
+            }
+        })
+    }
+
+    pub(super) async fn get_catalog_by_name(name: &str) -> Result<Option<Catalog>, ServiceError> {
+        get_catalog_repository()
+            .get_by_name(name)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load catalog: {}", e)))
+    }
+
+    pub(super) async fn list_catalogs() -> Result<Vec<Catalog>, ServiceError> {
+        get_catalog_repository()
+            .list_all()
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load catalogs: {}", e)))
+    }
+
+    pub(super) async fn delete_catalog(id: i32) -> Result<(), ServiceError> {
+        get_catalog_repository()
+            .delete(id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to delete catalog: {}", e)))
+    }
+
+    pub(super) async fn list_zone_catalog_properties_by_zone_id(
+        zone_id: i32,
+    ) -> Result<Vec<ZoneCatalogProperty>, ServiceError> {
+        get_catalog_repository()
+            .list_properties_by_zone_id(zone_id)
+            .await
+            .map_err(|e| {
+                ServiceError::internal(format!("failed to load catalog properties: {}", e))
+            })
+    }
+
+    pub(super) async fn list_zone_catalog_properties()
+    -> Result<Vec<ZoneCatalogProperty>, ServiceError> {
+        get_catalog_repository()
+            .list_all_properties()
+            .await
+            .map_err(|e| {
+                ServiceError::internal(format!("failed to load catalog properties: {}", e))
+            })
+    }
+
+    pub(super) async fn replace_zone_catalog_properties(
+        zone_id: i32,
+        properties: &[(String, String)],
+    ) -> Result<(), ServiceError> {
+        get_catalog_repository()
+            .replace_properties(zone_id, properties)
+            .await
+            .map_err(|e| {
+                // The zone can be deleted between the lookup and this write.
+                if e.is_foreign_key_violation() {
+                    ServiceError::new(ErrorCode::ZoneNotFound, "Zone no longer exists")
+                } else {
+                    ServiceError::internal(format!("failed to update catalog properties: {}", e))
+                }
+            })
+    }
+
+    pub(super) async fn create_zone_template(
+        template: ZoneTemplate,
+    ) -> Result<ZoneTemplate, ServiceError> {
+        let name = template.name.clone();
+        get_zone_template_repository()
+            .create(template)
+            .await
+            .map_err(|e| {
+                // A concurrent create can slip past the service-level name check.
+                if e.is_unique_violation() {
+                    ServiceError::template_conflict(&name)
+                } else {
+                    ServiceError::internal(format!("failed to create template: {}", e))
+                }
+            })
+    }
+
+    pub(super) async fn get_zone_template_by_name(
+        name: &str,
+    ) -> Result<Option<ZoneTemplate>, ServiceError> {
+        get_zone_template_repository()
+            .get_by_name(name)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load template: {}", e)))
+    }
+
+    pub(super) async fn list_zone_templates() -> Result<Vec<ZoneTemplate>, ServiceError> {
+        get_zone_template_repository()
+            .list_all()
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load templates: {}", e)))
+    }
+
+    pub(super) async fn update_zone_template(template: &ZoneTemplate) -> Result<(), ServiceError> {
+        get_zone_template_repository()
+            .update(template)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to update template: {}", e)))
+    }
+
+    pub(super) async fn delete_zone_template(template: &ZoneTemplate) -> Result<(), ServiceError> {
+        get_zone_template_repository()
+            .delete(template.id)
+            .await
+            .map_err(|e| {
+                // A zone can be created from the template after the link check.
+                if e.is_foreign_key_violation() {
+                    ServiceError::template_in_use(&template.name, 1)
+                } else {
+                    ServiceError::internal(format!("failed to delete template: {}", e))
+                }
+            })
+    }
+
+    pub(super) async fn create_zone_template_link_tx(
+        tx: &mut RepositoryTx<'_>,
+        link: ZoneTemplateLink,
+    ) -> Result<ZoneTemplateLink, ServiceError> {
+        get_zone_template_repository()
+            .create_link_tx(tx, link)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to link zone to template: {}", e)))
+    }
+
+    pub(super) async fn list_zone_template_links_by_template_id(
+        template_id: i32,
+    ) -> Result<Vec<ZoneTemplateLink>, ServiceError> {
+        get_zone_template_repository()
+            .list_links_by_template_id(template_id)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to load template links: {}", e)))
+    }
+
+    pub(super) async fn update_zone_template_link_applied_records(
+        id: i32,
+        applied_records: &str,
+    ) -> Result<(), ServiceError> {
+        get_zone_template_repository()
+            .update_link_applied_records(id, applied_records)
+            .await
+            .map_err(|e| ServiceError::internal(format!("failed to update template link: {}", e)))
+    }
 }
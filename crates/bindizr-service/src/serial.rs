@@ -1,116 +1,157 @@
-//! SOA serial-number generation: a plain monotonic counter.
+//! SOA serial-number generation under a zone's [`SerialPolicy`].
 //!
-//! Serials start at 1 and advance by exactly one on every zone mutation; the
-//! "when" of a serial comes from `zone_soa_history.created_at`, not from the
-//! serial itself. An explicit serial supplied at zone creation (e.g. when
-//! taking over a zone whose secondaries already track a serial) simply becomes
-//! the starting point and the counter continues from there. Stops at
-//! `i32::MAX` because IXFR encodes serials as `u32` and rejects negatives, so
-//! wrapping is not an option.
-
-use crate::error::ServiceError;
-
-/// Mutations a zone seeded with an explicit serial is guaranteed to have left.
-const RESERVED_SERIAL_HEADROOM: i32 = 10_000_000;
-
-/// Largest serial accepted as a zone's starting point, leaving
-/// `RESERVED_SERIAL_HEADROOM` mutations before the counter reaches the ceiling.
-pub(crate) const MAX_INITIAL_SERIAL: i32 = i32::MAX - RESERVED_SERIAL_HEADROOM;
-
-/// Generate the next SOA serial: `None` (new zone) yields 1; `Some(s)` yields
-/// `s + 1`. `i32::MAX` is an error rather than a saturating no-op, which would
-/// repeat a serial silently — `zone_soa_history` upserts on `(zone_id, serial)`.
-pub(crate) fn generate_serial(current_serial: Option<i32>) -> Result<i32, ServiceError> {
-    match current_serial {
-        Some(serial) if serial == i32::MAX => Err(ServiceError::zone_conflict(format!(
-            "zone serial reached its maximum of {}, so the zone can no longer accept changes",
-            i32::MAX
-        ))),
-        Some(serial) => Ok(serial + 1),
-        None => Ok(1),
-    }
+//! `increment` starts at 1 and advances by one on every mutation; `date`
+//! writes `YYYYMMDDnn`, and `unixtime` the current Unix time. Whatever the
+//! policy, the next serial is always RFC 1982 newer than the current one: a
+//! date or timestamp that would not move the serial forward (a busy day, a
+//! clock step back, a serial seeded ahead of the clock) falls back to adding
+//! one. Addition wraps past `u32::MAX` to 0 as RFC 1982 allows, so a zone
+//! never runs out of serials.
+
+use bindizr_core::dns::serial;
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::{error::ServiceError, model::zone::SerialPolicy};
+
+/// Generate the next SOA serial: `None` (new zone) yields the policy's
+/// starting serial; `Some(s)` yields a serial newer than `s`.
+pub(crate) fn generate_serial(policy: SerialPolicy, current_serial: Option<i32>) -> i32 {
+    serial::from_wire(next_serial_at(
+        policy,
+        current_serial.map(serial::to_wire),
+        Utc::now(),
+    ))
 }
 
-/// Validate a client-supplied starting serial, returning it unchanged.
-pub(crate) fn validate_initial_serial(serial: i32) -> Result<i32, ServiceError> {
-    if serial < 1 {
-        return Err(ServiceError::invalid_zone(format!(
-            "serial {} must be a positive integer",
-            serial
-        )));
+fn next_serial_at(policy: SerialPolicy, current: Option<u32>, now: DateTime<Utc>) -> u32 {
+    let candidate = match policy {
+        SerialPolicy::Increment => None,
+        SerialPolicy::Date => Some(date_serial(now)),
+        SerialPolicy::UnixTime => Some(now.timestamp() as u32),
+    };
+
+    match (current, candidate) {
+        (None, candidate) => candidate.unwrap_or(1),
+        (Some(current), Some(candidate)) => serial::advance(current, candidate),
+        (Some(current), None) => current.wrapping_add(1),
     }
+}
 
-    if serial > MAX_INITIAL_SERIAL {
-        return Err(ServiceError::invalid_zone(format!(
-            "serial {} must not exceed {}, leaving room for the counter to advance",
-            serial, MAX_INITIAL_SERIAL
-        )));
+/// The first `YYYYMMDDnn` serial of `now`'s UTC day.
+fn date_serial(now: DateTime<Utc>) -> u32 {
+    (now.year() as u32 * 10_000 + now.month() * 100 + now.day()) * 100
+}
+
+/// Validate a client-supplied starting serial, returning its stored form.
+pub(crate) fn validate_initial_serial(serial: u32) -> Result<i32, ServiceError> {
+    if serial == 0 {
+        return Err(ServiceError::invalid_zone(
+            "serial 0 is reserved; use a positive integer",
+        ));
     }
 
-    Ok(serial)
+    Ok(serial::from_wire(serial))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{MAX_INITIAL_SERIAL, generate_serial, validate_initial_serial};
+    use chrono::{DateTime, TimeZone, Utc};
 
-    #[test]
-    fn starts_at_one_for_new_zones() {
-        assert_eq!(generate_serial(None).unwrap(), 1);
-    }
+    use super::{SerialPolicy, next_serial_at, validate_initial_serial};
 
-    #[test]
-    fn increments_by_one() {
-        assert_eq!(generate_serial(Some(1)).unwrap(), 2);
-        assert_eq!(generate_serial(Some(41)).unwrap(), 42);
+    fn at(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
     }
 
     #[test]
-    fn continues_from_explicit_legacy_serials() {
-        assert_eq!(generate_serial(Some(2023010101)).unwrap(), 2023010102);
+    fn increment_starts_at_one_and_counts_up() {
+        let now = at(2026, 10, 18);
+        assert_eq!(next_serial_at(SerialPolicy::Increment, None, now), 1);
+        assert_eq!(next_serial_at(SerialPolicy::Increment, Some(41), now), 42);
+        assert_eq!(
+            next_serial_at(SerialPolicy::Increment, Some(2023010101), now),
+            2023010102
+        );
     }
 
     #[test]
-    fn rejects_mutations_once_the_serial_hits_i32_max() {
-        assert!(generate_serial(Some(i32::MAX)).is_err());
+    fn increment_wraps_past_the_largest_serial() {
+        let now = at(2026, 10, 18);
+        assert_eq!(
+            next_serial_at(SerialPolicy::Increment, Some(i32::MAX as u32), now),
+            1 << 31
+        );
+        assert_eq!(
+            next_serial_at(SerialPolicy::Increment, Some(u32::MAX), now),
+            0
+        );
     }
 
     #[test]
-    fn advances_up_to_i32_max() {
-        assert_eq!(generate_serial(Some(i32::MAX - 1)).unwrap(), i32::MAX);
+    fn date_policy_writes_the_day_and_counts_within_it() {
+        let now = at(2026, 10, 18);
+        assert_eq!(next_serial_at(SerialPolicy::Date, None, now), 2026101800);
+        assert_eq!(
+            next_serial_at(SerialPolicy::Date, Some(2026101712), now),
+            2026101800
+        );
+        assert_eq!(
+            next_serial_at(SerialPolicy::Date, Some(2026101800), now),
+            2026101801
+        );
+        // A hundredth change on one day borrows from the next.
+        assert_eq!(
+            next_serial_at(SerialPolicy::Date, Some(2026101899), now),
+            2026101900
+        );
+        // Switching from a small counter jumps to the date.
+        assert_eq!(next_serial_at(SerialPolicy::Date, Some(7), now), 2026101800);
     }
 
     #[test]
-    fn accepts_serial_formats_carried_over_from_another_primary() {
-        // The cap must still admit the formats a takeover carries over:
-        // datestamp (YYYYMMDDnn) and unixtime.
-        assert_eq!(validate_initial_serial(1).unwrap(), 1);
-        assert_eq!(validate_initial_serial(2026072501).unwrap(), 2026072501);
-        assert_eq!(validate_initial_serial(1753401600).unwrap(), 1753401600);
+    fn unixtime_policy_never_moves_backwards() {
+        let now = at(2026, 10, 18);
+        let stamp = now.timestamp() as u32;
+        assert_eq!(next_serial_at(SerialPolicy::UnixTime, None, now), stamp);
+        assert_eq!(
+            next_serial_at(SerialPolicy::UnixTime, Some(stamp - 60), now),
+            stamp
+        );
         assert_eq!(
-            validate_initial_serial(MAX_INITIAL_SERIAL).unwrap(),
-            MAX_INITIAL_SERIAL
+            next_serial_at(SerialPolicy::UnixTime, Some(stamp), now),
+            stamp + 1
+        );
+        assert_eq!(
+            next_serial_at(SerialPolicy::UnixTime, Some(stamp + 60), now),
+            stamp + 61
         );
     }
 
     #[test]
-    fn rejects_non_positive_serials() {
-        assert!(validate_initial_serial(0).is_err());
-        assert!(validate_initial_serial(-1).is_err());
+    fn date_policy_counts_on_from_a_serial_ahead_of_the_clock() {
+        // A serial seeded numerically past today's date stays ahead of it, so
+        // the policy keeps adding one rather than stepping backwards.
+        let now = at(2026, 10, 18);
+        assert_eq!(
+            next_serial_at(SerialPolicy::Date, Some(3_000_000_000), now),
+            3_000_000_001
+        );
+        // Past the wrap, today's date is ahead of u32::MAX again.
+        assert_eq!(
+            next_serial_at(SerialPolicy::Date, Some(u32::MAX), now),
+            2026101800
+        );
     }
 
     #[test]
-    fn rejects_serials_without_room_to_advance() {
-        assert!(validate_initial_serial(MAX_INITIAL_SERIAL + 1).is_err());
-        assert!(validate_initial_serial(i32::MAX).is_err());
+    fn accepts_any_nonzero_starting_serial() {
+        assert_eq!(validate_initial_serial(1).unwrap(), 1);
+        assert_eq!(validate_initial_serial(2026072501).unwrap(), 2026072501);
+        assert_eq!(validate_initial_serial(u32::MAX).unwrap(), -1);
     }
 
     #[test]
-    fn accepted_serials_leave_the_counter_advancing() {
-        let seeded = validate_initial_serial(MAX_INITIAL_SERIAL).unwrap();
-        assert_eq!(
-            generate_serial(Some(seeded)).unwrap(),
-            MAX_INITIAL_SERIAL + 1
-        );
+    fn rejects_zero() {
+        assert!(validate_initial_serial(0).is_err());
     }
 }
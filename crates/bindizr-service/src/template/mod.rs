@@ -0,0 +1,530 @@
+//! Zone templates: named record sets new zones start from.
+//!
+//! Record names and values may hold `{{variable}}` placeholders, filled in
+//! from the variables given when a zone is created from the template plus the
+//! built-in `{{zone}}`. The zone stays linked to the template together with
+//! its variables and the template records it last received, so a later
+//! template change can be diffed against, and applied to, every linked zone.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use bindizr_core::dns::{name::ZoneName, serial};
+use chrono::Utc;
+
+use crate::{
+    RepositoryTx,
+    authorization::{Caller, TokenRole},
+    error::ServiceError,
+    model::{
+        record::{Record, RecordType},
+        zone::Zone,
+        zone_template::{ZoneTemplate, ZoneTemplateLink},
+    },
+    precondition::Precondition,
+    record::{RecordService, parse_record_type, validate_record_add_constraints_normalized},
+    repository::RepositoryService,
+    token::normalize_token_name,
+    types::{
+        GetZoneTemplateResponse, RecordDiff, RecordItem, TemplateZoneSyncResponse,
+        UpdateZoneTemplateRequest, ZoneTemplateSyncResponse,
+    },
+};
+
+mod render;
+mod sync;
+
+use render::{ZONE_VARIABLE, at_record, record_variables, render_records, value_has_placeholder};
+use sync::{same_rdata, stored_records, sync_operations};
+
+const STATUS_IN_SYNC: &str = "in_sync";
+const STATUS_PENDING: &str = "pending";
+const STATUS_APPLIED: &str = "applied";
+const STATUS_FAILED: &str = "failed";
+
+/// Creates, updates, and deletes zone templates, provisions new zones from
+/// them, and carries template changes over to linked zones.
+pub struct ZoneTemplateService;
+
+/// A template rendered for a zone about to be created; see
+/// [`ZoneTemplateService::prepare_zone`].
+pub(crate) struct ZoneProvisioning {
+    template: ZoneTemplate,
+    variables: BTreeMap<String, String>,
+    /// The rendered records in stored form; `zone_id` is set once the zone
+    /// exists.
+    pub(crate) records: Vec<Record>,
+}
+
+impl ZoneTemplateService {
+    /// Create a template. Record types, placeholder syntax and values without
+    /// placeholders are checked now; the rest once a zone's variables are known.
+    pub async fn create(
+        caller: &Caller,
+        name: &str,
+        description: Option<&str>,
+        records: &[RecordItem],
+    ) -> Result<GetZoneTemplateResponse, ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage templates")?;
+
+        let name = normalize_template_name(name)?;
+        let records = validate_records(records)?;
+        if RepositoryService::get_zone_template_by_name(&name)
+            .await?
+            .is_some()
+        {
+            return Err(ServiceError::template_conflict(&name));
+        }
+
+        let now = Utc::now();
+        let template = RepositoryService::create_zone_template(ZoneTemplate {
+            id: 0,
+            name,
+            description: normalize_description(description),
+            records,
+            created_at: now,
+            updated_at: now,
+        })
+        .await?;
+        to_response(&template, Vec::new())
+    }
+
+    /// List the templates with the zones created from each.
+    pub async fn list(caller: &Caller) -> Result<Vec<GetZoneTemplateResponse>, ServiceError> {
+        caller.require_role(TokenRole::Viewer, "view templates")?;
+
+        let zone_names = zone_names().await?;
+        let mut templates = Vec::new();
+        for template in RepositoryService::list_zone_templates().await? {
+            let zones = linked_zone_names(&template, &zone_names).await?;
+            templates.push(to_response(&template, zones)?);
+        }
+        Ok(templates)
+    }
+
+    /// The named template with the zones created from it.
+    pub async fn get(caller: &Caller, name: &str) -> Result<GetZoneTemplateResponse, ServiceError> {
+        caller.require_role(TokenRole::Viewer, "view templates")?;
+
+        let template = find_template(name).await?;
+        let zones = linked_zone_names(&template, &zone_names().await?).await?;
+        to_response(&template, zones)
+    }
+
+    /// Replace a template's description and records, and diff every linked
+    /// zone against the change. With `apply` the change is applied to each
+    /// zone as well; on `dry_run` nothing is saved or applied.
+    pub async fn update(
+        caller: &Caller,
+        name: &str,
+        request: &UpdateZoneTemplateRequest,
+    ) -> Result<ZoneTemplateSyncResponse, ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage templates")?;
+
+        let mut template = find_template(name).await?;
+        template.description = normalize_description(request.description.as_deref());
+        template.records = validate_records(&request.records)?;
+
+        // Linked zones only hold the variables they were created with, so a
+        // new variable could never be rendered for them.
+        let used = record_variables(&request.records)?;
+        let zone_names = zone_names().await?;
+        for link in RepositoryService::list_zone_template_links_by_template_id(template.id).await? {
+            let Some(zone_name) = zone_names.get(&link.zone_id) else {
+                continue;
+            };
+            let variables: BTreeMap<String, String> = from_json(&link.variables)?;
+            check_linked_variables(&template.name, &used, zone_name, &variables)?;
+        }
+        template.updated_at = Utc::now();
+        if !request.dry_run {
+            RepositoryService::update_zone_template(&template).await?;
+        }
+
+        Self::sync(caller, &template, request.apply, request.dry_run).await
+    }
+
+    /// Apply the template's current records to every linked zone still
+    /// behind them; on `dry_run`, only diff them.
+    pub async fn apply(
+        caller: &Caller,
+        name: &str,
+        dry_run: bool,
+    ) -> Result<ZoneTemplateSyncResponse, ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage templates")?;
+
+        let template = find_template(name).await?;
+        Self::sync(caller, &template, true, dry_run).await
+    }
+
+    /// Delete a template. Refused while zones are linked to it; deleting a
+    /// zone drops its link.
+    pub async fn delete(caller: &Caller, name: &str) -> Result<(), ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage templates")?;
+
+        let template = find_template(name).await?;
+        let links = RepositoryService::list_zone_template_links_by_template_id(template.id).await?;
+        if !links.is_empty() {
+            return Err(ServiceError::template_in_use(&template.name, links.len()));
+        }
+        RepositoryService::delete_zone_template(&template).await
+    }
+
+    /// Render the named template for a zone about to be created. Every
+    /// variable the template uses must be supplied, and only those. Runs
+    /// before the zone's transaction; [`Self::provision_zone_tx`] writes the
+    /// result.
+    pub(crate) async fn prepare_zone(
+        template_name: &str,
+        variables: &BTreeMap<String, String>,
+        zone_name: &ZoneName,
+        zone_ttl: i32,
+    ) -> Result<ZoneProvisioning, ServiceError> {
+        let template = find_template(template_name).await?;
+        let records = parse_records(&template)?;
+        check_variables(&template.name, &record_variables(&records)?, variables)?;
+
+        let rendered = render_records(&records, &with_zone_variable(variables, zone_name))?;
+        let records = stored_records(zone_name, zone_ttl, &rendered)?;
+        Ok(ZoneProvisioning {
+            template,
+            variables: variables.clone(),
+            records,
+        })
+    }
+
+    /// Insert a prepared template's records into a zone created in this
+    /// transaction and link the zone to the template. `existing` is what the
+    /// zone was created with (its apex NS); a template record repeating one
+    /// is skipped.
+    pub(crate) async fn provision_zone_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone: &Zone,
+        existing: Vec<Record>,
+        provisioning: ZoneProvisioning,
+    ) -> Result<(), ServiceError> {
+        let mut current = existing;
+        let mut to_insert = Vec::with_capacity(provisioning.records.len());
+        for (index, record) in provisioning.records.into_iter().enumerate() {
+            if current.iter().any(|stored| same_rdata(stored, &record)) {
+                continue;
+            }
+            validate_record_add_constraints_normalized(
+                &current,
+                &record.name,
+                &record.record_type,
+                &record.value,
+                record.ttl,
+                record.priority,
+                None,
+            )
+            .map_err(at_record(index))?;
+            let record = Record {
+                zone_id: zone.id,
+                ..record
+            };
+            current.push(record.clone());
+            to_insert.push(record);
+        }
+
+        // A new zone has no IXFR history to log against, so the rows go in
+        // directly.
+        if !to_insert.is_empty() {
+            RepositoryService::create_records_tx(tx, &to_insert).await?;
+        }
+        RepositoryService::create_zone_template_link_tx(
+            tx,
+            ZoneTemplateLink {
+                id: 0,
+                zone_id: zone.id,
+                template_id: provisioning.template.id,
+                variables: to_json(&provisioning.variables)?,
+                applied_records: provisioning.template.records,
+                created_at: Utc::now(),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Diff every linked zone from the template records it last received to
+    /// `template`'s, applying the change unless `dry_run` or `!apply`. One
+    /// zone failing does not stop the others; it is reported and stays behind.
+    async fn sync(
+        caller: &Caller,
+        template: &ZoneTemplate,
+        apply: bool,
+        dry_run: bool,
+    ) -> Result<ZoneTemplateSyncResponse, ServiceError> {
+        let records = parse_records(template)?;
+        let links = RepositoryService::list_zone_template_links_by_template_id(template.id).await?;
+        // Zone rows are read before their records, so a write landing in
+        // between fails the serial precondition instead of going unseen.
+        let zones: HashMap<i32, Zone> = RepositoryService::list_zones()
+            .await?
+            .into_iter()
+            .map(|zone| (zone.id, zone))
+            .collect();
+
+        let mut results = Vec::with_capacity(links.len());
+        for link in &links {
+            // Deleted since the links were read.
+            let Some(zone) = zones.get(&link.zone_id) else {
+                continue;
+            };
+            let result = Self::sync_zone(caller, template, &records, link, zone, apply && !dry_run)
+                .await
+                .unwrap_or_else(|e| TemplateZoneSyncResponse {
+                    zone: zone.name.to_string(),
+                    status: STATUS_FAILED.to_string(),
+                    serial: None,
+                    diff: RecordDiff::default(),
+                    error: Some(e.message),
+                });
+            results.push(result);
+        }
+        results.sort_by(|a, b| a.zone.cmp(&b.zone));
+
+        let zone_names = results.iter().map(|result| result.zone.clone()).collect();
+        Ok(ZoneTemplateSyncResponse {
+            template: to_response(template, zone_names)?,
+            dry_run,
+            zones: results,
+        })
+    }
+
+    async fn sync_zone(
+        caller: &Caller,
+        template: &ZoneTemplate,
+        records: &[RecordItem],
+        link: &ZoneTemplateLink,
+        zone: &Zone,
+        apply: bool,
+    ) -> Result<TemplateZoneSyncResponse, ServiceError> {
+        let variables: BTreeMap<String, String> = from_json(&link.variables)?;
+        let variables = with_zone_variable(&variables, &zone.name);
+        let applied: Vec<RecordItem> = from_json(&link.applied_records)?;
+        let old = render_records(&applied, &variables)?;
+        let new = render_records(records, &variables)?;
+
+        let zone_records = RepositoryService::list_records_by_zone_id(zone.id).await?;
+        let operations = sync_operations(zone, &zone_records, &old, &new)?;
+        let response =
+            |status: &str, serial: Option<u32>, diff: RecordDiff| TemplateZoneSyncResponse {
+                zone: zone.name.to_string(),
+                status: status.to_string(),
+                serial,
+                diff,
+                error: None,
+            };
+
+        if operations.is_empty() {
+            // Nothing to write, but the zone is now level with the template.
+            if apply && link.applied_records != template.records {
+                RepositoryService::update_zone_template_link_applied_records(
+                    link.id,
+                    &template.records,
+                )
+                .await?;
+            }
+            return Ok(response(STATUS_IN_SYNC, None, RecordDiff::default()));
+        }
+
+        // Both runs must see the zone the operations were computed against.
+        let precondition = Precondition::expect_serial(Some(serial::to_wire(zone.serial)));
+        let preview = RecordService::apply_changeset(
+            caller,
+            zone.name.as_str(),
+            &operations,
+            true,
+            &precondition,
+        )
+        .await?;
+        if !apply {
+            return Ok(response(STATUS_PENDING, None, preview.diff));
+        }
+
+        let applied = RecordService::apply_changeset(
+            caller,
+            zone.name.as_str(),
+            &operations,
+            false,
+            &precondition,
+        )
+        .await?;
+        RepositoryService::update_zone_template_link_applied_records(link.id, &template.records)
+            .await?;
+        Ok(response(STATUS_APPLIED, Some(applied.serial), preview.diff))
+    }
+}
+
+async fn find_template(name: &str) -> Result<ZoneTemplate, ServiceError> {
+    RepositoryService::get_zone_template_by_name(&normalize_template_name(name)?)
+        .await?
+        .ok_or_else(|| ServiceError::template_not_found(name))
+}
+
+/// Template names follow the token naming rules.
+fn normalize_template_name(name: &str) -> Result<String, ServiceError> {
+    normalize_token_name(name).map_err(|e| {
+        ServiceError::new(e.code, e.message.replacen("token name", "template name", 1))
+    })
+}
+
+fn normalize_description(description: Option<&str>) -> Option<String> {
+    description
+        .map(str::trim)
+        .filter(|description| !description.is_empty())
+        .map(str::to_string)
+}
+
+/// Check what can be checked before a zone's variables are known, and
+/// serialize the records for storage.
+fn validate_records(records: &[RecordItem]) -> Result<String, ServiceError> {
+    if records.is_empty() {
+        return Err(ServiceError::invalid_input(
+            "a template needs at least one record",
+        ));
+    }
+    record_variables(records)?;
+    for (index, item) in records.iter().enumerate() {
+        let record_type = parse_record_type(&item.record_type).map_err(at_record(index))?;
+        if record_type == RecordType::SOA {
+            return Err(at_record(index)(ServiceError::invalid_input(
+                "the SOA record is generated from the zone and cannot be templated",
+            )));
+        }
+        if !value_has_placeholder(item) {
+            item.value
+                .to_encoded_value(&record_type, item.priority)
+                .map_err(|e| at_record(index)(ServiceError::invalid_record_value(e)))?;
+        }
+    }
+    to_json(records)
+}
+
+/// Every variable the template uses must be supplied, and only those.
+fn check_variables(
+    template_name: &str,
+    used: &BTreeSet<String>,
+    supplied: &BTreeMap<String, String>,
+) -> Result<(), ServiceError> {
+    if supplied.contains_key(ZONE_VARIABLE) {
+        return Err(ServiceError::invalid_input(format!(
+            "template variable '{}' is built in and cannot be set",
+            ZONE_VARIABLE
+        )));
+    }
+    if let Some(unknown) = supplied.keys().find(|name| !used.contains(*name)) {
+        return Err(ServiceError::invalid_input(format!(
+            "template '{}' has no variable '{}'",
+            template_name, unknown
+        )));
+    }
+    let missing: Vec<&str> = used
+        .iter()
+        .map(String::as_str)
+        .filter(|name| *name != ZONE_VARIABLE && !supplied.contains_key(*name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(ServiceError::invalid_input(format!(
+            "template '{}' needs variable{}: {}",
+            template_name,
+            if missing.len() == 1 { "" } else { "s" },
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// A template update may only use variables a linked zone already supplied.
+fn check_linked_variables(
+    template_name: &str,
+    used: &BTreeSet<String>,
+    zone_name: &str,
+    supplied: &BTreeMap<String, String>,
+) -> Result<(), ServiceError> {
+    let missing: Vec<&str> = used
+        .iter()
+        .map(String::as_str)
+        .filter(|name| *name != ZONE_VARIABLE && !supplied.contains_key(*name))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    Err(ServiceError::invalid_input(format!(
+        "template '{}' is linked to zone '{}', which has no value for variable{}: {}",
+        template_name,
+        zone_name,
+        if missing.len() == 1 { "" } else { "s" },
+        missing.join(", ")
+    )))
+}
+
+fn with_zone_variable(
+    variables: &BTreeMap<String, String>,
+    zone_name: &ZoneName,
+) -> BTreeMap<String, String> {
+    let mut variables = variables.clone();
+    variables.insert(ZONE_VARIABLE.to_string(), zone_name.to_string());
+    variables
+}
+
+fn parse_records(template: &ZoneTemplate) -> Result<Vec<RecordItem>, ServiceError> {
+    from_json(&template.records)
+}
+
+fn to_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<String, ServiceError> {
+    serde_json::to_string(value)
+        .map_err(|e| ServiceError::internal(format!("failed to encode template data: {}", e)))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, ServiceError> {
+    serde_json::from_str(json)
+        .map_err(|e| ServiceError::internal(format!("failed to decode template data: {}", e)))
+}
+
+async fn zone_names() -> Result<HashMap<i32, String>, ServiceError> {
+    Ok(RepositoryService::list_zones()
+        .await?
+        .into_iter()
+        .map(|zone| (zone.id, zone.name.to_string()))
+        .collect())
+}
+
+async fn linked_zone_names(
+    template: &ZoneTemplate,
+    zone_names: &HashMap<i32, String>,
+) -> Result<Vec<String>, ServiceError> {
+    let mut names: Vec<String> =
+        RepositoryService::list_zone_template_links_by_template_id(template.id)
+            .await?
+            .iter()
+            .filter_map(|link| zone_names.get(&link.zone_id).cloned())
+            .collect();
+    names.sort();
+    Ok(names)
+}
+
+fn to_response(
+    template: &ZoneTemplate,
+    zones: Vec<String>,
+) -> Result<GetZoneTemplateResponse, ServiceError> {
+    let records = parse_records(template)?;
+    let variables = record_variables(&records)?
+        .into_iter()
+        .filter(|name| name != ZONE_VARIABLE)
+        .collect();
+    Ok(GetZoneTemplateResponse {
+        id: template.id,
+        name: template.name.clone(),
+        description: template.description.clone(),
+        variables,
+        records,
+        zones,
+        created_at: template.created_at,
+        updated_at: template.updated_at,
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,137 @@
+//! `{{variable}}` placeholders in template record names and values.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    error::ServiceError,
+    types::{RecordItem, RecordValueRequest},
+};
+
+/// The variable every template gets for free: the name of the zone.
+pub(super) const ZONE_VARIABLE: &str = "zone";
+
+enum Piece<'a> {
+    Text(&'a str),
+    Variable(&'a str),
+}
+
+/// Split `text` into literal runs and placeholder names. Whitespace inside
+/// the braces is ignored; a lone `}}` is literal text.
+fn parse(text: &str) -> Result<Vec<Piece<'_>>, String> {
+    let mut pieces = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        if start > 0 {
+            pieces.push(Piece::Text(&rest[..start]));
+        }
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| format!("unclosed placeholder in '{}'", text))?;
+        let name = after[..end].trim();
+        if !is_variable_name(name) {
+            return Err(format!(
+                "invalid variable name '{}' in '{}'; use letters, digits, '_' and '-'",
+                name, text
+            ));
+        }
+        pieces.push(Piece::Variable(name));
+        rest = &after[end + 2..];
+    }
+    if !rest.is_empty() {
+        pieces.push(Piece::Text(rest));
+    }
+    Ok(pieces)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// The record's name and value strings, the fields placeholders may appear in.
+fn texts(item: &RecordItem) -> impl Iterator<Item = &str> {
+    let values: Vec<&str> = match &item.value {
+        RecordValueRequest::String(value) => vec![value.as_str()],
+        RecordValueRequest::Segments(segments) => segments.iter().map(String::as_str).collect(),
+    };
+    std::iter::once(item.name.as_str()).chain(values)
+}
+
+/// Whether the record's value holds a placeholder, so it can only be checked
+/// once rendered.
+pub(super) fn value_has_placeholder(item: &RecordItem) -> bool {
+    texts(item).skip(1).any(|text| text.contains("{{"))
+}
+
+/// Every variable the records use, `zone` included.
+pub(super) fn record_variables(records: &[RecordItem]) -> Result<BTreeSet<String>, ServiceError> {
+    let mut variables = BTreeSet::new();
+    for (index, item) in records.iter().enumerate() {
+        for text in texts(item) {
+            for piece in
+                parse(text).map_err(|e| at_record(index)(ServiceError::invalid_input(e)))?
+            {
+                if let Piece::Variable(name) = piece {
+                    variables.insert(name.to_string());
+                }
+            }
+        }
+    }
+    Ok(variables)
+}
+
+/// Substitute `variables` into the records' names and values. Values are
+/// inserted verbatim; the rendered records are validated like any other.
+pub(super) fn render_records(
+    records: &[RecordItem],
+    variables: &BTreeMap<String, String>,
+) -> Result<Vec<RecordItem>, ServiceError> {
+    records
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let render = |text: &str| {
+                substitute(text, variables)
+                    .map_err(|e| at_record(index)(ServiceError::invalid_input(e)))
+            };
+            Ok(RecordItem {
+                name: render(&item.name)?,
+                record_type: item.record_type.clone(),
+                value: match &item.value {
+                    RecordValueRequest::String(value) => RecordValueRequest::String(render(value)?),
+                    RecordValueRequest::Segments(segments) => RecordValueRequest::Segments(
+                        segments
+                            .iter()
+                            .map(|segment| render(segment))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                },
+                ttl: item.ttl,
+                priority: item.priority,
+            })
+        })
+        .collect()
+}
+
+fn substitute(text: &str, variables: &BTreeMap<String, String>) -> Result<String, String> {
+    let mut rendered = String::with_capacity(text.len());
+    for piece in parse(text)? {
+        match piece {
+            Piece::Text(text) => rendered.push_str(text),
+            Piece::Variable(name) => rendered.push_str(
+                variables
+                    .get(name)
+                    .ok_or_else(|| format!("variable '{}' is not set", name))?,
+            ),
+        }
+    }
+    Ok(rendered)
+}
+
+/// Prefix an error with the 1-based position of the template record.
+pub(super) fn at_record(index: usize) -> impl Fn(ServiceError) -> ServiceError {
+    move |e| ServiceError::new(e.code, format!("record {}: {}", index + 1, e.message))
+}
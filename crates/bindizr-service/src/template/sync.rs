@@ -0,0 +1,120 @@
+//! Carrying a template change over to a zone created from it.
+
+use std::collections::HashSet;
+
+use bindizr_core::dns::name::ZoneName;
+use chrono::Utc;
+
+use super::render::at_record;
+use crate::{
+    error::ServiceError,
+    model::{record::Record, zone::Zone},
+    record::{normalize_record_owner_name, prepare_record},
+    types::{ChangesetOperation, RecordItem},
+};
+
+/// Rendered template records in stored form: owner names relative to the
+/// zone, values encoded and omitted TTLs fixed to `zone_ttl`. `zone_id` is
+/// left for the caller.
+pub(super) fn stored_records(
+    zone_name: &ZoneName,
+    zone_ttl: i32,
+    items: &[RecordItem],
+) -> Result<Vec<Record>, ServiceError> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let prepared = prepare_record(
+                &item.name,
+                &item.record_type,
+                &item.value,
+                item.ttl,
+                item.priority,
+            )
+            .map_err(at_record(index))?;
+            Ok(Record {
+                id: 0,
+                name: normalize_record_owner_name(&prepared.owner_name, zone_name)
+                    .map_err(at_record(index))?,
+                record_type: prepared.record_type,
+                value: prepared.value,
+                ttl: prepared.ttl.unwrap_or(zone_ttl),
+                priority: prepared.priority,
+                zone_id: 0,
+                created_at: Utc::now(),
+            })
+        })
+        .collect()
+}
+
+/// Whether two rows hold the same rdata at the same name, whatever their TTL.
+pub(super) fn same_rdata(a: &Record, b: &Record) -> bool {
+    a.name == b.name
+        && a.record_type == b.record_type
+        && a.record_type
+            .values_equal(&a.value, a.priority, &b.value, b.priority)
+}
+
+/// The changeset moving a zone from the `old` rendering of its template to
+/// the `new` one. Only what the template changed is touched: records it
+/// dropped are deleted, records whose TTL it changed are replaced, and
+/// records it added are added unless the zone already holds them. Records
+/// edited or deleted by hand since are left as they are. Deletes come first,
+/// so a replaced RRset never holds two TTLs at once.
+pub(super) fn sync_operations(
+    zone: &Zone,
+    zone_records: &[Record],
+    old: &[RecordItem],
+    new: &[RecordItem],
+) -> Result<Vec<ChangesetOperation>, ServiceError> {
+    let old_records = stored_records(&zone.name, zone.ttl, old)?;
+    let new_records = stored_records(&zone.name, zone.ttl, new)?;
+    let held = |record: &Record| {
+        zone_records
+            .iter()
+            .filter(|stored| same_rdata(stored, record))
+            .collect::<Vec<_>>()
+    };
+
+    let mut deletes = Vec::new();
+    let mut adds = Vec::new();
+    for old_record in &old_records {
+        match new_records
+            .iter()
+            .position(|new_record| same_rdata(new_record, old_record))
+        {
+            None => deletes.extend(held(old_record).iter().map(|stored| stored.id)),
+            Some(index) => {
+                let retimed: Vec<i32> = held(old_record)
+                    .iter()
+                    .filter(|stored| stored.ttl != new_records[index].ttl)
+                    .map(|stored| stored.id)
+                    .collect();
+                if !retimed.is_empty() && old_record.ttl != new_records[index].ttl {
+                    deletes.extend(retimed);
+                    adds.push(index);
+                }
+            }
+        }
+    }
+    for (index, new_record) in new_records.iter().enumerate() {
+        if !old_records.iter().any(|old| same_rdata(old, new_record)) && held(new_record).is_empty()
+        {
+            adds.push(index);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    deletes.retain(|id| seen.insert(*id));
+    adds.sort_unstable();
+    adds.dedup();
+
+    Ok(deletes
+        .into_iter()
+        .map(|id| ChangesetOperation::Delete { id })
+        .chain(adds.into_iter().map(|index| ChangesetOperation::Add {
+            record: new[index].clone(),
+        }))
+        .collect())
+}
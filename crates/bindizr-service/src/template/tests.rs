@@ -0,0 +1,314 @@
+use bindizr_core::dns::name::{OwnerName, ZoneName};
+
+use super::*;
+use crate::{
+    error::ErrorCode,
+    model::zone::SerialPolicy,
+    types::{ChangesetOperation, RecordValueRequest},
+};
+
+fn test_zone() -> Zone {
+    Zone {
+        id: 1,
+        name: ZoneName::from_row("example.com"),
+        primary_ns: "ns1.example.net".to_string(),
+        admin_email: "hostmaster@example.com".to_string(),
+        ttl: 3600,
+        serial: 1,
+        serial_policy: SerialPolicy::Increment,
+        refresh: 7200,
+        retry: 3600,
+        expire: 604800,
+        minimum_ttl: 86400,
+        created_at: Utc::now(),
+    }
+}
+
+fn item(name: &str, record_type: &str, value: &str, ttl: Option<i32>) -> RecordItem {
+    RecordItem {
+        name: name.to_string(),
+        record_type: record_type.to_string(),
+        value: RecordValueRequest::String(value.to_string()),
+        ttl,
+        priority: (record_type == "MX").then_some(10),
+    }
+}
+
+fn variables(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+    pairs
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+/// The zone's rows as a template rendering would have stored them, with ids.
+fn zone_records(items: &[RecordItem]) -> Vec<Record> {
+    let zone = test_zone();
+    stored_records(&zone.name, zone.ttl, items)
+        .unwrap()
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| Record {
+            id: index as i32 + 1,
+            zone_id: zone.id,
+            ..record
+        })
+        .collect()
+}
+
+fn describe(operations: &[ChangesetOperation]) -> Vec<String> {
+    operations
+        .iter()
+        .map(|operation| match operation {
+            ChangesetOperation::Delete { id } => format!("delete {}", id),
+            ChangesetOperation::Add { record } => format!(
+                "add {} {} ttl={:?}",
+                record.name, record.record_type, record.ttl
+            ),
+            ChangesetOperation::Update { id, .. } => format!("update {}", id),
+        })
+        .collect()
+}
+
+#[test]
+fn render_records_fills_placeholders_in_names_values_and_txt_segments() {
+    let mut records = vec![
+        item("{{ env }}", "A", "{{ip}}", None),
+        item("@", "MX", "mail.{{zone}}.", Some(300)),
+    ];
+    records.push(RecordItem {
+        value: RecordValueRequest::Segments(vec![
+            "v=spf1 include:{{zone}}".to_string(),
+            " -all".to_string(),
+        ]),
+        ..item("@", "TXT", "", None)
+    });
+
+    let rendered = render_records(
+        &records,
+        &variables(&[
+            ("env", "staging"),
+            ("ip", "192.0.2.7"),
+            ("zone", "example.com"),
+        ]),
+    )
+    .unwrap();
+    assert_eq!(rendered[0].name, "staging");
+    assert!(matches!(&rendered[0].value, RecordValueRequest::String(v) if v == "192.0.2.7"));
+    assert!(
+        matches!(&rendered[1].value, RecordValueRequest::String(v) if v == "mail.example.com.")
+    );
+    assert_eq!(
+        (rendered[1].ttl, rendered[1].priority),
+        (Some(300), Some(10))
+    );
+    assert!(matches!(
+        &rendered[2].value,
+        RecordValueRequest::Segments(segments) if segments[0] == "v=spf1 include:example.com"
+    ));
+
+    let err = render_records(&records, &variables(&[("zone", "example.com")])).unwrap_err();
+    assert_eq!(err.message, "record 1: variable 'env' is not set");
+}
+
+#[test]
+fn record_variables_collects_names_and_rejects_malformed_placeholders() {
+    let used = record_variables(&[
+        item("{{host}}", "A", "{{ip}}", None),
+        item("@", "CNAME", "{{host}}.{{zone}}.", None),
+    ])
+    .unwrap();
+    assert_eq!(used.into_iter().collect::<Vec<_>>(), ["host", "ip", "zone"]);
+
+    for (value, message) in [
+        ("{{ip", "record 1: unclosed placeholder in '{{ip'"),
+        (
+            "{{not valid}}",
+            "record 1: invalid variable name 'not valid' in '{{not valid}}'; use letters, digits, '_' and '-'",
+        ),
+    ] {
+        let err = record_variables(&[item("@", "A", value, None)]).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+        assert_eq!(err.message, message);
+    }
+}
+
+#[test]
+fn check_variables_requires_exactly_the_variables_the_template_uses() {
+    let used: BTreeSet<String> = ["ip", "mail_host", "zone"]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+
+    check_variables(
+        "base",
+        &used,
+        &variables(&[("ip", "192.0.2.1"), ("mail_host", "mx")]),
+    )
+    .unwrap();
+
+    let missing = check_variables("base", &used, &variables(&[])).unwrap_err();
+    assert_eq!(
+        missing.message,
+        "template 'base' needs variables: ip, mail_host"
+    );
+    let unknown = check_variables(
+        "base",
+        &used,
+        &variables(&[("ip", "192.0.2.1"), ("mail_host", "mx"), ("extra", "x")]),
+    )
+    .unwrap_err();
+    assert_eq!(unknown.message, "template 'base' has no variable 'extra'");
+    let builtin = check_variables("base", &used, &variables(&[("zone", "x")])).unwrap_err();
+    assert_eq!(
+        builtin.message,
+        "template variable 'zone' is built in and cannot be set"
+    );
+}
+
+#[test]
+fn check_linked_variables_rejects_variables_a_linked_zone_never_supplied() {
+    let used: BTreeSet<String> = ["ip", "zone"].into_iter().map(str::to_string).collect();
+
+    check_linked_variables(
+        "base",
+        &used,
+        "example.com",
+        &variables(&[("ip", "192.0.2.1")]),
+    )
+    .unwrap();
+    // Variables the template no longer uses are left on the link unused.
+    check_linked_variables(
+        "base",
+        &used,
+        "example.com",
+        &variables(&[("ip", "192.0.2.1"), ("old", "x")]),
+    )
+    .unwrap();
+
+    let added: BTreeSet<String> = ["ip", "mail_host", "zone"]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let err = check_linked_variables(
+        "base",
+        &added,
+        "example.com",
+        &variables(&[("ip", "192.0.2.1")]),
+    )
+    .unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidInput);
+    assert_eq!(
+        err.message,
+        "template 'base' is linked to zone 'example.com', which has no value for variable: mail_host"
+    );
+}
+
+#[test]
+fn validate_records_checks_types_and_values_without_placeholders() {
+    validate_records(&[
+        item("@", "A", "{{ip}}", None),
+        item("@", "CAA", "0 issue \"letsencrypt.org\"", None),
+    ])
+    .unwrap();
+
+    let empty = validate_records(&[]).unwrap_err();
+    assert_eq!(empty.message, "a template needs at least one record");
+
+    let soa = validate_records(&[item("@", "SOA", "x", None)]).unwrap_err();
+    assert!(soa.message.starts_with("record 1: the SOA record"));
+
+    let bad_value = validate_records(&[
+        item("@", "A", "{{ip}}", None),
+        item("www", "A", "not-an-address", None),
+    ])
+    .unwrap_err();
+    assert_eq!(bad_value.code, ErrorCode::InvalidRecordValue);
+    assert!(bad_value.message.starts_with("record 2: "));
+}
+
+#[test]
+fn sync_operations_applies_only_what_the_template_changed() {
+    let old = [
+        item("@", "MX", "mail.example.com.", None),
+        item("@", "TXT", "v=spf1 mx -all", Some(300)),
+        item("_dmarc", "TXT", "v=DMARC1; p=none", None),
+    ];
+    let new = [
+        item("@", "MX", "mail.example.com.", None),
+        item("@", "TXT", "v=spf1 mx -all", Some(600)),
+        item("@", "CAA", "0 issue \"letsencrypt.org\"", None),
+    ];
+    let records = zone_records(&old);
+
+    let operations = sync_operations(&test_zone(), &records, &old, &new).unwrap();
+    assert_eq!(
+        describe(&operations),
+        [
+            "delete 2",
+            "delete 3",
+            "add @ TXT ttl=Some(600)",
+            "add @ CAA ttl=None",
+        ]
+    );
+
+    // Once applied, the same change has nothing left to do.
+    assert!(
+        sync_operations(&test_zone(), &zone_records(&new), &new, &new)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn sync_operations_leaves_records_edited_by_hand_alone() {
+    let old = [
+        item("@", "MX", "mail.example.com.", None),
+        item("www", "A", "192.0.2.10", None),
+    ];
+    let new = [
+        item("@", "MX", "mail.example.com.", Some(300)),
+        item("www", "A", "192.0.2.10", None),
+        item("api", "A", "192.0.2.20", None),
+    ];
+    // The MX was deleted by hand and `api` added by hand, TTL and all.
+    let mut records = zone_records(&[old[1].clone(), item("api", "A", "192.0.2.20", Some(60))]);
+    records.push(Record {
+        id: 9,
+        name: OwnerName::from_row("extra"),
+        ..records[0].clone()
+    });
+
+    assert!(
+        sync_operations(&test_zone(), &records, &old, &new)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn stored_records_normalizes_names_and_fixes_omitted_ttls() {
+    let zone = test_zone();
+    let records = stored_records(
+        &zone.name,
+        zone.ttl,
+        &[
+            item("WWW.example.com.", "A", "192.0.2.1", None),
+            item("@", "NS", "ns1.example.net.", Some(86400)),
+        ],
+    )
+    .unwrap();
+    assert_eq!(records[0].name, OwnerName::from_row("www"));
+    assert_eq!(records[0].ttl, 3600);
+    assert!(records[1].name.is_apex());
+    assert_eq!(records[1].ttl, 86400);
+
+    let outside = stored_records(
+        &zone.name,
+        zone.ttl,
+        &[item("www.example.org.", "A", "192.0.2.1", None)],
+    )
+    .unwrap_err();
+    assert_eq!(outside.code, ErrorCode::InvalidRecordName);
+    assert!(outside.message.starts_with("record 1: "));
+}
@@ -4,11 +4,20 @@ use rand::{RngExt, distr::Alphanumeric};
 use sha2::{Digest, Sha256};
 
 use super::{error::ServiceError, repository::RepositoryService};
-use crate::{authorization::Caller, model::api_token::ApiToken};
+use crate::{
+    authorization::Caller,
+    model::api_token::{ApiToken, TokenRole},
+};
 
 const MAX_TOKEN_NAME_LEN: usize = 255;
 
-/// Creates, lists, and revokes API tokens.
+/// How long a rotated-out secret keeps working when the caller names no
+/// overlap, long enough for a deploy to pick up the new one.
+pub const DEFAULT_ROTATION_OVERLAP_MINUTES: i64 = 60;
+
+const MAX_ROTATION_OVERLAP_MINUTES: i64 = 7 * 24 * 60;
+
+/// Creates, lists, rotates, and revokes API tokens.
 pub struct TokenService;
 
 pub(crate) fn hash_token(token: &str) -> String {
@@ -19,18 +28,21 @@ pub(crate) fn hash_token(token: &str) -> String {
 
 impl TokenService {
     /// Create a new API token; the returned token carries the raw secret to
-    /// show once.
+    /// show once. `global` is shorthand for the `admin` role; without either,
+    /// the token is scoped.
     pub async fn create(
         caller: &Caller,
         name: &str,
         description: Option<&str>,
         expires_in_days: Option<i64>,
-        is_global: bool,
+        global: bool,
+        role: Option<&str>,
     ) -> Result<ApiToken, ServiceError> {
         caller.require_global("manage API tokens")?;
 
         let name = normalize_token_name(name)?;
         validate_expires_in_days(expires_in_days)?;
+        let role = resolve_role(global, role)?;
 
         if RepositoryService::get_api_token_by_name(&name)
             .await?
@@ -39,12 +51,7 @@ impl TokenService {
             return Err(ServiceError::token_conflict(&name));
         }
 
-        let raw_token: String = rand::rng()
-            .sample_iter(Alphanumeric)
-            .take(32)
-            .map(char::from)
-            .collect();
-
+        let raw_token = generate_secret();
         let token_hash = hash_token(&raw_token);
 
         let expires_at = expires_in_days.map(|days| Utc::now() + Duration::days(days));
@@ -54,10 +61,12 @@ impl TokenService {
             name,
             token: token_hash,
             description: description.map(|d| d.to_string()),
-            is_global,
+            role,
             expires_at,
             created_at: Utc::now(),
             last_used_at: None,
+            previous_token: None,
+            previous_token_expires_at: None,
         })
         .await?;
 
@@ -72,10 +81,53 @@ impl TokenService {
         let mut tokens = RepositoryService::list_api_tokens().await?;
         for token in &mut tokens {
             token.token.clear();
+            token.previous_token = None;
         }
         Ok(tokens)
     }
 
+    /// Issue a new secret for the named token, keeping its name, scope and
+    /// policies. The old secret stays valid for `overlap_minutes` (default
+    /// `DEFAULT_ROTATION_OVERLAP_MINUTES`; 0 revokes it at once), replacing
+    /// any secret an earlier rotation left in its overlap.
+    pub async fn rotate(
+        caller: &Caller,
+        name: &str,
+        overlap_minutes: Option<i64>,
+    ) -> Result<ApiToken, ServiceError> {
+        caller.require_global("manage API tokens")?;
+
+        let overlap_minutes = overlap_minutes.unwrap_or(DEFAULT_ROTATION_OVERLAP_MINUTES);
+        validate_overlap_minutes(overlap_minutes)?;
+
+        let mut token = RepositoryService::get_api_token_by_name(&normalize_token_name(name)?)
+            .await?
+            .ok_or_else(|| ServiceError::token_not_found(name))?;
+
+        let raw_token = generate_secret();
+        let (previous_token, previous_token_expires_at) = if overlap_minutes > 0 {
+            (
+                Some(token.token),
+                Some(Utc::now() + Duration::minutes(overlap_minutes)),
+            )
+        } else {
+            (None, None)
+        };
+
+        RepositoryService::rotate_api_token(
+            token.id,
+            &hash_token(&raw_token),
+            previous_token.as_deref(),
+            previous_token_expires_at,
+        )
+        .await?;
+
+        token.token = raw_token;
+        token.previous_token = None;
+        token.previous_token_expires_at = previous_token_expires_at;
+        Ok(token)
+    }
+
     /// Delete the API token with the given name, returning `NotFound` if it
     /// is absent.
     pub async fn delete(caller: &Caller, name: &str) -> Result<(), ServiceError> {
@@ -89,6 +141,15 @@ impl TokenService {
     }
 }
 
+/// A fresh 32-character alphanumeric secret.
+fn generate_secret() -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
 /// Lowercased so one name means one token on every backend: MySQL compares the
 /// column case-insensitively, the others exactly.
 pub(crate) fn normalize_token_name(name: &str) -> Result<String, ServiceError> {
@@ -123,5 +184,34 @@ fn validate_expires_in_days(expires_in_days: Option<i64>) -> Result<(), ServiceE
     Ok(())
 }
 
+fn resolve_role(global: bool, role: Option<&str>) -> Result<TokenRole, ServiceError> {
+    let role = match role.map(str::trim).filter(|role| !role.is_empty()) {
+        Some(role) => role
+            .parse::<TokenRole>()
+            .map_err(ServiceError::invalid_input)?,
+        None if global => TokenRole::Admin,
+        None => TokenRole::Scoped,
+    };
+    if global && role != TokenRole::Admin {
+        return Err(ServiceError::invalid_input(format!(
+            "a global token has the 'admin' role, not '{}'",
+            role
+        )));
+    }
+
+    Ok(role)
+}
+
+fn validate_overlap_minutes(overlap_minutes: i64) -> Result<(), ServiceError> {
+    if !(0..=MAX_ROTATION_OVERLAP_MINUTES).contains(&overlap_minutes) {
+        return Err(ServiceError::invalid_input(format!(
+            "overlap_minutes must be between 0 and {}",
+            MAX_ROTATION_OVERLAP_MINUTES
+        )));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests;
@@ -1,5 +1,7 @@
-use super::{normalize_token_name, validate_expires_in_days};
-use crate::error::ErrorCode;
+use super::{
+    normalize_token_name, resolve_role, validate_expires_in_days, validate_overlap_minutes,
+};
+use crate::{error::ErrorCode, model::api_token::TokenRole};
 
 #[test]
 fn normalize_token_name_trims_and_accepts_plain_names() {
@@ -40,3 +42,33 @@ fn validate_expires_in_days_rejects_non_positive_values() {
     assert_eq!(zero.code, ErrorCode::InvalidInput);
     assert_eq!(negative.code, ErrorCode::InvalidInput);
 }
+
+#[test]
+fn validate_overlap_minutes_accepts_zero_up_to_a_week() {
+    validate_overlap_minutes(0).unwrap();
+    validate_overlap_minutes(7 * 24 * 60).unwrap();
+
+    for minutes in [-1, 7 * 24 * 60 + 1] {
+        let err = validate_overlap_minutes(minutes).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+    }
+}
+
+#[test]
+fn resolve_role_defaults_to_scoped_and_treats_global_as_admin() {
+    assert_eq!(resolve_role(false, None).unwrap(), TokenRole::Scoped);
+    assert_eq!(resolve_role(true, None).unwrap(), TokenRole::Admin);
+    assert_eq!(
+        resolve_role(false, Some("record-editor")).unwrap(),
+        TokenRole::RecordEditor
+    );
+    assert_eq!(resolve_role(true, Some("admin")).unwrap(), TokenRole::Admin);
+}
+
+#[test]
+fn resolve_role_rejects_unknown_and_conflicting_roles() {
+    for (global, role) in [(false, "root"), (true, "viewer")] {
+        let err = resolve_role(global, Some(role)).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidInput);
+    }
+}
@@ -0,0 +1,46 @@
+//! Audit log payloads.
+
+use bindizr_core::dns::serial;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::zone_audit::ZoneAuditEntry;
+
+/// One audit entry: which zone changed and how, and who changed it.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct AuditEntryResponse {
+    #[schema(example = "example.com")]
+    pub zone_name: String,
+    #[schema(example = 7)]
+    pub serial: u32,
+    /// `created`, `updated` or `deleted`; empty for entries recorded before
+    /// the audit log had its own table.
+    #[schema(example = "updated")]
+    pub action: Option<String>,
+    #[schema(example = "token:ci-deploy")]
+    pub actor: Option<String>,
+    pub tsig_key: Option<String>,
+    #[schema(example = "192.0.2.10")]
+    pub source_address: Option<String>,
+    #[schema(example = "http")]
+    pub transport: Option<String>,
+    pub request_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ZoneAuditEntry> for AuditEntryResponse {
+    fn from(entry: ZoneAuditEntry) -> Self {
+        AuditEntryResponse {
+            zone_name: entry.zone_name,
+            serial: serial::to_wire(entry.serial),
+            action: entry.action,
+            actor: entry.attribution.actor,
+            tsig_key: entry.attribution.tsig_key_name,
+            source_address: entry.attribution.source_address,
+            transport: entry.attribution.transport,
+            request_id: entry.attribution.request_id,
+            created_at: entry.created_at,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Catalog zone payloads.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{catalog::ZoneCatalogProperties, model::catalog::Catalog};
+
+/// Request body for creating a named catalog.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateCatalogRequest {
+    /// Catalog zone name secondaries transfer, e.g. as a BIND
+    /// `catalog-zones` entry.
+    #[schema(example = "pool-a.catalog.example")]
+    pub name: String,
+    #[schema(example = "Zones served by the EU secondaries")]
+    pub description: Option<String>,
+}
+
+/// API representation of a named catalog.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct GetCatalogResponse {
+    #[schema(example = 1)]
+    pub id: i32,
+    #[schema(example = "pool-a.catalog.example")]
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GetCatalogResponse {
+    pub fn from_catalog(catalog: &Catalog) -> Self {
+        GetCatalogResponse {
+            id: catalog.id,
+            name: catalog.name.clone(),
+            description: catalog.description.clone(),
+            created_at: catalog.created_at,
+        }
+    }
+}
+
+/// A single catalog wrapped in a response envelope.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct CatalogResponse {
+    pub catalog: GetCatalogResponse,
+}
+
+/// List of named catalogs; `catalog.bind` is implicit.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct CatalogListResponse {
+    pub catalogs: Vec<GetCatalogResponse>,
+}
+
+/// Request body replacing a zone's catalog membership and properties.
+/// Omitted fields are cleared.
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema)]
+pub struct SetZoneCatalogPropertiesRequest {
+    /// Catalog listing the zone; omit for `catalog.bind`.
+    #[schema(example = "pool-a.catalog.example")]
+    pub catalog: Option<String>,
+    /// RFC 9432 `group` property, telling consumers how to provision the zone.
+    #[schema(example = "signed")]
+    pub group: Option<String>,
+    /// RFC 9432 `coo` (change of ownership): the catalog taking the zone
+    /// over. The zone is listed in both until `catalog` is moved to it.
+    #[schema(example = "pool-b.catalog.example")]
+    pub coo: Option<String>,
+    /// Custom properties, served as TXT records under `ext`.
+    #[serde(default)]
+    pub ext: BTreeMap<String, String>,
+}
+
+impl SetZoneCatalogPropertiesRequest {
+    pub fn into_properties(self) -> ZoneCatalogProperties {
+        ZoneCatalogProperties {
+            catalog: self.catalog.unwrap_or_default(),
+            group: self.group,
+            coo: self.coo,
+            ext: self.ext,
+        }
+    }
+}
+
+/// API representation of a zone's catalog membership and properties.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct GetZoneCatalogPropertiesResponse {
+    #[schema(example = "example.com")]
+    pub zone: String,
+    #[schema(example = "pool-a.catalog.example")]
+    pub catalog: String,
+    #[schema(example = "signed")]
+    pub group: Option<String>,
+    pub coo: Option<String>,
+    pub ext: BTreeMap<String, String>,
+}
+
+impl GetZoneCatalogPropertiesResponse {
+    pub fn from_properties(zone: &str, properties: &ZoneCatalogProperties) -> Self {
+        GetZoneCatalogPropertiesResponse {
+            zone: zone.to_string(),
+            catalog: properties.catalog.clone(),
+            group: properties.group.clone(),
+            coo: properties.coo.clone(),
+            ext: properties.ext.clone(),
+        }
+    }
+}
+
+/// A zone's catalog properties wrapped in a response envelope.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ZoneCatalogPropertiesResponse {
+    pub catalog_properties: GetZoneCatalogPropertiesResponse,
+}
@@ -1,4 +1,4 @@
-//! Zone-file import request, mode, and summary payloads.
+//! Zone-file import and zone pull request, mode, and summary payloads.
 
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -64,3 +64,35 @@ pub struct ImportSummary {
     #[schema(example = 0)]
     pub skipped: usize,
 }
+
+/// Request body for pulling a zone from another primary by AXFR.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct PullZoneRequest {
+    /// The primary to transfer from: `host[:port]` or an IP address, port 53
+    /// by default.
+    #[schema(example = "192.0.2.53")]
+    pub server: String,
+    /// Name of a stored TSIG key to sign the transfer with.
+    #[serde(default, alias = "tsigKey")]
+    #[schema(example = "transfer-key")]
+    pub tsig_key: Option<String>,
+    #[serde(default)]
+    pub mode: ImportMode,
+    /// When true, transfer and validate without applying any change.
+    #[serde(default, alias = "dryRun")]
+    pub dry_run: bool,
+}
+
+/// Result of a zone pull: what the primary served, and how it was imported.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct PullZoneResponse {
+    #[schema(example = "192.0.2.53")]
+    pub server: String,
+    /// The serial of the primary's SOA. The zone keeps its own serial.
+    #[schema(example = 2026101801)]
+    pub serial: u32,
+    /// Records transferred, the SOA not counted.
+    #[schema(example = 12)]
+    pub transferred: usize,
+    pub import: ImportZoneFileResponse,
+}
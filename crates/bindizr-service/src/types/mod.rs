@@ -5,46 +5,84 @@
 //! socket carries the same shapes, so a response type the CLI reads back
 //! derives `Deserialize` too.
 
+mod audit;
+mod catalog;
 mod common;
 mod external_dns;
 mod import;
+mod notify_target;
 mod pagination;
 mod record;
+mod rrset;
 mod snapshot;
+mod template;
 mod token;
 mod token_policy;
+mod transfer_acl;
 mod tsig;
+mod webhook;
 mod zone;
 
+pub use audit::AuditEntryResponse;
+pub use catalog::{
+    CatalogListResponse, CatalogResponse, CreateCatalogRequest, GetCatalogResponse,
+    GetZoneCatalogPropertiesResponse, SetZoneCatalogPropertiesRequest,
+    ZoneCatalogPropertiesResponse,
+};
 pub use common::{ErrorResponse, HealthResponse, MessageResponse};
 pub use external_dns::{
     ExternalDnsAdjustRequest, ExternalDnsAdjustResponse, ExternalDnsChangesRequest,
     ExternalDnsChangesResponse, ExternalDnsRecordItem, ExternalDnsRecordsResponse,
     ExternalDnsRrset, ExternalDnsRrsetUpdate, ExternalDnsZonesResponse,
 };
-pub use import::{ImportMode, ImportSummary, ImportZoneFileRequest, ImportZoneFileResponse};
+pub use import::{
+    ImportMode, ImportSummary, ImportZoneFileRequest, ImportZoneFileResponse, PullZoneRequest,
+    PullZoneResponse,
+};
+pub use notify_target::{
+    CreateZoneNotifyTargetRequest, GetZoneNotifyTargetResponse, ZoneNotifyTargetListResponse,
+    ZoneNotifyTargetResponse,
+};
 pub use pagination::{PaginatedResponse, Pagination};
 pub(crate) use record::display_record_value_request;
 pub use record::{
-    BulkRecordsResponse, CreateBulkRecordsRequest, CreateRecordRequest, GetRecordResponse,
-    GetRecordsFilter, RecordItem, RecordResponse, RecordValueRequest, UpdateRecordPatch,
+    ApplyChangesetRequest, BulkRecordsResponse, ChangesetOperation, ChangesetResponse,
+    CreateBulkRecordsRequest, CreateRecordRequest, GetRecordResponse, GetRecordsFilter, RecordItem,
+    RecordResponse, RecordValueRequest, UpdateRecordPatch,
 };
+pub use rrset::{ReplaceRrsetRequest, RrsetResponse};
 pub use snapshot::{
-    RecordDiff, RecordDiffEntry, RecordDiffSummary, RecordDiffValue, RollbackSummary,
-    RollbackZoneRequest, RollbackZoneResponse, SnapshotDetailResponse, SnapshotDiffResponse,
-    SnapshotRecordResponse, ZoneSnapshotResponse,
+    PruneHistoryResponse, RecordDiff, RecordDiffEntry, RecordDiffSummary, RecordDiffValue,
+    RollbackSummary, RollbackZoneRequest, RollbackZoneResponse, SnapshotDetailResponse,
+    SnapshotDiffResponse, SnapshotRecordResponse, ZonePruneResult, ZoneSnapshotResponse,
+};
+pub use template::{
+    ApplyZoneTemplateRequest, CreateZoneTemplateRequest, GetZoneTemplateResponse,
+    TemplateZoneSyncResponse, UpdateZoneTemplateRequest, ZoneTemplateListResponse,
+    ZoneTemplateResponse, ZoneTemplateSyncResponse,
+};
+pub use token::{
+    CreateTokenRequest, GetTokenResponse, RotateTokenRequest, TokenListResponse, TokenResponse,
 };
-pub use token::GetTokenResponse;
 pub use token_policy::{
     CreateZoneTokenPolicyRequest, GetZoneTokenPolicyResponse, ZoneTokenPolicyListResponse,
     ZoneTokenPolicyResponse,
 };
+pub use transfer_acl::{
+    CreateZoneTransferAclRequest, GetZoneTransferAclResponse, ZoneTransferAclListResponse,
+    ZoneTransferAclResponse,
+};
 pub use tsig::{
     CreateTsigKeyRequest, CreateZoneTsigPolicyRequest, GetTsigKeyResponse,
     GetZoneTsigPolicyResponse, TsigKeyListResponse, TsigKeyResponse, ZoneTsigPolicyListResponse,
     ZoneTsigPolicyResponse,
 };
+pub use webhook::{
+    CreateWebhookRequest, GetWebhookDeliveryResponse, GetWebhookResponse,
+    WebhookDeliveryListResponse, WebhookListResponse, WebhookResponse,
+};
 pub use zone::{
     CreateZoneRequest, ExportZoneFileResponse, GetZoneResponse, GetZonesFilter, NotifyZoneRequest,
-    SecondaryStatusResponse, UpdateZonePatch, ZoneDetailResponse, ZoneResponse, ZoneStatusResponse,
+    SecondariesStatusResponse, SecondaryStatusResponse, SecondarySyncResponse, UpdateZonePatch,
+    ZoneDetailResponse, ZoneLintFinding, ZoneLintResponse, ZoneResponse, ZoneStatusResponse,
 };
@@ -0,0 +1,60 @@
+//! Zone NOTIFY target payloads.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::zone_notify_target::ZoneNotifyTarget;
+
+/// Request body for adding a NOTIFY target to a zone.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateZoneNotifyTargetRequest {
+    /// `notify` (replaces the global secondary list for the zone) or
+    /// `also-notify` (notified on top of it). Defaults to `notify`.
+    #[schema(example = "notify")]
+    pub kind: Option<String>,
+    /// `host[:port]` or an IP address, port 53 by default.
+    #[schema(example = "192.0.2.30:53")]
+    pub address: String,
+    /// TSIG key NOTIFYs to this target are signed with.
+    #[schema(example = "xfr-key")]
+    pub tsig_key: Option<String>,
+}
+
+/// API representation of a zone NOTIFY target.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct GetZoneNotifyTargetResponse {
+    #[schema(example = 1)]
+    pub id: i32,
+    #[schema(example = "notify")]
+    pub kind: String,
+    #[schema(example = "192.0.2.30:53")]
+    pub address: String,
+    #[schema(example = "xfr-key")]
+    pub tsig_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GetZoneNotifyTargetResponse {
+    pub fn from_entry(entry: &ZoneNotifyTarget) -> Self {
+        GetZoneNotifyTargetResponse {
+            id: entry.id,
+            kind: entry.kind.to_string(),
+            address: entry.address.clone(),
+            tsig_key: entry.tsig_key_name.clone(),
+            created_at: entry.created_at,
+        }
+    }
+}
+
+/// A single zone NOTIFY target wrapped in a response envelope.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ZoneNotifyTargetResponse {
+    pub notify_target: GetZoneNotifyTargetResponse,
+}
+
+/// List of a zone's NOTIFY targets.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ZoneNotifyTargetListResponse {
+    pub notify_targets: Vec<GetZoneNotifyTargetResponse>,
+}
@@ -106,20 +106,37 @@ impl RecordValueRequest {
             }
         }
     }
+
+    /// The priority to store alongside the value: a value given as RFC 3597
+    /// generic rdata carries its own, which a priority given with it must match.
+    pub(crate) fn resolve_priority(
+        &self,
+        record_type: &RecordType,
+        priority: Option<i32>,
+    ) -> Result<Option<i32>, String> {
+        match self {
+            RecordValueRequest::String(value) if record_type.takes_priority() => Ok(record_type
+                .decode_generic(value, priority)?
+                .map_or(priority, |(_, priority)| priority)),
+            _ => Ok(priority),
+        }
+    }
 }
 
 /// Request body for creating a record in a named zone.
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct CreateRecordRequest {
+    /// Owner name relative to the zone, or `@` for the apex. NS records at the apex name the zone's own servers; NS records below it delegate that name to a child zone.
     #[schema(example = "sub")]
     pub name: String,
+    /// One of A, AAAA, CAA, CNAME, HTTPS, MX, NS, PTR, SRV, SSHFP, SVCB, TLSA, or TXT; or `TYPEnnn` for any other type, with the value in RFC 3597 form (`\# <length> <hex>`). The listed types except TXT also take their value in that form, stored as if written out.
     #[schema(example = "A")]
     pub record_type: String,
     pub value: RecordValueRequest,
     /// Optional; an omitted TTL is fixed to the zone's TTL at write time. Every record of an RRset (same name and type) must share one TTL.
     #[schema(example = 3600)]
     pub ttl: Option<i32>,
-    /// MX and SRV priority, set here rather than inline in the value; other record types reject it.
+    /// MX, SRV, SVCB and HTTPS priority, set here rather than inline in the value; other record types reject it. A value in RFC 3597 form carries its own, which this must match if set.
     #[schema(example = 10)]
     pub priority: Option<i32>,
     #[schema(example = "example.com")]
@@ -129,17 +146,19 @@ pub struct CreateRecordRequest {
 /// A record's data fields, used both as a bulk-insertion entry and as the
 /// record update request body. The zone is taken from the request path, so
 /// unlike [`CreateRecordRequest`] it carries no `zone_name`.
-#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[derive(Clone, Serialize, Deserialize, Debug, ToSchema)]
 pub struct RecordItem {
+    /// Owner name relative to the zone, or `@` for the apex. NS records at the apex name the zone's own servers; NS records below it delegate that name to a child zone.
     #[schema(example = "sub")]
     pub name: String,
+    /// One of A, AAAA, CAA, CNAME, HTTPS, MX, NS, PTR, SRV, SSHFP, SVCB, TLSA, or TXT; or `TYPEnnn` for any other type, with the value in RFC 3597 form (`\# <length> <hex>`). The listed types except TXT also take their value in that form, stored as if written out.
     #[schema(example = "A")]
     pub record_type: String,
     pub value: RecordValueRequest,
     /// Optional; an omitted TTL is fixed to the zone's TTL at write time. Every record of an RRset (same name and type) must share one TTL.
     #[schema(example = 3600)]
     pub ttl: Option<i32>,
-    /// MX and SRV priority, set here rather than inline in the value; other record types reject it.
+    /// MX, SRV, SVCB and HTTPS priority, set here rather than inline in the value; other record types reject it. A value in RFC 3597 form carries its own, which this must match if set.
     #[schema(example = 10)]
     pub priority: Option<i32>,
 }
@@ -169,6 +188,48 @@ pub struct BulkRecordsResponse {
     pub diff: RecordDiff,
 }
 
+/// One step of a changeset. `update` replaces every field of the record with
+/// `id`, like a record PUT; `update` and `delete` address records that exist
+/// before the changeset runs.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum ChangesetOperation {
+    Add { record: RecordItem },
+    Update { id: i32, record: RecordItem },
+    Delete { id: i32 },
+}
+
+/// Request body for applying an ordered list of record operations to a zone
+/// as one atomic change.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ApplyChangesetRequest {
+    pub operations: Vec<ChangesetOperation>,
+    /// When true, validate the whole changeset without applying any change.
+    #[serde(default, alias = "dryRun")]
+    pub dry_run: bool,
+}
+
+/// Response for a changeset: per-kind counts of the net change, the records
+/// it added or updated, and the zone serial afterwards. On a dry run nothing is
+/// written, added records carry placeholder IDs and `diff` previews the change.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ChangesetResponse {
+    #[schema(example = true)]
+    pub applied: bool,
+    #[schema(example = false)]
+    pub dry_run: bool,
+    #[schema(example = 2)]
+    pub added: usize,
+    #[schema(example = 1)]
+    pub updated: usize,
+    #[schema(example = 1)]
+    pub deleted: usize,
+    #[schema(example = 2024010102)]
+    pub serial: u32,
+    pub records: Vec<GetRecordResponse>,
+    pub diff: RecordDiff,
+}
+
 /// Query filters and pagination for listing records.
 #[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
 pub struct GetRecordsFilter {
@@ -0,0 +1,33 @@
+//! RRset payloads: every record of one owner name and type, under one TTL.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// An RRset as stored. Values are in presentation form, sorted: priorities
+/// sit inline for MX, SRV, SVCB and HTTPS, and TXT values are quoted.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct RrsetResponse {
+    #[schema(example = "www.example.com.")]
+    pub name: String,
+    #[schema(example = "A")]
+    pub record_type: String,
+    #[schema(example = 300)]
+    pub ttl: i32,
+    #[schema(example = json!(["192.0.2.10", "192.0.2.11"]))]
+    pub values: Vec<String>,
+    /// The zone serial this RRset was read at; it doubles as the ETag.
+    #[schema(example = 2024010101)]
+    pub serial: u32,
+}
+
+/// Request body replacing an RRset's values in one change.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ReplaceRrsetRequest {
+    /// Optional; an omitted TTL is fixed to the zone's TTL at write time.
+    #[schema(example = 300)]
+    pub ttl: Option<i32>,
+    /// The complete set of values, in presentation form (`10 mail.example.com.`
+    /// for MX, quoted strings for TXT). Values missing here are deleted.
+    #[schema(example = json!(["192.0.2.10", "192.0.2.11"]))]
+    pub values: Vec<String>,
+}
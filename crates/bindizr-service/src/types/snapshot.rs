@@ -1,6 +1,6 @@
 //! Zone snapshot, diff, and rollback payloads.
 
-use bindizr_core::dns::record::SoaMailbox;
+use bindizr_core::dns::{record::SoaMailbox, serial};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -15,7 +15,7 @@ use crate::{
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct ZoneSnapshotResponse {
     #[schema(example = 7)]
-    pub serial: i32,
+    pub serial: u32,
     #[schema(example = "ns1.example.com")]
     pub primary_ns: String,
     #[schema(example = "admin@example.com")]
@@ -30,6 +30,22 @@ pub struct ZoneSnapshotResponse {
     pub expire: i32,
     #[schema(example = 3600)]
     pub minimum_ttl: i32,
+    /// Who made the change that produced this serial; `null` for serials
+    /// recorded before attribution existed.
+    #[schema(example = "token:ci-deploy")]
+    pub actor: Option<String>,
+    /// TSIG key that signed the change (nsupdate only).
+    pub tsig_key: Option<String>,
+    #[schema(example = "192.0.2.10")]
+    pub source_address: Option<String>,
+    /// Front end the change arrived through: `http`, `external-dns`,
+    /// `socket`, `nsupdate` or `internal`.
+    #[schema(example = "http")]
+    pub transport: Option<String>,
+    /// Id of the request that made the change: its HTTP `X-Request-Id`, or
+    /// one generated per CLI command.
+    #[schema(example = "5f0c2a9e1b7d4c36")]
+    pub request_id: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -41,7 +57,7 @@ impl ZoneSnapshotResponse {
                 ServiceError::internal(format!("Failed to decode snapshot admin email: {}", e))
             })?;
         Ok(ZoneSnapshotResponse {
-            serial: snapshot.serial,
+            serial: serial::to_wire(snapshot.serial),
             primary_ns: snapshot.primary_ns.clone(),
             admin_email,
             ttl: snapshot.ttl,
@@ -49,6 +65,11 @@ impl ZoneSnapshotResponse {
             retry: snapshot.retry,
             expire: snapshot.expire,
             minimum_ttl: snapshot.minimum_ttl,
+            actor: snapshot.attribution.actor.clone(),
+            tsig_key: snapshot.attribution.tsig_key_name.clone(),
+            source_address: snapshot.attribution.source_address.clone(),
+            transport: snapshot.attribution.transport.clone(),
+            request_id: snapshot.attribution.request_id.clone(),
             created_at: snapshot.created_at,
         })
     }
@@ -138,9 +159,9 @@ pub struct RecordDiff {
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct SnapshotDiffResponse {
     #[schema(example = 41)]
-    pub from_serial: i32,
+    pub from_serial: u32,
     #[schema(example = 42)]
-    pub to_serial: i32,
+    pub to_serial: u32,
     pub diff: RecordDiff,
 }
 
@@ -148,7 +169,7 @@ pub struct SnapshotDiffResponse {
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct RollbackZoneRequest {
     #[schema(example = 7)]
-    pub serial: i32,
+    pub serial: u32,
     /// When true, compute and report the rollback without applying any change.
     #[serde(default, alias = "dryRun")]
     pub dry_run: bool,
@@ -177,8 +198,31 @@ pub struct RollbackZoneResponse {
     #[schema(example = false)]
     pub dry_run: bool,
     #[schema(example = 7)]
-    pub target_serial: i32,
+    pub target_serial: u32,
     #[schema(example = 13)]
-    pub new_serial: i32,
+    pub new_serial: u32,
     pub summary: RollbackSummary,
 }
+
+/// What pruning removed (or, on a dry run, would remove) from one zone.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ZonePruneResult {
+    #[schema(example = "example.com")]
+    pub zone_name: String,
+    /// Oldest serial still diffable and restorable after pruning; `null` when
+    /// the limits keep the zone's whole history.
+    #[schema(example = 2024061501)]
+    pub oldest_retained_serial: Option<u32>,
+    #[schema(example = 12)]
+    pub snapshots_removed: u64,
+    #[schema(example = 40)]
+    pub changes_removed: u64,
+}
+
+/// Result of pruning zone history, one entry per zone examined.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct PruneHistoryResponse {
+    #[schema(example = false)]
+    pub dry_run: bool,
+    pub zones: Vec<ZonePruneResult>,
+}
@@ -0,0 +1,103 @@
+//! Zone template payloads.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{RecordDiff, RecordItem};
+
+/// Request body for creating a zone template.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateZoneTemplateRequest {
+    #[schema(example = "customer-baseline")]
+    pub name: String,
+    #[schema(example = "NS, MX, SPF/DMARC and CAA for customer zones")]
+    pub description: Option<String>,
+    /// Records every zone created from the template starts with. Names and
+    /// values may hold `{{variable}}` placeholders; `{{zone}}` is the zone's
+    /// name and the rest are supplied when the zone is created.
+    pub records: Vec<RecordItem>,
+}
+
+/// Request body replacing a template's records. Linked zones keep their
+/// records until the change is applied, here with `apply` or later through
+/// the apply endpoint.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct UpdateZoneTemplateRequest {
+    pub description: Option<String>,
+    pub records: Vec<RecordItem>,
+    /// When true, carry the change over to every linked zone as well.
+    #[serde(default)]
+    pub apply: bool,
+    /// When true, only preview each linked zone's diff; the template is not
+    /// saved.
+    #[serde(default, alias = "dryRun")]
+    pub dry_run: bool,
+}
+
+/// Request body for carrying a template's records over to its linked zones.
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema)]
+pub struct ApplyZoneTemplateRequest {
+    /// When true, only preview each linked zone's diff.
+    #[serde(default, alias = "dryRun")]
+    pub dry_run: bool,
+}
+
+/// API representation of a zone template.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct GetZoneTemplateResponse {
+    #[schema(example = 1)]
+    pub id: i32,
+    #[schema(example = "customer-baseline")]
+    pub name: String,
+    pub description: Option<String>,
+    /// Variables a zone must supply, besides the built-in `zone`.
+    #[schema(example = json!(["mail_host"]))]
+    pub variables: Vec<String>,
+    pub records: Vec<RecordItem>,
+    /// Zones created from the template.
+    #[schema(example = json!(["example.com"]))]
+    pub zones: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A single zone template wrapped in a response envelope.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ZoneTemplateResponse {
+    pub template: GetZoneTemplateResponse,
+}
+
+/// List of zone templates.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ZoneTemplateListResponse {
+    pub templates: Vec<GetZoneTemplateResponse>,
+}
+
+/// How a template change lands on one linked zone.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct TemplateZoneSyncResponse {
+    #[schema(example = "example.com")]
+    pub zone: String,
+    /// `in_sync` (nothing to change), `pending` (previewed, not applied),
+    /// `applied` or `failed`.
+    #[schema(example = "pending")]
+    pub status: String,
+    /// The zone's serial after the change was applied.
+    #[schema(example = 2024010102)]
+    pub serial: Option<u32>,
+    /// The zone's records before and after the change.
+    pub diff: RecordDiff,
+    /// Why the change could not be applied, for `failed`.
+    pub error: Option<String>,
+}
+
+/// Response for updating or applying a template: the template and the diff
+/// for every linked zone.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ZoneTemplateSyncResponse {
+    pub template: GetZoneTemplateResponse,
+    #[schema(example = false)]
+    pub dry_run: bool,
+    pub zones: Vec<TemplateZoneSyncResponse>,
+}
@@ -2,23 +2,65 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::model::api_token::ApiToken;
 
+/// Request body for creating an API token.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateTokenRequest {
+    /// Unique token name, used to reference the token elsewhere.
+    #[schema(example = "external-dns")]
+    pub name: String,
+    #[schema(example = "ExternalDNS webhook")]
+    pub description: Option<String>,
+    /// Days until the token expires; omit for a token that never expires.
+    #[schema(example = 90)]
+    pub expires_in_days: Option<i64>,
+    /// Make the token global: it may manage every zone, the zone plane and
+    /// other tokens without policies. Shorthand for `role: admin`.
+    #[serde(default)]
+    #[schema(example = false)]
+    pub global: bool,
+    /// `scoped` (the default; rights come from zone token policies),
+    /// `viewer`, `record-editor`, `zone-admin` or `admin`. Fixed at creation.
+    #[schema(example = "viewer")]
+    pub role: Option<String>,
+}
+
+/// Request body for rotating an API token's secret.
+#[derive(Serialize, Deserialize, Debug, Default, ToSchema)]
+pub struct RotateTokenRequest {
+    /// Minutes the old secret keeps working alongside the new one, up to a
+    /// week. Defaults to 60; 0 revokes the old secret at once.
+    #[schema(example = 60)]
+    pub overlap_minutes: Option<i64>,
+}
+
 /// API representation of an API token. `token` carries the raw secret and is
-/// only present in the create response — the one time it is shown.
-#[derive(Serialize, Deserialize, Debug)]
+/// only present in the create and rotate responses — the one time it is shown.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct GetTokenResponse {
+    #[schema(example = 1)]
     pub id: i32,
+    #[schema(example = "external-dns")]
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
     pub description: Option<String>,
-    /// Whether the token may manage every zone and the zone plane.
+    /// Whether the token may manage every zone and the zone plane (the
+    /// `admin` role).
     pub global: bool,
+    /// `scoped`, `viewer`, `record-editor`, `zone-admin` or `admin`.
+    #[schema(example = "viewer")]
+    pub role: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
     pub last_used_at: Option<DateTime<Utc>>,
+    /// Until when the secret replaced by the last rotation is still accepted;
+    /// absent once that overlap has ended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_token_expires_at: Option<DateTime<Utc>>,
 }
 
 impl GetTokenResponse {
@@ -29,10 +71,26 @@ impl GetTokenResponse {
             name: token.name.clone(),
             token: Some(token.token.clone()).filter(|secret| !secret.is_empty()),
             description: token.description.clone(),
-            global: token.is_global,
+            global: token.is_global(),
+            role: token.role.to_string(),
             created_at: token.created_at,
             expires_at: token.expires_at,
             last_used_at: token.last_used_at,
+            previous_token_expires_at: token
+                .previous_token_expires_at
+                .filter(|expires_at| *expires_at > Utc::now()),
         }
     }
 }
+
+/// A single API token wrapped in a response envelope.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct TokenResponse {
+    pub token: GetTokenResponse,
+}
+
+/// List of API tokens, secrets omitted.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct TokenListResponse {
+    pub tokens: Vec<GetTokenResponse>,
+}
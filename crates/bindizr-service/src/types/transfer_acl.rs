@@ -0,0 +1,60 @@
+//! Zone transfer ACL payloads.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::zone_transfer_acl::ZoneTransferAcl;
+
+/// Request body for adding an entry to a zone's transfer ACL.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateZoneTransferAclRequest {
+    /// `allow` or `deny`. Defaults to `allow`.
+    #[schema(example = "allow")]
+    pub action: Option<String>,
+    /// An IP address or a CIDR prefix.
+    #[schema(example = "198.51.100.0/24")]
+    pub address: String,
+    /// TSIG key a matching client must sign its transfer requests with.
+    /// Allow entries only.
+    #[schema(example = "xfr-key")]
+    pub tsig_key: Option<String>,
+}
+
+/// API representation of a zone transfer ACL entry.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct GetZoneTransferAclResponse {
+    #[schema(example = 1)]
+    pub id: i32,
+    #[schema(example = "allow")]
+    pub action: String,
+    #[schema(example = "198.51.100.0/24")]
+    pub address: String,
+    #[schema(example = "xfr-key")]
+    pub tsig_key: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GetZoneTransferAclResponse {
+    pub fn from_entry(entry: &ZoneTransferAcl) -> Self {
+        GetZoneTransferAclResponse {
+            id: entry.id,
+            action: entry.action.to_string(),
+            address: entry.address.clone(),
+            tsig_key: entry.tsig_key_name.clone(),
+            created_at: entry.created_at,
+        }
+    }
+}
+
+/// A single zone transfer ACL entry wrapped in a response envelope.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ZoneTransferAclResponse {
+    pub transfer_acl: GetZoneTransferAclResponse,
+}
+
+/// List of a zone's transfer ACL entries.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct ZoneTransferAclListResponse {
+    pub transfer_acls: Vec<GetZoneTransferAclResponse>,
+}
@@ -0,0 +1,130 @@
+//! Webhook payloads.
+
+use bindizr_core::dns::serial;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{model::webhook::WebhookDelivery, webhook::WebhookSubscription};
+
+/// Request body for creating a webhook.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct CreateWebhookRequest {
+    /// Unique webhook name, used to reference the webhook elsewhere.
+    #[schema(example = "audit-sink")]
+    pub name: String,
+    /// `http` or `https` URL the events are POSTed to.
+    #[schema(example = "https://hooks.example.com/bindizr")]
+    pub url: String,
+    /// Only send events for this zone; omit to hear about every zone.
+    #[schema(example = "example.com")]
+    pub zone: Option<String>,
+    /// HMAC-SHA256 signing key; omit to have one generated.
+    pub secret: Option<String>,
+    #[schema(example = "Ships zone changes to the audit pipeline")]
+    pub description: Option<String>,
+}
+
+/// API representation of a webhook. `secret` is only present in the create
+/// response — the one time it is shown.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct GetWebhookResponse {
+    #[schema(example = 1)]
+    pub id: i32,
+    #[schema(example = "audit-sink")]
+    pub name: String,
+    #[schema(example = "https://hooks.example.com/bindizr")]
+    pub url: String,
+    /// The zone the webhook is scoped to; absent for a global webhook.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "example.com")]
+    pub zone: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GetWebhookResponse {
+    /// An empty `secret` is treated as already-cleared and omitted.
+    pub fn from_subscription(subscription: &WebhookSubscription) -> Self {
+        let webhook = &subscription.webhook;
+        GetWebhookResponse {
+            id: webhook.id,
+            name: webhook.name.clone(),
+            url: webhook.url.clone(),
+            zone: subscription.zone_name.clone(),
+            secret: Some(webhook.secret.clone()).filter(|secret| !secret.is_empty()),
+            description: webhook.description.clone(),
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+/// A single webhook wrapped in a response envelope.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct WebhookResponse {
+    pub webhook: GetWebhookResponse,
+}
+
+/// List of webhooks, secrets omitted.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<GetWebhookResponse>,
+}
+
+/// One queued or finished delivery of an event to a webhook.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct GetWebhookDeliveryResponse {
+    /// Also sent as `X-Bindizr-Delivery`; the same on every retry.
+    #[schema(example = 42)]
+    pub id: i64,
+    /// `zone.created`, `zone.updated` or `zone.deleted`.
+    #[schema(example = "zone.updated")]
+    pub event: String,
+    #[schema(example = "example.com")]
+    pub zone: String,
+    /// The zone's serial before the change; absent for a new zone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 2026101801)]
+    pub previous_serial: Option<u32>,
+    /// The zone's serial after the change; absent for a deleted zone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 2026101802)]
+    pub serial: Option<u32>,
+    /// `pending`, `delivered` or `failed`.
+    #[schema(example = "delivered")]
+    pub status: String,
+    #[schema(example = 1)]
+    pub attempts: i32,
+    /// When a pending delivery is next attempted.
+    pub next_attempt_at: DateTime<Utc>,
+    /// Why the last attempt failed.
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl GetWebhookDeliveryResponse {
+    pub fn from_delivery(delivery: &WebhookDelivery) -> Self {
+        GetWebhookDeliveryResponse {
+            id: delivery.id,
+            event: delivery.event.clone(),
+            zone: delivery.zone_name.clone(),
+            previous_serial: delivery.from_serial.map(serial::to_wire),
+            serial: delivery.to_serial.map(serial::to_wire),
+            status: delivery.status.clone(),
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_error: delivery.last_error.clone(),
+            created_at: delivery.created_at,
+            delivered_at: delivery.delivered_at,
+        }
+    }
+}
+
+/// A webhook's deliveries, newest first.
+#[derive(Serialize, Debug, ToSchema)]
+pub struct WebhookDeliveryListResponse {
+    pub deliveries: Vec<GetWebhookDeliveryResponse>,
+}
@@ -1,5 +1,9 @@
 //! Zone request, patch, filter, and response payloads.
 
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use bindizr_core::dns::serial;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -20,7 +24,9 @@ pub struct GetZoneResponse {
     #[schema(example = 3600)]
     pub ttl: i32,
     #[schema(example = 42)]
-    pub serial: i32,
+    pub serial: u32,
+    #[schema(example = "increment")]
+    pub serial_policy: String,
     #[schema(example = 7200)]
     pub refresh: i32,
     #[schema(example = 3600)]
@@ -39,7 +45,8 @@ impl GetZoneResponse {
             primary_ns: zone.primary_ns.clone(),
             admin_email: zone.admin_email.clone(),
             ttl: zone.ttl,
-            serial: zone.serial,
+            serial: serial::to_wire(zone.serial),
+            serial_policy: zone.serial_policy.to_string(),
             refresh: zone.refresh,
             retry: zone.retry,
             expire: zone.expire,
@@ -59,9 +66,12 @@ pub struct CreateZoneRequest {
     pub admin_email: String,
     #[schema(example = 3600)]
     pub ttl: i32,
-    /// Starting serial, auto-generated if not provided. Must be 1-2137483647 so the counter keeps room to advance, and can only be set at creation.
+    /// Starting serial, generated by the serial policy if not provided. Any value but 0; can only be set at creation.
     #[schema(example = 42)]
-    pub serial: Option<i32>,
+    pub serial: Option<u32>,
+    /// How the serial advances: `increment` (default), `date` (YYYYMMDDnn) or `unixtime`. Omit on update to keep the current policy.
+    #[schema(example = "increment")]
+    pub serial_policy: Option<String>,
     #[schema(example = 7200)]
     pub refresh: Option<i32>,
     #[schema(example = 3600)]
@@ -70,6 +80,14 @@ pub struct CreateZoneRequest {
     pub expire: Option<i32>,
     #[schema(example = 3600)]
     pub minimum_ttl: Option<i32>,
+    /// Template whose records the zone starts with, kept linked so later template changes can be applied to it. Can only be set at creation.
+    #[serde(default)]
+    #[schema(example = "customer-baseline")]
+    pub template: Option<String>,
+    /// Values for the template's `{{variable}}` placeholders; `zone` is built in.
+    #[serde(default)]
+    #[schema(example = json!({"mail_host": "mx1.example.net."}))]
+    pub template_variables: BTreeMap<String, String>,
 }
 
 /// Query filters and pagination for listing zones.
@@ -90,7 +108,7 @@ pub struct GetZonesFilter {
     #[schema(example = 86400)]
     pub max_ttl: Option<i32>,
     #[schema(example = 42)]
-    pub serial: Option<i32>,
+    pub serial: Option<u32>,
     #[serde(alias = "q")]
     #[schema(example = "example")]
     pub search: Option<String>,
@@ -112,7 +130,8 @@ pub struct UpdateZonePatch {
     pub retry: Option<i32>,
     pub expire: Option<i32>,
     pub minimum_ttl: Option<i32>,
-    pub serial: Option<i32>,
+    pub serial: Option<u32>,
+    pub serial_policy: Option<String>,
 }
 
 /// Request body for triggering a NOTIFY, optionally scoped to one zone.
@@ -155,11 +174,24 @@ pub struct SecondaryStatusResponse {
     pub status: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schema(example = 42)]
-    pub visible_serial: Option<i64>,
+    pub visible_serial: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
 
+impl SecondaryStatusResponse {
+    /// The status of a secondary serving `visible` for a zone at `serial`, in
+    /// RFC 1982 order; a serial exactly half the serial space away reads as
+    /// `ahead`.
+    pub fn classify(visible: u32, serial: u32) -> &'static str {
+        match serial::compare(visible, serial) {
+            Some(Ordering::Equal) => "in_sync",
+            Some(Ordering::Less) => "lagging",
+            Some(Ordering::Greater) | None => "ahead",
+        }
+    }
+}
+
 /// A zone's serial and the sync state of every configured secondary, probed
 /// live via SOA queries.
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
@@ -167,13 +199,14 @@ pub struct ZoneStatusResponse {
     #[schema(example = "example.com")]
     pub zone: String,
     #[schema(example = 42)]
-    pub serial: i32,
+    pub serial: u32,
     pub secondaries: Vec<SecondaryStatusResponse>,
 }
 
 impl ZoneStatusResponse {
-    /// Classify each secondary's probed SOA serial against the zone's serial;
-    /// a probe error reads as `unreachable`.
+    /// Classify each secondary's probed SOA serial against the zone's serial
+    /// with [`SecondaryStatusResponse::classify`]; a probe error reads as
+    /// `unreachable`.
     pub fn from_probes(
         zone: &Zone,
         probes: impl IntoIterator<Item = (String, Result<u32, String>)>,
@@ -182,12 +215,8 @@ impl ZoneStatusResponse {
             .into_iter()
             .map(|(address, result)| match result {
                 Ok(visible) => {
-                    let visible = i64::from(visible);
-                    let status = match visible.cmp(&i64::from(zone.serial)) {
-                        std::cmp::Ordering::Equal => "in_sync",
-                        std::cmp::Ordering::Less => "lagging",
-                        std::cmp::Ordering::Greater => "ahead",
-                    };
+                    let status =
+                        SecondaryStatusResponse::classify(visible, serial::to_wire(zone.serial));
                     SecondaryStatusResponse {
                         address,
                         status: status.to_string(),
@@ -206,8 +235,112 @@ impl ZoneStatusResponse {
 
         ZoneStatusResponse {
             zone: zone.name.to_string(),
-            serial: zone.serial,
+            serial: serial::to_wire(zone.serial),
             secondaries,
         }
     }
 }
+
+/// The sync monitor's last probe of one configured secondary for one zone.
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct SecondarySyncResponse {
+    #[schema(example = "example.com")]
+    pub zone: String,
+    /// The zone's serial when the secondary was probed.
+    #[schema(example = 42)]
+    pub serial: u32,
+    /// The NOTIFY target probed: the zone's own or a `secondary_addrs` entry.
+    #[schema(example = "ns2.example.net:53")]
+    pub secondary: String,
+    /// The address that answered; on failure, the last one tried.
+    #[schema(example = "10.0.1.10:53")]
+    pub address: String,
+    /// `in_sync` | `lagging` | `ahead` | `unreachable`
+    #[schema(example = "lagging")]
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 40)]
+    pub visible_serial: Option<u32>,
+    /// Serials the secondary is behind the zone, negative when it is ahead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 2)]
+    pub lag: Option<i64>,
+    /// When the secondary was first seen behind; cleared once it catches up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lagging_since: Option<DateTime<Utc>>,
+    /// When the monitor last re-NOTIFYed the secondary for this zone.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_notified_at: Option<DateTime<Utc>>,
+    /// Failed probes in a row; 0 once one succeeds.
+    #[schema(example = 0)]
+    pub consecutive_failures: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub probed_at: DateTime<Utc>,
+}
+
+/// Every zone's sync state on every configured secondary, as of the sync
+/// monitor's last round.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct SecondariesStatusResponse {
+    /// Seconds between monitor rounds; 0 when the monitor is off.
+    #[schema(example = 60)]
+    pub interval_secs: u64,
+    /// When the last round finished; absent before the first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_round_at: Option<DateTime<Utc>>,
+    pub secondaries: Vec<SecondarySyncResponse>,
+}
+
+/// One problem `zone lint` found with a record, in the context of the rest
+/// of the zone.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ZoneLintFinding {
+    /// `error` (breaks resolution of the names involved) | `warning`
+    #[schema(example = "error")]
+    pub severity: String,
+    /// `missing_target` | `target_is_cname` | `missing_glue` | `orphaned_glue` |
+    /// `occluded_record` | `apex_ns_ttl_mismatch`
+    #[schema(example = "missing_glue")]
+    pub code: String,
+    #[schema(example = "sub.example.com.")]
+    pub name: String,
+    #[schema(example = "NS")]
+    pub record_type: String,
+    /// The record's rdata in zone-file form.
+    #[schema(example = "ns1.sub.example.com.")]
+    pub value: String,
+    #[schema(example = "delegation to ns1.sub.example.com. has no A or AAAA glue in the zone")]
+    pub message: String,
+}
+
+/// Whole-zone consistency findings, errors first.
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ZoneLintResponse {
+    #[schema(example = "example.com")]
+    pub zone: String,
+    #[schema(example = 42)]
+    pub serial: u32,
+    #[schema(example = 1)]
+    pub errors: usize,
+    #[schema(example = 0)]
+    pub warnings: usize,
+    pub findings: Vec<ZoneLintFinding>,
+}
+
+impl ZoneLintResponse {
+    /// Count `findings` by severity and order them errors first, keeping the
+    /// order they were found in within each severity.
+    pub fn from_findings(zone: &Zone, mut findings: Vec<ZoneLintFinding>) -> Self {
+        findings.sort_by_key(|finding| finding.severity != "error");
+        let errors = findings.iter().filter(|f| f.severity == "error").count();
+
+        ZoneLintResponse {
+            zone: zone.name.to_string(),
+            serial: serial::to_wire(zone.serial),
+            errors,
+            warnings: findings.len() - errors,
+            findings,
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use bindizr_core::{
+    config::{self, WebhookConfig},
+    dns::serial,
+};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use serde_json::json;
+use sha2::Sha256;
+use tokio::{
+    sync::{Notify, Semaphore},
+    task::JoinSet,
+    time::{Instant, sleep},
+};
+
+use super::{STATUS_DELIVERED, STATUS_FAILED};
+use crate::{
+    error::ServiceError,
+    log_debug, log_error, log_info, log_warn,
+    metrics::metrics,
+    model::webhook::{Webhook, WebhookDelivery},
+    repository::RepositoryService,
+    types::RecordDiff,
+};
+
+/// Deliveries attempted per queue read.
+const BATCH_SIZE: u32 = 50;
+
+/// Webhooks delivered to at once. Each webhook's own deliveries go out one
+/// at a time, in queue order.
+const MAX_CONCURRENT_WEBHOOKS: usize = 8;
+
+/// How often finished deliveries past `retention_days` are pruned.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+static WAKE: OnceLock<Notify> = OnceLock::new();
+static PENDING: AtomicBool = AtomicBool::new(false);
+static STARTED: OnceLock<()> = OnceLock::new();
+
+fn wake() -> &'static Notify {
+    WAKE.get_or_init(Notify::new)
+}
+
+/// Note that a transaction queued deliveries; they become visible when it
+/// commits.
+pub(super) fn mark_pending() {
+    PENDING.store(true, Ordering::Release);
+}
+
+/// Called after every commit: wake the worker if anything was queued, so a
+/// change goes out now rather than on the next poll. A rolled-back
+/// transaction leaves the flag set, which only costs an empty queue read.
+pub(crate) fn wake_after_commit() {
+    if PENDING.swap(false, Ordering::AcqRel) {
+        wake().notify_one();
+    }
+}
+
+/// Spawn the background worker that drains the delivery queue. First call
+/// wins; later calls are no-ops. Settings are read every round, so a reload
+/// applies from the next one.
+pub fn init_delivery_worker() {
+    if STARTED.set(()).is_err() {
+        return;
+    }
+
+    tokio::spawn(async {
+        let mut last_prune: Option<Instant> = None;
+        loop {
+            let config = config::get_bindizr_config().webhook.clone();
+
+            if let Err(e) = deliver_due(&config).await {
+                log_error!("Webhook delivery round failed: {}", e);
+            }
+
+            if last_prune.is_none_or(|at| at.elapsed() >= PRUNE_INTERVAL) {
+                last_prune = Some(Instant::now());
+                prune_finished(&config).await;
+            }
+
+            tokio::select! {
+                _ = wake().notified() => {}
+                _ = sleep(Duration::from_secs(config.poll_interval_secs)) => {}
+            }
+        }
+    });
+}
+
+/// Attempt every due delivery, a batch at a time, each webhook's
+/// independently of the others so a slow endpoint only holds up its own. A
+/// failed attempt is rescheduled into the future, so the loop ends once a
+/// batch comes back short.
+async fn deliver_due(config: &WebhookConfig) -> Result<(), ServiceError> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .user_agent(concat!("bindizr/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| ServiceError::internal(format!("failed to build HTTP client: {}", e)))?;
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_WEBHOOKS));
+
+    loop {
+        let due = RepositoryService::list_due_webhook_deliveries(Utc::now(), BATCH_SIZE).await?;
+        if due.is_empty() {
+            return Ok(());
+        }
+        let batch_len = due.len();
+
+        let mut webhooks: HashMap<i32, Webhook> = RepositoryService::list_webhooks()
+            .await?
+            .into_iter()
+            .map(|webhook| (webhook.id, webhook))
+            .collect();
+        let mut by_webhook: HashMap<i32, Vec<WebhookDelivery>> = HashMap::new();
+        for delivery in due {
+            by_webhook
+                .entry(delivery.webhook_id)
+                .or_default()
+                .push(delivery);
+        }
+
+        let mut rounds = JoinSet::new();
+        for (webhook_id, deliveries) in by_webhook {
+            // Deleting a webhook cascades to its deliveries; one missing here
+            // was deleted mid-round.
+            let Some(webhook) = webhooks.remove(&webhook_id) else {
+                continue;
+            };
+            let (client, config, permits) = (client.clone(), config.clone(), permits.clone());
+            rounds.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                deliver_to(&client, &config, &webhook, deliveries).await
+            });
+        }
+        let mut result = Ok(());
+        while let Some(joined) = rounds.join_next().await {
+            let round = joined.unwrap_or_else(|e| {
+                Err(ServiceError::internal(format!(
+                    "webhook delivery task failed: {}",
+                    e
+                )))
+            });
+            if let Err(e) = round {
+                result = Err(e);
+            }
+        }
+        result?;
+
+        if batch_len < BATCH_SIZE as usize {
+            return Ok(());
+        }
+    }
+}
+
+/// Attempt one webhook's due deliveries in order. Once its endpoint cannot
+/// be reached at all, the rest are deferred along with the failed one rather
+/// than each waiting out the timeout in turn; they are not counted as
+/// attempts.
+async fn deliver_to(
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+    webhook: &Webhook,
+    deliveries: Vec<WebhookDelivery>,
+) -> Result<(), ServiceError> {
+    let mut deliveries = deliveries.into_iter();
+    while let Some(mut delivery) = deliveries.next() {
+        let reached = attempt(client, config, webhook, &mut delivery).await;
+        RepositoryService::update_webhook_delivery(&delivery).await?;
+        if reached {
+            continue;
+        }
+
+        let retry_at = if delivery.status == STATUS_FAILED {
+            next_attempt_at(config, 1, Utc::now())
+        } else {
+            delivery.next_attempt_at
+        };
+        for mut deferred in deliveries.by_ref() {
+            deferred.next_attempt_at = retry_at;
+            RepositoryService::update_webhook_delivery(&deferred).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Send one delivery and record the outcome on it. `false` when the endpoint
+/// could not be reached at all, as opposed to answering with an error.
+async fn attempt(
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+    webhook: &Webhook,
+    delivery: &mut WebhookDelivery,
+) -> bool {
+    // Rows queued without a payload still go out, just without a diff.
+    let payload = delivery.payload.get_or_insert_with(|| {
+        event_payload(
+            &delivery.event,
+            &delivery.zone_name,
+            delivery.from_serial,
+            delivery.to_serial,
+            None,
+            delivery.created_at,
+        )
+    });
+    let body = addressed_body(webhook, delivery.id, payload);
+
+    let timestamp = Utc::now().timestamp();
+    let result = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-Bindizr-Event", &delivery.event)
+        .header("X-Bindizr-Delivery", delivery.id.to_string())
+        .header("X-Bindizr-Timestamp", timestamp.to_string())
+        .header(
+            "X-Bindizr-Signature",
+            sign(&webhook.secret, timestamp, &body),
+        )
+        .body(body)
+        .send()
+        .await;
+    let reached = result.is_ok();
+    let result = result.map_err(|e| e.to_string()).and_then(|response| {
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {}", response.status()))
+        }
+    });
+
+    delivery.attempts += 1;
+    match result {
+        Ok(()) => {
+            delivery.status = STATUS_DELIVERED.to_string();
+            delivery.delivered_at = Some(Utc::now());
+            delivery.last_error = None;
+            metrics()
+                .webhook_deliveries_total
+                .with_label_values(&["delivered"])
+                .inc();
+            log_debug!(
+                "Webhook '{}' delivery {} ({}) delivered",
+                webhook.name,
+                delivery.id,
+                delivery.event
+            );
+        }
+        Err(error) => {
+            if delivery.attempts >= config.max_attempts as i32 {
+                delivery.status = STATUS_FAILED.to_string();
+                metrics()
+                    .webhook_deliveries_total
+                    .with_label_values(&["failed"])
+                    .inc();
+                log_warn!(
+                    "Webhook '{}' delivery {} failed after {} attempt(s): {}",
+                    webhook.name,
+                    delivery.id,
+                    delivery.attempts,
+                    error
+                );
+            } else {
+                delivery.next_attempt_at = next_attempt_at(config, delivery.attempts, Utc::now());
+                metrics()
+                    .webhook_deliveries_total
+                    .with_label_values(&["retry"])
+                    .inc();
+                log_debug!(
+                    "Webhook '{}' delivery {} attempt {} failed: {}",
+                    webhook.name,
+                    delivery.id,
+                    delivery.attempts,
+                    error
+                );
+            }
+            delivery.last_error = Some(error);
+        }
+    }
+    reached
+}
+
+/// The event's JSON body, stored on each delivery when the change is
+/// queued. An update carries the record diff between the two serials.
+pub(super) fn event_payload(
+    event: &str,
+    zone_name: &str,
+    from_serial: Option<i32>,
+    to_serial: Option<i32>,
+    diff: Option<RecordDiff>,
+    created_at: DateTime<Utc>,
+) -> String {
+    json!({
+        "event": event,
+        "zone": zone_name,
+        "serial": to_serial.map(serial::to_wire),
+        "previous_serial": from_serial.map(serial::to_wire),
+        "diff": diff,
+        "created_at": created_at,
+    })
+    .to_string()
+}
+
+/// The body sent for one delivery: the stored event payload plus the
+/// delivery id and webhook name. Derived the same way every time, so each
+/// retry sends (and signs) the same bytes.
+fn addressed_body(webhook: &Webhook, delivery_id: i64, payload: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(payload) {
+        Ok(serde_json::Value::Object(mut body)) => {
+            body.insert("delivery_id".to_string(), json!(delivery_id));
+            body.insert("webhook".to_string(), json!(webhook.name));
+            serde_json::Value::Object(body).to_string()
+        }
+        _ => payload.to_string(),
+    }
+}
+
+/// `sha256=<hex>` of HMAC-SHA256 over `"{timestamp}.{body}"`. Binding the
+/// timestamp lets receivers reject replays of an old delivery.
+pub(super) fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// When to retry after `attempts` failures: the initial backoff doubled per
+/// earlier failure, capped at the maximum.
+pub(super) fn next_attempt_at(
+    config: &WebhookConfig,
+    attempts: i32,
+    now: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let doublings = attempts.saturating_sub(1).clamp(0, 32) as u32;
+    let delay = config
+        .initial_backoff_secs
+        .saturating_mul(1u64 << doublings)
+        .min(config.max_backoff_secs);
+    now + chrono::Duration::seconds(i64::try_from(delay).unwrap_or(i64::MAX))
+}
+
+async fn prune_finished(config: &WebhookConfig) {
+    if config.retention_days == 0 {
+        return;
+    }
+    let before = Utc::now() - chrono::Duration::days(i64::from(config.retention_days));
+    match RepositoryService::delete_finished_webhook_deliveries_before(before).await {
+        Ok(0) => {}
+        Ok(removed) => log_info!("Pruned {} finished webhook delivery(ies).", removed),
+        Err(e) => log_error!("Webhook delivery pruning failed: {}", e),
+    }
+}
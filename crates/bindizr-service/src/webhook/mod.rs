@@ -0,0 +1,279 @@
+//! Outbound webhooks: HTTP callbacks fired when a zone is created, changes
+//! serial, or is deleted.
+//!
+//! A change queues one delivery row per interested webhook inside the
+//! transaction that made it, so the queue and the zone never disagree. The
+//! delivery worker in [`delivery`] drains the queue after commit, signs each
+//! payload with the webhook's secret, and retries failures with exponential
+//! backoff. Delivery is at-least-once; receivers dedupe on the delivery id.
+
+use std::collections::HashMap;
+
+use bindizr_core::dns::name::has_whitespace_or_control;
+use chrono::Utc;
+use rand::{RngExt, distr::Alphanumeric};
+
+use crate::{
+    RepositoryTx,
+    authorization::Caller,
+    error::ServiceError,
+    model::{
+        webhook::{Webhook, WebhookDelivery},
+        zone::Zone,
+    },
+    repository::RepositoryService,
+    token::normalize_token_name,
+    zone::ZoneService,
+};
+
+mod delivery;
+
+pub use delivery::init_delivery_worker;
+pub(crate) use delivery::wake_after_commit;
+
+const MAX_SECRET_LEN: usize = 255;
+const DEFAULT_DELIVERY_LIMIT: u32 = 50;
+const MAX_DELIVERY_LIMIT: u32 = 500;
+
+pub(crate) const STATUS_PENDING: &str = "pending";
+pub(crate) const STATUS_DELIVERED: &str = "delivered";
+pub(crate) const STATUS_FAILED: &str = "failed";
+
+/// What happened to a zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WebhookEvent {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl WebhookEvent {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEvent::Created => "zone.created",
+            WebhookEvent::Updated => "zone.updated",
+            WebhookEvent::Deleted => "zone.deleted",
+        }
+    }
+}
+
+/// Creates, lists, and deletes webhooks, and lists their deliveries.
+pub struct WebhookService;
+
+/// A webhook with the name of the zone it is scoped to, if any.
+#[derive(Debug, Clone)]
+pub struct WebhookSubscription {
+    pub webhook: Webhook,
+    pub zone_name: Option<String>,
+}
+
+impl WebhookService {
+    /// Create a webhook. With a `zone` it fires for that zone only, without
+    /// one for every zone. A secret is generated when none is given; the
+    /// returned webhook carries it, the one time it is shown.
+    pub async fn create(
+        caller: &Caller,
+        name: &str,
+        url: &str,
+        zone: Option<&str>,
+        secret: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<WebhookSubscription, ServiceError> {
+        caller.require_global("manage webhooks")?;
+
+        let name = normalize_webhook_name(name)?;
+        let url = validate_url(url)?;
+        let secret = match secret {
+            Some(secret) => validate_secret(secret)?,
+            None => generate_secret(),
+        };
+
+        let zone = match zone {
+            Some(zone_name) => Some(
+                ZoneService::find_by_name(zone_name)
+                    .await?
+                    .ok_or_else(|| ServiceError::zone_not_found(zone_name))?,
+            ),
+            None => None,
+        };
+
+        if RepositoryService::get_webhook_by_name(&name)
+            .await?
+            .is_some()
+        {
+            return Err(ServiceError::webhook_conflict(&name));
+        }
+
+        let webhook = RepositoryService::create_webhook(Webhook {
+            id: 0,
+            name,
+            url,
+            secret,
+            zone_id: zone.as_ref().map(|zone| zone.id),
+            description: description.map(str::to_string),
+            created_at: Utc::now(),
+        })
+        .await?;
+
+        Ok(WebhookSubscription {
+            webhook,
+            zone_name: zone.map(|zone| zone.name.to_string()),
+        })
+    }
+
+    /// List all webhooks with their secrets cleared.
+    pub async fn list(caller: &Caller) -> Result<Vec<WebhookSubscription>, ServiceError> {
+        caller.require_global("manage webhooks")?;
+
+        let zone_names: HashMap<i32, String> = RepositoryService::list_zones()
+            .await?
+            .into_iter()
+            .map(|zone| (zone.id, zone.name.to_string()))
+            .collect();
+
+        Ok(RepositoryService::list_webhooks()
+            .await?
+            .into_iter()
+            .map(|mut webhook| {
+                webhook.secret.clear();
+                let zone_name = webhook.zone_id.and_then(|id| zone_names.get(&id).cloned());
+                WebhookSubscription { webhook, zone_name }
+            })
+            .collect())
+    }
+
+    /// Delete the named webhook along with its queued deliveries.
+    pub async fn delete(caller: &Caller, name: &str) -> Result<(), ServiceError> {
+        caller.require_global("manage webhooks")?;
+
+        let webhook = find_webhook(name).await?;
+        RepositoryService::delete_webhook(webhook.id).await
+    }
+
+    /// The named webhook's most recent deliveries, newest first. `limit`
+    /// defaults to 50 and is capped at 500.
+    pub async fn deliveries(
+        caller: &Caller,
+        name: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<WebhookDelivery>, ServiceError> {
+        caller.require_global("manage webhooks")?;
+
+        let limit = match limit {
+            Some(0) => return Err(ServiceError::invalid_input("limit must be greater than 0")),
+            Some(limit) => limit.min(MAX_DELIVERY_LIMIT),
+            None => DEFAULT_DELIVERY_LIMIT,
+        };
+
+        let webhook = find_webhook(name).await?;
+        RepositoryService::list_webhook_deliveries(webhook.id, limit).await
+    }
+
+    /// Queue `event` for every webhook watching `zone`, in the transaction
+    /// making the change. The payload is built here too, so an update's diff
+    /// is read while the history it comes from is certain to exist. The
+    /// worker is woken once the transaction commits.
+    pub(crate) async fn enqueue_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone: &Zone,
+        event: WebhookEvent,
+        from_serial: Option<i32>,
+        to_serial: Option<i32>,
+    ) -> Result<(), ServiceError> {
+        if RepositoryService::count_watching_webhooks_tx(tx, zone.id).await? == 0 {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let diff = match (event, from_serial, to_serial) {
+            (WebhookEvent::Updated, Some(from), Some(to)) => {
+                Some(ZoneService::diff_to_current_serial_tx(tx, zone, from, to).await?)
+            }
+            _ => None,
+        };
+        let payload = delivery::event_payload(
+            event.as_str(),
+            zone.name.as_str(),
+            from_serial,
+            to_serial,
+            diff,
+            now,
+        );
+        let queued = RepositoryService::enqueue_webhook_deliveries_tx(
+            tx,
+            zone.id,
+            &WebhookDelivery {
+                id: 0,
+                webhook_id: 0,
+                event: event.as_str().to_string(),
+                zone_name: zone.name.to_string(),
+                from_serial,
+                to_serial,
+                payload: Some(payload),
+                status: STATUS_PENDING.to_string(),
+                attempts: 0,
+                next_attempt_at: now,
+                last_error: None,
+                created_at: now,
+                delivered_at: None,
+            },
+        )
+        .await?;
+
+        if queued > 0 {
+            delivery::mark_pending();
+        }
+        Ok(())
+    }
+}
+
+async fn find_webhook(name: &str) -> Result<Webhook, ServiceError> {
+    RepositoryService::get_webhook_by_name(&normalize_webhook_name(name)?)
+        .await?
+        .ok_or_else(|| ServiceError::webhook_not_found(name))
+}
+
+/// Webhook names follow the token naming rules.
+fn normalize_webhook_name(name: &str) -> Result<String, ServiceError> {
+    normalize_token_name(name)
+        .map_err(|e| ServiceError::new(e.code, e.message.replacen("token name", "webhook name", 1)))
+}
+
+fn validate_url(url: &str) -> Result<String, ServiceError> {
+    let parsed = reqwest::Url::parse(url.trim())
+        .map_err(|e| ServiceError::invalid_input(format!("invalid webhook url: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(ServiceError::invalid_input(
+            "webhook url must be an http or https URL with a host",
+        ));
+    }
+    Ok(parsed.to_string())
+}
+
+fn validate_secret(secret: &str) -> Result<String, ServiceError> {
+    if secret.is_empty() {
+        return Err(ServiceError::invalid_input("secret must not be empty"));
+    }
+    if has_whitespace_or_control(secret) {
+        return Err(ServiceError::invalid_input(
+            "secret must not contain whitespace or control characters",
+        ));
+    }
+    if secret.len() > MAX_SECRET_LEN {
+        return Err(ServiceError::invalid_input(
+            "secret must be 255 bytes or fewer",
+        ));
+    }
+    Ok(secret.to_string())
+}
+
+fn generate_secret() -> String {
+    rand::rng()
+        .sample_iter(Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
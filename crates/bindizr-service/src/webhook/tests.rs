@@ -0,0 +1,82 @@
+use bindizr_core::config::WebhookConfig;
+use chrono::{TimeZone, Utc};
+
+use super::{
+    delivery::{next_attempt_at, sign},
+    normalize_webhook_name, validate_secret, validate_url,
+};
+use crate::error::ErrorCode;
+
+#[test]
+fn sign_is_hmac_sha256_over_timestamp_and_body() {
+    // HMAC-SHA256("secret", "1700000000.{}"), computed independently.
+    assert_eq!(
+        sign("secret", 1_700_000_000, "{}"),
+        "sha256=b8569b78799ff9e3cbff0fc2d63a33a2b57f3282abd07c37ae5e8e7d79a5f163"
+    );
+}
+
+#[test]
+fn sign_depends_on_secret_timestamp_and_body() {
+    let base = sign("secret", 1, "{}");
+    assert_ne!(base, sign("other", 1, "{}"));
+    assert_ne!(base, sign("secret", 2, "{}"));
+    assert_ne!(base, sign("secret", 1, "{ }"));
+}
+
+#[test]
+fn next_attempt_at_doubles_the_backoff_up_to_the_cap() {
+    let config = WebhookConfig {
+        initial_backoff_secs: 10,
+        max_backoff_secs: 60,
+        ..WebhookConfig::default()
+    };
+    let now = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+    let delays: Vec<i64> = (1..=5)
+        .map(|attempts| (next_attempt_at(&config, attempts, now) - now).num_seconds())
+        .collect();
+
+    assert_eq!(delays, vec![10, 20, 40, 60, 60]);
+}
+
+#[test]
+fn next_attempt_at_survives_many_attempts() {
+    let config = WebhookConfig::default();
+    let now = Utc::now();
+
+    assert_eq!(
+        (next_attempt_at(&config, i32::MAX, now) - now).num_seconds(),
+        config.max_backoff_secs as i64
+    );
+}
+
+#[test]
+fn validate_url_accepts_only_http_and_https() {
+    assert_eq!(
+        validate_url(" https://hooks.example.com/dns ").unwrap(),
+        "https://hooks.example.com/dns"
+    );
+    assert!(validate_url("http://127.0.0.1:8080/").is_ok());
+
+    for url in ["ftp://example.com/", "not a url", "mailto:ops@example.com"] {
+        assert_eq!(validate_url(url).unwrap_err().code, ErrorCode::InvalidInput);
+    }
+}
+
+#[test]
+fn validate_secret_rejects_blank_and_oversized_secrets() {
+    assert!(validate_secret("s3cret").is_ok());
+    for secret in [String::new(), "has space".to_string(), "x".repeat(256)] {
+        assert_eq!(
+            validate_secret(&secret).unwrap_err().code,
+            ErrorCode::InvalidInput
+        );
+    }
+}
+
+#[test]
+fn normalize_webhook_name_reports_webhook_in_errors() {
+    assert_eq!(normalize_webhook_name(" Deploy ").unwrap(), "deploy");
+    let err = normalize_webhook_name("bad name").unwrap_err();
+    assert!(err.message.starts_with("webhook name"), "{}", err.message);
+}
@@ -1,14 +1,17 @@
-use bindizr_core::dns::CATALOG_ZONE_NAME;
+use bindizr_core::dns::{CATALOG_ZONE_NAME, serial};
 use chrono::Utc;
 
-use super::ZoneService;
+use super::{ZoneService, apex_ns_rrset_ttl};
 use crate::{
-    authorization::Caller,
+    authorization::{Caller, TokenRole},
+    catalog::CatalogService,
     error::{ErrorCode, ServiceError},
     log_error, log_info, log_warn,
     model::zone::Zone,
+    precondition::Precondition,
     repository::RepositoryService,
     serial::{generate_serial, validate_initial_serial},
+    template::ZoneTemplateService,
     types::CreateZoneRequest,
     zone::{
         DEFAULT_EXPIRE, DEFAULT_MINIMUM_TTL, DEFAULT_REFRESH, DEFAULT_RETRY,
@@ -18,11 +21,14 @@ use crate::{
 
 impl ZoneService {
     /// Create a new zone with an apex NS record and NOTIFY the catalog zone.
+    /// With a `template`, the zone also starts with the template's records
+    /// rendered for it, and stays linked to the template.
     pub async fn create(
         caller: &Caller,
         create_zone_request: &CreateZoneRequest,
+        precondition: &Precondition,
     ) -> Result<Zone, ServiceError> {
-        caller.require_global("create zones")?;
+        caller.require_role(TokenRole::ZoneAdmin, "create zones")?;
 
         let validated = validate_create_zone_request(create_zone_request)?;
         let timers = resolve_soa_timers(
@@ -38,23 +44,59 @@ impl ZoneService {
         // Parent/child zones are allowed; only the same normalized zone name is rejected.
         // Names are stored normalized, so an exact lookup is enough to detect a collision.
         match RepositoryService::get_zone_by_name(validated.name.as_str()).await {
-            Ok(Some(_)) => {
+            Ok(Some(zone)) => {
+                // Under `If-None-Match: *` an existing zone fails the precondition.
+                precondition.check_zone(&zone)?;
                 log_error!("Zone with name {} already exists", validated.name);
                 return Err(ServiceError::zone_conflict(format!(
                     "Zone with name '{}' already exists",
                     validated.name
                 )));
             }
-            Ok(None) => {}
+            Ok(None) => {
+                precondition.check(&[], || format!("zone '{}' does not exist", validated.name))?
+            }
             Err(e) => {
                 log_error!("Failed to check existing zone: {}", e);
                 return Err(ServiceError::internal("Failed to create zone".to_string()));
             }
         };
 
+        // A catalog zone is served under its name; a zone cannot share it.
+        if CatalogService::resolve(validated.name.as_str())
+            .await?
+            .is_some()
+        {
+            return Err(ServiceError::zone_conflict(format!(
+                "'{}' is a catalog zone",
+                validated.name
+            )));
+        }
+
+        // Rendered before the transaction, so a missing variable or a record
+        // the zone name makes invalid fails without writing anything.
+        let provisioning = match &create_zone_request.template {
+            Some(template) => Some(
+                ZoneTemplateService::prepare_zone(
+                    template,
+                    &create_zone_request.template_variables,
+                    &validated.name,
+                    validated.ttl,
+                )
+                .await?,
+            ),
+            None if !create_zone_request.template_variables.is_empty() => {
+                return Err(ServiceError::invalid_input(
+                    "template_variables require a template",
+                ));
+            }
+            None => None,
+        };
+
+        let serial_policy = validated.serial_policy.unwrap_or_default();
         let serial = match create_zone_request.serial {
             Some(s) => validate_initial_serial(s)?,
-            None => generate_serial(None)?,
+            None => generate_serial(serial_policy, None),
         };
 
         let mut tx = RepositoryService::begin_tx("Failed to create zone").await?;
@@ -69,6 +111,7 @@ impl ZoneService {
                     admin_email: validated.admin_email.clone(),
                     ttl: validated.ttl,
                     serial,
+                    serial_policy,
                     refresh: timers.refresh,
                     retry: timers.retry,
                     expire: timers.expire,
@@ -89,10 +132,17 @@ impl ZoneService {
             })?;
 
             // A new zone has no IXFR history to log against, so the apex NS row
-            // goes in directly.
-            RepositoryService::create_record_tx(
+            // goes in directly. It joins the template's apex NS RRset, if any.
+            let primary_ns_ttl = apex_ns_rrset_ttl(
+                &created_zone,
+                provisioning
+                    .iter()
+                    .flat_map(|provisioning| &provisioning.records)
+                    .map(|r| (&r.record_type, &r.name, r.ttl)),
+            );
+            let primary_ns = RepositoryService::create_record_tx(
                 &mut tx,
-                created_zone.primary_ns_record(created_zone.ttl),
+                created_zone.primary_ns_record(primary_ns_ttl),
             )
             .await
             .map_err(|e| {
@@ -100,7 +150,18 @@ impl ZoneService {
                 ServiceError::internal("Failed to create primary NS record".to_string())
             })?;
 
-            ZoneService::save_snapshot_tx(&mut tx, &created_zone, created_zone.serial).await?;
+            if let Some(provisioning) = provisioning {
+                ZoneTemplateService::provision_zone_tx(
+                    &mut tx,
+                    &created_zone,
+                    vec![primary_ns],
+                    provisioning,
+                )
+                .await?;
+            }
+
+            ZoneService::save_snapshot_tx(&mut tx, &created_zone, created_zone.serial, None)
+                .await?;
 
             Ok::<Zone, ServiceError>(created_zone)
         }
@@ -113,7 +174,7 @@ impl ZoneService {
             "event=zone_create zone={} primary_ns={} serial={} zone_id={}",
             created_zone.name,
             created_zone.primary_ns,
-            created_zone.serial,
+            serial::to_wire(created_zone.serial),
             created_zone.id
         );
 
@@ -3,14 +3,34 @@ use bindizr_db::repository::LockLevel;
 
 use super::ZoneService;
 use crate::{
-    authorization::Caller, error::ServiceError, log_error, log_info, log_warn,
+    audit::{AuditAction, AuditService},
+    authorization::{Caller, TokenRole},
+    catalog::{self, CatalogService},
+    error::ServiceError,
+    log_error, log_info,
+    precondition::Precondition,
     repository::RepositoryService,
+    webhook::{WebhookEvent, WebhookService},
 };
 
 impl ZoneService {
-    /// Delete a zone by name and NOTIFY the catalog zone after commit.
-    pub async fn delete(caller: &Caller, zone_name: &str) -> Result<(), ServiceError> {
-        caller.require_global("delete zones")?;
+    /// Delete a zone by name, recording the deletion in the audit log, and
+    /// NOTIFY the catalogs listing it after commit.
+    pub async fn delete(
+        caller: &Caller,
+        zone_name: &str,
+        precondition: &Precondition,
+    ) -> Result<(), ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "delete zones")?;
+
+        // The zone's catalog properties go with it, so read which catalogs
+        // list it first.
+        let catalogs = match ZoneService::find_by_name(zone_name).await {
+            Ok(Some(zone)) => CatalogService::catalogs_of_zone(zone.id)
+                .await
+                .unwrap_or_else(|_| vec![CATALOG_ZONE_NAME.to_string()]),
+            _ => vec![CATALOG_ZONE_NAME.to_string()],
+        };
 
         let mut tx = RepositoryService::begin_tx("Failed to delete zone").await?;
 
@@ -18,6 +38,20 @@ impl ZoneService {
             // Locked lookup so a raced double-delete reports 404, not success.
             let zone =
                 ZoneService::get_by_name_tx(&mut tx, zone_name, LockLevel::Exclusive).await?;
+            precondition.check_zone(&zone)?;
+
+            AuditService::record_tx(&mut tx, &zone, zone.serial, AuditAction::Deleted).await?;
+
+            // Queued before the delete: a webhook scoped to this zone goes
+            // with it, so only global webhooks hear about the deletion.
+            WebhookService::enqueue_tx(
+                &mut tx,
+                &zone,
+                WebhookEvent::Deleted,
+                Some(zone.serial),
+                None,
+            )
+            .await?;
 
             RepositoryService::delete_zone_tx(&mut tx, zone.id)
                 .await
@@ -35,9 +69,7 @@ impl ZoneService {
         log_info!("event=zone_delete zone={} zone_id={}", zone_name, zone_id);
 
         // Send catalog NOTIFY so secondaries drop the removed zone
-        if let Err(e) = crate::notify::send_notify_after_update(Some(CATALOG_ZONE_NAME)).await {
-            log_warn!("Failed to send NOTIFY for {}: {}", CATALOG_ZONE_NAME, e);
-        }
+        catalog::notify_catalogs(catalogs).await;
 
         Ok(())
     }
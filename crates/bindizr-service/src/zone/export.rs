@@ -2,7 +2,7 @@
 
 use std::fmt::Write as _;
 
-use bindizr_core::dns::name::to_fqdn;
+use bindizr_core::dns::{name::to_fqdn, serial};
 use bindizr_db::repository::LockLevel;
 
 use super::{ZoneService, validation::normalize_zone_name};
@@ -66,7 +66,7 @@ impl ZoneService {
             zone.ttl,
             to_fqdn(&zone.primary_ns),
             to_fqdn(mailbox.as_str()),
-            zone.serial,
+            serial::to_wire(zone.serial),
             zone.refresh,
             zone.retry,
             zone.expire,
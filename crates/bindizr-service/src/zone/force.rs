@@ -1,3 +1,4 @@
+use bindizr_core::dns::serial;
 use bindizr_db::repository::LockLevel;
 
 use super::ZoneService;
@@ -40,7 +41,8 @@ impl ZoneService {
             let zone =
                 ZoneService::get_by_name_tx(&mut tx, zone_name, LockLevel::Exclusive).await?;
 
-            let new_serial = generate_serial(Some(zone.serial))?;
+            let previous_serial = zone.serial;
+            let new_serial = generate_serial(zone.serial_policy, Some(previous_serial));
             let updated_zone = RepositoryService::update_zone_tx(
                 &mut tx,
                 Zone {
@@ -54,7 +56,13 @@ impl ZoneService {
                 ServiceError::internal("Failed to force increment zone serial".to_string())
             })?;
 
-            ZoneService::save_snapshot_tx(&mut tx, &updated_zone, new_serial).await?;
+            ZoneService::save_snapshot_tx(
+                &mut tx,
+                &updated_zone,
+                new_serial,
+                Some(previous_serial),
+            )
+            .await?;
 
             Ok::<Zone, ServiceError>(updated_zone)
         }
@@ -67,7 +75,7 @@ impl ZoneService {
         log_info!(
             "event=zone_force_serial zone={} new_serial={} zone_id={}",
             updated_zone.name,
-            updated_zone.serial,
+            serial::to_wire(updated_zone.serial),
             updated_zone.id
         );
 
@@ -1,3 +1,4 @@
+use bindizr_core::dns::serial;
 use bindizr_db::repository::{LockLevel, ZoneFilter};
 
 use super::{ZoneService, validation::normalize_zone_name};
@@ -43,6 +44,13 @@ impl ZoneService {
         RepositoryService::ping_zones().await
     }
 
+    /// Changes whenever this process commits a write. Zones listed at one
+    /// value are current until it changes, barring writes made by another
+    /// instance sharing the database.
+    pub fn write_generation() -> u64 {
+        RepositoryService::commit_count()
+    }
+
     /// List all zones.
     pub async fn list() -> Result<Vec<Zone>, ServiceError> {
         RepositoryService::list_zones().await.map_err(|e| {
@@ -69,7 +77,7 @@ impl ZoneService {
             ttl: filter.ttl,
             min_ttl: filter.min_ttl,
             max_ttl: filter.max_ttl,
-            serial: filter.serial,
+            serial: filter.serial.map(serial::from_wire),
             search: filter.search,
             scope_token_id,
             limit,
@@ -3,7 +3,7 @@
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 
-use bindizr_core::dns::{name::OwnerName, record::SoaMailbox};
+use bindizr_core::dns::{name::OwnerName, record::SoaMailbox, serial};
 use bindizr_db::repository::LockLevel;
 use chrono::Utc;
 
@@ -13,7 +13,7 @@ use super::{
 };
 use crate::{
     RepositoryTx,
-    authorization::Caller,
+    authorization::{Caller, TokenRole},
     error::ServiceError,
     log_info, log_warn,
     model::{
@@ -22,6 +22,7 @@ use crate::{
         zone_change::ZoneChange,
         zone_snapshot::ZoneSnapshot,
     },
+    precondition::Precondition,
     record::{
         RecordService, validate_delete_constraints, validate_record_add_constraints_normalized,
     },
@@ -110,7 +111,7 @@ async fn reconstruct_records_at_serial(
     )
     .await?;
 
-    // Changes arrive ordered by (serial, id) ascending; undo them newest-first.
+    // Changes arrive in RFC 1982 serial order, then by id; undo them newest-first.
     for change in changes.iter().rev() {
         if change.record_type == "SOA" {
             continue;
@@ -337,8 +338,8 @@ fn sort_records(records: &mut [ReconstructedRecord]) {
 }
 
 /// Build the zone as it should look after rolling back to `snapshot`: SOA
-/// metadata from the snapshot, identity (id/name) and creation time unchanged,
-/// serial advanced to `new_serial`.
+/// metadata from the snapshot, identity (id/name), serial policy and creation
+/// time unchanged, serial advanced to `new_serial`.
 fn restored_zone_from_snapshot(
     zone: &Zone,
     snapshot: &ZoneSnapshot,
@@ -357,6 +358,7 @@ fn restored_zone_from_snapshot(
         admin_email,
         ttl: snapshot.ttl,
         serial: new_serial,
+        serial_policy: zone.serial_policy,
         refresh: snapshot.refresh,
         retry: snapshot.retry,
         expire: snapshot.expire,
@@ -407,8 +409,9 @@ impl ZoneService {
     pub async fn get_snapshot(
         caller: &Caller,
         zone_name: &str,
-        serial: i32,
+        serial: u32,
     ) -> Result<(ZoneSnapshot, Vec<ReconstructedRecord>), ServiceError> {
+        let serial = serial::from_wire(serial);
         let lookup_name = normalize_zone_name(zone_name)?;
         let mut tx = RepositoryService::begin_tx("Failed to load snapshot").await?;
 
@@ -445,9 +448,10 @@ impl ZoneService {
     pub async fn diff_snapshots(
         caller: &Caller,
         zone_name: &str,
-        from_serial: i32,
-        to_serial: Option<i32>,
+        from_serial: u32,
+        to_serial: Option<u32>,
     ) -> Result<SnapshotDiffResponse, ServiceError> {
+        let from_serial = serial::from_wire(from_serial);
         let lookup_name = normalize_zone_name(zone_name)?;
         let mut tx = RepositoryService::begin_tx("Failed to diff snapshots").await?;
 
@@ -458,7 +462,7 @@ impl ZoneService {
             if !caller.zone_visible(zone.id) {
                 return Err(ServiceError::zone_not_found(zone_name));
             }
-            let to_serial = to_serial.unwrap_or(zone.serial);
+            let to_serial = to_serial.map_or(zone.serial, serial::from_wire);
 
             require_serial(&mut tx, &zone, from_serial).await?;
             require_serial(&mut tx, &zone, to_serial).await?;
@@ -468,8 +472,8 @@ impl ZoneService {
             let to_records = records_at_serial(&mut tx, zone.id, to_serial, zone.serial).await?;
 
             Ok::<_, ServiceError>(SnapshotDiffResponse {
-                from_serial,
-                to_serial,
+                from_serial: serial::to_wire(from_serial),
+                to_serial: serial::to_wire(to_serial),
                 diff: build_record_diff(&zone, &from_records, &to_records),
             })
         }
@@ -478,6 +482,19 @@ impl ZoneService {
         RepositoryService::finish_tx(tx, result, "Failed to diff snapshots").await
     }
 
+    /// The record diff from `from_serial` to `to_serial`, inside the
+    /// transaction that has just advanced the zone to `to_serial`.
+    pub(crate) async fn diff_to_current_serial_tx(
+        tx: &mut RepositoryTx<'_>,
+        zone: &Zone,
+        from_serial: i32,
+        to_serial: i32,
+    ) -> Result<RecordDiff, ServiceError> {
+        let from_records = records_at_serial(tx, zone.id, from_serial, to_serial).await?;
+        let to_records = records_at_serial(tx, zone.id, to_serial, to_serial).await?;
+        Ok(build_record_diff(zone, &from_records, &to_records))
+    }
+
     /// Roll a zone back to the state captured at `target_serial`. The record
     /// set and SOA metadata return to that serial's state while the zone's
     /// serial advances to a new value (serials never go backward). The zone
@@ -485,10 +502,12 @@ impl ZoneService {
     pub async fn rollback(
         caller: &Caller,
         zone_name: &str,
-        target_serial: i32,
+        target_serial: u32,
         dry_run: bool,
+        precondition: &Precondition,
     ) -> Result<RollbackZoneResponse, ServiceError> {
-        caller.require_global("roll back zones")?;
+        caller.require_role(TokenRole::ZoneAdmin, "roll back zones")?;
+        let target_serial = serial::from_wire(target_serial);
 
         let lookup_name = normalize_zone_name(zone_name)?;
         let mut tx = RepositoryService::begin_tx("Failed to roll back zone").await?;
@@ -497,11 +516,13 @@ impl ZoneService {
             let zone =
                 ZoneService::get_by_name_tx(&mut tx, lookup_name.as_str(), LockLevel::Exclusive)
                     .await?;
+            precondition.check_zone(&zone)?;
 
-            if target_serial < 1 || target_serial >= zone.serial {
+            if !serial::is_newer(serial::to_wire(zone.serial), serial::to_wire(target_serial)) {
                 return Err(ServiceError::invalid_input(format!(
                     "target serial {} must be less than the current serial {}",
-                    target_serial, zone.serial
+                    serial::to_wire(target_serial),
+                    serial::to_wire(zone.serial)
                 )));
             }
             let snapshot = RepositoryService::get_zone_snapshot_by_serial_tx(
@@ -513,7 +534,7 @@ impl ZoneService {
             .await?
             .ok_or_else(|| ServiceError::snapshot_not_found(zone.name.as_str(), target_serial))?;
 
-            let new_serial = generate_serial(Some(zone.serial))?;
+            let new_serial = generate_serial(zone.serial_policy, Some(zone.serial));
             let restored_zone = restored_zone_from_snapshot(&zone, &snapshot, new_serial)?;
             let soa_changed = soa_metadata_changed(&zone, &restored_zone);
 
@@ -653,8 +674,8 @@ impl ZoneService {
                     RollbackZoneResponse {
                         applied: false,
                         dry_run: true,
-                        target_serial,
-                        new_serial,
+                        target_serial: serial::to_wire(target_serial),
+                        new_serial: serial::to_wire(new_serial),
                         summary,
                     },
                     zone.name.clone(),
@@ -673,14 +694,15 @@ impl ZoneService {
                 .await?;
             RecordService::insert_records_with_changes_tx(&mut tx, zone.id, new_serial, &to_insert)
                 .await?;
-            ZoneService::save_snapshot_tx(&mut tx, &restored_zone, new_serial).await?;
+            ZoneService::save_snapshot_tx(&mut tx, &restored_zone, new_serial, Some(zone.serial))
+                .await?;
 
             Ok((
                 RollbackZoneResponse {
                     applied: true,
                     dry_run: false,
-                    target_serial,
-                    new_serial,
+                    target_serial: serial::to_wire(target_serial),
+                    new_serial: serial::to_wire(new_serial),
                     summary,
                 },
                 zone.name.clone(),
@@ -0,0 +1,306 @@
+//! Whole-zone consistency checks. Record validation sees one record at a
+//! time; lint looks across the zone for names that point nowhere and
+//! delegations that do not hold together.
+
+use std::collections::{HashMap, HashSet};
+
+use bindizr_core::dns::name::OwnerName;
+use bindizr_db::repository::LockLevel;
+
+use super::{ZoneService, apex_ns_rrset_ttl, validation::normalize_zone_name};
+use crate::{
+    authorization::Caller,
+    error::ServiceError,
+    model::{
+        record::{Record, RecordType},
+        zone::Zone,
+    },
+    repository::RepositoryService,
+    types::{ZoneLintFinding, ZoneLintResponse},
+};
+
+const ERROR: &str = "error";
+const WARNING: &str = "warning";
+
+impl ZoneService {
+    /// Check a zone as a whole: in-zone targets that do not exist or are
+    /// aliases, delegations without glue, glue and other records a delegation
+    /// hides, and a split apex NS RRset. Read-only, so any caller who can see
+    /// the zone may lint it.
+    pub async fn lint(caller: &Caller, zone_name: &str) -> Result<ZoneLintResponse, ServiceError> {
+        // One snapshot, so a concurrent edit cannot pair old records with a
+        // new delegation.
+        let lookup_name = normalize_zone_name(zone_name)?;
+        let mut tx = RepositoryService::begin_tx("Failed to lint zone").await?;
+        let load_result = async {
+            let zone = RepositoryService::get_zone_by_name_tx(
+                &mut tx,
+                lookup_name.as_str(),
+                LockLevel::Shared,
+            )
+            .await?
+            .ok_or_else(|| ServiceError::zone_not_found(zone_name))?;
+            // Invisible zones read as 404 so scoped tokens cannot probe them.
+            if !caller.zone_visible(zone.id) {
+                return Err(ServiceError::zone_not_found(zone_name));
+            }
+            let records =
+                RepositoryService::list_records_by_zone_id_tx(&mut tx, zone.id, LockLevel::None)
+                    .await?;
+            Ok::<(Zone, Vec<Record>), ServiceError>((zone, records))
+        }
+        .await;
+        let (zone, records) =
+            RepositoryService::finish_tx(tx, load_result, "Failed to lint zone").await?;
+
+        let findings = lint_records(&zone, &records);
+        Ok(ZoneLintResponse::from_findings(&zone, findings))
+    }
+}
+
+/// The zone's records by owner name, and the names the rest of the checks
+/// look up for every record.
+struct ZoneIndex<'a> {
+    by_name: HashMap<&'a OwnerName, Vec<&'a Record>>,
+    /// Topmost delegation points only: an NS below another cut is itself
+    /// hidden by that cut, not a delegation of its own.
+    cuts: Vec<&'a OwnerName>,
+    /// Every in-zone name an NS record points at, so glue named by any
+    /// delegation counts as used.
+    ns_targets: HashSet<OwnerName>,
+    /// The labels each wildcard owner stands under.
+    wildcard_parents: Vec<&'a [String]>,
+}
+
+impl<'a> ZoneIndex<'a> {
+    fn new(zone: &Zone, records: &'a [Record]) -> Self {
+        let mut by_name: HashMap<&OwnerName, Vec<&Record>> = HashMap::new();
+        for record in records {
+            by_name.entry(&record.name).or_default().push(record);
+        }
+        let ns_targets = records
+            .iter()
+            .filter(|r| r.record_type == RecordType::NS)
+            .filter_map(|r| in_zone_target(zone, &r.value))
+            .collect();
+        let wildcard_parents = by_name
+            .keys()
+            .filter_map(|owner| match owner.labels() {
+                [first, parent @ ..] if first == "*" => Some(parent),
+                _ => None,
+            })
+            .collect();
+
+        let ns_owners = records
+            .iter()
+            .filter(|r| r.record_type == RecordType::NS && !r.name.is_apex())
+            .map(|r| &r.name)
+            .collect::<Vec<_>>();
+        let mut cuts = ns_owners
+            .iter()
+            .copied()
+            .filter(|name| {
+                !ns_owners
+                    .iter()
+                    .any(|other| other != name && name.is_same_or_under(other))
+            })
+            .collect::<Vec<_>>();
+        cuts.sort();
+        cuts.dedup();
+
+        ZoneIndex {
+            by_name,
+            cuts,
+            ns_targets,
+            wildcard_parents,
+        }
+    }
+
+    /// The delegation point `name` is at or below, if any.
+    fn cut_of(&self, name: &OwnerName) -> Option<&'a OwnerName> {
+        self.cuts
+            .iter()
+            .copied()
+            .find(|cut| name.is_same_or_under(cut))
+    }
+
+    fn has_type(&self, name: &OwnerName, types: &[RecordType]) -> bool {
+        self.by_name
+            .get(name)
+            .is_some_and(|records| records.iter().any(|r| types.contains(&r.record_type)))
+    }
+
+    /// Whether a query for `name` finds data: the apex always holds the SOA,
+    /// and a wildcard synthesizes any name below it. An empty non-terminal
+    /// only answers NODATA, so it does not count.
+    fn exists(&self, name: &OwnerName) -> bool {
+        if name.is_apex() || self.by_name.contains_key(name) {
+            return true;
+        }
+        // A closer existing name would block the wildcard (RFC 4592), which
+        // this ignores; lint errs toward not reporting a target it cannot rule out.
+        self.wildcard_parents
+            .iter()
+            .any(|parent| name.labels().len() > parent.len() && name.labels().ends_with(parent))
+    }
+}
+
+/// Lint a zone's records; see [`ZoneService::lint`]. Targets outside the zone
+/// or inside a delegated child are not this zone's to check and are skipped.
+pub(super) fn lint_records(zone: &Zone, records: &[Record]) -> Vec<ZoneLintFinding> {
+    let index = ZoneIndex::new(zone, records);
+    let mut findings = Vec::new();
+
+    // Deterministic order, as the zone file would list them.
+    let mut sorted = records.iter().collect::<Vec<_>>();
+    sorted.sort_by_cached_key(|r| (r.name.clone(), r.record_type.as_str(), r.value.clone()));
+
+    // The first apex NS sets the RRset TTL, as it does when the primary NS is
+    // synthesized; each row that disagrees is reported against it.
+    let apex_ns_ttl = apex_ns_rrset_ttl(
+        zone,
+        records.iter().map(|r| (&r.record_type, &r.name, r.ttl)),
+    );
+    for record in &sorted {
+        if zone.is_apex_ns(&record.record_type, &record.name) && record.ttl != apex_ns_ttl {
+            findings.push(finding(
+                zone,
+                WARNING,
+                "apex_ns_ttl_mismatch",
+                record,
+                format!(
+                    "TTL {} differs from the apex NS RRset's TTL {}; every record in an RRset must share one TTL (RFC 2181, Section 5.2)",
+                    record.ttl, apex_ns_ttl
+                ),
+            ));
+        }
+    }
+
+    for record in sorted {
+        let cut = index.cut_of(&record.name);
+
+        // Below a delegation only glue is served, and only the NS at the cut
+        // itself delegates.
+        if let Some(cut) = cut {
+            let cut_fqdn = cut.to_fqdn(&zone.name);
+            match record.record_type {
+                RecordType::NS if record.name == *cut => {}
+                RecordType::A | RecordType::AAAA => {
+                    if !index.ns_targets.contains(&record.name) {
+                        findings.push(finding(
+                            zone,
+                            WARNING,
+                            "orphaned_glue",
+                            record,
+                            format!(
+                                "glue below the delegation at {cut_fqdn} that no NS record names, so it is never served"
+                            ),
+                        ));
+                    }
+                    continue;
+                }
+                _ => {
+                    findings.push(finding(
+                        zone,
+                        WARNING,
+                        "occluded_record",
+                        record,
+                        format!(
+                            "below the delegation at {cut_fqdn}, so it is never served; the child zone answers for this name"
+                        ),
+                    ));
+                    continue;
+                }
+            }
+        }
+
+        let target = match record.record_type {
+            RecordType::CNAME | RecordType::NS => in_zone_target(zone, &record.value),
+            RecordType::MX => RecordType::mx_wire_fields(&record.value, record.priority)
+                .ok()
+                .and_then(|(_, target)| in_zone_target(zone, target)),
+            RecordType::SRV => RecordType::srv_wire_fields(&record.value, record.priority)
+                .ok()
+                .and_then(|(_, _, _, target)| in_zone_target(zone, target)),
+            _ => None,
+        };
+        let Some(target) = target else {
+            continue;
+        };
+        let target_fqdn = target.to_fqdn(&zone.name);
+
+        // MX, NS and SRV targets must name the host itself, not an alias
+        // (RFC 2181, Section 10.3; RFC 2782).
+        if record.record_type != RecordType::CNAME && index.has_type(&target, &[RecordType::CNAME])
+        {
+            findings.push(finding(
+                zone,
+                ERROR,
+                "target_is_cname",
+                record,
+                format!("{} target {target_fqdn} is a CNAME", record.record_type),
+            ));
+            continue;
+        }
+
+        let target_cut = index.cut_of(&target);
+        if record.record_type == RecordType::NS && cut.is_some() {
+            // Resolvers can only reach a name server inside this delegation,
+            // or elsewhere in this zone, through the address records here. One
+            // inside another delegation is found through that child instead.
+            if (target_cut.is_none() || target_cut == cut)
+                && !index.has_type(&target, &[RecordType::A, RecordType::AAAA])
+            {
+                findings.push(finding(
+                    zone,
+                    ERROR,
+                    "missing_glue",
+                    record,
+                    format!("delegation to {target_fqdn} has no A or AAAA glue in the zone"),
+                ));
+            }
+        } else if target_cut.is_none() && !index.exists(&target) {
+            findings.push(finding(
+                zone,
+                ERROR,
+                "missing_target",
+                record,
+                format!(
+                    "{} target {target_fqdn} does not exist in the zone",
+                    record.record_type
+                ),
+            ));
+        }
+    }
+
+    findings
+}
+
+/// The owner a target name maps to when it lies inside `zone`. Targets are
+/// stored absolute; the null MX/SRV target `.` and names outside the zone map
+/// to nothing.
+fn in_zone_target(zone: &Zone, target: &str) -> Option<OwnerName> {
+    OwnerName::parse_absolute_in_zone(target, &zone.name).ok()
+}
+
+fn finding(
+    zone: &Zone,
+    severity: &str,
+    code: &str,
+    record: &Record,
+    message: String,
+) -> ZoneLintFinding {
+    ZoneLintFinding {
+        severity: severity.to_string(),
+        code: code.to_string(),
+        name: record.name.to_fqdn(&zone.name),
+        record_type: record.record_type.to_string(),
+        value: record
+            .record_type
+            .presentation_rdata(&record.value, record.priority),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,201 @@
+use bindizr_core::dns::name::{OwnerName, ZoneName};
+use chrono::Utc;
+
+use super::*;
+use crate::model::zone::SerialPolicy;
+
+fn test_zone() -> Zone {
+    Zone {
+        id: 1,
+        name: ZoneName::from_row("example.com"),
+        primary_ns: "ns1.example.com".to_string(),
+        admin_email: "hostmaster@example.com".to_string(),
+        ttl: 3600,
+        serial: 1,
+        serial_policy: SerialPolicy::Increment,
+        refresh: 7200,
+        retry: 3600,
+        expire: 604800,
+        minimum_ttl: 86400,
+        created_at: Utc::now(),
+    }
+}
+
+fn record(name: &str, record_type: RecordType, value: &str, ttl: i32) -> Record {
+    Record {
+        id: 0,
+        name: OwnerName::from_row(name),
+        priority: record_type.takes_priority().then_some(10),
+        record_type,
+        value: value.to_string(),
+        ttl,
+        created_at: Utc::now(),
+        zone_id: 1,
+    }
+}
+
+/// A zone that lints clean, for the tests to break one way at a time.
+fn healthy_records() -> Vec<Record> {
+    vec![
+        record("", RecordType::NS, "ns1.example.com.", 3600),
+        record("", RecordType::NS, "ns.example.net.", 3600),
+        record("ns1", RecordType::A, "192.0.2.1", 3600),
+        record("", RecordType::MX, "mail.example.com.", 3600),
+        record("mail", RecordType::A, "192.0.2.25", 3600),
+        record("www", RecordType::CNAME, "example.com.", 3600),
+        record(
+            "_sip._tcp",
+            RecordType::SRV,
+            "5 5060 sip.example.com.",
+            3600,
+        ),
+        record("sip", RecordType::AAAA, "2001:db8::5", 3600),
+        record("sub", RecordType::NS, "ns1.sub.example.com.", 3600),
+        record("sub", RecordType::NS, "ns.example.net.", 3600),
+        record("ns1.sub", RecordType::A, "192.0.2.53", 3600),
+    ]
+}
+
+fn codes(findings: &[ZoneLintFinding]) -> Vec<(&str, &str, &str)> {
+    findings
+        .iter()
+        .map(|f| (f.code.as_str(), f.name.as_str(), f.record_type.as_str()))
+        .collect()
+}
+
+#[test]
+fn lint_records_passes_a_consistent_zone() {
+    assert!(lint_records(&test_zone(), &healthy_records()).is_empty());
+}
+
+#[test]
+fn lint_records_reports_in_zone_targets_that_do_not_exist() {
+    let mut records = healthy_records();
+    records.retain(|r| r.name != OwnerName::from_row("mail"));
+    records.push(record("ftp", RecordType::CNAME, "files.example.com.", 3600));
+    // Out-of-zone targets, the null target `.` and names a wildcard covers
+    // are not missing.
+    records.push(record("docs", RecordType::CNAME, "docs.example.org.", 3600));
+    records.push(record("_imap._tcp", RecordType::SRV, "0 0 .", 3600));
+    records.push(record("*.apps", RecordType::A, "192.0.2.80", 3600));
+    records.push(record(
+        "api",
+        RecordType::CNAME,
+        "v1.apps.example.com.",
+        3600,
+    ));
+
+    let findings = lint_records(&test_zone(), &records);
+    assert_eq!(
+        codes(&findings),
+        [
+            ("missing_target", "example.com.", "MX"),
+            ("missing_target", "ftp.example.com.", "CNAME"),
+        ]
+    );
+    assert!(findings.iter().all(|f| f.severity == ERROR));
+    assert_eq!(
+        findings[1].message,
+        "CNAME target files.example.com. does not exist in the zone"
+    );
+}
+
+#[test]
+fn lint_records_reports_mx_ns_and_srv_targets_that_are_cnames() {
+    let mut records = healthy_records();
+    records.retain(|r| r.name != OwnerName::from_row("mail"));
+    records.push(record("mail", RecordType::CNAME, "www.example.com.", 3600));
+    records.push(record("", RecordType::NS, "ns2.example.com.", 3600));
+    records.push(record("ns2", RecordType::CNAME, "ns1.example.com.", 3600));
+    records.push(record(
+        "_xmpp._tcp",
+        RecordType::SRV,
+        "5 5222 chat.example.com.",
+        3600,
+    ));
+    records.push(record("chat", RecordType::CNAME, "www.example.com.", 3600));
+
+    assert_eq!(
+        codes(&lint_records(&test_zone(), &records)),
+        [
+            ("target_is_cname", "example.com.", "MX"),
+            ("target_is_cname", "example.com.", "NS"),
+            ("target_is_cname", "_xmpp._tcp.example.com.", "SRV"),
+        ]
+    );
+}
+
+#[test]
+fn lint_records_reports_delegations_missing_glue() {
+    let mut records = healthy_records();
+    records.retain(|r| r.name != OwnerName::from_row("ns1.sub"));
+    // A sibling name server elsewhere in the zone needs its address here too,
+    // while one inside another delegation is that child's to serve.
+    records.push(record("lab", RecordType::NS, "ns-lab.example.com.", 3600));
+    records.push(record("dev", RecordType::NS, "ns1.sub.example.com.", 3600));
+
+    let findings = lint_records(&test_zone(), &records);
+    assert_eq!(
+        codes(&findings),
+        [
+            ("missing_glue", "lab.example.com.", "NS"),
+            ("missing_glue", "sub.example.com.", "NS"),
+        ]
+    );
+    assert_eq!(
+        findings[1].message,
+        "delegation to ns1.sub.example.com. has no A or AAAA glue in the zone"
+    );
+}
+
+#[test]
+fn lint_records_reports_glue_and_records_a_delegation_hides() {
+    let mut records = healthy_records();
+    records.push(record("old-ns.sub", RecordType::A, "192.0.2.99", 3600));
+    records.push(record("www.sub", RecordType::TXT, "\"hidden\"", 3600));
+    // An NS under the cut is not a delegation of its own.
+    records.push(record("deep.sub", RecordType::NS, "ns.example.net.", 3600));
+
+    let findings = lint_records(&test_zone(), &records);
+    assert_eq!(
+        codes(&findings),
+        [
+            ("occluded_record", "deep.sub.example.com.", "NS"),
+            ("orphaned_glue", "old-ns.sub.example.com.", "A"),
+            ("occluded_record", "www.sub.example.com.", "TXT"),
+        ]
+    );
+    assert!(findings.iter().all(|f| f.severity == WARNING));
+}
+
+#[test]
+fn lint_records_reports_apex_ns_ttls_that_split_the_rrset() {
+    let mut records = healthy_records();
+    records[1].ttl = 300;
+
+    let findings = lint_records(&test_zone(), &records);
+    assert_eq!(
+        codes(&findings),
+        [("apex_ns_ttl_mismatch", "example.com.", "NS")]
+    );
+    assert_eq!(findings[0].value, "ns.example.net.");
+    assert_eq!(findings[0].severity, WARNING);
+}
+
+#[test]
+fn from_findings_counts_severities_and_puts_errors_first() {
+    let mut records = healthy_records();
+    records[1].ttl = 300;
+    records.push(record("ftp", RecordType::CNAME, "files.example.com.", 3600));
+
+    let response =
+        ZoneLintResponse::from_findings(&test_zone(), lint_records(&test_zone(), &records));
+    assert_eq!((response.errors, response.warnings), (1, 1));
+    assert_eq!(
+        codes(&response.findings),
+        [
+            ("missing_target", "ftp.example.com.", "CNAME"),
+            ("apex_ns_ttl_mismatch", "example.com.", "NS"),
+        ]
+    );
+}
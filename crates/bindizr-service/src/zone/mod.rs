@@ -9,9 +9,13 @@ mod export;
 mod force;
 mod get;
 pub(crate) mod history;
+mod lint;
 mod notify;
+pub mod notify_target;
+mod prune;
 mod snapshot;
 pub mod token_policy;
+pub mod transfer_acl;
 pub mod tsig_policy;
 mod update;
 pub(crate) mod validation;
@@ -1,9 +1,12 @@
 //! Manual NOTIFY orchestration; delivery goes through the registered sender.
 
-use bindizr_core::dns::is_catalog_zone;
-
 use super::ZoneService;
-use crate::{authorization::Caller, error::ServiceError, log_info};
+use crate::{
+    authorization::{Caller, TokenRole},
+    catalog::CatalogService,
+    error::ServiceError,
+    log_info,
+};
 
 impl ZoneService {
     /// Send a manual NOTIFY for one zone or all zones, optionally forcing a
@@ -15,13 +18,17 @@ impl ZoneService {
     ) -> Result<(), ServiceError> {
         // Forcing bumps zone serials — a zone-plane mutation, not just a NOTIFY.
         if force {
-            caller.require_global("force a NOTIFY")?;
+            caller.require_role(TokenRole::ZoneAdmin, "force a NOTIFY")?;
         }
+        let catalog = match zone_name {
+            Some(name) => CatalogService::resolve(name).await?,
+            None => None,
+        };
         match zone_name {
-            // The virtual catalog zone has no row: nothing to bump, and no
-            // zone grant can cover it, so only a global caller may notify it.
-            Some(name) if is_catalog_zone(name) => {
-                caller.require_global("send NOTIFY for the catalog zone")?;
+            // Catalog zones are virtual and have no row: nothing to bump, and
+            // no zone grant can cover them, so only a zone admin may notify one.
+            Some(_) if catalog.is_some() => {
+                caller.require_role(TokenRole::ZoneAdmin, "send NOTIFY for a catalog zone")?;
                 if force {
                     log_info!("Skipping forced serial increment for virtual catalog zone");
                 }
@@ -34,7 +41,7 @@ impl ZoneService {
                 }
             }
             None => {
-                caller.require_global("send NOTIFY for all zones")?;
+                caller.require_role(TokenRole::ZoneAdmin, "send NOTIFY for all zones")?;
                 if force {
                     Self::force_increment_serial(zone_name).await?;
                 }
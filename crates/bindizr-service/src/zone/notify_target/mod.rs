@@ -0,0 +1,191 @@
+//! Per-zone NOTIFY targets: `notify` targets that, once a zone has any,
+//! replace the global `dns.secondary_addrs` servers for the zone's NOTIFYs and
+//! serial probes, and `also-notify` targets notified on top of either list.
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+};
+
+use bindizr_core::config::{self, SecondaryEntry};
+use chrono::Utc;
+
+use crate::{
+    authorization::{Caller, TokenRole},
+    error::ServiceError,
+    model::zone_notify_target::{NotifyTargetKind, ZoneNotifyTarget},
+    repository::RepositoryService,
+    tsig_key::TsigKeyService,
+    zone::ZoneService,
+};
+
+/// Adds, lists, and removes the NOTIFY targets of a zone.
+pub struct ZoneNotifyTargetService;
+
+impl ZoneNotifyTargetService {
+    /// Add a NOTIFY target to `zone_name`. `kind` defaults to notify; a
+    /// `tsig_key` must name an existing TSIG key.
+    pub async fn add(
+        caller: &Caller,
+        zone_name: &str,
+        kind: Option<&str>,
+        address: &str,
+        tsig_key: Option<&str>,
+    ) -> Result<ZoneNotifyTarget, ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage NOTIFY targets")?;
+
+        let zone = ZoneService::lookup_by_name(zone_name).await?;
+
+        let kind = match kind.map(str::trim).filter(|kind| !kind.is_empty()) {
+            Some(kind) => NotifyTargetKind::from_str(kind).map_err(ServiceError::invalid_input)?,
+            None => NotifyTargetKind::Notify,
+        };
+        let address = normalize_notify_address(address)?;
+
+        let existing = RepositoryService::list_zone_notify_targets_by_zone_id(zone.id).await?;
+        if existing.iter().any(|entry| entry.address == address) {
+            return Err(ServiceError::invalid_input(format!(
+                "'{}' is already a NOTIFY target of zone '{}'",
+                address, zone.name
+            )));
+        }
+
+        let tsig_key_name = match tsig_key.map(str::trim).filter(|key| !key.is_empty()) {
+            Some(key) => Some(TsigKeyService::lookup_by_name(key).await?.name),
+            None => None,
+        };
+
+        RepositoryService::create_zone_notify_target(ZoneNotifyTarget {
+            id: 0,
+            zone_id: zone.id,
+            kind,
+            address,
+            tsig_key_name,
+            created_at: Utc::now(),
+        })
+        .await
+    }
+
+    /// List the NOTIFY targets of a zone in creation order.
+    pub async fn list(
+        caller: &Caller,
+        zone_name: &str,
+    ) -> Result<Vec<ZoneNotifyTarget>, ServiceError> {
+        caller.require_role(TokenRole::Viewer, "view NOTIFY targets")?;
+
+        let zone = ZoneService::lookup_by_name(zone_name).await?;
+        RepositoryService::list_zone_notify_targets_by_zone_id(zone.id).await
+    }
+
+    /// Remove one NOTIFY target of a zone by id. Removing the last `notify`
+    /// target returns the zone to the global secondary list.
+    pub async fn remove(
+        caller: &Caller,
+        zone_name: &str,
+        entry_id: i32,
+    ) -> Result<(), ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage NOTIFY targets")?;
+
+        let zone = ZoneService::lookup_by_name(zone_name).await?;
+
+        let entry = RepositoryService::get_zone_notify_target_by_id(entry_id)
+            .await?
+            .filter(|entry| entry.zone_id == zone.id)
+            .ok_or_else(|| ServiceError::notify_target_not_found(entry_id))?;
+
+        RepositoryService::delete_zone_notify_target(entry.id).await
+    }
+
+    /// The servers NOTIFY for `zone_name` goes to and whose serial is probed
+    /// for it. A name that is no stored zone, such as a catalog zone, gets the
+    /// global servers. This is the unchecked read the NOTIFY path uses.
+    pub async fn targets_for(zone_name: &str) -> Result<Vec<SecondaryEntry>, ServiceError> {
+        let global = config::get_bindizr_config().dns.secondary_servers();
+        let Some(zone) = ZoneService::find_by_name(zone_name).await? else {
+            return Ok(global);
+        };
+
+        let own = RepositoryService::list_zone_notify_targets_by_zone_id(zone.id).await?;
+        Ok(merge_targets(global, &own))
+    }
+}
+
+/// A zone's `notify` targets, or `global` when it has none, followed by its
+/// `also-notify` targets. An address already listed is not listed again.
+pub(crate) fn merge_targets(
+    global: Vec<SecondaryEntry>,
+    own: &[ZoneNotifyTarget],
+) -> Vec<SecondaryEntry> {
+    let of_kind = |kind: NotifyTargetKind| {
+        own.iter()
+            .filter(move |entry| entry.kind == kind)
+            .map(|entry| SecondaryEntry {
+                address: entry.address.clone(),
+                key: entry.tsig_key_name.clone(),
+                deny: false,
+            })
+    };
+
+    let mut targets: Vec<SecondaryEntry> = of_kind(NotifyTargetKind::Notify).collect();
+    if targets.is_empty() {
+        targets = global;
+    }
+    for entry in of_kind(NotifyTargetKind::AlsoNotify) {
+        if !targets.iter().any(|target| target.address == entry.address) {
+            targets.push(entry);
+        }
+    }
+    targets
+}
+
+/// Normalize a NOTIFY target address: an IP address or socket address in
+/// canonical form, or a lowercase `host[:port]`. Prefixes and deny entries
+/// only make sense in a transfer ACL, so they are refused.
+pub(crate) fn normalize_notify_address(value: &str) -> Result<String, ServiceError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(ServiceError::invalid_input(
+            "NOTIFY target address cannot be empty",
+        ));
+    }
+    let invalid =
+        || ServiceError::invalid_input(format!("'{}' is not an IP address or host[:port]", value));
+
+    if let Ok(ip) = IpAddr::from_str(value) {
+        return Ok(ip.to_string());
+    }
+    if let Ok(addr) = SocketAddr::from_str(value) {
+        return match addr.port() {
+            0 => Err(invalid()),
+            _ => Ok(addr.to_string()),
+        };
+    }
+
+    let (host, port) = match value.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (value, None),
+    };
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    let valid_host = !host.is_empty()
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        });
+    if !valid_host {
+        return Err(invalid());
+    }
+
+    match port {
+        Some(port) => match port.parse::<u16>() {
+            Ok(port) if port != 0 => Ok(format!("{}:{}", host, port)),
+            _ => Err(invalid()),
+        },
+        None => Ok(host),
+    }
+}
+
+#[cfg(test)]
+mod tests;
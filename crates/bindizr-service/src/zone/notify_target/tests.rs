@@ -0,0 +1,93 @@
+use super::*;
+
+fn target(kind: NotifyTargetKind, address: &str, key: Option<&str>) -> ZoneNotifyTarget {
+    ZoneNotifyTarget {
+        id: 0,
+        zone_id: 1,
+        kind,
+        address: address.to_string(),
+        tsig_key_name: key.map(str::to_string),
+        created_at: Utc::now(),
+    }
+}
+
+fn global() -> Vec<SecondaryEntry> {
+    vec![
+        SecondaryEntry::parse("192.0.2.10:53 key xfr-key").unwrap(),
+        SecondaryEntry::parse("192.0.2.20").unwrap(),
+    ]
+}
+
+fn addresses(targets: &[SecondaryEntry]) -> Vec<&str> {
+    targets
+        .iter()
+        .map(|target| target.address.as_str())
+        .collect()
+}
+
+#[test]
+fn merge_targets_falls_back_to_the_global_servers() {
+    assert_eq!(merge_targets(global(), &[]), global());
+
+    let also = [target(
+        NotifyTargetKind::AlsoNotify,
+        "198.51.100.5",
+        Some("pool-key"),
+    )];
+    let targets = merge_targets(global(), &also);
+    assert_eq!(
+        addresses(&targets),
+        ["192.0.2.10:53", "192.0.2.20", "198.51.100.5"]
+    );
+    assert_eq!(targets[2].key.as_deref(), Some("pool-key"));
+}
+
+#[test]
+fn merge_targets_replaces_the_global_servers_with_notify_targets() {
+    let own = [
+        target(NotifyTargetKind::AlsoNotify, "198.51.100.5", None),
+        target(NotifyTargetKind::Notify, "203.0.113.1:5353", None),
+        // Listed once even when it also appears as also-notify.
+        target(NotifyTargetKind::AlsoNotify, "203.0.113.1:5353", None),
+    ];
+    assert_eq!(
+        addresses(&merge_targets(global(), &own)),
+        ["203.0.113.1:5353", "198.51.100.5"]
+    );
+}
+
+#[test]
+fn normalize_notify_address_canonicalizes_hosts_and_addresses() {
+    assert_eq!(
+        normalize_notify_address(" 192.0.2.10 ").unwrap(),
+        "192.0.2.10"
+    );
+    assert_eq!(
+        normalize_notify_address("[2001:DB8::1]:5353").unwrap(),
+        "[2001:db8::1]:5353"
+    );
+    assert_eq!(
+        normalize_notify_address("NS2.Example.COM.:53").unwrap(),
+        "ns2.example.com:53"
+    );
+    assert_eq!(normalize_notify_address("secondary").unwrap(), "secondary");
+}
+
+#[test]
+fn normalize_notify_address_rejects_prefixes_and_malformed_entries() {
+    for value in [
+        "",
+        "198.51.100.0/24",
+        "!192.0.2.10",
+        "192.0.2.10:0",
+        "192.0.2.10:99999",
+        "ns2.example.com:http",
+        "ns2 key xfr-key",
+        "bad..example",
+    ] {
+        assert!(
+            normalize_notify_address(value).is_err(),
+            "{value:?} should be rejected"
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! History retention: trimming a zone's old snapshots and the change journal
+//! rows behind them once they fall outside the configured limits.
+//!
+//! Pruning keeps every serial from a cutoff onward together with all changes
+//! after it, so each retained serial stays diffable, restorable, and usable as
+//! an IXFR base. Serials below the cutoff lose their snapshot; an IXFR from one
+//! of them falls back to AXFR.
+
+use bindizr_core::{config, dns::serial};
+use bindizr_db::repository::LockLevel;
+use chrono::{Duration, Utc};
+
+use super::ZoneService;
+use crate::{
+    RepositoryTx,
+    authorization::{Caller, TokenRole},
+    error::ServiceError,
+    log_info,
+    model::zone::Zone,
+    repository::RepositoryService,
+    types::{PruneHistoryResponse, ZonePruneResult},
+};
+
+/// The retention limits one prune run applies; `None` sets no limit.
+#[derive(Debug, Clone, Copy)]
+struct RetentionLimits {
+    keep_serials: Option<u32>,
+    keep_days: Option<u32>,
+}
+
+impl RetentionLimits {
+    /// The `[history]` limits, where 0 means unlimited.
+    fn from_config() -> Self {
+        let history = &config::get_bindizr_config().history;
+        RetentionLimits {
+            keep_serials: (history.keep_serials > 0).then_some(history.keep_serials),
+            keep_days: (history.keep_days > 0).then_some(history.keep_days),
+        }
+    }
+
+    fn is_unlimited(&self) -> bool {
+        self.keep_serials.is_none() && self.keep_days.is_none()
+    }
+}
+
+/// Combine each limit's oldest kept serial into the prune cutoff. A serial is
+/// kept while any limit still wants it, so the cutoff is the bound furthest
+/// behind the current serial in RFC 1982 order; a `None` bound (that limit
+/// keeps everything) or no limits at all prunes nothing. The current serial is
+/// never pruned.
+pub(crate) fn prune_cutoff(current_serial: i32, bounds: &[Option<i32>]) -> Option<i32> {
+    if bounds.is_empty() {
+        return None;
+    }
+    let current = serial::to_wire(current_serial);
+    // A bound that is not behind the current serial keeps everything up to it.
+    let behind = |stored: i32| {
+        let bound = serial::to_wire(stored);
+        if serial::is_newer(current, bound) {
+            serial::distance(bound, current)
+        } else {
+            0
+        }
+    };
+    bounds.iter().try_fold(current_serial, |cutoff, bound| {
+        let bound = (*bound)?;
+        Some(if behind(bound) > behind(cutoff) {
+            bound
+        } else {
+            cutoff
+        })
+    })
+}
+
+/// Prune one zone under the given limits. The caller holds the zone row lock,
+/// which also covers the snapshot and journal rows counted and deleted here.
+async fn prune_zone_tx(
+    tx: &mut RepositoryTx<'_>,
+    zone: &Zone,
+    limits: RetentionLimits,
+    dry_run: bool,
+) -> Result<ZonePruneResult, ServiceError> {
+    let mut bounds = Vec::with_capacity(2);
+    if let Some(keep_serials) = limits.keep_serials {
+        bounds.push(
+            RepositoryService::find_zone_snapshot_serial_by_rank_tx(
+                tx,
+                zone.id,
+                zone.serial,
+                u64::from(keep_serials) - 1,
+            )
+            .await?,
+        );
+    }
+    if let Some(keep_days) = limits.keep_days {
+        // A window reaching past the earliest representable time keeps everything.
+        let bound = match Utc::now().checked_sub_signed(Duration::days(i64::from(keep_days))) {
+            Some(since) => Some(
+                RepositoryService::find_oldest_zone_snapshot_serial_since_tx(
+                    tx,
+                    zone.id,
+                    zone.serial,
+                    since,
+                )
+                .await?
+                .unwrap_or(zone.serial),
+            ),
+            None => None,
+        };
+        bounds.push(bound);
+    }
+
+    let Some(cutoff) = prune_cutoff(zone.serial, &bounds) else {
+        return Ok(ZonePruneResult {
+            zone_name: zone.name.to_string(),
+            oldest_retained_serial: None,
+            snapshots_removed: 0,
+            changes_removed: 0,
+        });
+    };
+
+    // A retained snapshot at the cutoff needs only the changes after it.
+    let (snapshots_removed, changes_removed) = if dry_run {
+        (
+            RepositoryService::count_zone_snapshots_before_serial_tx(
+                tx,
+                zone.id,
+                zone.serial,
+                cutoff,
+            )
+            .await?,
+            RepositoryService::count_zone_changes_through_serial_tx(
+                tx,
+                zone.id,
+                zone.serial,
+                cutoff,
+            )
+            .await?,
+        )
+    } else {
+        (
+            RepositoryService::delete_zone_snapshots_before_serial_tx(
+                tx,
+                zone.id,
+                zone.serial,
+                cutoff,
+            )
+            .await?,
+            RepositoryService::delete_zone_changes_through_serial_tx(
+                tx,
+                zone.id,
+                zone.serial,
+                cutoff,
+            )
+            .await?,
+        )
+    };
+
+    Ok(ZonePruneResult {
+        zone_name: zone.name.to_string(),
+        oldest_retained_serial: Some(serial::to_wire(cutoff)),
+        snapshots_removed,
+        changes_removed,
+    })
+}
+
+/// Prune each zone in its own transaction so one zone's lock is never held
+/// while another is trimmed. A zone deleted in between is skipped.
+async fn prune_zones(
+    zone_ids: Vec<i32>,
+    limits: RetentionLimits,
+    dry_run: bool,
+) -> Result<PruneHistoryResponse, ServiceError> {
+    let mut zones = Vec::with_capacity(zone_ids.len());
+    for zone_id in zone_ids {
+        let mut tx = RepositoryService::begin_tx("Failed to prune zone history").await?;
+        let result = async {
+            match RepositoryService::get_zone_by_id_tx(&mut tx, zone_id, LockLevel::Exclusive)
+                .await?
+            {
+                Some(zone) => prune_zone_tx(&mut tx, &zone, limits, dry_run)
+                    .await
+                    .map(Some),
+                None => Ok(None),
+            }
+        }
+        .await;
+        let Some(pruned) =
+            RepositoryService::finish_tx(tx, result, "Failed to prune zone history").await?
+        else {
+            continue;
+        };
+
+        if !dry_run && (pruned.snapshots_removed > 0 || pruned.changes_removed > 0) {
+            log_info!(
+                "event=zone_history_pruned zone={} oldest_retained_serial={} snapshots={} changes={}",
+                pruned.zone_name,
+                pruned.oldest_retained_serial.unwrap_or_default(),
+                pruned.snapshots_removed,
+                pruned.changes_removed
+            );
+        }
+        zones.push(pruned);
+    }
+
+    Ok(PruneHistoryResponse { dry_run, zones })
+}
+
+impl ZoneService {
+    /// Prune the history of one zone, or of every zone when `zone_name` is
+    /// `None`. Explicit `keep_serials`/`keep_days` replace the configured
+    /// `[history]` limits as a pair; with neither given the configured limits
+    /// apply, and at least one limit must be set.
+    pub async fn prune_history(
+        caller: &Caller,
+        zone_name: Option<&str>,
+        keep_serials: Option<u32>,
+        keep_days: Option<u32>,
+        dry_run: bool,
+    ) -> Result<PruneHistoryResponse, ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "prune zone history")?;
+
+        if keep_serials == Some(0) {
+            return Err(ServiceError::invalid_input(
+                "keep_serials must be at least 1",
+            ));
+        }
+        if keep_days == Some(0) {
+            return Err(ServiceError::invalid_input("keep_days must be at least 1"));
+        }
+        let limits = if keep_serials.is_some() || keep_days.is_some() {
+            RetentionLimits {
+                keep_serials,
+                keep_days,
+            }
+        } else {
+            RetentionLimits::from_config()
+        };
+        if limits.is_unlimited() {
+            return Err(ServiceError::invalid_input(
+                "no history retention is configured; set [history] keep_serials or keep_days, or pass a limit",
+            ));
+        }
+
+        let zone_ids = match zone_name {
+            Some(zone_name) => vec![Self::get_by_name(caller, zone_name).await?.id],
+            None => RepositoryService::list_zones()
+                .await?
+                .iter()
+                .map(|zone| zone.id)
+                .collect(),
+        };
+
+        prune_zones(zone_ids, limits, dry_run).await
+    }
+
+    /// One pass of the background compaction: prune every zone under the
+    /// configured limits. Returns `None` without touching the database when
+    /// no limit is configured.
+    pub async fn compact_history() -> Result<Option<PruneHistoryResponse>, ServiceError> {
+        let limits = RetentionLimits::from_config();
+        if limits.is_unlimited() {
+            return Ok(None);
+        }
+        let zone_ids = RepositoryService::list_zones()
+            .await?
+            .iter()
+            .map(|zone| zone.id)
+            .collect();
+        prune_zones(zone_ids, limits, false).await.map(Some)
+    }
+}
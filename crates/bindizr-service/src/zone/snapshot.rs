@@ -3,11 +3,13 @@ use chrono::Utc;
 use super::ZoneService;
 use crate::{
     RepositoryTx,
+    audit::{self, AuditAction, AuditService},
     error::ServiceError,
     log_error,
     metrics::metrics,
     model::{zone::Zone, zone_snapshot::ZoneSnapshot},
     repository::RepositoryService,
+    webhook::{WebhookEvent, WebhookService},
 };
 
 impl ZoneService {
@@ -25,14 +27,18 @@ impl ZoneService {
                 ServiceError::internal("Failed to update zone serial".to_string())
             })?;
 
-        Self::save_snapshot_tx(tx, zone, new_serial).await
+        Self::save_snapshot_tx(tx, zone, new_serial, Some(zone.serial)).await
     }
 
-    /// Save a snapshot of the zone's SOA data for historical tracking.
+    /// Save a snapshot of the zone's SOA data for historical tracking, record
+    /// the change in the audit log, and queue the matching webhook event:
+    /// `zone.created` when there is no `previous_serial`, `zone.updated`
+    /// otherwise.
     pub(crate) async fn save_snapshot_tx(
         tx: &mut RepositoryTx<'_>,
         zone: &Zone,
         serial: i32,
+        previous_serial: Option<i32>,
     ) -> Result<(), ServiceError> {
         RepositoryService::upsert_zone_snapshot_tx(
             tx,
@@ -50,6 +56,7 @@ impl ZoneService {
                 retry: zone.retry,
                 expire: zone.expire,
                 minimum_ttl: zone.minimum_ttl,
+                attribution: audit::current().attribution(),
                 created_at: Utc::now(),
             },
         )
@@ -64,7 +71,12 @@ impl ZoneService {
         // is acceptable for a monitoring counter.
         metrics().zone_serial_bumps_total.inc();
 
-        Ok(())
+        let (action, event) = match previous_serial {
+            Some(_) => (AuditAction::Updated, WebhookEvent::Updated),
+            None => (AuditAction::Created, WebhookEvent::Created),
+        };
+        AuditService::record_tx(tx, zone, serial, action).await?;
+        WebhookService::enqueue_tx(tx, zone, event, previous_serial, Some(serial)).await
     }
 
     /// Fetch the SOA snapshot recorded for a zone at the given serial, if any.
@@ -1,8 +1,11 @@
 use bindizr_core::dns::name::{OwnerName, ZoneName};
 use chrono::Utc;
 
-use super::apex_ns_rrset_ttl;
-use crate::model::{record::RecordType, zone::Zone};
+use super::{apex_ns_rrset_ttl, prune::prune_cutoff};
+use crate::model::{
+    record::RecordType,
+    zone::{SerialPolicy, Zone},
+};
 
 fn test_zone() -> Zone {
     Zone {
@@ -12,6 +15,7 @@ fn test_zone() -> Zone {
         admin_email: "hostmaster@example.com".to_string(),
         ttl: 3600,
         serial: 1,
+        serial_policy: SerialPolicy::Increment,
         refresh: 7200,
         retry: 3600,
         expire: 604800,
@@ -57,3 +61,18 @@ fn apex_ns_rrset_ttl_falls_back_to_the_zone_ttl() {
         zone.ttl
     );
 }
+
+#[test]
+fn prune_cutoff_keeps_what_any_limit_keeps() {
+    // No limits, or a limit that keeps everything, prunes nothing.
+    assert_eq!(prune_cutoff(10, &[]), None);
+    assert_eq!(prune_cutoff(10, &[Some(7), None]), None);
+    // Limits combine as a union: the bound furthest behind wins.
+    assert_eq!(prune_cutoff(10, &[Some(7)]), Some(7));
+    assert_eq!(prune_cutoff(10, &[Some(7), Some(4)]), Some(4));
+    // The current serial is always retained.
+    assert_eq!(prune_cutoff(10, &[Some(12)]), Some(10));
+    // Serials order by RFC 1982, so bounds from before a wrap sit behind it.
+    assert_eq!(prune_cutoff(3, &[Some(-2), Some(1)]), Some(-2));
+    assert_eq!(prune_cutoff(3, &[Some(1)]), Some(1));
+}
@@ -8,7 +8,10 @@ use chrono::Utc;
 use crate::{
     authorization::Caller,
     error::ServiceError,
-    model::{api_token::ApiToken, zone_token_policy::ZoneTokenPolicy},
+    model::{
+        api_token::{ApiToken, TokenRole},
+        zone_token_policy::ZoneTokenPolicy,
+    },
     policy_pattern::{normalize_pattern, normalize_types},
     repository::RepositoryService,
     token::normalize_token_name,
@@ -27,8 +30,8 @@ pub struct ZoneTokenPolicyService;
 
 impl ZoneTokenPolicyService {
     /// Grant token `token_name` record rights in `zone_name`, optionally
-    /// restricted to a record name pattern and/or record types. Global tokens
-    /// are rejected: they already cover every zone and never carry policies.
+    /// restricted to a record name pattern and/or record types. Only scoped
+    /// tokens take policies: every other role already spans every zone.
     pub async fn add(
         caller: &Caller,
         zone_name: &str,
@@ -41,10 +44,10 @@ impl ZoneTokenPolicyService {
         let zone = ZoneService::lookup_by_name(zone_name).await?;
         let token = find_token(token_name).await?;
 
-        if token.is_global {
+        if token.role != TokenRole::Scoped {
             return Err(ServiceError::invalid_input(format!(
-                "API token '{}' is global and already covers every zone; policies cannot be added to it",
-                token.name
+                "API token '{}' has the '{}' role and already covers every zone; policies cannot be added to it",
+                token.name, token.role
             )));
         }
 
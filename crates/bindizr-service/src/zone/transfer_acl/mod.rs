@@ -0,0 +1,170 @@
+//! Per-zone transfer ACLs: the entries that, once a zone has any, replace the
+//! global `dns.secondary_addrs` list for transfers of that zone.
+
+use std::{collections::HashMap, net::IpAddr, str::FromStr};
+
+use chrono::Utc;
+use ipnet::IpNet;
+
+use crate::{
+    authorization::{Caller, TokenRole},
+    catalog::{self, CatalogService},
+    error::ServiceError,
+    log_warn,
+    model::zone_transfer_acl::{TransferAclAction, ZoneTransferAcl},
+    repository::RepositoryService,
+    tsig_key::TsigKeyService,
+    zone::ZoneService,
+};
+
+/// Adds, lists, and removes the transfer ACL entries of a zone.
+pub struct ZoneTransferAclService;
+
+impl ZoneTransferAclService {
+    /// Add an entry to the ACL of `zone_name`. `action` defaults to allow; a
+    /// `tsig_key` (allow entries only) must name an existing TSIG key.
+    pub async fn add(
+        caller: &Caller,
+        zone_name: &str,
+        action: Option<&str>,
+        address: &str,
+        tsig_key: Option<&str>,
+    ) -> Result<ZoneTransferAcl, ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage transfer ACLs")?;
+
+        let zone = ZoneService::lookup_by_name(zone_name).await?;
+
+        let action = match action.map(str::trim).filter(|action| !action.is_empty()) {
+            Some(action) => {
+                TransferAclAction::from_str(action).map_err(ServiceError::invalid_input)?
+            }
+            None => TransferAclAction::Allow,
+        };
+        let address = normalize_acl_address(address)?;
+
+        let tsig_key_name = match tsig_key.map(str::trim).filter(|key| !key.is_empty()) {
+            Some(_) if action == TransferAclAction::Deny => {
+                return Err(ServiceError::invalid_input(
+                    "a deny entry cannot carry a TSIG key",
+                ));
+            }
+            Some(key) => Some(TsigKeyService::lookup_by_name(key).await?.name),
+            None => None,
+        };
+
+        let entry = RepositoryService::create_zone_transfer_acl(ZoneTransferAcl {
+            id: 0,
+            zone_id: zone.id,
+            action,
+            address,
+            tsig_key_name,
+            created_at: Utc::now(),
+        })
+        .await?;
+
+        notify_catalogs_of(zone.id).await;
+        Ok(entry)
+    }
+
+    /// List the ACL entries of a zone in creation order.
+    pub async fn list(
+        caller: &Caller,
+        zone_name: &str,
+    ) -> Result<Vec<ZoneTransferAcl>, ServiceError> {
+        caller.require_role(TokenRole::Viewer, "view transfer ACLs")?;
+
+        let zone = ZoneService::lookup_by_name(zone_name).await?;
+        RepositoryService::list_zone_transfer_acls_by_zone_id(zone.id).await
+    }
+
+    /// Remove one entry of a zone by id. Removing the last entry returns the
+    /// zone to the global ACL.
+    pub async fn remove(
+        caller: &Caller,
+        zone_name: &str,
+        entry_id: i32,
+    ) -> Result<(), ServiceError> {
+        caller.require_role(TokenRole::ZoneAdmin, "manage transfer ACLs")?;
+
+        let zone = ZoneService::lookup_by_name(zone_name).await?;
+
+        let entry = RepositoryService::get_zone_transfer_acl_by_id(entry_id)
+            .await?
+            .filter(|entry| entry.zone_id == zone.id)
+            .ok_or_else(|| ServiceError::transfer_acl_not_found(entry_id))?;
+
+        RepositoryService::delete_zone_transfer_acl(entry.id).await?;
+
+        notify_catalogs_of(zone.id).await;
+        Ok(())
+    }
+
+    /// Every zone's own ACL entries, keyed by zone name. Zones governed by
+    /// the global ACL are absent. This is the unchecked read the transfer
+    /// path uses.
+    pub async fn list_by_zone_name() -> Result<HashMap<String, Vec<ZoneTransferAcl>>, ServiceError>
+    {
+        let entries = RepositoryService::list_zone_transfer_acls().await?;
+        if entries.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let zone_names: HashMap<i32, String> = ZoneService::list()
+            .await?
+            .into_iter()
+            .map(|zone| (zone.id, zone.name.to_string()))
+            .collect();
+
+        let mut by_zone: HashMap<String, Vec<ZoneTransferAcl>> = HashMap::new();
+        for entry in entries {
+            if let Some(zone_name) = zone_names.get(&entry.zone_id) {
+                by_zone.entry(zone_name.clone()).or_default().push(entry);
+            }
+        }
+        Ok(by_zone)
+    }
+}
+
+/// Which zones a secondary may transfer is also what the catalogs list for
+/// it, so an ACL change is a change to every catalog listing the zone.
+async fn notify_catalogs_of(zone_id: i32) {
+    match CatalogService::catalogs_of_zone(zone_id).await {
+        Ok(catalogs) => catalog::notify_catalogs(catalogs).await,
+        Err(e) => log_warn!("Failed to look up the catalogs of zone {}: {}", zone_id, e),
+    }
+}
+
+/// Normalize an ACL address: a single IP address, or a CIDR prefix without
+/// host bits set. Hostnames are not accepted here; they belong in the global
+/// list, which resolves them at transfer time.
+pub(crate) fn normalize_acl_address(value: &str) -> Result<String, ServiceError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(ServiceError::invalid_input(
+            "transfer ACL address cannot be empty",
+        ));
+    }
+
+    if value.contains('/') {
+        let network = IpNet::from_str(value).map_err(|_| {
+            ServiceError::invalid_input(format!("'{}' is not a valid CIDR prefix", value))
+        })?;
+        if network.trunc() != network {
+            return Err(ServiceError::invalid_input(format!(
+                "'{}' has host bits set; did you mean '{}'?",
+                value,
+                network.trunc()
+            )));
+        }
+        return Ok(network.to_string());
+    }
+
+    IpAddr::from_str(value)
+        .map(|ip| ip.to_string())
+        .map_err(|_| {
+            ServiceError::invalid_input(format!("'{}' is not an IP address or CIDR prefix", value))
+        })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn normalize_acl_address_canonicalizes_addresses_and_prefixes() {
+    assert_eq!(normalize_acl_address(" 192.0.2.10 ").unwrap(), "192.0.2.10");
+    assert_eq!(normalize_acl_address("2001:DB8::1").unwrap(), "2001:db8::1");
+    assert_eq!(
+        normalize_acl_address("198.51.100.0/24").unwrap(),
+        "198.51.100.0/24"
+    );
+    assert_eq!(
+        normalize_acl_address("2001:db8:0::/48").unwrap(),
+        "2001:db8::/48"
+    );
+}
+
+#[test]
+fn normalize_acl_address_rejects_hosts_and_malformed_prefixes() {
+    for value in [
+        "",
+        "secondary.example",
+        "192.0.2.10:53",
+        "192.0.2.0/33",
+        "192.0.2.10/24",
+    ] {
+        assert!(
+            normalize_acl_address(value).is_err(),
+            "{value:?} should be rejected"
+        );
+    }
+}
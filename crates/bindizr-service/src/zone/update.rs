@@ -1,15 +1,19 @@
+use std::collections::BTreeMap;
+
 use bindizr_core::dns::{
     CATALOG_ZONE_NAME,
     name::{OwnerName, ZoneName},
+    serial,
 };
 use bindizr_db::repository::LockLevel;
 
 use super::{ZoneService, apex_ns_rrset_ttl};
 use crate::{
-    authorization::Caller,
+    authorization::{Caller, TokenRole},
     error::{ErrorCode, ServiceError},
     log_error, log_info, log_warn,
     model::{zone::Zone, zone_change::ZoneChange},
+    precondition::Precondition,
     record::RecordService,
     repository::RepositoryService,
     serial::generate_serial,
@@ -56,19 +60,24 @@ impl ZoneService {
         caller: &Caller,
         zone_name: &str,
         request: &CreateZoneRequest,
+        precondition: &Precondition,
     ) -> Result<Zone, ServiceError> {
-        caller.require_global("update zones")?;
+        caller.require_role(TokenRole::ZoneAdmin, "update zones")?;
         reject_serial(request.serial)?;
-        Self::update_locked(zone_name, |_existing| CreateZoneRequest {
+        reject_template(request)?;
+        Self::update_locked(zone_name, precondition, |_existing| CreateZoneRequest {
             name: request.name.clone(),
             primary_ns: request.primary_ns.clone(),
             admin_email: request.admin_email.clone(),
             ttl: request.ttl,
             serial: None,
+            serial_policy: request.serial_policy.clone(),
             refresh: request.refresh,
             retry: request.retry,
             expire: request.expire,
             minimum_ttl: request.minimum_ttl,
+            template: None,
+            template_variables: BTreeMap::new(),
         })
         .await
     }
@@ -79,10 +88,11 @@ impl ZoneService {
         caller: &Caller,
         zone_name: &str,
         patch: &UpdateZonePatch,
+        precondition: &Precondition,
     ) -> Result<Zone, ServiceError> {
-        caller.require_global("update zones")?;
+        caller.require_role(TokenRole::ZoneAdmin, "update zones")?;
         reject_serial(patch.serial)?;
-        Self::update_locked(zone_name, |existing| CreateZoneRequest {
+        Self::update_locked(zone_name, precondition, |existing| CreateZoneRequest {
             name: patch
                 .new_name
                 .clone()
@@ -97,11 +107,14 @@ impl ZoneService {
                 .unwrap_or_else(|| existing.admin_email.clone()),
             ttl: patch.ttl.unwrap_or(existing.ttl),
             serial: None,
+            serial_policy: patch.serial_policy.clone(),
             // Omitted timers fall back to the existing zone in resolve_soa_timers.
             refresh: patch.refresh,
             retry: patch.retry,
             expire: patch.expire,
             minimum_ttl: patch.minimum_ttl,
+            template: None,
+            template_variables: BTreeMap::new(),
         })
         .await
     }
@@ -110,6 +123,7 @@ impl ZoneService {
     /// bump the serial and record SOA/NS changes for IXFR.
     async fn update_locked(
         zone_name: &str,
+        precondition: &Precondition,
         build: impl FnOnce(&Zone) -> CreateZoneRequest,
     ) -> Result<Zone, ServiceError> {
         let mut tx = RepositoryService::begin_tx("Failed to update zone").await?;
@@ -119,6 +133,7 @@ impl ZoneService {
             // concurrent record mutations and nsupdate on the same zone.
             let existing_zone =
                 ZoneService::get_by_name_tx(&mut tx, zone_name, LockLevel::Exclusive).await?;
+            precondition.check_zone(&existing_zone)?;
             let zone_id = existing_zone.id;
 
             // Merge against the locked row, then validate.
@@ -156,7 +171,11 @@ impl ZoneService {
                 }
             }
 
-            let new_serial = generate_serial(Some(existing_zone.serial))?;
+            // A new policy applies from this update's serial onward.
+            let serial_policy = validated
+                .serial_policy
+                .unwrap_or(existing_zone.serial_policy);
+            let new_serial = generate_serial(serial_policy, Some(existing_zone.serial));
 
             let updated_zone = RepositoryService::update_zone_tx(
                 &mut tx,
@@ -167,6 +186,7 @@ impl ZoneService {
                     admin_email: validated.admin_email.clone(),
                     ttl: validated.ttl,
                     serial: new_serial,
+                    serial_policy,
                     refresh: timers.refresh,
                     retry: timers.retry,
                     expire: timers.expire,
@@ -233,7 +253,13 @@ impl ZoneService {
                     ServiceError::internal("Failed to create zone change".to_string())
                 })?;
 
-            ZoneService::save_snapshot_tx(&mut tx, &updated_zone, new_serial).await?;
+            ZoneService::save_snapshot_tx(
+                &mut tx,
+                &updated_zone,
+                new_serial,
+                Some(existing_zone.serial),
+            )
+            .await?;
 
             Ok(AppliedZoneUpdate {
                 zone: updated_zone,
@@ -253,7 +279,7 @@ impl ZoneService {
             "event=zone_update zone={} previous_name={} new_serial={} zone_id={}",
             updated_zone.name,
             zone_name,
-            new_serial,
+            serial::to_wire(new_serial),
             updated_zone.id
         );
 
@@ -279,7 +305,7 @@ impl ZoneService {
 }
 
 /// The serial is a system-managed version counter and cannot be set on update.
-fn reject_serial(serial: Option<i32>) -> Result<(), ServiceError> {
+fn reject_serial(serial: Option<u32>) -> Result<(), ServiceError> {
     if serial.is_some() {
         return Err(ServiceError::invalid_input(
             "serial is managed automatically and cannot be set on update",
@@ -287,3 +313,13 @@ fn reject_serial(serial: Option<i32>) -> Result<(), ServiceError> {
     }
     Ok(())
 }
+
+/// A template seeds a zone's records once, when the zone is created.
+fn reject_template(request: &CreateZoneRequest) -> Result<(), ServiceError> {
+    if request.template.is_some() || !request.template_variables.is_empty() {
+        return Err(ServiceError::invalid_input(
+            "template can only be set when the zone is created",
+        ));
+    }
+    Ok(())
+}
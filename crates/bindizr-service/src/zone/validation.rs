@@ -3,7 +3,7 @@ use bindizr_core::dns::{
     record::SoaMailbox,
 };
 
-use crate::{error::ServiceError, types::CreateZoneRequest};
+use crate::{error::ServiceError, model::zone::SerialPolicy, types::CreateZoneRequest};
 
 const MAX_EMAIL_LEN: usize = 254;
 const MAX_EMAIL_LOCAL_LEN: usize = 64;
@@ -15,6 +15,8 @@ pub(super) struct ValidatedCreateZoneRequest {
     pub(crate) primary_ns: String,
     pub(crate) admin_email: String,
     pub(crate) ttl: i32,
+    /// `None` when the request leaves the policy unchanged (or defaulted).
+    pub(crate) serial_policy: Option<SerialPolicy>,
 }
 
 pub(super) fn validate_create_zone_request(
@@ -27,11 +29,18 @@ pub(super) fn validate_create_zone_request(
 
     validate_soa_wire_safety(&admin_email)?;
 
+    let serial_policy = request
+        .serial_policy
+        .as_deref()
+        .map(|policy| policy.parse().map_err(ServiceError::invalid_zone))
+        .transpose()?;
+
     Ok(ValidatedCreateZoneRequest {
         name: zone_name,
         primary_ns,
         admin_email,
         ttl,
+        serial_policy,
     })
 }
 
@@ -0,0 +1,192 @@
+use axum::{
+    Json, Router,
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use bindizr_service::{
+    catalog::CatalogService,
+    types::{
+        CatalogListResponse, CatalogResponse, CreateCatalogRequest, ErrorResponse,
+        GetCatalogResponse, GetZoneCatalogPropertiesResponse, MessageResponse,
+        SetZoneCatalogPropertiesRequest, ZoneCatalogPropertiesResponse,
+    },
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::{
+    RequestCaller, error::ApiError, middleware::body_parser::JsonBody, token_policy::ZoneNameParam,
+};
+
+/// Route group for catalog zone endpoints.
+pub(crate) struct CatalogApi;
+
+impl CatalogApi {
+    /// Build the router for catalog zone endpoints.
+    pub(crate) async fn routes() -> Router {
+        Router::new()
+            .route("/catalogs", routing::get(get_catalogs))
+            .route("/catalogs", routing::post(create_catalog))
+            .route("/catalogs/{name}", routing::delete(delete_catalog))
+            .route(
+                "/zones/{name}/catalog",
+                routing::get(get_zone_catalog_properties),
+            )
+            .route(
+                "/zones/{name}/catalog",
+                routing::put(set_zone_catalog_properties),
+            )
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CatalogNameParam {
+    pub(crate) name: String,
+}
+
+#[utoipa::path(
+        get,
+        path = "/catalogs",
+        tag = "Catalog",
+        summary = "List named catalogs",
+        description = "Lists the named catalog zones. The default catalog, `catalog.bind`, always exists and is not listed.",
+        responses(
+            (status = 200, description = "All named catalogs", body = CatalogListResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the viewer role is required", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// List the named catalogs.
+pub(crate) async fn get_catalogs(
+    RequestCaller(caller): RequestCaller,
+) -> Result<Response, ApiError> {
+    let catalogs = CatalogService::list(&caller).await?;
+    let catalogs: Vec<GetCatalogResponse> = catalogs
+        .iter()
+        .map(GetCatalogResponse::from_catalog)
+        .collect();
+    let json_body = json!({ "catalogs": catalogs });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/catalogs",
+        tag = "Catalog",
+        summary = "Create a named catalog",
+        description = "Creates a catalog zone served over AXFR/IXFR under its name, next to `catalog.bind`. Assign zones to it with `PUT /zones/{name}/catalog`, and point a secondary pool at it to have that pool serve only those zones.",
+        request_body = CreateCatalogRequest,
+        responses(
+            (status = 201, description = "Catalog created successfully", body = CatalogResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 409, description = "A catalog or zone with the same name already exists", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Create a named catalog.
+pub(crate) async fn create_catalog(
+    RequestCaller(caller): RequestCaller,
+    JsonBody(body): JsonBody<CreateCatalogRequest>,
+) -> Result<Response, ApiError> {
+    let catalog = CatalogService::create(&caller, &body.name, body.description.as_deref()).await?;
+    let json_body = json!({ "catalog": GetCatalogResponse::from_catalog(&catalog) });
+    Ok((StatusCode::CREATED, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        delete,
+        path = "/catalogs/{name}",
+        tag = "Catalog",
+        summary = "Delete a named catalog",
+        description = "Deletes a named catalog. Refused while any zone is listed in it or names it as `coo`.",
+        params(
+            ("name" = String, Path, description = "The name of the catalog.")
+        ),
+        responses(
+            (status = 200, description = "Catalog deleted", body = MessageResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 404, description = "Catalog not found", body = ErrorResponse),
+            (status = 409, description = "The catalog still lists zones", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Delete a named catalog.
+pub(crate) async fn delete_catalog(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<CatalogNameParam>,
+) -> Result<Response, ApiError> {
+    CatalogService::delete(&caller, &params.name).await?;
+    let json_body = json!({ "message": "Catalog deleted successfully" });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        get,
+        path = "/zones/{name}/catalog",
+        tag = "Catalog",
+        summary = "Get a zone's catalog properties",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone.")
+        ),
+        responses(
+            (status = 200, description = "The catalog listing the zone and its member properties", body = ZoneCatalogPropertiesResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the viewer role is required", body = ErrorResponse),
+            (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Get a zone's catalog membership and properties.
+pub(crate) async fn get_zone_catalog_properties(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneNameParam>,
+) -> Result<Response, ApiError> {
+    let properties = CatalogService::get_zone(&caller, &params.name).await?;
+    let json_body = json!({
+        "catalog_properties":
+            GetZoneCatalogPropertiesResponse::from_properties(&params.name, &properties)
+    });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        put,
+        path = "/zones/{name}/catalog",
+        tag = "Catalog",
+        summary = "Set a zone's catalog properties",
+        description = "Replaces the zone's catalog membership and RFC 9432 member properties; omitted fields are cleared. To move a zone between catalogs, first set `coo` to the new catalog, which lists the zone in both, then set `catalog` to it once its consumers have taken the zone over. Every catalog the zone enters or leaves is notified.",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone.")
+        ),
+        request_body = SetZoneCatalogPropertiesRequest,
+        responses(
+            (status = 200, description = "Catalog properties replaced", body = ZoneCatalogPropertiesResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 404, description = "Zone or catalog not found", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Replace a zone's catalog membership and properties.
+pub(crate) async fn set_zone_catalog_properties(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneNameParam>,
+    JsonBody(body): JsonBody<SetZoneCatalogPropertiesRequest>,
+) -> Result<Response, ApiError> {
+    let properties =
+        CatalogService::set_zone(&caller, &params.name, body.into_properties()).await?;
+    let json_body = json!({
+        "catalog_properties":
+            GetZoneCatalogPropertiesResponse::from_properties(&params.name, &properties)
+    });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
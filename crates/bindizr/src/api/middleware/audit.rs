@@ -0,0 +1,63 @@
+use std::net::SocketAddr;
+
+use axum::{
+    extract::{ConnectInfo, Request},
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use bindizr_service::audit::{self, AuditContext, Transport};
+
+/// The name of the token the auth middleware accepted.
+#[derive(Clone)]
+pub(crate) struct AuthenticatedToken(pub(crate) String);
+
+pub(crate) const REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Attribute the request's changes to its token and peer. A client-sent
+/// `X-Request-Id` is kept when it is a plain token of at most 64 characters,
+/// otherwise one is generated; either way it is echoed on the response so
+/// callers can find their change in the audit log.
+pub(crate) async fn audit_context(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| valid_request_id(value))
+        .map(str::to_owned)
+        .unwrap_or_else(audit::new_request_id);
+
+    let context = AuditContext {
+        actor: request
+            .extensions()
+            .get::<AuthenticatedToken>()
+            .map_or_else(
+                || "anonymous".to_string(),
+                |token| format!("token:{}", token.0),
+            ),
+        tsig_key: None,
+        source_address: request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip()),
+        transport: if request.uri().path().starts_with("/external-dns") {
+            Transport::ExternalDns
+        } else {
+            Transport::Http
+        },
+        request_id: Some(request_id.clone()),
+    };
+
+    let mut response = audit::scope(context, next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID, value);
+    }
+    response
+}
+
+fn valid_request_id(value: &str) -> bool {
+    (1..=64).contains(&value.len())
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
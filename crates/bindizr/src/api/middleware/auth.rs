@@ -7,6 +7,7 @@ use axum::{
 use bindizr_core::log_debug;
 use bindizr_service::{authorization::Caller, error::ServiceError};
 
+use super::audit::AuthenticatedToken;
 use crate::api::error::ApiError;
 
 /// Validate the request's Bearer token, rejecting unauthorized requests.
@@ -33,8 +34,9 @@ pub(crate) async fn auth_middleware(
     let token = &auth_str[7..];
 
     match Caller::authenticate(token).await {
-        Ok(caller) => {
+        Ok((caller, token_name)) => {
             req.extensions_mut().insert(caller);
+            req.extensions_mut().insert(AuthenticatedToken(token_name));
             Ok(next.run(req).await)
         }
         Err(err) => {
@@ -4,14 +4,14 @@ use axum::{
     Json,
     extract::{FromRequest, Request},
 };
-use bindizr_core::log_debug;
+use bindizr_core::{dns::MAX_ZONE_CONTENT_BYTES, log_debug};
 use serde::de::DeserializeOwned;
 
 use crate::api::error::ApiError;
 
 /// Body cap for whole-zone-file / bulk uploads (import, bulk create) — above
 /// axum's 2 MiB default, but bounded to limit per-request memory.
-pub(crate) const MAX_UPLOAD_BODY_BYTES: usize = 32 * 1024 * 1024;
+pub(crate) const MAX_UPLOAD_BODY_BYTES: usize = MAX_ZONE_CONTENT_BYTES;
 
 /// JSON body extractor that maps rejections to a JSON [`ApiError`] response and
 /// records deserialization time at debug level (`event=json_decode`), so the
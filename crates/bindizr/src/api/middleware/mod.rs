@@ -1,3 +1,4 @@
+pub(crate) mod audit;
 pub(crate) mod auth;
 pub(crate) mod body_parser;
 pub(crate) mod metrics;
@@ -1,16 +1,24 @@
 //! HTTP API server: routing, middleware, and the zone/record/notify endpoints.
 
+pub(crate) mod catalog;
 pub(crate) mod error;
 pub(crate) mod external_dns;
 pub(crate) mod health;
 pub(crate) mod metrics;
 pub(crate) mod middleware;
 pub(crate) mod notify;
+pub(crate) mod notify_target;
 pub(crate) mod openapi;
+pub(crate) mod precondition;
 pub(crate) mod record;
 pub(crate) mod router;
+pub(crate) mod secondary;
+pub(crate) mod template;
+pub(crate) mod token;
 pub(crate) mod token_policy;
+pub(crate) mod transfer_acl;
 pub(crate) mod tsig_key;
+pub(crate) mod webhook;
 pub(crate) mod zone;
 
 use std::net::SocketAddr;
@@ -58,7 +66,11 @@ pub(crate) async fn initialize() -> Result<(), String> {
     log_info!("HTTP API server listening on http://{}", addr);
 
     tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, ApiRouter::routes().await).await {
+        // Connect info carries the peer address into change attribution.
+        let app = ApiRouter::routes()
+            .await
+            .into_make_service_with_connect_info::<SocketAddr>();
+        if let Err(e) = axum::serve(listener, app).await {
             log_error!("API server error: {:?}", e);
         }
     });
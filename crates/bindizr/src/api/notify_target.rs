@@ -0,0 +1,144 @@
+use axum::{
+    Json, Router,
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use bindizr_service::{
+    types::{
+        CreateZoneNotifyTargetRequest, ErrorResponse, GetZoneNotifyTargetResponse, MessageResponse,
+        ZoneNotifyTargetListResponse, ZoneNotifyTargetResponse,
+    },
+    zone::notify_target::ZoneNotifyTargetService,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::{
+    RequestCaller, error::ApiError, middleware::body_parser::JsonBody, token_policy::ZoneNameParam,
+};
+
+/// Route group for zone NOTIFY-target endpoints.
+pub(crate) struct NotifyTargetApi;
+
+impl NotifyTargetApi {
+    /// Build the router for zone NOTIFY-target endpoints.
+    pub(crate) async fn routes() -> Router {
+        Router::new()
+            .route(
+                "/zones/{name}/notify-targets",
+                routing::get(get_zone_notify_targets),
+            )
+            .route(
+                "/zones/{name}/notify-targets",
+                routing::post(create_zone_notify_target),
+            )
+            .route(
+                "/zones/{name}/notify-targets/{id}",
+                routing::delete(delete_zone_notify_target),
+            )
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ZoneNotifyTargetParam {
+    pub(crate) name: String,
+    pub(crate) id: i32,
+}
+
+#[utoipa::path(
+        get,
+        path = "/zones/{name}/notify-targets",
+        tag = "Notify",
+        summary = "List a zone's NOTIFY targets",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone.")
+        ),
+        responses(
+            (status = 200, description = "The zone's NOTIFY targets; empty when only the global secondary list applies", body = ZoneNotifyTargetListResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the viewer role is required", body = ErrorResponse),
+            (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// List a zone's NOTIFY targets.
+pub(crate) async fn get_zone_notify_targets(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneNameParam>,
+) -> Result<Response, ApiError> {
+    let entries = ZoneNotifyTargetService::list(&caller, &params.name).await?;
+    let entries: Vec<GetZoneNotifyTargetResponse> = entries
+        .iter()
+        .map(GetZoneNotifyTargetResponse::from_entry)
+        .collect();
+    let json_body = json!({ "notify_targets": entries });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/zones/{name}/notify-targets",
+        tag = "Notify",
+        summary = "Add a NOTIFY target to a zone",
+        description = "Once a zone has `notify` targets, its NOTIFYs and secondary status probes go to them instead of the servers in the global `dns.secondary_addrs` list. `also-notify` targets are notified and probed on top of whichever list applies. The address is `host[:port]` or an IP address; with a TSIG key, NOTIFYs to it are signed. Targets do not grant transfers: admit them with `dns.secondary_addrs` or the zone's transfer ACL.",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone.")
+        ),
+        request_body = CreateZoneNotifyTargetRequest,
+        responses(
+            (status = 201, description = "NOTIFY target created", body = ZoneNotifyTargetResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 404, description = "Zone or TSIG key not found", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Add a NOTIFY target to a zone.
+pub(crate) async fn create_zone_notify_target(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneNameParam>,
+    JsonBody(body): JsonBody<CreateZoneNotifyTargetRequest>,
+) -> Result<Response, ApiError> {
+    let entry = ZoneNotifyTargetService::add(
+        &caller,
+        &params.name,
+        body.kind.as_deref(),
+        &body.address,
+        body.tsig_key.as_deref(),
+    )
+    .await?;
+    let json_body = json!({ "notify_target": GetZoneNotifyTargetResponse::from_entry(&entry) });
+    Ok((StatusCode::CREATED, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        delete,
+        path = "/zones/{name}/notify-targets/{id}",
+        tag = "Notify",
+        summary = "Remove a NOTIFY target from a zone",
+        description = "Removing a zone's last `notify` target returns it to the global secondary list.",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone."),
+            ("id" = i32, Path, description = "The id of the NOTIFY target to remove.")
+        ),
+        responses(
+            (status = 200, description = "NOTIFY target deleted", body = MessageResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 404, description = "Zone or NOTIFY target not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Remove one NOTIFY target of a zone by id.
+pub(crate) async fn delete_zone_notify_target(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneNotifyTargetParam>,
+) -> Result<Response, ApiError> {
+    ZoneNotifyTargetService::remove(&caller, &params.name, params.id).await?;
+    let json_body = json!({ "message": "NOTIFY target deleted successfully" });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
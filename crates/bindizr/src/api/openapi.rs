@@ -1,18 +1,31 @@
 use bindizr_service::types::{
-    BulkRecordsResponse, CreateBulkRecordsRequest, CreateRecordRequest, CreateTsigKeyRequest,
-    CreateZoneRequest, CreateZoneTokenPolicyRequest, CreateZoneTsigPolicyRequest, ErrorResponse,
-    ExternalDnsAdjustRequest, ExternalDnsAdjustResponse, ExternalDnsChangesRequest,
-    ExternalDnsChangesResponse, ExternalDnsRecordItem, ExternalDnsRecordsResponse,
-    ExternalDnsRrset, ExternalDnsRrsetUpdate, ExternalDnsZonesResponse, GetRecordResponse,
-    GetTsigKeyResponse, GetZoneResponse, GetZoneTokenPolicyResponse, GetZoneTsigPolicyResponse,
-    HealthResponse, ImportMode, ImportSummary, ImportZoneFileRequest, ImportZoneFileResponse,
-    MessageResponse, NotifyZoneRequest, PaginatedResponse, Pagination, RecordDiff, RecordDiffEntry,
-    RecordDiffSummary, RecordDiffValue, RecordItem, RecordResponse, RecordValueRequest,
-    RollbackSummary, RollbackZoneRequest, RollbackZoneResponse, SecondaryStatusResponse,
-    SnapshotDetailResponse, SnapshotDiffResponse, SnapshotRecordResponse, TsigKeyListResponse,
-    TsigKeyResponse, ZoneDetailResponse, ZoneResponse, ZoneSnapshotResponse, ZoneStatusResponse,
-    ZoneTokenPolicyListResponse, ZoneTokenPolicyResponse, ZoneTsigPolicyListResponse,
-    ZoneTsigPolicyResponse,
+    ApplyChangesetRequest, ApplyZoneTemplateRequest, BulkRecordsResponse, CatalogListResponse,
+    CatalogResponse, ChangesetOperation, ChangesetResponse, CreateBulkRecordsRequest,
+    CreateCatalogRequest, CreateRecordRequest, CreateTokenRequest, CreateTsigKeyRequest,
+    CreateWebhookRequest, CreateZoneNotifyTargetRequest, CreateZoneRequest,
+    CreateZoneTemplateRequest, CreateZoneTokenPolicyRequest, CreateZoneTransferAclRequest,
+    CreateZoneTsigPolicyRequest, ErrorResponse, ExternalDnsAdjustRequest,
+    ExternalDnsAdjustResponse, ExternalDnsChangesRequest, ExternalDnsChangesResponse,
+    ExternalDnsRecordItem, ExternalDnsRecordsResponse, ExternalDnsRrset, ExternalDnsRrsetUpdate,
+    ExternalDnsZonesResponse, GetCatalogResponse, GetRecordResponse, GetTokenResponse,
+    GetTsigKeyResponse, GetWebhookDeliveryResponse, GetWebhookResponse,
+    GetZoneCatalogPropertiesResponse, GetZoneNotifyTargetResponse, GetZoneResponse,
+    GetZoneTemplateResponse, GetZoneTokenPolicyResponse, GetZoneTransferAclResponse,
+    GetZoneTsigPolicyResponse, HealthResponse, ImportMode, ImportSummary, ImportZoneFileRequest,
+    ImportZoneFileResponse, MessageResponse, NotifyZoneRequest, PaginatedResponse, Pagination,
+    PullZoneRequest, PullZoneResponse, RecordDiff, RecordDiffEntry, RecordDiffSummary,
+    RecordDiffValue, RecordItem, RecordResponse, RecordValueRequest, ReplaceRrsetRequest,
+    RollbackSummary, RollbackZoneRequest, RollbackZoneResponse, RotateTokenRequest, RrsetResponse,
+    SecondariesStatusResponse, SecondaryStatusResponse, SecondarySyncResponse,
+    SetZoneCatalogPropertiesRequest, SnapshotDetailResponse, SnapshotDiffResponse,
+    SnapshotRecordResponse, TemplateZoneSyncResponse, TokenListResponse, TokenResponse,
+    TsigKeyListResponse, TsigKeyResponse, UpdateZoneTemplateRequest, WebhookDeliveryListResponse,
+    WebhookListResponse, WebhookResponse, ZoneCatalogPropertiesResponse, ZoneDetailResponse,
+    ZoneLintFinding, ZoneLintResponse, ZoneNotifyTargetListResponse, ZoneNotifyTargetResponse,
+    ZoneResponse, ZoneSnapshotResponse, ZoneStatusResponse, ZoneTemplateListResponse,
+    ZoneTemplateResponse, ZoneTemplateSyncResponse, ZoneTokenPolicyListResponse,
+    ZoneTokenPolicyResponse, ZoneTransferAclListResponse, ZoneTransferAclResponse,
+    ZoneTsigPolicyListResponse, ZoneTsigPolicyResponse,
 };
 use utoipa::{
     Modify, OpenApi,
@@ -35,14 +48,24 @@ use utoipa::{
         super::record::update_record,
         super::record::delete_record,
         super::record::create_records_bulk,
+        super::record::apply_changeset,
+        super::record::get_rrset,
+        super::record::replace_rrset,
+        super::record::delete_rrset,
         super::zone::import_zone,
+        super::zone::pull_zone,
         super::zone::export_zone,
+        super::zone::lint_zone,
         super::zone::list_zone_snapshots,
         super::zone::get_zone_snapshot,
         super::zone::diff_zone_snapshots,
         super::zone::rollback_zone,
         super::zone::get_zone_status,
+        super::secondary::get_secondaries_status,
         super::notify::notify_zones,
+        super::notify_target::get_zone_notify_targets,
+        super::notify_target::create_zone_notify_target,
+        super::notify_target::delete_zone_notify_target,
         super::tsig_key::get_tsig_keys,
         super::tsig_key::create_tsig_key,
         super::tsig_key::get_tsig_key,
@@ -50,21 +73,55 @@ use utoipa::{
         super::tsig_key::get_zone_tsig_policies,
         super::tsig_key::create_zone_tsig_policy,
         super::tsig_key::delete_zone_tsig_policy,
+        super::token::get_tokens,
+        super::token::create_token,
+        super::token::rotate_token,
+        super::token::delete_token,
         super::token_policy::get_zone_token_policies,
         super::token_policy::create_zone_token_policy,
         super::token_policy::delete_zone_token_policy,
+        super::transfer_acl::get_zone_transfer_acls,
+        super::transfer_acl::create_zone_transfer_acl,
+        super::transfer_acl::delete_zone_transfer_acl,
+        super::catalog::get_catalogs,
+        super::catalog::create_catalog,
+        super::catalog::delete_catalog,
+        super::catalog::get_zone_catalog_properties,
+        super::catalog::set_zone_catalog_properties,
+        super::template::get_templates,
+        super::template::create_template,
+        super::template::get_template,
+        super::template::update_template,
+        super::template::apply_template,
+        super::template::delete_template,
+        super::webhook::get_webhooks,
+        super::webhook::create_webhook,
+        super::webhook::delete_webhook,
+        super::webhook::get_webhook_deliveries,
         super::external_dns::get_external_dns_zones,
         super::external_dns::get_external_dns_records,
         super::external_dns::apply_external_dns_changes,
         super::external_dns::adjust_external_dns_rrsets
     ),
     components(schemas(
+        ApplyChangesetRequest,
+        ApplyZoneTemplateRequest,
         BulkRecordsResponse,
+        CatalogListResponse,
+        CatalogResponse,
+        ChangesetOperation,
+        ChangesetResponse,
         CreateBulkRecordsRequest,
+        CreateCatalogRequest,
         CreateRecordRequest,
+        CreateTokenRequest,
         CreateTsigKeyRequest,
+        CreateWebhookRequest,
+        CreateZoneNotifyTargetRequest,
         CreateZoneRequest,
+        CreateZoneTemplateRequest,
         CreateZoneTokenPolicyRequest,
+        CreateZoneTransferAclRequest,
         CreateZoneTsigPolicyRequest,
         ErrorResponse,
         ExternalDnsAdjustRequest,
@@ -76,10 +133,18 @@ use utoipa::{
         ExternalDnsRrset,
         ExternalDnsRrsetUpdate,
         ExternalDnsZonesResponse,
+        GetCatalogResponse,
         GetRecordResponse,
+        GetTokenResponse,
         GetTsigKeyResponse,
+        GetWebhookDeliveryResponse,
+        GetWebhookResponse,
+        GetZoneCatalogPropertiesResponse,
+        GetZoneNotifyTargetResponse,
         GetZoneResponse,
+        GetZoneTemplateResponse,
         GetZoneTokenPolicyResponse,
+        GetZoneTransferAclResponse,
         GetZoneTsigPolicyResponse,
         HealthResponse,
         ImportMode,
@@ -92,6 +157,8 @@ use utoipa::{
         PaginatedResponse<GetZoneResponse>,
         PaginatedResponse<ZoneSnapshotResponse>,
         Pagination,
+        PullZoneRequest,
+        PullZoneResponse,
         RecordDiff,
         RecordDiffEntry,
         RecordDiffSummary,
@@ -99,21 +166,44 @@ use utoipa::{
         RecordItem,
         RecordResponse,
         RecordValueRequest,
+        ReplaceRrsetRequest,
         RollbackSummary,
         RollbackZoneRequest,
+        RotateTokenRequest,
         RollbackZoneResponse,
+        RrsetResponse,
+        SecondariesStatusResponse,
         SecondaryStatusResponse,
+        SecondarySyncResponse,
+        SetZoneCatalogPropertiesRequest,
         SnapshotDetailResponse,
         SnapshotDiffResponse,
         SnapshotRecordResponse,
+        TemplateZoneSyncResponse,
+        TokenListResponse,
+        TokenResponse,
         TsigKeyListResponse,
         TsigKeyResponse,
+        UpdateZoneTemplateRequest,
+        WebhookDeliveryListResponse,
+        WebhookListResponse,
+        WebhookResponse,
+        ZoneCatalogPropertiesResponse,
         ZoneDetailResponse,
+        ZoneLintFinding,
+        ZoneLintResponse,
+        ZoneNotifyTargetListResponse,
+        ZoneNotifyTargetResponse,
         ZoneResponse,
         ZoneSnapshotResponse,
         ZoneStatusResponse,
+        ZoneTemplateListResponse,
+        ZoneTemplateResponse,
+        ZoneTemplateSyncResponse,
         ZoneTokenPolicyListResponse,
         ZoneTokenPolicyResponse,
+        ZoneTransferAclListResponse,
+        ZoneTransferAclResponse,
         ZoneTsigPolicyListResponse,
         ZoneTsigPolicyResponse
     )),
@@ -126,9 +216,14 @@ use utoipa::{
         (name = "Health", description = "Service health probe for load balancers and orchestrators."),
         (name = "Zone", description = "Manage DNS zones including creation, update, deletion, and retrieval."),
         (name = "Record", description = "Manage DNS records including creation, update, deletion, and retrieval."),
-        (name = "Notify", description = "Send DNS NOTIFY messages to secondary servers."),
+        (name = "Notify", description = "Send DNS NOTIFY messages to secondary servers, and manage the per-zone NOTIFY and also-notify targets."),
+        (name = "Secondary", description = "Fleet-wide secondary sync state from the background monitor."),
         (name = "TSIG", description = "Manage TSIG keys and per-zone TSIG policies for nsupdate authentication."),
-        (name = "Token", description = "Per-zone API token policies: record-plane grants for scoped tokens."),
+        (name = "Token", description = "API tokens (create, rotate, revoke) and per-zone token policies: record-plane grants for scoped tokens."),
+        (name = "Transfer", description = "Per-zone transfer ACLs: which secondaries may transfer a zone and see it in the catalog."),
+        (name = "Catalog", description = "Named catalog zones (RFC 9432) and the catalog membership and member properties of each zone."),
+        (name = "Template", description = "Zone templates: records with `{{variable}}` placeholders that new zones start from, and carrying template changes over to those zones."),
+        (name = "Webhook", description = "Outbound webhooks: signed HTTP callbacks on zone creation, serial changes and deletion, with their delivery queue."),
         (name = "ExternalDNS", description = "Provider endpoints for the ExternalDNS webhook adapter; registered only when api.external_dns_enabled is set.")
     ),
     info(
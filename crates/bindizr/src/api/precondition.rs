@@ -0,0 +1,127 @@
+//! `If-Match` / `If-None-Match` request headers and the `ETag` response header.
+
+use axum::{
+    extract::FromRequestParts,
+    http::{
+        HeaderMap, HeaderName,
+        header::{ETAG, IF_MATCH, IF_NONE_MATCH},
+        request::Parts,
+    },
+};
+use bindizr_service::{
+    error::ServiceError,
+    precondition::{EntityTags, Precondition},
+};
+
+use crate::api::error::ApiError;
+
+/// The write preconditions a request carries in its conditional headers.
+pub(crate) struct RequestPrecondition(pub(crate) Precondition);
+
+impl<S> FromRequestParts<S> for RequestPrecondition
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, ApiError> {
+        // If-Match compares strongly, so a weak tag can never satisfy it (RFC 9110 13.1.1).
+        let if_match = parse_condition(&parts.headers, &IF_MATCH, false)?;
+        let if_none_match = parse_condition(&parts.headers, &IF_NONE_MATCH, true)?;
+        Ok(RequestPrecondition(Precondition {
+            if_match,
+            if_none_match,
+        }))
+    }
+}
+
+/// The `ETag` header for an entity tag.
+pub(crate) fn etag_header(tag: &str) -> [(HeaderName, String); 1] {
+    [(ETAG, format!("\"{}\"", tag))]
+}
+
+/// Parse every line of a conditional header into one condition; `None` when
+/// the header is absent.
+fn parse_condition(
+    headers: &HeaderMap,
+    name: &HeaderName,
+    keep_weak: bool,
+) -> Result<Option<EntityTags>, ApiError> {
+    let mut tags = Vec::new();
+    let mut present = false;
+    for value in headers.get_all(name) {
+        present = true;
+        let value = value.to_str().map_err(|_| invalid_header(name))?.trim();
+        if value == "*" {
+            return Ok(Some(EntityTags::Any));
+        }
+        parse_tag_list(value, keep_weak, &mut tags).ok_or_else(|| invalid_header(name))?;
+    }
+    Ok(present.then_some(EntityTags::Tags(tags)))
+}
+
+/// Parse a comma-separated list of entity tags (`"tag"` or `W/"tag"`).
+fn parse_tag_list(value: &str, keep_weak: bool, tags: &mut Vec<String>) -> Option<()> {
+    let mut rest = value;
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ',']);
+        if rest.is_empty() {
+            return Some(());
+        }
+        let (weak, quoted) = match rest.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, rest),
+        };
+        let quoted = quoted.strip_prefix('"')?;
+        let end = quoted.find('"')?;
+        if !weak || keep_weak {
+            tags.push(quoted[..end].to_string());
+        }
+        rest = &quoted[end + 1..];
+        if !rest.is_empty() && !rest.starts_with([' ', '\t', ',']) {
+            return None;
+        }
+    }
+}
+
+fn invalid_header(name: &HeaderName) -> ApiError {
+    ApiError(ServiceError::invalid_input(format!(
+        "Invalid {} header: expected '*' or a list of quoted entity tags",
+        name
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn parse(value: &str, keep_weak: bool) -> Option<EntityTags> {
+        let mut headers = HeaderMap::new();
+        headers.insert(IF_MATCH, HeaderValue::from_str(value).unwrap());
+        parse_condition(&headers, &IF_MATCH, keep_weak).ok()?
+    }
+
+    fn tags(values: &[&str]) -> Option<EntityTags> {
+        Some(EntityTags::Tags(
+            values.iter().map(|v| v.to_string()).collect(),
+        ))
+    }
+
+    #[test]
+    fn parses_entity_tag_lists() {
+        assert_eq!(parse("*", false), Some(EntityTags::Any));
+        assert_eq!(parse("\"12\"", false), tags(&["12"]));
+        assert_eq!(parse("\"12\", \"7-ab\"", false), tags(&["12", "7-ab"]));
+        assert_eq!(parse("W/\"12\", \"13\"", false), tags(&["13"]));
+        assert_eq!(parse("W/\"12\", \"13\"", true), tags(&["12", "13"]));
+    }
+
+    #[test]
+    fn rejects_unquoted_tags() {
+        assert_eq!(parse("12", false), None);
+        assert_eq!(parse("\"12", false), None);
+        assert_eq!(parse("\"12\"x", false), None);
+    }
+}
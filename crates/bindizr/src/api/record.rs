@@ -6,11 +6,12 @@ use axum::{
     routing,
 };
 use bindizr_service::{
+    precondition::record_etag,
     record::RecordService,
     types::{
-        BulkRecordsResponse, CreateBulkRecordsRequest, CreateRecordRequest, ErrorResponse,
-        GetRecordResponse, GetRecordsFilter, MessageResponse, PaginatedResponse, RecordItem,
-        RecordResponse,
+        ApplyChangesetRequest, BulkRecordsResponse, ChangesetResponse, CreateBulkRecordsRequest,
+        CreateRecordRequest, ErrorResponse, GetRecordResponse, GetRecordsFilter, MessageResponse,
+        PaginatedResponse, RecordItem, RecordResponse, ReplaceRrsetRequest, RrsetResponse,
     },
 };
 use serde::Deserialize;
@@ -20,6 +21,7 @@ use crate::api::{
     RequestCaller,
     error::ApiError,
     middleware::body_parser::{JsonBody, MAX_UPLOAD_BODY_BYTES},
+    precondition::{RequestPrecondition, etag_header},
 };
 
 /// Route group for record endpoints.
@@ -39,6 +41,16 @@ impl RecordApi {
                 routing::post(create_records_bulk)
                     .layer(DefaultBodyLimit::max(MAX_UPLOAD_BODY_BYTES)),
             )
+            .route(
+                "/zones/{zone_name}/records/changeset",
+                routing::post(apply_changeset).layer(DefaultBodyLimit::max(MAX_UPLOAD_BODY_BYTES)),
+            )
+            .route(
+                "/zones/{zone_name}/rrsets/{name}/{record_type}",
+                routing::get(get_rrset)
+                    .put(replace_rrset)
+                    .delete(delete_rrset),
+            )
     }
 }
 
@@ -95,7 +107,8 @@ pub(crate) async fn get_records(
             ("record_id" = i32, Path, description = "The ID of the DNS record to retrieve.")
         ),
         responses(
-            (status = 200, description = "Details of the DNS record", body = RecordResponse),
+            (status = 200, description = "Details of the DNS record", body = RecordResponse,
+                headers(("ETag" = String, description = "The record version, for If-Match on later writes"))),
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 404, description = "Record not found", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
@@ -108,10 +121,11 @@ pub(crate) async fn get_record(
 ) -> Result<Response, ApiError> {
     let raw_record = RecordService::get_by_id_with_zone(&caller, params.record_id).await?;
 
+    let etag = etag_header(&record_etag(&raw_record.record()));
     let record = GetRecordResponse::from_record_with_zone(&raw_record);
 
     let json_body = json!({ "record": record });
-    Ok((StatusCode::OK, Json(json_body)).into_response())
+    Ok((StatusCode::OK, etag, Json(json_body)).into_response())
 }
 
 #[utoipa::path(
@@ -119,12 +133,17 @@ pub(crate) async fn get_record(
         path = "/records",
         tag = "Record",
         summary = "Create a new DNS record",
+        params(
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed.")
+        ),
         request_body = CreateRecordRequest,
         responses(
             (status = 201, description = "DNS record created successfully", body = RecordResponse),
             (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "The token's policies do not allow this record write", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
@@ -132,14 +151,16 @@ pub(crate) async fn get_record(
 /// Create a new DNS record.
 pub(crate) async fn create_record(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     JsonBody(body): JsonBody<CreateRecordRequest>,
 ) -> Result<Response, ApiError> {
-    let raw_record = RecordService::create(&caller, &body).await?;
+    let raw_record = RecordService::create(&caller, &body, &precondition).await?;
 
+    let etag = etag_header(&record_etag(&raw_record.record()));
     let record = GetRecordResponse::from_record_with_zone(&raw_record);
 
     let json_body = json!({ "record": record });
-    Ok((StatusCode::CREATED, Json(json_body)).into_response())
+    Ok((StatusCode::CREATED, etag, Json(json_body)).into_response())
 }
 
 #[utoipa::path(
@@ -148,7 +169,9 @@ pub(crate) async fn create_record(
         tag = "Record",
         summary = "Update a specific DNS record",
         params(
-            ("record_id" = i32, Path, description = "The ID of the DNS record to update.")
+            ("record_id" = i32, Path, description = "The ID of the DNS record to update."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the record's ETag (from GET /records/{record_id}) or its zone's ETag is listed."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if neither the record's nor its zone's ETag is listed.")
         ),
         request_body = RecordItem,
         responses(
@@ -157,6 +180,7 @@ pub(crate) async fn create_record(
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "The token's policies do not allow this record write", body = ErrorResponse),
             (status = 404, description = "Record not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
@@ -164,15 +188,18 @@ pub(crate) async fn create_record(
 /// Update an existing DNS record.
 pub(crate) async fn update_record(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     Path(params): Path<RecordIdParam>,
     JsonBody(body): JsonBody<RecordItem>,
 ) -> Result<Response, ApiError> {
-    let raw_record = RecordService::update_by_id(&caller, params.record_id, &body).await?;
+    let raw_record =
+        RecordService::update_by_id(&caller, params.record_id, &body, &precondition).await?;
 
+    let etag = etag_header(&record_etag(&raw_record.record()));
     let record = GetRecordResponse::from_record_with_zone(&raw_record);
 
     let json_body = json!({ "record": record });
-    Ok((StatusCode::OK, Json(json_body)).into_response())
+    Ok((StatusCode::OK, etag, Json(json_body)).into_response())
 }
 
 #[utoipa::path(
@@ -181,22 +208,26 @@ pub(crate) async fn update_record(
         tag = "Record",
         summary = "Delete a specific DNS record",
         params(
-            ("record_id" = i32, Path, description = "The ID of the DNS record to delete.")
+            ("record_id" = i32, Path, description = "The ID of the DNS record to delete."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the record's ETag (from GET /records/{record_id}) or its zone's ETag is listed."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if neither the record's nor its zone's ETag is listed.")
         ),
         responses(
             (status = 200, description = "DNS record deleted successfully", body = MessageResponse),
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "The token's policies do not allow this record write", body = ErrorResponse),
             (status = 404, description = "Record not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
 )]
 /// Delete a DNS record.
 pub(crate) async fn delete_record(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     Path(params): Path<RecordIdParam>,
 ) -> Result<Response, ApiError> {
-    RecordService::delete_by_id(&caller, params.record_id).await?;
+    RecordService::delete_by_id(&caller, params.record_id, &precondition).await?;
 
     let json_body = json!({ "message": "Record deleted successfully" });
     Ok((StatusCode::OK, Json(json_body)).into_response())
@@ -209,7 +240,9 @@ pub(crate) async fn delete_record(
         summary = "Bulk insert DNS records into a zone",
         description = "Insert many records into a single zone in one transaction. The zone serial is incremented once and a single NOTIFY is sent. Either all records are inserted or none are. With dry_run the same validation runs but nothing is applied.",
         params(
-            ("zone_name" = String, Path, description = "The name of the DNS zone to insert records into.")
+            ("zone_name" = String, Path, description = "The name of the DNS zone to insert records into."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed.")
         ),
         request_body = CreateBulkRecordsRequest,
         responses(
@@ -219,6 +252,7 @@ pub(crate) async fn delete_record(
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "The token's policies do not allow this record write", body = ErrorResponse),
             (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
@@ -226,11 +260,18 @@ pub(crate) async fn delete_record(
 /// Bulk insert DNS records into a zone in a single transaction.
 pub(crate) async fn create_records_bulk(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     Path(params): Path<ZoneScopedParam>,
     JsonBody(body): JsonBody<CreateBulkRecordsRequest>,
 ) -> Result<Response, ApiError> {
-    let response =
-        RecordService::create_bulk(&caller, &params.zone_name, &body.records, body.dry_run).await?;
+    let response = RecordService::create_bulk(
+        &caller,
+        &params.zone_name,
+        &body.records,
+        body.dry_run,
+        &precondition,
+    )
+    .await?;
 
     let status = if body.dry_run {
         StatusCode::OK
@@ -240,12 +281,190 @@ pub(crate) async fn create_records_bulk(
     Ok((status, Json(response)).into_response())
 }
 
+#[utoipa::path(
+        post,
+        path = "/zones/{zone_name}/records/changeset",
+        tag = "Record",
+        summary = "Apply an ordered changeset of record operations to a zone",
+        description = "Apply add, update and delete operations in order as one atomic change. Each operation is validated against the zone as the operations before it left it; if any fails, nothing is applied and the error names the failing operation. The zone serial moves once and a single NOTIFY is sent. `update` replaces every field of an existing record, like PUT /records/{record_id}. With dry_run the same validation runs and the diff is returned without applying anything.",
+        params(
+            ("zone_name" = String, Path, description = "The name of the DNS zone to change."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed.")
+        ),
+        request_body = ApplyChangesetRequest,
+        responses(
+            (status = 200, description = "Changeset applied, or validated on a dry run", body = ChangesetResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "The token's policies do not allow one of the record writes", body = ErrorResponse),
+            (status = 404, description = "Zone or record not found", body = ErrorResponse),
+            (status = 409, description = "An operation conflicts with the zone's records", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Apply an ordered changeset of record operations in a single transaction.
+pub(crate) async fn apply_changeset(
+    RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
+    Path(params): Path<ZoneScopedParam>,
+    JsonBody(body): JsonBody<ApplyChangesetRequest>,
+) -> Result<Response, ApiError> {
+    let response = RecordService::apply_changeset(
+        &caller,
+        &params.zone_name,
+        &body.operations,
+        body.dry_run,
+        &precondition,
+    )
+    .await?;
+
+    let etag = etag_header(&response.serial.to_string());
+    Ok((StatusCode::OK, etag, Json(response)).into_response())
+}
+
+#[utoipa::path(
+        get,
+        path = "/zones/{zone_name}/rrsets/{name}/{record_type}",
+        tag = "Record",
+        summary = "Get an RRset",
+        description = "Every record of one owner name and type, with the TTL they share and their values in presentation form.",
+        params(
+            ("zone_name" = String, Path, description = "The name of the DNS zone."),
+            ("name" = String, Path, description = "Owner name relative to the zone, '@' for the apex, or an absolute name with a trailing dot."),
+            ("record_type" = String, Path, description = "The record type, e.g. A, MX or TYPE65280.")
+        ),
+        responses(
+            (status = 200, description = "The RRset", body = RrsetResponse,
+                headers(("ETag" = String, description = "The zone serial, for If-Match on later writes"))),
+            (status = 400, description = "Bad request, invalid name or type", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 404, description = "Zone or RRset not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Get every record of one name and type.
+pub(crate) async fn get_rrset(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<RrsetParam>,
+) -> Result<Response, ApiError> {
+    let rrset = RecordService::get_rrset(
+        &caller,
+        &params.zone_name,
+        &params.name,
+        &params.record_type,
+    )
+    .await?;
+
+    let etag = etag_header(&rrset.serial.to_string());
+    Ok((StatusCode::OK, etag, Json(rrset)).into_response())
+}
+
+#[utoipa::path(
+        put,
+        path = "/zones/{zone_name}/rrsets/{name}/{record_type}",
+        tag = "Record",
+        summary = "Replace an RRset",
+        description = "Replace every record of one owner name and type with the given values under a single TTL, creating the RRset if the zone does not hold it. Values already stored at that TTL are kept as they are; a request matching the stored RRset changes nothing and leaves the serial alone. Otherwise the serial is incremented once and a single NOTIFY is sent.",
+        params(
+            ("zone_name" = String, Path, description = "The name of the DNS zone."),
+            ("name" = String, Path, description = "Owner name relative to the zone, '@' for the apex, or an absolute name with a trailing dot."),
+            ("record_type" = String, Path, description = "The record type, e.g. A, MX or TYPE65280."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the RRset exists and its zone's ETag (the serial) is listed, or for `*` if the RRset exists."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed; `*` applies only if the RRset does not exist yet.")
+        ),
+        request_body = ReplaceRrsetRequest,
+        responses(
+            (status = 200, description = "The RRset as stored after the replacement", body = RrsetResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "The token's policies do not allow this record write", body = ErrorResponse),
+            (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 409, description = "The RRset conflicts with other records at the name", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Atomically replace every record of one name and type.
+pub(crate) async fn replace_rrset(
+    RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
+    Path(params): Path<RrsetParam>,
+    JsonBody(body): JsonBody<ReplaceRrsetRequest>,
+) -> Result<Response, ApiError> {
+    let rrset = RecordService::replace_rrset(
+        &caller,
+        &params.zone_name,
+        &params.name,
+        &params.record_type,
+        &body,
+        &precondition,
+    )
+    .await?;
+
+    let etag = etag_header(&rrset.serial.to_string());
+    Ok((StatusCode::OK, etag, Json(rrset)).into_response())
+}
+
+#[utoipa::path(
+        delete,
+        path = "/zones/{zone_name}/rrsets/{name}/{record_type}",
+        tag = "Record",
+        summary = "Delete an RRset",
+        description = "Delete every record of one owner name and type in one change; the serial is incremented once.",
+        params(
+            ("zone_name" = String, Path, description = "The name of the DNS zone."),
+            ("name" = String, Path, description = "Owner name relative to the zone, '@' for the apex, or an absolute name with a trailing dot."),
+            ("record_type" = String, Path, description = "The record type, e.g. A, MX or TYPE65280."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the RRset exists and its zone's ETag (the serial) is listed, or for `*` if the RRset exists."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed; `*` applies only if the RRset does not exist yet.")
+        ),
+        responses(
+            (status = 200, description = "RRset deleted successfully", body = MessageResponse),
+            (status = 400, description = "Bad request, the RRset cannot be deleted", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "The token's policies do not allow this record write", body = ErrorResponse),
+            (status = 404, description = "Zone or RRset not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Delete every record of one name and type.
+pub(crate) async fn delete_rrset(
+    RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
+    Path(params): Path<RrsetParam>,
+) -> Result<Response, ApiError> {
+    RecordService::delete_rrset(
+        &caller,
+        &params.zone_name,
+        &params.name,
+        &params.record_type,
+        &precondition,
+    )
+    .await?;
+
+    let json_body = json!({ "message": "RRset deleted successfully" });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
 /// Path parameters scoped to a zone.
 #[derive(Debug, Deserialize)]
 pub(crate) struct ZoneScopedParam {
     zone_name: String,
 }
 
+/// Path parameters addressing an RRset by owner name and type within a zone.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RrsetParam {
+    zone_name: String,
+    name: String,
+    record_type: String,
+}
+
 /// Path parameters addressing a record by id.
 #[derive(Debug, Deserialize)]
 pub(crate) struct RecordIdParam {
@@ -11,8 +11,10 @@ use tower_http::cors::CorsLayer;
 use utoipa::OpenApi;
 
 use super::{
-    external_dns::ExternalDnsApi, notify::NotifyApi, openapi::ApiDoc, record::RecordApi,
-    token_policy::TokenPolicyApi, tsig_key::TsigKeyApi, zone::ZoneApi,
+    catalog::CatalogApi, external_dns::ExternalDnsApi, notify::NotifyApi,
+    notify_target::NotifyTargetApi, openapi::ApiDoc, record::RecordApi, secondary::SecondaryApi,
+    template::TemplateApi, token::TokenApi, token_policy::TokenPolicyApi,
+    transfer_acl::TransferAclApi, tsig_key::TsigKeyApi, webhook::WebhookApi, zone::ZoneApi,
 };
 
 /// HTTP API router assembling all route groups.
@@ -27,8 +29,15 @@ impl ApiRouter {
             .merge(ZoneApi::routes().await)
             .merge(RecordApi::routes().await)
             .merge(NotifyApi::routes().await)
+            .merge(NotifyTargetApi::routes().await)
+            .merge(SecondaryApi::routes().await)
             .merge(TsigKeyApi::routes().await)
+            .merge(TokenApi::routes().await)
             .merge(TokenPolicyApi::routes().await)
+            .merge(TransferAclApi::routes().await)
+            .merge(CatalogApi::routes().await)
+            .merge(TemplateApi::routes().await)
+            .merge(WebhookApi::routes().await)
             .route("/", routing::get(ApiRouter::get_home));
 
         // Unregistered when disabled, so the endpoints fall through to 404.
@@ -36,6 +45,11 @@ impl ApiRouter {
             api_router = api_router.merge(ExternalDnsApi::routes().await);
         }
 
+        // Inside the auth layer, so it sees the token auth accepted.
+        api_router = api_router.layer(axum::middleware::from_fn(
+            super::middleware::audit::audit_context,
+        ));
+
         if api_config.require_authentication {
             api_router = api_router.layer(axum::middleware::from_fn(
                 super::middleware::auth::auth_middleware,
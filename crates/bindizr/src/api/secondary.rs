@@ -0,0 +1,41 @@
+use axum::{
+    Json, Router,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use bindizr_dns as dns;
+use bindizr_service::types::{ErrorResponse, SecondariesStatusResponse};
+
+use crate::api::{RequestCaller, error::ApiError};
+
+/// Route group for fleet-wide secondary endpoints.
+pub(crate) struct SecondaryApi;
+
+impl SecondaryApi {
+    /// Build the router for fleet-wide secondary endpoints.
+    pub(crate) async fn routes() -> Router {
+        Router::new().route("/secondaries/status", routing::get(get_secondaries_status))
+    }
+}
+
+#[utoipa::path(
+        get,
+        path = "/secondaries/status",
+        tag = "Secondary",
+        summary = "Check how every secondary is keeping up with every zone",
+        description = "Returns the background sync monitor's last probe of each configured secondary for each zone: the serial it served, how far behind it was, how long it has lagged, and when it was last re-NOTIFYed. Nothing is probed by the request; see `/zones/{name}/status` for a live probe. `interval_secs` is 0 when the monitor is off, and `last_round_at` is absent until its first round completes.",
+        responses(
+            (status = 200, description = "The sync monitor's last view of every zone on every secondary", body = SecondariesStatusResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the viewer role is required", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Report the sync monitor's last view of every zone on every secondary.
+pub(crate) async fn get_secondaries_status(
+    RequestCaller(caller): RequestCaller,
+) -> Result<Response, ApiError> {
+    let status = dns::monitor::secondaries_status(&caller)?;
+    Ok((StatusCode::OK, Json(status)).into_response())
+}
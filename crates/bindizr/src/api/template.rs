@@ -0,0 +1,207 @@
+use axum::{
+    Json, Router,
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use bindizr_service::{
+    template::ZoneTemplateService,
+    types::{
+        ApplyZoneTemplateRequest, CreateZoneTemplateRequest, ErrorResponse, MessageResponse,
+        UpdateZoneTemplateRequest, ZoneTemplateListResponse, ZoneTemplateResponse,
+        ZoneTemplateSyncResponse,
+    },
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::{RequestCaller, error::ApiError, middleware::body_parser::JsonBody};
+
+/// Route group for zone template endpoints.
+pub(crate) struct TemplateApi;
+
+impl TemplateApi {
+    /// Build the router for zone template endpoints.
+    pub(crate) async fn routes() -> Router {
+        Router::new()
+            .route("/templates", routing::get(get_templates))
+            .route("/templates", routing::post(create_template))
+            .route("/templates/{name}", routing::get(get_template))
+            .route("/templates/{name}", routing::put(update_template))
+            .route("/templates/{name}", routing::delete(delete_template))
+            .route("/templates/{name}/apply", routing::post(apply_template))
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct TemplateNameParam {
+    pub(crate) name: String,
+}
+
+#[utoipa::path(
+        get,
+        path = "/templates",
+        tag = "Template",
+        summary = "List zone templates",
+        responses(
+            (status = 200, description = "All zone templates", body = ZoneTemplateListResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the viewer role is required", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// List the zone templates.
+pub(crate) async fn get_templates(
+    RequestCaller(caller): RequestCaller,
+) -> Result<Response, ApiError> {
+    let templates = ZoneTemplateService::list(&caller).await?;
+    let json_body = json!({ "templates": templates });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/templates",
+        tag = "Template",
+        summary = "Create a zone template",
+        description = "Creates a template of records for new zones. Record names and values may hold `{{variable}}` placeholders: `{{zone}}` is the zone's name, and every other variable is supplied in `template_variables` when a zone is created with `template` set.",
+        request_body = CreateZoneTemplateRequest,
+        responses(
+            (status = 201, description = "Template created successfully", body = ZoneTemplateResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 409, description = "A template with the same name already exists", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Create a zone template.
+pub(crate) async fn create_template(
+    RequestCaller(caller): RequestCaller,
+    JsonBody(body): JsonBody<CreateZoneTemplateRequest>,
+) -> Result<Response, ApiError> {
+    let template = ZoneTemplateService::create(
+        &caller,
+        &body.name,
+        body.description.as_deref(),
+        &body.records,
+    )
+    .await?;
+    let json_body = json!({ "template": template });
+    Ok((StatusCode::CREATED, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        get,
+        path = "/templates/{name}",
+        tag = "Template",
+        summary = "Get a zone template",
+        params(
+            ("name" = String, Path, description = "The name of the template.")
+        ),
+        responses(
+            (status = 200, description = "The template and the zones created from it", body = ZoneTemplateResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the viewer role is required", body = ErrorResponse),
+            (status = 404, description = "Template not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Get a zone template.
+pub(crate) async fn get_template(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<TemplateNameParam>,
+) -> Result<Response, ApiError> {
+    let template = ZoneTemplateService::get(&caller, &params.name).await?;
+    let json_body = json!({ "template": template });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        put,
+        path = "/templates/{name}",
+        tag = "Template",
+        summary = "Update a zone template",
+        description = "Replaces the template's description and records and returns the diff the change makes to every zone created from it. Linked zones keep their records unless `apply` is set; the change can also be applied later with `POST /templates/{name}/apply`. Only what the template changed is carried over, so records edited by hand are left alone. With `dry_run` the template is not saved and no zone is changed.",
+        params(
+            ("name" = String, Path, description = "The name of the template.")
+        ),
+        request_body = UpdateZoneTemplateRequest,
+        responses(
+            (status = 200, description = "Template updated; per-zone diffs and results", body = ZoneTemplateSyncResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 404, description = "Template not found", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Update a zone template and optionally apply it to linked zones.
+pub(crate) async fn update_template(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<TemplateNameParam>,
+    JsonBody(body): JsonBody<UpdateZoneTemplateRequest>,
+) -> Result<Response, ApiError> {
+    let result = ZoneTemplateService::update(&caller, &params.name, &body).await?;
+    Ok((StatusCode::OK, Json(result)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/templates/{name}/apply",
+        tag = "Template",
+        summary = "Apply a zone template to its zones",
+        description = "Carries the template's current records over to every zone created from it that is still behind them. Each zone is changed in its own changeset, bumping its serial and sending NOTIFY; a zone that fails is reported and does not stop the others. With `dry_run` only the diffs are returned.",
+        params(
+            ("name" = String, Path, description = "The name of the template.")
+        ),
+        request_body = ApplyZoneTemplateRequest,
+        responses(
+            (status = 200, description = "Per-zone diffs and results", body = ZoneTemplateSyncResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 404, description = "Template not found", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Apply a zone template to the zones created from it.
+pub(crate) async fn apply_template(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<TemplateNameParam>,
+    JsonBody(body): JsonBody<ApplyZoneTemplateRequest>,
+) -> Result<Response, ApiError> {
+    let result = ZoneTemplateService::apply(&caller, &params.name, body.dry_run).await?;
+    Ok((StatusCode::OK, Json(result)).into_response())
+}
+
+#[utoipa::path(
+        delete,
+        path = "/templates/{name}",
+        tag = "Template",
+        summary = "Delete a zone template",
+        description = "Deletes a template. Refused while any zone created from it still exists; the zones' records are never touched.",
+        params(
+            ("name" = String, Path, description = "The name of the template.")
+        ),
+        responses(
+            (status = 200, description = "Template deleted", body = MessageResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A token with the zone-admin role is required", body = ErrorResponse),
+            (status = 404, description = "Template not found", body = ErrorResponse),
+            (status = 409, description = "Zones are still linked to the template", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Delete a zone template.
+pub(crate) async fn delete_template(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<TemplateNameParam>,
+) -> Result<Response, ApiError> {
+    ZoneTemplateService::delete(&caller, &params.name).await?;
+    let json_body = json!({ "message": "Template deleted successfully" });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
@@ -0,0 +1,151 @@
+use axum::{
+    Json, Router,
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use bindizr_service::{
+    token::TokenService,
+    types::{
+        CreateTokenRequest, ErrorResponse, GetTokenResponse, MessageResponse, RotateTokenRequest,
+        TokenListResponse, TokenResponse,
+    },
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::{RequestCaller, error::ApiError, middleware::body_parser::JsonBody};
+
+/// Route group for API token endpoints.
+pub(crate) struct TokenApi;
+
+impl TokenApi {
+    /// Build the router for API token endpoints.
+    pub(crate) async fn routes() -> Router {
+        Router::new()
+            .route("/tokens", routing::get(get_tokens))
+            .route("/tokens", routing::post(create_token))
+            .route("/tokens/{name}", routing::delete(delete_token))
+            .route("/tokens/{name}/rotate", routing::post(rotate_token))
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct TokenNameParam {
+    pub(crate) name: String,
+}
+
+#[utoipa::path(
+        get,
+        path = "/tokens",
+        tag = "Token",
+        summary = "List all API tokens",
+        description = "Lists every API token without its secret.",
+        responses(
+            (status = 200, description = "All API tokens", body = TokenListResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// List all API tokens (secrets omitted).
+pub(crate) async fn get_tokens(RequestCaller(caller): RequestCaller) -> Result<Response, ApiError> {
+    let tokens = TokenService::list(&caller).await?;
+    let tokens: Vec<GetTokenResponse> = tokens.iter().map(GetTokenResponse::from_token).collect();
+    let json_body = json!({ "tokens": tokens });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/tokens",
+        tag = "Token",
+        summary = "Create an API token",
+        description = "Creates an API token. The response carries the secret, which is stored only as a hash and never shown again. `role` sets what the token may do: `viewer` reads every zone, `record-editor` also writes records, `zone-admin` also manages zones, and `admin` (or `global: true`) may do everything, including managing tokens. A `scoped` token, the default, can do nothing until it is granted token policies in a zone.",
+        request_body = CreateTokenRequest,
+        responses(
+            (status = 201, description = "API token created successfully", body = TokenResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 409, description = "An API token with the same name already exists", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Create an API token, returning its secret once.
+pub(crate) async fn create_token(
+    RequestCaller(caller): RequestCaller,
+    JsonBody(body): JsonBody<CreateTokenRequest>,
+) -> Result<Response, ApiError> {
+    let token = TokenService::create(
+        &caller,
+        &body.name,
+        body.description.as_deref(),
+        body.expires_in_days,
+        body.global,
+        body.role.as_deref(),
+    )
+    .await?;
+    let json_body = json!({ "token": GetTokenResponse::from_token(&token) });
+    Ok((StatusCode::CREATED, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/tokens/{name}/rotate",
+        tag = "Token",
+        summary = "Rotate an API token's secret",
+        description = "Issues a new secret for the token, keeping its name, scope, expiry and zone policies. The old secret keeps working for `overlap_minutes` so clients can switch over without downtime; `previous_token_expires_at` in the response says until when. Rotating again ends any earlier overlap.",
+        params(
+            ("name" = String, Path, description = "The name of the API token to rotate.")
+        ),
+        request_body = RotateTokenRequest,
+        responses(
+            (status = 200, description = "API token rotated; the response carries the new secret", body = TokenResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "API token not found", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Issue a new secret for an API token, keeping the old one through an overlap.
+pub(crate) async fn rotate_token(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<TokenNameParam>,
+    JsonBody(body): JsonBody<RotateTokenRequest>,
+) -> Result<Response, ApiError> {
+    let token = TokenService::rotate(&caller, &params.name, body.overlap_minutes).await?;
+    let json_body = json!({ "token": GetTokenResponse::from_token(&token) });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        delete,
+        path = "/tokens/{name}",
+        tag = "Token",
+        summary = "Revoke an API token",
+        description = "Deletes the token and its zone policies. Both its current secret and any secret still in a rotation overlap stop working immediately.",
+        params(
+            ("name" = String, Path, description = "The name of the API token to revoke.")
+        ),
+        responses(
+            (status = 200, description = "API token revoked", body = MessageResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "API token not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Revoke (delete) an API token by name.
+pub(crate) async fn delete_token(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<TokenNameParam>,
+) -> Result<Response, ApiError> {
+    TokenService::delete(&caller, &params.name).await?;
+    let json_body = json!({ "message": "Token deleted successfully" });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
@@ -0,0 +1,144 @@
+use axum::{
+    Json, Router,
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use bindizr_service::{
+    types::{
+        CreateZoneTransferAclRequest, ErrorResponse, GetZoneTransferAclResponse, MessageResponse,
+        ZoneTransferAclListResponse, ZoneTransferAclResponse,
+    },
+    zone::transfer_acl::ZoneTransferAclService,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::{
+    RequestCaller, error::ApiError, middleware::body_parser::JsonBody, token_policy::ZoneNameParam,
+};
+
+/// Route group for zone transfer-ACL endpoints.
+pub(crate) struct TransferAclApi;
+
+impl TransferAclApi {
+    /// Build the router for zone transfer-ACL endpoints.
+    pub(crate) async fn routes() -> Router {
+        Router::new()
+            .route(
+                "/zones/{name}/transfer-acls",
+                routing::get(get_zone_transfer_acls),
+            )
+            .route(
+                "/zones/{name}/transfer-acls",
+                routing::post(create_zone_transfer_acl),
+            )
+            .route(
+                "/zones/{name}/transfer-acls/{id}",
+                routing::delete(delete_zone_transfer_acl),
+            )
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ZoneTransferAclParam {
+    pub(crate) name: String,
+    pub(crate) id: i32,
+}
+
+#[utoipa::path(
+        get,
+        path = "/zones/{name}/transfer-acls",
+        tag = "Transfer",
+        summary = "List a zone's transfer ACL",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone.")
+        ),
+        responses(
+            (status = 200, description = "The zone's transfer ACL entries; empty when the global ACL applies", body = ZoneTransferAclListResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// List a zone's transfer ACL entries.
+pub(crate) async fn get_zone_transfer_acls(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneNameParam>,
+) -> Result<Response, ApiError> {
+    let entries = ZoneTransferAclService::list(&caller, &params.name).await?;
+    let entries: Vec<GetZoneTransferAclResponse> = entries
+        .iter()
+        .map(GetZoneTransferAclResponse::from_entry)
+        .collect();
+    let json_body = json!({ "transfer_acls": entries });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/zones/{name}/transfer-acls",
+        tag = "Transfer",
+        summary = "Add an entry to a zone's transfer ACL",
+        description = "Once a zone has entries of its own, they replace the global `dns.secondary_addrs` list for transfers of that zone, and the catalog zone lists the zone only to the secondaries they admit. The address is an IP address or a CIDR prefix. A matching deny entry refuses the client whatever else matches; an allow entry with a TSIG key admits the client only for requests signed with that key.",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone.")
+        ),
+        request_body = CreateZoneTransferAclRequest,
+        responses(
+            (status = 201, description = "Transfer ACL entry created", body = ZoneTransferAclResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "Zone or TSIG key not found", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Add an entry to a zone's transfer ACL.
+pub(crate) async fn create_zone_transfer_acl(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneNameParam>,
+    JsonBody(body): JsonBody<CreateZoneTransferAclRequest>,
+) -> Result<Response, ApiError> {
+    let entry = ZoneTransferAclService::add(
+        &caller,
+        &params.name,
+        body.action.as_deref(),
+        &body.address,
+        body.tsig_key.as_deref(),
+    )
+    .await?;
+    let json_body = json!({ "transfer_acl": GetZoneTransferAclResponse::from_entry(&entry) });
+    Ok((StatusCode::CREATED, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        delete,
+        path = "/zones/{name}/transfer-acls/{id}",
+        tag = "Transfer",
+        summary = "Remove an entry from a zone's transfer ACL",
+        description = "Removing a zone's last entry returns it to the global ACL.",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone."),
+            ("id" = i32, Path, description = "The id of the transfer ACL entry to remove.")
+        ),
+        responses(
+            (status = 200, description = "Transfer ACL entry deleted", body = MessageResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "Zone or entry not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Remove one transfer ACL entry of a zone by id.
+pub(crate) async fn delete_zone_transfer_acl(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneTransferAclParam>,
+) -> Result<Response, ApiError> {
+    ZoneTransferAclService::remove(&caller, &params.name, params.id).await?;
+    let json_body = json!({ "message": "Transfer ACL entry deleted successfully" });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
@@ -0,0 +1,169 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing,
+};
+use bindizr_service::{
+    types::{
+        CreateWebhookRequest, ErrorResponse, GetWebhookDeliveryResponse, GetWebhookResponse,
+        MessageResponse, WebhookDeliveryListResponse, WebhookListResponse, WebhookResponse,
+    },
+    webhook::WebhookService,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::api::{RequestCaller, error::ApiError, middleware::body_parser::JsonBody};
+
+/// Route group for webhook endpoints.
+pub(crate) struct WebhookApi;
+
+impl WebhookApi {
+    /// Build the router for webhook endpoints.
+    pub(crate) async fn routes() -> Router {
+        Router::new()
+            .route("/webhooks", routing::get(get_webhooks))
+            .route("/webhooks", routing::post(create_webhook))
+            .route("/webhooks/{name}", routing::delete(delete_webhook))
+            .route(
+                "/webhooks/{name}/deliveries",
+                routing::get(get_webhook_deliveries),
+            )
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct WebhookNameParam {
+    pub(crate) name: String,
+}
+
+/// Query parameters for listing a webhook's deliveries.
+#[derive(Debug, Deserialize)]
+pub(crate) struct DeliveryListQuery {
+    limit: Option<u32>,
+}
+
+#[utoipa::path(
+        get,
+        path = "/webhooks",
+        tag = "Webhook",
+        summary = "List webhooks",
+        description = "Lists every webhook without its secret.",
+        responses(
+            (status = 200, description = "All webhooks", body = WebhookListResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// List all webhooks (secrets omitted).
+pub(crate) async fn get_webhooks(
+    RequestCaller(caller): RequestCaller,
+) -> Result<Response, ApiError> {
+    let webhooks = WebhookService::list(&caller).await?;
+    let webhooks: Vec<GetWebhookResponse> = webhooks
+        .iter()
+        .map(GetWebhookResponse::from_subscription)
+        .collect();
+    let json_body = json!({ "webhooks": webhooks });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/webhooks",
+        tag = "Webhook",
+        summary = "Create a webhook",
+        description = "Subscribes a URL to zone events: `zone.created`, `zone.updated` (every serial change, with the record diff) and `zone.deleted`. With `zone` set only that zone's events are sent. Each event is POSTed as JSON signed with `X-Bindizr-Signature: sha256=<HMAC-SHA256(secret, \"{X-Bindizr-Timestamp}.{body}\")>` and retried with exponential backoff until a 2xx answer. The response carries the secret, which is never shown again.",
+        request_body = CreateWebhookRequest,
+        responses(
+            (status = 201, description = "Webhook created successfully", body = WebhookResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 409, description = "A webhook with the same name already exists", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Create a webhook, returning its secret once.
+pub(crate) async fn create_webhook(
+    RequestCaller(caller): RequestCaller,
+    JsonBody(body): JsonBody<CreateWebhookRequest>,
+) -> Result<Response, ApiError> {
+    let webhook = WebhookService::create(
+        &caller,
+        &body.name,
+        &body.url,
+        body.zone.as_deref(),
+        body.secret.as_deref(),
+        body.description.as_deref(),
+    )
+    .await?;
+    let json_body = json!({ "webhook": GetWebhookResponse::from_subscription(&webhook) });
+    Ok((StatusCode::CREATED, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        delete,
+        path = "/webhooks/{name}",
+        tag = "Webhook",
+        summary = "Delete a webhook",
+        description = "Deletes the webhook and drops its queued deliveries.",
+        params(
+            ("name" = String, Path, description = "The name of the webhook.")
+        ),
+        responses(
+            (status = 200, description = "Webhook deleted", body = MessageResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "Webhook not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Delete a webhook.
+pub(crate) async fn delete_webhook(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<WebhookNameParam>,
+) -> Result<Response, ApiError> {
+    WebhookService::delete(&caller, &params.name).await?;
+    let json_body = json!({ "message": "Webhook deleted successfully" });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
+
+#[utoipa::path(
+        get,
+        path = "/webhooks/{name}/deliveries",
+        tag = "Webhook",
+        summary = "List a webhook's deliveries",
+        description = "Lists the webhook's most recent deliveries, newest first, with their status and attempt count. Delivered and failed deliveries are kept for `webhook.retention_days`.",
+        params(
+            ("name" = String, Path, description = "The name of the webhook."),
+            ("limit" = Option<u32>, Query, description = "Maximum number of deliveries to return; defaults to 50, at most 500.")
+        ),
+        responses(
+            (status = 200, description = "The webhook's deliveries", body = WebhookDeliveryListResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "Webhook not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// List a webhook's deliveries, newest first.
+pub(crate) async fn get_webhook_deliveries(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<WebhookNameParam>,
+    Query(query): Query<DeliveryListQuery>,
+) -> Result<Response, ApiError> {
+    let deliveries = WebhookService::deliveries(&caller, &params.name, query.limit).await?;
+    let deliveries: Vec<GetWebhookDeliveryResponse> = deliveries
+        .iter()
+        .map(GetWebhookDeliveryResponse::from_delivery)
+        .collect();
+    let json_body = json!({ "deliveries": deliveries });
+    Ok((StatusCode::OK, Json(json_body)).into_response())
+}
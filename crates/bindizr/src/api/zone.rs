@@ -7,13 +7,14 @@ use axum::{
 };
 use bindizr_dns as dns;
 use bindizr_service::{
+    precondition::zone_etag,
     record::RecordService,
     types::{
         CreateZoneRequest, ErrorResponse, GetRecordResponse, GetZoneResponse, GetZonesFilter,
         ImportZoneFileRequest, ImportZoneFileResponse, MessageResponse, PaginatedResponse,
-        RollbackZoneRequest, RollbackZoneResponse, SnapshotDetailResponse, SnapshotDiffResponse,
-        SnapshotRecordResponse, ZoneDetailResponse, ZoneResponse, ZoneSnapshotResponse,
-        ZoneStatusResponse,
+        PullZoneRequest, PullZoneResponse, RollbackZoneRequest, RollbackZoneResponse,
+        SnapshotDetailResponse, SnapshotDiffResponse, SnapshotRecordResponse, ZoneDetailResponse,
+        ZoneLintResponse, ZoneResponse, ZoneSnapshotResponse, ZoneStatusResponse,
     },
     zone::ZoneService,
 };
@@ -24,6 +25,7 @@ use crate::api::{
     RequestCaller,
     error::ApiError,
     middleware::body_parser::{JsonBody, MAX_UPLOAD_BODY_BYTES},
+    precondition::{RequestPrecondition, etag_header},
 };
 
 /// Route group for zone endpoints.
@@ -42,7 +44,9 @@ impl ZoneApi {
                 "/zones/{name}/imports",
                 routing::post(import_zone).layer(DefaultBodyLimit::max(MAX_UPLOAD_BODY_BYTES)),
             )
+            .route("/zones/{name}/pull", routing::post(pull_zone))
             .route("/zones/{name}/export", routing::get(export_zone))
+            .route("/zones/{name}/lint", routing::get(lint_zone))
             .route("/zones/{name}/snapshots", routing::get(list_zone_snapshots))
             .route(
                 "/zones/{name}/snapshots/diff",
@@ -62,7 +66,7 @@ impl ZoneApi {
         path = "/zones/{name}/status",
         tag = "Zone",
         summary = "Check how far each secondary has caught up with a zone",
-        description = "Queries every configured secondary for the SOA serial it currently serves and compares it with the zone's serial. Probes run live and in parallel; an unreachable secondary is reported with the failure reason. With no secondaries configured the list is empty.",
+        description = "Queries every secondary the zone NOTIFYs (its own NOTIFY targets, or the configured secondaries, plus its also-notify targets) for the SOA serial it currently serves and compares it with the zone's serial. Probes run live and in parallel; an unreachable secondary is reported with the failure reason. With no targets the list is empty.",
         params(
             ("name" = String, Path, description = "The name of the DNS zone.")
         ),
@@ -112,6 +116,31 @@ pub(crate) async fn export_zone(
         .into_response())
 }
 
+#[utoipa::path(
+        get,
+        path = "/zones/{name}/lint",
+        tag = "Zone",
+        summary = "Check a zone for cross-record consistency problems",
+        description = "Checks the zone as a whole, where record validation sees one record at a time. Reports in-zone CNAME, MX and SRV targets that do not exist, MX, NS and SRV targets that are CNAMEs, delegations whose name servers have no glue, glue no delegation names, records hidden below a delegation point, and apex NS records whose TTL splits the RRset. Each finding has a `severity` (`error` or `warning`) and a stable `code`; findings are listed errors first. Nothing is changed.",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone to lint.")
+        ),
+        responses(
+            (status = 200, description = "The zone's lint findings, empty when it is consistent", body = ZoneLintResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse)
+        )
+)]
+/// Check a zone for cross-record consistency problems.
+pub(crate) async fn lint_zone(
+    RequestCaller(caller): RequestCaller,
+    Path(params): Path<ZoneNameParam>,
+) -> Result<Response, ApiError> {
+    let lint = ZoneService::lint(&caller, &params.name).await?;
+    Ok((StatusCode::OK, Json(lint)).into_response())
+}
+
 #[utoipa::path(
         get,
         path = "/zones/{name}/snapshots",
@@ -154,7 +183,7 @@ pub(crate) async fn list_zone_snapshots(
         description = "Returns the SOA snapshot at the given serial together with the zone's record set at that serial, reconstructed from the change history.",
         params(
             ("name" = String, Path, description = "The name of the DNS zone."),
-            ("serial" = i32, Path, description = "The snapshot serial to inspect.")
+            ("serial" = u32, Path, description = "The snapshot serial to inspect.")
         ),
         responses(
             (status = 200, description = "The snapshot and its reconstructed records", body = SnapshotDetailResponse),
@@ -186,7 +215,9 @@ pub(crate) async fn get_zone_snapshot(
         summary = "Roll a zone back to a snapshot serial",
         description = "Restores the zone's record set and SOA metadata to the state captured at the target serial. The zone serial still advances to a new value (serials never go backward) and a single NOTIFY is sent. The zone name is not part of a snapshot and is never changed. With dry_run the rollback is computed and reported without applying any change.",
         params(
-            ("name" = String, Path, description = "The name of the DNS zone to roll back.")
+            ("name" = String, Path, description = "The name of the DNS zone to roll back."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed, or the zone exists for `*`."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed; `*` applies only if the zone does not exist.")
         ),
         request_body = RollbackZoneRequest,
         responses(
@@ -195,6 +226,7 @@ pub(crate) async fn get_zone_snapshot(
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "A global API token is required", body = ErrorResponse),
             (status = 404, description = "Zone or snapshot not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
@@ -202,10 +234,18 @@ pub(crate) async fn get_zone_snapshot(
 /// Roll a zone back to the state captured at a snapshot serial.
 pub(crate) async fn rollback_zone(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     Path(params): Path<ZoneNameParam>,
     JsonBody(body): JsonBody<RollbackZoneRequest>,
 ) -> Result<Response, ApiError> {
-    let response = ZoneService::rollback(&caller, &params.name, body.serial, body.dry_run).await?;
+    let response = ZoneService::rollback(
+        &caller,
+        &params.name,
+        body.serial,
+        body.dry_run,
+        &precondition,
+    )
+    .await?;
     Ok((StatusCode::OK, Json(response)).into_response())
 }
 
@@ -220,14 +260,14 @@ pub(crate) struct SnapshotListQuery {
 #[derive(Debug, Deserialize)]
 pub(crate) struct ZoneSnapshotParam {
     name: String,
-    serial: i32,
+    serial: u32,
 }
 
 /// Query parameters selecting the two serials to diff.
 #[derive(Debug, Deserialize)]
 pub(crate) struct SnapshotDiffQuery {
-    from: i32,
-    to: Option<i32>,
+    from: u32,
+    to: Option<u32>,
 }
 
 #[utoipa::path(
@@ -238,8 +278,8 @@ pub(crate) struct SnapshotDiffQuery {
         description = "Reports the RRsets added, removed, and changed between `from` and `to`. Omitting `to` compares against the current serial. Each serial must be the current one or an existing snapshot.",
         params(
             ("name" = String, Path, description = "The name of the DNS zone."),
-            ("from" = i32, Query, description = "The serial to diff from."),
-            ("to" = Option<i32>, Query, description = "The serial to diff to; defaults to the current serial.")
+            ("from" = u32, Query, description = "The serial to diff from."),
+            ("to" = Option<u32>, Query, description = "The serial to diff to; defaults to the current serial.")
         ),
         responses(
             (status = 200, description = "The record differences between the two serials", body = SnapshotDiffResponse),
@@ -271,7 +311,7 @@ pub(crate) async fn diff_zone_snapshots(
             ("ttl" = Option<i32>, Query, description = "Filter by TTL."),
             ("min_ttl" = Option<i32>, Query, description = "Filter by minimum TTL."),
             ("max_ttl" = Option<i32>, Query, description = "Filter by maximum TTL."),
-            ("serial" = Option<i32>, Query, description = "Filter by serial."),
+            ("serial" = Option<u32>, Query, description = "Filter by serial."),
             ("search" = Option<String>, Query, description = "Partially search zones."),
             ("limit" = Option<u32>, Query, description = "Maximum number of zones to return."),
             ("offset" = Option<u64>, Query, description = "Number of zones to skip.")
@@ -308,7 +348,8 @@ pub(crate) async fn get_zones(
             ("records" = Option<bool>, Query, description = "Whether to include records for the DNS zone.")
         ),
         responses(
-            (status = 200, description = "Details of the DNS zone", body = ZoneDetailResponse),
+            (status = 200, description = "Details of the DNS zone", body = ZoneDetailResponse,
+                headers(("ETag" = String, description = "The zone serial, for If-Match on later writes"))),
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 404, description = "Zone not found", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
@@ -331,9 +372,10 @@ pub(crate) async fn get_zone(
         .map(|record| GetRecordResponse::from_record_and_zone_name(record, &raw_zone.name))
         .collect::<Vec<GetRecordResponse>>();
 
+    let etag = etag_header(&zone_etag(&raw_zone));
     let zone = GetZoneResponse::from_zone(&raw_zone);
     let json_body = json!({ "zone": zone, "records": records });
-    Ok((StatusCode::OK, Json(json_body)).into_response())
+    Ok((StatusCode::OK, etag, Json(json_body)).into_response())
 }
 
 #[utoipa::path(
@@ -341,12 +383,19 @@ pub(crate) async fn get_zone(
         path = "/zones",
         tag = "Zone",
         summary = "Create a new DNS zone",
+        description = "Creates a zone with its SOA and primary NS record. With `template` set, the zone also starts with the template's records, rendered with `template_variables`, and stays linked to the template so later template changes can be applied to it.",
+        params(
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed, or the zone exists for `*`."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed; `*` applies only if the zone does not exist.")
+        ),
         request_body = CreateZoneRequest,
         responses(
             (status = 201, description = "DNS zone created successfully", body = ZoneResponse),
             (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "Template not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
@@ -354,12 +403,14 @@ pub(crate) async fn get_zone(
 /// Create a new DNS zone.
 pub(crate) async fn create_zone(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     JsonBody(body): JsonBody<CreateZoneRequest>,
 ) -> Result<Response, ApiError> {
-    let zone = ZoneService::create(&caller, &body).await?;
+    let zone = ZoneService::create(&caller, &body, &precondition).await?;
+    let etag = etag_header(&zone_etag(&zone));
     let zone = GetZoneResponse::from_zone(&zone);
     let json_body = json!({ "zone": zone });
-    Ok((StatusCode::CREATED, Json(json_body)).into_response())
+    Ok((StatusCode::CREATED, etag, Json(json_body)).into_response())
 }
 
 #[utoipa::path(
@@ -368,7 +419,9 @@ pub(crate) async fn create_zone(
         tag = "Zone",
         summary = "Update a specific DNS zone",
         params(
-            ("name" = String, Path, description = "The name of the DNS zone to update.")
+            ("name" = String, Path, description = "The name of the DNS zone to update."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed, or the zone exists for `*`."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed; `*` applies only if the zone does not exist.")
         ),
         request_body = CreateZoneRequest,
         responses(
@@ -377,6 +430,7 @@ pub(crate) async fn create_zone(
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "A global API token is required", body = ErrorResponse),
             (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
@@ -384,13 +438,15 @@ pub(crate) async fn create_zone(
 /// Update an existing DNS zone.
 pub(crate) async fn update_zone(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     Path(params): Path<ZoneNameParam>,
     JsonBody(body): JsonBody<CreateZoneRequest>,
 ) -> Result<Response, ApiError> {
-    let zone = ZoneService::update(&caller, &params.name, &body).await?;
+    let zone = ZoneService::update(&caller, &params.name, &body, &precondition).await?;
+    let etag = etag_header(&zone_etag(&zone));
     let zone = GetZoneResponse::from_zone(&zone);
     let json_body = json!({ "zone": zone });
-    Ok((StatusCode::OK, Json(json_body)).into_response())
+    Ok((StatusCode::OK, etag, Json(json_body)).into_response())
 }
 
 #[utoipa::path(
@@ -399,22 +455,26 @@ pub(crate) async fn update_zone(
         tag = "Zone",
         summary = "Delete a specific DNS zone",
         params(
-            ("name" = String, Path, description = "The name of the DNS zone to delete.")
+            ("name" = String, Path, description = "The name of the DNS zone to delete."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed, or the zone exists for `*`."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed; `*` applies only if the zone does not exist.")
         ),
         responses(
             (status = 200, description = "DNS zone deleted successfully", body = MessageResponse),
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "A global API token is required", body = ErrorResponse),
             (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
 )]
 /// Delete a DNS zone.
 pub(crate) async fn delete_zone(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     Path(params): Path<ZoneNameParam>,
 ) -> Result<Response, ApiError> {
-    ZoneService::delete(&caller, &params.name).await?;
+    ZoneService::delete(&caller, &params.name, &precondition).await?;
     let json_body = json!({ "message": "Zone deleted successfully" });
     Ok((StatusCode::OK, Json(json_body)).into_response())
 }
@@ -426,7 +486,9 @@ pub(crate) async fn delete_zone(
         summary = "Import a BIND zone file into a zone",
         description = "Parse BIND zone file text and reconcile it with the zone using append/upsert/replace. When applied, the zone serial is incremented once and a single NOTIFY is sent. If any record fails validation nothing is applied and the errors are returned.",
         params(
-            ("name" = String, Path, description = "The name of the DNS zone to import records into.")
+            ("name" = String, Path, description = "The name of the DNS zone to import records into."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed, or the zone exists for `*`."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed; `*` applies only if the zone does not exist.")
         ),
         request_body = ImportZoneFileRequest,
         responses(
@@ -435,6 +497,7 @@ pub(crate) async fn delete_zone(
             (status = 401, description = "Unauthorized", body = ErrorResponse),
             (status = 403, description = "A global API token is required", body = ErrorResponse),
             (status = 404, description = "Zone not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
             (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
             (status = 500, description = "Internal server error", body = ErrorResponse)
         )
@@ -442,10 +505,47 @@ pub(crate) async fn delete_zone(
 /// Import a BIND zone file into a zone, reconciling records in one transaction.
 pub(crate) async fn import_zone(
     RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
     Path(params): Path<ZoneNameParam>,
     JsonBody(body): JsonBody<ImportZoneFileRequest>,
 ) -> Result<Response, ApiError> {
-    let response = RecordService::import_zone_file(&caller, &params.name, &body).await?;
+    let response =
+        RecordService::import_zone_file(&caller, &params.name, &body, &precondition).await?;
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
+#[utoipa::path(
+        post,
+        path = "/zones/{name}/pull",
+        tag = "Zone",
+        summary = "Pull a zone from another primary by AXFR",
+        description = "Transfers the zone from a live primary, optionally TSIG-signed with a stored key, and reconciles the transferred records with the zone exactly like a zone-file import (append/upsert/replace, with dry run). The zone must already exist; its SOA fields and serial stay bindizr's own.",
+        params(
+            ("name" = String, Path, description = "The name of the DNS zone to pull."),
+            ("If-Match" = Option<String>, Header, description = "Apply only if the zone's ETag (its serial, as returned by GET /zones/{name}) is listed, or the zone exists for `*`."),
+            ("If-None-Match" = Option<String>, Header, description = "Apply only if the zone's ETag is not listed; `*` applies only if the zone does not exist.")
+        ),
+        request_body = PullZoneRequest,
+        responses(
+            (status = 200, description = "Transfer details, import summary, and validation errors", body = PullZoneResponse),
+            (status = 400, description = "Bad request, invalid input", body = ErrorResponse),
+            (status = 401, description = "Unauthorized", body = ErrorResponse),
+            (status = 403, description = "A global API token is required", body = ErrorResponse),
+            (status = 404, description = "Zone or TSIG key not found", body = ErrorResponse),
+            (status = 412, description = "An If-Match or If-None-Match precondition does not hold", body = ErrorResponse),
+            (status = 415, description = "Unsupported media type, expected JSON request body", body = ErrorResponse),
+            (status = 500, description = "Internal server error", body = ErrorResponse),
+            (status = 502, description = "The transfer from the primary failed", body = ErrorResponse)
+        )
+)]
+/// Pull a zone from another primary and import it.
+pub(crate) async fn pull_zone(
+    RequestCaller(caller): RequestCaller,
+    RequestPrecondition(precondition): RequestPrecondition,
+    Path(params): Path<ZoneNameParam>,
+    JsonBody(body): JsonBody<PullZoneRequest>,
+) -> Result<Response, ApiError> {
+    let response = dns::pull::pull_zone(&caller, &params.name, &body, &precondition).await?;
     Ok((StatusCode::OK, Json(response)).into_response())
 }
 
@@ -0,0 +1,95 @@
+//! The `audit` command: who changed which zone serial, and how.
+
+use bindizr_service::types::AuditEntryResponse;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use clap::Args;
+
+use crate::{
+    cli::{
+        error::CliError,
+        output::{AuditRow, OutputFormat, print_response},
+    },
+    socket::{
+        client::DaemonSocketClient,
+        types::{DaemonCommandKind, ListAuditEntriesParams},
+    },
+};
+
+/// Filters for the `audit` command.
+#[derive(Args, Debug)]
+pub(crate) struct AuditArgs {
+    /// Only changes to this zone
+    #[arg(long)]
+    zone: Option<String>,
+    /// Only changes by this actor (e.g. "token:ci", "tsig:ddns-key", "uid:0")
+    #[arg(long)]
+    actor: Option<String>,
+    /// Only changes through this transport (http, external-dns, socket,
+    /// nsupdate, internal)
+    #[arg(long)]
+    transport: Option<String>,
+    /// Only changes at or after this time (RFC 3339, or YYYY-MM-DD for the
+    /// start of that day in UTC)
+    #[arg(long, value_name = "TIME", value_parser = parse_since)]
+    since: Option<DateTime<Utc>>,
+    /// Only changes at or before this time (RFC 3339, or YYYY-MM-DD for the
+    /// end of that day in UTC)
+    #[arg(long, value_name = "TIME", value_parser = parse_until)]
+    until: Option<DateTime<Utc>>,
+    /// Maximum number of entries to return (default: 100)
+    #[arg(long)]
+    limit: Option<u32>,
+    /// Output format (json, yaml, table)
+    #[arg(short, long, default_value = "table")]
+    output: OutputFormat,
+}
+
+/// Handle the `audit` command by reading the daemon's audit log.
+pub(crate) async fn handle_command(args: AuditArgs) -> Result<(), CliError> {
+    let data = DaemonSocketClient::new()
+        .send_command(
+            DaemonCommandKind::ListAuditEntries,
+            ListAuditEntriesParams {
+                zone_name: args.zone,
+                actor: args.actor,
+                transport: args.transport,
+                since: args.since,
+                until: args.until,
+                limit: args.limit,
+            },
+        )
+        .await?
+        .data;
+
+    print_response(&data, args.output, |entries: &Vec<AuditEntryResponse>| {
+        entries.iter().map(AuditRow::from).collect()
+    })?;
+
+    Ok(())
+}
+
+fn parse_since(value: &str) -> Result<DateTime<Utc>, String> {
+    parse_time(value, NaiveTime::MIN)
+}
+
+fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    parse_time(
+        value,
+        NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999).unwrap(),
+    )
+}
+
+/// An RFC 3339 timestamp, or a bare date at `time_of_day` UTC.
+fn parse_time(value: &str, time_of_day: NaiveTime) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(time_of_day).and_utc())
+        .map_err(|_| {
+            format!(
+                "'{}' is neither an RFC 3339 time nor a YYYY-MM-DD date",
+                value
+            )
+        })
+}
@@ -0,0 +1,88 @@
+use bindizr_core::log_debug;
+use bindizr_service::types::{CreateCatalogRequest, GetCatalogResponse};
+use clap::Subcommand;
+
+use crate::{
+    cli::error::CliError,
+    socket::{
+        client::DaemonSocketClient,
+        types::{CatalogNameParams, DaemonCommandKind},
+    },
+};
+
+/// Subcommands for managing named catalog zones.
+#[derive(Subcommand, Debug)]
+pub(crate) enum CatalogCommand {
+    /// Create a named catalog zone, served next to catalog.bind
+    Create {
+        /// Catalog zone name secondaries transfer (e.g. "pool-a.catalog.example")
+        #[arg(long)]
+        name: String,
+        /// Description of the catalog
+        #[arg(long, value_name = "TEXT")]
+        description: Option<String>,
+    },
+    /// List the named catalogs
+    #[command(alias = "ls")]
+    List,
+    /// Delete a named catalog; refused while it still lists zones
+    Delete {
+        /// Name of the catalog to delete
+        name: String,
+    },
+}
+
+/// Handle the `catalog` subcommand by dispatching to the daemon over the socket.
+pub(crate) async fn handle_command(subcommand: CatalogCommand) -> Result<(), CliError> {
+    let client = DaemonSocketClient::new();
+
+    match subcommand {
+        CatalogCommand::Create { name, description } => {
+            let res = client
+                .send_command(
+                    DaemonCommandKind::CatalogCreate,
+                    CreateCatalogRequest { name, description },
+                )
+                .await?;
+            log_debug!("Catalog creation result: {:?}", res);
+
+            let catalog: GetCatalogResponse = serde_json::from_value(res.data)
+                .map_err(|e| format!("Failed to parse catalog creation response: {}", e))?;
+            println!("Catalog created successfully: {}", catalog.name);
+        }
+        CatalogCommand::List => {
+            let res = client
+                .send_command(DaemonCommandKind::CatalogList, ())
+                .await?;
+            log_debug!("Catalog list result: {:?}", res);
+
+            let catalogs: Vec<GetCatalogResponse> = serde_json::from_value(res.data)
+                .map_err(|e| format!("Failed to parse catalog list response: {}", e))?;
+            if catalogs.is_empty() {
+                println!("No named catalogs found (every zone is listed in catalog.bind)");
+                return Ok(());
+            }
+
+            println!("Catalogs:");
+            println!("{:<40} {:<40}", "NAME", "DESCRIPTION");
+            println!("{}", "-".repeat(81));
+            for catalog in catalogs {
+                println!(
+                    "{:<40} {:<40}",
+                    catalog.name,
+                    catalog.description.as_deref().unwrap_or("-")
+                );
+            }
+        }
+        CatalogCommand::Delete { name } => {
+            let res = client
+                .send_command(DaemonCommandKind::CatalogDelete, CatalogNameParams { name })
+                .await?;
+            log_debug!("Catalog deletion result: {:?}", res);
+
+            println!("{}", res.message);
+        }
+    }
+
+    Ok(())
+}
@@ -85,6 +85,7 @@ fn print_config(config: &BindizrConfig) {
 
     print_section("database");
     print_value("type", config.database.database_type);
+    print_value("auto_migrate", config.database.auto_migrate);
     println!();
 
     print_section("database.mysql");
@@ -110,14 +111,45 @@ fn print_config(config: &BindizrConfig) {
     print_value("notify_on_startup", config.dns.notify_on_startup);
     print_value("notify_retries", config.dns.notify_retries);
     print_value("notify_timeout_secs", config.dns.notify_timeout_secs);
+    print_value(
+        "sync_monitor_interval_secs",
+        config.dns.sync_monitor_interval_secs,
+    );
+    print_value(
+        "sync_renotify_after_secs",
+        config.dns.sync_renotify_after_secs,
+    );
     print_value(
         "nsupdate_allow_unsigned",
         config.dns.nsupdate_allow_unsigned,
     );
+    print_value("answer_queries", config.dns.answer_queries);
+    print_value("edns_udp_payload_size", config.dns.edns_udp_payload_size);
+    print_value("tls_enabled", config.dns.tls_enabled);
+    print_value("tls_listen_port", config.dns.tls_listen_port);
+    print_value("tls_cert_path", &config.dns.tls_cert_path);
+    print_value("tls_key_path", &config.dns.tls_key_path);
+    print_value("tls_client_ca_path", &config.dns.tls_client_ca_path);
+    println!();
+
+    print_section("history");
+    print_value("keep_serials", config.history.keep_serials);
+    print_value("keep_days", config.history.keep_days);
+    print_value("prune_interval_secs", config.history.prune_interval_secs);
+    print_value("audit_keep_days", config.history.audit_keep_days);
     println!();
 
     print_section("logging");
     print_value("log_level", config.logging.log_level);
+    println!();
+
+    print_section("webhook");
+    print_value("max_attempts", config.webhook.max_attempts);
+    print_value("initial_backoff_secs", config.webhook.initial_backoff_secs);
+    print_value("max_backoff_secs", config.webhook.max_backoff_secs);
+    print_value("timeout_secs", config.webhook.timeout_secs);
+    print_value("poll_interval_secs", config.webhook.poll_interval_secs);
+    print_value("retention_days", config.webhook.retention_days);
 }
 
 fn print_section(name: &str) {
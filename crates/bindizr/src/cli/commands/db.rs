@@ -0,0 +1,89 @@
+//! The `db` subcommands. They connect to the database directly, so they work
+//! whether or not the daemon is running.
+
+use bindizr_core::config::{self, DatabaseConfig};
+use bindizr_db as database;
+use clap::Subcommand;
+
+use crate::cli::{
+    error::CliError,
+    output::{MigrationRow, print_table},
+};
+
+/// Subcommands for managing the database schema.
+#[derive(Subcommand, Debug)]
+pub(crate) enum DbCommand {
+    /// Apply pending schema migrations
+    Migrate {
+        /// Path to the configuration file (default: /etc/bindizr/bindizr.conf.toml)
+        #[arg(short, long, value_name = "FILE")]
+        config: Option<String>,
+    },
+    /// Show which schema migrations the database has applied
+    Status {
+        /// Path to the configuration file (default: /etc/bindizr/bindizr.conf.toml)
+        #[arg(short, long, value_name = "FILE")]
+        config: Option<String>,
+    },
+}
+
+/// Handle the `db` subcommand.
+pub(crate) async fn handle_command(subcommand: DbCommand) -> Result<(), CliError> {
+    match subcommand {
+        DbCommand::Migrate { config } => migrate(config.as_deref()).await,
+        DbCommand::Status { config } => status(config.as_deref()).await,
+    }
+}
+
+fn database_config(config_file: Option<&str>) -> Result<DatabaseConfig, CliError> {
+    let path = config::resolve_config_path(config_file);
+    Ok(config::load_config_file(&path)?.database)
+}
+
+async fn migrate(config_file: Option<&str>) -> Result<(), CliError> {
+    let applied = database::migrate(&database_config(config_file)?)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if applied.is_empty() {
+        println!(
+            "Database schema is up to date (version {})",
+            database::SCHEMA_VERSION
+        );
+        return Ok(());
+    }
+
+    for migration in &applied {
+        println!(
+            "Applied migration {}: {}",
+            migration.version, migration.description
+        );
+    }
+    println!(
+        "Database schema is now at version {}",
+        database::SCHEMA_VERSION
+    );
+    Ok(())
+}
+
+async fn status(config_file: Option<&str>) -> Result<(), CliError> {
+    let status = database::schema_status(&database_config(config_file)?)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    println!(
+        "Database schema version: {} (this bindizr: {})",
+        status.database_version,
+        database::SCHEMA_VERSION
+    );
+    print_table(status.migrations.iter().map(MigrationRow::from).collect());
+
+    if status.is_ahead_of_binary() {
+        println!(
+            "The database was migrated by a newer bindizr; upgrade bindizr before starting it."
+        );
+    } else if status.pending().next().is_some() {
+        println!("Pending migrations apply at startup, or run `bindizr db migrate`.");
+    }
+    Ok(())
+}
@@ -1,12 +1,19 @@
+pub(super) mod audit;
+pub(super) mod catalog;
 pub(super) mod config;
+pub(super) mod db;
 pub(super) mod doctor;
 pub(super) mod record;
+pub(super) mod reload;
 pub(super) mod restart;
+pub(super) mod secondary;
 pub(super) mod start;
 pub(super) mod status;
 pub(super) mod stop;
+pub(super) mod template;
 pub(super) mod token;
 pub(super) mod tsig_key;
+pub(super) mod webhook;
 pub(super) mod zone;
 
 use std::time::Duration;
@@ -1,8 +1,15 @@
+//! The `record` subcommands. RRset-level editing lives in the sibling `rrset`
+//! module.
+
+mod rrset;
+
 use bindizr_service::types::{
-    BulkRecordsResponse, CreateBulkRecordsRequest, CreateRecordRequest, GetRecordResponse,
-    GetRecordsFilter, RecordItem, RecordValueRequest, UpdateRecordPatch,
+    ApplyChangesetRequest, BulkRecordsResponse, ChangesetOperation, ChangesetResponse,
+    CreateBulkRecordsRequest, CreateRecordRequest, GetRecordResponse, GetRecordsFilter, RecordItem,
+    RecordValueRequest, UpdateRecordPatch,
 };
 use clap::Subcommand;
+pub(crate) use rrset::RecordRrsetCommand;
 
 use crate::{
     cli::{
@@ -14,7 +21,10 @@ use crate::{
     },
     socket::{
         client::DaemonSocketClient,
-        types::{BulkCreateRecordsParams, DaemonCommandKind, RecordIdParams, UpdateRecordParams},
+        types::{
+            ApplyChangesetParams, BulkCreateRecordsParams, CreateRecordParams, DaemonCommandKind,
+            DeleteRecordParams, RecordIdParams, UpdateRecordParams,
+        },
     },
 };
 
@@ -26,7 +36,7 @@ pub(crate) enum RecordCommand {
         /// Record name
         #[arg(long)]
         name: String,
-        /// Record type (A, AAAA, CNAME, MX, etc.)
+        /// Record type (A, AAAA, CNAME, MX, etc., or TYPEnnn)
         #[arg(long = "type", alias = "record-type")]
         record_type: String,
         /// Record value
@@ -38,9 +48,12 @@ pub(crate) enum RecordCommand {
         /// TTL in seconds, defaulting to the zone TTL (records of one RRset share a TTL)
         #[arg(long)]
         ttl: Option<i32>,
-        /// Priority (MX and SRV only)
+        /// Priority (MX, SRV, SVCB and HTTPS only)
         #[arg(long)]
         priority: Option<i32>,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
     },
 
     /// List records
@@ -95,10 +108,13 @@ pub(crate) enum RecordCommand {
 Input format (JSON or YAML): an array of records, or an object with a
 'records' array. Fields per record:
   name         owner name relative to the zone, or '@' for the apex (required)
-  record_type  A, AAAA, CNAME, MX, NS, PTR, SRV, TXT (required)
-  value        record value; TXT also accepts an array of strings (required)
+  record_type  A, AAAA, CAA, CNAME, HTTPS, MX, NS, PTR, SRV, SSHFP,
+               SVCB, TLSA, TXT, or TYPEnnn for any other type (required)
+  value        record value; TXT also accepts an array of strings, and
+               TYPEnnn takes the generic form '\\# <length> <hex>', which
+               the other types except TXT accept too (required)
   ttl          seconds (optional; defaults to the zone TTL)
-  priority     MX/SRV priority (optional)
+  priority     MX/SRV/SVCB/HTTPS priority (optional)
 
 JSON example:
   [{\"name\": \"www\", \"record_type\": \"A\", \"value\": \"192.0.2.1\", \"ttl\": 300},
@@ -122,6 +138,53 @@ YAML example:
         /// Preview the inserts as a +/-/~ diff without applying them (implies --dry-run)
         #[arg(long)]
         preview: bool,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
+        /// Output format (json, yaml, table)
+        #[arg(short, long, default_value = "table")]
+        output: OutputFormat,
+    },
+
+    /// Apply ordered add/update/delete operations to a zone as one change
+    #[command(after_help = "\
+Input format (JSON or YAML): an array of operations, or an object with an
+'operations' array. Operations run in order, each validated against the zone
+as the ones before it left it; if any fails, nothing is applied. Fields:
+  op      add, update or delete (required)
+  id      ID of an existing record (update and delete)
+  record  the record's fields, as in 'bindizr record bulk' (add and update);
+          update replaces every field of the record
+
+JSON example:
+  [{\"op\": \"delete\", \"id\": 12},
+   {\"op\": \"add\", \"record\": {\"name\": \"www\", \"record_type\": \"A\", \"value\": \"192.0.2.1\"}},
+   {\"op\": \"update\", \"id\": 7, \"record\": {\"name\": \"@\", \"record_type\": \"MX\", \"value\": \"mail\", \"priority\": 20}}]
+
+YAML example:
+  - op: delete
+    id: 12
+  - op: add
+    record:
+      name: www
+      record_type: A
+      value: 192.0.2.1")]
+    Changeset {
+        /// Path to a JSON or YAML file (an array of operations, or an object
+        /// with an 'operations' array), or '-' to read from stdin
+        file: String,
+        /// Zone name
+        #[arg(short, long)]
+        zone: String,
+        /// Validate the whole changeset without applying any change
+        #[arg(long)]
+        dry_run: bool,
+        /// Preview the changeset as a +/-/~ diff without applying it (implies --dry-run)
+        #[arg(long)]
+        preview: bool,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
         /// Output format (json, yaml, table)
         #[arg(short, long, default_value = "table")]
         output: OutputFormat,
@@ -143,7 +206,7 @@ YAML example:
         /// Record name
         #[arg(long)]
         name: Option<String>,
-        /// Record type (A, AAAA, CNAME, MX, etc.)
+        /// Record type (A, AAAA, CNAME, MX, etc., or TYPEnnn)
         #[arg(long = "type", alias = "record-type")]
         record_type: Option<String>,
         /// Record value
@@ -152,9 +215,12 @@ YAML example:
         /// TTL (records of one RRset share a TTL)
         #[arg(long)]
         ttl: Option<i32>,
-        /// Priority (MX and SRV only)
+        /// Priority (MX, SRV, SVCB and HTTPS only)
         #[arg(long)]
         priority: Option<i32>,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
         /// Output format (json, yaml, table)
         #[arg(short, long, default_value = "table")]
         output: OutputFormat,
@@ -164,6 +230,15 @@ YAML example:
     Delete {
         /// The record ID
         record_id: i32,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
+    },
+
+    /// Read, replace or delete a whole RRset (every record of one name and type)
+    Rrset {
+        #[command(subcommand)]
+        subcommand: RecordRrsetCommand,
     },
 }
 
@@ -179,17 +254,21 @@ pub(crate) async fn handle_command(subcommand: RecordCommand) -> Result<(), CliE
             zone,
             ttl,
             priority,
+            expect_serial,
         } => {
             let response = client
                 .send_command(
                     DaemonCommandKind::CreateRecord,
-                    CreateRecordRequest {
-                        name,
-                        record_type,
-                        value: RecordValueRequest::String(value),
-                        zone_name: zone,
-                        ttl,
-                        priority,
+                    CreateRecordParams {
+                        request: CreateRecordRequest {
+                            name,
+                            record_type,
+                            value: RecordValueRequest::String(value),
+                            zone_name: zone,
+                            ttl,
+                            priority,
+                        },
+                        expect_serial,
                     },
                 )
                 .await?;
@@ -251,6 +330,7 @@ pub(crate) async fn handle_command(subcommand: RecordCommand) -> Result<(), CliE
             zone,
             dry_run,
             preview,
+            expect_serial,
             output,
         } => {
             let content = super::read_input(&file)?;
@@ -281,6 +361,7 @@ pub(crate) async fn handle_command(subcommand: RecordCommand) -> Result<(), CliE
                             // Preview never applies; it is a dry run rendered as a diff.
                             dry_run: dry_run || preview,
                         },
+                        expect_serial,
                     },
                 )
                 .await?;
@@ -298,6 +379,59 @@ pub(crate) async fn handle_command(subcommand: RecordCommand) -> Result<(), CliE
                 bulk.records.iter().map(RecordRow::from).collect()
             })?;
         }
+        RecordCommand::Changeset {
+            file,
+            zone,
+            dry_run,
+            preview,
+            expect_serial,
+            output,
+        } => {
+            let content = super::read_input(&file)?;
+            let parsed: serde_json::Value = serde_yaml::from_str(&content)
+                .map_err(|e| format!("Invalid JSON/YAML in '{}': {}", file, e))?;
+            let operations = match parsed {
+                serde_json::Value::Array(_) => parsed,
+                serde_json::Value::Object(mut obj) => obj
+                    .remove("operations")
+                    .ok_or("Input object must contain an 'operations' array")?,
+                _ => {
+                    return Err(
+                        "Expected an array of operations or an object with an 'operations' array"
+                            .into(),
+                    );
+                }
+            };
+            let operations: Vec<ChangesetOperation> = serde_json::from_value(operations)
+                .map_err(|e| format!("Invalid operation in '{}': {}", file, e))?;
+
+            let response = client
+                .send_command(
+                    DaemonCommandKind::ApplyChangeset,
+                    ApplyChangesetParams {
+                        zone_name: zone,
+                        request: ApplyChangesetRequest {
+                            operations,
+                            dry_run: dry_run || preview,
+                        },
+                        expect_serial,
+                    },
+                )
+                .await?;
+
+            if preview && output == OutputFormat::Table {
+                let changeset: ChangesetResponse = parse_response(&response.data)?;
+                print!("{}", render_change_preview(&changeset.diff.entries));
+                return Ok(());
+            }
+
+            if output == OutputFormat::Table {
+                println!("{}", response.message);
+            }
+            print_response(&response.data, output, |changeset: &ChangesetResponse| {
+                changeset.records.iter().map(RecordRow::from).collect()
+            })?;
+        }
         RecordCommand::Get { id, output } => {
             let data = client
                 .send_command(DaemonCommandKind::GetRecord, RecordIdParams { id })
@@ -313,6 +447,7 @@ pub(crate) async fn handle_command(subcommand: RecordCommand) -> Result<(), CliE
             value,
             ttl,
             priority,
+            expect_serial,
             output,
         } => {
             let data = client
@@ -327,6 +462,7 @@ pub(crate) async fn handle_command(subcommand: RecordCommand) -> Result<(), CliE
                             ttl,
                             priority,
                         },
+                        expect_serial,
                     },
                 )
                 .await?
@@ -334,15 +470,22 @@ pub(crate) async fn handle_command(subcommand: RecordCommand) -> Result<(), CliE
 
             print_records(&data, output)?;
         }
-        RecordCommand::Delete { record_id } => {
+        RecordCommand::Delete {
+            record_id,
+            expect_serial,
+        } => {
             let response = client
                 .send_command(
                     DaemonCommandKind::DeleteRecord,
-                    RecordIdParams { id: record_id },
+                    DeleteRecordParams {
+                        id: record_id,
+                        expect_serial,
+                    },
                 )
                 .await?;
             println!("{}", response.message);
         }
+        RecordCommand::Rrset { subcommand } => rrset::handle_command(&client, subcommand).await?,
     }
 
     Ok(())
@@ -0,0 +1,154 @@
+//! The `record rrset` subcommands: get, set, and delete.
+
+use bindizr_service::types::ReplaceRrsetRequest;
+use clap::Subcommand;
+
+use crate::{
+    cli::{
+        error::CliError,
+        output::{OutputFormat, RrsetRow, print_response},
+    },
+    socket::{
+        client::DaemonSocketClient,
+        types::{DaemonCommandKind, DeleteRrsetParams, ReplaceRrsetParams, RrsetParams},
+    },
+};
+
+/// Subcommands for managing a whole RRset.
+#[derive(Subcommand, Debug)]
+pub(crate) enum RecordRrsetCommand {
+    /// Show every record of one name and type
+    Get {
+        /// Owner name relative to the zone ('@' for the apex)
+        name: String,
+        /// Record type (A, AAAA, CNAME, MX, etc., or TYPEnnn)
+        record_type: String,
+        /// Zone name
+        #[arg(short, long)]
+        zone: String,
+        /// Output format (json, yaml, table)
+        #[arg(short, long, default_value = "table")]
+        output: OutputFormat,
+    },
+    /// Replace every record of one name and type in a single change
+    #[command(after_help = "\
+Values are in presentation form; MX, SRV, SVCB and HTTPS values lead with
+their priority, as in a zone file:
+
+  bindizr record rrset set @ MX --zone example.com --value '10 mx1' --value '20 mx2'")]
+    Set {
+        /// Owner name relative to the zone ('@' for the apex)
+        name: String,
+        /// Record type (A, AAAA, CNAME, MX, etc., or TYPEnnn)
+        record_type: String,
+        /// Zone name
+        #[arg(short, long)]
+        zone: String,
+        /// TTL shared by the whole RRset, defaulting to the zone TTL
+        #[arg(long)]
+        ttl: Option<i32>,
+        /// A value of the RRset; repeat for each value
+        #[arg(long = "value", required = true)]
+        values: Vec<String>,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
+        /// Output format (json, yaml, table)
+        #[arg(short, long, default_value = "table")]
+        output: OutputFormat,
+    },
+    /// Delete every record of one name and type
+    Delete {
+        /// Owner name relative to the zone ('@' for the apex)
+        name: String,
+        /// Record type (A, AAAA, CNAME, MX, etc., or TYPEnnn)
+        record_type: String,
+        /// Zone name
+        #[arg(short, long)]
+        zone: String,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
+    },
+}
+
+/// Handle a `record rrset` subcommand by forwarding it to the daemon.
+pub(super) async fn handle_command(
+    client: &DaemonSocketClient,
+    subcommand: RecordRrsetCommand,
+) -> Result<(), CliError> {
+    match subcommand {
+        RecordRrsetCommand::Get {
+            name,
+            record_type,
+            zone,
+            output,
+        } => {
+            let data = client
+                .send_command(
+                    DaemonCommandKind::GetRrset,
+                    RrsetParams {
+                        zone_name: zone,
+                        name,
+                        record_type,
+                    },
+                )
+                .await?
+                .data;
+
+            print_response(&data, output, RrsetRow::rows)?;
+        }
+        RecordRrsetCommand::Set {
+            name,
+            record_type,
+            zone,
+            ttl,
+            values,
+            expect_serial,
+            output,
+        } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::ReplaceRrset,
+                    ReplaceRrsetParams {
+                        rrset: RrsetParams {
+                            zone_name: zone,
+                            name,
+                            record_type,
+                        },
+                        request: ReplaceRrsetRequest { ttl, values },
+                        expect_serial,
+                    },
+                )
+                .await?;
+
+            if output == OutputFormat::Table {
+                println!("{}", response.message);
+            }
+            print_response(&response.data, output, RrsetRow::rows)?;
+        }
+        RecordRrsetCommand::Delete {
+            name,
+            record_type,
+            zone,
+            expect_serial,
+        } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::DeleteRrset,
+                    DeleteRrsetParams {
+                        rrset: RrsetParams {
+                            zone_name: zone,
+                            name,
+                            record_type,
+                        },
+                        expect_serial,
+                    },
+                )
+                .await?;
+            println!("{}", response.message);
+        }
+    }
+
+    Ok(())
+}
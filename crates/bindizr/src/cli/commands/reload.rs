@@ -0,0 +1,27 @@
+use crate::{
+    cli::error::CliError,
+    socket::{
+        client::DaemonSocketClient,
+        types::{DaemonCommandKind, DaemonReloadResponse},
+    },
+};
+
+/// Handle the `reload` subcommand: have the daemon re-read its configuration
+/// file and report the settings that still need a restart.
+pub(crate) async fn handle_command() -> Result<(), CliError> {
+    let res = DaemonSocketClient::new()
+        .send_control_command(DaemonCommandKind::Reload)
+        .await?;
+    println!("{}", res.message);
+
+    let reload: DaemonReloadResponse = serde_json::from_value(res.data)
+        .map_err(|e| CliError::from(format!("Failed to parse reload response: {}", e)))?;
+    if !reload.restart_required.is_empty() {
+        println!(
+            "Restart required to apply: {} (run `bindizr restart`)",
+            reload.restart_required.join(", ")
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use bindizr_service::types::SecondariesStatusResponse;
+use clap::Subcommand;
+
+use crate::{
+    cli::{
+        error::CliError,
+        output::{OutputFormat, SecondarySyncRow, parse_response, print_response, print_table},
+    },
+    socket::{client::DaemonSocketClient, types::DaemonCommandKind},
+};
+
+/// Subcommands for the secondaries bindizr feeds.
+#[derive(Subcommand, Debug)]
+pub(crate) enum SecondaryCommand {
+    /// Show every zone's sync state on every secondary, as of the sync
+    /// monitor's last round
+    Status {
+        /// Output format (json, yaml, table)
+        #[arg(short, long, default_value = "table")]
+        output: OutputFormat,
+    },
+}
+
+/// Handle the `secondary` subcommand by dispatching to the daemon over the socket.
+pub(crate) async fn handle_command(subcommand: SecondaryCommand) -> Result<(), CliError> {
+    let client = DaemonSocketClient::new();
+
+    match subcommand {
+        SecondaryCommand::Status { output } => {
+            let response = client
+                .send_command(DaemonCommandKind::SecondariesStatus, ())
+                .await?;
+
+            if output == OutputFormat::Table {
+                let status: SecondariesStatusResponse = parse_response(&response.data)?;
+                if status.interval_secs == 0 || status.last_round_at.is_none() {
+                    println!("{}.", response.message);
+                    return Ok(());
+                }
+                print_table(SecondarySyncRow::rows(&status));
+                return Ok(());
+            }
+            print_response(
+                &response.data,
+                output,
+                |status: &SecondariesStatusResponse| SecondarySyncRow::rows(status),
+            )?;
+        }
+    }
+
+    Ok(())
+}
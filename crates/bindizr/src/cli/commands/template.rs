@@ -0,0 +1,305 @@
+use bindizr_core::log_debug;
+use bindizr_service::types::{
+    CreateZoneTemplateRequest, GetZoneTemplateResponse, RecordItem, RecordValueRequest,
+    UpdateZoneTemplateRequest, ZoneTemplateSyncResponse,
+};
+use clap::Subcommand;
+
+use crate::{
+    cli::{
+        error::CliError,
+        output::{parse_response, render_diff_lines},
+    },
+    socket::{
+        client::DaemonSocketClient,
+        types::{ApplyTemplateParams, DaemonCommandKind, TemplateNameParams, UpdateTemplateParams},
+    },
+};
+
+/// Subcommands for managing zone templates.
+#[derive(Subcommand, Debug)]
+pub(crate) enum TemplateCommand {
+    /// Create a zone template from a file of records
+    #[command(after_help = "\
+Input format (JSON or YAML): an array of records, or an object with a
+'records' array, with the fields of 'bindizr record bulk'. Record names and
+values may hold {{variable}} placeholders. {{zone}} is the zone's name; every
+other variable is supplied with --var when a zone is created from the
+template.
+
+YAML example:
+  - name: '@'
+    record_type: MX
+    value: '{{mail_host}}.{{zone}}.'
+    priority: 10
+  - name: '@'
+    record_type: TXT
+    value: v=spf1 mx -all
+  - name: '@'
+    record_type: CAA
+    value: 0 issue \"letsencrypt.org\"
+
+Then: bindizr zone create --name example.com ... --template NAME --var mail_host=mx1")]
+    Create {
+        /// Template name
+        #[arg(long)]
+        name: String,
+        /// Path to a JSON or YAML file of records, or '-' to read from stdin
+        #[arg(long)]
+        file: String,
+        /// Description of the template
+        #[arg(long, value_name = "TEXT")]
+        description: Option<String>,
+    },
+    /// List the zone templates
+    #[command(alias = "ls")]
+    List,
+    /// Show a template's records and the zones created from it
+    Get {
+        /// Name of the template
+        name: String,
+    },
+    /// Replace a template's records and show the diff for every linked zone
+    #[command(after_help = "\
+Without --apply, linked zones keep their records and the diff shows what
+'bindizr template apply' would change. Only what the template changed is
+carried over: records edited or deleted by hand in a zone are left alone.")]
+    Update {
+        /// Name of the template
+        name: String,
+        /// Path to a JSON or YAML file of records, or '-' to read from stdin
+        #[arg(long)]
+        file: String,
+        /// Description of the template (cleared when omitted)
+        #[arg(long, value_name = "TEXT")]
+        description: Option<String>,
+        /// Apply the change to every linked zone as well
+        #[arg(long)]
+        apply: bool,
+        /// Show the diffs without saving the template or changing any zone
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Apply a template's records to every linked zone still behind them
+    Apply {
+        /// Name of the template
+        name: String,
+        /// Show the diffs without changing any zone
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Delete a template; refused while zones are linked to it
+    Delete {
+        /// Name of the template to delete
+        name: String,
+    },
+}
+
+/// Handle the `template` subcommand by dispatching to the daemon over the socket.
+pub(crate) async fn handle_command(subcommand: TemplateCommand) -> Result<(), CliError> {
+    let client = DaemonSocketClient::new();
+
+    match subcommand {
+        TemplateCommand::Create {
+            name,
+            file,
+            description,
+        } => {
+            let records = read_records(&file)?;
+            let res = client
+                .send_command(
+                    DaemonCommandKind::TemplateCreate,
+                    CreateZoneTemplateRequest {
+                        name,
+                        description,
+                        records,
+                    },
+                )
+                .await?;
+            log_debug!("Template creation result: {:?}", res);
+
+            let template: GetZoneTemplateResponse = parse_response(&res.data)?;
+            println!("Template created successfully: {}", template.name);
+        }
+        TemplateCommand::List => {
+            let res = client
+                .send_command(DaemonCommandKind::TemplateList, ())
+                .await?;
+            log_debug!("Template list result: {:?}", res);
+
+            let templates: Vec<GetZoneTemplateResponse> = parse_response(&res.data)?;
+            if templates.is_empty() {
+                println!("No templates found");
+                return Ok(());
+            }
+
+            println!("Templates:");
+            println!(
+                "{:<30} {:<8} {:<6} {:<40}",
+                "NAME", "RECORDS", "ZONES", "DESCRIPTION"
+            );
+            println!("{}", "-".repeat(87));
+            for template in templates {
+                println!(
+                    "{:<30} {:<8} {:<6} {:<40}",
+                    template.name,
+                    template.records.len(),
+                    template.zones.len(),
+                    template.description.as_deref().unwrap_or("-")
+                );
+            }
+        }
+        TemplateCommand::Get { name } => {
+            let res = client
+                .send_command(DaemonCommandKind::TemplateGet, TemplateNameParams { name })
+                .await?;
+            log_debug!("Template get result: {:?}", res);
+
+            let template: GetZoneTemplateResponse = parse_response(&res.data)?;
+            print_template(&template);
+        }
+        TemplateCommand::Update {
+            name,
+            file,
+            description,
+            apply,
+            dry_run,
+        } => {
+            let records = read_records(&file)?;
+            let res = client
+                .send_command(
+                    DaemonCommandKind::TemplateUpdate,
+                    UpdateTemplateParams {
+                        name,
+                        request: UpdateZoneTemplateRequest {
+                            description,
+                            records,
+                            apply,
+                            dry_run,
+                        },
+                    },
+                )
+                .await?;
+            log_debug!("Template update result: {:?}", res);
+
+            let sync: ZoneTemplateSyncResponse = parse_response(&res.data)?;
+            if sync.dry_run {
+                println!("Dry run: template '{}' not saved", sync.template.name);
+            } else {
+                println!("{}", res.message);
+            }
+            print_sync(&sync);
+        }
+        TemplateCommand::Apply { name, dry_run } => {
+            let res = client
+                .send_command(
+                    DaemonCommandKind::TemplateApply,
+                    ApplyTemplateParams { name, dry_run },
+                )
+                .await?;
+            log_debug!("Template apply result: {:?}", res);
+
+            let sync: ZoneTemplateSyncResponse = parse_response(&res.data)?;
+            print_sync(&sync);
+        }
+        TemplateCommand::Delete { name } => {
+            let res = client
+                .send_command(
+                    DaemonCommandKind::TemplateDelete,
+                    TemplateNameParams { name },
+                )
+                .await?;
+            log_debug!("Template deletion result: {:?}", res);
+
+            println!("{}", res.message);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a template's records from a JSON or YAML file, as `record bulk` does.
+fn read_records(file: &str) -> Result<Vec<RecordItem>, String> {
+    let content = super::read_input(file)?;
+    // YAML is a superset of JSON, so one parse accepts both formats.
+    let parsed: serde_json::Value = serde_yaml::from_str(&content)
+        .map_err(|e| format!("Invalid JSON/YAML in '{}': {}", file, e))?;
+    let records = match parsed {
+        serde_json::Value::Array(_) => parsed,
+        serde_json::Value::Object(mut obj) => obj
+            .remove("records")
+            .ok_or("Input object must contain a 'records' array")?,
+        _ => {
+            return Err("Expected an array of records or an object with a 'records' array".into());
+        }
+    };
+    serde_json::from_value(records).map_err(|e| format!("Invalid record in '{}': {}", file, e))
+}
+
+fn print_template(template: &GetZoneTemplateResponse) {
+    println!("Name: {}", template.name);
+    println!(
+        "Description: {}",
+        template.description.as_deref().unwrap_or("-")
+    );
+    println!(
+        "Variables: {}",
+        if template.variables.is_empty() {
+            "-".to_string()
+        } else {
+            template.variables.join(", ")
+        }
+    );
+    println!(
+        "Zones: {}",
+        if template.zones.is_empty() {
+            "-".to_string()
+        } else {
+            template.zones.join(", ")
+        }
+    );
+    println!("Records:");
+    println!(
+        "  {:<30} {:<8} {:<8} {:<40}",
+        "NAME", "TYPE", "TTL", "VALUE"
+    );
+    for record in &template.records {
+        let value = match &record.value {
+            RecordValueRequest::String(value) => value.clone(),
+            RecordValueRequest::Segments(segments) => segments
+                .iter()
+                .map(|segment| format!("\"{}\"", segment))
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        let value = match record.priority {
+            Some(priority) => format!("{} {}", priority, value),
+            None => value,
+        };
+        println!(
+            "  {:<30} {:<8} {:<8} {:<40}",
+            record.name,
+            record.record_type,
+            record.ttl.map_or("-".to_string(), |ttl| ttl.to_string()),
+            value
+        );
+    }
+}
+
+/// Print each linked zone's status and the `+`/`-`/`~` lines of its diff.
+fn print_sync(sync: &ZoneTemplateSyncResponse) {
+    if sync.zones.is_empty() {
+        println!("No zones are linked to template '{}'", sync.template.name);
+        return;
+    }
+
+    for zone in &sync.zones {
+        let detail = match (&zone.error, zone.serial) {
+            (Some(error), _) => format!(": {}", error),
+            (None, Some(serial)) => format!(" (serial {})", serial),
+            (None, None) => String::new(),
+        };
+        println!("{}: {}{}", zone.zone, zone.status, detail);
+        print!("{}", render_diff_lines(&zone.diff.entries));
+    }
+}
@@ -1,12 +1,12 @@
 use bindizr_core::log_debug;
-use bindizr_service::types::GetTokenResponse;
+use bindizr_service::types::{GetTokenResponse, RotateTokenRequest};
 use clap::Subcommand;
 
 use crate::{
     cli::error::CliError,
     socket::{
         client::DaemonSocketClient,
-        types::{CreateTokenParams, DaemonCommandKind, TokenNameParams},
+        types::{CreateTokenParams, DaemonCommandKind, RotateTokenParams, TokenNameParams},
     },
 };
 
@@ -26,12 +26,26 @@ pub(crate) enum TokenCommand {
         #[arg(long, value_name = "N")]
         expires_in_days: Option<i64>,
         /// Make the token global: it may manage every zone and the zone
-        /// plane without policies. Fixed at creation.
+        /// plane without policies. Same as `--role admin`.
         #[arg(long)]
         global: bool,
+        /// What the token may do: scoped (default; rights come from zone
+        /// token policies), viewer, record-editor, zone-admin or admin.
+        /// Fixed at creation.
+        #[arg(long, value_name = "ROLE")]
+        role: Option<String>,
     },
     /// List all API tokens
     List,
+    /// Issue a new secret for an API token, keeping its name, scope and policies
+    Rotate {
+        /// Name of the token to rotate
+        name: String,
+        /// Minutes the old secret keeps working alongside the new one
+        /// (default: 60; 0 revokes it at once)
+        #[arg(long, value_name = "N")]
+        overlap_minutes: Option<i64>,
+    },
     /// Delete an API token by name
     Delete {
         /// Name of the token to delete
@@ -49,8 +63,13 @@ pub(crate) async fn handle_command(subcommand: TokenCommand) -> Result<(), CliEr
             description,
             expires_in_days,
             global,
-        } => create_token(&client, name, description, expires_in_days, global).await,
+            role,
+        } => create_token(&client, name, description, expires_in_days, global, role).await,
         TokenCommand::List => list_tokens(&client).await,
+        TokenCommand::Rotate {
+            name,
+            overlap_minutes,
+        } => rotate_token(&client, name, overlap_minutes).await,
         TokenCommand::Delete { name } => delete_token(&client, name).await,
     }
 }
@@ -61,6 +80,7 @@ async fn create_token(
     description: Option<String>,
     expires_in_days: Option<i64>,
     global: bool,
+    role: Option<String>,
 ) -> Result<(), CliError> {
     let res = client
         .send_command(
@@ -70,6 +90,7 @@ async fn create_token(
                 description,
                 expires_in_days,
                 global,
+                role,
             },
         )
         .await?;
@@ -85,6 +106,7 @@ async fn create_token(
         println!("Token: {}", secret);
     }
     println!("Global: {}", if token.global { "yes" } else { "no" });
+    println!("Role: {}", token.role);
     if let Some(desc) = token.description {
         println!("Description: {}", desc);
     }
@@ -118,10 +140,10 @@ async fn list_tokens(client: &DaemonSocketClient) -> Result<(), CliError> {
 
     println!("API Tokens:");
     println!(
-        "{:<25} {:<8} {:<20} {:<20}",
-        "NAME", "GLOBAL", "DESCRIPTION", "EXPIRES AT"
+        "{:<25} {:<14} {:<20} {:<20}",
+        "NAME", "ROLE", "DESCRIPTION", "EXPIRES AT"
     );
-    println!("{}", "-".repeat(75));
+    println!("{}", "-".repeat(81));
 
     for token in tokens {
         let desc = token.description.unwrap_or_else(|| "-".to_string());
@@ -131,17 +153,50 @@ async fn list_tokens(client: &DaemonSocketClient) -> Result<(), CliError> {
             .unwrap_or_else(|| "Never".to_string());
 
         println!(
-            "{:<25} {:<8} {:<20} {:<20}",
-            token.name,
-            if token.global { "yes" } else { "no" },
-            desc,
-            expires
+            "{:<25} {:<14} {:<20} {:<20}",
+            token.name, token.role, desc, expires
         );
     }
 
     Ok(())
 }
 
+async fn rotate_token(
+    client: &DaemonSocketClient,
+    name: String,
+    overlap_minutes: Option<i64>,
+) -> Result<(), CliError> {
+    let res = client
+        .send_command(
+            DaemonCommandKind::TokenRotate,
+            RotateTokenParams {
+                name,
+                request: RotateTokenRequest { overlap_minutes },
+            },
+        )
+        .await?;
+
+    log_debug!("Token rotation result: {:?}", res);
+
+    let token: GetTokenResponse = serde_json::from_value(res.data)
+        .map_err(|e| format!("Failed to parse token rotation response: {}", e))?;
+
+    println!("API token rotated successfully:");
+    println!("Name: {}", token.name);
+    if let Some(secret) = &token.token {
+        println!("Token: {}", secret);
+    }
+    match token.previous_token_expires_at {
+        Some(until) => println!(
+            "Previous token valid until: {}",
+            until.format("%Y-%m-%d %H:%M:%S")
+        ),
+        None => println!("Previous token revoked"),
+    }
+
+    Ok(())
+}
+
 async fn delete_token(client: &DaemonSocketClient, name: String) -> Result<(), CliError> {
     let res = client
         .send_command(DaemonCommandKind::TokenDelete, TokenNameParams { name })
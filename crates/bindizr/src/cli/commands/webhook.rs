@@ -0,0 +1,166 @@
+use bindizr_core::log_debug;
+use bindizr_service::types::{
+    CreateWebhookRequest, GetWebhookDeliveryResponse, GetWebhookResponse,
+};
+use clap::Subcommand;
+
+use crate::{
+    cli::error::CliError,
+    socket::{
+        client::DaemonSocketClient,
+        types::{DaemonCommandKind, WebhookDeliveriesParams, WebhookNameParams},
+    },
+};
+
+/// Subcommands for managing outbound webhooks.
+#[derive(Subcommand, Debug)]
+pub(crate) enum WebhookCommand {
+    /// Create a webhook that receives signed zone change events
+    Create {
+        /// Unique webhook name
+        #[arg(long)]
+        name: String,
+        /// http or https URL the events are POSTed to
+        #[arg(long)]
+        url: String,
+        /// Only send events for this zone (default: every zone)
+        #[arg(long, value_name = "ZONE")]
+        zone: Option<String>,
+        /// HMAC-SHA256 signing key (default: generated and printed once)
+        #[arg(long)]
+        secret: Option<String>,
+        /// Description of the webhook
+        #[arg(long, value_name = "TEXT")]
+        description: Option<String>,
+    },
+    /// List webhooks
+    #[command(alias = "ls")]
+    List,
+    /// Delete a webhook and its queued deliveries
+    Delete {
+        /// Name of the webhook to delete
+        name: String,
+    },
+    /// Show a webhook's recent deliveries, newest first
+    Deliveries {
+        /// Name of the webhook
+        name: String,
+        /// Maximum number of deliveries to show (default: 50)
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+}
+
+/// Handle the `webhook` subcommand by dispatching to the daemon over the socket.
+pub(crate) async fn handle_command(subcommand: WebhookCommand) -> Result<(), CliError> {
+    let client = DaemonSocketClient::new();
+
+    match subcommand {
+        WebhookCommand::Create {
+            name,
+            url,
+            zone,
+            secret,
+            description,
+        } => {
+            let res = client
+                .send_command(
+                    DaemonCommandKind::WebhookCreate,
+                    CreateWebhookRequest {
+                        name,
+                        url,
+                        zone,
+                        secret,
+                        description,
+                    },
+                )
+                .await?;
+            log_debug!("Webhook creation result: {:?}", res);
+
+            let webhook: GetWebhookResponse = serde_json::from_value(res.data)
+                .map_err(|e| format!("Failed to parse webhook creation response: {}", e))?;
+            println!("Webhook created successfully:");
+            println!("Name: {}", webhook.name);
+            println!("URL: {}", webhook.url);
+            println!("Zone: {}", webhook.zone.as_deref().unwrap_or("(all zones)"));
+            if let Some(secret) = &webhook.secret {
+                println!("Secret: {}", secret);
+            }
+            if let Some(desc) = webhook.description {
+                println!("Description: {}", desc);
+            }
+        }
+        WebhookCommand::List => {
+            let res = client
+                .send_command(DaemonCommandKind::WebhookList, ())
+                .await?;
+            log_debug!("Webhook list result: {:?}", res);
+
+            let webhooks: Vec<GetWebhookResponse> = serde_json::from_value(res.data)
+                .map_err(|e| format!("Failed to parse webhook list response: {}", e))?;
+            if webhooks.is_empty() {
+                println!("No webhooks found");
+                return Ok(());
+            }
+
+            println!("Webhooks:");
+            println!("{:<24} {:<32} {:<48}", "NAME", "ZONE", "URL");
+            println!("{}", "-".repeat(106));
+            for webhook in webhooks {
+                println!(
+                    "{:<24} {:<32} {:<48}",
+                    webhook.name,
+                    webhook.zone.as_deref().unwrap_or("*"),
+                    webhook.url
+                );
+            }
+        }
+        WebhookCommand::Delete { name } => {
+            let res = client
+                .send_command(DaemonCommandKind::WebhookDelete, WebhookNameParams { name })
+                .await?;
+            log_debug!("Webhook deletion result: {:?}", res);
+
+            println!("{}", res.message);
+        }
+        WebhookCommand::Deliveries { name, limit } => {
+            let res = client
+                .send_command(
+                    DaemonCommandKind::WebhookDeliveries,
+                    WebhookDeliveriesParams { name, limit },
+                )
+                .await?;
+            log_debug!("Webhook deliveries result: {:?}", res);
+
+            let deliveries: Vec<GetWebhookDeliveryResponse> = serde_json::from_value(res.data)
+                .map_err(|e| format!("Failed to parse webhook deliveries response: {}", e))?;
+            if deliveries.is_empty() {
+                println!("No deliveries found");
+                return Ok(());
+            }
+
+            println!(
+                "{:<8} {:<14} {:<32} {:<12} {:<10} {:<8} {:<20}",
+                "ID", "EVENT", "ZONE", "SERIAL", "STATUS", "ATTEMPTS", "LAST ERROR"
+            );
+            println!("{}", "-".repeat(110));
+            for delivery in deliveries {
+                println!(
+                    "{:<8} {:<14} {:<32} {:<12} {:<10} {:<8} {:<20}",
+                    delivery.id,
+                    delivery.event,
+                    delivery.zone,
+                    delivery
+                        .serial
+                        .or(delivery.previous_serial)
+                        .map_or("-".to_string(), |serial| serial.to_string()),
+                    delivery.status,
+                    delivery.attempts,
+                    delivery.last_error.as_deref().unwrap_or("-")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+//! The `zone catalog` subcommands.
+
+use std::collections::BTreeMap;
+
+use bindizr_service::types::{GetZoneCatalogPropertiesResponse, SetZoneCatalogPropertiesRequest};
+use clap::Subcommand;
+
+use crate::{
+    cli::{error::CliError, output::parse_response},
+    socket::{
+        client::DaemonSocketClient,
+        types::{DaemonCommandKind, SetZoneCatalogParams, ZoneNameParams},
+    },
+};
+
+/// Subcommands for a zone's catalog membership and RFC 9432 properties.
+#[derive(Subcommand, Debug)]
+pub(crate) enum ZoneCatalogCommand {
+    /// Show the catalog listing a zone and its member properties
+    Get {
+        /// The name of the zone
+        name: String,
+    },
+    /// Replace a zone's catalog membership and properties; omitted options are cleared
+    Set {
+        /// The name of the zone
+        name: String,
+        /// Catalog listing the zone (default: catalog.bind)
+        #[arg(long, value_name = "CATALOG")]
+        catalog: Option<String>,
+        /// RFC 9432 group property, telling consumers how to provision the zone
+        #[arg(long, value_name = "GROUP")]
+        group: Option<String>,
+        /// RFC 9432 change of ownership: the catalog taking the zone over
+        #[arg(long, value_name = "CATALOG")]
+        coo: Option<String>,
+        /// Custom property served as TXT under `ext`; repeat for several
+        #[arg(long, value_name = "NAME=VALUE", value_parser = parse_ext_property)]
+        ext: Vec<(String, String)>,
+    },
+}
+
+pub(super) async fn handle_command(
+    client: &DaemonSocketClient,
+    subcommand: ZoneCatalogCommand,
+) -> Result<(), CliError> {
+    let response = match subcommand {
+        ZoneCatalogCommand::Get { name } => {
+            client
+                .send_command(DaemonCommandKind::ZoneCatalogGet, ZoneNameParams { name })
+                .await?
+        }
+        ZoneCatalogCommand::Set {
+            name,
+            catalog,
+            group,
+            coo,
+            ext,
+        } => {
+            client
+                .send_command(
+                    DaemonCommandKind::ZoneCatalogSet,
+                    SetZoneCatalogParams {
+                        zone_name: name,
+                        request: SetZoneCatalogPropertiesRequest {
+                            catalog,
+                            group,
+                            coo,
+                            ext: ext.into_iter().collect::<BTreeMap<_, _>>(),
+                        },
+                    },
+                )
+                .await?
+        }
+    };
+
+    print_catalog_properties(&response.data)?;
+    Ok(())
+}
+
+fn parse_ext_property(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", value))
+}
+
+fn print_catalog_properties(data: &serde_json::Value) -> Result<(), String> {
+    let properties: GetZoneCatalogPropertiesResponse = parse_response(data)?;
+
+    println!("Zone: {}", properties.zone);
+    println!("Catalog: {}", properties.catalog);
+    println!("Group: {}", properties.group.as_deref().unwrap_or("-"));
+    println!(
+        "Change of ownership: {}",
+        properties.coo.as_deref().unwrap_or("-")
+    );
+    for (name, value) in &properties.ext {
+        println!("ext.{}: {}", name, value);
+    }
+
+    Ok(())
+}
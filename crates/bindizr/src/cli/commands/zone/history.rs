@@ -0,0 +1,74 @@
+//! The `zone history` subcommands: retention maintenance for snapshots and
+//! the change journal.
+
+use bindizr_service::types::PruneHistoryResponse;
+use clap::Subcommand;
+
+use crate::{
+    cli::{
+        error::CliError,
+        output::{OutputFormat, PruneRow, print_response},
+    },
+    socket::{
+        client::DaemonSocketClient,
+        types::{DaemonCommandKind, PruneZoneHistoryParams},
+    },
+};
+
+/// Subcommands for maintaining zone history.
+#[derive(Subcommand, Debug)]
+pub(crate) enum ZoneHistoryCommand {
+    /// Drop snapshots and change journal rows outside the retention limits
+    Prune {
+        /// The zone to prune (omit to prune every zone)
+        name: Option<String>,
+        /// Keep the newest N serials (overrides [history] keep_serials)
+        #[arg(long)]
+        keep_serials: Option<u32>,
+        /// Keep serials from the last N days (overrides [history] keep_days)
+        #[arg(long)]
+        keep_days: Option<u32>,
+        /// Report what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Output format (json, yaml, table)
+        #[arg(short, long, default_value = "table")]
+        output: OutputFormat,
+    },
+}
+
+pub(super) async fn handle_command(
+    client: &DaemonSocketClient,
+    subcommand: ZoneHistoryCommand,
+) -> Result<(), CliError> {
+    match subcommand {
+        ZoneHistoryCommand::Prune {
+            name,
+            keep_serials,
+            keep_days,
+            dry_run,
+            output,
+        } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::PruneZoneHistory,
+                    PruneZoneHistoryParams {
+                        name,
+                        keep_serials,
+                        keep_days,
+                        dry_run,
+                    },
+                )
+                .await?;
+
+            if output == OutputFormat::Table {
+                println!("{}", response.message);
+            }
+            print_response(&response.data, output, |pruned: &PruneHistoryResponse| {
+                pruned.zones.iter().map(PruneRow::from).collect()
+            })?;
+        }
+    }
+
+    Ok(())
+}
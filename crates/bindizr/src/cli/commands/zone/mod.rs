@@ -1,31 +1,43 @@
 //! The `zone` subcommands. Each nested family owns its own grammar, dispatch,
 //! and output rendering in a sibling module.
 
+mod catalog;
+mod history;
+mod notify_target;
 mod snapshot;
 mod token_policy;
+mod transfer_acl;
 mod tsig_policy;
 
 use bindizr_service::types::{
     CreateZoneRequest, ExportZoneFileResponse, GetZoneResponse, GetZonesFilter,
     ImportMode as ServiceImportMode, ImportZoneFileRequest, ImportZoneFileResponse,
-    NotifyZoneRequest, UpdateZonePatch, ZoneStatusResponse,
+    NotifyZoneRequest, PullZoneRequest, PullZoneResponse, UpdateZonePatch, ZoneLintResponse,
+    ZoneStatusResponse,
 };
+pub(crate) use catalog::ZoneCatalogCommand;
 use clap::{Args, Subcommand, ValueEnum};
+pub(crate) use history::ZoneHistoryCommand;
+pub(crate) use notify_target::ZoneNotifyTargetCommand;
 pub(crate) use snapshot::ZoneSnapshotCommand;
 pub(crate) use token_policy::ZoneTokenPolicyCommand;
+pub(crate) use transfer_acl::ZoneTransferAclCommand;
 pub(crate) use tsig_policy::ZoneTsigPolicyCommand;
 
 use crate::{
     cli::{
         error::CliError,
         output::{
-            ImportSummaryRow, ItemOrPage, OutputFormat, SecondaryStatusRow, ZoneRow,
-            parse_response, print_response, print_table, render_change_preview,
+            ImportSummaryRow, ItemOrPage, LintFindingRow, OutputFormat, SecondaryStatusRow,
+            ZoneRow, parse_response, print_response, print_table, render_change_preview,
         },
     },
     socket::{
         client::DaemonSocketClient,
-        types::{DaemonCommandKind, ImportZoneFileParams, UpdateZoneParams, ZoneNameParams},
+        types::{
+            DaemonCommandKind, DeleteZoneParams, ImportZoneFileParams, PullZoneParams,
+            UpdateZoneParams, ZoneNameParams,
+        },
     },
 };
 
@@ -46,9 +58,18 @@ pub(crate) enum ZoneCommand {
         /// TTL
         #[arg(long)]
         ttl: i32,
-        /// Starting serial, 1-2137483647 (optional, auto-generated if not provided)
+        /// Starting serial, any value but 0 (optional, generated by the serial policy if not provided)
+        #[arg(long)]
+        serial: Option<u32>,
+        /// How the serial advances: increment (default), date (YYYYMMDDnn) or unixtime
         #[arg(long)]
-        serial: Option<i32>,
+        serial_policy: Option<String>,
+        /// Start the zone from this template's records and link it to the template
+        #[arg(long, value_name = "TEMPLATE")]
+        template: Option<String>,
+        /// Value for a template variable; repeat for each variable the template uses
+        #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_template_variable, requires = "template")]
+        template_variables: Vec<(String, String)>,
     },
 
     /// Update a zone, changing only the fields you pass
@@ -79,6 +100,12 @@ pub(crate) enum ZoneCommand {
         /// SOA minimum TTL (seconds)
         #[arg(long)]
         minimum_ttl: Option<i32>,
+        /// How the serial advances: increment, date (YYYYMMDDnn) or unixtime
+        #[arg(long)]
+        serial_policy: Option<String>,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
         /// Output format (json, yaml, table)
         #[arg(short, long, default_value = "table")]
         output: OutputFormat,
@@ -110,7 +137,7 @@ pub(crate) enum ZoneCommand {
         max_ttl: Option<i32>,
         /// Filter by serial
         #[arg(long)]
-        serial: Option<i32>,
+        serial: Option<u32>,
         /// Search zones by partial text
         #[arg(short = 'q', long)]
         search: Option<String>,
@@ -138,6 +165,9 @@ pub(crate) enum ZoneCommand {
     Delete {
         /// The name of the zone
         name: String,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
     },
 
     /// Import a BIND zone file into a zone
@@ -164,6 +194,40 @@ $INCLUDE is not supported.")]
         /// Preview the change as a +/-/~ diff without applying it (implies --dry-run)
         #[arg(long)]
         preview: bool,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
+        /// Output format (json, yaml, table)
+        #[arg(short, long, default_value = "table")]
+        output: OutputFormat,
+    },
+
+    /// Pull a zone from another primary by AXFR and import it
+    #[command(after_help = "\
+The zone must already exist in bindizr. Its records are transferred from the
+primary and reconciled exactly like `zone import`; the SOA fields and serial
+stay bindizr's own. The primary must allow transfers to this host.")]
+    Pull {
+        /// The name of the zone
+        name: String,
+        /// The primary to transfer from: host[:port] or an IP address (port 53 by default)
+        #[arg(long = "from", value_name = "SERVER")]
+        server: String,
+        /// Name of a stored TSIG key to sign the transfer with
+        #[arg(long)]
+        tsig_key: Option<String>,
+        /// How transferred records are reconciled with existing records
+        #[arg(long, value_enum, default_value_t = ImportMode::Append)]
+        mode: ImportMode,
+        /// Transfer and validate without applying any change
+        #[arg(long)]
+        dry_run: bool,
+        /// Preview the change as a +/-/~ diff without applying it (implies --dry-run)
+        #[arg(long)]
+        preview: bool,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
         /// Output format (json, yaml, table)
         #[arg(short, long, default_value = "table")]
         output: OutputFormat,
@@ -175,12 +239,32 @@ $INCLUDE is not supported.")]
         name: String,
     },
 
+    /// Check a zone for dangling targets, missing glue and other cross-record problems
+    #[command(after_help = "\
+Reports in-zone CNAME/MX/SRV targets that do not exist, MX/NS/SRV targets that
+are CNAMEs, delegations missing glue, glue no delegation names, records hidden
+below a delegation point, and apex NS TTLs that split the RRset. Exits non-zero
+when any finding is an error, so it can gate a pipeline.")]
+    Lint {
+        /// The name of the zone
+        name: String,
+        /// Output format (json, yaml, table)
+        #[arg(short, long, default_value = "table")]
+        output: OutputFormat,
+    },
+
     /// Inspect or roll back a zone's snapshots (serial history)
     Snapshot {
         #[command(subcommand)]
         subcommand: ZoneSnapshotCommand,
     },
 
+    /// Maintain zone history (snapshots and the change journal)
+    History {
+        #[command(subcommand)]
+        subcommand: ZoneHistoryCommand,
+    },
+
     /// Show how far each secondary has caught up with a zone
     Status {
         /// The name of the zone
@@ -204,9 +288,27 @@ $INCLUDE is not supported.")]
         #[command(subcommand)]
         subcommand: ZoneTokenPolicyCommand,
     },
+
+    /// Manage a zone's NOTIFY and also-notify targets
+    NotifyTarget {
+        #[command(subcommand)]
+        subcommand: ZoneNotifyTargetCommand,
+    },
+
+    /// Manage a zone's transfer ACL (which secondaries may transfer it)
+    TransferAcl {
+        #[command(subcommand)]
+        subcommand: ZoneTransferAclCommand,
+    },
+
+    /// Manage a zone's catalog membership and RFC 9432 member properties
+    Catalog {
+        #[command(subcommand)]
+        subcommand: ZoneCatalogCommand,
+    },
 }
 
-/// How `zone import` reconciles parsed records with the records already in the
+/// How `zone import` and `zone pull` reconcile parsed records with the records already in the
 /// zone. Mirrors the service-layer `ImportMode`; serialized as its lowercase
 /// wire name.
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -251,6 +353,9 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
             admin_email,
             ttl,
             serial,
+            serial_policy,
+            template,
+            template_variables,
         } => {
             let response = client
                 .send_command(
@@ -261,10 +366,13 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
                         admin_email,
                         ttl,
                         serial,
+                        serial_policy,
                         refresh: None,
                         retry: None,
                         expire: None,
                         minimum_ttl: None,
+                        template,
+                        template_variables: template_variables.into_iter().collect(),
                     },
                 )
                 .await?;
@@ -336,6 +444,8 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
             retry,
             expire,
             minimum_ttl,
+            serial_policy,
+            expect_serial,
             output,
         } => {
             let data = client
@@ -354,7 +464,9 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
                             expire,
                             minimum_ttl,
                             serial: None,
+                            serial_policy,
                         },
+                        expect_serial,
                     },
                 )
                 .await?
@@ -362,9 +474,18 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
 
             print_zones(&data, output)?;
         }
-        ZoneCommand::Delete { name } => {
+        ZoneCommand::Delete {
+            name,
+            expect_serial,
+        } => {
             let response = client
-                .send_command(DaemonCommandKind::DeleteZone, ZoneNameParams { name })
+                .send_command(
+                    DaemonCommandKind::DeleteZone,
+                    DeleteZoneParams {
+                        name,
+                        expect_serial,
+                    },
+                )
                 .await?;
             println!("{}", response.message);
         }
@@ -376,12 +497,40 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
             let export: ExportZoneFileResponse = parse_response(&data)?;
             print!("{}", export.zone_file);
         }
+        ZoneCommand::Lint { name, output } => {
+            let response = client
+                .send_command(DaemonCommandKind::LintZone, ZoneNameParams { name })
+                .await?;
+            let lint: ZoneLintResponse = parse_response(&response.data)?;
+
+            if output == OutputFormat::Table {
+                println!("{}", response.message);
+                if !lint.findings.is_empty() {
+                    print_table(lint.findings.iter().map(LintFindingRow::from).collect());
+                }
+            } else {
+                print_response(&response.data, output, |lint: &ZoneLintResponse| {
+                    lint.findings
+                        .iter()
+                        .map(LintFindingRow::from)
+                        .collect::<Vec<_>>()
+                })?;
+            }
+
+            if lint.errors > 0 {
+                return Err(CliError::from(format!(
+                    "zone '{}' has {} lint error(s)",
+                    lint.zone, lint.errors
+                )));
+            }
+        }
         ZoneCommand::Import {
             name,
             file,
             mode,
             dry_run,
             preview,
+            expect_serial,
             output,
         } => {
             let content = super::read_input(&file)?;
@@ -396,6 +545,7 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
                             // Preview never applies; it is a dry run rendered as a diff.
                             dry_run: dry_run || preview,
                         },
+                        expect_serial,
                     },
                 )
                 .await?;
@@ -421,9 +571,58 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
                 vec![ImportSummaryRow::from(&import.summary)]
             })?;
         }
+        ZoneCommand::Pull {
+            name,
+            server,
+            tsig_key,
+            mode,
+            dry_run,
+            preview,
+            expect_serial,
+            output,
+        } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::PullZone,
+                    PullZoneParams {
+                        zone_name: name,
+                        request: PullZoneRequest {
+                            server,
+                            tsig_key,
+                            mode: mode.into(),
+                            // Preview never applies; it is a dry run rendered as a diff.
+                            dry_run: dry_run || preview,
+                        },
+                        expect_serial,
+                    },
+                )
+                .await?;
+
+            if output == OutputFormat::Table {
+                let pull: PullZoneResponse = parse_response(&response.data)?;
+                if pull.import.errors.is_empty() {
+                    println!("{}", response.message);
+                } else {
+                    eprintln!("{}", response.message);
+                    for error in &pull.import.errors {
+                        eprintln!("  - {}", error);
+                    }
+                }
+
+                if preview {
+                    print!("{}", render_change_preview(&pull.import.diff.entries));
+                    return Ok(());
+                }
+            }
+
+            print_response(&response.data, output, |pull: &PullZoneResponse| {
+                vec![ImportSummaryRow::from(&pull.import.summary)]
+            })?;
+        }
         ZoneCommand::Snapshot { subcommand } => {
             snapshot::handle_command(&client, subcommand).await?
         }
+        ZoneCommand::History { subcommand } => history::handle_command(&client, subcommand).await?,
         ZoneCommand::Status { name, output } => {
             let response = client
                 .send_command(DaemonCommandKind::ZoneStatus, ZoneNameParams { name })
@@ -461,11 +660,25 @@ pub(crate) async fn handle_command(subcommand: ZoneCommand) -> Result<(), CliErr
         ZoneCommand::TsigPolicy { subcommand } => {
             tsig_policy::handle_command(&client, subcommand).await?
         }
+        ZoneCommand::NotifyTarget { subcommand } => {
+            notify_target::handle_command(&client, subcommand).await?
+        }
+        ZoneCommand::TransferAcl { subcommand } => {
+            transfer_acl::handle_command(&client, subcommand).await?
+        }
+        ZoneCommand::Catalog { subcommand } => catalog::handle_command(&client, subcommand).await?,
     }
 
     Ok(())
 }
 
+fn parse_template_variable(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", value))
+}
+
 fn print_zones(data: &serde_json::Value, output: OutputFormat) -> Result<(), String> {
     print_response(data, output, |zones: &ItemOrPage<GetZoneResponse>| {
         zones.items().iter().map(ZoneRow::from).collect()
@@ -0,0 +1,127 @@
+//! The `zone notify-target` subcommands.
+
+use bindizr_service::types::{CreateZoneNotifyTargetRequest, GetZoneNotifyTargetResponse};
+use clap::Subcommand;
+
+use crate::{
+    cli::{error::CliError, output::parse_response},
+    socket::{
+        client::DaemonSocketClient,
+        types::{
+            AddZoneNotifyTargetParams, DaemonCommandKind, RemoveZonePolicyParams,
+            ZonePolicyListParams,
+        },
+    },
+};
+
+/// Subcommands for managing a zone's NOTIFY targets.
+#[derive(Subcommand, Debug)]
+pub(crate) enum ZoneNotifyTargetCommand {
+    /// Add a NOTIFY target to a zone (the zone then stops notifying the global secondary list)
+    Add {
+        /// The name of the zone
+        name: String,
+        /// host[:port] or IP address, e.g. '192.0.2.30:53'
+        #[arg(long, value_name = "ADDRESS")]
+        address: String,
+        /// Notify this target on top of whichever list applies, keeping the global secondaries
+        #[arg(long)]
+        also: bool,
+        /// TSIG key NOTIFYs to this target are signed with
+        #[arg(long, value_name = "NAME")]
+        key: Option<String>,
+    },
+    /// List a zone's NOTIFY targets
+    #[command(alias = "ls")]
+    List {
+        /// The name of the zone
+        name: String,
+    },
+    /// Remove a NOTIFY target from a zone by target ID
+    Remove {
+        /// The name of the zone
+        name: String,
+        /// ID of the target to remove (see `zone notify-target list`)
+        id: i32,
+    },
+}
+
+pub(super) async fn handle_command(
+    client: &DaemonSocketClient,
+    subcommand: ZoneNotifyTargetCommand,
+) -> Result<(), CliError> {
+    match subcommand {
+        ZoneNotifyTargetCommand::Add {
+            name,
+            address,
+            also,
+            key,
+        } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::ZoneNotifyTargetAdd,
+                    AddZoneNotifyTargetParams {
+                        zone_name: name,
+                        request: CreateZoneNotifyTargetRequest {
+                            kind: Some(if also { "also-notify" } else { "notify" }.to_string()),
+                            address,
+                            tsig_key: key,
+                        },
+                    },
+                )
+                .await?;
+            println!("{}", response.message);
+        }
+        ZoneNotifyTargetCommand::List { name } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::ZoneNotifyTargetList,
+                    ZonePolicyListParams { zone_name: name },
+                )
+                .await?;
+            print_notify_targets(&response.data)?;
+        }
+        ZoneNotifyTargetCommand::Remove { name, id } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::ZoneNotifyTargetRemove,
+                    RemoveZonePolicyParams {
+                        zone_name: name,
+                        id,
+                    },
+                )
+                .await?;
+            println!("{}", response.message);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_notify_targets(data: &serde_json::Value) -> Result<(), String> {
+    let entries: Vec<GetZoneNotifyTargetResponse> = parse_response(data)?;
+
+    if entries.is_empty() {
+        println!("No NOTIFY targets found (the global secondary list applies)");
+        return Ok(());
+    }
+
+    println!("NOTIFY targets:");
+    println!(
+        "{:<5} {:<12} {:<40} {:<20}",
+        "ID", "KIND", "ADDRESS", "TSIG KEY"
+    );
+    println!("{}", "-".repeat(79));
+
+    for entry in entries {
+        println!(
+            "{:<5} {:<12} {:<40} {:<20}",
+            entry.id,
+            entry.kind,
+            entry.address,
+            entry.tsig_key.as_deref().unwrap_or("-")
+        );
+    }
+
+    Ok(())
+}
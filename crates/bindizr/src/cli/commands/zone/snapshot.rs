@@ -46,7 +46,7 @@ pub(crate) enum ZoneSnapshotCommand {
         /// The name of the zone
         name: String,
         /// Snapshot serial to inspect
-        serial: i32,
+        serial: u32,
         /// Output format (json, yaml, table)
         #[arg(short, long, default_value = "table")]
         output: OutputFormat,
@@ -56,9 +56,9 @@ pub(crate) enum ZoneSnapshotCommand {
         /// The name of the zone
         name: String,
         /// The serial to diff from
-        from_serial: i32,
+        from_serial: u32,
         /// The serial to diff to (omit to compare against the current serial)
-        to_serial: Option<i32>,
+        to_serial: Option<u32>,
         /// Output format (json, yaml, table)
         #[arg(short, long, default_value = "table")]
         output: OutputFormat,
@@ -68,10 +68,13 @@ pub(crate) enum ZoneSnapshotCommand {
         /// The name of the zone
         name: String,
         /// Target snapshot serial (the zone serial still advances)
-        serial: i32,
+        serial: u32,
         /// Compute and report the rollback without applying any change
         #[arg(long)]
         dry_run: bool,
+        /// Fail unless the zone is still at this serial (guards against concurrent edits)
+        #[arg(long)]
+        expect_serial: Option<u32>,
         /// Output format (json, yaml, table)
         #[arg(short, long, default_value = "table")]
         output: OutputFormat,
@@ -165,6 +168,7 @@ pub(super) async fn handle_command(
             name,
             serial,
             dry_run,
+            expect_serial,
             output,
         } => {
             let response = client
@@ -173,6 +177,7 @@ pub(super) async fn handle_command(
                     RollbackZoneParams {
                         name,
                         request: RollbackZoneRequest { serial, dry_run },
+                        expect_serial,
                     },
                 )
                 .await?;
@@ -0,0 +1,127 @@
+//! The `zone transfer-acl` subcommands.
+
+use bindizr_service::types::{CreateZoneTransferAclRequest, GetZoneTransferAclResponse};
+use clap::Subcommand;
+
+use crate::{
+    cli::{error::CliError, output::parse_response},
+    socket::{
+        client::DaemonSocketClient,
+        types::{
+            AddZoneTransferAclParams, DaemonCommandKind, RemoveZonePolicyParams,
+            ZonePolicyListParams,
+        },
+    },
+};
+
+/// Subcommands for managing a zone's transfer ACL.
+#[derive(Subcommand, Debug)]
+pub(crate) enum ZoneTransferAclCommand {
+    /// Add an entry to a zone's transfer ACL (the zone then stops using the global secondary list)
+    Add {
+        /// The name of the zone
+        name: String,
+        /// IP address or CIDR prefix, e.g. '198.51.100.0/24'
+        #[arg(long, value_name = "ADDRESS")]
+        address: String,
+        /// Refuse matching clients instead of admitting them; wins over any allow entry
+        #[arg(long, conflicts_with = "key")]
+        deny: bool,
+        /// TSIG key matching clients must sign their transfer requests with
+        #[arg(long, value_name = "NAME")]
+        key: Option<String>,
+    },
+    /// List a zone's transfer ACL entries
+    #[command(alias = "ls")]
+    List {
+        /// The name of the zone
+        name: String,
+    },
+    /// Remove a transfer ACL entry from a zone by entry ID
+    Remove {
+        /// The name of the zone
+        name: String,
+        /// ID of the entry to remove (see `zone transfer-acl list`)
+        id: i32,
+    },
+}
+
+pub(super) async fn handle_command(
+    client: &DaemonSocketClient,
+    subcommand: ZoneTransferAclCommand,
+) -> Result<(), CliError> {
+    match subcommand {
+        ZoneTransferAclCommand::Add {
+            name,
+            address,
+            deny,
+            key,
+        } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::ZoneTransferAclAdd,
+                    AddZoneTransferAclParams {
+                        zone_name: name,
+                        request: CreateZoneTransferAclRequest {
+                            action: Some(if deny { "deny" } else { "allow" }.to_string()),
+                            address,
+                            tsig_key: key,
+                        },
+                    },
+                )
+                .await?;
+            println!("{}", response.message);
+        }
+        ZoneTransferAclCommand::List { name } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::ZoneTransferAclList,
+                    ZonePolicyListParams { zone_name: name },
+                )
+                .await?;
+            print_transfer_acls(&response.data)?;
+        }
+        ZoneTransferAclCommand::Remove { name, id } => {
+            let response = client
+                .send_command(
+                    DaemonCommandKind::ZoneTransferAclRemove,
+                    RemoveZonePolicyParams {
+                        zone_name: name,
+                        id,
+                    },
+                )
+                .await?;
+            println!("{}", response.message);
+        }
+    }
+
+    Ok(())
+}
+
+fn print_transfer_acls(data: &serde_json::Value) -> Result<(), String> {
+    let entries: Vec<GetZoneTransferAclResponse> = parse_response(data)?;
+
+    if entries.is_empty() {
+        println!("No transfer ACL entries found (the global secondary list applies)");
+        return Ok(());
+    }
+
+    println!("Transfer ACL:");
+    println!(
+        "{:<5} {:<8} {:<40} {:<20}",
+        "ID", "ACTION", "ADDRESS", "TSIG KEY"
+    );
+    println!("{}", "-".repeat(75));
+
+    for entry in entries {
+        println!(
+            "{:<5} {:<8} {:<40} {:<20}",
+            entry.id,
+            entry.action,
+            entry.address,
+            entry.tsig_key.as_deref().unwrap_or("-")
+        );
+    }
+
+    Ok(())
+}
@@ -37,6 +37,9 @@ impl CliError {
             ErrorCode::SnapshotNotFound => {
                 Some("Run 'bindizr zone snapshot list <NAME>' to see available serials.")
             }
+            ErrorCode::PreconditionFailed => {
+                Some("Run 'bindizr zone get <NAME>' to see the current serial, then retry.")
+            }
             ErrorCode::Internal => Some("Check the daemon logs for details."),
             _ => None,
         }
@@ -9,8 +9,9 @@ mod output;
 use clap::{Parser, Subcommand};
 
 use crate::cli::commands::{
-    config::ConfigCommand, record::RecordCommand, token::TokenCommand, tsig_key::TsigKeyCommand,
-    zone::ZoneCommand,
+    audit::AuditArgs, catalog::CatalogCommand, config::ConfigCommand, db::DbCommand,
+    record::RecordCommand, secondary::SecondaryCommand, template::TemplateCommand,
+    token::TokenCommand, tsig_key::TsigKeyCommand, webhook::WebhookCommand, zone::ZoneCommand,
 };
 
 /// Top-level CLI argument parser.
@@ -36,6 +37,8 @@ pub(crate) enum Command {
     Stop,
     /// Restart the running bindizr daemon in place
     Restart,
+    /// Reload the configuration file without restarting the daemon
+    Reload,
     /// Check that the bindizr installation is healthy
     Doctor {
         /// Path to the configuration file (default: /etc/bindizr/bindizr.conf.toml)
@@ -47,6 +50,11 @@ pub(crate) enum Command {
         #[command(subcommand)]
         subcommand: ConfigCommand,
     },
+    /// Inspect and migrate the database schema
+    Db {
+        #[command(subcommand)]
+        subcommand: DbCommand,
+    },
     /// Manage API tokens
     Token {
         #[command(subcommand)]
@@ -62,11 +70,33 @@ pub(crate) enum Command {
         #[command(subcommand)]
         subcommand: ZoneCommand,
     },
+    /// Show how secondaries are keeping up with every zone
+    Secondary {
+        #[command(subcommand)]
+        subcommand: SecondaryCommand,
+    },
+    /// Manage named catalog zones
+    Catalog {
+        #[command(subcommand)]
+        subcommand: CatalogCommand,
+    },
+    /// Manage zone templates and apply their changes to linked zones
+    Template {
+        #[command(subcommand)]
+        subcommand: TemplateCommand,
+    },
+    /// Manage outbound webhooks on zone changes
+    Webhook {
+        #[command(subcommand)]
+        subcommand: WebhookCommand,
+    },
     /// Manage records
     Record {
         #[command(subcommand)]
         subcommand: RecordCommand,
     },
+    /// Show who changed which zone serial, newest first
+    Audit(AuditArgs),
 }
 
 /// Parse CLI arguments and dispatch to the matching command handler.
@@ -80,12 +110,19 @@ pub async fn execute() {
         Command::Status => commands::status::handle_command().await,
         Command::Stop => commands::stop::handle_command().await,
         Command::Restart => commands::restart::handle_command().await,
+        Command::Reload => commands::reload::handle_command().await,
         Command::Doctor { config } => commands::doctor::handle_command(config).await,
         Command::Config { subcommand } => commands::config::handle_command(subcommand).await,
+        Command::Db { subcommand } => commands::db::handle_command(subcommand).await,
         Command::Token { subcommand } => commands::token::handle_command(subcommand).await,
         Command::TsigKey { subcommand } => commands::tsig_key::handle_command(subcommand).await,
         Command::Zone { subcommand } => commands::zone::handle_command(subcommand).await,
+        Command::Secondary { subcommand } => commands::secondary::handle_command(subcommand).await,
+        Command::Catalog { subcommand } => commands::catalog::handle_command(subcommand).await,
+        Command::Template { subcommand } => commands::template::handle_command(subcommand).await,
+        Command::Webhook { subcommand } => commands::webhook::handle_command(subcommand).await,
         Command::Record { subcommand } => commands::record::handle_command(subcommand).await,
+        Command::Audit(args) => commands::audit::handle_command(args).await,
     } {
         eprintln!("Error: {}", e.message);
         if let Some(hint) = e.hint() {
@@ -3,7 +3,7 @@
 use bindizr_core::dns::record::TxtRecordValue;
 use bindizr_service::types::{RecordDiffEntry, RecordDiffValue, RecordValueRequest};
 
-/// Render one record's value as zone-file rdata: MX/SRV carry the priority
+/// Render one record's value as zone-file rdata: MX/SRV/SVCB/HTTPS carry the priority
 /// inline, TXT is quoted per character-string, other types use the value as-is.
 fn rdata(record: &RecordDiffValue, record_type: &str) -> String {
     let segments: &[String] = match &record.value {
@@ -17,7 +17,9 @@ fn rdata(record: &RecordDiffValue, record_type: &str) -> String {
             .map(|segment| TxtRecordValue::to_quoted_charstr(segment.as_bytes()))
             .collect::<Vec<_>>()
             .join(" "),
-        "MX" | "SRV" => format!("{} {}", record.priority.unwrap_or(10), segments.concat()),
+        "MX" | "SRV" | "SVCB" | "HTTPS" => {
+            format!("{} {}", record.priority.unwrap_or(10), segments.concat())
+        }
         _ => segments.concat(),
     }
 }
@@ -5,6 +5,7 @@ pub(super) mod table;
 pub(super) use diff::{render_change_preview, render_diff_lines};
 pub(super) use format::{ItemOrPage, OutputFormat, parse_response, print_response, print_table};
 pub(super) use table::{
-    ImportSummaryRow, RecordRow, RollbackSummaryRow, SecondaryStatusRow, SnapshotRecordRow,
+    AuditRow, ImportSummaryRow, LintFindingRow, MigrationRow, PruneRow, RecordRow,
+    RollbackSummaryRow, RrsetRow, SecondaryStatusRow, SecondarySyncRow, SnapshotRecordRow,
     SnapshotRow, ZoneRow,
 };
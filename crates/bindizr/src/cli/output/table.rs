@@ -1,9 +1,12 @@
 //! Table rows for CLI output, each built from the typed daemon response so
 //! the column set is all this module decides.
 
+use bindizr_core::dns::serial;
 use bindizr_service::types::{
-    GetRecordResponse, GetZoneResponse, ImportSummary, RecordValueRequest, RollbackZoneResponse,
-    SecondaryStatusResponse, SnapshotRecordResponse, ZoneSnapshotResponse, ZoneStatusResponse,
+    AuditEntryResponse, GetRecordResponse, GetZoneResponse, ImportSummary, RecordValueRequest,
+    RollbackZoneResponse, RrsetResponse, SecondariesStatusResponse, SecondaryStatusResponse,
+    SnapshotRecordResponse, ZoneLintFinding, ZonePruneResult, ZoneSnapshotResponse,
+    ZoneStatusResponse,
 };
 use tabled::Tabled;
 
@@ -15,6 +18,19 @@ fn display_option_i32(opt: &Option<i32>) -> String {
     }
 }
 
+// Display Option<u32> in tables, using "-" for None.
+fn display_option_u32(opt: &Option<u32>) -> String {
+    match opt {
+        Some(val) => val.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+// Display Option<String> in tables, using "-" for None.
+fn display_option_string(opt: &Option<String>) -> String {
+    opt.clone().unwrap_or_else(|| "-".to_string())
+}
+
 /// A record value as one table cell; TXT segments concatenate into the string
 /// they encode.
 fn value_text(value: &RecordValueRequest) -> String {
@@ -38,7 +54,7 @@ pub(crate) struct ZoneRow {
     #[tabled(rename = "TTL")]
     pub(crate) ttl: i32,
     #[tabled(rename = "SERIAL")]
-    pub(crate) serial: i32,
+    pub(crate) serial: u32,
 }
 
 impl From<&GetZoneResponse> for ZoneRow {
@@ -87,17 +103,47 @@ impl From<&GetRecordResponse> for RecordRow {
     }
 }
 
+/// Table row for RRset display: one per value, the name, type and TTL shared.
+#[derive(Debug, Tabled)]
+pub(crate) struct RrsetRow {
+    #[tabled(rename = "NAME")]
+    pub(crate) name: String,
+    #[tabled(rename = "TYPE")]
+    pub(crate) record_type: String,
+    #[tabled(rename = "TTL")]
+    pub(crate) ttl: i32,
+    #[tabled(rename = "VALUE")]
+    pub(crate) value: String,
+}
+
+impl RrsetRow {
+    pub(crate) fn rows(rrset: &RrsetResponse) -> Vec<RrsetRow> {
+        rrset
+            .values
+            .iter()
+            .map(|value| RrsetRow {
+                name: rrset.name.clone(),
+                record_type: rrset.record_type.clone(),
+                ttl: rrset.ttl,
+                value: value.clone(),
+            })
+            .collect()
+    }
+}
+
 /// Table row for zone snapshot display.
 #[derive(Debug, Tabled)]
 pub(crate) struct SnapshotRow {
     #[tabled(rename = "SERIAL")]
-    pub(crate) serial: i32,
+    pub(crate) serial: u32,
     #[tabled(rename = "PRIMARY-NS")]
     pub(crate) primary_ns: String,
     #[tabled(rename = "ADMIN-EMAIL")]
     pub(crate) admin_email: String,
     #[tabled(rename = "TTL")]
     pub(crate) ttl: i32,
+    #[tabled(rename = "ACTOR", display = "display_option_string")]
+    pub(crate) actor: Option<String>,
     #[tabled(rename = "CREATED-AT")]
     pub(crate) created_at: String,
 }
@@ -109,11 +155,48 @@ impl From<&ZoneSnapshotResponse> for SnapshotRow {
             primary_ns: snapshot.primary_ns.clone(),
             admin_email: snapshot.admin_email.clone(),
             ttl: snapshot.ttl,
+            actor: snapshot.actor.clone(),
             created_at: snapshot.created_at.to_rfc3339(),
         }
     }
 }
 
+/// Table row for audit log display.
+#[derive(Debug, Tabled)]
+pub(crate) struct AuditRow {
+    #[tabled(rename = "CREATED-AT")]
+    pub(crate) created_at: String,
+    #[tabled(rename = "ZONE")]
+    pub(crate) zone_name: String,
+    #[tabled(rename = "SERIAL")]
+    pub(crate) serial: u32,
+    #[tabled(rename = "ACTION", display = "display_option_string")]
+    pub(crate) action: Option<String>,
+    #[tabled(rename = "ACTOR", display = "display_option_string")]
+    pub(crate) actor: Option<String>,
+    #[tabled(rename = "TRANSPORT", display = "display_option_string")]
+    pub(crate) transport: Option<String>,
+    #[tabled(rename = "SOURCE", display = "display_option_string")]
+    pub(crate) source_address: Option<String>,
+    #[tabled(rename = "REQUEST-ID", display = "display_option_string")]
+    pub(crate) request_id: Option<String>,
+}
+
+impl From<&AuditEntryResponse> for AuditRow {
+    fn from(entry: &AuditEntryResponse) -> Self {
+        AuditRow {
+            created_at: entry.created_at.to_rfc3339(),
+            zone_name: entry.zone_name.clone(),
+            serial: entry.serial,
+            action: entry.action.clone(),
+            actor: entry.actor.clone(),
+            transport: entry.transport.clone(),
+            source_address: entry.source_address.clone(),
+            request_id: entry.request_id.clone(),
+        }
+    }
+}
+
 /// Table row for records reconstructed at a snapshot serial (no database id).
 #[derive(Debug, Tabled)]
 pub(crate) struct SnapshotRecordRow {
@@ -145,9 +228,9 @@ impl From<&SnapshotRecordResponse> for SnapshotRecordRow {
 #[derive(Debug, Tabled)]
 pub(crate) struct RollbackSummaryRow {
     #[tabled(rename = "TARGET-SERIAL")]
-    pub(crate) target_serial: i32,
+    pub(crate) target_serial: u32,
     #[tabled(rename = "NEW-SERIAL")]
-    pub(crate) new_serial: i32,
+    pub(crate) new_serial: u32,
     #[tabled(rename = "APPLIED")]
     pub(crate) applied: bool,
     #[tabled(rename = "ADDED")]
@@ -174,6 +257,30 @@ impl From<&RollbackZoneResponse> for RollbackSummaryRow {
     }
 }
 
+/// Table row for one zone's history prune result.
+#[derive(Debug, Tabled)]
+pub(crate) struct PruneRow {
+    #[tabled(rename = "ZONE")]
+    pub(crate) zone_name: String,
+    #[tabled(rename = "OLDEST-RETAINED", display = "display_option_u32")]
+    pub(crate) oldest_retained_serial: Option<u32>,
+    #[tabled(rename = "SNAPSHOTS")]
+    pub(crate) snapshots_removed: u64,
+    #[tabled(rename = "CHANGES")]
+    pub(crate) changes_removed: u64,
+}
+
+impl From<&ZonePruneResult> for PruneRow {
+    fn from(result: &ZonePruneResult) -> Self {
+        PruneRow {
+            zone_name: result.zone_name.clone(),
+            oldest_retained_serial: result.oldest_retained_serial,
+            snapshots_removed: result.snapshots_removed,
+            changes_removed: result.changes_removed,
+        }
+    }
+}
+
 /// Table row for per-secondary zone sync status.
 #[derive(Debug, Tabled)]
 pub(crate) struct SecondaryStatusRow {
@@ -198,7 +305,7 @@ impl SecondaryStatusRow {
             .collect()
     }
 
-    fn from_secondary(secondary: &SecondaryStatusResponse, zone_serial: i32) -> Self {
+    fn from_secondary(secondary: &SecondaryStatusResponse, zone_serial: u32) -> Self {
         let detail = match (secondary.status.as_str(), secondary.error.as_deref()) {
             ("unreachable", Some(error)) => format!("unreachable ({})", error),
             _ => secondary.status.clone(),
@@ -211,12 +318,88 @@ impl SecondaryStatusRow {
                 .map_or_else(|| "-".to_string(), |serial| serial.to_string()),
             lag: secondary.visible_serial.map_or_else(
                 || "-".to_string(),
-                |serial| (i64::from(zone_serial) - serial).to_string(),
+                // RFC 1982 distance read as signed, so a secondary ahead shows negative.
+                |visible| (serial::distance(visible, zone_serial) as i32).to_string(),
             ),
         }
     }
 }
 
+/// Table row for one zone on one secondary, as the sync monitor last saw it.
+#[derive(Debug, Tabled)]
+pub(crate) struct SecondarySyncRow {
+    #[tabled(rename = "ZONE")]
+    pub(crate) zone: String,
+    #[tabled(rename = "SECONDARY")]
+    pub(crate) secondary: String,
+    #[tabled(rename = "STATUS")]
+    pub(crate) status: String,
+    #[tabled(rename = "SERIAL")]
+    pub(crate) serial: u32,
+    #[tabled(rename = "VISIBLE-SERIAL")]
+    pub(crate) visible_serial: String,
+    #[tabled(rename = "LAG")]
+    pub(crate) lag: String,
+    #[tabled(rename = "PROBED")]
+    pub(crate) probed_at: String,
+}
+
+impl SecondarySyncRow {
+    pub(crate) fn rows(status: &SecondariesStatusResponse) -> Vec<Self> {
+        status
+            .secondaries
+            .iter()
+            .map(|sync| {
+                let status = match (sync.status.as_str(), sync.error.as_deref()) {
+                    ("unreachable", Some(error)) => format!("unreachable ({})", error),
+                    _ => sync.status.clone(),
+                };
+                SecondarySyncRow {
+                    zone: sync.zone.clone(),
+                    secondary: sync.secondary.clone(),
+                    status,
+                    serial: sync.serial,
+                    visible_serial: display_option_u32(&sync.visible_serial),
+                    lag: sync
+                        .lag
+                        .map_or_else(|| "-".to_string(), |lag| lag.to_string()),
+                    probed_at: sync.probed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Table row for one `zone lint` finding.
+#[derive(Debug, Tabled)]
+pub(crate) struct LintFindingRow {
+    #[tabled(rename = "SEVERITY")]
+    pub(crate) severity: String,
+    #[tabled(rename = "CODE")]
+    pub(crate) code: String,
+    #[tabled(rename = "NAME")]
+    pub(crate) name: String,
+    #[tabled(rename = "TYPE")]
+    pub(crate) record_type: String,
+    #[tabled(rename = "VALUE")]
+    pub(crate) value: String,
+    #[tabled(rename = "MESSAGE")]
+    pub(crate) message: String,
+}
+
+impl From<&ZoneLintFinding> for LintFindingRow {
+    fn from(finding: &ZoneLintFinding) -> Self {
+        LintFindingRow {
+            severity: finding.severity.clone(),
+            code: finding.code.clone(),
+            name: finding.name.clone(),
+            record_type: finding.record_type.clone(),
+            value: finding.value.clone(),
+            message: finding.message.clone(),
+        }
+    }
+}
+
 /// Table row for zone-file import summaries.
 #[derive(Debug, Tabled)]
 pub(crate) struct ImportSummaryRow {
@@ -246,3 +429,26 @@ impl From<&ImportSummary> for ImportSummaryRow {
         }
     }
 }
+
+/// Table row for one schema migration in `db status`.
+#[derive(Debug, Tabled)]
+pub(crate) struct MigrationRow {
+    #[tabled(rename = "VERSION")]
+    pub(crate) version: i32,
+    #[tabled(rename = "DESCRIPTION")]
+    pub(crate) description: String,
+    #[tabled(rename = "APPLIED-AT")]
+    pub(crate) applied_at: String,
+}
+
+impl From<&bindizr_db::MigrationState> for MigrationRow {
+    fn from(migration: &bindizr_db::MigrationState) -> Self {
+        MigrationRow {
+            version: migration.version,
+            description: migration.description.clone(),
+            applied_at: migration
+                .applied_at
+                .map_or_else(|| "pending".to_string(), |at| at.to_rfc3339()),
+        }
+    }
+}
@@ -5,7 +5,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use bindizr_core::{config, log_error, log_info, logger};
+use bindizr_core::{config, log_error, log_info, log_warn, logger};
 use bindizr_db as database;
 use bindizr_dns as dns;
 use bindizr_service as service;
@@ -47,6 +47,9 @@ pub(crate) async fn bootstrap(config_file: Option<&str>) -> Result<(), String> {
     database::initialize().await.map_err(|e| e.to_string())?;
 
     dns::initialize().await;
+    spawn_history_compaction();
+    service::webhook::init_delivery_worker();
+    dns::monitor::init_sync_monitor();
 
     if config::get_bindizr_config().dns.notify_on_startup {
         match dns::client::notify::send_notify(None).await {
@@ -63,6 +66,9 @@ pub(crate) async fn bootstrap(config_file: Option<&str>) -> Result<(), String> {
     socket::server::initialize().await?;
     api::initialize().await?;
 
+    let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+        .map_err(|e| format!("Failed to listen for SIGHUP: {}", e))?;
+
     loop {
         let control = tokio::select! {
             result = tokio::signal::ctrl_c() => {
@@ -70,6 +76,13 @@ pub(crate) async fn bootstrap(config_file: Option<&str>) -> Result<(), String> {
                 log_info!("Shutdown signal received, exiting gracefully...");
                 break;
             }
+            _ = hangup.recv() => {
+                log_info!("SIGHUP received, reloading configuration...");
+                if let Err(e) = reload_config() {
+                    log_error!("Configuration reload failed: {}", e);
+                }
+                continue;
+            }
             control = control_rx.recv() => control,
         };
 
@@ -90,6 +103,60 @@ pub(crate) async fn bootstrap(config_file: Option<&str>) -> Result<(), String> {
     Ok(())
 }
 
+/// Run history compaction and audit log expiry in the background. The
+/// interval and limits are read each round, so a reload applies from the next
+/// one; with no limit configured a round is a no-op.
+fn spawn_history_compaction() {
+    tokio::spawn(async {
+        loop {
+            let interval = config::get_bindizr_config().history.prune_interval_secs;
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+
+            match service::zone::ZoneService::compact_history().await {
+                Ok(Some(pruned)) => {
+                    let snapshots: u64 = pruned.zones.iter().map(|z| z.snapshots_removed).sum();
+                    let changes: u64 = pruned.zones.iter().map(|z| z.changes_removed).sum();
+                    if snapshots > 0 || changes > 0 {
+                        log_info!(
+                            "History compaction removed {} snapshot(s) and {} change(s).",
+                            snapshots,
+                            changes
+                        );
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log_error!("History compaction failed: {}", e),
+            }
+
+            match service::audit::AuditService::prune_expired().await {
+                Ok(0) => {}
+                Ok(removed) => log_info!("Pruned {} expired audit entry(ies).", removed),
+                Err(e) => log_error!("Audit log pruning failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Re-read the configuration file and apply it. The log level changes here;
+/// the secondary ACL, NOTIFY targets, and apply-mode settings are read per use
+/// and pick up the new values on their next read. A file that fails to load
+/// leaves the running configuration in place.
+pub(crate) fn reload_config() -> Result<config::ReloadOutcome, String> {
+    let outcome = config::reload()?;
+    logger::set_level(outcome.config.logging.log_level);
+
+    if outcome.restart_required.is_empty() {
+        log_info!("Configuration reloaded.");
+    } else {
+        log_warn!(
+            "Configuration reloaded; changes to {} take effect after a restart.",
+            outcome.restart_required.join(", ")
+        );
+    }
+
+    Ok(outcome)
+}
+
 /// Re-exec the original command line in place. exec keeps the PID, so
 /// systemd/docker supervision and a foreground terminal stay attached.
 /// Returns only when exec itself fails.
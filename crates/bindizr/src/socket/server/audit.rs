@@ -0,0 +1,39 @@
+use bindizr_service::{
+    audit::{AuditQuery, AuditService},
+    authorization::Caller,
+    error::ServiceError,
+    types::AuditEntryResponse,
+};
+
+use crate::socket::{
+    server::{parse_params, to_response_data},
+    types::{DaemonResponse, ListAuditEntriesParams},
+};
+
+/// Handle the `ListAuditEntries` command by returning attributed serials,
+/// newest first.
+pub(super) async fn list_audit_entries(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: ListAuditEntriesParams = parse_params(data)?;
+
+    let entries = AuditService::list(
+        &Caller::Global,
+        AuditQuery {
+            zone_name: params.zone_name,
+            actor: params.actor,
+            transport: params.transport,
+            since: params.since,
+            until: params.until,
+            limit: params.limit,
+        },
+    )
+    .await?;
+    let entries: Vec<AuditEntryResponse> =
+        entries.into_iter().map(AuditEntryResponse::from).collect();
+
+    Ok(DaemonResponse {
+        message: format!("Found {} audit entries", entries.len()),
+        data: to_response_data(entries)?,
+    })
+}
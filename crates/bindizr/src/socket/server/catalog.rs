@@ -0,0 +1,94 @@
+use bindizr_service::{
+    authorization::Caller,
+    catalog::CatalogService,
+    error::ServiceError,
+    types::{CreateCatalogRequest, GetCatalogResponse, GetZoneCatalogPropertiesResponse},
+};
+
+use crate::socket::{
+    server::{parse_params, to_response_data},
+    types::{CatalogNameParams, DaemonResponse, SetZoneCatalogParams, ZoneNameParams},
+};
+
+/// Handle the `CatalogCreate` command by creating a named catalog.
+pub(super) async fn create_catalog(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: CreateCatalogRequest = parse_params(data)?;
+
+    let catalog =
+        CatalogService::create(&Caller::Global, &params.name, params.description.as_deref())
+            .await?;
+
+    Ok(DaemonResponse {
+        message: "Catalog created successfully".to_string(),
+        data: to_response_data(GetCatalogResponse::from_catalog(&catalog))?,
+    })
+}
+
+/// Handle the `CatalogList` command by returning the named catalogs.
+pub(super) async fn list_catalogs() -> Result<DaemonResponse, ServiceError> {
+    let catalogs = CatalogService::list(&Caller::Global).await?;
+    let catalogs: Vec<GetCatalogResponse> = catalogs
+        .iter()
+        .map(GetCatalogResponse::from_catalog)
+        .collect();
+
+    Ok(DaemonResponse {
+        message: "Catalogs retrieved successfully".to_string(),
+        data: to_response_data(catalogs)?,
+    })
+}
+
+/// Handle the `CatalogDelete` command by deleting a named catalog.
+pub(super) async fn delete_catalog(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: CatalogNameParams = parse_params(data)?;
+
+    CatalogService::delete(&Caller::Global, &params.name).await?;
+
+    Ok(DaemonResponse {
+        message: "Catalog deleted successfully".to_string(),
+        data: serde_json::Value::Null,
+    })
+}
+
+/// Handle the `ZoneCatalogGet` command by returning a zone's catalog properties.
+pub(super) async fn get_zone_catalog_properties(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: ZoneNameParams = parse_params(data)?;
+
+    let properties = CatalogService::get_zone(&Caller::Global, &params.name).await?;
+
+    Ok(DaemonResponse {
+        message: "Catalog properties retrieved successfully".to_string(),
+        data: to_response_data(GetZoneCatalogPropertiesResponse::from_properties(
+            &params.name,
+            &properties,
+        ))?,
+    })
+}
+
+/// Handle the `ZoneCatalogSet` command by replacing a zone's catalog properties.
+pub(super) async fn set_zone_catalog_properties(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: SetZoneCatalogParams = parse_params(data)?;
+
+    let properties = CatalogService::set_zone(
+        &Caller::Global,
+        &params.zone_name,
+        params.request.into_properties(),
+    )
+    .await?;
+
+    Ok(DaemonResponse {
+        message: "Catalog properties updated successfully".to_string(),
+        data: to_response_data(GetZoneCatalogPropertiesResponse::from_properties(
+            &params.zone_name,
+            &properties,
+        ))?,
+    })
+}
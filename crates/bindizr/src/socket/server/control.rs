@@ -3,7 +3,7 @@ use std::{sync::OnceLock, time::Duration};
 use bindizr_service::error::ServiceError;
 use tokio::sync::mpsc;
 
-use crate::socket::types::DaemonResponse;
+use crate::socket::types::{DaemonReloadResponse, DaemonResponse};
 
 /// Daemon lifecycle transitions requestable over the control socket.
 pub(crate) enum DaemonControl {
@@ -36,6 +36,25 @@ pub(super) fn restart() -> Result<DaemonResponse, ServiceError> {
     })
 }
 
+/// Reloads in place rather than through the control channel, so a file that
+/// fails validation is reported back to the caller.
+pub(super) fn reload() -> Result<DaemonResponse, ServiceError> {
+    let outcome = crate::daemon::reload_config()
+        .map_err(|e| ServiceError::invalid_input(format!("Configuration reload failed: {}", e)))?;
+    let response = DaemonReloadResponse {
+        restart_required: outcome
+            .restart_required
+            .iter()
+            .map(|key| key.to_string())
+            .collect(),
+    };
+
+    Ok(DaemonResponse {
+        message: "Configuration reloaded".to_string(),
+        data: super::to_response_data(response)?,
+    })
+}
+
 /// Deliver the transition after a short delay so the command response reaches
 /// the client before the daemon tears down.
 fn request(control: DaemonControl) -> Result<(), ServiceError> {
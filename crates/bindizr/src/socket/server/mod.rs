@@ -1,19 +1,28 @@
 //! Unix-socket daemon API for the CLI; reachable only by the local daemon
 //! owner, so every command runs with global access (no token scoping).
 
+mod audit;
+mod catalog;
 pub(crate) mod control;
 mod doctor;
 mod notify;
+mod notify_target;
 mod record;
 mod status;
+mod template;
 mod token;
+mod transfer_acl;
 mod tsig_key;
+mod webhook;
 mod zone;
 
 use std::{io, os::unix::fs::FileTypeExt, path::Path};
 
 use bindizr_core::{log_error, log_info, log_warn};
-use bindizr_service::error::ServiceError;
+use bindizr_service::{
+    audit::{AuditContext, Transport, new_request_id, scope as audit_scope},
+    error::ServiceError,
+};
 use serde_json::json;
 use tokio::{
     fs,
@@ -23,7 +32,7 @@ use tokio::{
 
 use crate::socket::{
     FALLBACK_SOCKET_FILE_PATH, SOCKET_FILE_PATH,
-    types::{DaemonCommand, DaemonCommandKind},
+    types::{DaemonCommand, DaemonCommandKind, DaemonResponse},
 };
 
 /// Upper bound on a single command line, so a buggy or malicious client cannot
@@ -31,7 +40,107 @@ use crate::socket::{
 /// zone-file content arrives JSON-escaped, roughly doubling in the worst case.
 const MAX_COMMAND_LINE_BYTES: u64 = 64 * 1024 * 1024;
 
+/// Run one parsed command against its handler.
+async fn dispatch(cmd: DaemonCommand) -> Result<DaemonResponse, ServiceError> {
+    match cmd.command {
+        DaemonCommandKind::Status => status::get_status(),
+        DaemonCommandKind::TokenCreate => token::create_token(&cmd.data).await,
+        DaemonCommandKind::TokenList => token::list_tokens().await,
+        DaemonCommandKind::TokenDelete => token::delete_token(&cmd.data).await,
+        DaemonCommandKind::TokenRotate => token::rotate_token(&cmd.data).await,
+        DaemonCommandKind::TsigKeyCreate => tsig_key::create_tsig_key(&cmd.data).await,
+        DaemonCommandKind::TsigKeyList => tsig_key::list_tsig_keys().await,
+        DaemonCommandKind::TsigKeyGet => tsig_key::get_tsig_key(&cmd.data).await,
+        DaemonCommandKind::TsigKeyDelete => tsig_key::delete_tsig_key(&cmd.data).await,
+        DaemonCommandKind::ZoneTsigPolicyAdd => tsig_key::add_zone_tsig_policy(&cmd.data).await,
+        DaemonCommandKind::ZoneTsigPolicyList => tsig_key::list_zone_tsig_policies(&cmd.data).await,
+        DaemonCommandKind::ZoneTsigPolicyRemove => {
+            tsig_key::remove_zone_tsig_policy(&cmd.data).await
+        }
+        DaemonCommandKind::ZoneTokenPolicyAdd => token::add_zone_token_policy(&cmd.data).await,
+        DaemonCommandKind::ZoneTokenPolicyList => token::list_zone_token_policies(&cmd.data).await,
+        DaemonCommandKind::ZoneTokenPolicyRemove => {
+            token::remove_zone_token_policy(&cmd.data).await
+        }
+        DaemonCommandKind::ZoneTransferAclAdd => {
+            transfer_acl::add_zone_transfer_acl(&cmd.data).await
+        }
+        DaemonCommandKind::ZoneTransferAclList => {
+            transfer_acl::list_zone_transfer_acls(&cmd.data).await
+        }
+        DaemonCommandKind::ZoneTransferAclRemove => {
+            transfer_acl::remove_zone_transfer_acl(&cmd.data).await
+        }
+        DaemonCommandKind::ZoneNotifyTargetAdd => {
+            notify_target::add_zone_notify_target(&cmd.data).await
+        }
+        DaemonCommandKind::ZoneNotifyTargetList => {
+            notify_target::list_zone_notify_targets(&cmd.data).await
+        }
+        DaemonCommandKind::ZoneNotifyTargetRemove => {
+            notify_target::remove_zone_notify_target(&cmd.data).await
+        }
+        DaemonCommandKind::CatalogCreate => catalog::create_catalog(&cmd.data).await,
+        DaemonCommandKind::CatalogList => catalog::list_catalogs().await,
+        DaemonCommandKind::CatalogDelete => catalog::delete_catalog(&cmd.data).await,
+        DaemonCommandKind::ZoneCatalogGet => catalog::get_zone_catalog_properties(&cmd.data).await,
+        DaemonCommandKind::ZoneCatalogSet => catalog::set_zone_catalog_properties(&cmd.data).await,
+        DaemonCommandKind::TemplateCreate => template::create_template(&cmd.data).await,
+        DaemonCommandKind::TemplateList => template::list_templates().await,
+        DaemonCommandKind::TemplateGet => template::get_template(&cmd.data).await,
+        DaemonCommandKind::TemplateUpdate => template::update_template(&cmd.data).await,
+        DaemonCommandKind::TemplateApply => template::apply_template(&cmd.data).await,
+        DaemonCommandKind::TemplateDelete => template::delete_template(&cmd.data).await,
+        DaemonCommandKind::WebhookCreate => webhook::create_webhook(&cmd.data).await,
+        DaemonCommandKind::WebhookList => webhook::list_webhooks().await,
+        DaemonCommandKind::WebhookDelete => webhook::delete_webhook(&cmd.data).await,
+        DaemonCommandKind::WebhookDeliveries => webhook::list_webhook_deliveries(&cmd.data).await,
+        DaemonCommandKind::GetZone => zone::get_zone(&cmd.data).await,
+        DaemonCommandKind::ListZones => zone::list_zones(&cmd.data).await,
+        DaemonCommandKind::CreateZone => zone::create_zone(&cmd.data).await,
+        DaemonCommandKind::UpdateZone => zone::update_zone(&cmd.data).await,
+        DaemonCommandKind::DeleteZone => zone::delete_zone(&cmd.data).await,
+        DaemonCommandKind::GetRecord => record::get_record(&cmd.data).await,
+        DaemonCommandKind::ListRecords => record::list_records(&cmd.data).await,
+        DaemonCommandKind::CreateRecord => record::create_record(&cmd.data).await,
+        DaemonCommandKind::UpdateRecord => record::update_record(&cmd.data).await,
+        DaemonCommandKind::BulkCreateRecords => record::bulk_create_records(&cmd.data).await,
+        DaemonCommandKind::ApplyChangeset => record::apply_changeset(&cmd.data).await,
+        DaemonCommandKind::DeleteRecord => record::delete_record(&cmd.data).await,
+        DaemonCommandKind::GetRrset => record::get_rrset(&cmd.data).await,
+        DaemonCommandKind::ReplaceRrset => record::replace_rrset(&cmd.data).await,
+        DaemonCommandKind::DeleteRrset => record::delete_rrset(&cmd.data).await,
+        DaemonCommandKind::NotifyZone => notify::handle_notify_zone(&cmd.data).await,
+        DaemonCommandKind::ImportZoneFile => zone::import_zone(&cmd.data).await,
+        DaemonCommandKind::PullZone => zone::pull_zone(&cmd.data).await,
+        DaemonCommandKind::ExportZoneFile => zone::export_zone(&cmd.data).await,
+        DaemonCommandKind::LintZone => zone::lint_zone(&cmd.data).await,
+        DaemonCommandKind::ListZoneSnapshots => zone::list_zone_snapshots(&cmd.data).await,
+        DaemonCommandKind::GetZoneSnapshot => zone::get_zone_snapshot(&cmd.data).await,
+        DaemonCommandKind::DiffZoneSnapshots => zone::diff_zone_snapshots(&cmd.data).await,
+        DaemonCommandKind::RollbackZone => zone::rollback_zone(&cmd.data).await,
+        DaemonCommandKind::PruneZoneHistory => zone::prune_zone_history(&cmd.data).await,
+        DaemonCommandKind::ZoneStatus => zone::zone_status(&cmd.data).await,
+        DaemonCommandKind::SecondariesStatus => zone::secondaries_status(),
+        DaemonCommandKind::Doctor => doctor::doctor().await,
+        DaemonCommandKind::Shutdown => control::shutdown(),
+        DaemonCommandKind::Restart => control::restart(),
+        DaemonCommandKind::Reload => control::reload(),
+        DaemonCommandKind::ListAuditEntries => audit::list_audit_entries(&cmd.data).await,
+    }
+}
+
 async fn handle_client(stream: UnixStream) {
+    let audit_context = AuditContext {
+        actor: stream.peer_cred().map_or_else(
+            |_| "local".to_string(),
+            |cred| format!("uid:{}", cred.uid()),
+        ),
+        tsig_key: None,
+        source_address: None,
+        transport: Transport::Socket,
+        request_id: Some(new_request_id()),
+    };
     let mut reader = BufReader::new(stream).take(MAX_COMMAND_LINE_BYTES);
     let mut line = String::new();
 
@@ -39,58 +148,7 @@ async fn handle_client(stream: UnixStream) {
         let parsed: Result<DaemonCommand, _> = serde_json::from_str(&line);
 
         let raw_response = match parsed {
-            Ok(cmd) => match cmd.command {
-                DaemonCommandKind::Status => status::get_status(),
-                DaemonCommandKind::TokenCreate => token::create_token(&cmd.data).await,
-                DaemonCommandKind::TokenList => token::list_tokens().await,
-                DaemonCommandKind::TokenDelete => token::delete_token(&cmd.data).await,
-                DaemonCommandKind::TsigKeyCreate => tsig_key::create_tsig_key(&cmd.data).await,
-                DaemonCommandKind::TsigKeyList => tsig_key::list_tsig_keys().await,
-                DaemonCommandKind::TsigKeyGet => tsig_key::get_tsig_key(&cmd.data).await,
-                DaemonCommandKind::TsigKeyDelete => tsig_key::delete_tsig_key(&cmd.data).await,
-                DaemonCommandKind::ZoneTsigPolicyAdd => {
-                    tsig_key::add_zone_tsig_policy(&cmd.data).await
-                }
-                DaemonCommandKind::ZoneTsigPolicyList => {
-                    tsig_key::list_zone_tsig_policies(&cmd.data).await
-                }
-                DaemonCommandKind::ZoneTsigPolicyRemove => {
-                    tsig_key::remove_zone_tsig_policy(&cmd.data).await
-                }
-                DaemonCommandKind::ZoneTokenPolicyAdd => {
-                    token::add_zone_token_policy(&cmd.data).await
-                }
-                DaemonCommandKind::ZoneTokenPolicyList => {
-                    token::list_zone_token_policies(&cmd.data).await
-                }
-                DaemonCommandKind::ZoneTokenPolicyRemove => {
-                    token::remove_zone_token_policy(&cmd.data).await
-                }
-                DaemonCommandKind::GetZone => zone::get_zone(&cmd.data).await,
-                DaemonCommandKind::ListZones => zone::list_zones(&cmd.data).await,
-                DaemonCommandKind::CreateZone => zone::create_zone(&cmd.data).await,
-                DaemonCommandKind::UpdateZone => zone::update_zone(&cmd.data).await,
-                DaemonCommandKind::DeleteZone => zone::delete_zone(&cmd.data).await,
-                DaemonCommandKind::GetRecord => record::get_record(&cmd.data).await,
-                DaemonCommandKind::ListRecords => record::list_records(&cmd.data).await,
-                DaemonCommandKind::CreateRecord => record::create_record(&cmd.data).await,
-                DaemonCommandKind::UpdateRecord => record::update_record(&cmd.data).await,
-                DaemonCommandKind::BulkCreateRecords => {
-                    record::bulk_create_records(&cmd.data).await
-                }
-                DaemonCommandKind::DeleteRecord => record::delete_record(&cmd.data).await,
-                DaemonCommandKind::NotifyZone => notify::handle_notify_zone(&cmd.data).await,
-                DaemonCommandKind::ImportZoneFile => zone::import_zone(&cmd.data).await,
-                DaemonCommandKind::ExportZoneFile => zone::export_zone(&cmd.data).await,
-                DaemonCommandKind::ListZoneSnapshots => zone::list_zone_snapshots(&cmd.data).await,
-                DaemonCommandKind::GetZoneSnapshot => zone::get_zone_snapshot(&cmd.data).await,
-                DaemonCommandKind::DiffZoneSnapshots => zone::diff_zone_snapshots(&cmd.data).await,
-                DaemonCommandKind::RollbackZone => zone::rollback_zone(&cmd.data).await,
-                DaemonCommandKind::ZoneStatus => zone::zone_status(&cmd.data).await,
-                DaemonCommandKind::Doctor => doctor::doctor().await,
-                DaemonCommandKind::Shutdown => control::shutdown(),
-                DaemonCommandKind::Restart => control::restart(),
-            },
+            Ok(cmd) => audit_scope(audit_context, dispatch(cmd)).await,
 
             Err(e) => {
                 log_error!("Failed to parse command: {}", e);
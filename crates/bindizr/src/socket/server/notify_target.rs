@@ -0,0 +1,64 @@
+use bindizr_service::{
+    authorization::Caller, error::ServiceError, types::GetZoneNotifyTargetResponse,
+    zone::notify_target::ZoneNotifyTargetService,
+};
+
+use crate::socket::{
+    server::{parse_params, to_response_data},
+    types::{
+        AddZoneNotifyTargetParams, DaemonResponse, RemoveZonePolicyParams, ZonePolicyListParams,
+    },
+};
+
+/// Handle the `ZoneNotifyTargetAdd` command by adding a NOTIFY target to a zone.
+pub(super) async fn add_zone_notify_target(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: AddZoneNotifyTargetParams = parse_params(data)?;
+
+    let entry = ZoneNotifyTargetService::add(
+        &Caller::Global,
+        &params.zone_name,
+        params.request.kind.as_deref(),
+        &params.request.address,
+        params.request.tsig_key.as_deref(),
+    )
+    .await?;
+
+    Ok(DaemonResponse {
+        message: "NOTIFY target created successfully".to_string(),
+        data: to_response_data(GetZoneNotifyTargetResponse::from_entry(&entry))?,
+    })
+}
+
+/// Handle the `ZoneNotifyTargetList` command by returning a zone's NOTIFY targets.
+pub(super) async fn list_zone_notify_targets(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: ZonePolicyListParams = parse_params(data)?;
+
+    let entries = ZoneNotifyTargetService::list(&Caller::Global, &params.zone_name).await?;
+    let entries: Vec<GetZoneNotifyTargetResponse> = entries
+        .iter()
+        .map(GetZoneNotifyTargetResponse::from_entry)
+        .collect();
+
+    Ok(DaemonResponse {
+        message: "NOTIFY targets retrieved successfully".to_string(),
+        data: to_response_data(entries)?,
+    })
+}
+
+/// Handle the `ZoneNotifyTargetRemove` command by removing one target of a zone.
+pub(super) async fn remove_zone_notify_target(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: RemoveZonePolicyParams = parse_params(data)?;
+
+    ZoneNotifyTargetService::remove(&Caller::Global, &params.zone_name, params.id).await?;
+
+    Ok(DaemonResponse {
+        message: "NOTIFY target deleted successfully".to_string(),
+        data: serde_json::Value::Null,
+    })
+}
@@ -1,14 +1,19 @@
 use bindizr_service::{
     authorization::Caller,
     error::ServiceError,
+    precondition::Precondition,
     record::RecordService,
-    types::{CreateRecordRequest, GetRecordResponse, GetRecordsFilter},
+    types::{GetRecordResponse, GetRecordsFilter},
 };
 use serde_json::json;
 
 use crate::socket::{
     server::{parse_params, to_response_data},
-    types::{BulkCreateRecordsParams, DaemonResponse, RecordIdParams, UpdateRecordParams},
+    types::{
+        ApplyChangesetParams, BulkCreateRecordsParams, CreateRecordParams, DaemonResponse,
+        DeleteRecordParams, DeleteRrsetParams, RecordIdParams, ReplaceRrsetParams, RrsetParams,
+        UpdateRecordParams,
+    },
 };
 
 /// Handle the `GetRecord` command by returning a record by ID.
@@ -50,9 +55,14 @@ pub(super) async fn list_records(data: &serde_json::Value) -> Result<DaemonRespo
 pub(super) async fn create_record(
     data: &serde_json::Value,
 ) -> Result<DaemonResponse, ServiceError> {
-    let request: CreateRecordRequest = parse_params(data)?;
+    let params: CreateRecordParams = parse_params(data)?;
 
-    let record = RecordService::create(&Caller::Global, &request).await?;
+    let record = RecordService::create(
+        &Caller::Global,
+        &params.request,
+        &Precondition::expect_serial(params.expect_serial),
+    )
+    .await?;
     Ok(DaemonResponse {
         message: "Record created successfully".to_string(),
         data: to_response_data(GetRecordResponse::from_record_with_zone(&record))?,
@@ -65,7 +75,13 @@ pub(super) async fn update_record(
 ) -> Result<DaemonResponse, ServiceError> {
     let params: UpdateRecordParams = parse_params(data)?;
 
-    let record = RecordService::patch_by_id(&Caller::Global, params.id, &params.patch).await?;
+    let record = RecordService::patch_by_id(
+        &Caller::Global,
+        params.id,
+        &params.patch,
+        &Precondition::expect_serial(params.expect_serial),
+    )
+    .await?;
     Ok(DaemonResponse {
         message: "Record updated successfully".to_string(),
         data: to_response_data(GetRecordResponse::from_record_with_zone(&record))?,
@@ -77,13 +93,18 @@ pub(super) async fn update_record(
 pub(super) async fn bulk_create_records(
     data: &serde_json::Value,
 ) -> Result<DaemonResponse, ServiceError> {
-    let BulkCreateRecordsParams { zone_name, request } = parse_params(data)?;
+    let BulkCreateRecordsParams {
+        zone_name,
+        request,
+        expect_serial,
+    } = parse_params(data)?;
 
     let response = RecordService::create_bulk(
         &Caller::Global,
         &zone_name,
         &request.records,
         request.dry_run,
+        &Precondition::expect_serial(expect_serial),
     )
     .await?;
     let message = if response.dry_run {
@@ -101,15 +122,129 @@ pub(super) async fn bulk_create_records(
     })
 }
 
+/// Handle the `ApplyChangeset` command by applying ordered record operations
+/// to a zone in a single transaction.
+pub(super) async fn apply_changeset(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let ApplyChangesetParams {
+        zone_name,
+        request,
+        expect_serial,
+    } = parse_params(data)?;
+
+    let response = RecordService::apply_changeset(
+        &Caller::Global,
+        &zone_name,
+        &request.operations,
+        request.dry_run,
+        &Precondition::expect_serial(expect_serial),
+    )
+    .await?;
+    let message = if response.dry_run {
+        format!(
+            "Dry run: {} operation(s) validated; nothing applied",
+            request.operations.len()
+        )
+    } else {
+        format!(
+            "Applied changeset: {} added, {} updated, {} deleted (serial {})",
+            response.added, response.updated, response.deleted, response.serial
+        )
+    };
+
+    Ok(DaemonResponse {
+        message,
+        data: to_response_data(response)?,
+    })
+}
+
 /// Handle the `DeleteRecord` command by deleting a record by ID.
 pub(super) async fn delete_record(
     data: &serde_json::Value,
 ) -> Result<DaemonResponse, ServiceError> {
-    let params: RecordIdParams = parse_params(data)?;
+    let params: DeleteRecordParams = parse_params(data)?;
 
-    RecordService::delete_by_id(&Caller::Global, params.id).await?;
+    RecordService::delete_by_id(
+        &Caller::Global,
+        params.id,
+        &Precondition::expect_serial(params.expect_serial),
+    )
+    .await?;
     Ok(DaemonResponse {
         message: format!("Record '{}' deleted successfully", params.id),
         data: json!(null),
     })
 }
+
+/// Handle the `GetRrset` command by returning every record of one name and type.
+pub(super) async fn get_rrset(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
+    let params: RrsetParams = parse_params(data)?;
+
+    let rrset = RecordService::get_rrset(
+        &Caller::Global,
+        &params.zone_name,
+        &params.name,
+        &params.record_type,
+    )
+    .await?;
+    Ok(DaemonResponse {
+        message: "RRset retrieved successfully".to_string(),
+        data: to_response_data(rrset)?,
+    })
+}
+
+/// Handle the `ReplaceRrset` command by atomically replacing every record of
+/// one name and type.
+pub(super) async fn replace_rrset(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let ReplaceRrsetParams {
+        rrset,
+        request,
+        expect_serial,
+    } = parse_params(data)?;
+
+    let response = RecordService::replace_rrset(
+        &Caller::Global,
+        &rrset.zone_name,
+        &rrset.name,
+        &rrset.record_type,
+        &request,
+        &Precondition::expect_serial(expect_serial),
+    )
+    .await?;
+    Ok(DaemonResponse {
+        message: format!(
+            "RRset '{}' {} replaced with {} value(s)",
+            response.name,
+            response.record_type,
+            response.values.len()
+        ),
+        data: to_response_data(response)?,
+    })
+}
+
+/// Handle the `DeleteRrset` command by deleting every record of one name and type.
+pub(super) async fn delete_rrset(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
+    let DeleteRrsetParams {
+        rrset,
+        expect_serial,
+    } = parse_params(data)?;
+
+    RecordService::delete_rrset(
+        &Caller::Global,
+        &rrset.zone_name,
+        &rrset.name,
+        &rrset.record_type,
+        &Precondition::expect_serial(expect_serial),
+    )
+    .await?;
+    Ok(DaemonResponse {
+        message: format!(
+            "RRset '{}' {} deleted successfully",
+            rrset.name, rrset.record_type
+        ),
+        data: json!(null),
+    })
+}
@@ -32,7 +32,7 @@ pub(super) fn get_status() -> Result<DaemonResponse, ServiceError> {
         pid,
         version: version.to_string(),
         started_at_ms: STARTED_AT_MS.get().copied().unwrap_or(0),
-        config: config::get_bindizr_config().clone(),
+        config: config::get_bindizr_config().as_ref().clone(),
     };
 
     let response = DaemonResponse {
@@ -0,0 +1,96 @@
+use bindizr_service::{
+    authorization::Caller, error::ServiceError, template::ZoneTemplateService,
+    types::CreateZoneTemplateRequest,
+};
+
+use crate::socket::{
+    server::{parse_params, to_response_data},
+    types::{ApplyTemplateParams, DaemonResponse, TemplateNameParams, UpdateTemplateParams},
+};
+
+/// Handle the `TemplateCreate` command by creating a zone template.
+pub(super) async fn create_template(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: CreateZoneTemplateRequest = parse_params(data)?;
+
+    let template = ZoneTemplateService::create(
+        &Caller::Global,
+        &params.name,
+        params.description.as_deref(),
+        &params.records,
+    )
+    .await?;
+
+    Ok(DaemonResponse {
+        message: "Template created successfully".to_string(),
+        data: to_response_data(template)?,
+    })
+}
+
+/// Handle the `TemplateList` command by returning all zone templates.
+pub(super) async fn list_templates() -> Result<DaemonResponse, ServiceError> {
+    let templates = ZoneTemplateService::list(&Caller::Global).await?;
+
+    Ok(DaemonResponse {
+        message: "Templates retrieved successfully".to_string(),
+        data: to_response_data(templates)?,
+    })
+}
+
+/// Handle the `TemplateGet` command by returning a zone template.
+pub(super) async fn get_template(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
+    let params: TemplateNameParams = parse_params(data)?;
+
+    let template = ZoneTemplateService::get(&Caller::Global, &params.name).await?;
+
+    Ok(DaemonResponse {
+        message: "Template retrieved successfully".to_string(),
+        data: to_response_data(template)?,
+    })
+}
+
+/// Handle the `TemplateUpdate` command by replacing a zone template's records
+/// and diffing, or applying the change to, its linked zones.
+pub(super) async fn update_template(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: UpdateTemplateParams = parse_params(data)?;
+
+    let result =
+        ZoneTemplateService::update(&Caller::Global, &params.name, &params.request).await?;
+
+    Ok(DaemonResponse {
+        message: "Template updated successfully".to_string(),
+        data: to_response_data(result)?,
+    })
+}
+
+/// Handle the `TemplateApply` command by applying a zone template to its
+/// linked zones.
+pub(super) async fn apply_template(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: ApplyTemplateParams = parse_params(data)?;
+
+    let result = ZoneTemplateService::apply(&Caller::Global, &params.name, params.dry_run).await?;
+
+    Ok(DaemonResponse {
+        message: "Template applied successfully".to_string(),
+        data: to_response_data(result)?,
+    })
+}
+
+/// Handle the `TemplateDelete` command by deleting a zone template.
+pub(super) async fn delete_template(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: TemplateNameParams = parse_params(data)?;
+
+    ZoneTemplateService::delete(&Caller::Global, &params.name).await?;
+
+    Ok(DaemonResponse {
+        message: "Template deleted successfully".to_string(),
+        data: serde_json::Value::Null,
+    })
+}
@@ -57,12 +57,14 @@ fn command_payloads_round_trip_between_client_and_server() {
             ttl: Some(300),
             ..UpdateZonePatch::default()
         },
+        expect_serial: Some(41),
     })
     .unwrap();
     let parsed: UpdateZoneParams = parse_params(&sent).unwrap();
     assert_eq!(parsed.name, "example.com");
     assert_eq!(parsed.patch.new_name.as_deref(), Some("new.example.com"));
     assert_eq!(parsed.patch.ttl, Some(300));
+    assert_eq!(parsed.expect_serial, Some(41));
 
     let sent = serde_json::to_value(RollbackZoneParams {
         name: "example.com".to_string(),
@@ -70,11 +72,13 @@ fn command_payloads_round_trip_between_client_and_server() {
             serial: 7,
             dry_run: true,
         },
+        expect_serial: None,
     })
     .unwrap();
     let parsed: RollbackZoneParams = parse_params(&sent).unwrap();
     assert_eq!(parsed.request.serial, 7);
     assert!(parsed.request.dry_run);
+    assert_eq!(parsed.expect_serial, None);
 }
 
 #[tokio::test]
@@ -10,7 +10,7 @@ use crate::socket::{
     server::{parse_params, to_response_data},
     types::{
         AddZoneTokenPolicyParams, CreateTokenParams, DaemonResponse, RemoveZonePolicyParams,
-        TokenNameParams, ZonePolicyListParams,
+        RotateTokenParams, TokenNameParams, ZonePolicyListParams,
     },
 };
 
@@ -24,6 +24,7 @@ pub(super) async fn create_token(data: &serde_json::Value) -> Result<DaemonRespo
         params.description.as_deref(),
         params.expires_in_days,
         params.global,
+        params.role.as_deref(),
     )
     .await?;
 
@@ -46,6 +47,23 @@ pub(super) async fn list_tokens() -> Result<DaemonResponse, ServiceError> {
     Ok(response)
 }
 
+/// Handle the `TokenRotate` command by issuing a new secret for an API token.
+pub(super) async fn rotate_token(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
+    let params: RotateTokenParams = parse_params(data)?;
+
+    let rotated_token = TokenService::rotate(
+        &Caller::Global,
+        &params.name,
+        params.request.overlap_minutes,
+    )
+    .await?;
+
+    Ok(DaemonResponse {
+        message: "Token rotated successfully".to_string(),
+        data: to_response_data(GetTokenResponse::from_token(&rotated_token))?,
+    })
+}
+
 /// Handle the `TokenDelete` command by deleting an API token by name.
 pub(super) async fn delete_token(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
     let params: TokenNameParams = parse_params(data)?;
@@ -0,0 +1,64 @@
+use bindizr_service::{
+    authorization::Caller, error::ServiceError, types::GetZoneTransferAclResponse,
+    zone::transfer_acl::ZoneTransferAclService,
+};
+
+use crate::socket::{
+    server::{parse_params, to_response_data},
+    types::{
+        AddZoneTransferAclParams, DaemonResponse, RemoveZonePolicyParams, ZonePolicyListParams,
+    },
+};
+
+/// Handle the `ZoneTransferAclAdd` command by adding an entry to a zone's ACL.
+pub(super) async fn add_zone_transfer_acl(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: AddZoneTransferAclParams = parse_params(data)?;
+
+    let entry = ZoneTransferAclService::add(
+        &Caller::Global,
+        &params.zone_name,
+        params.request.action.as_deref(),
+        &params.request.address,
+        params.request.tsig_key.as_deref(),
+    )
+    .await?;
+
+    Ok(DaemonResponse {
+        message: "Transfer ACL entry created successfully".to_string(),
+        data: to_response_data(GetZoneTransferAclResponse::from_entry(&entry))?,
+    })
+}
+
+/// Handle the `ZoneTransferAclList` command by returning a zone's ACL.
+pub(super) async fn list_zone_transfer_acls(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: ZonePolicyListParams = parse_params(data)?;
+
+    let entries = ZoneTransferAclService::list(&Caller::Global, &params.zone_name).await?;
+    let entries: Vec<GetZoneTransferAclResponse> = entries
+        .iter()
+        .map(GetZoneTransferAclResponse::from_entry)
+        .collect();
+
+    Ok(DaemonResponse {
+        message: "Transfer ACL entries retrieved successfully".to_string(),
+        data: to_response_data(entries)?,
+    })
+}
+
+/// Handle the `ZoneTransferAclRemove` command by removing one entry of a zone.
+pub(super) async fn remove_zone_transfer_acl(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: RemoveZonePolicyParams = parse_params(data)?;
+
+    ZoneTransferAclService::remove(&Caller::Global, &params.zone_name, params.id).await?;
+
+    Ok(DaemonResponse {
+        message: "Transfer ACL entry deleted successfully".to_string(),
+        data: serde_json::Value::Null,
+    })
+}
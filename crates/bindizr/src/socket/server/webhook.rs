@@ -0,0 +1,81 @@
+use bindizr_service::{
+    authorization::Caller,
+    error::ServiceError,
+    types::{CreateWebhookRequest, GetWebhookDeliveryResponse, GetWebhookResponse},
+    webhook::WebhookService,
+};
+
+use crate::socket::{
+    server::{parse_params, to_response_data},
+    types::{DaemonResponse, WebhookDeliveriesParams, WebhookNameParams},
+};
+
+/// Handle the `WebhookCreate` command by creating a webhook.
+pub(super) async fn create_webhook(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: CreateWebhookRequest = parse_params(data)?;
+
+    let webhook = WebhookService::create(
+        &Caller::Global,
+        &params.name,
+        &params.url,
+        params.zone.as_deref(),
+        params.secret.as_deref(),
+        params.description.as_deref(),
+    )
+    .await?;
+
+    Ok(DaemonResponse {
+        message: "Webhook created successfully".to_string(),
+        data: to_response_data(GetWebhookResponse::from_subscription(&webhook))?,
+    })
+}
+
+/// Handle the `WebhookList` command by returning all webhooks.
+pub(super) async fn list_webhooks() -> Result<DaemonResponse, ServiceError> {
+    let webhooks = WebhookService::list(&Caller::Global).await?;
+    let webhooks: Vec<GetWebhookResponse> = webhooks
+        .iter()
+        .map(GetWebhookResponse::from_subscription)
+        .collect();
+
+    Ok(DaemonResponse {
+        message: "Webhooks retrieved successfully".to_string(),
+        data: to_response_data(webhooks)?,
+    })
+}
+
+/// Handle the `WebhookDelete` command by deleting a webhook.
+pub(super) async fn delete_webhook(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: WebhookNameParams = parse_params(data)?;
+
+    WebhookService::delete(&Caller::Global, &params.name).await?;
+
+    Ok(DaemonResponse {
+        message: "Webhook deleted successfully".to_string(),
+        data: serde_json::Value::Null,
+    })
+}
+
+/// Handle the `WebhookDeliveries` command by returning a webhook's recent
+/// deliveries.
+pub(super) async fn list_webhook_deliveries(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: WebhookDeliveriesParams = parse_params(data)?;
+
+    let deliveries =
+        WebhookService::deliveries(&Caller::Global, &params.name, params.limit).await?;
+    let deliveries: Vec<GetWebhookDeliveryResponse> = deliveries
+        .iter()
+        .map(GetWebhookDeliveryResponse::from_delivery)
+        .collect();
+
+    Ok(DaemonResponse {
+        message: "Webhook deliveries retrieved successfully".to_string(),
+        data: to_response_data(deliveries)?,
+    })
+}
@@ -1,6 +1,7 @@
 use bindizr_service::{
     authorization::Caller,
     error::ServiceError,
+    precondition::Precondition,
     record::RecordService,
     types::{
         CreateZoneRequest, ExportZoneFileResponse, GetZoneResponse, GetZonesFilter,
@@ -13,8 +14,9 @@ use serde_json::json;
 use crate::socket::{
     server::{parse_params, to_response_data},
     types::{
-        DaemonResponse, DiffZoneSnapshotsParams, ImportZoneFileParams, ListZoneSnapshotsParams,
-        RollbackZoneParams, UpdateZoneParams, ZoneNameParams, ZoneSnapshotParams,
+        DaemonResponse, DeleteZoneParams, DiffZoneSnapshotsParams, ImportZoneFileParams,
+        ListZoneSnapshotsParams, PruneZoneHistoryParams, PullZoneParams, RollbackZoneParams,
+        UpdateZoneParams, ZoneNameParams, ZoneSnapshotParams,
     },
 };
 
@@ -53,7 +55,7 @@ pub(super) async fn list_zones(data: &serde_json::Value) -> Result<DaemonRespons
 pub(super) async fn create_zone(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
     let request: CreateZoneRequest = parse_params(data)?;
 
-    let zone = ZoneService::create(&Caller::Global, &request).await?;
+    let zone = ZoneService::create(&Caller::Global, &request, &Precondition::default()).await?;
     Ok(DaemonResponse {
         message: "Zone created successfully".to_string(),
         data: to_response_data(GetZoneResponse::from_zone(&zone))?,
@@ -64,7 +66,13 @@ pub(super) async fn create_zone(data: &serde_json::Value) -> Result<DaemonRespon
 pub(super) async fn update_zone(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
     let params: UpdateZoneParams = parse_params(data)?;
 
-    let zone = ZoneService::patch(&Caller::Global, &params.name, &params.patch).await?;
+    let zone = ZoneService::patch(
+        &Caller::Global,
+        &params.name,
+        &params.patch,
+        &Precondition::expect_serial(params.expect_serial),
+    )
+    .await?;
     Ok(DaemonResponse {
         message: "Zone updated successfully".to_string(),
         data: to_response_data(GetZoneResponse::from_zone(&zone))?,
@@ -76,9 +84,13 @@ pub(super) async fn update_zone(data: &serde_json::Value) -> Result<DaemonRespon
 pub(super) async fn import_zone(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
     let params: ImportZoneFileParams = parse_params(data)?;
 
-    let response =
-        RecordService::import_zone_file(&Caller::Global, &params.zone_name, &params.request)
-            .await?;
+    let response = RecordService::import_zone_file(
+        &Caller::Global,
+        &params.zone_name,
+        &params.request,
+        &Precondition::expect_serial(params.expect_serial),
+    )
+    .await?;
     let message = if !response.errors.is_empty() {
         format!(
             "Import validation failed with {} error(s); nothing applied",
@@ -96,6 +108,43 @@ pub(super) async fn import_zone(data: &serde_json::Value) -> Result<DaemonRespon
     })
 }
 
+/// Handle the `PullZone` command by transferring a zone from another primary
+/// and reconciling it like a zone-file import.
+pub(super) async fn pull_zone(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
+    let params: PullZoneParams = parse_params(data)?;
+
+    let response = bindizr_dns::pull::pull_zone(
+        &Caller::Global,
+        &params.zone_name,
+        &params.request,
+        &Precondition::expect_serial(params.expect_serial),
+    )
+    .await?;
+    let message = if !response.import.errors.is_empty() {
+        format!(
+            "Transferred {} record(s) from {}, but import validation failed with {} error(s); nothing applied",
+            response.transferred,
+            response.server,
+            response.import.errors.len()
+        )
+    } else if response.import.dry_run {
+        format!(
+            "Transferred {} record(s) from {} (serial {}); dry run, no changes applied",
+            response.transferred, response.server, response.serial
+        )
+    } else {
+        format!(
+            "Zone pulled successfully from {} (serial {}, {} record(s))",
+            response.server, response.serial, response.transferred
+        )
+    };
+
+    Ok(DaemonResponse {
+        message,
+        data: to_response_data(response)?,
+    })
+}
+
 /// Handle the `ExportZoneFile` command by rendering a zone as master-file text.
 pub(super) async fn export_zone(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
     let params: ZoneNameParams = parse_params(data)?;
@@ -106,6 +155,27 @@ pub(super) async fn export_zone(data: &serde_json::Value) -> Result<DaemonRespon
     })
 }
 
+/// Handle the `LintZone` command by checking a zone for cross-record
+/// consistency problems.
+pub(super) async fn lint_zone(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
+    let params: ZoneNameParams = parse_params(data)?;
+    let response = ZoneService::lint(&Caller::Global, &params.name).await?;
+
+    let message = if response.findings.is_empty() {
+        format!("Zone '{}' has no lint findings", response.zone)
+    } else {
+        format!(
+            "Zone '{}' has {} error(s) and {} warning(s)",
+            response.zone, response.errors, response.warnings
+        )
+    };
+
+    Ok(DaemonResponse {
+        message,
+        data: to_response_data(response)?,
+    })
+}
+
 /// Handle the `ListZoneSnapshots` command by returning a zone's serial history.
 pub(super) async fn list_zone_snapshots(
     data: &serde_json::Value,
@@ -191,6 +261,7 @@ pub(super) async fn rollback_zone(
         &params.name,
         params.request.serial,
         params.request.dry_run,
+        &Precondition::expect_serial(params.expect_serial),
     )
     .await?;
     let message = if response.dry_run {
@@ -213,6 +284,44 @@ pub(super) async fn rollback_zone(
     })
 }
 
+/// Handle the `PruneZoneHistory` command.
+pub(super) async fn prune_zone_history(
+    data: &serde_json::Value,
+) -> Result<DaemonResponse, ServiceError> {
+    let params: PruneZoneHistoryParams = parse_params(data)?;
+
+    let response = ZoneService::prune_history(
+        &Caller::Global,
+        params.name.as_deref(),
+        params.keep_serials,
+        params.keep_days,
+        params.dry_run,
+    )
+    .await?;
+    let snapshots: u64 = response.zones.iter().map(|z| z.snapshots_removed).sum();
+    let changes: u64 = response.zones.iter().map(|z| z.changes_removed).sum();
+    let message = if response.dry_run {
+        format!(
+            "Dry run: pruning would remove {} snapshot(s) and {} change(s) across {} zone(s); nothing applied",
+            snapshots,
+            changes,
+            response.zones.len()
+        )
+    } else {
+        format!(
+            "Pruned {} snapshot(s) and {} change(s) across {} zone(s)",
+            snapshots,
+            changes,
+            response.zones.len()
+        )
+    };
+
+    Ok(DaemonResponse {
+        message,
+        data: to_response_data(response)?,
+    })
+}
+
 /// Handle the `ZoneStatus` command by probing every configured secondary for
 /// the SOA serial it serves and comparing it with the zone's serial.
 pub(super) async fn zone_status(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
@@ -242,11 +351,42 @@ pub(super) async fn zone_status(data: &serde_json::Value) -> Result<DaemonRespon
     })
 }
 
+/// Handle the `SecondariesStatus` command by returning the sync monitor's last
+/// view of every zone on every secondary.
+pub(super) fn secondaries_status() -> Result<DaemonResponse, ServiceError> {
+    let response = bindizr_dns::monitor::secondaries_status(&Caller::Global)?;
+
+    let lagging = response
+        .secondaries
+        .iter()
+        .filter(|s| s.status != "in_sync")
+        .count();
+    let message = match response.last_round_at {
+        _ if response.interval_secs == 0 => "Secondary sync monitor is disabled".to_string(),
+        None => "Secondary sync monitor has not completed a round yet".to_string(),
+        Some(_) => format!(
+            "{} of {} zone/secondary pairs not in sync",
+            lagging,
+            response.secondaries.len()
+        ),
+    };
+
+    Ok(DaemonResponse {
+        message,
+        data: to_response_data(response)?,
+    })
+}
+
 /// Handle the `DeleteZone` command by deleting a zone by name.
 pub(super) async fn delete_zone(data: &serde_json::Value) -> Result<DaemonResponse, ServiceError> {
-    let params: ZoneNameParams = parse_params(data)?;
+    let params: DeleteZoneParams = parse_params(data)?;
 
-    ZoneService::delete(&Caller::Global, &params.name).await?;
+    ZoneService::delete(
+        &Caller::Global,
+        &params.name,
+        &Precondition::expect_serial(params.expect_serial),
+    )
+    .await?;
     Ok(DaemonResponse {
         message: format!("Zone '{}' deleted successfully", params.name),
         data: json!(null),
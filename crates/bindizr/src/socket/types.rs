@@ -1,8 +1,12 @@
 use bindizr_core::config::BindizrConfig;
 use bindizr_service::types::{
-    CreateBulkRecordsRequest, CreateZoneTokenPolicyRequest, CreateZoneTsigPolicyRequest,
-    ImportZoneFileRequest, RollbackZoneRequest, UpdateRecordPatch, UpdateZonePatch,
+    ApplyChangesetRequest, CreateBulkRecordsRequest, CreateRecordRequest,
+    CreateZoneNotifyTargetRequest, CreateZoneTokenPolicyRequest, CreateZoneTransferAclRequest,
+    CreateZoneTsigPolicyRequest, ImportZoneFileRequest, PullZoneRequest, ReplaceRrsetRequest,
+    RollbackZoneRequest, RotateTokenRequest, SetZoneCatalogPropertiesRequest, UpdateRecordPatch,
+    UpdateZonePatch, UpdateZoneTemplateRequest,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Command kinds accepted by the daemon over the Unix socket.
@@ -13,6 +17,7 @@ pub(crate) enum DaemonCommandKind {
     TokenCreate,
     TokenList,
     TokenDelete,
+    TokenRotate,
     TsigKeyCreate,
     TsigKeyList,
     TsigKeyGet,
@@ -23,6 +28,27 @@ pub(crate) enum DaemonCommandKind {
     ZoneTokenPolicyAdd,
     ZoneTokenPolicyList,
     ZoneTokenPolicyRemove,
+    ZoneTransferAclAdd,
+    ZoneTransferAclList,
+    ZoneTransferAclRemove,
+    ZoneNotifyTargetAdd,
+    ZoneNotifyTargetList,
+    ZoneNotifyTargetRemove,
+    CatalogCreate,
+    CatalogList,
+    CatalogDelete,
+    ZoneCatalogGet,
+    ZoneCatalogSet,
+    TemplateCreate,
+    TemplateList,
+    TemplateGet,
+    TemplateUpdate,
+    TemplateApply,
+    TemplateDelete,
+    WebhookCreate,
+    WebhookList,
+    WebhookDelete,
+    WebhookDeliveries,
     GetZone,
     ListZones,
     CreateZone,
@@ -33,18 +59,28 @@ pub(crate) enum DaemonCommandKind {
     CreateRecord,
     UpdateRecord,
     BulkCreateRecords,
+    ApplyChangeset,
     DeleteRecord,
+    GetRrset,
+    ReplaceRrset,
+    DeleteRrset,
     NotifyZone,
     ImportZoneFile,
+    PullZone,
     ExportZoneFile,
+    LintZone,
     ListZoneSnapshots,
     GetZoneSnapshot,
     DiffZoneSnapshots,
     RollbackZone,
+    PruneZoneHistory,
     ZoneStatus,
+    SecondariesStatus,
     Doctor,
     Shutdown,
     Restart,
+    Reload,
+    ListAuditEntries,
 }
 
 /// A command and its payload sent to the daemon.
@@ -77,6 +113,34 @@ pub(crate) struct RecordIdParams {
     pub(crate) id: i32,
 }
 
+/// Payload for deleting a zone.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DeleteZoneParams {
+    pub(crate) name: String,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
+}
+
+/// Payload for creating a record.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct CreateRecordParams {
+    #[serde(flatten)]
+    pub(crate) request: CreateRecordRequest,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
+}
+
+/// Payload for deleting a record.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DeleteRecordParams {
+    pub(crate) id: i32,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
+}
+
 /// Payload addressing a TSIG key by name.
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct TsigKeyNameParams {
@@ -89,6 +153,14 @@ pub(crate) struct TokenNameParams {
     pub(crate) name: String,
 }
 
+/// Payload for rotating an API token's secret.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct RotateTokenParams {
+    pub(crate) name: String,
+    #[serde(flatten)]
+    pub(crate) request: RotateTokenRequest,
+}
+
 /// Payload for creating an API token.
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct CreateTokenParams {
@@ -97,6 +169,8 @@ pub(crate) struct CreateTokenParams {
     pub(crate) expires_in_days: Option<i64>,
     #[serde(default)]
     pub(crate) global: bool,
+    #[serde(default)]
+    pub(crate) role: Option<String>,
 }
 
 /// Payload addressing a zone's policies.
@@ -128,12 +202,91 @@ pub(crate) struct AddZoneTokenPolicyParams {
     pub(crate) request: CreateZoneTokenPolicyRequest,
 }
 
+/// Payload for adding an entry to a zone's transfer ACL.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct AddZoneTransferAclParams {
+    pub(crate) zone_name: String,
+    #[serde(flatten)]
+    pub(crate) request: CreateZoneTransferAclRequest,
+}
+
+/// Payload for adding a NOTIFY target to a zone.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct AddZoneNotifyTargetParams {
+    pub(crate) zone_name: String,
+    #[serde(flatten)]
+    pub(crate) request: CreateZoneNotifyTargetRequest,
+}
+
+/// Payload addressing a named catalog.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct CatalogNameParams {
+    pub(crate) name: String,
+}
+
+/// Payload for replacing a zone's catalog membership and properties.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct SetZoneCatalogParams {
+    pub(crate) zone_name: String,
+    #[serde(flatten)]
+    pub(crate) request: SetZoneCatalogPropertiesRequest,
+}
+
+/// Payload addressing a zone template by name.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct TemplateNameParams {
+    pub(crate) name: String,
+}
+
+/// Payload for replacing a zone template's records.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct UpdateTemplateParams {
+    pub(crate) name: String,
+    #[serde(flatten)]
+    pub(crate) request: UpdateZoneTemplateRequest,
+}
+
+/// Payload for applying a zone template to its linked zones.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ApplyTemplateParams {
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) dry_run: bool,
+}
+
+/// Payload addressing a webhook by name.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct WebhookNameParams {
+    pub(crate) name: String,
+}
+
+/// Payload for listing a webhook's deliveries.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct WebhookDeliveriesParams {
+    pub(crate) name: String,
+    pub(crate) limit: Option<u32>,
+}
+
 /// Payload for importing zone-file text into a zone.
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct ImportZoneFileParams {
     pub(crate) zone_name: String,
     #[serde(flatten)]
     pub(crate) request: ImportZoneFileRequest,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
+}
+
+/// Payload for pulling a zone from another primary by AXFR.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct PullZoneParams {
+    pub(crate) zone_name: String,
+    #[serde(flatten)]
+    pub(crate) request: PullZoneRequest,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
 }
 
 /// Payload for inserting records into a zone in one transaction.
@@ -142,6 +295,50 @@ pub(crate) struct BulkCreateRecordsParams {
     pub(crate) zone_name: String,
     #[serde(flatten)]
     pub(crate) request: CreateBulkRecordsRequest,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
+}
+
+/// Payload for applying an ordered changeset of record operations to a zone.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ApplyChangesetParams {
+    pub(crate) zone_name: String,
+    #[serde(flatten)]
+    pub(crate) request: ApplyChangesetRequest,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
+}
+
+/// Payload addressing an RRset by owner name and type within a zone.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct RrsetParams {
+    pub(crate) zone_name: String,
+    pub(crate) name: String,
+    pub(crate) record_type: String,
+}
+
+/// Payload for replacing an RRset.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ReplaceRrsetParams {
+    #[serde(flatten)]
+    pub(crate) rrset: RrsetParams,
+    #[serde(flatten)]
+    pub(crate) request: ReplaceRrsetRequest,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
+}
+
+/// Payload for deleting an RRset.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DeleteRrsetParams {
+    #[serde(flatten)]
+    pub(crate) rrset: RrsetParams,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
 }
 
 /// Payload for patching a zone.
@@ -150,6 +347,9 @@ pub(crate) struct UpdateZoneParams {
     pub(crate) name: String,
     #[serde(flatten)]
     pub(crate) patch: UpdateZonePatch,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
 }
 
 /// Payload for patching a record.
@@ -158,6 +358,9 @@ pub(crate) struct UpdateRecordParams {
     pub(crate) id: i32,
     #[serde(flatten)]
     pub(crate) patch: UpdateRecordPatch,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
 }
 
 /// Payload for rolling a zone back to a snapshot serial.
@@ -166,6 +369,19 @@ pub(crate) struct RollbackZoneParams {
     pub(crate) name: String,
     #[serde(flatten)]
     pub(crate) request: RollbackZoneRequest,
+    /// Fail unless the zone is still at this serial.
+    #[serde(default)]
+    pub(crate) expect_serial: Option<u32>,
+}
+
+/// Payload for pruning zone history; a missing `name` prunes every zone, and
+/// missing limits fall back to the configured `[history]` ones.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct PruneZoneHistoryParams {
+    pub(crate) name: Option<String>,
+    pub(crate) keep_serials: Option<u32>,
+    pub(crate) keep_days: Option<u32>,
+    pub(crate) dry_run: bool,
 }
 
 /// Payload for listing a zone's snapshots.
@@ -180,7 +396,7 @@ pub(crate) struct ListZoneSnapshotsParams {
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct ZoneSnapshotParams {
     pub(crate) name: String,
-    pub(crate) serial: i32,
+    pub(crate) serial: u32,
 }
 
 /// Payload for diffing two of a zone's serials; a missing `to_serial` compares
@@ -188,8 +404,8 @@ pub(crate) struct ZoneSnapshotParams {
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct DiffZoneSnapshotsParams {
     pub(crate) name: String,
-    pub(crate) from_serial: i32,
-    pub(crate) to_serial: Option<i32>,
+    pub(crate) from_serial: u32,
+    pub(crate) to_serial: Option<u32>,
 }
 
 /// Daemon status details returned by the `Status` command.
@@ -203,6 +419,25 @@ pub(crate) struct DaemonStatusResponse {
     pub(crate) config: BindizrConfig,
 }
 
+/// Payload for reading the audit log; every filter is optional.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ListAuditEntriesParams {
+    pub(crate) zone_name: Option<String>,
+    pub(crate) actor: Option<String>,
+    pub(crate) transport: Option<String>,
+    pub(crate) since: Option<DateTime<Utc>>,
+    pub(crate) until: Option<DateTime<Utc>>,
+    pub(crate) limit: Option<u32>,
+}
+
+/// Outcome of the `Reload` command.
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct DaemonReloadResponse {
+    /// Settings changed in the file that keep their running values until the
+    /// daemon restarts.
+    pub(crate) restart_required: Vec<String>,
+}
+
 /// Daemon-side installation checks returned by the `Doctor` command.
 #[derive(Serialize, Deserialize, Debug)]
 pub(crate) struct DaemonDoctorResponse {